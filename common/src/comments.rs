@@ -0,0 +1,86 @@
+//! Comments attached to byte positions.
+
+use pos::{BytePos, Span};
+use rustc_data_structures::sync::Lock;
+use std::collections::HashMap;
+
+/// Comments of a file, recorded by the lexer.
+///
+/// A comment is *leading* if it's followed by a token, and it's stored at the
+/// start of the token. A comment is *trailing* if it's on the same line as
+/// the previous token, and it's stored at the end of the token.
+///
+/// ```js
+/// /* leading of `a` */ a; // trailing of `;`
+/// ```
+pub struct Comments {
+    leading: Lock<HashMap<BytePos, Vec<Comment>>>,
+    trailing: Lock<HashMap<BytePos, Vec<Comment>>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comment {
+    pub kind: CommentKind,
+    pub span: Span,
+    /// Content without `//`, or `/*` and `*/`.
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentKind {
+    Line,
+    Block,
+}
+
+impl Default for Comments {
+    fn default() -> Self {
+        Comments {
+            leading: Lock::new(Default::default()),
+            trailing: Lock::new(Default::default()),
+        }
+    }
+}
+
+impl Comments {
+    pub fn add_leading(&self, pos: BytePos, comment: Comment) {
+        self.leading
+            .lock()
+            .entry(pos)
+            .or_insert_with(Vec::new)
+            .push(comment)
+    }
+
+    pub fn add_trailing(&self, pos: BytePos, comment: Comment) {
+        self.trailing
+            .lock()
+            .entry(pos)
+            .or_insert_with(Vec::new)
+            .push(comment)
+    }
+
+    pub fn has_leading(&self, pos: BytePos) -> bool {
+        self.leading.lock().contains_key(&pos)
+    }
+
+    pub fn has_trailing(&self, pos: BytePos) -> bool {
+        self.trailing.lock().contains_key(&pos)
+    }
+
+    pub fn leading_comments(&self, pos: BytePos) -> Vec<Comment> {
+        self.leading.lock().get(&pos).cloned().unwrap_or_default()
+    }
+
+    pub fn trailing_comments(&self, pos: BytePos) -> Vec<Comment> {
+        self.trailing.lock().get(&pos).cloned().unwrap_or_default()
+    }
+
+    /// Removes leading comments at `pos`, so that they are not printed twice.
+    pub fn take_leading_comments(&self, pos: BytePos) -> Vec<Comment> {
+        self.leading.lock().remove(&pos).unwrap_or_default()
+    }
+
+    /// Removes trailing comments at `pos`, so that they are not printed twice.
+    pub fn take_trailing_comments(&self, pos: BytePos) -> Vec<Comment> {
+        self.trailing.lock().remove(&pos).unwrap_or_default()
+    }
+}
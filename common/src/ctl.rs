@@ -0,0 +1,132 @@
+//! Visiting which can be stopped early.
+
+use either::Either;
+use string_cache::{Atom, StaticAtomSet};
+
+/// Returned by [VisitCtl::visit_ctl].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VisitControl {
+    /// Visit children of the node.
+    Continue,
+    /// Don't visit children of the node, but continue visiting siblings.
+    SkipChildren,
+    /// Stop the whole traversal.
+    Stop,
+}
+
+/// Visitor which controls traversal.
+///
+/// `visit_ctl` is called before children of a node are visited. By default,
+/// this returns [VisitControl::Continue] for all nodes. For example, a
+/// visitor checking if a function contains `await` can stop at the first
+/// `await` and skip nested functions.
+///
+/// This trait requires `#![feature(specialization)]`.
+pub trait VisitCtl<T> {
+    fn visit_ctl(&mut self, node: &T) -> VisitControl;
+}
+
+impl<T, F> VisitCtl<T> for F {
+    default fn visit_ctl(&mut self, _: &T) -> VisitControl {
+        VisitControl::Continue
+    }
+}
+
+/// Trait implemented for types which know how to visit itself with a
+/// [VisitCtl].
+///
+///
+///#Derive
+///
+/// This trait can be derived with `#[derive(Fold)]`.
+///
+/// Note that derive ignores all fields with primitive type
+/// because it would encourage mistakes. Use new type instead.
+///
+/// `#[fold(ignore)]` can be used to ignore a field.
+pub trait VisitCtlWith<F>: Sized {
+    /// Visits children, returning [VisitControl::Stop] if the traversal is
+    /// stopped and [VisitControl::Continue] otherwise.
+    fn visit_children_ctl(&self, f: &mut F) -> VisitControl;
+
+    /// Calls `f.visit_ctl(self)`, and visits children if it returns
+    /// [VisitControl::Continue].
+    ///
+    /// Returns [VisitControl::Stop] if the traversal is stopped and
+    /// [VisitControl::Continue] otherwise.
+    fn visit_ctl_with(&self, f: &mut F) -> VisitControl {
+        match VisitCtl::<Self>::visit_ctl(f, self) {
+            VisitControl::Continue => self.visit_children_ctl(f),
+            VisitControl::SkipChildren => VisitControl::Continue,
+            VisitControl::Stop => VisitControl::Stop,
+        }
+    }
+}
+
+impl<F> VisitCtlWith<F> for ! {
+    fn visit_children_ctl(&self, _: &mut F) -> VisitControl {
+        VisitControl::Continue
+    }
+}
+
+impl<T, F> VisitCtlWith<F> for Box<T>
+where
+    T: VisitCtlWith<F>,
+{
+    fn visit_children_ctl(&self, f: &mut F) -> VisitControl {
+        (**self).visit_ctl_with(f)
+    }
+}
+
+impl<T, F> VisitCtlWith<F> for Vec<T>
+where
+    T: VisitCtlWith<F>,
+{
+    fn visit_children_ctl(&self, f: &mut F) -> VisitControl {
+        for node in self {
+            if node.visit_ctl_with(f) == VisitControl::Stop {
+                return VisitControl::Stop;
+            }
+        }
+        VisitControl::Continue
+    }
+}
+
+impl<T, F> VisitCtlWith<F> for Option<T>
+where
+    T: VisitCtlWith<F>,
+{
+    fn visit_children_ctl(&self, f: &mut F) -> VisitControl {
+        match *self {
+            Some(ref node) => node.visit_ctl_with(f),
+            None => VisitControl::Continue,
+        }
+    }
+}
+
+impl<F> VisitCtlWith<F> for String {
+    /// No op.
+    fn visit_children_ctl(&self, _: &mut F) -> VisitControl {
+        VisitControl::Continue
+    }
+}
+
+impl<F, S: StaticAtomSet> VisitCtlWith<F> for Atom<S> {
+    /// No op.
+    fn visit_children_ctl(&self, _: &mut F) -> VisitControl {
+        VisitControl::Continue
+    }
+}
+
+impl<A, B, F> VisitCtlWith<F> for Either<A, B>
+where
+    A: VisitCtlWith<F>,
+    B: VisitCtlWith<F>,
+{
+    fn visit_children_ctl(&self, f: &mut F) -> VisitControl {
+        match *self {
+            Either::Left(ref a) => a.visit_ctl_with(f),
+            Either::Right(ref b) => b.visit_ctl_with(f),
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use super::Span;
+use std::hash::{Hash, Hasher};
+use string_cache::{Atom, StaticAtomSet};
+
+/// Equality which ignores spans, including their syntax contexts.
+///
+/// This is useful to compare a transformed tree with an expected one, which
+/// doesn't have positions.
+///
+/// # Derive
+/// This trait can be derived with `#[derive(EqIgnoreSpan)]`, which compares
+/// all fields.
+pub trait EqIgnoreSpan {
+    fn eq_ignore_span(&self, other: &Self) -> bool;
+}
+
+/// Hashing which is consistent with [EqIgnoreSpan].
+///
+/// # Derive
+/// This trait can be derived with `#[derive(HashIgnoreSpan)]`, which hashes
+/// all fields.
+pub trait HashIgnoreSpan {
+    fn hash_ignore_span<H: Hasher>(&self, state: &mut H);
+}
+
+impl EqIgnoreSpan for Span {
+    #[inline(always)]
+    fn eq_ignore_span(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl HashIgnoreSpan for Span {
+    #[inline(always)]
+    fn hash_ignore_span<H: Hasher>(&self, _: &mut H) {}
+}
+
+/// Types which don't contain spans.
+macro_rules! impl_by_eq {
+    ($($T:ty),*) => {
+        $(
+            impl EqIgnoreSpan for $T {
+                #[inline(always)]
+                fn eq_ignore_span(&self, other: &Self) -> bool {
+                    *self == *other
+                }
+            }
+
+            impl HashIgnoreSpan for $T {
+                #[inline(always)]
+                fn hash_ignore_span<H: Hasher>(&self, state: &mut H) {
+                    self.hash(state)
+                }
+            }
+        )*
+    };
+}
+
+impl_by_eq!(bool, char, u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, str, String);
+
+impl<S: StaticAtomSet> EqIgnoreSpan for Atom<S> {
+    #[inline(always)]
+    fn eq_ignore_span(&self, other: &Self) -> bool {
+        *self == *other
+    }
+}
+
+impl<S: StaticAtomSet> HashIgnoreSpan for Atom<S> {
+    #[inline(always)]
+    fn hash_ignore_span<H: Hasher>(&self, state: &mut H) {
+        self.hash(state)
+    }
+}
+
+/// Numbers are compared by their bits, so that `NaN` is equal to itself.
+impl EqIgnoreSpan for f64 {
+    #[inline(always)]
+    fn eq_ignore_span(&self, other: &Self) -> bool {
+        self.to_bits() == other.to_bits()
+    }
+}
+
+impl HashIgnoreSpan for f64 {
+    #[inline(always)]
+    fn hash_ignore_span<H: Hasher>(&self, state: &mut H) {
+        self.to_bits().hash(state)
+    }
+}
+
+impl<T: ?Sized + EqIgnoreSpan> EqIgnoreSpan for Box<T> {
+    #[inline(always)]
+    fn eq_ignore_span(&self, other: &Self) -> bool {
+        (**self).eq_ignore_span(&**other)
+    }
+}
+
+impl<T: ?Sized + HashIgnoreSpan> HashIgnoreSpan for Box<T> {
+    #[inline(always)]
+    fn hash_ignore_span<H: Hasher>(&self, state: &mut H) {
+        (**self).hash_ignore_span(state)
+    }
+}
+
+impl<T: EqIgnoreSpan> EqIgnoreSpan for Option<T> {
+    fn eq_ignore_span(&self, other: &Self) -> bool {
+        match (self, other) {
+            (&Some(ref l), &Some(ref r)) => l.eq_ignore_span(r),
+            (&None, &None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl<T: HashIgnoreSpan> HashIgnoreSpan for Option<T> {
+    fn hash_ignore_span<H: Hasher>(&self, state: &mut H) {
+        match *self {
+            Some(ref value) => {
+                1u8.hash(state);
+                value.hash_ignore_span(state)
+            }
+            None => 0u8.hash(state),
+        }
+    }
+}
+
+impl<T: EqIgnoreSpan> EqIgnoreSpan for [T] {
+    fn eq_ignore_span(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().zip(other).all(|(l, r)| l.eq_ignore_span(r))
+    }
+}
+
+impl<T: HashIgnoreSpan> HashIgnoreSpan for [T] {
+    fn hash_ignore_span<H: Hasher>(&self, state: &mut H) {
+        self.len().hash(state);
+        for item in self {
+            item.hash_ignore_span(state)
+        }
+    }
+}
+
+impl<T: EqIgnoreSpan> EqIgnoreSpan for Vec<T> {
+    #[inline(always)]
+    fn eq_ignore_span(&self, other: &Self) -> bool {
+        (**self).eq_ignore_span(&**other)
+    }
+}
+
+impl<T: HashIgnoreSpan> HashIgnoreSpan for Vec<T> {
+    #[inline(always)]
+    fn hash_ignore_span<H: Hasher>(&self, state: &mut H) {
+        (**self).hash_ignore_span(state)
+    }
+}
@@ -43,6 +43,20 @@ impl Diagnostic {
         Self::new(Level::Fatal, msg)
     }
 
+    pub fn level(&self) -> Level {
+        self.inner.level
+    }
+
+    /// The main message, without labels and notes.
+    pub fn message(&self) -> String {
+        self.inner.message()
+    }
+
+    /// Primary spans and labels.
+    pub fn spans(&self) -> &MultiSpan {
+        &self.inner.span
+    }
+
     #[inline(always)]
     pub fn emit_to(self, handler: &Handler) {
         handler.emit(self)
@@ -1,13 +1,18 @@
 use super::{Diagnostic, DiagnosticBuilder};
-use rustc_data_structures::sync::{Lrc, Send, Sync};
+use rustc_data_structures::sync::{Lock, Lrc, Send, Sync};
+use std::{
+    io::{self, Write},
+    sync::{Arc, Mutex},
+};
 pub use rustc_errors::{
     emitter::{Emitter, EmitterWriter},
     HandlerFlags,
 };
 use rustc_errors::{
     ColorConfig, DiagnosticBuilder as RustcDiagnosticBuilder, Handler as RustcHandler, Level,
-    SourceMapper,
+    SourceMapper, SourceMapperDyn,
 };
+use syntax::{json::JsonEmitter, source_map::SourceMap};
 
 /// A handler deals with errors.
 pub struct Handler {
@@ -36,6 +41,26 @@ impl Handler {
         RustcHandler::with_emitter_and_flags(e, flags).into()
     }
 
+    /// Creates a handler which stores diagnostics to the returned buffer
+    /// instead of printing them.
+    ///
+    /// This is useful to collect all errors of a file, e.g. for an editor.
+    pub fn with_buffer(flags: HandlerFlags) -> (Self, DiagnosticBuffer) {
+        let buffer = DiagnosticBuffer::default();
+        let handler = Handler::with_emitter(box BufferEmitter(buffer.clone()), flags);
+        (handler, buffer)
+    }
+
+    /// Creates a handler which writes each diagnostic to `dst` as a line of
+    /// json, with spans resolved by `cm`.
+    pub fn with_json_emitter(
+        cm: Lrc<SourceMap>,
+        dst: Box<Write + Send>,
+        flags: HandlerFlags,
+    ) -> Self {
+        Handler::with_emitter(box JsonEmitter::new(dst, None, cm, false), flags)
+    }
+
     pub(crate) fn emit(&self, d: Diagnostic) {
         RustcDiagnosticBuilder::new_diagnostic(&self.inner, *d.inner).emit()
     }
@@ -78,3 +103,64 @@ impl From<RustcHandler> for Handler {
         Handler { inner }
     }
 }
+
+/// Diagnostics emitted to a handler created by [Handler::with_buffer].
+#[derive(Clone, Default)]
+pub struct DiagnosticBuffer {
+    diagnostics: Lrc<Lock<Vec<Diagnostic>>>,
+}
+
+impl DiagnosticBuffer {
+    pub fn len(&self) -> usize {
+        self.diagnostics.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.diagnostics.lock().is_empty()
+    }
+
+    /// Removes all diagnostics from the buffer.
+    pub fn take(&self) -> Vec<Diagnostic> {
+        self.diagnostics.lock().drain(..).collect()
+    }
+
+    /// Removes all diagnostics from the buffer and renders them as the tty
+    /// emitter does, without colors.
+    pub fn render(&self, cm: Lrc<SourceMapperDyn>) -> String {
+        let buf = RenderBuffer::default();
+        let handler = Handler::with_emitter(
+            box EmitterWriter::new(box buf.clone(), Some(cm), false, false),
+            HandlerFlags {
+                can_emit_warnings: true,
+                ..Default::default()
+            },
+        );
+        for d in self.take() {
+            d.emit_to(&handler);
+        }
+
+        let buf = buf.0.lock().unwrap();
+        String::from_utf8_lossy(&buf).into_owned()
+    }
+}
+
+#[derive(Clone, Default)]
+struct RenderBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for RenderBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+struct BufferEmitter(DiagnosticBuffer);
+
+impl Emitter for BufferEmitter {
+    fn emit(&mut self, db: &RustcDiagnosticBuilder) {
+        self.0.diagnostics.lock().push((**db).clone().into())
+    }
+}
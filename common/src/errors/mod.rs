@@ -11,6 +11,8 @@ pub use rustc_errors::{
     Level::{self, *},
     SourceMapper, SourceMapperDyn,
 };
+#[doc(inline)]
+pub use syntax::json::JsonEmitter;
 
 mod diagnostic;
 mod diagnostic_builder;
@@ -1,8 +1,9 @@
 use super::*;
 use crate::{FileLoader, FilePathMapping, SourceMap};
 use std::{
-    io,
+    io::{self, Write},
     path::{Path, PathBuf},
+    sync::{Arc, Mutex},
 };
 use sync::Lrc;
 use BytePos;
@@ -73,3 +74,78 @@ fn test() {
         .emit();
     })
 }
+
+#[derive(Clone, Default)]
+struct Buf(Arc<Mutex<Vec<u8>>>);
+impl Write for Buf {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn json() {
+    let cm = Lrc::new(SourceMap::with_file_loader(
+        box MyFileLoader,
+        FilePathMapping::empty(),
+    ));
+    let file_map = cm
+        .load_file(Path::new("tmp.js").into())
+        .expect("failed to load tmp.js");
+    let buf = Buf::default();
+    let flags = HandlerFlags {
+        can_emit_warnings: true,
+        ..Default::default()
+    };
+    let handler = Handler::with_json_emitter(cm, box buf.clone(), flags);
+
+    ::syntax_pos::GLOBALS.set(&::syntax_pos::Globals::new(), || {
+        let lo = file_map.start_pos + BytePos(1);
+        handler
+            .error("unexpected token")
+            .span(Span::new(lo, lo + BytePos(8), Default::default()))
+            .code(DiagnosticId::Error("E1".into()))
+            .note("a note")
+            .emit();
+        handler.warn("second").emit();
+    });
+
+    let out = String::from_utf8(buf.0.lock().unwrap().clone()).unwrap();
+    let lines: Vec<_> = out.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].contains(r#""message":"unexpected token""#));
+    assert!(lines[0].contains(r#""level":"error""#));
+    assert!(lines[0].contains(r#""code":"E1""#));
+    assert!(lines[0].contains(r#""line_start":2"#));
+    assert!(lines[0].contains(r#""message":"a note""#));
+    assert!(lines[1].contains(r#""level":"warning""#));
+}
+
+#[test]
+fn render_buffer() {
+    let cm = Lrc::new(SourceMap::with_file_loader(
+        box MyFileLoader,
+        FilePathMapping::empty(),
+    ));
+    let file_map = cm
+        .load_file(Path::new("tmp.js").into())
+        .expect("failed to load tmp.js");
+    let (handler, buffer) = Handler::with_buffer(Default::default());
+
+    let out = ::syntax_pos::GLOBALS.set(&::syntax_pos::Globals::new(), || {
+        let lo = file_map.start_pos + BytePos(1);
+        handler
+            .error("unexpected token")
+            .span(Span::new(lo, lo + BytePos(8), Default::default()))
+            .emit();
+        buffer.render(cm.clone())
+    });
+
+    assert!(out.contains("error: unexpected token"), "{}", out);
+    assert!(out.contains(":2:1"), "{}", out);
+    assert!(buffer.is_empty());
+}
@@ -1,9 +1,18 @@
 use either::Either;
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::{BuildHasher, Hash},
+    rc::Rc,
+    sync::Arc,
+};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 use string_cache::{Atom, StaticAtomSet};
 
 /// Folder based on a type system.
 ///
-/// This trait requires `#![feature(specialization)]`.
+/// This trait requires `#![feature(specialization)]`. With the `stable-fold`
+/// feature, `FoldNode` can be used instead.
 pub trait Fold<T> {
     /// By default, this folds fields of `node`
     ///  and reconstruct `node` with folded fields
@@ -22,6 +31,27 @@ pub trait Fold<T> {
     }
 }
 
+/// Folder which can abort folding with an error.
+///
+/// This trait requires `#![feature(specialization)]`.
+pub trait TryFold<T, E> {
+    /// By default, this folds fields of `node` and reconstruct `node` with
+    /// folded fields, returning the first error.
+    fn try_fold(&mut self, node: T) -> Result<T, E>;
+
+    /// Creates a folder which applies `folder` after `self`.
+    fn then<F>(self, folder: F) -> AndThen<Self, F>
+    where
+        Self: Sized,
+        F: TryFold<T, E>,
+    {
+        AndThen {
+            first: self,
+            second: folder,
+        }
+    }
+}
+
 /// Visitor based on a type system.
 ///
 /// This trait requires `#![feature(specialization)]`.
@@ -41,6 +71,28 @@ pub trait Visit<T> {
     }
 }
 
+/// Visitor which mutates nodes in place.
+///
+/// Unlike [Fold], this does not move nodes, so passes which modify only a
+/// few nodes don't have to reconstruct the whole tree.
+///
+/// This trait requires `#![feature(specialization)]`.
+pub trait VisitMut<T> {
+    fn visit_mut(&mut self, node: &mut T);
+
+    /// Creates a visitor which applies `visitor` after `self`.
+    fn then<F>(self, visitor: F) -> AndThen<Self, F>
+    where
+        Self: Sized,
+        F: VisitMut<T>,
+    {
+        AndThen {
+            first: self,
+            second: visitor,
+        }
+    }
+}
+
 impl<T, F: ?Sized> Fold<T> for Box<F>
 where
     T: FoldWith<Self>,
@@ -51,6 +103,16 @@ where
     }
 }
 
+impl<T, E, F: ?Sized> TryFold<T, E> for Box<F>
+where
+    T: TryFoldWith<Self, E>,
+    F: TryFold<T, E>,
+{
+    fn try_fold(&mut self, node: T) -> Result<T, E> {
+        (**self).try_fold(node)
+    }
+}
+
 impl<T, F: ?Sized> Visit<T> for Box<F>
 where
     T: VisitWith<Self>,
@@ -61,6 +123,16 @@ where
     }
 }
 
+impl<T, F: ?Sized> VisitMut<T> for Box<F>
+where
+    T: VisitMutWith<Self>,
+    F: VisitMut<T>,
+{
+    fn visit_mut(&mut self, node: &mut T) {
+        (**self).visit_mut(node)
+    }
+}
+
 impl<'a, T, F: ?Sized> Fold<T> for &'a mut F
 where
     T: FoldWith<Self>,
@@ -71,6 +143,16 @@ where
     }
 }
 
+impl<'a, T, E, F: ?Sized> TryFold<T, E> for &'a mut F
+where
+    T: TryFoldWith<Self, E>,
+    F: TryFold<T, E>,
+{
+    fn try_fold(&mut self, node: T) -> Result<T, E> {
+        (**self).try_fold(node)
+    }
+}
+
 impl<'a, T, F: ?Sized> Visit<T> for &'a mut F
 where
     T: VisitWith<Self>,
@@ -81,6 +163,16 @@ where
     }
 }
 
+impl<'a, T, F: ?Sized> VisitMut<T> for &'a mut F
+where
+    T: VisitMutWith<Self>,
+    F: VisitMut<T>,
+{
+    fn visit_mut(&mut self, node: &mut T) {
+        (**self).visit_mut(node)
+    }
+}
+
 impl<T, F> Fold<T> for F
 where
     T: FoldWith<F>,
@@ -90,6 +182,15 @@ where
     }
 }
 
+impl<T, E, F> TryFold<T, E> for F
+where
+    T: TryFoldWith<F, E>,
+{
+    default fn try_fold(&mut self, t: T) -> Result<T, E> {
+        t.try_fold_children(self)
+    }
+}
+
 impl<T, F> Visit<T> for F
 where
     T: VisitWith<F>,
@@ -99,6 +200,15 @@ where
     }
 }
 
+impl<T, F> VisitMut<T> for F
+where
+    T: VisitMutWith<F>,
+{
+    default fn visit_mut(&mut self, t: &mut T) {
+        t.visit_mut_children(self)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct AndThen<F1, F2> {
     first: F1,
@@ -117,6 +227,18 @@ where
     }
 }
 
+impl<T, E, F1, F2> TryFold<T, E> for AndThen<F1, F2>
+where
+    T: TryFoldWith<Self, E>,
+    F1: TryFold<T, E>,
+    F2: TryFold<T, E>,
+{
+    fn try_fold(&mut self, node: T) -> Result<T, E> {
+        let node = self.first.try_fold(node)?;
+        self.second.try_fold(node)
+    }
+}
+
 impl<T, F1, F2> Visit<T> for AndThen<F1, F2>
 where
     T: VisitWith<Self>,
@@ -129,6 +251,160 @@ where
     }
 }
 
+impl<T, F1, F2> VisitMut<T> for AndThen<F1, F2>
+where
+    T: VisitMutWith<Self>,
+    F1: VisitMut<T>,
+    F2: VisitMut<T>,
+{
+    fn visit_mut(&mut self, node: &mut T) {
+        self.first.visit_mut(node);
+        self.second.visit_mut(node);
+    }
+}
+
+/// Folder which does not change nodes. Created by [noop].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Noop;
+
+/// Creates a folder which returns nodes as-is, to be used as a placeholder.
+pub fn noop() -> Noop {
+    Noop
+}
+
+impl<T> Fold<T> for Noop
+where
+    T: FoldWith<Self>,
+{
+    fn fold(&mut self, node: T) -> T {
+        node
+    }
+}
+
+/// Folder which applies a pass only if it's enabled. Created by [when].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Optional<F> {
+    enabled: bool,
+    pass: F,
+}
+
+/// Creates a folder which applies `pass` only if `enabled` is true.
+///
+/// This allows toggling a pass without changing the type of the pipeline.
+pub fn when<F>(enabled: bool, pass: F) -> Optional<F> {
+    Optional { enabled, pass }
+}
+
+impl<T, F> Fold<T> for Optional<F>
+where
+    T: FoldWith<Self>,
+    F: Fold<T>,
+{
+    fn fold(&mut self, node: T) -> T {
+        if self.enabled {
+            self.pass.fold(node)
+        } else {
+            node
+        }
+    }
+}
+
+/// Hooks called by folding of nodes with `#[fold(hooks)]`, which includes
+/// all `#[ast_node]`s.
+///
+/// `enter` is called before fields of a node are folded, and `exit` is called
+/// with the folded node. Both do nothing by default, so a folder can
+/// implement only hooks it needs.
+///
+/// Note that hooks are called by `FoldWith::fold_children`, so they are not
+/// called for nodes whose `Fold::fold` is overridden and does not call
+/// `fold_children`.
+///
+/// This trait requires `#![feature(specialization)]`.
+pub trait FoldHooks<T> {
+    fn enter(&mut self, node: &T);
+
+    fn exit(&mut self, node: T) -> T;
+}
+
+impl<T, F> FoldHooks<T> for F {
+    default fn enter(&mut self, _: &T) {}
+
+    default fn exit(&mut self, node: T) -> T {
+        node
+    }
+}
+
+/// Pass which knows if it changed nodes, used by [Repeat].
+pub trait Repeated {
+    /// Returns true if nodes are changed since the last call to `reset`.
+    fn changed(&self) -> bool;
+
+    /// Resets the state used by `changed`.
+    fn reset(&mut self);
+}
+
+impl<F1, F2> Repeated for AndThen<F1, F2>
+where
+    F1: Repeated,
+    F2: Repeated,
+{
+    fn changed(&self) -> bool {
+        self.first.changed() || self.second.changed()
+    }
+
+    fn reset(&mut self) {
+        self.first.reset();
+        self.second.reset();
+    }
+}
+
+/// Folder which applies a pass until it does not change nodes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Repeat<F> {
+    pass: F,
+    max_iterations: usize,
+}
+
+impl<F> Repeat<F>
+where
+    F: Repeated,
+{
+    pub fn new(pass: F) -> Self {
+        Repeat {
+            pass,
+            max_iterations: usize::max_value(),
+        }
+    }
+
+    /// Stops after applying the pass `max_iterations` times, even if it
+    /// changes nodes.
+    pub fn max_iterations(self, max_iterations: usize) -> Self {
+        Repeat {
+            max_iterations,
+            ..self
+        }
+    }
+}
+
+impl<T, F> Fold<T> for Repeat<F>
+where
+    T: FoldWith<Self>,
+    F: Fold<T> + Repeated,
+{
+    fn fold(&mut self, mut node: T) -> T {
+        for _ in 0..self.max_iterations {
+            self.pass.reset();
+            node = self.pass.fold(node);
+            if !self.pass.changed() {
+                break;
+            }
+        }
+
+        node
+    }
+}
+
 /// Trait implemented for types which know how to fold itself.
 ///
 ///
@@ -152,6 +428,30 @@ pub trait FoldWith<F>: Sized {
     }
 }
 
+/// Trait implemented for types which know how to fold itself with a folder
+/// which can fail.
+///
+///
+///#Derive
+///
+/// This trait can be derived with `#[derive(Fold)]`.
+///
+/// Note that derive ignores all fields with primitive type
+/// because it would encourage mistakes. Use new type instead.
+///
+/// `#[fold(ignore)]` can be used to ignore a field.
+pub trait TryFoldWith<F, E>: Sized {
+    /// This is used by default implementation of `TryFold<Self, E>::try_fold`.
+    fn try_fold_children(self, f: &mut F) -> Result<Self, E>;
+
+    /// Call `f.try_fold(self)`.
+    ///
+    /// This bypasses a type inference bug which is caused by specialization.
+    fn try_fold_with(self, f: &mut F) -> Result<Self, E> {
+        f.try_fold(self)
+    }
+}
+
 /// Trait implemented for types which know how to visit itself.
 ///
 ///
@@ -175,16 +475,49 @@ pub trait VisitWith<F>: Sized {
     }
 }
 
+/// Trait implemented for types which know how to visit itself mutably.
+///
+///
+///#Derive
+///
+/// This trait can be derived with `#[derive(Fold)]`.
+///
+/// Note that derive ignores all fields with primitive type
+/// because it would encourage mistakes. Use new type instead.
+///
+/// `#[fold(ignore)]` can be used to ignore a field.
+pub trait VisitMutWith<F>: Sized {
+    /// This is used by default implementation of `VisitMut<Self>::visit_mut`.
+    fn visit_mut_children(&mut self, f: &mut F);
+
+    /// Call `f.visit_mut(self)`.
+    ///
+    /// This bypasses a type inference bug which is caused by specialization.
+    fn visit_mut_with(&mut self, f: &mut F) {
+        f.visit_mut(self)
+    }
+}
+
 impl<F> FoldWith<F> for ! {
     fn fold_children(self, _: &mut F) -> Self {
         self
     }
 }
 
+impl<F, E> TryFoldWith<F, E> for ! {
+    fn try_fold_children(self, _: &mut F) -> Result<Self, E> {
+        self
+    }
+}
+
 impl<F> VisitWith<F> for ! {
     fn visit_children(&self, _: &mut F) {}
 }
 
+impl<F> VisitMutWith<F> for ! {
+    fn visit_mut_children(&mut self, _: &mut F) {}
+}
+
 impl<T, F> FoldWith<F> for Box<T>
 where
     F: Fold<T>,
@@ -194,6 +527,15 @@ where
     }
 }
 
+impl<T, E, F> TryFoldWith<F, E> for Box<T>
+where
+    F: TryFold<T, E>,
+{
+    fn try_fold_children(self, f: &mut F) -> Result<Self, E> {
+        f.try_fold(*self).map(Box::new)
+    }
+}
+
 impl<T, F> VisitWith<F> for Box<T>
 where
     F: Visit<T>,
@@ -203,6 +545,15 @@ where
     }
 }
 
+impl<T, F> VisitMutWith<F> for Box<T>
+where
+    F: VisitMut<T>,
+{
+    fn visit_mut_children(&mut self, f: &mut F) {
+        f.visit_mut(&mut **self)
+    }
+}
+
 impl<T, F> FoldWith<F> for Vec<T>
 where
     F: Fold<T>,
@@ -212,6 +563,40 @@ where
     }
 }
 
+impl<T, E, F> TryFoldWith<F, E> for Vec<T>
+where
+    F: TryFold<T, E>,
+{
+    fn try_fold_children(self, f: &mut F) -> Result<Self, E> {
+        self.into_iter().map(|it| f.try_fold(it)).collect()
+    }
+}
+
+/// Trait implemented for types which can fold their children in parallel.
+///
+/// As the folder is cloned for each thread, this is useful only for folders
+/// which don't collect states, like constant folding.
+///
+/// This requires the `rayon` feature.
+#[cfg(feature = "rayon")]
+pub trait ParFoldWith<F>: Sized {
+    /// Folds children with clones of `f`, using threads of rayon.
+    fn par_fold_children(self, f: &F) -> Self;
+}
+
+#[cfg(feature = "rayon")]
+impl<T, F> ParFoldWith<F> for Vec<T>
+where
+    T: Send,
+    F: Fold<T> + Clone + Send + Sync,
+{
+    fn par_fold_children(self, f: &F) -> Self {
+        self.into_par_iter()
+            .map_with(f.clone(), |f, it| f.fold(it))
+            .collect()
+    }
+}
+
 impl<T, F> VisitWith<F> for Vec<T>
 where
     F: Visit<T>,
@@ -221,6 +606,15 @@ where
     }
 }
 
+impl<T, F> VisitMutWith<F> for Vec<T>
+where
+    F: VisitMut<T>,
+{
+    fn visit_mut_children(&mut self, f: &mut F) {
+        self.iter_mut().for_each(|node| f.visit_mut(node))
+    }
+}
+
 impl<T, F> FoldWith<F> for Option<T>
 where
     F: Fold<T>,
@@ -230,6 +624,18 @@ where
     }
 }
 
+impl<T, E, F> TryFoldWith<F, E> for Option<T>
+where
+    F: TryFold<T, E>,
+{
+    fn try_fold_children(self, f: &mut F) -> Result<Self, E> {
+        match self {
+            Some(t) => f.try_fold(t).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
 impl<T, F> VisitWith<F> for Option<T>
 where
     F: Visit<T>,
@@ -241,6 +647,17 @@ where
     }
 }
 
+impl<T, F> VisitMutWith<F> for Option<T>
+where
+    F: VisitMut<T>,
+{
+    fn visit_mut_children(&mut self, f: &mut F) {
+        if let Some(ref mut node) = *self {
+            f.visit_mut(node)
+        }
+    }
+}
+
 impl<F> FoldWith<F> for String {
     /// No op.
     fn fold_children(self, _: &mut F) -> Self {
@@ -248,11 +665,23 @@ impl<F> FoldWith<F> for String {
     }
 }
 
+impl<F, E> TryFoldWith<F, E> for String {
+    /// No op.
+    fn try_fold_children(self, _: &mut F) -> Result<Self, E> {
+        Ok(self)
+    }
+}
+
 impl<F> VisitWith<F> for String {
     /// No op.
     fn visit_children(&self, _: &mut F) {}
 }
 
+impl<F> VisitMutWith<F> for String {
+    /// No op.
+    fn visit_mut_children(&mut self, _: &mut F) {}
+}
+
 impl<F, S: StaticAtomSet> FoldWith<F> for Atom<S> {
     /// No op.
     fn fold_children(self, _: &mut F) -> Self {
@@ -260,11 +689,23 @@ impl<F, S: StaticAtomSet> FoldWith<F> for Atom<S> {
     }
 }
 
+impl<F, E, S: StaticAtomSet> TryFoldWith<F, E> for Atom<S> {
+    /// No op.
+    fn try_fold_children(self, _: &mut F) -> Result<Self, E> {
+        Ok(self)
+    }
+}
+
 impl<F, S: StaticAtomSet> VisitWith<F> for Atom<S> {
     /// No op.
     fn visit_children(&self, _: &mut F) {}
 }
 
+impl<F, S: StaticAtomSet> VisitMutWith<F> for Atom<S> {
+    /// No op.
+    fn visit_mut_children(&mut self, _: &mut F) {}
+}
+
 impl<A, B, F> FoldWith<F> for Either<A, B>
 where
     F: Fold<A> + Fold<B>,
@@ -277,6 +718,18 @@ where
     }
 }
 
+impl<A, B, E, F> TryFoldWith<F, E> for Either<A, B>
+where
+    F: TryFold<A, E> + TryFold<B, E>,
+{
+    fn try_fold_children(self, f: &mut F) -> Result<Self, E> {
+        match self {
+            Either::Left(a) => TryFold::<A, E>::try_fold(f, a).map(Either::Left),
+            Either::Right(b) => TryFold::<B, E>::try_fold(f, b).map(Either::Right),
+        }
+    }
+}
+
 impl<A, B, F> VisitWith<F> for Either<A, B>
 where
     F: Visit<A> + Visit<B>,
@@ -288,3 +741,153 @@ where
         }
     }
 }
+
+impl<A, B, F> VisitMutWith<F> for Either<A, B>
+where
+    F: VisitMut<A> + VisitMut<B>,
+{
+    fn visit_mut_children(&mut self, f: &mut F) {
+        match *self {
+            Either::Left(ref mut a) => f.visit_mut(a),
+            Either::Right(ref mut b) => f.visit_mut(b),
+        }
+    }
+}
+
+macro_rules! tuple {
+    ($($T:ident: $idx:tt),*) => {
+        impl<$($T,)* F> FoldWith<F> for ($($T,)*)
+        where
+            F: $(Fold<$T> +)*,
+        {
+            fn fold_children(self, f: &mut F) -> Self {
+                ($(Fold::<$T>::fold(f, self.$idx),)*)
+            }
+        }
+
+        impl<$($T,)* F> VisitWith<F> for ($($T,)*)
+        where
+            F: $(Visit<$T> +)*,
+        {
+            fn visit_children(&self, f: &mut F) {
+                $(Visit::<$T>::visit(f, &self.$idx);)*
+            }
+        }
+    };
+}
+
+tuple!(A: 0, B: 1);
+tuple!(A: 0, B: 1, C: 2);
+tuple!(A: 0, B: 1, C: 2, D: 3);
+
+impl<T, F> FoldWith<F> for Rc<T>
+where
+    T: Clone,
+    F: Fold<T>,
+{
+    /// Clones the value only if it's shared.
+    fn fold_children(self, f: &mut F) -> Self {
+        let node = Rc::try_unwrap(self).unwrap_or_else(|rc| (*rc).clone());
+        Rc::new(f.fold(node))
+    }
+}
+
+impl<T, F> VisitWith<F> for Rc<T>
+where
+    F: Visit<T>,
+{
+    fn visit_children(&self, f: &mut F) {
+        f.visit(&**self)
+    }
+}
+
+impl<T, F> FoldWith<F> for Arc<T>
+where
+    T: Clone,
+    F: Fold<T>,
+{
+    /// Clones the value only if it's shared.
+    fn fold_children(self, f: &mut F) -> Self {
+        let node = Arc::try_unwrap(self).unwrap_or_else(|arc| (*arc).clone());
+        Arc::new(f.fold(node))
+    }
+}
+
+impl<T, F> VisitWith<F> for Arc<T>
+where
+    F: Visit<T>,
+{
+    fn visit_children(&self, f: &mut F) {
+        f.visit(&**self)
+    }
+}
+
+/// Folds values. Keys are not folded.
+impl<K, V, S, F> FoldWith<F> for HashMap<K, V, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher + Default,
+    F: Fold<V>,
+{
+    fn fold_children(self, f: &mut F) -> Self {
+        self.into_iter().map(|(k, v)| (k, f.fold(v))).collect()
+    }
+}
+
+/// Visits values. Keys are not visited.
+impl<K, V, S, F> VisitWith<F> for HashMap<K, V, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+    F: Visit<V>,
+{
+    fn visit_children(&self, f: &mut F) {
+        self.values().for_each(|v| f.visit(v))
+    }
+}
+
+/// Folds values. Keys are not folded.
+impl<K, V, F> FoldWith<F> for BTreeMap<K, V>
+where
+    K: Ord,
+    F: Fold<V>,
+{
+    fn fold_children(self, f: &mut F) -> Self {
+        self.into_iter().map(|(k, v)| (k, f.fold(v))).collect()
+    }
+}
+
+/// Visits values. Keys are not visited.
+impl<K, V, F> VisitWith<F> for BTreeMap<K, V>
+where
+    K: Ord,
+    F: Visit<V>,
+{
+    fn visit_children(&self, f: &mut F) {
+        self.values().for_each(|v| f.visit(v))
+    }
+}
+
+impl<T, E, F> FoldWith<F> for Result<T, E>
+where
+    F: Fold<T> + Fold<E>,
+{
+    fn fold_children(self, f: &mut F) -> Self {
+        match self {
+            Ok(t) => Ok(Fold::<T>::fold(f, t)),
+            Err(e) => Err(Fold::<E>::fold(f, e)),
+        }
+    }
+}
+
+impl<T, E, F> VisitWith<F> for Result<T, E>
+where
+    F: Visit<T> + Visit<E>,
+{
+    fn visit_children(&self, f: &mut F) {
+        match *self {
+            Ok(ref t) => f.visit(t),
+            Err(ref e) => f.visit(e),
+        }
+    }
+}
@@ -0,0 +1,140 @@
+//! Profiling of passes.
+
+use fold::{Fold, FoldWith, Visit, VisitWith};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    intrinsics::type_name,
+    time::{Duration, Instant},
+};
+
+/// Folder which records how long `folder` takes and how many nodes it
+/// visits.
+///
+/// Nodes given to `folder` are counted by types, including containers like
+/// `Vec<T>`. Time spent to count nodes is not included.
+pub struct Instrument<F> {
+    folder: F,
+    stats: InstrumentStats,
+    callback: Option<Box<FnMut(&InstrumentStats) + Send>>,
+}
+
+/// Recorded by [Instrument].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct InstrumentStats {
+    /// Number of times the folder is applied.
+    pub runs: usize,
+    /// Total time spent in the folder.
+    pub time: Duration,
+    /// Names of node types to the number of nodes visited.
+    pub nodes: BTreeMap<&'static str, usize>,
+}
+
+impl<F> Instrument<F> {
+    pub fn new(folder: F) -> Self {
+        Instrument {
+            folder,
+            stats: Default::default(),
+            callback: None,
+        }
+    }
+
+    /// Calls `callback` with stats after each time the folder is applied.
+    pub fn with_callback<C>(self, callback: C) -> Self
+    where
+        C: FnMut(&InstrumentStats) + Send + 'static,
+    {
+        Instrument {
+            callback: Some(box callback),
+            ..self
+        }
+    }
+
+    pub fn stats(&self) -> &InstrumentStats {
+        &self.stats
+    }
+
+    pub fn into_inner(self) -> F {
+        self.folder
+    }
+
+    fn count<T>(&mut self, node: &T)
+    where
+        T: VisitWith<NodeCounter>,
+    {
+        let mut counter = NodeCounter::default();
+        node.visit_with(&mut counter);
+        for (name, count) in counter.nodes {
+            *self.stats.nodes.entry(name).or_insert(0) += count;
+        }
+    }
+
+    fn time<Ret, Op>(&mut self, op: Op) -> Ret
+    where
+        Op: FnOnce(&mut F) -> Ret,
+    {
+        let start = Instant::now();
+        let ret = op(&mut self.folder);
+        self.stats.time += start.elapsed();
+        self.stats.runs += 1;
+
+        if let Some(ref mut callback) = self.callback {
+            callback(&self.stats);
+        }
+
+        ret
+    }
+}
+
+impl<F> fmt::Debug for Instrument<F>
+where
+    F: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Instrument")
+            .field("folder", &self.folder)
+            .field("stats", &self.stats)
+            .finish()
+    }
+}
+
+impl<T, F> Fold<T> for Instrument<F>
+where
+    T: FoldWith<Self> + VisitWith<NodeCounter>,
+    F: Fold<T>,
+{
+    fn fold(&mut self, node: T) -> T {
+        self.count(&node);
+        self.time(|folder| folder.fold(node))
+    }
+}
+
+impl<T, F> Visit<T> for Instrument<F>
+where
+    T: VisitWith<Self> + VisitWith<NodeCounter>,
+    F: Visit<T>,
+{
+    fn visit(&mut self, node: &T) {
+        self.count(node);
+        self.time(|folder| folder.visit(node))
+    }
+}
+
+/// Counts nodes by types, used by [Instrument].
+#[derive(Debug, Default)]
+pub struct NodeCounter {
+    nodes: BTreeMap<&'static str, usize>,
+}
+
+impl<T> Visit<T> for NodeCounter
+where
+    T: VisitWith<Self>,
+{
+    fn visit(&mut self, node: &T) {
+        *self
+            .nodes
+            .entry(unsafe { type_name::<T>() })
+            .or_insert(0) += 1;
+        node.visit_children(self)
+    }
+}
@@ -1,10 +1,13 @@
 #![feature(box_syntax)]
+#![feature(core_intrinsics)]
 #![feature(range_contains)]
 #![feature(try_trait)]
 #![feature(never_type)]
 #![feature(specialization)]
 extern crate ast_node;
 extern crate either;
+#[cfg(feature = "rayon")]
+extern crate rayon;
 extern crate rustc_data_structures;
 extern crate rustc_errors;
 extern crate string_cache;
@@ -12,15 +15,29 @@ extern crate syntax;
 extern crate syntax_pos;
 
 pub use self::{
+    comments::{Comment, CommentKind, Comments},
+    ctl::{VisitControl, VisitCtl, VisitCtlWith},
+    eq::{EqIgnoreSpan, HashIgnoreSpan},
     errors::{SourceMapper, SourceMapperDyn},
-    fold::{Fold, FoldWith, Visit, VisitWith},
+    fold::{
+        noop, when, Fold, FoldHooks, FoldWith, Noop, Optional, Repeat, Repeated, TryFold,
+        TryFoldWith, Visit, VisitMut, VisitMutWith, VisitWith,
+    },
+    instrument::{Instrument, InstrumentStats},
+    loader::MemoryFileLoader,
+    map_fold::{MapFold, MapFoldWith},
+    path::{AstParentKind, AstPath, FoldPath, FoldWithPath, VisitPath, VisitWithPath},
     pos::*,
 };
-pub use ast_node::{ast_node, Fold, FromVariant, Spanned};
+#[cfg(feature = "rayon")]
+pub use self::fold::ParFoldWith;
+#[cfg(feature = "stable-fold")]
+pub use self::stable_fold::{FoldNode, FoldNodeChildren};
+pub use ast_node::{ast_node, EqIgnoreSpan, Fold, FromVariant, HashIgnoreSpan, MapFold, Spanned};
 pub use rustc_data_structures::sync;
 use std::fmt::Debug;
 pub use syntax::source_map::{
-    FileLines, FileLoader, FileName, FilePathMapping, SourceMap, SpanSnippetError,
+    FileLines, FileLoader, FileName, FilePathMapping, RealFileLoader, SourceMap, SpanSnippetError,
 };
 
 /// A marker trait for ast nodes.
@@ -28,7 +45,16 @@ pub trait AstNode: Debug + PartialEq + Clone + Spanned {}
 
 impl<N: Debug + PartialEq + Clone + Spanned> AstNode for N {}
 
+mod comments;
+mod ctl;
+mod eq;
 pub mod errors;
 mod fold;
+mod instrument;
+mod loader;
+mod map_fold;
 pub mod macros;
+mod path;
 mod pos;
+#[cfg(feature = "stable-fold")]
+mod stable_fold;
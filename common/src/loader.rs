@@ -0,0 +1,84 @@
+//! Sources of files loaded by a [SourceMap](::SourceMap).
+
+use rustc_data_structures::sync::{Lock, Lrc};
+use std::{
+    collections::HashMap,
+    io,
+    path::{Path, PathBuf},
+};
+use syntax::source_map::FileLoader;
+
+/// Loads files from memory, for bundlers and language servers which have
+/// virtual or unsaved files.
+///
+/// Clones share files, so files can be updated after the loader is given to
+/// `SourceMap::with_file_loader`.
+///
+/// # Example
+///
+/// ```ignore
+/// let loader = MemoryFileLoader::default();
+/// loader.insert("/src/a.js", "export default 1;");
+///
+/// let cm = SourceMap::with_file_loader(box loader.clone(), FilePathMapping::empty());
+/// let fm = cm.load_file(Path::new("/src/a.js"))?;
+/// ```
+#[derive(Clone, Default)]
+pub struct MemoryFileLoader {
+    files: Lrc<Lock<HashMap<PathBuf, String>>>,
+}
+
+impl MemoryFileLoader {
+    /// Adds or replaces a file.
+    pub fn insert<P, S>(&self, path: P, src: S)
+    where
+        P: Into<PathBuf>,
+        S: Into<String>,
+    {
+        self.files.lock().insert(path.into(), src.into());
+    }
+
+    pub fn remove(&self, path: &Path) -> Option<String> {
+        self.files.lock().remove(path)
+    }
+}
+
+impl FileLoader for MemoryFileLoader {
+    fn file_exists(&self, path: &Path) -> bool {
+        self.files.lock().contains_key(path)
+    }
+
+    /// Paths are used as is.
+    fn abs_path(&self, path: &Path) -> Option<PathBuf> {
+        Some(path.to_path_buf())
+    }
+
+    fn read_file(&self, path: &Path) -> io::Result<String> {
+        self.files.lock().get(path).cloned().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{} does not exist", path.display()),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syntax::source_map::{FilePathMapping, SourceMap};
+
+    #[test]
+    fn load() {
+        ::syntax_pos::GLOBALS.set(&::syntax_pos::Globals::new(), || {
+            let loader = MemoryFileLoader::default();
+            let cm = SourceMap::with_file_loader(box loader.clone(), FilePathMapping::empty());
+
+            assert!(cm.load_file(Path::new("/a.js")).is_err());
+
+            loader.insert("/a.js", "foo();");
+            let fm = cm.load_file(Path::new("/a.js")).unwrap();
+            assert_eq!(&**fm.src.as_ref().unwrap(), "foo();");
+        })
+    }
+}
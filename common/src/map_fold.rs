@@ -0,0 +1,143 @@
+//! Folding a tree into another tree, e.g. lowering an ast into a custom ir.
+
+use either::Either;
+use string_cache::{Atom, StaticAtomSet};
+
+/// Folder which converts a node of type `A` into a node of type `B`.
+///
+/// A node is converted by [MapFoldWith], which can be derived for a node of
+/// the target tree with `#[derive(MapFold)]`. Implement this trait for
+/// nodes which should be converted in another way.
+///
+/// This trait requires `#![feature(specialization)]`.
+///
+/// # Example
+///
+/// ```ignore
+/// #[derive(MapFold)]
+/// #[map_fold(from = "ast::CallExpr")]
+/// pub struct CallExpr {
+///     pub callee: ExprOrSuper,
+///     pub args: Vec<ExprOrSpread>,
+/// }
+///
+/// struct Lowering;
+///
+/// let call: CallExpr = Lowering.map_fold(call);
+/// ```
+pub trait MapFold<A, B> {
+    fn map_fold(&mut self, node: A) -> B;
+}
+
+impl<'a, A, B, F: ?Sized> MapFold<A, B> for &'a mut F
+where
+    A: MapFoldWith<B, Self>,
+    F: MapFold<A, B>,
+{
+    fn map_fold(&mut self, node: A) -> B {
+        (**self).map_fold(node)
+    }
+}
+
+impl<A, B, F: ?Sized> MapFold<A, B> for Box<F>
+where
+    A: MapFoldWith<B, Self>,
+    F: MapFold<A, B>,
+{
+    fn map_fold(&mut self, node: A) -> B {
+        (**self).map_fold(node)
+    }
+}
+
+impl<A, B, F> MapFold<A, B> for F
+where
+    A: MapFoldWith<B, F>,
+{
+    default fn map_fold(&mut self, node: A) -> B {
+        node.map_fold_children(self)
+    }
+}
+
+/// Trait implemented for types which know how to convert itself into `B`.
+///
+/// `B` comes first so that this trait can be implemented for a node of a
+/// foreign tree.
+///
+/// This trait can be derived with `#[derive(MapFold)]` on `B`:
+///
+///  - `#[map_fold(from = "path::Node")]` converts fields (or variants) of
+///    `path::Node` with the same names. Fields of `path::Node` which `B` does
+///    not have are dropped.
+///
+///  - `#[map_fold(bridge = "path::Node")]` converts `path::Node` with
+///    `From`, for nodes which are not mirrored field by field.
+pub trait MapFoldWith<B, F>: Sized {
+    /// This is used by default implementation of `MapFold<Self, B>::map_fold`.
+    fn map_fold_children(self, f: &mut F) -> B;
+
+    /// Call `f.map_fold(self)`.
+    ///
+    /// This bypasses a type inference bug which is caused by specialization.
+    fn map_fold_with(self, f: &mut F) -> B {
+        f.map_fold(self)
+    }
+}
+
+impl<B, F> MapFoldWith<B, F> for ! {
+    fn map_fold_children(self, _: &mut F) -> B {
+        self
+    }
+}
+
+impl<A, B, F> MapFoldWith<Box<B>, F> for Box<A>
+where
+    F: MapFold<A, B>,
+{
+    fn map_fold_children(self, f: &mut F) -> Box<B> {
+        box f.map_fold(*self)
+    }
+}
+
+impl<A, B, F> MapFoldWith<Vec<B>, F> for Vec<A>
+where
+    F: MapFold<A, B>,
+{
+    fn map_fold_children(self, f: &mut F) -> Vec<B> {
+        self.into_iter().map(|node| f.map_fold(node)).collect()
+    }
+}
+
+impl<A, B, F> MapFoldWith<Option<B>, F> for Option<A>
+where
+    F: MapFold<A, B>,
+{
+    fn map_fold_children(self, f: &mut F) -> Option<B> {
+        self.map(|node| f.map_fold(node))
+    }
+}
+
+impl<F> MapFoldWith<String, F> for String {
+    /// No op.
+    fn map_fold_children(self, _: &mut F) -> String {
+        self
+    }
+}
+
+impl<F, S: StaticAtomSet> MapFoldWith<Atom<S>, F> for Atom<S> {
+    /// No op.
+    fn map_fold_children(self, _: &mut F) -> Atom<S> {
+        self
+    }
+}
+
+impl<A, B, C, D, F> MapFoldWith<Either<C, D>, F> for Either<A, B>
+where
+    F: MapFold<A, C> + MapFold<B, D>,
+{
+    fn map_fold_children(self, f: &mut F) -> Either<C, D> {
+        match self {
+            Either::Left(l) => Either::Left(f.map_fold(l)),
+            Either::Right(r) => Either::Right(f.map_fold(r)),
+        }
+    }
+}
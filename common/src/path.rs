@@ -0,0 +1,258 @@
+//! Traversal with ancestors of nodes.
+
+use either::Either;
+use string_cache::{Atom, StaticAtomSet};
+
+/// Kind of an ancestor node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AstParentKind {
+    /// A struct, with the name of its type.
+    Struct(&'static str),
+    /// A variant of an enum, with the name of the enum and the variant.
+    Variant(&'static str, &'static str),
+}
+
+impl AstParentKind {
+    /// Name of the type of the node.
+    pub fn type_name(self) -> &'static str {
+        match self {
+            AstParentKind::Struct(name) | AstParentKind::Variant(name, _) => name,
+        }
+    }
+}
+
+/// Ancestors of the node being visited, from the root to the parent.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct AstPath {
+    kinds: Vec<AstParentKind>,
+}
+
+impl AstPath {
+    pub fn kinds(&self) -> &[AstParentKind] {
+        &self.kinds
+    }
+
+    /// Kind of the direct parent.
+    pub fn parent(&self) -> Option<AstParentKind> {
+        self.kinds.last().cloned()
+    }
+
+    /// Returns true if a type named `type_name` is an ancestor.
+    pub fn is_inside(&self, type_name: &str) -> bool {
+        self.kinds.iter().any(|kind| kind.type_name() == type_name)
+    }
+
+    /// Calls `op` with `kind` pushed to the path.
+    ///
+    /// This is used by `#[derive(Fold)]`.
+    pub fn with<F, Ret>(&mut self, kind: AstParentKind, op: F) -> Ret
+    where
+        F: FnOnce(&mut AstPath) -> Ret,
+    {
+        self.kinds.push(kind);
+        let ret = op(self);
+        self.kinds.pop();
+        ret
+    }
+}
+
+/// `Fold` which knows ancestors of nodes.
+///
+/// This trait requires `#![feature(specialization)]`.
+pub trait FoldPath<T> {
+    fn fold_path(&mut self, node: T, path: &mut AstPath) -> T;
+}
+
+/// `Visit` which knows ancestors of nodes.
+///
+/// This trait requires `#![feature(specialization)]`.
+pub trait VisitPath<T> {
+    fn visit_path(&mut self, node: &T, path: &mut AstPath);
+}
+
+impl<'a, T, F: ?Sized> FoldPath<T> for &'a mut F
+where
+    T: FoldWithPath<Self>,
+    F: FoldPath<T>,
+{
+    fn fold_path(&mut self, node: T, path: &mut AstPath) -> T {
+        (**self).fold_path(node, path)
+    }
+}
+
+impl<'a, T, F: ?Sized> VisitPath<T> for &'a mut F
+where
+    T: VisitWithPath<Self>,
+    F: VisitPath<T>,
+{
+    fn visit_path(&mut self, node: &T, path: &mut AstPath) {
+        (**self).visit_path(node, path)
+    }
+}
+
+impl<T, F> FoldPath<T> for F
+where
+    T: FoldWithPath<F>,
+{
+    default fn fold_path(&mut self, t: T, path: &mut AstPath) -> T {
+        t.fold_children_with_path(self, path)
+    }
+}
+
+impl<T, F> VisitPath<T> for F
+where
+    T: VisitWithPath<F>,
+{
+    default fn visit_path(&mut self, t: &T, path: &mut AstPath) {
+        t.visit_children_with_path(self, path)
+    }
+}
+
+/// Trait implemented for types which know how to fold itself while tracking
+/// ancestors.
+///
+/// This trait can be derived with `#[derive(Fold)]`, which pushes the node
+/// to the path while folding its fields.
+pub trait FoldWithPath<F>: Sized {
+    /// This is used by default implementation of `FoldPath<Self>::fold_path`.
+    fn fold_children_with_path(self, f: &mut F, path: &mut AstPath) -> Self;
+
+    /// Call `f.fold_path(self, path)`.
+    ///
+    /// This bypasses a type inference bug which is caused by specialization.
+    fn fold_with_path(self, f: &mut F, path: &mut AstPath) -> Self {
+        f.fold_path(self, path)
+    }
+}
+
+/// Trait implemented for types which know how to visit itself while tracking
+/// ancestors.
+///
+/// This trait can be derived with `#[derive(Fold)]`, which pushes the node
+/// to the path while visiting its fields.
+pub trait VisitWithPath<F>: Sized {
+    /// This is used by default implementation of
+    /// `VisitPath<Self>::visit_path`.
+    fn visit_children_with_path(&self, f: &mut F, path: &mut AstPath);
+
+    /// Call `f.visit_path(self, path)`.
+    ///
+    /// This bypasses a type inference bug which is caused by specialization.
+    fn visit_with_path(&self, f: &mut F, path: &mut AstPath) {
+        f.visit_path(self, path)
+    }
+}
+
+impl<F> FoldWithPath<F> for ! {
+    fn fold_children_with_path(self, _: &mut F, _: &mut AstPath) -> Self {
+        self
+    }
+}
+
+impl<F> VisitWithPath<F> for ! {
+    fn visit_children_with_path(&self, _: &mut F, _: &mut AstPath) {}
+}
+
+impl<T, F> FoldWithPath<F> for Box<T>
+where
+    F: FoldPath<T>,
+{
+    fn fold_children_with_path(self, f: &mut F, path: &mut AstPath) -> Self {
+        box f.fold_path(*self, path)
+    }
+}
+
+impl<T, F> VisitWithPath<F> for Box<T>
+where
+    F: VisitPath<T>,
+{
+    fn visit_children_with_path(&self, f: &mut F, path: &mut AstPath) {
+        f.visit_path(&**self, path)
+    }
+}
+
+impl<T, F> FoldWithPath<F> for Vec<T>
+where
+    F: FoldPath<T>,
+{
+    fn fold_children_with_path(self, f: &mut F, path: &mut AstPath) -> Self {
+        self.into_iter().map(|it| f.fold_path(it, path)).collect()
+    }
+}
+
+impl<T, F> VisitWithPath<F> for Vec<T>
+where
+    F: VisitPath<T>,
+{
+    fn visit_children_with_path(&self, f: &mut F, path: &mut AstPath) {
+        self.iter().for_each(|node| f.visit_path(node, path))
+    }
+}
+
+impl<T, F> FoldWithPath<F> for Option<T>
+where
+    F: FoldPath<T>,
+{
+    fn fold_children_with_path(self, f: &mut F, path: &mut AstPath) -> Self {
+        self.map(|t| f.fold_path(t, path))
+    }
+}
+
+impl<T, F> VisitWithPath<F> for Option<T>
+where
+    F: VisitPath<T>,
+{
+    fn visit_children_with_path(&self, f: &mut F, path: &mut AstPath) {
+        if let Some(ref node) = *self {
+            f.visit_path(node, path)
+        }
+    }
+}
+
+impl<F> FoldWithPath<F> for String {
+    /// No op.
+    fn fold_children_with_path(self, _: &mut F, _: &mut AstPath) -> Self {
+        self
+    }
+}
+
+impl<F> VisitWithPath<F> for String {
+    /// No op.
+    fn visit_children_with_path(&self, _: &mut F, _: &mut AstPath) {}
+}
+
+impl<F, S: StaticAtomSet> FoldWithPath<F> for Atom<S> {
+    /// No op.
+    fn fold_children_with_path(self, _: &mut F, _: &mut AstPath) -> Self {
+        self
+    }
+}
+
+impl<F, S: StaticAtomSet> VisitWithPath<F> for Atom<S> {
+    /// No op.
+    fn visit_children_with_path(&self, _: &mut F, _: &mut AstPath) {}
+}
+
+impl<A, B, F> FoldWithPath<F> for Either<A, B>
+where
+    F: FoldPath<A> + FoldPath<B>,
+{
+    fn fold_children_with_path(self, f: &mut F, path: &mut AstPath) -> Self {
+        match self {
+            Either::Left(a) => Either::Left(FoldPath::<A>::fold_path(f, a, path)),
+            Either::Right(b) => Either::Right(FoldPath::<B>::fold_path(f, b, path)),
+        }
+    }
+}
+
+impl<A, B, F> VisitWithPath<F> for Either<A, B>
+where
+    F: VisitPath<A> + VisitPath<B>,
+{
+    fn visit_children_with_path(&self, f: &mut F, path: &mut AstPath) {
+        match *self {
+            Either::Left(ref a) => f.visit_path(a, path),
+            Either::Right(ref b) => f.visit_path(b, path),
+        }
+    }
+}
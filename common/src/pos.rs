@@ -1,7 +1,10 @@
-use fold::{FoldWith, VisitWith};
+use ctl::{VisitControl, VisitCtlWith};
+use fold::{Fold, FoldWith, TryFoldWith, VisitMutWith, VisitWith};
+use map_fold::MapFoldWith;
+use path::{AstPath, FoldWithPath, VisitWithPath};
 pub use syntax_pos::{
-    hygiene, BytePos, ExpnFormat, ExpnInfo, FileName, Globals, Mark, MultiSpan, SourceFile, Span,
-    SpanData, SyntaxContext, DUMMY_SP, GLOBALS, NO_EXPANSION,
+    hygiene, BytePos, CharPos, ExpnFormat, ExpnInfo, FileName, Globals, Loc, Mark, MultiSpan,
+    SourceFile, Span, SpanData, SyntaxContext, DUMMY_SP, GLOBALS, NO_EXPANSION,
 };
 
 ///
@@ -57,6 +60,40 @@ where
     }
 }
 
+/// Folder which applies a function to all spans in a node.
+///
+/// Created by [span_remapper].
+#[derive(Debug, Clone, Copy)]
+pub struct SpanFold<F> {
+    op: F,
+}
+
+/// Creates a folder which replaces all spans with `op(span)`, including dummy
+/// spans.
+///
+/// # Example
+///
+/// ```ignore
+/// let module = module.fold_with(&mut span_remapper(|sp| {
+///     sp.with_lo(sp.lo() + offset).with_hi(sp.hi() + offset)
+/// }));
+/// ```
+pub fn span_remapper<F>(op: F) -> SpanFold<F>
+where
+    F: FnMut(Span) -> Span,
+{
+    SpanFold { op }
+}
+
+impl<F> Fold<Span> for SpanFold<F>
+where
+    F: FnMut(Span) -> Span,
+{
+    fn fold(&mut self, span: Span) -> Span {
+        (self.op)(span)
+    }
+}
+
 impl<F> FoldWith<F> for Span {
     /// No op as span does not have any child.
     fn fold_children(self, _: &mut F) -> Span {
@@ -64,7 +101,45 @@ impl<F> FoldWith<F> for Span {
     }
 }
 
+impl<F, E> TryFoldWith<F, E> for Span {
+    /// No op as span does not have any child.
+    fn try_fold_children(self, _: &mut F) -> Result<Span, E> {
+        Ok(self)
+    }
+}
+
+impl<F> MapFoldWith<Span, F> for Span {
+    /// No op as span does not have any child.
+    fn map_fold_children(self, _: &mut F) -> Span {
+        self
+    }
+}
+
 impl<F> VisitWith<F> for Span {
     /// No op as span does not have any child.
     fn visit_children(&self, _: &mut F) {}
 }
+
+impl<F> VisitMutWith<F> for Span {
+    /// No op as span does not have any child.
+    fn visit_mut_children(&mut self, _: &mut F) {}
+}
+
+impl<F> FoldWithPath<F> for Span {
+    /// No op as span does not have any child.
+    fn fold_children_with_path(self, _: &mut F, _: &mut AstPath) -> Self {
+        self
+    }
+}
+
+impl<F> VisitWithPath<F> for Span {
+    /// No op as span does not have any child.
+    fn visit_children_with_path(&self, _: &mut F, _: &mut AstPath) {}
+}
+
+impl<F> VisitCtlWith<F> for Span {
+    /// No op as span does not have any child.
+    fn visit_children_ctl(&self, _: &mut F) -> VisitControl {
+        VisitControl::Continue
+    }
+}
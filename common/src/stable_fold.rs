@@ -0,0 +1,136 @@
+//! Folding which does not require specialization.
+//!
+//! `Fold` falls back to folding fields of nodes which a folder does not
+//! handle, using specialization. Instead, a crate of nodes defines a folder
+//! trait with a method for each node type by [define_fold], and a folder
+//! overrides only the methods it needs.
+//!
+//! `#[derive(Fold)]` implements [FoldNodeChildren] if the crate using it
+//! has a `stable-fold` feature which is enabled. Hooks of `#[fold(hooks)]`
+//! are not called.
+
+use either::Either;
+use pos::Span;
+use string_cache::{Atom, StaticAtomSet};
+
+/// Value which contains nodes folded by `F`.
+///
+/// This is implemented for node types by [define_fold], and for containers
+/// of nodes.
+pub trait FoldNode<F: ?Sized>: Sized {
+    fn fold_node(self, f: &mut F) -> Self;
+}
+
+/// Node which knows how to fold its fields.
+///
+/// This is used by default methods of traits created by [define_fold].
+///
+///#Derive
+///
+/// This trait can be derived with `#[derive(Fold)]`, which ignores the same
+/// fields as `FoldWith`.
+pub trait FoldNodeChildren<F: ?Sized>: Sized {
+    fn fold_node_children(self, f: &mut F) -> Self;
+}
+
+/// Defines a folder trait with a method for each node type, and implements
+/// [FoldNode] for the node types.
+///
+/// Each method folds fields of the node by default.
+///
+/// ```ignore
+/// define_fold! {
+///     /// Folder of expressions.
+///     pub trait ExprFold {
+///         fn fold_expr(Expr);
+///         fn fold_lit(Lit);
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! define_fold {
+    (
+        $(#[$attr:meta])*
+        pub trait $name:ident {
+            $(fn $method:ident($T:ty);)*
+        }
+    ) => {
+        $(#[$attr])*
+        pub trait $name {
+            $(
+                fn $method(&mut self, node: $T) -> $T {
+                    $crate::FoldNodeChildren::fold_node_children(node, self)
+                }
+            )*
+        }
+
+        $(
+            impl<F: ?Sized + $name> $crate::FoldNode<F> for $T {
+                fn fold_node(self, f: &mut F) -> Self {
+                    f.$method(self)
+                }
+            }
+        )*
+    };
+}
+
+impl<T, F: ?Sized> FoldNode<F> for Box<T>
+where
+    T: FoldNode<F>,
+{
+    fn fold_node(self, f: &mut F) -> Self {
+        Box::new((*self).fold_node(f))
+    }
+}
+
+impl<T, F: ?Sized> FoldNode<F> for Vec<T>
+where
+    T: FoldNode<F>,
+{
+    fn fold_node(self, f: &mut F) -> Self {
+        self.into_iter().map(|node| node.fold_node(f)).collect()
+    }
+}
+
+impl<T, F: ?Sized> FoldNode<F> for Option<T>
+where
+    T: FoldNode<F>,
+{
+    fn fold_node(self, f: &mut F) -> Self {
+        self.map(|node| node.fold_node(f))
+    }
+}
+
+impl<A, B, F: ?Sized> FoldNode<F> for Either<A, B>
+where
+    A: FoldNode<F>,
+    B: FoldNode<F>,
+{
+    fn fold_node(self, f: &mut F) -> Self {
+        match self {
+            Either::Left(a) => Either::Left(a.fold_node(f)),
+            Either::Right(b) => Either::Right(b.fold_node(f)),
+        }
+    }
+}
+
+impl<F: ?Sized> FoldNode<F> for Span {
+    /// No op as span does not have any child.
+    fn fold_node(self, _: &mut F) -> Self {
+        self
+    }
+}
+
+impl<F: ?Sized> FoldNode<F> for String {
+    /// No op.
+    fn fold_node(self, _: &mut F) -> Self {
+        self
+    }
+}
+
+impl<F: ?Sized, S: StaticAtomSet> FoldNode<F> for Atom<S> {
+    /// No op.
+    fn fold_node(self, _: &mut F) -> Self {
+        self
+    }
+}
@@ -1,15 +1,39 @@
-use super::{Expr, Function, PropName};
-use swc_common::{ast_node, Fold, Span};
+use super::{
+    BlockStmt, Expr, Function, Ident, PropName, TsExprWithTypeArgs, TsTypeAnn, TsTypeParamDecl,
+    TsTypeParamInstantiation,
+};
+use swc_common::{ast_node, EqIgnoreSpan, Fold, HashIgnoreSpan, Span};
 
 #[ast_node]
 pub struct Class {
     pub span: Span,
 
-    pub body: Vec<ClassMethod>,
+    pub body: Vec<ClassMember>,
     pub super_class: Option<(Box<Expr>)>,
+
+    pub decorators: Vec<Decorator>,
+
+    /// typescript only
+    #[serde(rename = "typeParameters")]
+    pub type_params: Option<TsTypeParamDecl>,
+    /// `<T>` in `extends Foo<T>`. typescript only
+    #[serde(rename = "superTypeParameters")]
+    pub super_type_params: Option<TsTypeParamInstantiation>,
+    /// typescript only
+    pub implements: Vec<TsExprWithTypeArgs>,
 }
 
 #[ast_node]
+pub enum ClassMember {
+    Method(ClassMethod),
+    PrivateMethod(PrivateMethod),
+    ClassProp(ClassProp),
+    PrivateProp(PrivateProp),
+    /// `static { }`
+    StaticBlock(StaticBlock),
+}
+
+#[ast_node("ClassMethod")]
 pub struct ClassMethod {
     pub span: Span,
     pub key: PropName,
@@ -20,12 +44,93 @@ pub struct ClassMethod {
     pub kind: ClassMethodKind,
 
     pub static_token: Option<Span>,
+
+    pub decorators: Vec<Decorator>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Fold)]
+/// `#a() {}`
+#[ast_node("ClassPrivateMethod")]
+pub struct PrivateMethod {
+    pub span: Span,
+    pub key: PrivateName,
+
+    pub function: Function,
+
+    /// Private methods can't be constructors.
+    #[fold(ignore)]
+    pub kind: ClassMethodKind,
+
+    pub static_token: Option<Span>,
+
+    pub decorators: Vec<Decorator>,
+}
+
+/// `a = 1;`
+#[ast_node("ClassProperty")]
+pub struct ClassProp {
+    pub span: Span,
+    pub key: PropName,
+
+    pub value: Option<(Box<Expr>)>,
+
+    pub static_token: Option<Span>,
+
+    pub decorators: Vec<Decorator>,
+
+    /// typescript only
+    #[serde(rename = "typeAnnotation")]
+    pub type_ann: Option<TsTypeAnn>,
+}
+
+/// `#a = 1;`
+#[ast_node("ClassPrivateProperty")]
+pub struct PrivateProp {
+    pub span: Span,
+    pub key: PrivateName,
+
+    pub value: Option<(Box<Expr>)>,
+
+    pub static_token: Option<Span>,
+
+    pub decorators: Vec<Decorator>,
+
+    /// typescript only
+    #[serde(rename = "typeAnnotation")]
+    pub type_ann: Option<TsTypeAnn>,
+}
+
+/// `#a` in `#a = 1;`, `this.#a` or `#a in obj`.
+#[ast_node]
+pub struct PrivateName {
+    /// Span including `#`.
+    pub span: Span,
+    pub id: Ident,
+}
+
+#[ast_node]
+pub struct StaticBlock {
+    pub span: Span,
+    pub body: BlockStmt,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Fold, EqIgnoreSpan, HashIgnoreSpan)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum ClassMethodKind {
+    #[cfg_attr(feature = "serde", serde(rename = "constructor"))]
     Constructor,
+    #[cfg_attr(feature = "serde", serde(rename = "method"))]
     Method,
+    #[cfg_attr(feature = "serde", serde(rename = "get"))]
     Getter,
+    #[cfg_attr(feature = "serde", serde(rename = "set"))]
     Setter,
 }
+
+/// `@foo` or `@foo.bar(baz)`
+#[ast_node]
+pub struct Decorator {
+    pub span: Span,
+    /// Expression after `@`.
+    #[serde(rename = "expression")]
+    pub expr: Box<Expr>,
+}
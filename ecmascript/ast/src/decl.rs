@@ -1,36 +1,68 @@
-use super::{Class, Expr, Function, Ident, Pat};
-use swc_common::{ast_node, Fold, Span};
+use super::{
+    Class, Expr, Function, Ident, Pat, TsEnumDecl, TsInterfaceDecl, TsModuleDecl, TsTypeAliasDecl,
+};
+use swc_common::{ast_node, EqIgnoreSpan, Fold, HashIgnoreSpan, Span};
 
 #[ast_node]
 pub enum Decl {
     Class(ClassDecl),
     Fn(FnDecl),
     Var(VarDecl),
+
+    /// typescript only
+    TsInterface(TsInterfaceDecl),
+    /// typescript only
+    TsTypeAlias(TsTypeAliasDecl),
+    /// typescript only
+    TsEnum(TsEnumDecl),
+    /// typescript only
+    TsModule(TsModuleDecl),
 }
 
-#[ast_node]
+#[ast_node("FunctionDeclaration")]
 pub struct FnDecl {
+    #[serde(rename = "identifier")]
     pub ident: Ident,
+    /// `declare function foo();`. typescript only
+    pub declare: bool,
     #[span]
     pub function: Function,
 }
 
-#[ast_node]
+#[ast_node("ClassDeclaration")]
 pub struct ClassDecl {
+    #[serde(rename = "identifier")]
     pub ident: Ident,
+    /// `declare class Foo {}`. typescript only
+    pub declare: bool,
     #[span]
     pub class: Class,
 }
 
-#[ast_node]
+#[ast_node("VariableDeclaration")]
 pub struct VarDecl {
     pub span: Span,
     pub kind: VarDeclKind,
+    /// `declare var foo;`. typescript only
+    pub declare: bool,
 
+    #[serde(rename = "declarations")]
     pub decls: Vec<VarDeclarator>,
 }
 
-#[derive(Fold, StringEnum, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
+#[derive(
+    Fold,
+    EqIgnoreSpan,
+    HashIgnoreSpan,
+    StringEnum,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    PartialOrd,
+    Ord,
+    Hash,
+)]
 pub enum VarDeclKind {
     /// `var`
     Var,
@@ -40,10 +72,11 @@ pub enum VarDeclKind {
     Const,
 }
 
-#[ast_node]
+#[ast_node("VariableDeclarator")]
 pub struct VarDeclarator {
     pub span: Span,
 
+    #[serde(rename = "id")]
     pub name: Pat,
     /// Initialization expresion.
     pub init: Option<(Box<Expr>)>,
@@ -1,7 +1,8 @@
 use super::{
-    AssignOp, BinaryOp, BlockStmt, Class, Function, Ident, Lit, Pat, Prop, UnaryOp, UpdateOp,
+    AssignOp, BinaryOp, BlockStmt, Class, Function, Ident, JSXElement, JSXFragment, Lit, Pat,
+    PrivateName, Prop, TsAsExpr, TsTypeAnn, TsTypeParamDecl, UnaryOp, UpdateOp,
 };
-use swc_common::{ast_node, Fold, Span, Spanned};
+use swc_common::{ast_node, EqIgnoreSpan, Fold, HashIgnoreSpan, Span, Spanned};
 
 #[ast_node]
 pub enum Expr {
@@ -40,6 +41,10 @@ pub enum Expr {
 
     Call(CallExpr),
 
+    /// `a?.b`, `a?.[b]` or `a?.()`. Subscripts applied to this node belong to
+    /// the same chain, and are skipped together if the object is nullish.
+    OptChain(OptChainExpr),
+
     /// `new Cat()`
     New(NewExpr),
 
@@ -62,25 +67,38 @@ pub enum Expr {
     Await(AwaitExpr),
 
     Paren(ParenExpr),
+
+    JSXElement(Box<JSXElement>),
+
+    /// `<></>`
+    JSXFragment(JSXFragment),
+
+    /// typescript only
+    TsAs(TsAsExpr),
+
+    /// `#a` in `this.#a` or `#a in obj`. This can't appear elsewhere.
+    PrivateName(PrivateName),
 }
 
-#[ast_node]
+#[ast_node("ThisExpression")]
 #[derive(Copy)]
 pub struct ThisExpr {
     pub span: Span,
 }
 
 /// Array literal.
-#[ast_node]
+#[ast_node("ArrayExpression")]
 pub struct ArrayLit {
     pub span: Span,
+    #[serde(rename = "elements")]
     pub elems: Vec<(Option<ExprOrSpread>)>,
 }
 
 /// Object literal.
-#[ast_node]
+#[ast_node("ObjectExpression")]
 pub struct ObjectLit {
     pub span: Span,
+    #[serde(rename = "properties")]
     pub props: Vec<PropOrSpread>,
 }
 
@@ -96,29 +114,35 @@ pub struct SpreadElement {
     #[span(lo)]
     pub dot3_token: Span,
     #[span(hi)]
+    #[serde(rename = "argument")]
     pub expr: Box<Expr>,
 }
 
-#[ast_node]
+#[ast_node("UnaryExpression")]
 pub struct UnaryExpr {
     pub span: Span,
+    #[serde(rename = "operator")]
     pub op: UnaryOp,
 
+    #[serde(rename = "argument")]
     pub arg: Box<Expr>,
 }
 
-#[ast_node]
+#[ast_node("UpdateExpression")]
 pub struct UpdateExpr {
     pub span: Span,
+    #[serde(rename = "operator")]
     pub op: UpdateOp,
     pub prefix: bool,
 
+    #[serde(rename = "argument")]
     pub arg: Box<Expr>,
 }
 
-#[ast_node]
+#[ast_node("BinaryExpression")]
 pub struct BinExpr {
     pub span: Span,
+    #[serde(rename = "operator")]
     pub op: BinaryOp,
 
     pub left: Box<Expr>,
@@ -127,124 +151,163 @@ pub struct BinExpr {
 }
 
 /// Function expression.
-#[ast_node]
+#[ast_node("FunctionExpression")]
 pub struct FnExpr {
+    #[serde(rename = "identifier")]
     pub ident: Option<Ident>,
     #[span]
     pub function: Function,
 }
 
 /// Class expression.
-#[ast_node]
+#[ast_node("ClassExpression")]
 pub struct ClassExpr {
+    #[serde(rename = "identifier")]
     pub ident: Option<Ident>,
     #[span]
     pub class: Class,
 }
 
-#[ast_node]
+#[ast_node("AssignmentExpression")]
 pub struct AssignExpr {
     pub span: Span,
+    #[serde(rename = "operator")]
     pub op: AssignOp,
     pub left: PatOrExpr,
     pub right: Box<Expr>,
 }
 
-#[ast_node]
+#[ast_node("MemberExpression")]
 pub struct MemberExpr {
     pub span: Span,
+    #[serde(rename = "object")]
     pub obj: ExprOrSuper,
+    #[serde(rename = "property")]
     pub prop: Box<Expr>,
     pub computed: bool,
 }
 
-#[ast_node]
+#[ast_node("ConditionalExpression")]
 pub struct CondExpr {
     pub span: Span,
     pub test: Box<Expr>,
+    #[serde(rename = "consequent")]
     pub cons: Box<Expr>,
+    #[serde(rename = "alternate")]
     pub alt: Box<Expr>,
 }
 
-#[ast_node]
+#[ast_node("CallExpression")]
 pub struct CallExpr {
     pub span: Span,
     pub callee: ExprOrSuper,
+    #[serde(rename = "arguments")]
     pub args: Vec<ExprOrSpread>,
 }
-#[ast_node]
+
+/// `expr` is the member or call expression right after `?.`, like `a?.b`
+/// of `a?.b.c`.
+#[ast_node("OptionalChainingExpression")]
+pub struct OptChainExpr {
+    pub span: Span,
+    #[serde(rename = "expression")]
+    pub expr: Box<Expr>,
+}
+
+#[ast_node("NewExpression")]
 pub struct NewExpr {
     pub span: Span,
     pub callee: Box<Expr>,
+    #[serde(rename = "arguments")]
     pub args: Option<(Vec<ExprOrSpread>)>,
 }
-#[ast_node]
+#[ast_node("SequenceExpression")]
 pub struct SeqExpr {
     /// TODO: Calculate
     pub span: Span,
+    #[serde(rename = "expressions")]
     pub exprs: Vec<(Box<Expr>)>,
 }
 
-#[ast_node]
+#[ast_node("ArrowFunctionExpression")]
 pub struct ArrowExpr {
     pub span: Span,
     pub params: Vec<Pat>,
     pub body: BlockStmtOrExpr,
     pub generator_token: Option<Span>,
     pub async_token: Option<Span>,
+
+    /// typescript only
+    #[serde(rename = "typeParameters")]
+    pub type_params: Option<TsTypeParamDecl>,
+    /// typescript only
+    pub return_type: Option<TsTypeAnn>,
 }
 
-#[ast_node]
+#[ast_node("YieldExpression")]
 pub struct YieldExpr {
     pub span: Span,
+    #[serde(rename = "argument")]
     pub arg: Option<(Box<Expr>)>,
     pub delegate: bool,
 }
-#[ast_node]
+#[ast_node("MetaProperty")]
 pub struct MetaPropExpr {
     #[span(lo)]
     pub meta: Ident,
     #[span(hi)]
+    #[serde(rename = "property")]
     pub prop: Ident,
 }
-#[ast_node]
+#[ast_node("AwaitExpression")]
 pub struct AwaitExpr {
     pub span: Span,
+    #[serde(rename = "argument")]
     pub arg: Box<Expr>,
 }
 
-#[ast_node]
+#[ast_node("TemplateLiteral")]
 pub struct TplLit {
     pub span: Span,
     pub tag: Option<(Box<Expr>)>,
+    #[serde(rename = "expressions")]
     pub exprs: Vec<(Box<Expr>)>,
     pub quasis: Vec<TplElement>,
 }
 
-#[ast_node]
+#[ast_node("TemplateElement")]
 pub struct TplElement {
     pub span: Span,
     pub tail: bool,
+    /// Text with escapes processed. This is `None` if the text contains an
+    /// invalid escape, which is allowed only in tagged templates.
     pub cooked: Option<String>,
+    /// Source text, with line breaks normalized to `\n`.
     pub raw: String,
 }
 
-#[ast_node]
+#[ast_node("ParenthesizedExpression")]
 pub struct ParenExpr {
     pub span: Span,
+    #[serde(rename = "expression")]
     pub expr: Box<Expr>,
 }
 
 #[ast_node]
 #[allow(variant_size_differences)]
 pub enum ExprOrSuper {
-    Super(Span),
+    Super(#[serde(with = "::serde_impls::super_")] Span),
+    /// `import` of `import(src)`, which can only be a callee.
+    Import(#[serde(with = "::serde_impls::import")] Span),
     Expr(Box<Expr>),
 }
 
-#[derive(Fold, Clone, Debug, PartialEq)]
+#[derive(Fold, EqIgnoreSpan, HashIgnoreSpan, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct ExprOrSpread {
+    #[cfg_attr(feature = "serde", serde(with = "::serde_impls::opt_span"))]
     pub spread: Option<Span>,
+    #[cfg_attr(feature = "serde", serde(rename = "expression"))]
     pub expr: Box<Expr>,
 }
 impl Spanned for ExprOrSpread {
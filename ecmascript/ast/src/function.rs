@@ -1,17 +1,35 @@
-use super::{BlockStmt, Pat};
+use super::{BlockStmt, Decorator, Pat, TsTypeAnn, TsTypeParamDecl};
 use swc_common::{ast_node, Span};
 
 /// Common parts of function and method.
 #[ast_node]
 pub struct Function {
-    pub params: Vec<Pat>,
+    pub params: Vec<Param>,
     pub span: Span,
 
-    pub body: BlockStmt,
+    /// `None` for typescript declarations without a body, e.g.
+    /// `declare function foo(): void;`
+    pub body: Option<BlockStmt>,
 
     /// `Some` if it's a generator.
     pub generator_token: Option<Span>,
 
     /// `Some` if it's an async function.
     pub async_token: Option<Span>,
+
+    /// typescript only
+    #[serde(rename = "typeParameters")]
+    pub type_params: Option<TsTypeParamDecl>,
+    /// typescript only
+    pub return_type: Option<TsTypeAnn>,
+}
+
+/// A parameter of a function or a method.
+#[ast_node("Parameter")]
+pub struct Param {
+    pub span: Span,
+    /// `@Inject()` in `constructor(@Inject() a) {}`. Only legacy decorators
+    /// can decorate parameters.
+    pub decorators: Vec<Decorator>,
+    pub pat: Pat,
 }
@@ -0,0 +1,170 @@
+use super::{Expr, Ident, Lit, SpreadElement};
+use swc_atoms::JsWord;
+use swc_common::{ast_node, Span};
+
+/// Used for `obj` property of `JSXMemberExpr`.
+#[ast_node]
+#[allow(variant_size_differences)]
+pub enum JSXObject {
+    JSXMemberExpr(Box<JSXMemberExpr>),
+    Ident(Ident),
+}
+
+/// `Foo.Bar` in `<Foo.Bar />`
+#[ast_node("JSXMemberExpression")]
+pub struct JSXMemberExpr {
+    #[span(lo)]
+    #[serde(rename = "object")]
+    pub obj: JSXObject,
+    #[span(hi)]
+    #[serde(rename = "property")]
+    pub prop: Ident,
+}
+
+/// XML-based namespace syntax, e.g. `svg:rect` in `<svg:rect />`.
+#[ast_node]
+pub struct JSXNamespacedName {
+    #[span(lo)]
+    #[serde(rename = "namespace")]
+    pub ns: Ident,
+    #[span(hi)]
+    pub name: Ident,
+}
+
+/// `{}` or `{/* comment */}`
+#[ast_node("JSXEmptyExpression")]
+#[derive(Copy)]
+pub struct JSXEmptyExpr {
+    pub span: Span,
+}
+
+#[ast_node("JSXExpressionContainer")]
+pub struct JSXExprContainer {
+    pub span: Span,
+    #[serde(rename = "expression")]
+    pub expr: JSXExpr,
+}
+
+#[ast_node]
+#[allow(variant_size_differences)]
+pub enum JSXExpr {
+    JSXEmptyExpr(JSXEmptyExpr),
+    Expr(Box<Expr>),
+}
+
+/// `{...children}` in children of an element.
+#[ast_node]
+pub struct JSXSpreadChild {
+    pub span: Span,
+    #[serde(rename = "expression")]
+    pub expr: Box<Expr>,
+}
+
+#[ast_node]
+pub enum JSXElementName {
+    Ident(Ident),
+    JSXMemberExpr(JSXMemberExpr),
+    JSXNamespacedName(JSXNamespacedName),
+}
+
+#[ast_node]
+pub struct JSXOpeningElement {
+    pub name: JSXElementName,
+    pub span: Span,
+    #[serde(rename = "attributes")]
+    pub attrs: Vec<JSXAttrOrSpread>,
+    /// `<a />`
+    pub self_closing: bool,
+}
+
+#[ast_node]
+#[allow(variant_size_differences)]
+pub enum JSXAttrOrSpread {
+    JSXAttr(Box<JSXAttr>),
+    /// `{...props}`
+    SpreadElement(SpreadElement),
+}
+
+#[ast_node]
+pub struct JSXClosingElement {
+    pub span: Span,
+    pub name: JSXElementName,
+}
+
+#[ast_node("JSXAttribute")]
+pub struct JSXAttr {
+    pub span: Span,
+    pub name: JSXAttrName,
+    /// `None` for `<input disabled />`.
+    pub value: Option<JSXAttrValue>,
+}
+
+#[ast_node]
+pub enum JSXAttrName {
+    Ident(Ident),
+    JSXNamespacedName(JSXNamespacedName),
+}
+
+#[ast_node]
+#[allow(variant_size_differences)]
+pub enum JSXAttrValue {
+    /// String literal. Escapes are not allowed, but entities are decoded.
+    Lit(Lit),
+    JSXExprContainer(JSXExprContainer),
+    JSXElement(Box<JSXElement>),
+    JSXFragment(JSXFragment),
+}
+
+#[ast_node]
+pub struct JSXText {
+    pub span: Span,
+    /// Text with entities decoded.
+    pub value: JsWord,
+    pub raw: JsWord,
+}
+
+#[ast_node]
+pub struct JSXElement {
+    pub span: Span,
+    #[serde(rename = "openingElement")]
+    pub opening: JSXOpeningElement,
+    pub children: Vec<JSXElementChild>,
+    /// `None` for self-closing elements.
+    #[serde(rename = "closingElement")]
+    pub closing: Option<JSXClosingElement>,
+}
+
+#[ast_node]
+#[allow(variant_size_differences)]
+pub enum JSXElementChild {
+    JSXText(JSXText),
+    JSXExprContainer(JSXExprContainer),
+    JSXSpreadChild(JSXSpreadChild),
+    JSXElement(Box<JSXElement>),
+    JSXFragment(JSXFragment),
+}
+
+/// `<></>`
+#[ast_node]
+pub struct JSXFragment {
+    pub span: Span,
+    #[serde(rename = "openingFragment")]
+    pub opening: JSXOpeningFragment,
+    pub children: Vec<JSXElementChild>,
+    #[serde(rename = "closingFragment")]
+    pub closing: JSXClosingFragment,
+}
+
+/// `<>`
+#[ast_node]
+#[derive(Copy)]
+pub struct JSXOpeningFragment {
+    pub span: Span,
+}
+
+/// `</>`
+#[ast_node]
+#[derive(Copy)]
+pub struct JSXClosingFragment {
+    pub span: Span,
+}
@@ -10,47 +10,79 @@
 
 #[macro_use]
 extern crate enum_kind;
+#[cfg(feature = "serde")]
+extern crate serde;
 #[macro_use]
 extern crate string_enum;
 extern crate swc_atoms;
+#[cfg_attr(feature = "stable-fold", macro_use(define_fold))]
 extern crate swc_common;
 
 pub use self::{
-    class::{Class, ClassMethod, ClassMethodKind},
+    class::{
+        Class, ClassMember, ClassMethod, ClassMethodKind, ClassProp, Decorator, PrivateMethod,
+        PrivateName, PrivateProp, StaticBlock,
+    },
     decl::{ClassDecl, Decl, FnDecl, VarDecl, VarDeclKind, VarDeclarator},
     expr::{
         ArrayLit, ArrowExpr, AssignExpr, AwaitExpr, BinExpr, BlockStmtOrExpr, CallExpr, ClassExpr,
         CondExpr, Expr, ExprOrSpread, ExprOrSuper, FnExpr, MemberExpr, MetaPropExpr, NewExpr,
-        ObjectLit, ParenExpr, PatOrExpr, PropOrSpread, SeqExpr, SpreadElement, ThisExpr,
-        TplElement, TplLit, UnaryExpr, UpdateExpr, YieldExpr,
+        ObjectLit, OptChainExpr, ParenExpr, PatOrExpr, PropOrSpread, SeqExpr, SpreadElement,
+        ThisExpr, TplElement, TplLit, UnaryExpr, UpdateExpr, YieldExpr,
+    },
+    function::{Function, Param},
+    jsx::{
+        JSXAttr, JSXAttrName, JSXAttrOrSpread, JSXAttrValue, JSXClosingElement,
+        JSXClosingFragment, JSXElement, JSXElementChild, JSXElementName, JSXEmptyExpr, JSXExpr,
+        JSXExprContainer, JSXFragment, JSXMemberExpr, JSXNamespacedName, JSXObject,
+        JSXOpeningElement, JSXOpeningFragment, JSXSpreadChild, JSXText,
     },
-    function::Function,
     keywords::IdentExt,
-    lit::{Bool, Lit, Null, Number, Regex, RegexFlags, Str},
-    module::{Module, ModuleItem},
+    lit::{BigInt, Bool, Lit, Null, Number, Regex, RegexFlags, Str},
+    module::{Module, ModuleItem, Program, Script},
     module_decl::{
-        ExportAll, ExportDefaultDecl, ExportSpecifier, ImportDecl, ImportDefault, ImportSpecific,
-        ImportSpecifier, ImportStarAs, ModuleDecl, NamedExport,
+        DefaultDecl, ExportAll, ExportDecl, ExportDefaultDecl, ExportDefaultExpr, ExportSpecifier,
+        ImportDecl, ImportDefault, ImportSpecific, ImportSpecifier, ImportStarAs, ModuleDecl,
+        NamedExport,
     },
     operators::{AssignOp, BinaryOp, UnaryOp, UpdateOp},
     pat::{
         ArrayPat, AssignPat, AssignPatProp, KeyValuePatProp, ObjectPat, ObjectPatProp, Pat, RestPat,
     },
-    prop::{AssignProp, GetterProp, KeyValueProp, MethodProp, Prop, PropName, SetterProp},
+    prop::{
+        AssignProp, ComputedPropName, GetterProp, KeyValueProp, MethodProp, Prop, PropName,
+        SetterProp,
+    },
     stmt::{
         BlockStmt, BreakStmt, CatchClause, ContinueStmt, DebuggerStmt, DoWhileStmt, EmptyStmt,
-        ForInStmt, ForOfStmt, ForStmt, IfStmt, LabeledStmt, ReturnStmt, Stmt, SwitchCase,
-        SwitchStmt, ThrowStmt, TryStmt, VarDeclOrExpr, VarDeclOrPat, WhileStmt, WithStmt,
+        ExprStmt, ForInStmt, ForOfStmt, ForStmt, IfStmt, LabeledStmt, ReturnStmt, Stmt,
+        SwitchCase, SwitchStmt, ThrowStmt, TryStmt, VarDeclOrExpr, VarDeclOrPat, WhileStmt,
+        WithStmt,
+    },
+    target::EsVersion,
+    typescript::{
+        TsArrayType, TsAsExpr, TsCallSignatureDecl, TsConditionalType, TsConstructSignatureDecl,
+        TsConstructorType, TsEntityName, TsEnumDecl, TsEnumMember, TsEnumMemberId,
+        TsExprWithTypeArgs, TsFnType, TsIndexSignature, TsIndexedAccessType, TsInterfaceBody,
+        TsInterfaceDecl, TsIntersectionType, TsKeywordType, TsKeywordTypeKind, TsLit, TsLitType,
+        TsMappedType, TsMethodSignature, TsModuleBlock, TsModuleDecl, TsModuleName,
+        TsParenthesizedType, TsPropertySignature, TsQualifiedName, TsThisType, TsTupleType,
+        TsType, TsTypeAliasDecl, TsTypeAnn, TsTypeElement, TsTypeLit, TsTypeOperator,
+        TsTypeOperatorOp, TsTypeParam, TsTypeParamDecl, TsTypeParamInstantiation, TsTypeQuery,
+        TsTypeRef, TsTypedPat, TsUnionType,
     },
 };
+#[cfg(feature = "stable-fold")]
+pub use self::stable_fold::StableFold;
 use std::fmt::{self, Debug, Display, Formatter};
 use swc_atoms::JsWord;
-use swc_common::{Fold, Span, Spanned};
+use swc_common::{EqIgnoreSpan, Fold, HashIgnoreSpan, Span, Spanned};
 
 mod class;
 mod decl;
 mod expr;
 mod function;
+mod jsx;
 mod keywords;
 mod lit;
 mod macros;
@@ -59,10 +91,20 @@ mod module_decl;
 mod operators;
 mod pat;
 mod prop;
+#[cfg(feature = "serde")]
+mod serde_impls;
+#[cfg(feature = "stable-fold")]
+mod stable_fold;
 mod stmt;
+mod target;
+mod typescript;
 
 /// Ident with span.
-#[derive(Spanned, Fold, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+///
+/// This is serialized as an ESTree `Identifier`, with `sym` as `name`.
+#[derive(
+    Spanned, Fold, EqIgnoreSpan, HashIgnoreSpan, Clone, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
 pub struct Ident {
     pub span: Span,
     #[fold(ignore)]
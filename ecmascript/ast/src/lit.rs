@@ -9,44 +9,63 @@ pub enum Lit {
     Bool(Bool),
     Null(Null),
     Num(Number),
+    BigInt(BigInt),
     Regex(Regex),
 }
 
-#[ast_node]
+#[ast_node("StringLiteral")]
 pub struct Str {
     pub span: Span,
     pub value: JsWord,
     /// This includes line escape.
     pub has_escape: bool,
+    /// Source text including quotes, like `'\u0041'`.
+    ///
+    /// This is `None` for generated literals.
+    pub raw: Option<JsWord>,
 }
 
-#[ast_node]
+#[ast_node("BooleanLiteral")]
 #[derive(Copy)]
 pub struct Bool {
     pub span: Span,
     pub value: bool,
 }
 
-#[ast_node]
+#[ast_node("NullLiteral")]
 #[derive(Copy)]
 pub struct Null {
     pub span: Span,
 }
 
-#[ast_node]
+#[ast_node("RegExpLiteral")]
 pub struct Regex {
     pub span: Span,
+    #[serde(rename = "pattern")]
     pub exp: Str,
     pub flags: Option<RegexFlags>,
 }
 
 pub type RegexFlags = Str;
 
-#[ast_node]
-#[derive(Copy)]
+#[ast_node("NumericLiteral")]
 pub struct Number {
     pub span: Span,
     pub value: f64,
+    /// Source text like `0x1F` or `1_000`.
+    ///
+    /// This is `None` for generated literals.
+    pub raw: Option<JsWord>,
+}
+
+/// Bigint literal like `10n`.
+#[ast_node("BigIntLiteral")]
+pub struct BigInt {
+    pub span: Span,
+    /// Digits as written in the source, without `n`.
+    ///
+    /// The radix prefix is included, so `0xFFn` is stored as `0xFF`.
+    pub value: JsWord,
 }
 
 impl Display for Number {
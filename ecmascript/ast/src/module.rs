@@ -1,10 +1,50 @@
 use super::{ModuleDecl, Stmt};
+#[cfg(feature = "rayon")]
+use swc_common::{Fold, ParFoldWith};
+use swc_atoms::JsWord;
 use swc_common::{ast_node, Span};
 
+/// A module or a script, which are parsed differently.
+#[ast_node]
+pub enum Program {
+    Module(Module),
+    Script(Script),
+}
+
 #[ast_node]
 pub struct Module {
     pub span: Span,
     pub body: Vec<ModuleItem>,
+    /// Text after `#!` in the first line, like `/usr/bin/env node`.
+    pub shebang: Option<JsWord>,
+}
+
+/// Code which is not a module. It can't import or export, and it's strict
+/// only if it starts with `"use strict"`.
+#[ast_node]
+pub struct Script {
+    pub span: Span,
+    pub body: Vec<Stmt>,
+    /// Text after `#!` in the first line, like `/usr/bin/env node`.
+    pub shebang: Option<JsWord>,
+}
+
+/// Folds items of the module in parallel.
+///
+/// Note that this folds each item with `Fold<ModuleItem>`, so `f` should not
+/// depend on `Fold<Module>` or `Fold<Vec<ModuleItem>>`.
+#[cfg(feature = "rayon")]
+impl<F> ParFoldWith<F> for Module
+where
+    F: Fold<ModuleItem> + Clone + Send + Sync,
+{
+    fn par_fold_children(self, f: &F) -> Self {
+        Module {
+            span: self.span,
+            body: self.body.par_fold_children(f),
+            shebang: self.shebang,
+        }
+    }
 }
 
 #[ast_node]
@@ -5,42 +5,72 @@ use swc_common::ast_node;
 #[ast_node]
 pub enum ModuleDecl {
     Import(ImportDecl),
-    ExportDecl(Decl),
+    ExportDecl(ExportDecl),
     ExportNamed(NamedExport),
 
     ExportDefaultDecl(ExportDefaultDecl),
 
-    ExportDefaultExpr(Box<Expr>),
+    ExportDefaultExpr(ExportDefaultExpr),
     ExportAll(ExportAll),
 }
 
-#[ast_node]
+/// `export const a = 1`
+#[ast_node("ExportDeclaration")]
+pub struct ExportDecl {
+    /// Span including `export`.
+    pub span: Span,
+    #[serde(rename = "declaration")]
+    pub decl: Decl,
+}
+
+#[ast_node("ImportDeclaration")]
 pub struct ImportDecl {
     pub span: Span,
     pub specifiers: Vec<ImportSpecifier>,
 
+    #[serde(rename = "source")]
     pub src: Str,
 }
 
 /// `export * from 'mod'`
-#[ast_node]
+#[ast_node("ExportAllDeclaration")]
 pub struct ExportAll {
     pub span: Span,
+    #[serde(rename = "source")]
     pub src: Str,
 }
 
 /// `export { foo } from 'mod'`
 /// `export { foo as bar } from 'mod'`
-#[ast_node]
+#[ast_node("ExportNamedDeclaration")]
 pub struct NamedExport {
     pub span: Span,
     pub specifiers: Vec<ExportSpecifier>,
 
+    #[serde(rename = "source")]
     pub src: Option<Str>,
 }
 
+/// `export default function a() {}`
+#[ast_node("ExportDefaultDeclaration")]
+pub struct ExportDefaultDecl {
+    /// Span including `export default`.
+    pub span: Span,
+    #[serde(rename = "declaration")]
+    pub decl: DefaultDecl,
+}
+
+/// `export default a + b`
+#[ast_node("ExportDefaultExpression")]
+pub struct ExportDefaultExpr {
+    /// Span including `export default` and the semicolon.
+    pub span: Span,
+    #[serde(rename = "expression")]
+    pub expr: Box<Expr>,
+}
+
 #[ast_node]
-pub enum ExportDefaultDecl {
+pub enum DefaultDecl {
     Class(ClassExpr),
 
     Fn(FnExpr),
@@ -56,13 +86,13 @@ pub enum ImportSpecifier {
 }
 
 /// e.g. `import foo from 'mod.js'`
-#[ast_node]
+#[ast_node("ImportDefaultSpecifier")]
 pub struct ImportDefault {
     pub span: Span,
     pub local: Ident,
 }
 /// e.g. `import * as foo from 'mod.js'`.
-#[ast_node]
+#[ast_node("ImportNamespaceSpecifier")]
 pub struct ImportStarAs {
     pub span: Span,
     pub local: Ident,
@@ -70,7 +100,7 @@ pub struct ImportStarAs {
 /// e.g. local = foo, imported = None `import { foo } from 'mod.js'`
 /// e.g. local = bar, imported = Some(foo) for `import { foo as bar } from
 /// 'mod.js'`
-#[ast_node]
+#[ast_node("ImportSpecifier")]
 pub struct ImportSpecific {
     pub span: Span,
     pub local: Ident,
@@ -81,6 +111,7 @@ pub struct ImportSpecific {
 pub struct ExportSpecifier {
     pub span: Span,
     /// `foo` in `export { foo as bar }`
+    #[serde(rename = "local")]
     pub orig: Ident,
     /// `Some(bar)` in `export { foo as bar }`
     pub exported: Option<Ident>,
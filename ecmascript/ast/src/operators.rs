@@ -1,8 +1,21 @@
 use enum_kind::Kind;
 use string_enum::StringEnum;
-use swc_common::Fold;
+use swc_common::{EqIgnoreSpan, Fold, HashIgnoreSpan};
 
-#[derive(Kind, Fold, StringEnum, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
+#[derive(
+    Kind,
+    Fold,
+    EqIgnoreSpan,
+    HashIgnoreSpan,
+    StringEnum,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    PartialOrd,
+    Ord,
+    Hash,
+)]
 #[kind(function(precedence = "u8"))]
 pub enum BinaryOp {
     /// `==`
@@ -83,9 +96,25 @@ pub enum BinaryOp {
     /// `**`
     #[kind(precedence = "11")]
     Exp,
+
+    /// `??`
+    #[kind(precedence = "1")]
+    NullishCoalescing,
 }
 
-#[derive(Fold, StringEnum, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
+#[derive(
+    Fold,
+    EqIgnoreSpan,
+    HashIgnoreSpan,
+    StringEnum,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    PartialOrd,
+    Ord,
+    Hash,
+)]
 pub enum AssignOp {
     /// `=`
     Assign,
@@ -116,7 +145,19 @@ pub enum AssignOp {
     ExpAssign,
 }
 
-#[derive(Fold, StringEnum, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
+#[derive(
+    Fold,
+    EqIgnoreSpan,
+    HashIgnoreSpan,
+    StringEnum,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    PartialOrd,
+    Ord,
+    Hash,
+)]
 pub enum UpdateOp {
     /// `++`
     PlusPlus,
@@ -124,7 +165,19 @@ pub enum UpdateOp {
     MinusMinus,
 }
 
-#[derive(Fold, StringEnum, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
+#[derive(
+    Fold,
+    EqIgnoreSpan,
+    HashIgnoreSpan,
+    StringEnum,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    PartialOrd,
+    Ord,
+    Hash,
+)]
 pub enum UnaryOp {
     /// `-`
     Minus,
@@ -1,4 +1,4 @@
-use super::{Expr, Ident, PropName};
+use super::{Expr, Ident, PropName, TsTypedPat};
 use swc_common::{ast_node, Span};
 
 #[ast_node]
@@ -15,21 +15,26 @@ pub enum Pat {
 
     /// Only for for-in / for-of loops. This is *syntatically* valid.
     Expr(Box<Expr>),
+
+    /// typescript only
+    TsTyped(TsTypedPat),
 }
 
-#[ast_node]
+#[ast_node("ArrayPattern")]
 pub struct ArrayPat {
     pub span: Span,
+    #[serde(rename = "elements")]
     pub elems: Vec<(Option<Pat>)>,
 }
 
-#[ast_node]
+#[ast_node("ObjectPattern")]
 pub struct ObjectPat {
     pub span: Span,
+    #[serde(rename = "properties")]
     pub props: Vec<ObjectPatProp>,
 }
 
-#[ast_node]
+#[ast_node("AssignmentPattern")]
 pub struct AssignPat {
     pub span: Span,
     pub left: Box<Pat>,
@@ -37,11 +42,12 @@ pub struct AssignPat {
 }
 
 /// EsTree `RestElement`
-#[ast_node]
+#[ast_node("RestElement")]
 pub struct RestPat {
     #[span(lo)]
     pub dot3_token: Span,
     #[span(hi)]
+    #[serde(rename = "argument")]
     pub arg: Box<Pat>,
 }
 
@@ -53,7 +59,7 @@ pub enum ObjectPatProp {
 }
 
 /// `{key: value}`
-#[ast_node]
+#[ast_node("KeyValuePatternProperty")]
 pub struct KeyValuePatProp {
     #[span(lo)]
     pub key: PropName,
@@ -61,7 +67,7 @@ pub struct KeyValuePatProp {
     pub value: Box<Pat>,
 }
 /// `{key}` or `{key = value}`
-#[ast_node]
+#[ast_node("AssignmentPatternProperty")]
 pub struct AssignPatProp {
     pub span: Span,
     pub key: Ident,
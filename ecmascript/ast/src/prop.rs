@@ -15,7 +15,7 @@ pub enum Prop {
     Method(MethodProp),
 }
 
-#[ast_node]
+#[ast_node("KeyValueProperty")]
 pub struct KeyValueProp {
     #[span(lo)]
     pub key: PropName,
@@ -24,27 +24,27 @@ pub struct KeyValueProp {
     pub value: Box<Expr>,
 }
 
-#[ast_node]
+#[ast_node("AssignmentProperty")]
 pub struct AssignProp {
     #[span(lo)]
     pub key: Ident,
     #[span(hi)]
     pub value: Box<Expr>,
 }
-#[ast_node]
+#[ast_node("GetterProperty")]
 pub struct GetterProp {
     pub span: Span,
     pub key: PropName,
     pub body: BlockStmt,
 }
-#[ast_node]
+#[ast_node("SetterProperty")]
 pub struct SetterProp {
     pub span: Span,
     pub key: PropName,
     pub param: Pat,
     pub body: BlockStmt,
 }
-#[ast_node]
+#[ast_node("MethodProperty")]
 pub struct MethodProp {
     #[span(lo)]
     pub key: PropName,
@@ -59,5 +59,14 @@ pub enum PropName {
     Str(Str),
     /// Numeric literal.
     Num(Number),
-    Computed(Box<Expr>),
+    Computed(ComputedPropName),
+}
+
+/// `[a + b]` of `{ [a + b]: c }`
+#[ast_node("Computed")]
+pub struct ComputedPropName {
+    /// Span including the brackets.
+    pub span: Span,
+    #[serde(rename = "expression")]
+    pub expr: Box<Expr>,
 }
@@ -0,0 +1,230 @@
+//! Serialization of nodes, which is enabled by the `serde` feature.
+//!
+//! Nodes use names and fields of ESTree where they have the same shape, and
+//! babel or typescript-estree names otherwise. Spans are serialized as
+//! `{ "start": lo, "end": hi }`, and syntax contexts are dropped.
+//!
+//! Enums don't have tags of their own; each node has a `type` field. Variants
+//! of `ExprOrSuper` which only have a span are wrapped in objects to make
+//! them distinguishable, like `ExprOrSuper::Super` which is a `Super`.
+//!
+//! `PatOrExpr::Expr` of an identifier, like `a` of `a += 1`, is deserialized
+//! as `PatOrExpr::Pat`.
+use super::{
+    AssignOp, BinaryOp, Ident, TsKeywordTypeKind, TsTypeOperatorOp, UnaryOp, UpdateOp, VarDeclKind,
+};
+use serde::{
+    de::{self, IgnoredAny, MapAccess, Unexpected, Visitor},
+    ser::SerializeStruct,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{fmt, marker::PhantomData};
+use swc_atoms::JsWord;
+use swc_common::{BytePos, Span, SyntaxContext};
+
+#[derive(Serialize, Deserialize)]
+struct SpanRepr {
+    start: u32,
+    end: u32,
+}
+
+impl From<Span> for SpanRepr {
+    fn from(span: Span) -> Self {
+        SpanRepr {
+            start: span.lo().0,
+            end: span.hi().0,
+        }
+    }
+}
+
+impl From<SpanRepr> for Span {
+    fn from(span: SpanRepr) -> Self {
+        Span::new(
+            BytePos(span.start),
+            BytePos(span.end),
+            SyntaxContext::empty(),
+        )
+    }
+}
+
+/// Fields of type `Span`.
+pub(crate) mod span {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(span: &Span, s: S) -> Result<S::Ok, S::Error> {
+        SpanRepr::from(*span).serialize(s)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Span, D::Error> {
+        SpanRepr::deserialize(d).map(Span::from)
+    }
+}
+
+/// Fields of type `Option<Span>`.
+pub(crate) mod opt_span {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(span: &Option<Span>, s: S) -> Result<S::Ok, S::Error> {
+        span.map(SpanRepr::from).serialize(s)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Option<Span>, D::Error> {
+        Option::<SpanRepr>::deserialize(d).map(|span| span.map(Span::from))
+    }
+}
+
+fn check_type<E: de::Error>(ty: &str, expected: &'static str) -> Result<(), E> {
+    if ty == expected {
+        Ok(())
+    } else {
+        Err(E::invalid_value(Unexpected::Str(ty), &expected))
+    }
+}
+
+/// Serializes `{ "type": ty, key: value }`.
+fn serialize_wrapped<S, T>(
+    s: S,
+    ty: &'static str,
+    key: &'static str,
+    value: &T,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Serialize,
+{
+    let mut s = s.serialize_struct(ty, 2)?;
+    s.serialize_field("type", ty)?;
+    s.serialize_field(key, value)?;
+    s.end()
+}
+
+fn deserialize_wrapped<'de, D, T>(d: D, ty: &'static str, key: &'static str) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    struct WrappedVisitor<T> {
+        ty: &'static str,
+        key: &'static str,
+        value: PhantomData<T>,
+    }
+
+    impl<'de, T: Deserialize<'de>> Visitor<'de> for WrappedVisitor<T> {
+        type Value = T;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "{}", self.ty)
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<T, A::Error> {
+            let mut has_type = false;
+            let mut value = None;
+            while let Some(key) = map.next_key::<String>()? {
+                if key == "type" {
+                    check_type::<A::Error>(&map.next_value::<String>()?, self.ty)?;
+                    has_type = true;
+                } else if key == self.key {
+                    value = Some(map.next_value()?);
+                } else {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+
+            if !has_type {
+                return Err(de::Error::missing_field("type"));
+            }
+            value.ok_or_else(|| de::Error::missing_field(self.key))
+        }
+    }
+
+    d.deserialize_map(WrappedVisitor {
+        ty,
+        key,
+        value: PhantomData,
+    })
+}
+
+/// Variants of `Span`, which are serialized as `{ "type": $ty, "span": .. }`.
+macro_rules! span_wrapper {
+    ($(#[$attr:meta])* $name:ident = $ty:expr) => {
+        $(#[$attr])*
+        pub(crate) mod $name {
+            use super::*;
+
+            pub(crate) fn serialize<S: Serializer>(span: &Span, s: S) -> Result<S::Ok, S::Error> {
+                serialize_wrapped(s, $ty, "span", &SpanRepr::from(*span))
+            }
+
+            pub(crate) fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Span, D::Error> {
+                deserialize_wrapped(d, $ty, "span").map(|span: SpanRepr| span.into())
+            }
+        }
+    };
+}
+
+span_wrapper!(
+    /// `ExprOrSuper::Super`
+    super_ = "Super"
+);
+span_wrapper!(
+    /// `ExprOrSuper::Import`
+    import = "Import"
+);
+
+impl Serialize for Ident {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        let mut s = s.serialize_struct("Identifier", 3)?;
+        s.serialize_field("type", "Identifier")?;
+        s.serialize_field("span", &SpanRepr::from(self.span))?;
+        s.serialize_field("name", &self.sym)?;
+        s.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Ident {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct IdentRepr {
+            #[serde(rename = "type")]
+            ty: String,
+            span: SpanRepr,
+            name: JsWord,
+        }
+
+        let ident = IdentRepr::deserialize(d)?;
+        check_type::<D::Error>(&ident.ty, "Identifier")?;
+        Ok(Ident::new(ident.name, ident.span.into()))
+    }
+}
+
+/// Enums which are serialized as their `as_str()`, like `"+"`.
+macro_rules! string_enum {
+    ($($T:ident),*) => {
+        $(
+            impl Serialize for $T {
+                fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+                    s.serialize_str(self.as_str())
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $T {
+                fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+                    let s = String::deserialize(d)?;
+                    s.parse().map_err(|()| {
+                        de::Error::invalid_value(Unexpected::Str(&s), &stringify!($T))
+                    })
+                }
+            }
+        )*
+    };
+}
+
+string_enum!(
+    BinaryOp,
+    AssignOp,
+    UpdateOp,
+    UnaryOp,
+    VarDeclKind,
+    TsKeywordTypeKind,
+    TsTypeOperatorOp
+);
@@ -0,0 +1,192 @@
+use super::*;
+
+define_fold! {
+    /// Folder with a method for each node type, which does not require
+    /// `#![feature(specialization)]` in crates implementing it.
+    ///
+    /// Each method folds fields of the node by default. Use
+    /// `swc_common::FoldNode` to fold a node with this, like
+    /// `module.fold_node(&mut folder)`.
+    pub trait StableFold {
+        fn fold_array_lit(ArrayLit);
+        fn fold_array_pat(ArrayPat);
+        fn fold_arrow_expr(ArrowExpr);
+        fn fold_assign_expr(AssignExpr);
+        fn fold_assign_op(AssignOp);
+        fn fold_assign_pat(AssignPat);
+        fn fold_assign_pat_prop(AssignPatProp);
+        fn fold_assign_prop(AssignProp);
+        fn fold_await_expr(AwaitExpr);
+        fn fold_big_int(BigInt);
+        fn fold_bin_expr(BinExpr);
+        fn fold_binary_op(BinaryOp);
+        fn fold_block_stmt(BlockStmt);
+        fn fold_block_stmt_or_expr(BlockStmtOrExpr);
+        fn fold_bool(Bool);
+        fn fold_break_stmt(BreakStmt);
+        fn fold_call_expr(CallExpr);
+        fn fold_catch_clause(CatchClause);
+        fn fold_class(Class);
+        fn fold_class_decl(ClassDecl);
+        fn fold_class_expr(ClassExpr);
+        fn fold_class_member(ClassMember);
+        fn fold_class_method(ClassMethod);
+        fn fold_class_method_kind(ClassMethodKind);
+        fn fold_class_prop(ClassProp);
+        fn fold_computed_prop_name(ComputedPropName);
+        fn fold_cond_expr(CondExpr);
+        fn fold_continue_stmt(ContinueStmt);
+        fn fold_debugger_stmt(DebuggerStmt);
+        fn fold_decl(Decl);
+        fn fold_decorator(Decorator);
+        fn fold_default_decl(DefaultDecl);
+        fn fold_do_while_stmt(DoWhileStmt);
+        fn fold_empty_stmt(EmptyStmt);
+        fn fold_export_all(ExportAll);
+        fn fold_export_decl(ExportDecl);
+        fn fold_export_default_decl(ExportDefaultDecl);
+        fn fold_export_default_expr(ExportDefaultExpr);
+        fn fold_export_specifier(ExportSpecifier);
+        fn fold_expr(Expr);
+        fn fold_expr_or_spread(ExprOrSpread);
+        fn fold_expr_or_super(ExprOrSuper);
+        fn fold_expr_stmt(ExprStmt);
+        fn fold_fn_decl(FnDecl);
+        fn fold_fn_expr(FnExpr);
+        fn fold_for_in_stmt(ForInStmt);
+        fn fold_for_of_stmt(ForOfStmt);
+        fn fold_for_stmt(ForStmt);
+        fn fold_function(Function);
+        fn fold_getter_prop(GetterProp);
+        fn fold_ident(Ident);
+        fn fold_if_stmt(IfStmt);
+        fn fold_import_decl(ImportDecl);
+        fn fold_import_default(ImportDefault);
+        fn fold_import_specific(ImportSpecific);
+        fn fold_import_specifier(ImportSpecifier);
+        fn fold_import_star_as(ImportStarAs);
+        fn fold_jsx_attr(JSXAttr);
+        fn fold_jsx_attr_name(JSXAttrName);
+        fn fold_jsx_attr_or_spread(JSXAttrOrSpread);
+        fn fold_jsx_attr_value(JSXAttrValue);
+        fn fold_jsx_closing_element(JSXClosingElement);
+        fn fold_jsx_closing_fragment(JSXClosingFragment);
+        fn fold_jsx_element(JSXElement);
+        fn fold_jsx_element_child(JSXElementChild);
+        fn fold_jsx_element_name(JSXElementName);
+        fn fold_jsx_empty_expr(JSXEmptyExpr);
+        fn fold_jsx_expr(JSXExpr);
+        fn fold_jsx_expr_container(JSXExprContainer);
+        fn fold_jsx_fragment(JSXFragment);
+        fn fold_jsx_member_expr(JSXMemberExpr);
+        fn fold_jsx_namespaced_name(JSXNamespacedName);
+        fn fold_jsx_object(JSXObject);
+        fn fold_jsx_opening_element(JSXOpeningElement);
+        fn fold_jsx_opening_fragment(JSXOpeningFragment);
+        fn fold_jsx_spread_child(JSXSpreadChild);
+        fn fold_jsx_text(JSXText);
+        fn fold_key_value_pat_prop(KeyValuePatProp);
+        fn fold_key_value_prop(KeyValueProp);
+        fn fold_labeled_stmt(LabeledStmt);
+        fn fold_lit(Lit);
+        fn fold_member_expr(MemberExpr);
+        fn fold_meta_prop_expr(MetaPropExpr);
+        fn fold_method_prop(MethodProp);
+        fn fold_module(Module);
+        fn fold_module_decl(ModuleDecl);
+        fn fold_module_item(ModuleItem);
+        fn fold_named_export(NamedExport);
+        fn fold_new_expr(NewExpr);
+        fn fold_null(Null);
+        fn fold_number(Number);
+        fn fold_object_lit(ObjectLit);
+        fn fold_object_pat(ObjectPat);
+        fn fold_object_pat_prop(ObjectPatProp);
+        fn fold_opt_chain_expr(OptChainExpr);
+        fn fold_param(Param);
+        fn fold_paren_expr(ParenExpr);
+        fn fold_pat(Pat);
+        fn fold_pat_or_expr(PatOrExpr);
+        fn fold_private_method(PrivateMethod);
+        fn fold_private_name(PrivateName);
+        fn fold_private_prop(PrivateProp);
+        fn fold_program(Program);
+        fn fold_prop(Prop);
+        fn fold_prop_name(PropName);
+        fn fold_prop_or_spread(PropOrSpread);
+        fn fold_regex(Regex);
+        fn fold_rest_pat(RestPat);
+        fn fold_return_stmt(ReturnStmt);
+        fn fold_script(Script);
+        fn fold_seq_expr(SeqExpr);
+        fn fold_setter_prop(SetterProp);
+        fn fold_spread_element(SpreadElement);
+        fn fold_static_block(StaticBlock);
+        fn fold_stmt(Stmt);
+        fn fold_str(Str);
+        fn fold_switch_case(SwitchCase);
+        fn fold_switch_stmt(SwitchStmt);
+        fn fold_this_expr(ThisExpr);
+        fn fold_throw_stmt(ThrowStmt);
+        fn fold_tpl_element(TplElement);
+        fn fold_tpl_lit(TplLit);
+        fn fold_try_stmt(TryStmt);
+        fn fold_ts_array_type(TsArrayType);
+        fn fold_ts_as_expr(TsAsExpr);
+        fn fold_ts_call_signature_decl(TsCallSignatureDecl);
+        fn fold_ts_conditional_type(TsConditionalType);
+        fn fold_ts_construct_signature_decl(TsConstructSignatureDecl);
+        fn fold_ts_constructor_type(TsConstructorType);
+        fn fold_ts_entity_name(TsEntityName);
+        fn fold_ts_enum_decl(TsEnumDecl);
+        fn fold_ts_enum_member(TsEnumMember);
+        fn fold_ts_enum_member_id(TsEnumMemberId);
+        fn fold_ts_expr_with_type_args(TsExprWithTypeArgs);
+        fn fold_ts_fn_type(TsFnType);
+        fn fold_ts_index_signature(TsIndexSignature);
+        fn fold_ts_indexed_access_type(TsIndexedAccessType);
+        fn fold_ts_interface_body(TsInterfaceBody);
+        fn fold_ts_interface_decl(TsInterfaceDecl);
+        fn fold_ts_intersection_type(TsIntersectionType);
+        fn fold_ts_keyword_type(TsKeywordType);
+        fn fold_ts_keyword_type_kind(TsKeywordTypeKind);
+        fn fold_ts_lit(TsLit);
+        fn fold_ts_lit_type(TsLitType);
+        fn fold_ts_mapped_type(TsMappedType);
+        fn fold_ts_method_signature(TsMethodSignature);
+        fn fold_ts_module_block(TsModuleBlock);
+        fn fold_ts_module_decl(TsModuleDecl);
+        fn fold_ts_module_name(TsModuleName);
+        fn fold_ts_parenthesized_type(TsParenthesizedType);
+        fn fold_ts_property_signature(TsPropertySignature);
+        fn fold_ts_qualified_name(TsQualifiedName);
+        fn fold_ts_this_type(TsThisType);
+        fn fold_ts_tuple_type(TsTupleType);
+        fn fold_ts_type(TsType);
+        fn fold_ts_type_alias_decl(TsTypeAliasDecl);
+        fn fold_ts_type_ann(TsTypeAnn);
+        fn fold_ts_type_element(TsTypeElement);
+        fn fold_ts_type_lit(TsTypeLit);
+        fn fold_ts_type_operator(TsTypeOperator);
+        fn fold_ts_type_operator_op(TsTypeOperatorOp);
+        fn fold_ts_type_param(TsTypeParam);
+        fn fold_ts_type_param_decl(TsTypeParamDecl);
+        fn fold_ts_type_param_instantiation(TsTypeParamInstantiation);
+        fn fold_ts_type_query(TsTypeQuery);
+        fn fold_ts_type_ref(TsTypeRef);
+        fn fold_ts_typed_pat(TsTypedPat);
+        fn fold_ts_union_type(TsUnionType);
+        fn fold_unary_expr(UnaryExpr);
+        fn fold_unary_op(UnaryOp);
+        fn fold_update_expr(UpdateExpr);
+        fn fold_update_op(UpdateOp);
+        fn fold_var_decl(VarDecl);
+        fn fold_var_decl_kind(VarDeclKind);
+        fn fold_var_decl_or_expr(VarDeclOrExpr);
+        fn fold_var_decl_or_pat(VarDeclOrPat);
+        fn fold_var_declarator(VarDeclarator);
+        fn fold_while_stmt(WhileStmt);
+        fn fold_with_stmt(WithStmt);
+        fn fold_yield_expr(YieldExpr);
+    }
+}
@@ -3,17 +3,18 @@ use swc_common::Span;
 use swc_common::ast_node;
 
 /// Use when only block statements are allowed.
-#[ast_node]
+#[ast_node("BlockStatement")]
 pub struct BlockStmt {
     /// Span including the braces.
     pub span: Span,
 
+    #[serde(rename = "body")]
     pub stmts: Vec<Stmt>,
 }
 
 #[ast_node]
 pub enum Stmt {
-    Expr(Box<Expr>),
+    Expr(ExprStmt),
 
     Block(BlockStmt),
 
@@ -53,86 +54,99 @@ pub enum Stmt {
     Decl(Decl),
 }
 
-#[ast_node]
+#[ast_node("ExpressionStatement")]
+pub struct ExprStmt {
+    /// Span including the semicolon.
+    pub span: Span,
+    #[serde(rename = "expression")]
+    pub expr: Box<Expr>,
+}
+
+#[ast_node("EmptyStatement")]
 #[derive(Copy)]
 pub struct EmptyStmt {
     /// Span of semicolon.
     pub span: Span,
 }
 
-#[ast_node]
+#[ast_node("DebuggerStatement")]
 #[derive(Copy)]
 pub struct DebuggerStmt {
     pub span: Span,
 }
 
-#[ast_node]
+#[ast_node("WithStatement")]
 pub struct WithStmt {
     pub span: Span,
+    #[serde(rename = "object")]
     pub obj: Box<Expr>,
     pub body: Box<Stmt>,
 }
 
-#[ast_node]
+#[ast_node("ReturnStatement")]
 pub struct ReturnStmt {
     pub span: Span,
+    #[serde(rename = "argument")]
     pub arg: Option<(Box<Expr>)>,
 }
 
-#[ast_node]
+#[ast_node("LabeledStatement")]
 pub struct LabeledStmt {
     pub span: Span,
     pub label: Ident,
     pub body: Box<Stmt>,
 }
-#[ast_node]
+#[ast_node("BreakStatement")]
 pub struct BreakStmt {
     pub span: Span,
     pub label: Option<Ident>,
 }
-#[ast_node]
+#[ast_node("ContinueStatement")]
 pub struct ContinueStmt {
     pub span: Span,
     pub label: Option<Ident>,
 }
-#[ast_node]
+#[ast_node("IfStatement")]
 pub struct IfStmt {
     pub span: Span,
     pub test: Box<Expr>,
+    #[serde(rename = "consequent")]
     pub cons: Box<Stmt>,
+    #[serde(rename = "alternate")]
     pub alt: Option<(Box<Stmt>)>,
 }
-#[ast_node]
+#[ast_node("SwitchStatement")]
 pub struct SwitchStmt {
     pub span: Span,
     pub discriminant: Box<Expr>,
     pub cases: Vec<SwitchCase>,
 }
-#[ast_node]
+#[ast_node("ThrowStatement")]
 pub struct ThrowStmt {
     pub span: Span,
+    #[serde(rename = "argument")]
     pub arg: Box<Expr>,
 }
-#[ast_node]
+#[ast_node("TryStatement")]
 pub struct TryStmt {
     pub span: Span,
     pub block: BlockStmt,
     pub handler: Option<CatchClause>,
     pub finalizer: Option<BlockStmt>,
 }
-#[ast_node]
+#[ast_node("WhileStatement")]
 pub struct WhileStmt {
     pub span: Span,
     pub test: Box<Expr>,
     pub body: Box<Stmt>,
 }
-#[ast_node]
+#[ast_node("DoWhileStatement")]
 pub struct DoWhileStmt {
     pub span: Span,
     pub test: Box<Expr>,
     pub body: Box<Stmt>,
 }
-#[ast_node]
+#[ast_node("ForStatement")]
 pub struct ForStmt {
     pub span: Span,
     pub init: Option<VarDeclOrExpr>,
@@ -140,14 +154,14 @@ pub struct ForStmt {
     pub update: Option<(Box<Expr>)>,
     pub body: Box<Stmt>,
 }
-#[ast_node]
+#[ast_node("ForInStatement")]
 pub struct ForInStmt {
     pub span: Span,
     pub left: VarDeclOrPat,
     pub right: Box<Expr>,
     pub body: Box<Stmt>,
 }
-#[ast_node]
+#[ast_node("ForOfStatement")]
 pub struct ForOfStmt {
     pub span: Span,
     /// Span of the await token.
@@ -167,6 +181,7 @@ pub struct SwitchCase {
     /// None for `default:`
     pub test: Option<(Box<Expr>)>,
 
+    #[serde(rename = "consequent")]
     pub cons: Vec<Stmt>,
 }
 
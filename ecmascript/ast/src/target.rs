@@ -0,0 +1,31 @@
+/// Version of ecmascript which a program is compiled to.
+///
+/// Versions are ordered, so `target < EsVersion::Es2015` means that the
+/// target doesn't support es2015.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum EsVersion {
+    Es3,
+    Es5,
+    Es2015,
+    Es2016,
+    Es2017,
+    Es2018,
+    Es2019,
+    Es2020,
+    Es2021,
+    Es2022,
+}
+
+impl EsVersion {
+    /// The latest version.
+    pub const LATEST: EsVersion = EsVersion::Es2022;
+}
+
+/// The latest version, which doesn't require any lowering.
+impl Default for EsVersion {
+    fn default() -> Self {
+        EsVersion::LATEST
+    }
+}
@@ -0,0 +1,467 @@
+//! Nodes of typescript syntax.
+//!
+//! These only describe types, and have no runtime semantics. Everything in
+//! this module can be removed to get plain javascript.
+use super::{Bool, Expr, Ident, ModuleItem, Number, Pat, PropName, Str};
+use swc_common::{ast_node, EqIgnoreSpan, Fold, HashIgnoreSpan, Span};
+
+/// `: T`, including the colon.
+#[ast_node("TSTypeAnnotation")]
+pub struct TsTypeAnn {
+    pub span: Span,
+    #[serde(rename = "typeAnnotation")]
+    pub type_ann: Box<TsType>,
+}
+
+/// `<T, U extends V = W>`
+#[ast_node("TSTypeParameterDeclaration")]
+pub struct TsTypeParamDecl {
+    pub span: Span,
+    pub params: Vec<TsTypeParam>,
+}
+
+#[ast_node("TSTypeParameter")]
+pub struct TsTypeParam {
+    pub span: Span,
+    pub name: Ident,
+    /// `U` in `T extends U`.
+    pub constraint: Option<Box<TsType>>,
+    /// `U` in `T = U`.
+    pub default: Option<Box<TsType>>,
+}
+
+/// `<string, T>` in `Foo<string, T>`
+#[ast_node("TSTypeParameterInstantiation")]
+pub struct TsTypeParamInstantiation {
+    pub span: Span,
+    pub params: Vec<Box<TsType>>,
+}
+
+/// A binding with a type annotation, e.g. `a: T`, `{ b }: U` or `c?`.
+///
+/// This only appears at the top level of a parameter or a variable
+/// declarator.
+#[ast_node("TSTypedPattern")]
+pub struct TsTypedPat {
+    pub span: Span,
+    pub pat: Box<Pat>,
+    /// `a?: T`. Only valid for parameters.
+    pub optional: bool,
+    #[serde(rename = "typeAnnotation")]
+    pub type_ann: Option<TsTypeAnn>,
+}
+
+#[ast_node]
+#[allow(variant_size_differences)]
+pub enum TsEntityName {
+    TsQualifiedName(Box<TsQualifiedName>),
+    Ident(Ident),
+}
+
+/// `a.b` in `let c: a.b;`
+#[ast_node("TSQualifiedName")]
+pub struct TsQualifiedName {
+    #[span(lo)]
+    pub left: TsEntityName,
+    #[span(hi)]
+    pub right: Ident,
+}
+
+#[ast_node]
+#[allow(variant_size_differences)]
+pub enum TsType {
+    TsKeywordType(TsKeywordType),
+    TsThisType(TsThisType),
+    TsFnType(TsFnType),
+    TsConstructorType(TsConstructorType),
+    TsTypeRef(TsTypeRef),
+    TsTypeQuery(TsTypeQuery),
+    TsTypeLit(TsTypeLit),
+    TsArrayType(TsArrayType),
+    TsTupleType(TsTupleType),
+    TsUnionType(TsUnionType),
+    TsIntersectionType(TsIntersectionType),
+    TsConditionalType(TsConditionalType),
+    TsParenthesizedType(TsParenthesizedType),
+    TsTypeOperator(TsTypeOperator),
+    TsIndexedAccessType(TsIndexedAccessType),
+    TsMappedType(TsMappedType),
+    TsLitType(TsLitType),
+}
+
+#[ast_node("TSKeywordType")]
+#[derive(Copy)]
+pub struct TsKeywordType {
+    pub span: Span,
+    pub kind: TsKeywordTypeKind,
+}
+
+#[derive(
+    Fold,
+    EqIgnoreSpan,
+    HashIgnoreSpan,
+    StringEnum,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    PartialOrd,
+    Ord,
+    Hash,
+)]
+pub enum TsKeywordTypeKind {
+    /// `any`
+    Any,
+    /// `unknown`
+    Unknown,
+    /// `number`
+    Number,
+    /// `object`
+    Object,
+    /// `boolean`
+    Boolean,
+    /// `bigint`
+    BigInt,
+    /// `string`
+    String,
+    /// `symbol`
+    Symbol,
+    /// `void`
+    Void,
+    /// `undefined`
+    Undefined,
+    /// `null`
+    Null,
+    /// `never`
+    Never,
+}
+
+#[ast_node("TSThisType")]
+#[derive(Copy)]
+pub struct TsThisType {
+    pub span: Span,
+}
+
+/// `(a: T) => U`
+#[ast_node("TSFunctionType")]
+pub struct TsFnType {
+    pub span: Span,
+    #[serde(rename = "typeParameters")]
+    pub type_params: Option<TsTypeParamDecl>,
+    pub params: Vec<Pat>,
+    #[serde(rename = "typeAnnotation")]
+    pub type_ann: TsTypeAnn,
+}
+
+/// `new (a: T) => U`
+#[ast_node("TSConstructorType")]
+pub struct TsConstructorType {
+    pub span: Span,
+    #[serde(rename = "typeParameters")]
+    pub type_params: Option<TsTypeParamDecl>,
+    pub params: Vec<Pat>,
+    #[serde(rename = "typeAnnotation")]
+    pub type_ann: TsTypeAnn,
+}
+
+/// `Foo` or `a.Foo<T>`
+#[ast_node("TSTypeReference")]
+pub struct TsTypeRef {
+    pub span: Span,
+    pub type_name: TsEntityName,
+    #[serde(rename = "typeParameters")]
+    pub type_params: Option<TsTypeParamInstantiation>,
+}
+
+/// `typeof a.b`
+#[ast_node("TSTypeQuery")]
+pub struct TsTypeQuery {
+    pub span: Span,
+    pub expr_name: TsEntityName,
+}
+
+/// `{ a: T; b(): U }`
+#[ast_node("TSTypeLiteral")]
+pub struct TsTypeLit {
+    pub span: Span,
+    pub members: Vec<TsTypeElement>,
+}
+
+/// Member of an interface or a type literal.
+#[ast_node]
+pub enum TsTypeElement {
+    TsPropertySignature(TsPropertySignature),
+    TsMethodSignature(TsMethodSignature),
+    /// `(a: T): U`
+    TsCallSignatureDecl(TsCallSignatureDecl),
+    /// `new (a: T): U`
+    TsConstructSignatureDecl(TsConstructSignatureDecl),
+    TsIndexSignature(TsIndexSignature),
+}
+
+/// `readonly a?: T`
+#[ast_node("TSPropertySignature")]
+pub struct TsPropertySignature {
+    pub span: Span,
+    pub readonly: bool,
+    pub key: PropName,
+    pub optional: bool,
+    #[serde(rename = "typeAnnotation")]
+    pub type_ann: Option<TsTypeAnn>,
+}
+
+/// `a?<T>(b: T): U`
+#[ast_node("TSMethodSignature")]
+pub struct TsMethodSignature {
+    pub span: Span,
+    pub key: PropName,
+    pub optional: bool,
+    #[serde(rename = "typeParameters")]
+    pub type_params: Option<TsTypeParamDecl>,
+    pub params: Vec<Pat>,
+    #[serde(rename = "typeAnnotation")]
+    pub type_ann: Option<TsTypeAnn>,
+}
+
+#[ast_node("TSCallSignatureDeclaration")]
+pub struct TsCallSignatureDecl {
+    pub span: Span,
+    #[serde(rename = "typeParameters")]
+    pub type_params: Option<TsTypeParamDecl>,
+    pub params: Vec<Pat>,
+    #[serde(rename = "typeAnnotation")]
+    pub type_ann: Option<TsTypeAnn>,
+}
+
+#[ast_node("TSConstructSignatureDeclaration")]
+pub struct TsConstructSignatureDecl {
+    pub span: Span,
+    #[serde(rename = "typeParameters")]
+    pub type_params: Option<TsTypeParamDecl>,
+    pub params: Vec<Pat>,
+    #[serde(rename = "typeAnnotation")]
+    pub type_ann: Option<TsTypeAnn>,
+}
+
+/// `readonly [key: string]: T`
+#[ast_node("TSIndexSignature")]
+pub struct TsIndexSignature {
+    pub span: Span,
+    pub readonly: bool,
+    pub params: Vec<Pat>,
+    #[serde(rename = "typeAnnotation")]
+    pub type_ann: Option<TsTypeAnn>,
+}
+
+/// `T[]`
+#[ast_node("TSArrayType")]
+pub struct TsArrayType {
+    pub span: Span,
+    pub elem_type: Box<TsType>,
+}
+
+/// `[T, U]`
+#[ast_node("TSTupleType")]
+pub struct TsTupleType {
+    pub span: Span,
+    pub elem_types: Vec<Box<TsType>>,
+}
+
+/// `T | U`
+#[ast_node("TSUnionType")]
+pub struct TsUnionType {
+    pub span: Span,
+    pub types: Vec<Box<TsType>>,
+}
+
+/// `T & U`
+#[ast_node("TSIntersectionType")]
+pub struct TsIntersectionType {
+    pub span: Span,
+    pub types: Vec<Box<TsType>>,
+}
+
+/// `T extends U ? X : Y`
+#[ast_node("TSConditionalType")]
+pub struct TsConditionalType {
+    pub span: Span,
+    pub check_type: Box<TsType>,
+    pub extends_type: Box<TsType>,
+    pub true_type: Box<TsType>,
+    pub false_type: Box<TsType>,
+}
+
+/// `(T)`
+#[ast_node("TSParenthesizedType")]
+pub struct TsParenthesizedType {
+    pub span: Span,
+    #[serde(rename = "typeAnnotation")]
+    pub type_ann: Box<TsType>,
+}
+
+/// `keyof T`
+#[ast_node("TSTypeOperator")]
+pub struct TsTypeOperator {
+    pub span: Span,
+    #[serde(rename = "operator")]
+    pub op: TsTypeOperatorOp,
+    #[serde(rename = "typeAnnotation")]
+    pub type_ann: Box<TsType>,
+}
+
+#[derive(
+    Fold,
+    EqIgnoreSpan,
+    HashIgnoreSpan,
+    StringEnum,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    PartialOrd,
+    Ord,
+    Hash,
+)]
+pub enum TsTypeOperatorOp {
+    /// `keyof`
+    KeyOf,
+    /// `unique`
+    Unique,
+    /// `readonly`
+    ReadOnly,
+}
+
+/// `T[K]`
+#[ast_node("TSIndexedAccessType")]
+pub struct TsIndexedAccessType {
+    pub span: Span,
+    pub obj_type: Box<TsType>,
+    pub index_type: Box<TsType>,
+}
+
+/// `{ readonly [K in keyof T]?: T[K] }`
+///
+/// `keyof T` is stored as the constraint of `type_param`.
+#[ast_node("TSMappedType")]
+pub struct TsMappedType {
+    pub span: Span,
+    pub readonly: bool,
+    pub type_param: TsTypeParam,
+    pub optional: bool,
+    #[serde(rename = "typeAnnotation")]
+    pub type_ann: Option<Box<TsType>>,
+}
+
+/// `'a'`, `1` or `true` used as a type.
+#[ast_node("TSLiteralType")]
+pub struct TsLitType {
+    pub span: Span,
+    pub lit: TsLit,
+}
+
+#[ast_node]
+pub enum TsLit {
+    Number(Number),
+    Str(Str),
+    Bool(Bool),
+}
+
+/// `Foo<T>` in `extends` and `implements` clauses.
+#[ast_node("TSExpressionWithTypeArguments")]
+pub struct TsExprWithTypeArgs {
+    pub span: Span,
+    #[serde(rename = "expression")]
+    pub expr: TsEntityName,
+    #[serde(rename = "typeParameters")]
+    pub type_params: Option<TsTypeParamInstantiation>,
+}
+
+/// `interface Foo<T> extends Bar { ... }`
+#[ast_node("TSInterfaceDeclaration")]
+pub struct TsInterfaceDecl {
+    pub span: Span,
+    pub declare: bool,
+    pub id: Ident,
+    #[serde(rename = "typeParameters")]
+    pub type_params: Option<TsTypeParamDecl>,
+    pub extends: Vec<TsExprWithTypeArgs>,
+    pub body: TsInterfaceBody,
+}
+
+#[ast_node("TSInterfaceBody")]
+pub struct TsInterfaceBody {
+    /// Span including the braces.
+    pub span: Span,
+    pub body: Vec<TsTypeElement>,
+}
+
+/// `type Foo<T> = Bar<T>;`
+#[ast_node("TSTypeAliasDeclaration")]
+pub struct TsTypeAliasDecl {
+    pub span: Span,
+    pub declare: bool,
+    pub id: Ident,
+    #[serde(rename = "typeParameters")]
+    pub type_params: Option<TsTypeParamDecl>,
+    #[serde(rename = "typeAnnotation")]
+    pub type_ann: Box<TsType>,
+}
+
+/// `enum Foo { A, B = 1 }` or `const enum Foo {}`
+///
+/// Unlike other nodes in this module, non-const enums exist at runtime.
+#[ast_node("TSEnumDeclaration")]
+pub struct TsEnumDecl {
+    pub span: Span,
+    pub declare: bool,
+    pub is_const: bool,
+    pub id: Ident,
+    pub members: Vec<TsEnumMember>,
+}
+
+#[ast_node("TSEnumMember")]
+pub struct TsEnumMember {
+    pub span: Span,
+    pub id: TsEnumMemberId,
+    pub init: Option<Box<Expr>>,
+}
+
+#[ast_node]
+pub enum TsEnumMemberId {
+    Ident(Ident),
+    /// `'a-b'` in `enum Foo { 'a-b' }`
+    Str(Str),
+}
+
+/// `declare module 'foo' { ... }` or `declare namespace Foo { ... }`
+#[ast_node("TSModuleDeclaration")]
+pub struct TsModuleDecl {
+    pub span: Span,
+    pub declare: bool,
+    pub id: TsModuleName,
+    /// `None` for shorthand declarations like `declare module 'foo';`.
+    pub body: Option<TsModuleBlock>,
+}
+
+#[ast_node]
+pub enum TsModuleName {
+    Ident(Ident),
+    Str(Str),
+}
+
+#[ast_node("TSModuleBlock")]
+pub struct TsModuleBlock {
+    /// Span including the braces.
+    pub span: Span,
+    pub body: Vec<ModuleItem>,
+}
+
+/// `a as T`
+#[ast_node("TSAsExpression")]
+pub struct TsAsExpr {
+    pub span: Span,
+    #[serde(rename = "expression")]
+    pub expr: Box<Expr>,
+    #[serde(rename = "typeAnnotation")]
+    pub type_ann: Box<TsType>,
+}
@@ -0,0 +1,48 @@
+//! `StableFold` should work without `#![feature(specialization)]`.
+#![cfg(feature = "stable-fold")]
+
+extern crate swc_common;
+extern crate swc_ecma_ast as ast;
+
+use ast::*;
+use swc_common::{FoldNode, DUMMY_SP};
+
+/// Renames `a` to `b`.
+struct Rename;
+
+impl StableFold for Rename {
+    fn fold_ident(&mut self, i: Ident) -> Ident {
+        if &*i.sym == "a" {
+            Ident::new("b".into(), i.span)
+        } else {
+            i
+        }
+    }
+}
+
+fn ident(sym: &str) -> Box<Expr> {
+    Box::new(Expr::Ident(Ident::new(sym.into(), DUMMY_SP)))
+}
+
+fn add(left: Box<Expr>, right: Box<Expr>) -> Expr {
+    Expr::Bin(BinExpr {
+        span: DUMMY_SP,
+        op: BinaryOp::Add,
+        left,
+        right,
+    })
+}
+
+fn paren(expr: Box<Expr>) -> Box<Expr> {
+    Box::new(Expr::Paren(ParenExpr {
+        span: DUMMY_SP,
+        expr,
+    }))
+}
+
+#[test]
+fn fold_nested() {
+    let e = add(paren(ident("a")), ident("c"));
+
+    assert_eq!(e.fold_node(&mut Rename), add(paren(ident("b")), ident("c")));
+}
@@ -0,0 +1,150 @@
+use super::graph::{DepKind, ModuleGraph, ModuleId};
+use std::collections::HashSet;
+
+/// Modules emitted to a file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chunk {
+    pub name: String,
+    pub kind: ChunkKind,
+    /// Modules in the chunk, with dependencies first.
+    pub modules: Vec<ModuleId>,
+    /// Names of shared chunks which should be loaded before this chunk.
+    pub requires: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChunkKind {
+    /// Starts with an entry.
+    Entry(ModuleId),
+    /// Loaded by `import()` of the module.
+    Dynamic(ModuleId),
+    /// Modules used by multiple chunks.
+    Shared,
+}
+
+impl Chunk {
+    pub fn file_name(&self) -> String {
+        format!("{}.js", self.name)
+    }
+}
+
+impl ModuleGraph {
+    /// Splits modules into chunks.
+    ///
+    /// Each entry and each dynamically imported module starts a chunk
+    /// containing modules it imports statically. A module imported by
+    /// multiple chunks is hoisted into a shared chunk, which contains modules
+    /// imported by the same chunks. The shared chunk is named after those
+    /// chunks, like `main~page`.
+    ///
+    /// Chunks for entries come first, followed by chunks for dynamic imports
+    /// and shared chunks.
+    pub fn chunks(&self) -> Vec<Chunk> {
+        let mut roots: Vec<_> = self.entries.iter().map(|&id| ChunkKind::Entry(id)).collect();
+        // Chunks including each module, as indices of `roots`.
+        let mut owners = vec![vec![]; self.modules.len()];
+        let mut order = vec![];
+
+        let mut i = 0;
+        while i < roots.len() {
+            let root = match roots[i] {
+                ChunkKind::Entry(id) | ChunkKind::Dynamic(id) => id,
+                ChunkKind::Shared => unreachable!(),
+            };
+
+            let mut modules = vec![];
+            self.visit_post_order(root, &mut vec![false; self.modules.len()], &mut modules);
+            for id in modules {
+                if owners[id.0].is_empty() {
+                    order.push(id);
+                }
+                owners[id.0].push(i);
+
+                for dep in &self[id].deps {
+                    let kind = ChunkKind::Dynamic(dep.module);
+                    let is_root = roots.iter().any(|&root| match root {
+                        ChunkKind::Entry(root) | ChunkKind::Dynamic(root) => root == dep.module,
+                        ChunkKind::Shared => false,
+                    });
+                    if dep.kind == DepKind::Dynamic && !is_root {
+                        roots.push(kind);
+                    }
+                }
+            }
+            i += 1;
+        }
+
+        let mut names = HashSet::new();
+        let mut chunks: Vec<_> = roots
+            .iter()
+            .enumerate()
+            .map(|(i, &kind)| {
+                let root = match kind {
+                    ChunkKind::Entry(id) | ChunkKind::Dynamic(id) => id,
+                    ChunkKind::Shared => unreachable!(),
+                };
+                Chunk {
+                    name: self.chunk_name(root, &mut names),
+                    kind,
+                    modules: order
+                        .iter()
+                        .cloned()
+                        .filter(|id| owners[id.0] == [i])
+                        .collect(),
+                    requires: vec![],
+                }
+            })
+            .collect();
+
+        let mut shared: Vec<(&[usize], Chunk)> = vec![];
+        for &id in &order {
+            let owners = &owners[id.0];
+            if owners.len() == 1 {
+                continue;
+            }
+            if let Some(&mut (_, ref mut chunk)) = shared.iter_mut().find(|c| c.0 == &**owners) {
+                chunk.modules.push(id);
+                continue;
+            }
+
+            let name = owners
+                .iter()
+                .map(|&i| &*chunks[i].name)
+                .collect::<Vec<_>>()
+                .join("~");
+            for &i in owners {
+                chunks[i].requires.push(name.clone());
+            }
+            shared.push((
+                owners,
+                Chunk {
+                    name,
+                    kind: ChunkKind::Shared,
+                    modules: vec![id],
+                    requires: vec![],
+                },
+            ));
+        }
+
+        chunks.extend(shared.into_iter().map(|(_, chunk)| chunk));
+        chunks
+    }
+
+    /// Returns the file name of `id` without extensions, adding a number if
+    /// it's already used.
+    fn chunk_name(&self, id: ModuleId, names: &mut HashSet<String>) -> String {
+        let stem = self[id]
+            .path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_else(|| String::from("chunk"));
+
+        let mut name = stem.clone();
+        let mut cnt = 1;
+        while !names.insert(name.clone()) {
+            name = format!("{}{}", stem, cnt);
+            cnt += 1;
+        }
+        name
+    }
+}
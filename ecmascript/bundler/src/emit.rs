@@ -0,0 +1,150 @@
+use super::{
+    chunk::{Chunk, ChunkKind},
+    graph::{ModuleGraph, ModuleId},
+    link::link,
+    loader::Bundler,
+    resolve::Resolve,
+};
+use ast::*;
+use serde::Serialize;
+use sourcemap::SourceMapBuilder;
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+use swc_common::{Fold, DUMMY_SP};
+use swc_ecma_codegen::{text_writer::JsWriter, Emitter, Handlers};
+use swc_ecma_transforms::compat::helpers::{Helpers, InjectHelpers};
+
+const RUNTIME: &str = include_str!("runtime.js");
+
+/// Output of [Bundler::emit].
+#[derive(Debug)]
+pub struct Bundle {
+    /// File names and code of chunks, in the order of chunks.
+    pub files: Vec<(String, String)>,
+    pub manifest: Manifest,
+}
+
+/// Files of chunks.
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct Manifest {
+    /// Chunk names to file names.
+    pub chunks: BTreeMap<String, String>,
+}
+
+impl<'a, R: Resolve> Bundler<'a, R> {
+    /// Emits `chunks` of `graph`.
+    ///
+    /// Modules are linked as commonjs modules and registered by their ids.
+    /// A chunk of an entry starts with a runtime, which loads shared chunks
+    /// required by the chunk and then runs the entry. `import()` loads the
+    /// chunk of the imported module and shared chunks it requires.
+    pub fn emit(&self, graph: &ModuleGraph, chunks: &[Chunk]) -> Bundle {
+        let mut files = Vec::with_capacity(chunks.len());
+        let mut manifest = Manifest::default();
+
+        let loads: HashMap<_, _> = chunks
+            .iter()
+            .filter_map(|chunk| match chunk.kind {
+                ChunkKind::Dynamic(id) => {
+                    let mut files: Vec<_> = chunk
+                        .requires
+                        .iter()
+                        .map(|name| format!("{}.js", name))
+                        .collect();
+                    files.push(chunk.file_name());
+                    Some((id, files))
+                }
+                ChunkKind::Entry(..) | ChunkKind::Shared => None,
+            })
+            .collect();
+
+        for chunk in chunks {
+            let helpers: Arc<Helpers> = Default::default();
+            let modules: Vec<_> = chunk
+                .modules
+                .iter()
+                .map(|&id| {
+                    let code = self.emit_module(graph, id, &helpers, &loads);
+                    format!("{}: function (module, exports, require) {{\n{}}}", id.0, code)
+                })
+                .collect();
+            let modules = format!("{{\n{}\n}}", modules.join(",\n"));
+
+            let code = match chunk.kind {
+                ChunkKind::Entry(entry) => {
+                    let requires: Vec<_> = chunk
+                        .requires
+                        .iter()
+                        .map(|name| format!("{}.js", name))
+                        .collect();
+                    format!(
+                        "{}({}, {}, {});\n",
+                        RUNTIME.trim_right(),
+                        modules,
+                        entry.0,
+                        ::serde_json::to_string(&requires).unwrap()
+                    )
+                }
+                ChunkKind::Dynamic(..) | ChunkKind::Shared => {
+                    format!("__swcRegister({});\n", modules)
+                }
+            };
+
+            // Helpers are shared by modules of the chunk.
+            let helpers = InjectHelpers {
+                cm: self.cm.clone(),
+                helpers,
+                external: false,
+            }
+            .fold(Module {
+                span: DUMMY_SP,
+                body: vec![],
+                shebang: None,
+            });
+            let code = format!("(function () {{\n{}{}}})();\n", self.print(&helpers), code);
+
+            manifest.chunks.insert(chunk.name.clone(), chunk.file_name());
+            files.push((chunk.file_name(), code));
+        }
+
+        Bundle { files, manifest }
+    }
+
+    fn emit_module(
+        &self,
+        graph: &ModuleGraph,
+        id: ModuleId,
+        helpers: &Helpers,
+        loads: &HashMap<ModuleId, Vec<String>>,
+    ) -> String {
+        let module = link(graph, &graph[id], helpers, loads);
+
+        self.print(&module)
+    }
+
+    fn print(&self, module: &Module) -> String {
+        let mut buf = vec![];
+        {
+            let mut src_map_builder = SourceMapBuilder::new(None);
+            let mut emitter = Emitter {
+                cfg: Default::default(),
+                cm: self.cm.clone(),
+                wr: box JsWriter::new(self.cm.clone(), "\n", &mut buf, &mut src_map_builder),
+                handlers: box NoopHandlers,
+                comments: None,
+            };
+
+            emitter
+                .emit_module(module)
+                .expect("failed to write to a buffer");
+        }
+
+        String::from_utf8(buf).expect("codegen should emit utf8")
+    }
+}
+
+struct NoopHandlers;
+
+impl Handlers for NoopHandlers {}
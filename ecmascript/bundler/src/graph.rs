@@ -0,0 +1,294 @@
+use ast::*;
+use std::{
+    collections::HashMap,
+    ops::Index,
+    path::{Path, PathBuf},
+};
+use swc_atoms::JsWord;
+use swc_common::{Span, Visit, VisitWith};
+use swc_ecma_transforms::util::pat_ids;
+
+/// Index of a module in a [ModuleGraph].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ModuleId(pub(crate) usize);
+
+/// Modules reachable from entries, and imports between them.
+#[derive(Debug, Default)]
+pub struct ModuleGraph {
+    pub(crate) modules: Vec<ModuleData>,
+    pub(crate) ids: HashMap<PathBuf, ModuleId>,
+    pub(crate) entries: Vec<ModuleId>,
+}
+
+#[derive(Debug)]
+pub struct ModuleData {
+    pub path: PathBuf,
+    pub module: Module,
+    /// Imports in the order they appear, followed by `require` calls and
+    /// `import()`.
+    pub deps: Vec<Dep>,
+    /// Specifiers of external modules imported by the module. They are not
+    /// loaded, and imports of them are kept.
+    pub externals: Vec<JsWord>,
+    /// Names exported by the module, including names re-exported from named
+    /// modules. Names exported by `export *` are not included.
+    pub exports: Vec<JsWord>,
+    /// False if the module can be removed when none of its exports are used.
+    pub side_effects: bool,
+}
+
+/// An import of a module.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dep {
+    /// Specifier as written.
+    pub src: JsWord,
+    pub span: Span,
+    pub kind: DepKind,
+    /// The imported module.
+    pub module: ModuleId,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DepKind {
+    /// `import a, { b } from 'x'` imports `default` and `b`, and `import 'x'`
+    /// imports nothing.
+    Import(Vec<JsWord>),
+    /// `import * as ns from 'x'`
+    ImportAll,
+    /// `export { a as b } from 'x'`, as pairs of imported and exported names.
+    ReExport(Vec<(JsWord, JsWord)>),
+    /// `export * from 'x'`
+    ReExportAll,
+    /// `import('x')`
+    Dynamic,
+    /// `require('x')`
+    Require,
+}
+
+impl ModuleGraph {
+    pub fn entries(&self) -> &[ModuleId] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.modules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.modules.is_empty()
+    }
+
+    pub fn id_of(&self, path: &Path) -> Option<ModuleId> {
+        self.ids.get(path).cloned()
+    }
+
+    /// Modules in the order they were loaded.
+    pub fn modules<'a>(&'a self) -> impl Iterator<Item = (ModuleId, &'a ModuleData)> + 'a {
+        self.modules
+            .iter()
+            .enumerate()
+            .map(|(i, m)| (ModuleId(i), m))
+    }
+
+    /// Modules importing `id`.
+    pub fn dependents(&self, id: ModuleId) -> Vec<ModuleId> {
+        self.modules()
+            .filter(|&(_, m)| m.deps.iter().any(|d| d.module == id))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Modules reachable from entries, with dependencies before modules
+    /// importing them. Modules in a cycle are ordered as they are executed.
+    pub fn sorted(&self) -> Vec<ModuleId> {
+        let mut visited = vec![false; self.modules.len()];
+        let mut order = Vec::with_capacity(self.modules.len());
+        for &entry in &self.entries {
+            self.visit_post_order(entry, &mut visited, &mut order);
+        }
+        order
+    }
+
+    pub(crate) fn visit_post_order(
+        &self,
+        id: ModuleId,
+        visited: &mut [bool],
+        order: &mut Vec<ModuleId>,
+    ) {
+        if visited[id.0] {
+            return;
+        }
+        visited[id.0] = true;
+
+        for dep in &self[id].deps {
+            // Dynamically imported modules are executed later.
+            if dep.kind != DepKind::Dynamic {
+                self.visit_post_order(dep.module, visited, order);
+            }
+        }
+        order.push(id);
+    }
+}
+
+impl Index<ModuleId> for ModuleGraph {
+    type Output = ModuleData;
+
+    fn index(&self, id: ModuleId) -> &ModuleData {
+        &self.modules[id.0]
+    }
+}
+
+/// Returns imports and exports of `module`.
+pub(crate) fn collect(module: &Module) -> (Vec<(Str, DepKind)>, Vec<JsWord>) {
+    let mut deps = vec![];
+    let mut exports = vec![];
+
+    for item in &module.body {
+        let decl = match *item {
+            ModuleItem::ModuleDecl(ref decl) => decl,
+            ModuleItem::Stmt(..) => continue,
+        };
+
+        match *decl {
+            ModuleDecl::Import(ref import) => {
+                let all = import.specifiers.iter().any(|s| match *s {
+                    ImportSpecifier::Namespace(..) => true,
+                    _ => false,
+                });
+                let kind = if all {
+                    DepKind::ImportAll
+                } else {
+                    DepKind::Import(
+                        import
+                            .specifiers
+                            .iter()
+                            .map(|s| match *s {
+                                ImportSpecifier::Specific(ref s) => {
+                                    s.imported.as_ref().unwrap_or(&s.local).sym.clone()
+                                }
+                                _ => js_word!("default"),
+                            })
+                            .collect(),
+                    )
+                };
+                deps.push((import.src.clone(), kind));
+            }
+
+            ModuleDecl::ExportDecl(ExportDecl { ref decl, .. }) => match *decl {
+                Decl::Fn(FnDecl { ref ident, .. }) | Decl::Class(ClassDecl { ref ident, .. }) => {
+                    exports.push(ident.sym.clone())
+                }
+                Decl::Var(ref var) => {
+                    let mut ids = vec![];
+                    for decl in &var.decls {
+                        pat_ids(&decl.name, &mut ids);
+                    }
+                    exports.extend(ids.into_iter().map(|i| i.sym));
+                }
+                Decl::TsEnum(TsEnumDecl { ref id, .. }) => exports.push(id.sym.clone()),
+                Decl::TsInterface(..) | Decl::TsTypeAlias(..) | Decl::TsModule(..) => {}
+            },
+
+            ModuleDecl::ExportNamed(NamedExport {
+                ref specifiers,
+                ref src,
+                ..
+            }) => {
+                let names: Vec<_> = specifiers
+                    .iter()
+                    .map(|s| {
+                        let exported = s.exported.as_ref().unwrap_or(&s.orig);
+                        (s.orig.sym.clone(), exported.sym.clone())
+                    })
+                    .collect();
+                exports.extend(names.iter().map(|&(_, ref exported)| exported.clone()));
+
+                if let Some(ref src) = *src {
+                    deps.push((src.clone(), DepKind::ReExport(names)));
+                }
+            }
+
+            ModuleDecl::ExportDefaultDecl(..) | ModuleDecl::ExportDefaultExpr(..) => {
+                exports.push(js_word!("default"))
+            }
+
+            ModuleDecl::ExportAll(ExportAll { ref src, .. }) => {
+                deps.push((src.clone(), DepKind::ReExportAll))
+            }
+        }
+    }
+
+    deps.extend(requires(module).into_iter().map(|src| (src, DepKind::Require)));
+    deps.extend(dynamic_imports(module).into_iter().map(|src| (src, DepKind::Dynamic)));
+
+    (deps, exports)
+}
+
+/// Returns sources of `require` calls in `module`.
+pub(crate) fn requires(module: &Module) -> Vec<Str> {
+    let mut requires = Requires(vec![]);
+    module.visit_with(&mut requires);
+    requires.0
+}
+
+/// Returns `'x'` of `require('x')`.
+pub(crate) fn require_src(call: &CallExpr) -> Option<&Str> {
+    let is_require = match call.callee {
+        ExprOrSuper::Expr(box Expr::Ident(ref i)) => &*i.sym == "require",
+        _ => false,
+    };
+    if !is_require || call.args.len() != 1 || call.args[0].spread.is_some() {
+        return None;
+    }
+
+    match *call.args[0].expr {
+        Expr::Lit(Lit::Str(ref src)) => Some(src),
+        _ => None,
+    }
+}
+
+struct Requires(Vec<Str>);
+
+impl Visit<CallExpr> for Requires {
+    fn visit(&mut self, call: &CallExpr) {
+        if let Some(src) = require_src(call) {
+            self.0.push(src.clone());
+        }
+        call.visit_children(self)
+    }
+}
+
+/// Returns sources of `import()` in `module`.
+pub(crate) fn dynamic_imports(module: &Module) -> Vec<Str> {
+    let mut imports = DynamicImports(vec![]);
+    module.visit_with(&mut imports);
+    imports.0
+}
+
+/// Returns `'x'` of `import('x')`. Specifiers which are not string literals
+/// can't be bundled.
+pub(crate) fn dynamic_import_src(call: &CallExpr) -> Option<&Str> {
+    match call.callee {
+        ExprOrSuper::Import(..) => {}
+        _ => return None,
+    }
+    if call.args.len() != 1 || call.args[0].spread.is_some() {
+        return None;
+    }
+
+    match *call.args[0].expr {
+        Expr::Lit(Lit::Str(ref src)) => Some(src),
+        _ => None,
+    }
+}
+
+struct DynamicImports(Vec<Str>);
+
+impl Visit<CallExpr> for DynamicImports {
+    fn visit(&mut self, call: &CallExpr) {
+        if let Some(src) = dynamic_import_src(call) {
+            self.0.push(src.clone());
+        }
+        call.visit_children(self)
+    }
+}
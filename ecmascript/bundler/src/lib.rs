@@ -0,0 +1,47 @@
+//! Bundler for ecmascript modules.
+//!
+//! [Bundler::load] parses entries and modules imported by them into a
+//! [ModuleGraph]. Specifiers are resolved by an implementation of [Resolve], like
+//! [NodeResolver]. External modules given to [Bundler::with_externals] are not
+//! loaded, and they are required from the host at runtime.
+//! [ModuleGraph::shake] removes code which is not used by entries.
+//!
+//! [ModuleGraph::chunks] splits modules into chunks at dynamic imports, and
+//! [Bundler::emit] emits them with a runtime which loads chunks.
+#![feature(box_patterns)]
+#![feature(box_syntax)]
+#![feature(specialization)]
+
+extern crate rayon;
+extern crate serde;
+extern crate serde_json;
+extern crate slog;
+extern crate sourcemap;
+#[macro_use(js_word)]
+extern crate swc_atoms;
+extern crate swc_common;
+extern crate swc_ecma_ast as ast;
+extern crate swc_ecma_codegen;
+extern crate swc_ecma_parser;
+#[macro_use(member_expr, quote_ident, quote_str)]
+extern crate swc_ecma_transforms;
+#[cfg(test)]
+extern crate testing;
+
+pub use self::{
+    chunk::{Chunk, ChunkKind},
+    emit::{Bundle, Manifest},
+    graph::{Dep, DepKind, ModuleData, ModuleGraph, ModuleId},
+    loader::{Bundler, Error, ErrorKind},
+    resolve::{NodeResolver, Resolve},
+};
+
+mod chunk;
+mod emit;
+mod graph;
+mod link;
+mod loader;
+pub mod resolve;
+mod shake;
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,778 @@
+use super::graph::{dynamic_import_src, require_src, ModuleData, ModuleGraph, ModuleId};
+use ast::*;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::atomic::Ordering,
+};
+use swc_atoms::JsWord;
+use swc_common::{Fold, FoldWith, Span, Visit, VisitWith, DUMMY_SP};
+use swc_ecma_transforms::{
+    compat::helpers::Helpers,
+    util::{pat_ids, ExprFactory},
+};
+
+/// Converts a module to the body of `function (module, exports, require)`
+/// registered to the runtime.
+///
+/// Es modules are converted to commonjs modules which keep live bindings.
+/// Exports are defined as getters before the module runs, and imported
+/// bindings are replaced with properties of imported modules. So modules in
+/// a cycle can use hoisted declarations of each other, and see values
+/// assigned later.
+///
+/// Commonjs modules are kept as is. Default and namespace imports of them are
+/// wrapped with `_interopRequireDefault` and `_interopRequireWildcard`.
+///
+/// `require('./foo')` of bundled modules are replaced with `require(id)`, and
+/// external modules are required by their specifiers.
+///
+/// `import('./foo')` is replaced with
+/// `require.load(files).then(function () { return require(id); })`, where
+/// `files` are files of chunks in `loads` which contain the module.
+pub(crate) fn link(
+    graph: &ModuleGraph,
+    data: &ModuleData,
+    helpers: &Helpers,
+    loads: &HashMap<ModuleId, Vec<String>>,
+) -> Module {
+    let module = if is_es_module(&data.module) {
+        let is_cjs = |src: &JsWord| match data.deps.iter().find(|d| d.src == *src) {
+            Some(dep) => !is_es_module(&graph[dep.module].module),
+            // External modules are provided by the host as commonjs modules.
+            None => true,
+        };
+        es_to_cjs(data.module.clone(), helpers, &is_cjs)
+    } else {
+        data.module.clone()
+    };
+
+    let ids = data
+        .deps
+        .iter()
+        .map(|d| (d.src.clone(), d.module))
+        .collect();
+    module.fold_with(&mut RequireIds { ids, loads })
+}
+
+fn is_es_module(module: &Module) -> bool {
+    module.body.iter().any(|item| match *item {
+        ModuleItem::ModuleDecl(..) => true,
+        _ => false,
+    })
+}
+
+/// How a module is imported.
+#[derive(Debug, Default)]
+struct Usage {
+    default: bool,
+    named: bool,
+    namespace: bool,
+}
+
+fn es_to_cjs(module: Module, helpers: &Helpers, is_cjs: &Fn(&JsWord) -> bool) -> Module {
+    let mut usages: HashMap<JsWord, Usage> = HashMap::new();
+    for item in &module.body {
+        match *item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(ref import)) => {
+                let usage = usages.entry(import.src.value.clone()).or_insert_with(Default::default);
+                for s in &import.specifiers {
+                    match *s {
+                        ImportSpecifier::Default(..) => usage.default = true,
+                        ImportSpecifier::Specific(..) => usage.named = true,
+                        ImportSpecifier::Namespace(..) => usage.namespace = true,
+                    }
+                }
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+                ref specifiers,
+                src: Some(ref src),
+                ..
+            })) => {
+                let usage = usages.entry(src.value.clone()).or_insert_with(Default::default);
+                for s in specifiers {
+                    if s.orig.sym == js_word!("default") {
+                        usage.default = true;
+                    } else {
+                        usage.named = true;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut r = Requires {
+        helpers,
+        is_cjs,
+        usages,
+        names: Names::new(&module),
+        tmps: HashMap::new(),
+        stmts: vec![],
+    };
+    let mut imports = HashMap::new();
+    let mut getters = vec![];
+    let mut stmts = vec![];
+
+    for item in module.body {
+        let decl = match item {
+            ModuleItem::Stmt(stmt) => {
+                stmts.push(stmt);
+                continue;
+            }
+            ModuleItem::ModuleDecl(decl) => decl,
+        };
+
+        match decl {
+            ModuleDecl::Import(import) => {
+                if import.specifiers.is_empty() {
+                    if !r.tmps.contains_key(&import.src.value) {
+                        r.stmts.push(Stmt::Expr(ExprStmt {
+                            span: DUMMY_SP,
+                            expr: require(import.src),
+                        }));
+                    }
+                    continue;
+                }
+
+                let tmp = r.tmp_for(&import.src);
+                for s in import.specifiers {
+                    let (local, imported) = match s {
+                        ImportSpecifier::Specific(ImportSpecific {
+                            local, imported, ..
+                        }) => {
+                            let imported = imported.unwrap_or_else(|| local.clone()).sym;
+                            (local, Some(imported))
+                        }
+                        ImportSpecifier::Default(ImportDefault { local, .. }) => {
+                            (local, Some(js_word!("default")))
+                        }
+                        ImportSpecifier::Namespace(ImportStarAs { local, .. }) => {
+                            (local, None)
+                        }
+                    };
+                    imports.insert(local.sym, (tmp.clone(), imported));
+                }
+            }
+
+            ModuleDecl::ExportDecl(ExportDecl { decl, .. }) => {
+                for name in decl_idents(&decl) {
+                    getters.push((name.sym.clone(), box Expr::Ident(name)));
+                }
+                stmts.push(Stmt::Decl(decl));
+            }
+
+            ModuleDecl::ExportNamed(NamedExport {
+                specifiers, src, ..
+            }) => {
+                let tmp = src.map(|src| r.tmp_for(&src));
+                for s in specifiers {
+                    let orig = s.orig;
+                    let exported = s.exported.unwrap_or_else(|| orig.clone()).sym;
+                    let value = match tmp {
+                        Some(ref tmp) => member(tmp.clone(), orig.sym),
+                        None => box Expr::Ident(orig),
+                    };
+                    getters.push((exported, value));
+                }
+            }
+
+            ModuleDecl::ExportDefaultDecl(ExportDefaultDecl { decl, .. }) => {
+                let decl = match decl {
+                    DefaultDecl::Fn(FnExpr { ident, function }) => {
+                        let ident = ident.unwrap_or_else(|| r.names.fresh("_default"));
+                        Decl::Fn(FnDecl {
+                            ident,
+                            declare: false,
+                            function,
+                        })
+                    }
+                    DefaultDecl::Class(ClassExpr { ident, class }) => {
+                        let ident = ident.unwrap_or_else(|| r.names.fresh("_default"));
+                        Decl::Class(ClassDecl {
+                            ident,
+                            declare: false,
+                            class,
+                        })
+                    }
+                    DefaultDecl::Var(var) => {
+                        stmts.push(Stmt::Decl(Decl::Var(var)));
+                        continue;
+                    }
+                };
+                let ident = decl_idents(&decl).remove(0);
+                getters.push((js_word!("default"), box Expr::Ident(ident)));
+                stmts.push(Stmt::Decl(decl));
+            }
+
+            ModuleDecl::ExportDefaultExpr(ExportDefaultExpr { expr, .. }) => {
+                let ident = r.names.fresh("_default");
+                getters.push((js_word!("default"), box Expr::Ident(ident.clone())));
+                stmts.push(var(ident, expr));
+            }
+
+            ModuleDecl::ExportAll(ExportAll { src, .. }) => {
+                r.helpers.export_star.store(true, Ordering::SeqCst);
+                let from = match r.tmps.get(&src.value) {
+                    Some(tmp) => box Expr::Ident(tmp.clone()),
+                    None => require(src),
+                };
+                r.stmts.push(Stmt::Expr(ExprStmt {
+                    span: DUMMY_SP,
+                    expr: call(
+                        quote_ident!("_exportStar"),
+                        vec![from, box Expr::Ident(quote_ident!("exports"))],
+                    ),
+                }));
+            }
+        }
+    }
+
+    // Directives must come first.
+    let has_use_strict = match stmts.first() {
+        Some(&Stmt::Expr(ExprStmt {
+            expr: box Expr::Lit(Lit::Str(Str { ref value, .. })),
+            ..
+        })) => &**value == "use strict",
+        _ => false,
+    };
+    if has_use_strict {
+        stmts.remove(0);
+    }
+
+    let mut body = vec![
+        Lit::Str(quote_str!("use strict")).into_stmt(),
+        define_property("__esModule".into(), value_descriptor()),
+    ];
+    // Exports are defined first, so they can be used by modules in a cycle.
+    body.extend(
+        getters
+            .into_iter()
+            .map(|(name, value)| define_property(name, getter_descriptor(value))),
+    );
+    body.extend(r.stmts);
+    body.extend(stmts);
+
+    let body = body.fold_with(&mut ImportRefs {
+        imports,
+        scopes: vec![],
+    });
+
+    Module {
+        span: module.span,
+        body: body.into_iter().map(ModuleItem::Stmt).collect(),
+        // Modules are wrapped by functions in a chunk.
+        shebang: None,
+    }
+}
+
+/// `require` calls of an es module.
+struct Requires<'a> {
+    helpers: &'a Helpers,
+    is_cjs: &'a Fn(&JsWord) -> bool,
+    usages: HashMap<JsWord, Usage>,
+    names: Names,
+    /// Variables holding imported modules.
+    tmps: HashMap<JsWord, Ident>,
+    stmts: Vec<Stmt>,
+}
+
+impl<'a> Requires<'a> {
+    /// Returns a variable holding the module imported from `src`.
+    fn tmp_for(&mut self, src: &Str) -> Ident {
+        if let Some(tmp) = self.tmps.get(&src.value) {
+            return tmp.clone();
+        }
+
+        let tmp = self.names.tmp_for(&src.value);
+        let init = require(src.clone());
+        let init = {
+            let usage = &self.usages[&src.value];
+            if !(self.is_cjs)(&src.value) {
+                init
+            } else if usage.namespace || (usage.default && usage.named) {
+                self.helpers
+                    .interop_require_wildcard
+                    .store(true, Ordering::SeqCst);
+                call(quote_ident!("_interopRequireWildcard"), vec![init])
+            } else if usage.default {
+                self.helpers
+                    .interop_require_default
+                    .store(true, Ordering::SeqCst);
+                call(quote_ident!("_interopRequireDefault"), vec![init])
+            } else {
+                init
+            }
+        };
+        self.stmts.push(var(tmp.clone(), init));
+        self.tmps.insert(src.value.clone(), tmp.clone());
+        tmp
+    }
+}
+
+fn decl_idents(decl: &Decl) -> Vec<Ident> {
+    match *decl {
+        Decl::Fn(FnDecl { ref ident, .. }) | Decl::Class(ClassDecl { ref ident, .. }) => {
+            vec![ident.clone()]
+        }
+        Decl::Var(ref var) => {
+            let mut ids = vec![];
+            for decl in &var.decls {
+                pat_ids(&decl.name, &mut ids);
+            }
+            ids
+        }
+        Decl::TsEnum(TsEnumDecl { ref id, .. }) => vec![id.clone()],
+        Decl::TsInterface(..) | Decl::TsTypeAlias(..) | Decl::TsModule(..) => vec![],
+    }
+}
+
+/// Returns `require('src')`.
+fn require(src: Str) -> Box<Expr> {
+    call(quote_ident!("require"), vec![box Expr::Lit(Lit::Str(src))])
+}
+
+fn call(callee: Ident, args: Vec<Box<Expr>>) -> Box<Expr> {
+    box Expr::Call(CallExpr {
+        span: DUMMY_SP,
+        callee: callee.as_callee(),
+        args: args
+            .into_iter()
+            .map(|expr| ExprOrSpread { spread: None, expr })
+            .collect(),
+    })
+}
+
+/// Returns `var name = init;`
+fn var(name: Ident, init: Box<Expr>) -> Stmt {
+    Stmt::Decl(Decl::Var(VarDecl {
+        span: DUMMY_SP,
+        kind: VarDeclKind::Var,
+        declare: false,
+        decls: vec![VarDeclarator {
+            span: DUMMY_SP,
+            name: Pat::Ident(name),
+            init: Some(init),
+        }],
+    }))
+}
+
+/// Returns `obj.prop`.
+fn member(obj: Ident, prop: JsWord) -> Box<Expr> {
+    box Expr::Member(MemberExpr {
+        span: DUMMY_SP,
+        obj: obj.as_callee(),
+        prop: box Expr::Ident(quote_ident!(prop)),
+        computed: false,
+    })
+}
+
+/// Returns `Object.defineProperty(exports, "name", descriptor);`
+fn define_property(name: JsWord, descriptor: Vec<PropOrSpread>) -> Stmt {
+    Expr::Call(CallExpr {
+        span: DUMMY_SP,
+        callee: member_expr!(DUMMY_SP, Object.defineProperty).as_callee(),
+        args: vec![
+            quote_ident!("exports").as_arg(),
+            Lit::Str(quote_str!(name)).as_arg(),
+            ObjectLit {
+                span: DUMMY_SP,
+                props: descriptor,
+            }
+            .as_arg(),
+        ],
+    })
+    .into_stmt()
+}
+
+fn prop(key: &str, value: Box<Expr>) -> PropOrSpread {
+    PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+        key: PropName::Ident(quote_ident!(key)),
+        value,
+    }))
+}
+
+fn bool_lit(value: bool) -> Box<Expr> {
+    box Expr::Lit(Lit::Bool(Bool {
+        span: DUMMY_SP,
+        value,
+    }))
+}
+
+/// `{ value: true }`
+fn value_descriptor() -> Vec<PropOrSpread> {
+    vec![prop("value", bool_lit(true))]
+}
+
+/// `{ enumerable: true, get: function () { return value; } }`
+fn getter_descriptor(value: Box<Expr>) -> Vec<PropOrSpread> {
+    vec![
+        prop("enumerable", bool_lit(true)),
+        prop("get", box Expr::Fn(returning(value))),
+    ]
+}
+
+/// `function () { return value; }`
+fn returning(value: Box<Expr>) -> FnExpr {
+    let function = Function {
+        params: vec![],
+        span: DUMMY_SP,
+        body: Some(BlockStmt {
+            span: DUMMY_SP,
+            stmts: vec![Stmt::Return(ReturnStmt {
+                span: DUMMY_SP,
+                arg: Some(value),
+            })],
+        }),
+        generator_token: None,
+        async_token: None,
+        type_params: None,
+        return_type: None,
+    };
+
+    FnExpr {
+        ident: None,
+        function,
+    }
+}
+
+/// Generates names which are not used by a module.
+struct Names(HashSet<JsWord>);
+
+impl Names {
+    fn new(module: &Module) -> Self {
+        let mut names = Names(HashSet::new());
+        module.visit_with(&mut names);
+        names
+    }
+
+    fn fresh(&mut self, base: &str) -> Ident {
+        let mut sym: JsWord = base.into();
+        let mut cnt = 1;
+        while self.0.contains(&sym) {
+            sym = format!("{}{}", base, cnt).into();
+            cnt += 1;
+        }
+        self.0.insert(sym.clone());
+
+        quote_ident!(sym)
+    }
+
+    /// `'./foo/bar-baz.js'` -> `_bar_baz`
+    fn tmp_for(&mut self, src: &str) -> Ident {
+        let stem = src
+            .rsplit('/')
+            .next()
+            .unwrap_or(src)
+            .split('.')
+            .next()
+            .unwrap_or("");
+        let stem: String = stem
+            .chars()
+            .map(|c| {
+                if c.is_alphanumeric() || c == '_' || c == '$' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+
+        self.fresh(&format!("_{}", stem))
+    }
+}
+
+impl Visit<Ident> for Names {
+    fn visit(&mut self, i: &Ident) {
+        self.0.insert(i.sym.clone());
+    }
+}
+
+/// Replaces imported bindings with properties of imported modules.
+struct ImportRefs {
+    /// Local names to modules and imported names. Imported name is `None` for
+    /// namespaces.
+    imports: HashMap<JsWord, (Ident, Option<JsWord>)>,
+    /// Names declared by enclosing functions and blocks.
+    scopes: Vec<HashSet<JsWord>>,
+}
+
+impl ImportRefs {
+    fn replace(&self, i: &Ident) -> Option<Box<Expr>> {
+        if self.scopes.iter().any(|scope| scope.contains(&i.sym)) {
+            return None;
+        }
+
+        let &(ref tmp, ref imported) = self.imports.get(&i.sym)?;
+        Some(match *imported {
+            Some(ref imported) => box Expr::Member(MemberExpr {
+                span: i.span,
+                obj: tmp.clone().as_callee(),
+                prop: box Expr::Ident(quote_ident!(imported.clone())),
+                computed: false,
+            }),
+            None => box Expr::Ident(tmp.clone()),
+        })
+    }
+
+    fn scoped<T>(&mut self, scope: HashSet<JsWord>, node: T) -> T
+    where
+        T: FoldWith<Self>,
+    {
+        self.scopes.push(scope);
+        let node = node.fold_children(self);
+        self.scopes.pop();
+        node
+    }
+}
+
+impl Fold<Expr> for ImportRefs {
+    fn fold(&mut self, e: Expr) -> Expr {
+        match e {
+            Expr::Ident(i) => match self.replace(&i) {
+                Some(box e) => e,
+                None => Expr::Ident(i),
+            },
+            _ => e.fold_children(self),
+        }
+    }
+}
+
+/// `foo()` -> `(0, _foo.foo)()`, so `this` is not the imported module.
+impl Fold<CallExpr> for ImportRefs {
+    fn fold(&mut self, call: CallExpr) -> CallExpr {
+        let is_named_import = match call.callee {
+            ExprOrSuper::Expr(box Expr::Ident(ref i)) => match self.replace(i) {
+                Some(box Expr::Member(..)) => true,
+                _ => false,
+            },
+            _ => false,
+        };
+
+        let call = call.fold_children(self);
+        if !is_named_import {
+            return call;
+        }
+
+        let callee = match call.callee {
+            ExprOrSuper::Expr(callee) => callee,
+            ExprOrSuper::Super(..) | ExprOrSuper::Import(..) => unreachable!(),
+        };
+        let zero = box Expr::Lit(Lit::Num(Number {
+            span: DUMMY_SP,
+            value: 0.0,
+            raw: None,
+        }));
+        CallExpr {
+            callee: ExprOrSuper::Expr(box Expr::Paren(ParenExpr {
+                span: DUMMY_SP,
+                expr: box Expr::Seq(SeqExpr {
+                    span: DUMMY_SP,
+                    exprs: vec![zero, callee],
+                }),
+            })),
+            ..call
+        }
+    }
+}
+
+/// Properties of `a.foo` are not references.
+impl Fold<MemberExpr> for ImportRefs {
+    fn fold(&mut self, e: MemberExpr) -> MemberExpr {
+        let obj = e.obj.fold_with(self);
+        let prop = if e.computed {
+            e.prop.fold_with(self)
+        } else {
+            e.prop
+        };
+
+        MemberExpr { obj, prop, ..e }
+    }
+}
+
+/// `{ foo }` -> `{ foo: _foo.foo }`
+impl Fold<Prop> for ImportRefs {
+    fn fold(&mut self, prop: Prop) -> Prop {
+        match prop {
+            Prop::Shorthand(i) => match self.replace(&i) {
+                Some(value) => Prop::KeyValue(KeyValueProp {
+                    key: PropName::Ident(i),
+                    value,
+                }),
+                None => Prop::Shorthand(i),
+            },
+            _ => prop.fold_children(self),
+        }
+    }
+}
+
+impl Fold<Function> for ImportRefs {
+    fn fold(&mut self, f: Function) -> Function {
+        let mut scope = HashSet::new();
+        for param in &f.params {
+            add_pat(&mut scope, &param.pat);
+        }
+        f.body.visit_with(&mut VarNames(&mut scope));
+
+        self.scoped(scope, f)
+    }
+}
+
+impl Fold<ArrowExpr> for ImportRefs {
+    fn fold(&mut self, f: ArrowExpr) -> ArrowExpr {
+        let mut scope = HashSet::new();
+        for param in &f.params {
+            add_pat(&mut scope, param);
+        }
+        if let BlockStmtOrExpr::BlockStmt(ref body) = f.body {
+            body.visit_with(&mut VarNames(&mut scope));
+        }
+
+        self.scoped(scope, f)
+    }
+}
+
+/// `function foo() {}` can refer to itself as `foo`.
+impl Fold<FnExpr> for ImportRefs {
+    fn fold(&mut self, f: FnExpr) -> FnExpr {
+        let scope = f.ident.iter().map(|i| i.sym.clone()).collect();
+        self.scoped(scope, f)
+    }
+}
+
+/// Lexical declarations.
+impl Fold<BlockStmt> for ImportRefs {
+    fn fold(&mut self, block: BlockStmt) -> BlockStmt {
+        let mut scope = HashSet::new();
+        for stmt in &block.stmts {
+            match *stmt {
+                Stmt::Decl(Decl::Fn(FnDecl { ref ident, .. }))
+                | Stmt::Decl(Decl::Class(ClassDecl { ref ident, .. })) => {
+                    scope.insert(ident.sym.clone());
+                }
+                Stmt::Decl(Decl::Var(ref var)) => match var.kind {
+                    VarDeclKind::Var => {}
+                    _ => {
+                        for decl in &var.decls {
+                            add_pat(&mut scope, &decl.name);
+                        }
+                    }
+                },
+                _ => {}
+            }
+        }
+
+        self.scoped(scope, block)
+    }
+}
+
+impl Fold<CatchClause> for ImportRefs {
+    fn fold(&mut self, c: CatchClause) -> CatchClause {
+        let mut scope = HashSet::new();
+        if let Some(ref param) = c.param {
+            add_pat(&mut scope, param);
+        }
+
+        self.scoped(scope, c)
+    }
+}
+
+fn add_pat(scope: &mut HashSet<JsWord>, pat: &Pat) {
+    let mut ids = vec![];
+    pat_ids(pat, &mut ids);
+    scope.extend(ids.into_iter().map(|i| i.sym));
+}
+
+/// Collects `var` and function declarations of a function body, excluding
+/// ones in nested functions.
+struct VarNames<'a>(&'a mut HashSet<JsWord>);
+
+impl<'a> Visit<VarDecl> for VarNames<'a> {
+    fn visit(&mut self, var: &VarDecl) {
+        if let VarDeclKind::Var = var.kind {
+            for decl in &var.decls {
+                add_pat(self.0, &decl.name);
+            }
+        }
+        var.visit_children(self)
+    }
+}
+
+impl<'a> Visit<FnDecl> for VarNames<'a> {
+    fn visit(&mut self, f: &FnDecl) {
+        self.0.insert(f.ident.sym.clone());
+    }
+}
+
+impl<'a> Visit<Function> for VarNames<'a> {
+    fn visit(&mut self, _: &Function) {}
+}
+
+impl<'a> Visit<ArrowExpr> for VarNames<'a> {
+    fn visit(&mut self, _: &ArrowExpr) {}
+}
+
+/// Replaces `require('./foo')` with `require(1)`, and `import('./foo')` with
+/// a call loading chunks before `require(1)`, using ids of imported modules.
+struct RequireIds<'a> {
+    ids: HashMap<JsWord, ModuleId>,
+    loads: &'a HashMap<ModuleId, Vec<String>>,
+}
+
+impl<'a> Fold<CallExpr> for RequireIds<'a> {
+    fn fold(&mut self, call: CallExpr) -> CallExpr {
+        let call = call.fold_children(self);
+
+        let id = dynamic_import_src(&call).and_then(|src| self.ids.get(&src.value).cloned());
+        if let Some(id) = id {
+            return self.load(call.span, id);
+        }
+
+        let id = require_src(&call).and_then(|src| self.ids.get(&src.value).cloned());
+        match id {
+            Some(id) => CallExpr {
+                args: vec![id_lit(id).as_arg()],
+                ..call
+            },
+            None => call,
+        }
+    }
+}
+
+impl<'a> RequireIds<'a> {
+    /// Returns `require.load(files).then(function () { return require(id); })`.
+    fn load(&self, span: Span, id: ModuleId) -> CallExpr {
+        let files = self.loads.get(&id).map(|files| &**files).unwrap_or(&[]);
+        let files = ArrayLit {
+            span: DUMMY_SP,
+            elems: files
+                .iter()
+                .map(|file| Some(Lit::Str(quote_str!(&**file)).as_arg()))
+                .collect(),
+        };
+        let load = CallExpr {
+            span: DUMMY_SP,
+            callee: ExprOrSuper::Expr(member(quote_ident!("require"), "load".into())),
+            args: vec![files.as_arg()],
+        };
+        let then = MemberExpr {
+            span: DUMMY_SP,
+            obj: load.as_callee(),
+            prop: box Expr::Ident(quote_ident!("then")),
+            computed: false,
+        };
+
+        CallExpr {
+            span,
+            callee: then.as_callee(),
+            args: vec![returning(call(quote_ident!("require"), vec![box id_lit(id)])).as_arg()],
+        }
+    }
+}
+
+fn id_lit(id: ModuleId) -> Expr {
+    Expr::Lit(Lit::Num(Number {
+        span: DUMMY_SP,
+        value: id.0 as f64,
+        raw: None,
+    }))
+}
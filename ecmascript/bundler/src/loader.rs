@@ -0,0 +1,223 @@
+use super::{
+    graph::{collect, Dep, ModuleData, ModuleGraph, ModuleId},
+    resolve::Resolve,
+};
+use ast::Module;
+use rayon::prelude::*;
+use slog::Logger;
+use std::{
+    error, fmt, io,
+    path::{Path, PathBuf},
+};
+use swc_atoms::JsWord;
+use swc_common::{errors::Handler, sync::Lrc, SourceMap, GLOBALS};
+use swc_ecma_parser::{EsConfig, Parser, Session, SourceFileInput, Syntax};
+
+/// Loads modules.
+pub struct Bundler<'a, R: Resolve> {
+    pub(crate) cm: Lrc<SourceMap>,
+    handler: &'a Handler,
+    logger: &'a Logger,
+    resolver: R,
+    externals: Vec<String>,
+}
+
+impl<'a, R: Resolve> Bundler<'a, R> {
+    pub fn new(
+        cm: Lrc<SourceMap>,
+        handler: &'a Handler,
+        logger: &'a Logger,
+        resolver: R,
+    ) -> Self {
+        Bundler {
+            cm,
+            handler,
+            logger,
+            resolver,
+            externals: vec![],
+        }
+    }
+
+    /// Leaves imports of `externals` to the host instead of bundling them.
+    ///
+    /// A pattern matches a specifier and its subpaths, so `react` matches
+    /// `react/jsx-runtime`. `*` at the end matches any suffix, like `node:*`.
+    pub fn with_externals(self, externals: Vec<String>) -> Self {
+        Bundler { externals, ..self }
+    }
+
+    fn is_external(&self, specifier: &str) -> bool {
+        self.externals.iter().any(|pattern| {
+            if pattern.ends_with('*') {
+                specifier.starts_with(&pattern[..pattern.len() - 1])
+            } else {
+                specifier.starts_with(&**pattern)
+                    && (specifier.len() == pattern.len()
+                        || specifier[pattern.len()..].starts_with('/'))
+            }
+        })
+    }
+
+    /// Loads `entries` and modules they import, except external modules.
+    ///
+    /// Modules found while loading the previous ones are parsed in parallel,
+    /// but ids are assigned in the same order as loading them one by one.
+    ///
+    /// Syntax errors are reported to the handler.
+    pub fn load(&self, entries: &[PathBuf]) -> Result<ModuleGraph, Error> {
+        let mut graph = ModuleGraph::default();
+        // Paths of modules in the order of ids.
+        let mut paths = vec![];
+
+        for entry in entries {
+            let id = intern(&mut graph, &mut paths, entry);
+            graph.entries.push(id);
+        }
+
+        while graph.modules.len() < paths.len() {
+            let pending = paths[graph.modules.len()..].to_vec();
+            let (cm, handler, logger) = (&*self.cm, self.handler, self.logger);
+            let modules: Vec<_> = GLOBALS.with(|globals| {
+                pending
+                    .par_iter()
+                    .map(|path| GLOBALS.set(globals, || parse(cm, handler, logger, path)))
+                    .collect()
+            });
+
+            for (path, module) in pending.into_iter().zip(modules) {
+                self.add(&mut graph, &mut paths, path, module?)?;
+            }
+        }
+
+        Ok(graph)
+    }
+
+    /// Resolves imports of `module` and adds it to `graph`.
+    fn add(
+        &self,
+        graph: &mut ModuleGraph,
+        paths: &mut Vec<PathBuf>,
+        path: PathBuf,
+        module: Module,
+    ) -> Result<(), Error> {
+        let (imports, exports) = collect(&module);
+        let mut deps = Vec::with_capacity(imports.len());
+        let mut externals = vec![];
+        for (src, kind) in imports {
+            if self.is_external(&src.value) {
+                if !externals.contains(&src.value) {
+                    externals.push(src.value);
+                }
+                continue;
+            }
+
+            let resolved = self
+                .resolver
+                .resolve(&path, &src.value)
+                .map_err(|err| Error {
+                    path: path.clone(),
+                    kind: ErrorKind::Resolve {
+                        specifier: src.value.clone(),
+                        err,
+                    },
+                })?;
+
+            deps.push(Dep {
+                module: intern(graph, paths, &resolved),
+                src: src.value,
+                span: src.span,
+                kind,
+            });
+        }
+
+        graph.modules.push(ModuleData {
+            side_effects: self.resolver.has_side_effects(&path),
+            path,
+            module,
+            deps,
+            externals,
+            exports,
+        });
+
+        Ok(())
+    }
+}
+
+/// Called from worker threads.
+fn parse(
+    cm: &SourceMap,
+    handler: &Handler,
+    logger: &Logger,
+    path: &Path,
+) -> Result<Module, Error> {
+    let err = |kind| Error {
+        path: path.to_path_buf(),
+        kind,
+    };
+
+    let fm = cm.load_file(path).map_err(|e| err(ErrorKind::Load(e)))?;
+    let session = Session { handler, logger };
+
+    // `import()` marks code splitting points.
+    let syntax = Syntax::Es(EsConfig {
+        dynamic_import: true,
+        ..Default::default()
+    });
+    Parser::new(session, syntax, SourceFileInput::from(&*fm))
+        .parse_module()
+        .map_err(|()| err(ErrorKind::Parse))
+}
+
+/// Returns the id of `path`, assigning a new one if it's not loaded yet.
+fn intern(graph: &mut ModuleGraph, paths: &mut Vec<PathBuf>, path: &Path) -> ModuleId {
+    if let Some(id) = graph.id_of(path) {
+        return id;
+    }
+
+    let id = ModuleId(paths.len());
+    paths.push(path.to_path_buf());
+    graph.ids.insert(path.to_path_buf(), id);
+    id
+}
+
+#[derive(Debug)]
+pub struct Error {
+    /// The module which failed to load, or which imports a module which can't
+    /// be resolved.
+    pub path: PathBuf,
+    pub kind: ErrorKind,
+}
+
+#[derive(Debug)]
+pub enum ErrorKind {
+    Load(io::Error),
+    /// Diagnostics are reported to the handler.
+    Parse,
+    Resolve {
+        specifier: JsWord,
+        err: Box<error::Error>,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            ErrorKind::Load(ref err) => {
+                write!(f, "failed to load {}: {}", self.path.display(), err)
+            }
+            ErrorKind::Parse => write!(f, "failed to parse {}", self.path.display()),
+            ErrorKind::Resolve {
+                ref specifier,
+                ref err,
+            } => write!(
+                f,
+                "failed to resolve '{}' from {}: {}",
+                specifier,
+                self.path.display(),
+                err
+            ),
+        }
+    }
+}
+
+impl error::Error for Error {}
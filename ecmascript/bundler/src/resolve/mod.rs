@@ -0,0 +1,44 @@
+//! Resolution of import specifiers.
+
+pub use self::node::NodeResolver;
+use std::{
+    error::Error,
+    path::{Path, PathBuf},
+};
+
+mod node;
+
+/// Finds the file imported by a module.
+pub trait Resolve {
+    /// Resolves `specifier` imported by the module at `base`.
+    ///
+    /// Modules are identified by their paths, so a file should be resolved to
+    /// the same path from any module.
+    fn resolve(&self, base: &Path, specifier: &str) -> Result<PathBuf, Box<Error>>;
+
+    /// Returns false if the module at `path` can be removed by tree shaking
+    /// when none of its exports are used.
+    fn has_side_effects(&self, _path: &Path) -> bool {
+        true
+    }
+}
+
+impl<'a, R: ?Sized + Resolve> Resolve for &'a R {
+    fn resolve(&self, base: &Path, specifier: &str) -> Result<PathBuf, Box<Error>> {
+        (**self).resolve(base, specifier)
+    }
+
+    fn has_side_effects(&self, path: &Path) -> bool {
+        (**self).has_side_effects(path)
+    }
+}
+
+impl<R: ?Sized + Resolve> Resolve for Box<R> {
+    fn resolve(&self, base: &Path, specifier: &str) -> Result<PathBuf, Box<Error>> {
+        (**self).resolve(base, specifier)
+    }
+
+    fn has_side_effects(&self, path: &Path) -> bool {
+        (**self).has_side_effects(path)
+    }
+}
@@ -0,0 +1,304 @@
+use super::Resolve;
+use serde_json::Value;
+use std::{
+    error::Error,
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+/// Resolves specifiers like node.js and bundlers do.
+///
+/// Relative specifiers are resolved from the importing module, and packages
+/// are looked up from `node_modules` directories of the importing module and
+/// its ancestors. Files are probed with `extensions`, and directories through
+/// `package.json` and `index` files.
+#[derive(Debug, Clone)]
+pub struct NodeResolver {
+    /// Appended to specifiers without extensions, in order.
+    pub extensions: Vec<String>,
+    /// Fields of `package.json` naming the entry of a package, in order. Used
+    /// only if the package doesn't have `exports`.
+    pub main_fields: Vec<String>,
+    /// Conditions matched against conditional `exports`. `default` always
+    /// matches.
+    pub conditions: Vec<String>,
+}
+
+impl Default for NodeResolver {
+    fn default() -> Self {
+        NodeResolver {
+            extensions: vec![".js".into(), ".mjs".into(), ".json".into()],
+            main_fields: vec!["module".into(), "main".into()],
+            conditions: vec!["import".into()],
+        }
+    }
+}
+
+impl Resolve for NodeResolver {
+    fn resolve(&self, base: &Path, specifier: &str) -> Result<PathBuf, Box<Error>> {
+        let dir = base.parent().unwrap_or(base);
+
+        let resolved = if specifier.starts_with("./")
+            || specifier.starts_with("../")
+            || specifier == "."
+            || specifier == ".."
+        {
+            self.resolve_path(&join(dir, specifier))
+        } else if Path::new(specifier).is_absolute() {
+            self.resolve_path(&join(Path::new("/"), specifier))
+        } else {
+            return self.resolve_package(dir, specifier);
+        };
+
+        resolved.ok_or_else(|| not_found(specifier, base))
+    }
+
+    /// Uses `sideEffects` of the nearest `package.json`, which is `false` or
+    /// globs of files with side effects.
+    fn has_side_effects(&self, path: &Path) -> bool {
+        let (dir, pkg) = match path
+            .ancestors()
+            .skip(1)
+            .filter_map(|dir| read_package_json(dir).map(|pkg| (dir, pkg)))
+            .next()
+        {
+            Some(v) => v,
+            None => return true,
+        };
+
+        match pkg.get("sideEffects") {
+            Some(&Value::Bool(side_effects)) => side_effects,
+            Some(&Value::Array(ref globs)) => {
+                let path = match path.strip_prefix(dir) {
+                    Ok(path) => path.to_string_lossy().replace('\\', "/"),
+                    Err(..) => return true,
+                };
+                globs.iter().filter_map(Value::as_str).any(|glob| {
+                    let glob = if glob.starts_with("./") {
+                        glob[2..].to_string()
+                    } else if !glob.contains('/') {
+                        // Matches files in any directory.
+                        format!("**/{}", glob)
+                    } else {
+                        glob.to_string()
+                    };
+                    glob_match(&glob, &path)
+                })
+            }
+            _ => true,
+        }
+    }
+}
+
+impl NodeResolver {
+    /// Resolves a path as a file or a directory.
+    fn resolve_path(&self, path: &Path) -> Option<PathBuf> {
+        self.resolve_file(path).or_else(|| self.resolve_dir(path))
+    }
+
+    fn resolve_file(&self, path: &Path) -> Option<PathBuf> {
+        if path.is_file() {
+            return Some(path.to_path_buf());
+        }
+
+        self.extensions
+            .iter()
+            .map(|ext| {
+                let mut path = path.as_os_str().to_owned();
+                path.push(ext);
+                PathBuf::from(path)
+            })
+            .find(|path| path.is_file())
+    }
+
+    fn resolve_dir(&self, dir: &Path) -> Option<PathBuf> {
+        if let Some(pkg) = read_package_json(dir) {
+            let main = self
+                .main_fields
+                .iter()
+                .filter_map(|field| pkg.get(&**field).and_then(Value::as_str))
+                .next();
+            if let Some(main) = main {
+                if let Some(path) = self.resolve_path(&join(dir, main)) {
+                    return Some(path);
+                }
+            }
+        }
+
+        self.resolve_file(&dir.join("index"))
+    }
+
+    /// Finds `specifier` in `node_modules` directories of `dir` and its
+    /// ancestors.
+    fn resolve_package(&self, dir: &Path, specifier: &str) -> Result<PathBuf, Box<Error>> {
+        let (name, subpath) = split_package(specifier);
+
+        for dir in dir.ancestors() {
+            if dir.file_name().map(|name| name == "node_modules").unwrap_or(false) {
+                continue;
+            }
+
+            let pkg_dir = dir.join("node_modules").join(name);
+            if !pkg_dir.is_dir() {
+                continue;
+            }
+
+            let pkg = read_package_json(&pkg_dir);
+            if let Some(exports) = pkg.as_ref().and_then(|pkg| pkg.get("exports")) {
+                // Only paths listed in `exports` can be imported.
+                let subpath = format!(".{}", subpath);
+                return self
+                    .resolve_exports(&pkg_dir, exports, &subpath)
+                    .ok_or_else(|| {
+                        format!("'{}' is not exported by {}", subpath, pkg_dir.display()).into()
+                    });
+            }
+
+            let path = if subpath.is_empty() {
+                self.resolve_dir(&pkg_dir)
+            } else {
+                self.resolve_path(&join(&pkg_dir, &subpath[1..]))
+            };
+            return path.ok_or_else(|| not_found(specifier, dir));
+        }
+
+        Err(not_found(specifier, dir))
+    }
+
+    /// Resolves `subpath` (`.` or `./foo`) using `exports` of a package.
+    fn resolve_exports(&self, pkg_dir: &Path, exports: &Value, subpath: &str) -> Option<PathBuf> {
+        let is_subpath_map = match *exports {
+            Value::Object(ref map) => map.keys().any(|key| key.starts_with('.')),
+            _ => false,
+        };
+        if !is_subpath_map {
+            // `"exports": "./index.js"` or conditions
+            return if subpath == "." {
+                self.resolve_target(pkg_dir, exports, "")
+            } else {
+                None
+            };
+        }
+
+        let map = exports.as_object()?;
+        if let Some(target) = map.get(subpath) {
+            return self.resolve_target(pkg_dir, target, "");
+        }
+
+        // `"./features/*": "./src/features/*.js"`, and deprecated
+        // `"./features/": "./src/features/"`
+        map.iter()
+            .filter_map(|(key, target)| {
+                if let Some(star) = key.find('*') {
+                    let (prefix, suffix) = (&key[..star], &key[star + 1..]);
+                    if subpath.len() >= key.len() - 1
+                        && subpath.starts_with(prefix)
+                        && subpath.ends_with(suffix)
+                    {
+                        let matched = &subpath[prefix.len()..subpath.len() - suffix.len()];
+                        return Some((prefix.len(), target, matched));
+                    }
+                } else if key.ends_with('/') && subpath.starts_with(&**key) {
+                    return Some((key.len(), target, &subpath[key.len()..]));
+                }
+                None
+            })
+            // The longest prefix wins.
+            .max_by_key(|&(len, ..)| len)
+            .and_then(|(_, target, matched)| self.resolve_target(pkg_dir, target, matched))
+    }
+
+    /// Resolves a target of `exports`, replacing `*` with `matched`.
+    fn resolve_target(&self, pkg_dir: &Path, target: &Value, matched: &str) -> Option<PathBuf> {
+        match *target {
+            Value::String(ref target) => {
+                let target = if target.contains('*') {
+                    target.replace('*', matched)
+                } else {
+                    format!("{}{}", target, matched)
+                };
+                let path = join(pkg_dir, &target);
+                if path.is_file() {
+                    Some(path)
+                } else {
+                    None
+                }
+            }
+            Value::Object(ref conditions) => conditions
+                .iter()
+                .filter(|&(cond, _)| cond == "default" || self.conditions.contains(cond))
+                .filter_map(|(_, target)| self.resolve_target(pkg_dir, target, matched))
+                .next(),
+            Value::Array(ref targets) => targets
+                .iter()
+                .filter_map(|target| self.resolve_target(pkg_dir, target, matched))
+                .next(),
+            _ => None,
+        }
+    }
+}
+
+/// `@scope/pkg/foo` -> (`@scope/pkg`, `/foo`)
+fn split_package(specifier: &str) -> (&str, &str) {
+    let skip = if specifier.starts_with('@') { 2 } else { 1 };
+    let end = specifier
+        .match_indices('/')
+        .nth(skip - 1)
+        .map(|(i, _)| i)
+        .unwrap_or_else(|| specifier.len());
+
+    specifier.split_at(end)
+}
+
+/// Matches `path` against `glob`, where `*` matches anything except `/` and
+/// `**/` matches any directories.
+fn glob_match(glob: &str, path: &str) -> bool {
+    if glob.starts_with("**/") {
+        return glob_match(&glob[3..], path)
+            || path
+                .find('/')
+                .map(|i| glob_match(glob, &path[i + 1..]))
+                .unwrap_or(false);
+    }
+
+    match glob.chars().next() {
+        None => path.is_empty(),
+        Some('*') => {
+            let mut i = 0;
+            loop {
+                if glob_match(&glob[1..], &path[i..]) {
+                    return true;
+                }
+                match path[i..].chars().next() {
+                    None | Some('/') => return false,
+                    Some(c) => i += c.len_utf8(),
+                }
+            }
+        }
+        Some(c) => path.starts_with(c) && glob_match(&glob[c.len_utf8()..], &path[c.len_utf8()..]),
+    }
+}
+
+fn read_package_json(dir: &Path) -> Option<Value> {
+    let s = fs::read_to_string(dir.join("package.json")).ok()?;
+    serde_json::from_str(&s).ok()
+}
+
+/// Joins `path` to `base`, removing `.` and `..`.
+fn join(base: &Path, path: &str) -> PathBuf {
+    let mut buf = base.to_path_buf();
+    for c in Path::new(path).components() {
+        match c {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                buf.pop();
+            }
+            c => buf.push(c.as_os_str()),
+        }
+    }
+    buf
+}
+
+fn not_found(specifier: &str, base: &Path) -> Box<Error> {
+    format!("cannot find module '{}' from {}", specifier, base.display()).into()
+}
@@ -0,0 +1,60 @@
+(function (modules, entry, files) {
+    var root = typeof self !== "undefined" ? self : global;
+    var base =
+        typeof document !== "undefined" && document.currentScript
+            ? document.currentScript.src.replace(/[^/]*$/, "")
+            : "";
+    var cache = {};
+    var loading = {};
+
+    function __swc_require(id) {
+        // External modules are required by specifiers.
+        if (typeof id === "string") {
+            return require(id);
+        }
+        if (cache[id]) {
+            return cache[id].exports;
+        }
+        var module = (cache[id] = { exports: {} });
+        modules[id].call(module.exports, module, module.exports, __swc_require);
+        return module.exports;
+    }
+
+    // Called by chunks to add their modules.
+    root.__swcRegister = function (chunk) {
+        for (var id in chunk) {
+            modules[id] = chunk[id];
+        }
+    };
+
+    // Loads chunks which are not loaded yet.
+    __swc_require.load = function (files) {
+        return Promise.all(
+            files.map(function (file) {
+                if (!loading[file]) {
+                    loading[file] = new Promise(function (resolve, reject) {
+                        if (typeof document === "undefined") {
+                            require("./" + file);
+                            resolve();
+                            return;
+                        }
+                        var script = document.createElement("script");
+                        script.src = base + file;
+                        script.onload = resolve;
+                        script.onerror = reject;
+                        document.head.appendChild(script);
+                    });
+                }
+                return loading[file];
+            })
+        );
+    };
+
+    if (files.length === 0) {
+        __swc_require(entry);
+    } else {
+        __swc_require.load(files).then(function () {
+            __swc_require(entry);
+        });
+    }
+})
@@ -0,0 +1,623 @@
+use super::graph::{collect, dynamic_imports, requires, Dep, ModuleGraph, ModuleId};
+use ast::*;
+use std::{
+    collections::{HashMap, HashSet},
+    mem,
+};
+use swc_atoms::JsWord;
+use swc_common::{Visit, VisitWith};
+use swc_ecma_transforms::util::{pat_ids, ExprExt};
+
+impl ModuleGraph {
+    /// Removes code which is not used by entries.
+    ///
+    /// All exports of entries are used. Exports of other modules are kept only
+    /// if they are imported by code which is kept, and top-level declarations
+    /// are kept only if they are exported or used by code which is kept.
+    /// Statements with side effects are always kept.
+    ///
+    /// A module which has no side effects (see [Resolve::has_side_effects])
+    /// is not imported anymore if none of its exports are used. It stays in
+    /// the graph, but it's not reachable from entries.
+    ///
+    /// References are matched by names, so a top-level declaration shadowed
+    /// in a nested scope is kept if the shadowing name is used.
+    ///
+    /// [Resolve::has_side_effects]: ::Resolve::has_side_effects
+    pub fn shake(&mut self) {
+        let analyses: Vec<_> = self.modules.iter().map(|m| Analysis::new(&m.module)).collect();
+        let mut used: Vec<Used> = self.modules.iter().map(|_| Used::default()).collect();
+        let mut included = vec![false; self.modules.len()];
+
+        let mut queue = self.entries.clone();
+        for &entry in &self.entries {
+            used[entry.0].all = true;
+            included[entry.0] = true;
+        }
+
+        while let Some(id) = queue.pop() {
+            let reach = analyses[id.0].reach(&used[id.0]);
+
+            for (src, demand) in reach.demands {
+                if self.modules[id.0].externals.contains(&src) {
+                    continue;
+                }
+                let dep = dep_of(&self.modules[id.0].deps, &src);
+                let changed = used[dep.0].merge(demand);
+                let include = self.modules[dep.0].side_effects || !used[dep.0].is_empty();
+
+                if include && (changed || !included[dep.0]) {
+                    included[dep.0] = true;
+                    queue.push(dep);
+                }
+            }
+        }
+
+        for (i, analysis) in analyses.iter().enumerate() {
+            if included[i] {
+                let reach = analysis.reach(&used[i]);
+                self.shake_module(i, &reach, &used[i]);
+            }
+        }
+    }
+
+    fn shake_module(&mut self, i: usize, reach: &Reach, used: &Used) {
+        let effects: HashMap<_, _> = self.modules[i]
+            .deps
+            .iter()
+            .map(|d| (d.src.clone(), self.modules[d.module.0].side_effects))
+            .collect();
+        // `import 'x'` if `x` has side effects. External modules are assumed to
+        // have side effects.
+        let side_effect_import = |span, src: Str| {
+            if effects.get(&src.value).cloned().unwrap_or(true) {
+                Some(ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                    span,
+                    specifiers: vec![],
+                    src,
+                })))
+            } else {
+                None
+            }
+        };
+
+        let data = &mut self.modules[i];
+        let body = mem::replace(&mut data.module.body, vec![]);
+        data.module.body = body
+            .into_iter()
+            .zip(&reach.reached)
+            .filter_map(|(item, &reached)| match item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(mut import)) => {
+                    if import.specifiers.is_empty() {
+                        return side_effect_import(import.span, import.src);
+                    }
+                    import
+                        .specifiers
+                        .retain(|s| reach.refs.contains(&import_local(s).sym));
+                    if import.specifiers.is_empty() {
+                        side_effect_import(import.span, import.src)
+                    } else {
+                        Some(ModuleItem::ModuleDecl(ModuleDecl::Import(import)))
+                    }
+                }
+
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => {
+                    if !reached {
+                        None
+                    } else if decl_names(&export.decl).iter().any(|name| used.contains(name)) {
+                        Some(ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)))
+                    } else {
+                        Some(ModuleItem::Stmt(Stmt::Decl(export.decl)))
+                    }
+                }
+
+                ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(mut export)) => {
+                    export.specifiers.retain(|s| {
+                        used.contains(&s.exported.as_ref().unwrap_or(&s.orig).sym)
+                    });
+                    if !export.specifiers.is_empty() {
+                        Some(ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)))
+                    } else if let Some(src) = export.src {
+                        side_effect_import(export.span, src)
+                    } else {
+                        None
+                    }
+                }
+
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(export)) => {
+                    if used.contains(&js_word!("default")) {
+                        return Some(ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(export)));
+                    }
+                    if !reached {
+                        return None;
+                    }
+                    let ExportDefaultDecl { span, decl } = export;
+                    match decl {
+                        DefaultDecl::Fn(FnExpr {
+                            ident: Some(ident),
+                            function,
+                        }) => Some(ModuleItem::Stmt(Stmt::Decl(Decl::Fn(FnDecl {
+                            ident,
+                            declare: false,
+                            function,
+                        })))),
+                        DefaultDecl::Class(ClassExpr {
+                            ident: Some(ident),
+                            class,
+                        }) => Some(ModuleItem::Stmt(Stmt::Decl(Decl::Class(ClassDecl {
+                            ident,
+                            declare: false,
+                            class,
+                        })))),
+                        DefaultDecl::Class(class) => Some(ModuleItem::Stmt(Stmt::Expr(ExprStmt {
+                            span,
+                            expr: box Expr::Class(class),
+                        }))),
+                        DefaultDecl::Fn(..) => None,
+                        DefaultDecl::Var(var) => {
+                            Some(ModuleItem::Stmt(Stmt::Decl(Decl::Var(var))))
+                        }
+                    }
+                }
+
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(export)) => {
+                    if used.contains(&js_word!("default")) {
+                        Some(ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(export)))
+                    } else if reached {
+                        Some(ModuleItem::Stmt(Stmt::Expr(ExprStmt {
+                            span: export.span,
+                            expr: export.expr,
+                        })))
+                    } else {
+                        None
+                    }
+                }
+
+                ModuleItem::ModuleDecl(ModuleDecl::ExportAll(export)) => {
+                    if reach.star_used {
+                        Some(ModuleItem::ModuleDecl(ModuleDecl::ExportAll(export)))
+                    } else {
+                        side_effect_import(export.span, export.src)
+                    }
+                }
+
+                ModuleItem::Stmt(stmt) => {
+                    if reached {
+                        Some(ModuleItem::Stmt(stmt))
+                    } else {
+                        None
+                    }
+                }
+            })
+            .collect();
+
+        let (imports, exports) = collect(&data.module);
+        let externals = mem::replace(&mut data.externals, vec![]);
+        data.externals = externals
+            .into_iter()
+            .filter(|src| imports.iter().any(|&(ref import, _)| import.value == *src))
+            .collect();
+        let deps = imports
+            .into_iter()
+            .filter(|&(ref src, _)| !data.externals.contains(&src.value))
+            .map(|(src, kind)| Dep {
+                module: dep_of(&data.deps, &src.value),
+                src: src.value,
+                span: src.span,
+                kind,
+            })
+            .collect();
+        data.deps = deps;
+        data.exports = exports;
+    }
+}
+
+fn dep_of(deps: &[Dep], src: &JsWord) -> ModuleId {
+    deps.iter()
+        .find(|d| d.src == *src)
+        .map(|d| d.module)
+        .expect("all imports should be resolved")
+}
+
+/// Used exports of a module.
+#[derive(Debug, Default)]
+struct Used {
+    all: bool,
+    names: HashSet<JsWord>,
+}
+
+impl Used {
+    fn contains(&self, name: &JsWord) -> bool {
+        self.all || self.names.contains(name)
+    }
+
+    fn is_empty(&self) -> bool {
+        !self.all && self.names.is_empty()
+    }
+
+    /// Returns true if `self` is changed.
+    fn merge(&mut self, other: Used) -> bool {
+        if self.all {
+            return false;
+        }
+        if other.all {
+            self.all = true;
+            return true;
+        }
+
+        let len = self.names.len();
+        self.names.extend(other.names);
+        self.names.len() != len
+    }
+}
+
+/// Local name of `export default` without a name.
+const DEFAULT: &str = "*default*";
+
+/// A top-level item of a module.
+struct Item {
+    /// Top-level names declared by the item.
+    decls: Vec<JsWord>,
+    /// Names used by the item.
+    refs: HashSet<JsWord>,
+    side_effects: bool,
+}
+
+struct Analysis {
+    items: Vec<Item>,
+    /// Exported names to local names.
+    locals: HashMap<JsWord, JsWord>,
+    /// Exported names to sources and imported names.
+    reexports: HashMap<JsWord, (JsWord, JsWord)>,
+    /// Sources of `export *`.
+    stars: Vec<JsWord>,
+    /// Local names, sources and imported names. Imported name is `None` for
+    /// `import * as ns`.
+    imports: Vec<(JsWord, JsWord, Option<JsWord>)>,
+    /// Sources of `require` calls and `import()`.
+    requires: Vec<JsWord>,
+    srcs: Vec<JsWord>,
+}
+
+/// Code of a module used by its exports.
+struct Reach {
+    /// Indexed by items.
+    reached: Vec<bool>,
+    /// Names used by reached items and exports.
+    refs: HashSet<JsWord>,
+    /// Used exports of imported modules, by sources.
+    demands: HashMap<JsWord, Used>,
+    /// True if names are re-exported from `export *`.
+    star_used: bool,
+}
+
+impl Analysis {
+    fn new(module: &Module) -> Self {
+        let mut analysis = Analysis {
+            items: Vec::with_capacity(module.body.len()),
+            locals: HashMap::new(),
+            reexports: HashMap::new(),
+            stars: vec![],
+            imports: vec![],
+            requires: vec![],
+            srcs: vec![],
+        };
+
+        for item in &module.body {
+            let item = match *item {
+                ModuleItem::ModuleDecl(ref decl) => analysis.module_decl(decl),
+                ModuleItem::Stmt(Stmt::Decl(ref decl)) => stmt_decl(decl),
+                ModuleItem::Stmt(ref stmt) => Item {
+                    decls: vec![],
+                    refs: refs(stmt),
+                    side_effects: true,
+                },
+            };
+            analysis.items.push(item);
+        }
+
+        analysis.requires = requires(module)
+            .into_iter()
+            .chain(dynamic_imports(module))
+            .map(|src| src.value)
+            .collect();
+        analysis.srcs.extend(analysis.requires.iter().cloned());
+
+        analysis
+    }
+
+    fn module_decl(&mut self, decl: &ModuleDecl) -> Item {
+        let mut item = Item {
+            decls: vec![],
+            refs: HashSet::new(),
+            side_effects: false,
+        };
+
+        match *decl {
+            ModuleDecl::Import(ref import) => {
+                self.srcs.push(import.src.value.clone());
+                for s in &import.specifiers {
+                    let imported = match *s {
+                        ImportSpecifier::Specific(ref s) => {
+                            Some(s.imported.as_ref().unwrap_or(&s.local).sym.clone())
+                        }
+                        ImportSpecifier::Default(..) => Some(js_word!("default")),
+                        ImportSpecifier::Namespace(..) => None,
+                    };
+                    let local = import_local(s).sym.clone();
+                    item.decls.push(local.clone());
+                    self.imports
+                        .push((local, import.src.value.clone(), imported));
+                }
+            }
+
+            ModuleDecl::ExportDecl(ExportDecl { ref decl, .. }) => {
+                item = stmt_decl(decl);
+                for name in &item.decls {
+                    self.locals.insert(name.clone(), name.clone());
+                }
+            }
+
+            ModuleDecl::ExportNamed(NamedExport {
+                ref specifiers,
+                ref src,
+                ..
+            }) => {
+                for s in specifiers {
+                    let exported = s.exported.as_ref().unwrap_or(&s.orig).sym.clone();
+                    match *src {
+                        Some(ref src) => {
+                            self.reexports
+                                .insert(exported, (src.value.clone(), s.orig.sym.clone()));
+                        }
+                        None => {
+                            self.locals.insert(exported, s.orig.sym.clone());
+                        }
+                    }
+                }
+                if let Some(ref src) = *src {
+                    self.srcs.push(src.value.clone());
+                }
+            }
+
+            ModuleDecl::ExportDefaultDecl(ExportDefaultDecl { ref decl, .. }) => {
+                let (ident, side_effects) = match *decl {
+                    DefaultDecl::Fn(FnExpr { ref ident, .. }) => (ident.as_ref(), false),
+                    DefaultDecl::Class(ClassExpr {
+                        ref ident,
+                        ref class,
+                    }) => (ident.as_ref(), class_has_side_effects(class)),
+                    DefaultDecl::Var(..) => (None, true),
+                };
+                item.decls.extend(ident.map(|i| i.sym.clone()));
+                item.decls.push(DEFAULT.into());
+                item.refs = refs(decl);
+                item.side_effects = side_effects;
+                self.locals.insert(js_word!("default"), DEFAULT.into());
+            }
+
+            ModuleDecl::ExportDefaultExpr(ExportDefaultExpr { ref expr, .. }) => {
+                item.decls.push(DEFAULT.into());
+                item.refs = refs(&**expr);
+                item.side_effects = expr.may_have_side_effects();
+                self.locals.insert(js_word!("default"), DEFAULT.into());
+            }
+
+            ModuleDecl::ExportAll(ExportAll { ref src, .. }) => {
+                self.stars.push(src.value.clone());
+                self.srcs.push(src.value.clone());
+            }
+        }
+
+        item
+    }
+
+    /// Finds code used by `used` exports, and exports of imported modules used
+    /// by it.
+    fn reach(&self, used: &Used) -> Reach {
+        let mut demands: HashMap<_, _> = self
+            .srcs
+            .iter()
+            .map(|src| (src.clone(), Used::default()))
+            .collect();
+        let mut star_used = false;
+        // A required or dynamically imported module is used as a whole.
+        for src in &self.requires {
+            demands.get_mut(src).unwrap().all = true;
+        }
+
+        let names: Vec<JsWord> = if used.all {
+            for star in &self.stars {
+                demands.get_mut(star).unwrap().all = true;
+            }
+            star_used = !self.stars.is_empty();
+            self.locals
+                .keys()
+                .chain(self.reexports.keys())
+                .cloned()
+                .collect()
+        } else {
+            used.names.iter().cloned().collect()
+        };
+
+        let mut roots = vec![];
+        for name in names {
+            if let Some(local) = self.locals.get(&name) {
+                roots.push(local.clone());
+            } else if let Some(&(ref src, ref orig)) = self.reexports.get(&name) {
+                demands.get_mut(src).unwrap().names.insert(orig.clone());
+            } else {
+                for star in &self.stars {
+                    demands.get_mut(star).unwrap().names.insert(name.clone());
+                    star_used = true;
+                }
+            }
+        }
+
+        let mut declared_by: HashMap<&JsWord, Vec<usize>> = HashMap::new();
+        for (i, item) in self.items.iter().enumerate() {
+            for name in &item.decls {
+                declared_by.entry(name).or_insert_with(Vec::new).push(i);
+            }
+        }
+
+        let mut reached = vec![false; self.items.len()];
+        let mut refs = HashSet::new();
+        let mut items: Vec<_> = (0..self.items.len())
+            .filter(|&i| self.items[i].side_effects)
+            .collect();
+        let mut names = vec![];
+        for root in roots {
+            if refs.insert(root.clone()) {
+                names.push(root);
+            }
+        }
+
+        loop {
+            while let Some(i) = items.pop() {
+                if reached[i] {
+                    continue;
+                }
+                reached[i] = true;
+                for name in &self.items[i].refs {
+                    if refs.insert(name.clone()) {
+                        names.push(name.clone());
+                    }
+                }
+            }
+
+            match names.pop() {
+                Some(name) => {
+                    if let Some(decls) = declared_by.get(&name) {
+                        items.extend(decls);
+                    }
+                }
+                None => break,
+            }
+        }
+
+        for &(ref local, ref src, ref imported) in &self.imports {
+            if refs.contains(local) {
+                let demand = demands.get_mut(src).unwrap();
+                match *imported {
+                    Some(ref imported) => {
+                        demand.names.insert(imported.clone());
+                    }
+                    None => demand.all = true,
+                }
+            }
+        }
+
+        Reach {
+            reached,
+            refs,
+            demands,
+            star_used,
+        }
+    }
+}
+
+fn stmt_decl(decl: &Decl) -> Item {
+    let side_effects = match *decl {
+        Decl::Fn(..) => false,
+        Decl::Class(ClassDecl { ref class, .. }) => class_has_side_effects(class),
+        Decl::Var(ref var) => var.decls.iter().any(|decl| {
+            // Destructuring may call getters.
+            let is_ident = match decl.name {
+                Pat::Ident(..) => true,
+                _ => false,
+            };
+            !is_ident || decl.init.as_ref().map(|e| e.may_have_side_effects()).unwrap_or(false)
+        }),
+        // Enums and namespaces create objects.
+        Decl::TsEnum(..) | Decl::TsModule(..) => true,
+        Decl::TsInterface(..) | Decl::TsTypeAlias(..) => false,
+    };
+
+    Item {
+        decls: decl_names(decl),
+        refs: refs(decl),
+        side_effects,
+    }
+}
+
+fn decl_names(decl: &Decl) -> Vec<JsWord> {
+    match *decl {
+        Decl::Fn(FnDecl { ref ident, .. }) | Decl::Class(ClassDecl { ref ident, .. }) => {
+            vec![ident.sym.clone()]
+        }
+        Decl::Var(ref var) => {
+            let mut ids = vec![];
+            for decl in &var.decls {
+                pat_ids(&decl.name, &mut ids);
+            }
+            ids.into_iter().map(|i| i.sym).collect()
+        }
+        Decl::TsEnum(TsEnumDecl { ref id, .. }) => vec![id.sym.clone()],
+        Decl::TsInterface(..) | Decl::TsTypeAlias(..) | Decl::TsModule(..) => vec![],
+    }
+}
+
+/// Defining a class evaluates its super class and computed keys.
+fn class_has_side_effects(class: &Class) -> bool {
+    let super_class = class
+        .super_class
+        .as_ref()
+        .map(|e| e.may_have_side_effects())
+        .unwrap_or(false);
+
+    super_class
+        || class.body.iter().any(|m| match *m {
+            ClassMember::Method(ClassMethod {
+                key: PropName::Computed(ref e),
+                ..
+            }) => e.expr.may_have_side_effects(),
+            ClassMember::ClassProp(ClassProp {
+                ref key,
+                ref value,
+                ref static_token,
+                ..
+            }) => {
+                let key = match *key {
+                    PropName::Computed(ref e) => e.expr.may_have_side_effects(),
+                    _ => false,
+                };
+                let value = match *value {
+                    Some(ref value) => static_token.is_some() && value.may_have_side_effects(),
+                    None => false,
+                };
+                key || value
+            }
+            ClassMember::PrivateProp(PrivateProp {
+                value: Some(ref value),
+                static_token: Some(..),
+                ..
+            }) => value.may_have_side_effects(),
+            ClassMember::StaticBlock(..) => true,
+            _ => false,
+        })
+}
+
+fn import_local(s: &ImportSpecifier) -> &Ident {
+    match *s {
+        ImportSpecifier::Specific(ImportSpecific { ref local, .. })
+        | ImportSpecifier::Default(ImportDefault { ref local, .. })
+        | ImportSpecifier::Namespace(ImportStarAs { ref local, .. }) => local,
+    }
+}
+
+/// Collects names used by `node`, including names which are not references,
+/// like property names.
+fn refs<T: VisitWith<Refs>>(node: &T) -> HashSet<JsWord> {
+    let mut refs = Refs(HashSet::new());
+    node.visit_with(&mut refs);
+    refs.0
+}
+
+struct Refs(HashSet<JsWord>);
+
+impl Visit<Ident> for Refs {
+    fn visit(&mut self, i: &Ident) {
+        self.0.insert(i.sym.clone());
+    }
+}
@@ -0,0 +1,408 @@
+use super::*;
+use std::{
+    error,
+    path::{Component, Path, PathBuf},
+};
+
+pub(crate) fn fixture(path: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixture")
+        .join(path)
+}
+
+/// Resolves relative specifiers, appending `.js` if there's no extension.
+pub(crate) struct Relative;
+
+impl Resolve for Relative {
+    fn resolve(&self, base: &Path, specifier: &str) -> Result<PathBuf, Box<error::Error>> {
+        let mut path = base.parent().unwrap().to_path_buf();
+        for c in Path::new(specifier).components() {
+            match c {
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    path.pop();
+                }
+                c => path.push(c.as_os_str()),
+            }
+        }
+        if path.extension().is_none() {
+            path.set_extension("js");
+        }
+
+        if path.is_file() {
+            Ok(path)
+        } else {
+            Err("not found".into())
+        }
+    }
+}
+
+/// Loads `entries` from the fixture directory.
+pub(crate) fn load<R: Resolve>(resolver: R, entries: &[&str]) -> Result<ModuleGraph, Error> {
+    let entries: Vec<_> = entries.iter().map(|e| fixture(e)).collect();
+
+    ::testing::run_test(|logger, cm, handler| {
+        Ok(Bundler::new(cm, handler, &logger, resolver).load(&entries))
+    })
+    .unwrap_or_else(|stderr| panic!("Stderr:\n{}", stderr))
+}
+
+#[test]
+fn graph() {
+    let graph = load(Relative, &["graph/a.js"]).unwrap();
+    let id = |name: &str| graph.id_of(&fixture(name)).unwrap();
+
+    assert_eq!(graph.len(), 5);
+    assert_eq!(graph.entries(), &[id("graph/a.js")]);
+
+    let a = &graph[id("graph/a.js")];
+    let deps: Vec<_> = a.deps.iter().map(|d| (&*d.src, d.module, &d.kind)).collect();
+    assert_eq!(
+        deps,
+        vec![
+            (
+                "./b",
+                id("graph/b.js"),
+                &DepKind::Import(vec!["default".into(), "c".into()])
+            ),
+            ("./c.js", id("graph/c.js"), &DepKind::ImportAll),
+            ("./d", id("graph/d.js"), &DepKind::ReExportAll),
+            (
+                "./e",
+                id("graph/e.js"),
+                &DepKind::ReExport(vec![("e".into(), "f".into())])
+            ),
+        ]
+    );
+    let exports: Vec<_> = a.exports.iter().map(|e| &**e).collect();
+    assert_eq!(exports, ["f", "g", "default"]);
+
+    assert_eq!(
+        graph.dependents(id("graph/c.js")),
+        vec![id("graph/a.js"), id("graph/b.js")]
+    );
+    assert_eq!(graph.dependents(id("graph/a.js")), vec![id("graph/d.js")]);
+
+    assert_eq!(
+        graph.sorted(),
+        ["c", "b", "d", "e", "a"]
+            .iter()
+            .map(|name| id(&format!("graph/{}.js", name)))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn unresolved() {
+    let err = load(Relative, &["missing/a.js"]).unwrap_err();
+
+    assert_eq!(err.path, fixture("missing/a.js"));
+    match err.kind {
+        ErrorKind::Resolve { ref specifier, .. } => assert_eq!(&**specifier, "./missing"),
+        _ => panic!("unexpected error: {}", err),
+    }
+}
+
+#[test]
+fn node_resolver() {
+    let resolve = |resolver: &NodeResolver, base: &str, specifier: &str| {
+        resolver
+            .resolve(&fixture(base), specifier)
+            .map(|path| path.strip_prefix(fixture("node")).unwrap().to_path_buf())
+    };
+    let default = NodeResolver::default();
+    let ok = |base: &str, specifier: &str, expected: &str| {
+        assert_eq!(
+            resolve(&default, base, specifier).unwrap(),
+            Path::new(expected)
+        )
+    };
+
+    let index = "node/src/index.js";
+    ok(index, "./util", "src/util.js");
+    ok(index, "../src/util.js", "src/util.js");
+    ok(index, "./dir", "src/dir/index.js");
+    ok(index, "./data.json", "src/data.json");
+
+    ok(index, "main-pkg", "node_modules/main-pkg/lib/main.js");
+    ok(index, "main-pkg/other", "node_modules/main-pkg/other.js");
+    ok("node/src/dir/index.js", "main-pkg", "src/dir/node_modules/main-pkg/index.js");
+    ok(index, "module-pkg", "node_modules/module-pkg/esm.js");
+    ok(index, "@scope/pkg", "node_modules/@scope/pkg/index.js");
+
+    ok(index, "exports-pkg", "node_modules/exports-pkg/esm/index.js");
+    ok(index, "exports-pkg/feature", "node_modules/exports-pkg/esm/feature.js");
+    ok(index, "exports-pkg/utils/a", "node_modules/exports-pkg/esm/utils/a.js");
+
+    let cjs = NodeResolver {
+        main_fields: vec!["main".into()],
+        conditions: vec!["require".into()],
+        ..Default::default()
+    };
+    assert_eq!(
+        resolve(&cjs, index, "module-pkg").unwrap(),
+        Path::new("node_modules/module-pkg/cjs.js")
+    );
+    assert_eq!(
+        resolve(&cjs, index, "exports-pkg").unwrap(),
+        Path::new("node_modules/exports-pkg/cjs/index.js")
+    );
+
+    assert!(resolve(&default, index, "./missing").is_err());
+    assert!(resolve(&default, index, "missing-pkg").is_err());
+    // Not listed in `exports`.
+    assert!(resolve(&default, index, "exports-pkg/cjs/index.js").is_err());
+}
+
+#[test]
+fn side_effects() {
+    let resolver = NodeResolver::default();
+
+    assert!(resolver.has_side_effects(&fixture("shake/src/effects.js")));
+    assert!(!resolver.has_side_effects(&fixture("shake/src/lib.js")));
+    assert!(!resolver.has_side_effects(&fixture("node/node_modules/module-pkg/esm.js")));
+}
+
+#[test]
+fn shake() {
+    let mut graph = load(NodeResolver::default(), &["shake/src/entry.js"]).unwrap();
+    graph.shake();
+    let name = |id: ModuleId| {
+        let path = &graph[id].path;
+        path.file_stem().unwrap().to_str().unwrap().to_string()
+    };
+    let info = |id: ModuleId| {
+        let module = &graph[id];
+        let exports: Vec<_> = module.exports.iter().map(|e| e.to_string()).collect();
+        (name(id), exports, module.module.body.len())
+    };
+
+    let modules: Vec<_> = graph.sorted().into_iter().map(info).collect();
+    let expected = vec![
+        ("helper", vec!["helper"], 1),
+        ("lib", vec!["used"], 2),
+        ("effects", vec![], 1),
+        ("values", vec!["a"], 1),
+        ("reexport", vec!["reexported"], 1),
+        ("ns", vec!["x", "y"], 2),
+        ("entry", vec!["api"], 6),
+    ];
+    let expected: Vec<_> = expected
+        .into_iter()
+        .map(|(name, exports, len)| {
+            let exports: Vec<_> = exports.into_iter().map(String::from).collect();
+            (name.to_string(), exports, len)
+        })
+        .collect();
+    assert_eq!(modules, expected);
+
+    // Removed because they don't have side effects.
+    let entry = &graph[graph.entries()[0]];
+    let deps: Vec<_> = entry.deps.iter().map(|d| &*d.src).collect();
+    assert_eq!(deps, ["./lib", "./effects", "./reexport", "./ns"]);
+}
+
+#[test]
+fn chunks() {
+    let graph = load(Relative, &["split/a.js", "split/b.js"]).unwrap();
+    let id = |name: &str| graph.id_of(&fixture(&format!("split/{}.js", name))).unwrap();
+
+    assert_eq!(
+        graph.chunks(),
+        vec![
+            Chunk {
+                name: "a".into(),
+                kind: ChunkKind::Entry(id("a")),
+                modules: vec![id("only-a"), id("a")],
+                requires: vec!["a~b".into()],
+            },
+            Chunk {
+                name: "b".into(),
+                kind: ChunkKind::Entry(id("b")),
+                modules: vec![id("b")],
+                requires: vec!["a~b".into()],
+            },
+            Chunk {
+                name: "a~b".into(),
+                kind: ChunkKind::Shared,
+                modules: vec![id("shared")],
+                requires: vec![],
+            },
+        ]
+    );
+}
+
+#[test]
+fn chunks_dynamic() {
+    let graph = load(Relative, &["split/lazy.js"]).unwrap();
+    let id = |name: &str| graph.id_of(&fixture(&format!("split/{}.js", name))).unwrap();
+    let (lazy, shared, only_a) = (id("lazy"), id("shared"), id("only-a"));
+    assert_eq!(graph[lazy].deps[1].kind, DepKind::Dynamic);
+
+    assert_eq!(
+        graph.chunks(),
+        vec![
+            Chunk {
+                name: "lazy".into(),
+                kind: ChunkKind::Entry(lazy),
+                modules: vec![only_a, lazy],
+                requires: vec![],
+            },
+            Chunk {
+                name: "shared".into(),
+                kind: ChunkKind::Dynamic(shared),
+                modules: vec![shared],
+                requires: vec![],
+            },
+        ]
+    );
+}
+
+#[test]
+fn emit() {
+    let entries = [fixture("split/a.js"), fixture("split/b.js")];
+    let bundle = ::testing::run_test(|logger, cm, handler| {
+        let bundler = Bundler::new(cm, handler, &logger, Relative);
+        let graph = bundler.load(&entries).unwrap();
+        Ok(bundler.emit(&graph, &graph.chunks()))
+    })
+    .unwrap_or_else(|stderr| panic!("Stderr:\n{}", stderr));
+
+    let files: Vec<_> = bundle.files.iter().map(|f| &*f.0).collect();
+    assert_eq!(files, ["a.js", "b.js", "a~b.js"]);
+    let manifest: Vec<_> = bundle
+        .manifest
+        .chunks
+        .iter()
+        .map(|(name, file)| (&**name, &**file))
+        .collect();
+    assert_eq!(manifest, [("a", "a.js"), ("a~b", "a~b.js"), ("b", "b.js")]);
+
+    // a: 0, b: 1, shared: 2, only-a: 3
+    let a = &bundle.files[0].1;
+    assert!(a.contains("function __swc_require(id)"), "{}", a);
+    assert!(a.contains("var _shared = require(2);"), "{}", a);
+    assert!(a.contains("require(3);"), "{}", a);
+    assert!(a.contains("console.log(_shared.shared);"), "{}", a);
+    assert!(a.ends_with(", 0, [\"a~b.js\"]);\n})();\n"), "{}", a);
+
+    let shared = &bundle.files[2].1;
+    assert!(
+        shared.starts_with(concat!(
+            "(function () {\n",
+            "__swcRegister({\n",
+            "2: function (module, exports, require) {"
+        )),
+        "{}",
+        shared
+    );
+    assert!(shared.contains("defineProperty(exports, 'shared'"), "{}", shared);
+}
+
+#[test]
+fn emit_dynamic() {
+    let (_, bundle) = bundle(&["split/lazy.js"]);
+    let files: Vec<_> = bundle.files.iter().map(|f| &*f.0).collect();
+    assert_eq!(files, ["lazy.js", "shared.js"]);
+
+    // lazy: 0, only-a: 1, shared: 2
+    let lazy = &bundle.files[0].1;
+    assert!(lazy.contains("require.load(['shared.js']).then("), "{}", lazy);
+    assert!(lazy.contains("return require(2);"), "{}", lazy);
+    assert!(!lazy.contains("import("), "{}", lazy);
+}
+
+/// Loads and emits `entries` with [Relative].
+fn bundle(entries: &[&str]) -> (ModuleGraph, Bundle) {
+    let entries: Vec<_> = entries.iter().map(|e| fixture(e)).collect();
+
+    ::testing::run_test(|logger, cm, handler| {
+        let bundler = Bundler::new(cm, handler, &logger, Relative);
+        let graph = bundler.load(&entries).unwrap();
+        let bundle = bundler.emit(&graph, &graph.chunks());
+        Ok((graph, bundle))
+    })
+    .unwrap_or_else(|stderr| panic!("Stderr:\n{}", stderr))
+}
+
+#[test]
+fn link_cycle() {
+    let (_, bundle) = bundle(&["link/cycle/a.js"]);
+    let code = &bundle.files[0].1;
+
+    // a: 0, b: 1
+    for export in &["'a'", "'default'", "'b'", "'main'"] {
+        let define = format!("defineProperty(exports, {}", export);
+        assert!(code.contains(&define), "{}", code);
+    }
+    assert!(code.contains("var _b = require(1);"), "{}", code);
+    assert!(code.contains("var _a = require(0);"), "{}", code);
+    // Exports are defined before modules in the cycle are required.
+    assert!(
+        code.find("defineProperty(exports, 'b'").unwrap() < code.find("var _a").unwrap(),
+        "{}",
+        code
+    );
+
+    assert!(code.contains("return (0, _b.b)();"), "{}", code);
+    assert!(code.contains("return (0, _a.a)(1);"), "{}", code);
+    assert!(code.contains("return _a.default;"), "{}", code);
+    assert!(code.contains("function _default()"), "{}", code);
+    // The parameter shadows the import.
+    assert!(code.contains("function a(b) {"), "{}", code);
+    assert!(code.contains("return b;"), "{}", code);
+}
+
+#[test]
+fn link_cjs() {
+    let (graph, bundle) = bundle(&["link/cjs/main.js"]);
+    let id = |name: &str| {
+        let path = fixture(&format!("link/cjs/{}.js", name));
+        graph.id_of(&path).unwrap().0
+    };
+    let code = &bundle.files[0].1;
+
+    let lib = format!("var _lib = _interopRequireWildcard(require({}));", id("lib"));
+    assert!(code.contains(&lib), "{}", code);
+    let value = format!("var _value = _interopRequireDefault(require({}));", id("value"));
+    assert!(code.contains(&value), "{}", code);
+    assert!(code.contains("console.log(_lib.default, _lib.named, _value.default);"), "{}", code);
+    assert!(code.contains("function _interopRequireWildcard("), "{}", code);
+    assert!(code.contains("function _interopRequireDefault("), "{}", code);
+
+    // Commonjs modules are kept as is.
+    let dep = format!("var dep = require({});", id("dep"));
+    assert!(code.contains(&dep), "{}", code);
+    let header = "function (module, exports, require)";
+    let start = code.find(&format!("{}: {}", id("lib"), header)).unwrap() + header.len();
+    let lib = &code[start..];
+    let lib = &lib[..lib.find(header).unwrap_or(lib.len())];
+    assert!(!lib.contains("__esModule"), "{}", lib);
+}
+
+#[test]
+fn externals() {
+    let entries = [fixture("link/external/main.js")];
+    let (graph, bundle) = ::testing::run_test(|logger, cm, handler| {
+        let bundler = Bundler::new(cm, handler, &logger, Relative)
+            .with_externals(vec!["react".into(), "node:*".into()]);
+        let mut graph = bundler.load(&entries).unwrap();
+        graph.shake();
+        let bundle = bundler.emit(&graph, &graph.chunks());
+        Ok((graph, bundle))
+    })
+    .unwrap_or_else(|stderr| panic!("Stderr:\n{}", stderr));
+
+    assert_eq!(graph.len(), 2);
+    let main = &graph[graph.entries()[0]];
+    let externals: Vec<_> = main.externals.iter().map(|e| &**e).collect();
+    assert_eq!(externals, ["react", "node:fs", "react/jsx-runtime"]);
+    assert_eq!(main.deps.len(), 1);
+
+    let code = &bundle.files[0].1;
+    assert!(code.contains("var _react = _interopRequireDefault(require('react'));"), "{}", code);
+    assert!(code.contains("var _node_fs = require('node:fs');"), "{}", code);
+    assert!(code.contains("(0, _node_fs.readFile)(_react.default, _jsx_runtime.jsx);"), "{}", code);
+    assert!(code.contains("require(1);"), "{}", code);
+}
@@ -0,0 +1,6 @@
+import b, { c as d } from './b';
+import * as ns from './c.js';
+export * from './d';
+export { e as f } from './e';
+export const g = b + d + ns.h;
+export default function () {}
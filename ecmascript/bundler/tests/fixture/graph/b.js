@@ -0,0 +1,3 @@
+import './c';
+export default 1;
+export var c = 2;
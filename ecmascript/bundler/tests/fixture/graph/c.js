@@ -0,0 +1 @@
+export var h = 1;
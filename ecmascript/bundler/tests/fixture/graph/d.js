@@ -0,0 +1,2 @@
+import { g } from './a';
+export var i = g;
@@ -0,0 +1 @@
+export var e = 1;
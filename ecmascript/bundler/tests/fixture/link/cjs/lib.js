@@ -0,0 +1,3 @@
+var dep = require('./dep');
+
+module.exports = { named: dep };
@@ -0,0 +1,4 @@
+import lib, { named } from './lib';
+import value from './value';
+
+console.log(lib, named, value);
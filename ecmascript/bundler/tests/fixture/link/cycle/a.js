@@ -0,0 +1,9 @@
+import { b } from './b';
+
+export function a(b) {
+    return b;
+}
+
+export default function () {
+    return b();
+}
@@ -0,0 +1,7 @@
+import main, { a } from './a';
+
+export function b() {
+    return a(1);
+}
+
+export { main };
@@ -0,0 +1,6 @@
+import React from 'react';
+import { readFile } from 'node:fs';
+import { jsx } from 'react/jsx-runtime';
+import './local';
+
+readFile(React, jsx);
@@ -0,0 +1,2 @@
+window.x = 1;
+export const unusedEffect = 1;
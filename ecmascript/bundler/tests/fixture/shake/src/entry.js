@@ -0,0 +1,11 @@
+import { used } from './lib';
+import unused from './unused';
+import './effects';
+import { reexported } from './reexport';
+import * as ns from './ns';
+
+sideEffect(ns);
+
+export function api() {
+    return used() + reexported;
+}
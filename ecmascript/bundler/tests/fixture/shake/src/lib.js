@@ -0,0 +1,14 @@
+import { helper } from './helper';
+import { other } from './other';
+
+export function used() {
+    return helper();
+}
+
+export function notUsed() {
+    return other;
+}
+
+const local = 1;
+export const value = local;
+let dead = 2;
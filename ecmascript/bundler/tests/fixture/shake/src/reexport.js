@@ -0,0 +1,2 @@
+export { a as reexported, b } from './values';
+export * from './star';
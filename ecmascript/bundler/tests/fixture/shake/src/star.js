@@ -0,0 +1 @@
+export const s = 1;
@@ -0,0 +1,3 @@
+import { shared } from './shared';
+import './only-a';
+console.log(shared);
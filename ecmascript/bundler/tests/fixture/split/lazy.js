@@ -0,0 +1,4 @@
+import './only-a';
+import('./shared').then(function (shared) {
+    console.log(shared.shared);
+});
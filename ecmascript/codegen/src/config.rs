@@ -1,7 +1,30 @@
+use swc_common::{Comment, CommentKind};
+use swc_ecma_ast::EsVersion;
+
 #[derive(Debug, Default, Clone, Copy)]
 pub struct Config {
-    pub enable_comments: bool,
+    /// Comments to emit, which are read from `Emitter::comments`.
+    pub comments: Option<CommentsConfig>,
     pub omit_trailing_semi: bool,
+    /// Drops whitespace and newlines which are only for readability, and
+    /// prints literals and parentheses in their shortest form.
+    ///
+    /// Semicolons are dropped by `text_writer::omit_trailing_semi`, which
+    /// should be used together.
+    pub minify: bool,
+    /// If true, the last line is not terminated by a newline.
+    pub omit_trailing_newline: bool,
+    /// Quotes of string literals which don't have raw text, like strings
+    /// created by transforms.
+    pub quote: QuoteStyle,
+    /// Used by `JsWriter::with_indent`.
+    pub indent: Indent,
+    /// Escapes non-ascii characters of identifiers, strings, templates and
+    /// regular expressions, like `\u00e9`.
+    pub ascii_only: bool,
+    /// Syntax which can't be represented in the target is rewritten, like
+    /// `{ a }` which is emitted as `{ a: a }` for es5, or rejected.
+    pub target: EsVersion,
     pub sourcemap: Option<SourceMapConfig>,
 }
 
@@ -11,6 +34,73 @@ pub enum SourceMapConfig {
     Inline,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CommentsConfig {
+    All,
+    /// Comments starting with `/*!`, or containing `@license`, `@preserve`
+    /// or `@cc_on`.
+    License,
+}
+
+impl CommentsConfig {
+    pub fn keeps(self, comment: &Comment) -> bool {
+        match self {
+            CommentsConfig::All => true,
+            CommentsConfig::License => {
+                let text = &comment.text;
+                (comment.kind == CommentKind::Block && text.starts_with('!'))
+                    || text.contains("@license")
+                    || text.contains("@preserve")
+                    || text.contains("@cc_on")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QuoteStyle {
+    /// `'a'`
+    Single,
+    /// `"a"`
+    Double,
+}
+
+impl Default for QuoteStyle {
+    fn default() -> Self {
+        QuoteStyle::Single
+    }
+}
+
+impl QuoteStyle {
+    pub fn as_char(self) -> char {
+        match self {
+            QuoteStyle::Single => '\'',
+            QuoteStyle::Double => '"',
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            QuoteStyle::Single => "'",
+            QuoteStyle::Double => "\"",
+        }
+    }
+}
+
+/// Unit of indentation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Indent {
+    Spaces(u8),
+    Tab,
+}
+
+/// Four spaces.
+impl Default for Indent {
+    fn default() -> Self {
+        Indent::Spaces(4)
+    }
+}
+
 impl Config {
     pub fn is_sourcemap_enabled(&self) -> bool {
         self.sourcemap.is_some()
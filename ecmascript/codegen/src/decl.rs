@@ -7,18 +7,30 @@ impl<'a> Emitter<'a> {
     #[emitter]
     pub fn emit_decl(&mut self, node: &Decl) -> Result {
         match *node {
-            Decl::Class(ref n) => emit!(n),
+            Decl::Class(ref n) => {
+                self.emit_leading_decorators(&n.class.decorators)?;
+                emit!(n);
+            }
             Decl::Fn(ref n) => emit!(n),
 
             Decl::Var(ref n) => {
                 emit!(n);
                 semi!(); // VarDecl is also used for for-loops
             }
+            Decl::TsInterface(ref n) => emit!(n),
+            Decl::TsTypeAlias(ref n) => emit!(n),
+            Decl::TsEnum(ref n) => emit!(n),
+            Decl::TsModule(ref n) => emit!(n),
         }
     }
 
     #[emitter]
     pub fn emit_class_decl(&mut self, node: &ClassDecl) -> Result {
+        if node.declare {
+            keyword!("declare");
+            space!();
+        }
+
         keyword!("class");
         space!();
         emit!(node.ident);
@@ -28,6 +40,11 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     pub fn emit_fn_decl(&mut self, node: &FnDecl) -> Result {
+        if node.declare {
+            keyword!("declare");
+            space!();
+        }
+
         if node.function.async_token.is_some() {
             keyword!("async");
             space!();
@@ -46,6 +63,11 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     pub fn emit_var_decl(&mut self, node: &VarDecl) -> Result {
+        if node.declare {
+            keyword!("declare");
+            space!();
+        }
+
         keyword!(node.kind.as_str());
         space!();
 
@@ -0,0 +1,170 @@
+use super::{Emitter, Result};
+use swc_ecma_ast::*;
+use swc_ecma_codegen_macros::emitter;
+
+impl<'a> Emitter<'a> {
+    #[emitter]
+    pub fn emit_jsx_element(&mut self, node: &JSXElement) -> Result {
+        emit!(node.opening);
+        for child in &node.children {
+            emit!(child);
+        }
+        opt!(node.closing);
+    }
+
+    #[emitter]
+    pub fn emit_jsx_opening_element(&mut self, node: &JSXOpeningElement) -> Result {
+        punct!("<");
+        emit!(node.name);
+
+        for attr in &node.attrs {
+            space!();
+            emit!(attr);
+        }
+
+        if node.self_closing {
+            punct!("/");
+        }
+        punct!(">");
+    }
+
+    #[emitter]
+    pub fn emit_jsx_closing_element(&mut self, node: &JSXClosingElement) -> Result {
+        punct!("</");
+        emit!(node.name);
+        punct!(">");
+    }
+
+    #[emitter]
+    pub fn emit_jsx_element_name(&mut self, node: &JSXElementName) -> Result {
+        match *node {
+            JSXElementName::Ident(ref n) => emit!(n),
+            JSXElementName::JSXMemberExpr(ref n) => emit!(n),
+            JSXElementName::JSXNamespacedName(ref n) => emit!(n),
+        }
+    }
+
+    #[emitter]
+    pub fn emit_jsx_object(&mut self, node: &JSXObject) -> Result {
+        match *node {
+            JSXObject::Ident(ref n) => emit!(n),
+            JSXObject::JSXMemberExpr(ref n) => emit!(n),
+        }
+    }
+
+    #[emitter]
+    pub fn emit_jsx_member_expr(&mut self, node: &JSXMemberExpr) -> Result {
+        emit!(node.obj);
+        punct!(".");
+        emit!(node.prop);
+    }
+
+    #[emitter]
+    pub fn emit_jsx_namespaced_name(&mut self, node: &JSXNamespacedName) -> Result {
+        emit!(node.ns);
+        punct!(":");
+        emit!(node.name);
+    }
+
+    #[emitter]
+    pub fn emit_jsx_attr_or_spread(&mut self, node: &JSXAttrOrSpread) -> Result {
+        match *node {
+            JSXAttrOrSpread::JSXAttr(ref n) => emit!(n),
+            JSXAttrOrSpread::SpreadElement(ref n) => {
+                punct!("{");
+                emit!(n);
+                punct!("}");
+            }
+        }
+    }
+
+    #[emitter]
+    pub fn emit_jsx_attr(&mut self, node: &JSXAttr) -> Result {
+        emit!(node.name);
+
+        if let Some(ref value) = node.value {
+            punct!("=");
+            emit!(value);
+        }
+    }
+
+    #[emitter]
+    pub fn emit_jsx_attr_name(&mut self, node: &JSXAttrName) -> Result {
+        match *node {
+            JSXAttrName::Ident(ref n) => emit!(n),
+            JSXAttrName::JSXNamespacedName(ref n) => emit!(n),
+        }
+    }
+
+    #[emitter]
+    pub fn emit_jsx_attr_value(&mut self, node: &JSXAttrValue) -> Result {
+        match *node {
+            JSXAttrValue::Lit(ref n) => emit!(n),
+            JSXAttrValue::JSXExprContainer(ref n) => emit!(n),
+            JSXAttrValue::JSXElement(ref n) => emit!(n),
+            JSXAttrValue::JSXFragment(ref n) => emit!(n),
+        }
+    }
+
+    #[emitter]
+    pub fn emit_jsx_element_child(&mut self, node: &JSXElementChild) -> Result {
+        match *node {
+            JSXElementChild::JSXText(ref n) => emit!(n),
+            JSXElementChild::JSXExprContainer(ref n) => emit!(n),
+            JSXElementChild::JSXSpreadChild(ref n) => emit!(n),
+            JSXElementChild::JSXElement(ref n) => emit!(n),
+            JSXElementChild::JSXFragment(ref n) => emit!(n),
+        }
+    }
+
+    #[emitter]
+    pub fn emit_jsx_expr_container(&mut self, node: &JSXExprContainer) -> Result {
+        punct!("{");
+        emit!(node.expr);
+        punct!("}");
+    }
+
+    #[emitter]
+    pub fn emit_jsx_expr(&mut self, node: &JSXExpr) -> Result {
+        match *node {
+            JSXExpr::JSXEmptyExpr(ref n) => emit!(n),
+            JSXExpr::Expr(ref n) => emit!(n),
+        }
+    }
+
+    #[emitter]
+    pub fn emit_jsx_empty_expr(&mut self, node: &JSXEmptyExpr) -> Result {}
+
+    #[emitter]
+    pub fn emit_jsx_spread_child(&mut self, node: &JSXSpreadChild) -> Result {
+        punct!("{");
+        punct!("...");
+        emit!(node.expr);
+        punct!("}");
+    }
+
+    #[emitter]
+    pub fn emit_jsx_text(&mut self, node: &JSXText) -> Result {
+        // Entities are kept as is.
+        self.wr.write_str_lit(node.span, &node.raw)?;
+    }
+
+    #[emitter]
+    pub fn emit_jsx_fragment(&mut self, node: &JSXFragment) -> Result {
+        emit!(node.opening);
+        for child in &node.children {
+            emit!(child);
+        }
+        emit!(node.closing);
+    }
+
+    #[emitter]
+    pub fn emit_jsx_opening_fragment(&mut self, node: &JSXOpeningFragment) -> Result {
+        punct!("<>");
+    }
+
+    #[emitter]
+    pub fn emit_jsx_closing_fragment(&mut self, node: &JSXClosingFragment) -> Result {
+        punct!("</>");
+    }
+}
@@ -15,15 +15,15 @@ extern crate swc_ecma_codegen_macros;
 extern crate swc_common;
 extern crate swc_ecma_ast;
 
-pub use self::config::{Config, SourceMapConfig};
+pub use self::config::{CommentsConfig, Config, Indent, QuoteStyle, SourceMapConfig};
 use self::{
     list::ListFormat,
     text_writer::WriteJs,
     util::{SourceMapperExt, SpanExt, StartsWithAlphaNum},
 };
-use std::{collections::HashSet, io};
+use std::{borrow::Cow, io};
 use swc_atoms::JsWord;
-use swc_common::{sync::Lrc, BytePos, SourceMap, Span, Spanned, SyntaxContext, DUMMY_SP};
+use swc_common::{sync::Lrc, BytePos, Comments, SourceMap, Span, Spanned, SyntaxContext};
 use swc_ecma_ast::*;
 use swc_ecma_codegen_macros::emitter;
 
@@ -32,10 +32,12 @@ pub mod macros;
 mod comments;
 mod config;
 mod decl;
+mod jsx;
 pub mod list;
 #[cfg(test)]
 mod tests;
 pub mod text_writer;
+mod typescript;
 pub mod util;
 
 pub type Result = io::Result<()>;
@@ -64,39 +66,53 @@ pub struct Emitter<'a> {
     pub cm: Lrc<SourceMap>,
     pub wr: Box<('a + WriteJs)>,
     pub handlers: Box<('a + Handlers)>,
-    pub pos_of_leading_comments: HashSet<BytePos>,
+    /// Comments recorded by the parser, which are emitted according to
+    /// `cfg.comments`.
+    pub comments: Option<&'a Comments>,
 }
 
 impl<'a> Emitter<'a> {
-    pub fn emit_script(&mut self, stmts: &[Stmt]) -> Result {
-        let span = if stmts.is_empty() {
-            DUMMY_SP
-        } else {
-            stmts[0].span().with_hi(
-                stmts
-                    .last()
-                    .map(|s| s.span().hi())
-                    .unwrap_or(stmts[0].span().lo()),
-            )
-        };
-
-        self.emit_list(span, Some(stmts), ListFormat::SourceFileStatements)?;
+    #[emitter]
+    pub fn emit_program(&mut self, node: &Program) -> Result {
+        match *node {
+            Program::Module(ref m) => emit!(m),
+            Program::Script(ref s) => emit!(s),
+        }
+    }
 
-        Ok(())
+    #[emitter]
+    pub fn emit_script(&mut self, node: &Script) -> Result {
+        self.emit_shebang(&node.shebang)?;
+        self.emit_list(node.span, Some(&node.body), ListFormat::SourceFileStatements)?;
     }
 
     #[emitter]
     pub fn emit_module(&mut self, node: &Module) -> Result {
+        self.emit_shebang(&node.shebang)?;
         for stmt in &node.body {
             emit!(stmt);
         }
     }
 
+    fn emit_shebang(&mut self, shebang: &Option<JsWord>) -> Result {
+        if let Some(ref shebang) = *shebang {
+            self.wr.write_punct("#!")?;
+            self.wr.write_lit(Span::default(), shebang)?;
+            self.wr.write_line()?;
+        }
+
+        Ok(())
+    }
+
     #[emitter]
     pub fn emit_module_item(&mut self, node: &ModuleItem) -> Result {
         match *node {
             ModuleItem::Stmt(ref stmt) => emit!(stmt),
-            ModuleItem::ModuleDecl(ref decl) => emit!(decl),
+            ModuleItem::ModuleDecl(ref decl) => {
+                self.emit_leading_comments_of_pos(decl.span().lo())?;
+                emit!(decl);
+                self.emit_trailing_comments_of_pos(decl.span().hi(), true)?;
+            }
         }
     }
 
@@ -104,40 +120,52 @@ impl<'a> Emitter<'a> {
     pub fn emit_module_decl(&mut self, node: &ModuleDecl) -> Result {
         match *node {
             ModuleDecl::Import(ref d) => emit!(d),
-            ModuleDecl::ExportDecl(ref d) => {
-                keyword!("export");
-                space!();
-                emit!(d);
-                semi!();
-            }
+            ModuleDecl::ExportDecl(ref d) => emit!(d),
             ModuleDecl::ExportNamed(ref d) => emit!(d),
             ModuleDecl::ExportDefaultDecl(ref d) => emit!(d),
-            ModuleDecl::ExportDefaultExpr(ref d) => {
-                keyword!("export");
-                space!();
-                keyword!("default");
-                space!();
-                emit!(d);
-                semi!();
-            }
+            ModuleDecl::ExportDefaultExpr(ref d) => emit!(d),
             ModuleDecl::ExportAll(ref d) => emit!(d),
         }
     }
 
+    #[emitter]
+    pub fn emit_export_decl(&mut self, node: &ExportDecl) -> Result {
+        if let Decl::Class(ref class) = node.decl {
+            self.emit_leading_decorators(&class.class.decorators)?;
+        }
+        keyword!("export");
+        space!();
+        emit!(node.decl);
+        semi!();
+    }
+
     #[emitter]
     pub fn emit_export_default_decl(&mut self, node: &ExportDefaultDecl) -> Result {
+        if let DefaultDecl::Class(ref class) = node.decl {
+            self.emit_leading_decorators(&class.class.decorators)?;
+        }
         keyword!("export");
         space!();
         keyword!("default");
         space!();
-        match *node {
-            ExportDefaultDecl::Class(ref class) => emit!(class),
-            ExportDefaultDecl::Fn(ref f) => emit!(f),
-            ExportDefaultDecl::Var(ref decl) => emit!(decl),
+        match node.decl {
+            DefaultDecl::Class(ref class) => emit!(class),
+            DefaultDecl::Fn(ref f) => emit!(f),
+            DefaultDecl::Var(ref decl) => emit!(decl),
         }
         semi!();
     }
 
+    #[emitter]
+    pub fn emit_export_default_expr(&mut self, node: &ExportDefaultExpr) -> Result {
+        keyword!("export");
+        space!();
+        keyword!("default");
+        space!();
+        emit!(node.expr);
+        semi!();
+    }
+
     #[emitter]
     pub fn emit_import(&mut self, node: &ImportDecl) -> Result {
         keyword!("import");
@@ -251,6 +279,7 @@ impl<'a> Emitter<'a> {
             Lit::Null(Null { span }) => keyword!(span, "null"),
             Lit::Str(ref s) => emit!(s),
             Lit::Num(ref n) => emit!(n),
+            Lit::BigInt(ref n) => emit!(n),
             Lit::Regex(ref n) => {
                 punct!("/");
                 self.emit_js_word(n.exp.span, &n.exp.value)?;
@@ -263,43 +292,89 @@ impl<'a> Emitter<'a> {
     }
 
     fn emit_js_word(&mut self, span: Span, value: &JsWord) -> Result {
+        let value = self.ascii_only(value, false);
         self.wr.write_str_lit(span, &value)?;
 
         Ok(())
     }
 
+    /// Error for syntax which can't be emitted for `cfg.target`.
+    fn unsupported(&self, syntax: &str) -> io::Error {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} are not supported by {:?}", syntax, self.cfg.target),
+        )
+    }
+
+    /// Escapes non-ascii characters of `s` if `cfg.ascii_only` is true.
+    fn ascii_only<'s>(&self, s: &'s str, is_ident: bool) -> Cow<'s, str> {
+        if self.cfg.ascii_only {
+            escape_non_ascii(s, is_ident)
+        } else {
+            Cow::Borrowed(s)
+        }
+    }
+
     #[emitter]
     pub fn emit_str_lit(&mut self, node: &Str) -> Result {
-        // TODO: quote
-        if let Some(s) = get_text_of_node(&self.cm, node, false) {
-            self.wr.write_str_lit(node.span, &s)?;
-        } else {
-            punct!("'");
-            self.wr.write_str_lit(node.span, &node.value)?;
-            punct!("'");
+        match node.raw {
+            Some(ref raw) if !self.cfg.minify => {
+                let raw = self.ascii_only(raw, false);
+                self.wr.write_str_lit(node.span, &raw)?
+            }
+            _ => {
+                let quote = if self.cfg.minify {
+                    // Uses the quote which needs less escapes.
+                    let singles = node.value.matches('\'').count();
+                    let doubles = node.value.matches('"').count();
+                    if singles > doubles {
+                        QuoteStyle::Double
+                    } else {
+                        QuoteStyle::Single
+                    }
+                } else {
+                    self.cfg.quote
+                };
+                let value = escape(&node.value, quote.as_char());
+                let value = self.ascii_only(&value, false);
+                punct!(quote.as_str());
+                self.wr.write_str_lit(node.span, &value)?;
+                punct!(quote.as_str());
+            }
         }
     }
 
+    #[emitter]
+    pub fn emit_big_int_lit(&mut self, node: &BigInt) -> Result {
+        self.wr.write_str_lit(node.span, &node.value)?;
+        self.wr.write_str_lit(node.span, "n")?;
+    }
+
     #[emitter]
     pub fn emit_num_lit(&mut self, num: &Number) -> Result {
-        // FIXME: Emitter might de-optimize
+        let text = self.num_to_str(num);
+        self.wr.write_str_lit(num.span, &text)?;
+    }
 
-        match get_text_of_node(&self.cm, num, false) {
-            Some(s) => self.wr.write_str_lit(num.span, &s)?,
-            None => {
+    /// Text of a numeric literal.
+    fn num_to_str<'n>(&self, num: &'n Number) -> Cow<'n, str> {
+        match num.raw {
+            Some(ref raw) if !self.cfg.minify => Cow::Borrowed(&**raw),
+            _ => {
                 // Handle infinity
                 if num.value.is_infinite() {
                     if num.value.is_sign_negative() {
-                        self.wr.write_str_lit(num.span, "-")?;
+                        Cow::Borrowed("-Infinity")
+                    } else {
+                        Cow::Borrowed("Infinity")
                     }
-                    self.wr.write_str_lit(num.span, "Infinity")?;
+                } else if self.cfg.minify {
+                    Cow::Owned(shortest_num(num.value))
                 } else {
-                    self.wr.write_str_lit(num.span, &format!("{}", num.value))?
+                    Cow::Owned(format!("{}", num.value))
                 }
             }
-        };
-
-        return Ok(());
+        }
     }
 
     // pub fn emit_object_binding_pat(&mut self, node: &ObjectPat) -> Result {
@@ -331,6 +406,7 @@ impl<'a> Emitter<'a> {
         match *node {
             ExprOrSuper::Expr(ref e) => emit!(e),
             ExprOrSuper::Super(_) => keyword!("super"),
+            ExprOrSuper::Import(span) => keyword!(span, "import"),
         }
     }
 
@@ -343,7 +419,10 @@ impl<'a> Emitter<'a> {
             Expr::Await(ref n) => emit!(n),
             Expr::Bin(ref n) => emit!(n),
             Expr::Call(ref n) => emit!(n),
-            Expr::Class(ref n) => emit!(n),
+            Expr::Class(ref n) => {
+                self.emit_leading_decorators(&n.class.decorators)?;
+                emit!(n);
+            }
             Expr::Cond(ref n) => emit!(n),
             Expr::Fn(ref n) => emit!(n),
             Expr::Ident(ref n) => emit!(n),
@@ -351,6 +430,7 @@ impl<'a> Emitter<'a> {
             Expr::Member(ref n) => emit!(n),
             Expr::MetaProp(ref n) => emit!(n),
             Expr::New(ref n) => emit!(n),
+            Expr::OptChain(ref n) => emit!(n),
             Expr::Object(ref n) => emit!(n),
             Expr::Paren(ref n) => emit!(n),
             Expr::Seq(ref n) => emit!(n),
@@ -359,6 +439,10 @@ impl<'a> Emitter<'a> {
             Expr::Unary(ref n) => emit!(n),
             Expr::Update(ref n) => emit!(n),
             Expr::Yield(ref n) => emit!(n),
+            Expr::JSXElement(ref n) => emit!(n),
+            Expr::JSXFragment(ref n) => emit!(n),
+            Expr::TsAs(ref n) => emit!(n),
+            Expr::PrivateName(ref n) => emit!(n),
         }
     }
 
@@ -401,14 +485,40 @@ impl<'a> Emitter<'a> {
         }
     }
 
+    #[emitter]
+    pub fn emit_opt_chain_expr(&mut self, node: &OptChainExpr) -> Result {
+        match *node.expr {
+            Expr::Member(ref e) => {
+                emit!(e.obj);
+                punct!("?.");
+                if e.computed {
+                    punct!("[");
+                    emit!(e.prop);
+                    punct!("]");
+                } else {
+                    emit!(e.prop);
+                }
+            }
+            Expr::Call(ref e) => {
+                emit!(e.callee);
+                punct!("?.");
+                punct!("(");
+                self.emit_expr_or_spreads(e.span, &e.args, ListFormat::CallExpressionArguments)?;
+                punct!(")");
+            }
+            _ => unreachable!("optional chain of {:?}", node.expr),
+        }
+    }
+
     /// `1..toString` is a valid property access, emit a dot after the literal
     pub fn needs_2dots_for_property_access(&self, expr: &ExprOrSuper) -> bool {
         match *expr {
-            ExprOrSuper::Expr(box Expr::Lit(Lit::Num(Number { span, .. }))) => {
-                // check if numeric literal is a decimal literal that was originally written
-                // with a dot
-                let text = self.cm.span_to_string(span);
-                return !text.contains(".");
+            ExprOrSuper::Expr(box Expr::Lit(Lit::Num(ref num))) => {
+                // check if numeric literal is a decimal literal which is written without a
+                // dot
+                self.num_to_str(num)
+                    .bytes()
+                    .all(|c| c.is_ascii_digit() || c == b'_' || c == b'-')
             }
             _ => false,
         }
@@ -423,9 +533,11 @@ impl<'a> Emitter<'a> {
         if node.generator_token.is_some() {
             punct!("*")
         }
+        emit!(node.type_params);
         punct!("(");
         self.emit_list(node.span, Some(&node.params), ListFormat::CommaListElements)?;
         punct!(")");
+        emit!(node.return_type);
 
         punct!("=>");
         emit!(node.body);
@@ -447,8 +559,8 @@ impl<'a> Emitter<'a> {
                 first = false
             } else {
                 punct!(",");
+                formatting_space!();
             }
-            formatting_space!();
 
             emit!(e);
         }
@@ -468,10 +580,29 @@ impl<'a> Emitter<'a> {
         // let indent_before_op = needs_indention(node, &node.left, node.op);
         // let indent_after_op = needs_indention(node, node.op, &node.right);
 
+        // `a in b` and `a - -b` can't be written without spaces.
+        let is_word = match node.op {
+            op!("in") | op!("instanceof") => true,
+            _ => false,
+        };
+        let needs_space_after = match node.op {
+            op!(bin, "+") => starts_with_op(&node.right, "+"),
+            op!(bin, "-") => starts_with_op(&node.right, "-"),
+            _ => is_word,
+        };
+
         emit!(node.left);
-        formatting_space!();
+        if is_word {
+            space!();
+        } else {
+            formatting_space!();
+        }
         operator!(node.op.as_str());
-        formatting_space!();
+        if needs_space_after {
+            space!();
+        } else {
+            formatting_space!();
+        }
         emit!(node.right);
     }
 
@@ -486,11 +617,25 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     pub fn emit_class_trailing(&mut self, node: &Class) -> Result {
+        emit!(node.type_params);
+
         if node.super_class.is_some() {
             space!();
             keyword!("extends");
             space!();
             emit!(node.super_class);
+            emit!(node.super_type_params);
+        }
+
+        if !node.implements.is_empty() {
+            space!();
+            keyword!("implements");
+            space!();
+            self.emit_list(
+                node.span,
+                Some(&node.implements),
+                ListFormat::HeritageClauseTypes,
+            )?;
         }
 
         punct!("{");
@@ -498,8 +643,21 @@ impl<'a> Emitter<'a> {
         punct!("}");
     }
 
+    #[emitter]
+    pub fn emit_class_member(&mut self, node: &ClassMember) -> Result {
+        match *node {
+            ClassMember::Method(ref n) => emit!(n),
+            ClassMember::PrivateMethod(ref n) => emit!(n),
+            ClassMember::ClassProp(ref n) => emit!(n),
+            ClassMember::PrivateProp(ref n) => emit!(n),
+            ClassMember::StaticBlock(ref n) => emit!(n),
+        }
+    }
+
     #[emitter]
     pub fn emit_class_method(&mut self, node: &ClassMethod) -> Result {
+        self.emit_leading_decorators(&node.decorators)?;
+
         if let Some(_st) = node.static_token {
             keyword!("static");
             space!();
@@ -536,6 +694,108 @@ impl<'a> Emitter<'a> {
         self.emit_fn_trailing(&node.function)?;
     }
 
+    #[emitter]
+    pub fn emit_private_method(&mut self, node: &PrivateMethod) -> Result {
+        self.emit_leading_decorators(&node.decorators)?;
+
+        if let Some(_st) = node.static_token {
+            keyword!("static");
+            space!();
+        }
+        match node.kind {
+            ClassMethodKind::Constructor => unreachable!("private constructor"),
+            ClassMethodKind::Method => {
+                if node.function.async_token.is_some() {
+                    keyword!("async");
+                    space!();
+                }
+                if node.function.generator_token.is_some() {
+                    punct!("*");
+                }
+            }
+            ClassMethodKind::Getter => {
+                keyword!("get");
+                space!();
+            }
+            ClassMethodKind::Setter => {
+                keyword!("set");
+                space!();
+            }
+        }
+        emit!(node.key);
+
+        self.emit_fn_trailing(&node.function)?;
+    }
+
+    #[emitter]
+    pub fn emit_class_prop(&mut self, node: &ClassProp) -> Result {
+        self.emit_leading_decorators(&node.decorators)?;
+
+        if let Some(_st) = node.static_token {
+            keyword!("static");
+            space!();
+        }
+        emit!(node.key);
+        emit!(node.type_ann);
+        if let Some(ref value) = node.value {
+            formatting_space!();
+            punct!("=");
+            formatting_space!();
+            emit!(value);
+        }
+        semi!();
+    }
+
+    #[emitter]
+    pub fn emit_private_prop(&mut self, node: &PrivateProp) -> Result {
+        self.emit_leading_decorators(&node.decorators)?;
+
+        if let Some(_st) = node.static_token {
+            keyword!("static");
+            space!();
+        }
+        emit!(node.key);
+        emit!(node.type_ann);
+        if let Some(ref value) = node.value {
+            formatting_space!();
+            punct!("=");
+            formatting_space!();
+            emit!(value);
+        }
+        semi!();
+    }
+
+    #[emitter]
+    pub fn emit_private_name(&mut self, node: &PrivateName) -> Result {
+        punct!("#");
+        emit!(node.id);
+    }
+
+    #[emitter]
+    pub fn emit_static_block(&mut self, node: &StaticBlock) -> Result {
+        keyword!("static");
+        formatting_space!();
+        emit!(node.body);
+    }
+
+    #[emitter]
+    pub fn emit_decorator(&mut self, node: &Decorator) -> Result {
+        punct!("@");
+        emit!(node.expr);
+    }
+
+    /// Emits decorators followed by a space.
+    ///
+    /// Decorators of a class are emitted by its parent, as they come before
+    /// `export`.
+    fn emit_leading_decorators(&mut self, decorators: &[Decorator]) -> Result {
+        for dec in decorators {
+            dec.emit_with(self)?;
+            self.wr.write_space()?;
+        }
+        Ok(())
+    }
+
     #[emitter]
     pub fn emit_prop_name(&mut self, node: &PropName) -> Result {
         match *node {
@@ -546,6 +806,17 @@ impl<'a> Emitter<'a> {
         }
     }
 
+    #[emitter]
+    pub fn emit_computed_prop_name(&mut self, node: &ComputedPropName) -> Result {
+        if self.cfg.target < EsVersion::Es2015 {
+            return Err(self.unsupported("computed property names"));
+        }
+
+        punct!("[");
+        emit!(node.expr);
+        punct!("]");
+    }
+
     #[emitter]
     pub fn emit_cond_expr(&mut self, node: &CondExpr) -> Result {
         // TODO: Indent
@@ -579,12 +850,26 @@ impl<'a> Emitter<'a> {
     #[emitter]
     /// prints `(b){}` from `function a(b){}`
     pub fn emit_fn_trailing(&mut self, node: &Function) -> Result {
+        emit!(node.type_params);
         punct!("(");
         self.emit_list(node.span, Some(&node.params), ListFormat::CommaListElements)?;
         punct!(")");
+        emit!(node.return_type);
 
-        formatting_space!();
-        emit!(node.body);
+        match node.body {
+            Some(ref body) => {
+                formatting_space!();
+                emit!(body);
+            }
+            // `declare function a(): void;`
+            None => semi!(),
+        }
+    }
+
+    #[emitter]
+    pub fn emit_param(&mut self, node: &Param) -> Result {
+        self.emit_leading_decorators(&node.decorators)?;
+        emit!(node.pat);
     }
 
     #[emitter]
@@ -631,7 +916,8 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     pub fn emit_quasi(&mut self, node: &TplElement) -> Result {
-        self.wr.write_str_lit(node.span, &node.raw)?;
+        let raw = self.ascii_only(&node.raw, false);
+        self.wr.write_str_lit(node.span, &raw)?;
         return Ok(());
     }
 
@@ -639,7 +925,15 @@ impl<'a> Emitter<'a> {
     pub fn emit_unary_expr(&mut self, node: &UnaryExpr) -> Result {
         //TODO: Operator vs Keyword
         keyword!(node.op.as_str());
-        if should_emit_whitespace_before_operand(node) {
+        // `typeof (a)` is minified as `typeof a`.
+        let is_unwrapped_word = self.cfg.minify
+            && match *node.arg {
+                Expr::Paren(ParenExpr { ref expr, .. }) => {
+                    is_redundant_paren(expr) && expr.starts_with_alpha_num()
+                }
+                _ => false,
+            };
+        if should_emit_whitespace_before_operand(node) || is_unwrapped_word {
             space!();
         } else {
             // TODO:
@@ -721,14 +1015,14 @@ impl<'a> Emitter<'a> {
         // allowTrailingComma | preferNewLine);
 
         punct!("{");
-        self.wr.write_line()?;
+        self.write_formatting_newline()?;
         self.wr.increase_indent()?;
         self.emit_list(
             node.span(),
             Some(&node.props),
             ListFormat::ObjectLiteralExpressionProperties,
         )?;
-        self.wr.write_line()?;
+        self.write_formatting_newline()?;
         self.wr.decrease_indent()?;
         punct!("}");
     }
@@ -736,6 +1030,13 @@ impl<'a> Emitter<'a> {
     #[emitter]
     pub fn emit_prop(&mut self, node: &Prop) -> Result {
         match *node {
+            // `{ a: a }`
+            Prop::Shorthand(ref n) if self.cfg.target < EsVersion::Es2015 => {
+                emit!(n);
+                punct!(":");
+                formatting_space!();
+                emit!(n);
+            }
             Prop::Shorthand(ref n) => emit!(n),
             Prop::KeyValue(ref n) => emit!(n),
             Prop::Assign(ref n) => emit!(n),
@@ -788,6 +1089,21 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     pub fn emit_method_prop(&mut self, node: &MethodProp) -> Result {
+        if self.cfg.target < EsVersion::Es2015 {
+            // `{ a: function () {} }`
+            if node.function.async_token.is_some() || node.function.generator_token.is_some() {
+                return Err(self.unsupported("async or generator methods"));
+            }
+
+            emit!(node.key);
+            punct!(":");
+            formatting_space!();
+            keyword!("function");
+            formatting_space!();
+            self.emit_fn_trailing(&node.function)?;
+            return Ok(());
+        }
+
         if let Some(_gen) = node.function.generator_token {
             punct!("*");
         }
@@ -800,6 +1116,11 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     pub fn emit_paren_expr(&mut self, node: &ParenExpr) -> Result {
+        if self.cfg.minify && is_redundant_paren(&node.expr) {
+            emit!(node.expr);
+            return Ok(());
+        }
+
         punct!("(");
         emit!(node.expr);
         punct!(")");
@@ -820,9 +1141,11 @@ impl<'a> Emitter<'a> {
         } else {
             // TODO: span
             if let Some(s) = get_text_of_node(&self.cm, &ident, false) {
+                let s = self.ascii_only(&s, true);
                 self.wr.write_symbol(ident.span, &s)?
             } else {
-                self.wr.write_symbol(ident.span, &ident.sym)?
+                let s = self.ascii_only(&ident.sym, true);
+                self.wr.write_symbol(ident.span, &s)?
             }
 
             // self.wr
@@ -895,11 +1218,11 @@ impl<'a> Emitter<'a> {
             // Write a line terminator if the parent node was multi-line
 
             if format.contains(ListFormat::MultiLine) {
-                self.wr.write_line()?;
+                self.write_formatting_newline()?;
             } else if format.contains(ListFormat::SpaceBetweenBraces)
                 && !(format.contains(ListFormat::NoSpaceIfEmpty))
             {
-                self.wr.write_space()?;
+                self.write_formatting_space()?;
             }
         } else {
             let children = children.unwrap();
@@ -912,10 +1235,10 @@ impl<'a> Emitter<'a> {
                 .cm
                 .should_write_leading_line_terminator(parent_node, children, format)
             {
-                self.wr.write_line()?;
+                self.write_formatting_newline()?;
                 should_emit_intervening_comments = false;
             } else if format.contains(ListFormat::SpaceBetweenBraces) {
-                self.wr.write_space()?;
+                self.write_formatting_space()?;
             }
 
             // Increase the indent, if requested.
@@ -961,17 +1284,22 @@ impl<'a> Emitter<'a> {
                             should_decrease_indent_after_emit = true;
                         }
 
-                        self.wr.write_line()?;
+                        self.write_formatting_newline()?;
                         should_emit_intervening_comments = false;
                     } else if format.contains(ListFormat::SpaceBetweenSiblings) {
-                        self.wr.write_space()?;
+                        // Siblings without delimiters, like jsx attributes, need a space.
+                        if format.intersects(ListFormat::DelimitersMask) {
+                            self.write_formatting_space()?;
+                        } else {
+                            self.wr.write_space()?;
+                        }
                     }
                 }
 
                 // Emit this child.
                 if should_emit_intervening_comments {
                     let comment_range = child.comment_range();
-                    self.emit_trailing_comments_of_pos(comment_range.hi(), false)?;
+                    self.emit_leading_comments_of_pos(comment_range.lo())?;
                 } else {
                     should_emit_intervening_comments = may_emit_intervening_comments;
                 }
@@ -1031,9 +1359,9 @@ impl<'a> Emitter<'a> {
                 .cm
                 .should_write_closing_line_terminator(parent_node, children, format)
             {
-                self.wr.write_line()?;
+                self.write_formatting_newline()?;
             } else if format.contains(ListFormat::SpaceBetweenBraces) {
-                self.wr.write_space()?;
+                self.write_formatting_space()?;
             }
         }
 
@@ -1065,6 +1393,7 @@ impl<'a> Emitter<'a> {
             Pat::Ident(ref n) => emit!(n),
             Pat::Object(ref n) => emit!(n),
             Pat::Rest(ref n) => emit!(n),
+            Pat::TsTyped(ref n) => emit!(n),
         }
     }
 
@@ -1177,13 +1506,13 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     pub fn emit_stmt(&mut self, node: &Stmt) -> Result {
+        self.emit_leading_comments_of_pos(node.span().lo())?;
+
         match *node {
-            Stmt::Expr(ref e) => {
-                emit!(e);
-                semi!();
-            }
+            Stmt::Expr(ref e) => emit!(e),
             Stmt::Block(ref e) => {
                 emit!(e);
+                self.emit_trailing_comments_of_pos(node.span().hi(), true)?;
                 return Ok(());
             }
             Stmt::Empty(ref e) => emit!(e),
@@ -1204,7 +1533,8 @@ impl<'a> Emitter<'a> {
             Stmt::ForOf(ref e) => emit!(e),
             Stmt::Decl(ref e) => emit!(e),
         }
-        self.wr.write_line()?;
+        self.emit_trailing_comments_of_pos(node.span().hi(), true)?;
+        self.write_formatting_newline()?;
     }
 
     #[emitter]
@@ -1218,9 +1548,16 @@ impl<'a> Emitter<'a> {
         punct!("}");
     }
 
+    #[emitter]
+    pub fn emit_expr_stmt(&mut self, node: &ExprStmt) -> Result {
+        emit!(node.expr);
+        semi!();
+    }
+
     #[emitter]
     pub fn emit_empty_stmt(&mut self, node: &EmptyStmt) -> Result {
-        punct!(";")
+        // Not `write_semi`, which may be omitted.
+        self.wr.write_punct(";")?;
     }
 
     #[emitter]
@@ -1280,11 +1617,11 @@ impl<'a> Emitter<'a> {
     pub fn emit_if_stmt(&mut self, node: &IfStmt) -> Result {
         keyword!("if");
 
-        space!();
+        formatting_space!();
         punct!("(");
         emit!(node.test);
         punct!(")");
-        space!();
+        formatting_space!();
 
         let is_block_stmt = match *node.cons {
             Stmt::Block(_) => true,
@@ -1294,10 +1631,13 @@ impl<'a> Emitter<'a> {
 
         if let Some(ref alt) = node.alt {
             if is_block_stmt {
-                space!();
+                formatting_space!();
             }
             keyword!("else");
-            space!();
+            match **alt {
+                Stmt::Block(..) => formatting_space!(),
+                _ => space!(),
+            }
             emit!(alt);
         }
     }
@@ -1318,13 +1658,13 @@ impl<'a> Emitter<'a> {
     #[emitter]
     pub fn emit_catch_clause(&mut self, node: &CatchClause) -> Result {
         keyword!("catch");
-        space!();
+        formatting_space!();
 
         punct!("(");
         emit!(node.param);
         punct!(")");
 
-        space!();
+        formatting_space!();
 
         emit!(node.body);
     }
@@ -1451,6 +1791,24 @@ impl<'a> Emitter<'a> {
 }
 
 impl<'a> Emitter<'a> {
+    /// Writes a space unless the output is minified.
+    fn write_formatting_space(&mut self) -> Result {
+        if !self.cfg.minify {
+            self.wr.write_space()?;
+        }
+        Ok(())
+    }
+
+    /// Writes a newline unless the output is minified.
+    ///
+    /// This is safe as statements are terminated by semicolons or braces.
+    fn write_formatting_newline(&mut self) -> Result {
+        if !self.cfg.minify {
+            self.wr.write_line()?;
+        }
+        Ok(())
+    }
+
     fn write_delim(&mut self, f: ListFormat) -> Result {
         match f & ListFormat::DelimitersMask {
             ListFormat::None => {}
@@ -1562,6 +1920,161 @@ fn should_emit_whitespace_before_operand(node: &UnaryExpr) -> bool {
     }
 }
 
+/// Replaces non-ascii characters with escapes like `\u00e9`.
+///
+/// Characters outside of the basic multilingual plane are written as
+/// `\u{1F600}` in identifiers, and as surrogate pairs otherwise, which is
+/// also valid in es5. `s` is source text, so escaped characters like `\é`
+/// are handled.
+fn escape_non_ascii(s: &str, is_ident: bool) -> Cow<str> {
+    fn push_escape(buf: &mut String, c: char, is_ident: bool) {
+        if (c as u32) <= 0xffff {
+            buf.push_str(&format!("\\u{:04X}", c as u32));
+        } else if is_ident {
+            buf.push_str(&format!("\\u{{{:X}}}", c as u32));
+        } else {
+            let mut units = [0; 2];
+            for unit in c.encode_utf16(&mut units).iter() {
+                buf.push_str(&format!("\\u{:04X}", unit));
+            }
+        }
+    }
+
+    if s.is_ascii() {
+        return Cow::Borrowed(s);
+    }
+
+    let mut buf = String::with_capacity(s.len() + 8);
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                // Line continuation
+                Some('\u{2028}') | Some('\u{2029}') => {}
+                // `\é` is `é`
+                Some(c) if !c.is_ascii() => push_escape(&mut buf, c, is_ident),
+                Some(c) => {
+                    buf.push('\\');
+                    buf.push(c);
+                }
+                None => buf.push('\\'),
+            },
+            _ if c.is_ascii() => buf.push(c),
+            _ => push_escape(&mut buf, c, is_ident),
+        }
+    }
+
+    Cow::Owned(buf)
+}
+
+/// Returns true if parentheses around `expr` can be omitted, which is the case
+/// for primary expressions.
+///
+/// Number and regex literals are excluded, as `(1).a` and `a / (/b/)` need
+/// them. So are strings, because `('use strict');` is not a directive.
+fn is_redundant_paren(expr: &Expr) -> bool {
+    match *expr {
+        Expr::Ident(..)
+        | Expr::This(..)
+        | Expr::Array(..)
+        | Expr::Lit(Lit::Bool(..))
+        | Expr::Lit(Lit::Null(..))
+        | Expr::Lit(Lit::BigInt(..))
+        | Expr::Tpl(TplLit { tag: None, .. }) => true,
+        _ => false,
+    }
+}
+
+/// Shortest representation of a finite number, like `.5` or `1e3`.
+fn shortest_num(value: f64) -> String {
+    let plain = format!("{}", value);
+    let plain = if plain.starts_with("0.") {
+        plain[1..].to_string()
+    } else if plain.starts_with("-0.") {
+        format!("-{}", &plain[2..])
+    } else {
+        plain
+    };
+    let exp = format!("{:e}", value);
+
+    if exp.len() < plain.len() {
+        exp
+    } else {
+        plain
+    }
+}
+
+/// Returns true if `expr` is printed starting with `op`, which is `"+"` or
+/// `"-"`.
+fn starts_with_op(expr: &Expr, op: &str) -> bool {
+    match *expr {
+        Expr::Unary(UnaryExpr { op: unary_op, .. }) => unary_op.as_str() == op,
+        Expr::Update(UpdateExpr {
+            op: update_op,
+            prefix: true,
+            ..
+        }) => update_op.as_str().starts_with(op),
+        Expr::Update(UpdateExpr { ref arg, .. }) => starts_with_op(arg, op),
+        Expr::Bin(BinExpr { ref left, .. }) => starts_with_op(left, op),
+        Expr::Cond(CondExpr { ref test, .. }) => starts_with_op(test, op),
+        Expr::Seq(SeqExpr { ref exprs, .. }) => {
+            exprs.first().map_or(false, |e| starts_with_op(e, op))
+        }
+        Expr::Member(MemberExpr {
+            obj: ExprOrSuper::Expr(ref obj),
+            ..
+        })
+        | Expr::Call(CallExpr {
+            callee: ExprOrSuper::Expr(ref obj),
+            ..
+        }) => starts_with_op(obj, op),
+        _ => false,
+    }
+}
+
+/// Escapes the value of a string literal quoted by `quote`.
+fn escape(value: &str, quote: char) -> Cow<str> {
+    let needs_escape = |c: char| match c {
+        '\\' | '\0' | '\n' | '\r' | '\t' | '\u{8}' | '\u{b}' | '\u{c}' => true,
+        '\u{2028}' | '\u{2029}' => true,
+        _ => c == quote,
+    };
+    if !value.contains(needs_escape) {
+        return Cow::Borrowed(value);
+    }
+
+    let mut buf = String::with_capacity(value.len() + 2);
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => buf.push_str("\\\\"),
+            // `\01` is an octal escape.
+            '\0' => {
+                if chars.peek().map_or(false, |c| c.is_ascii_digit()) {
+                    buf.push_str("\\x00")
+                } else {
+                    buf.push_str("\\0")
+                }
+            }
+            '\n' => buf.push_str("\\n"),
+            '\r' => buf.push_str("\\r"),
+            '\t' => buf.push_str("\\t"),
+            '\u{8}' => buf.push_str("\\b"),
+            '\u{b}' => buf.push_str("\\v"),
+            '\u{c}' => buf.push_str("\\f"),
+            '\u{2028}' => buf.push_str("\\u2028"),
+            '\u{2029}' => buf.push_str("\\u2029"),
+            _ if c == quote => {
+                buf.push('\\');
+                buf.push(c);
+            }
+            _ => buf.push(c),
+        }
+    }
+
+    Cow::Owned(buf)
+}
+
 impl<N> Node for Option<N>
 where
     N: Node,
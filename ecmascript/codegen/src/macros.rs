@@ -51,9 +51,10 @@ macro_rules! space {
     };
 }
 
+/// A space which is omitted if minified.
 macro_rules! formatting_space {
     () => {
-        __cur_emitter!().wr.write_space()?;
+        __cur_emitter!().write_formatting_space()?;
     };
 }
 
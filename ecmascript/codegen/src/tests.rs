@@ -1,7 +1,7 @@
 extern crate swc_ecma_parser;
 extern crate testing;
 use self::{
-    swc_ecma_parser::{PResult, Parser, Session, SourceFileInput},
+    swc_ecma_parser::{EsConfig, PResult, Parser, Session, SourceFileInput, Syntax},
     testing::NormalizedOutput,
 };
 use super::*;
@@ -12,7 +12,7 @@ use std::{
     path::Path,
     sync::{Arc, RwLock},
 };
-use swc_common::{FileName, FilePathMapping, SourceMap};
+use swc_common::{Comments, FileName, FilePathMapping, SourceMap, DUMMY_SP};
 
 struct Noop;
 impl Handlers for Noop {}
@@ -20,6 +20,7 @@ impl Handlers for Noop {}
 struct Builder {
     cfg: Config,
     cm: Lrc<SourceMap>,
+    comments: Option<Lrc<Comments>>,
 }
 
 fn test() -> Builder {
@@ -28,6 +29,7 @@ fn test() -> Builder {
     Builder {
         cfg: Default::default(),
         cm: Lrc::new(src),
+        comments: None,
     }
 }
 
@@ -37,12 +39,21 @@ impl Builder {
         F: FnOnce(&mut Emitter) -> Ret,
     {
         let mut src_map_builder = SourceMapBuilder::new(Some(src));
+        let mut wr: Box<text_writer::WriteJs + '_> =
+            box text_writer::JsWriter::new(self.cm.clone(), "\n", s, &mut src_map_builder)
+                .with_indent(self.cfg.indent);
+        if self.cfg.omit_trailing_newline {
+            wr = box text_writer::omit_trailing_newline(wr);
+        }
+        if self.cfg.omit_trailing_semi || self.cfg.minify {
+            wr = box text_writer::omit_trailing_semi(wr);
+        }
         let mut e = Emitter {
             cfg: self.cfg,
             cm: self.cm.clone(),
-            wr: box text_writer::JsWriter::new(self.cm.clone(), "\n", s, &mut src_map_builder),
+            wr,
             handlers: box Noop,
-            pos_of_leading_comments: Default::default(),
+            comments: self.comments.as_ref().map(|c| &**c),
         };
 
         let ret = op(&mut e);
@@ -63,7 +74,16 @@ impl Builder {
 }
 
 fn test_from_to(from: &str, to: &str) {
+    test_from_to_with(Default::default(), from, to)
+}
+
+fn test_from_to_with(syntax: Syntax, from: &str, to: &str) {
+    test_from_to_with_cfg(Default::default(), syntax, from, to)
+}
+
+fn test_from_to_with_cfg(cfg: Config, syntax: Syntax, from: &str, to: &str) {
     fn with_parser<F, Ret>(
+        syntax: Syntax,
         file_name: &Path,
         s: &str,
         f: F,
@@ -82,17 +102,18 @@ fn test_from_to(from: &str, to: &str) {
                 Session {
                     logger: &logger,
                     handler: &handler,
-                    cfg: Default::default(),
                 },
+                syntax,
                 (&*src).into(),
             ));
 
             res
         })
     }
-    let res = with_parser(Path::new("test.js"), from, |p| p.parse_module()).unwrap();
+    let res = with_parser(syntax, Path::new("test.js"), from, |p| p.parse_module()).unwrap();
 
-    assert_eq!(test().text(from, |e| e.emit_module(&res).unwrap()), to,);
+    let builder = Builder { cfg, ..test() };
+    assert_eq!(builder.text(from, |e| e.emit_module(&res).unwrap()), to,);
 }
 
 #[test]
@@ -100,6 +121,245 @@ fn empty_stmt() {
     test_from_to(";", ";\n");
 }
 
+#[test]
+fn jsx() {
+    let syntax = Syntax::Es(EsConfig {
+        jsx: true,
+        ..Default::default()
+    });
+    test_from_to_with(
+        syntax,
+        "<a b=\"c\" {...d}>x &amp; {e}<f.g /><></></a>",
+        "<a b=\"c\" {...d}>x &amp; {e}<f.g/><></></a>;\n",
+    );
+}
+
+#[test]
+fn typescript() {
+    let syntax = Syntax::Typescript(Default::default());
+    test_from_to_with(syntax, "let a: string = b as any;", "let a: string = b as any;\n");
+    test_from_to_with(syntax, "type A<T> = B<T>[] | null;", "type A<T> = B<T>[] | null;\n");
+    test_from_to_with(
+        syntax,
+        "declare function a(b: string): void;",
+        "declare function a(b: string): void;\n",
+    );
+}
+
+#[test]
+fn decorators() {
+    let syntax = Syntax::Es(EsConfig {
+        decorators: Some(swc_ecma_parser::Decorators::Legacy),
+        ..Default::default()
+    });
+    test_from_to_with(
+        syntax,
+        "@a class B { @c.d() set e(@f g) {} }",
+        "@a class B{\n    @c.d() set e(@f g) {\n    }\n}\n",
+    );
+}
+
+#[test]
+fn class_fields() {
+    let syntax = Syntax::Es(EsConfig {
+        class_props: true,
+        ..Default::default()
+    });
+    test_from_to_with(
+        syntax,
+        "class A { static b = 1; #c; get #d() {} }",
+        "class A{\n    static b = 1;\n    #c;\n    get #d() {\n    }\n}\n",
+    );
+}
+
+#[test]
+fn dynamic_import() {
+    let syntax = Syntax::Es(EsConfig {
+        dynamic_import: true,
+        import_meta: true,
+        ..Default::default()
+    });
+    test_from_to_with(
+        syntax,
+        "import('./a').then(b); c(import.meta.url);",
+        "import('./a').then(b);\nc(import.meta.url);\n",
+    );
+}
+
+#[test]
+fn big_int() {
+    test_from_to("a = 0xFFn + 10n;", "a = 0xFFn + 10n;\n");
+}
+
+#[test]
+fn shebang() {
+    test_from_to("#!/usr/bin/env node\nfoo();", "#!/usr/bin/env node\nfoo();\n");
+}
+
+#[test]
+fn tpl_lit() {
+    test_from_to(
+        r"a`\u{g}${b}`; `\n${c}\u0041`;",
+        "a`\\u{g}${b}`;\n`\\n${c}\\u0041`;\n",
+    );
+}
+
+#[test]
+fn raw_lits() {
+    let syntax = Syntax::Es(EsConfig {
+        num_sep: true,
+        ..Default::default()
+    });
+    test_from_to_with(
+        syntax,
+        r#"a = 0x1F + 1_000 + 1. + '\u0041' + "b"; 1..c;"#,
+        "a = 0x1F + 1_000 + 1. + '\\u0041' + \"b\";\n1..c;\n",
+    );
+}
+
+#[test]
+fn optional_chaining_and_nullish_coalescing() {
+    let syntax = Syntax::Es(EsConfig {
+        optional_chaining: true,
+        nullish_coalescing: true,
+        ..Default::default()
+    });
+    test_from_to_with(
+        syntax,
+        "a?.b.c; a?.[b]?.(c, d); (a ?? b) ?? c;",
+        "a?.b.c;\na?.[b]?.(c, d);\n(a ?? b) ?? c;\n",
+    );
+}
+
+#[test]
+fn indent_and_trailing_newline() {
+    let cfg = Config {
+        indent: Indent::Tab,
+        omit_trailing_newline: true,
+        ..Default::default()
+    };
+    test_from_to_with_cfg(
+        cfg,
+        Default::default(),
+        "class A { b() {} }",
+        "class A{\n\tb() {\n\t}\n}",
+    );
+}
+
+#[test]
+fn minify() {
+    let cfg = Config {
+        minify: true,
+        ..Default::default()
+    };
+    test_from_to_with_cfg(
+        cfg,
+        Default::default(),
+        "if (a) { b(); } else { c = 0.50 - -d; }\nx = typeof (y) + 'it\\'s' + 1000 + (1).a;",
+        "if(a){b()}else{c=.5- -d}x=typeof y+\"it's\"+1e3+(1).a",
+    );
+    test_from_to_with_cfg(
+        cfg,
+        Default::default(),
+        "for (;;) ; if (a in b) x(); else if (c) {}",
+        "for(;;);if(a in b)x();else if(c){}",
+    );
+}
+
+#[test]
+fn ascii_only() {
+    let cfg = Config {
+        ascii_only: true,
+        ..Default::default()
+    };
+    test_from_to_with_cfg(
+        cfg,
+        Default::default(),
+        "var é = 'ü\\é' + `😀`;",
+        "var \\u00E9 = '\\u00FC\\u00E9' + `\\uD83D\\uDE00`;\n",
+    );
+}
+
+#[test]
+fn es5_target() {
+    let cfg = Config {
+        minify: true,
+        target: EsVersion::Es5,
+        ..Default::default()
+    };
+    test_from_to_with_cfg(
+        cfg,
+        Default::default(),
+        "x = { a, b() {}, c: d };",
+        "x={a:a,b:function(){},c:d}",
+    );
+}
+
+#[test]
+fn comments() {
+    fn emit(kept: Option<CommentsConfig>, src: &str) -> String {
+        let comments = Lrc::new(Comments::default());
+        let module = self::testing::run_test(|logger, cm, handler| {
+            let fm = cm.new_source_file(FileName::Real("test.js".into()), src.to_string());
+            let session = Session {
+                logger: &logger,
+                handler: &handler,
+            };
+            Parser::with_comments(session, Default::default(), (&*fm).into(), &comments)
+                .parse_module()
+                .map_err(|mut err| err.emit())
+        })
+        .unwrap();
+
+        let builder = Builder {
+            cfg: Config {
+                comments: kept,
+                ..Default::default()
+            },
+            comments: Some(comments.clone()),
+            ..test()
+        };
+        builder.text(src, |e| e.emit_module(&module).unwrap())
+    }
+
+    let src = "/*! license */\n// a\nfoo(); // b\n/* c */ bar();";
+    assert_eq!(
+        emit(Some(CommentsConfig::All), src),
+        "/*! license */ // a\nfoo(); // b\n/* c */ bar();\n"
+    );
+    assert_eq!(
+        emit(Some(CommentsConfig::License), src),
+        "/*! license */ foo();\nbar();\n"
+    );
+    assert_eq!(emit(None, src), "foo();\nbar();\n");
+}
+
+#[test]
+fn str_lit_quote_style() {
+    fn emit(quote: QuoteStyle, value: &str) -> String {
+        let builder = Builder {
+            cfg: Config {
+                quote,
+                ..Default::default()
+            },
+            ..test()
+        };
+        let s = Str {
+            span: DUMMY_SP,
+            value: value.into(),
+            has_escape: false,
+            raw: None,
+        };
+        builder.text("", |e| e.emit_str_lit(&s).unwrap())
+    }
+
+    assert_eq!(emit(QuoteStyle::Single, "a"), "'a'");
+    assert_eq!(emit(QuoteStyle::Single, "a'b\"c"), "'a\\'b\"c'");
+    assert_eq!(emit(QuoteStyle::Double, "a'b\"c"), "\"a'b\\\"c\"");
+    assert_eq!(emit(QuoteStyle::Double, "\\\n\u{2028}"), "\"\\\\\\n\\u2028\"");
+    assert_eq!(emit(QuoteStyle::Single, "\01\0"), "'\\x001\\0'");
+}
+
 #[test]
 #[ignore]
 fn simple_if_else_stmt() {
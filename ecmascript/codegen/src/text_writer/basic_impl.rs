@@ -1,7 +1,8 @@
 use super::{Result, WriteJs};
+use config::Indent;
 use sourcemap::SourceMapBuilder;
 use std::io::{self, Write};
-use swc_common::{sync::Lrc, SourceMap, Span};
+use swc_common::{sync::Lrc, BytePos, SourceMap, Span};
 
 ///
 /// -----
@@ -12,6 +13,7 @@ use swc_common::{sync::Lrc, SourceMap, Span};
 pub struct JsWriter<'a, W: Write> {
     cm: Lrc<SourceMap>,
     indent: usize,
+    indent_unit: Indent,
     line_start: bool,
     line_count: usize,
     line_pos: usize,
@@ -31,6 +33,7 @@ impl<'a, W: Write> JsWriter<'a, W> {
         JsWriter {
             cm,
             indent: Default::default(),
+            indent_unit: Default::default(),
             line_start: true,
             line_count: 0,
             line_pos: Default::default(),
@@ -41,12 +44,21 @@ impl<'a, W: Write> JsWriter<'a, W> {
         }
     }
 
+    /// Uses `indent` as the unit of indentation, instead of four spaces.
+    pub fn with_indent(mut self, indent: Indent) -> Self {
+        self.indent_unit = indent;
+        self
+    }
+
     fn write_indent_string(&mut self) -> io::Result<usize> {
-        const INDENT: &[u8] = b"    ";
+        let (unit, width): (&[u8], _) = match self.indent_unit {
+            Indent::Spaces(width) => (b" ", width as usize),
+            Indent::Tab => (b"\t", 1),
+        };
 
         let mut cnt = 0;
-        for _ in 0..self.indent {
-            cnt += self.raw_write(INDENT)?;
+        for _ in 0..self.indent * width {
+            cnt += self.raw_write(unit)?;
         }
 
         Ok(cnt)
@@ -58,40 +70,40 @@ impl<'a, W: Write> JsWriter<'a, W> {
         Ok(written)
     }
 
+    fn srcmap(&mut self, pos: BytePos) {
+        let loc = self.cm.lookup_char_pos(pos);
+        let src = loc.file.name.to_string();
+
+        self.srcmap.add(
+            self.line_count as _,
+            (self.written_bytes - self.line_pos) as _,
+            (loc.line - 1) as _,
+            loc.col.0 as _,
+            Some(&src),
+            None,
+        );
+    }
+
     fn write(&mut self, span: Option<Span>, data: &str) -> io::Result<usize> {
         let mut cnt = 0;
 
         if data.len() > 0 {
-            if let Some(span) = span {
-                let loc = self.cm.lookup_char_pos(span.lo());
-
-                self.srcmap.add(
-                    self.line_count as _,
-                    self.line_pos as _,
-                    loc.line as _,
-                    loc.col.0 as _,
-                    None,
-                    None,
-                );
-            }
-
             if self.line_start {
                 cnt += self.write_indent_string()?;
                 self.line_start = false;
             }
+
+            if let Some(span) = span {
+                if !span.is_dummy() {
+                    self.srcmap(span.lo());
+                }
+            }
             cnt += self.raw_write(data.as_bytes())?;
 
             if let Some(span) = span {
-                let loc = self.cm.lookup_char_pos(span.hi());
-
-                self.srcmap.add(
-                    self.line_count as _,
-                    self.line_pos as _,
-                    loc.line as _,
-                    loc.col.0 as _,
-                    None,
-                    None,
-                );
+                if !span.is_dummy() {
+                    self.srcmap(span.hi());
+                }
             }
         }
 
@@ -1,8 +1,11 @@
-pub use self::{basic_impl::JsWriter, semicolon::omit_trailing_semi};
+pub use self::{
+    basic_impl::JsWriter, newline::omit_trailing_newline, semicolon::omit_trailing_semi,
+};
 use super::*;
 use swc_common::Span;
 
 mod basic_impl;
+mod newline;
 mod semicolon;
 
 /// TODO
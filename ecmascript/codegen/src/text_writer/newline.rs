@@ -0,0 +1,73 @@
+use super::{Result, WriteJs};
+use swc_common::Span;
+
+/// Delays newlines until something is written after them, so that the
+/// output doesn't end with a newline.
+pub fn omit_trailing_newline<W: WriteJs>(w: W) -> impl WriteJs {
+    box OmitTrailingNewline {
+        inner: w,
+        pending_newline: false,
+    }
+}
+
+#[derive(Debug, Clone)]
+struct OmitTrailingNewline<W: WriteJs> {
+    inner: W,
+    pending_newline: bool,
+}
+
+macro_rules! with_newline {
+    (
+        $fn_name:ident
+        (
+            $(
+                $arg_name:ident
+                :
+                $arg_ty:ty
+            ),*
+        )
+    ) => {
+        fn $fn_name(&mut self, $($arg_name: $arg_ty),* ) -> Result {
+            self.commit_pending_newline()?;
+
+            self.inner.$fn_name( $($arg_name),* )
+        }
+    };
+}
+
+impl<W: WriteJs> WriteJs for OmitTrailingNewline<W> {
+    // Indentation is written lazily by the inner writer.
+    fn increase_indent(&mut self) -> Result {
+        self.inner.increase_indent()
+    }
+    fn decrease_indent(&mut self) -> Result {
+        self.inner.decrease_indent()
+    }
+
+    fn write_line(&mut self) -> Result {
+        self.pending_newline = true;
+        Ok(())
+    }
+
+    with_newline!(write_semi());
+    with_newline!(write_space());
+    with_newline!(write_comment(span: Span, s: &str));
+    with_newline!(write_keyword(span: Option<Span>, s: &'static str));
+    with_newline!(write_operator(s: &str));
+    with_newline!(write_param(s: &str));
+    with_newline!(write_property(s: &str));
+    with_newline!(write_lit(span: Span, s: &str));
+    with_newline!(write_str_lit(span: Span, s: &str));
+    with_newline!(write_symbol(span: Span, s: &str));
+    with_newline!(write_punct(s: &'static str));
+}
+
+impl<W: WriteJs> OmitTrailingNewline<W> {
+    fn commit_pending_newline(&mut self) -> Result {
+        if self.pending_newline {
+            self.inner.write_line()?;
+            self.pending_newline = false;
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,9 @@
 use super::{Result, WriteJs};
 use swc_common::Span;
 
+/// Drops semicolons which are followed by `}` or the end of the output.
+///
+/// Empty statements should not be written with `write_semi`.
 pub fn omit_trailing_semi<W: WriteJs>(w: W) -> impl WriteJs {
     box OmitTrailingSemi {
         inner: w,
@@ -38,6 +41,7 @@ impl<W: WriteJs> WriteJs for OmitTrailingSemi<W> {
     with_semi!(decrease_indent());
 
     fn write_semi(&mut self) -> Result {
+        self.commit_pending_semi()?;
         self.pending_semi = true;
         Ok(())
     }
@@ -52,7 +56,15 @@ impl<W: WriteJs> WriteJs for OmitTrailingSemi<W> {
     with_semi!(write_lit(span: Span, s: &str));
     with_semi!(write_str_lit(span: Span, s: &str));
     with_semi!(write_symbol(span: Span, s: &str));
-    with_semi!(write_punct(s: &'static str));
+
+    fn write_punct(&mut self, s: &'static str) -> Result {
+        if s == "}" {
+            self.pending_semi = false;
+        }
+        self.commit_pending_semi()?;
+
+        self.inner.write_punct(s)
+    }
 }
 
 impl<W: WriteJs> OmitTrailingSemi<W> {
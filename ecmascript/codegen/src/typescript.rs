@@ -0,0 +1,483 @@
+use super::{list::ListFormat, Emitter, Result};
+use swc_ecma_ast::*;
+use swc_ecma_codegen_macros::emitter;
+
+impl<'a> Emitter<'a> {
+    #[emitter]
+    pub fn emit_ts_type_ann(&mut self, node: &TsTypeAnn) -> Result {
+        punct!(":");
+        formatting_space!();
+        emit!(node.type_ann);
+    }
+
+    #[emitter]
+    pub fn emit_ts_type_param_decl(&mut self, node: &TsTypeParamDecl) -> Result {
+        self.emit_list(node.span, Some(&node.params), ListFormat::TypeParameters)?;
+    }
+
+    #[emitter]
+    pub fn emit_ts_type_param(&mut self, node: &TsTypeParam) -> Result {
+        emit!(node.name);
+
+        if let Some(ref constraint) = node.constraint {
+            space!();
+            keyword!("extends");
+            space!();
+            emit!(constraint);
+        }
+
+        if let Some(ref default) = node.default {
+            formatting_space!();
+            punct!("=");
+            formatting_space!();
+            emit!(default);
+        }
+    }
+
+    #[emitter]
+    pub fn emit_ts_type_param_instantiation(&mut self, node: &TsTypeParamInstantiation) -> Result {
+        self.emit_list(node.span, Some(&node.params), ListFormat::TypeArguments)?;
+    }
+
+    #[emitter]
+    pub fn emit_ts_typed_pat(&mut self, node: &TsTypedPat) -> Result {
+        emit!(node.pat);
+        if node.optional {
+            punct!("?");
+        }
+        opt!(node.type_ann);
+    }
+
+    #[emitter]
+    pub fn emit_ts_entity_name(&mut self, node: &TsEntityName) -> Result {
+        match *node {
+            TsEntityName::TsQualifiedName(ref n) => emit!(n),
+            TsEntityName::Ident(ref n) => emit!(n),
+        }
+    }
+
+    #[emitter]
+    pub fn emit_ts_qualified_name(&mut self, node: &TsQualifiedName) -> Result {
+        emit!(node.left);
+        punct!(".");
+        emit!(node.right);
+    }
+
+    #[emitter]
+    pub fn emit_ts_type(&mut self, node: &TsType) -> Result {
+        match *node {
+            TsType::TsKeywordType(ref n) => emit!(n),
+            TsType::TsThisType(ref n) => emit!(n),
+            TsType::TsFnType(ref n) => emit!(n),
+            TsType::TsConstructorType(ref n) => emit!(n),
+            TsType::TsTypeRef(ref n) => emit!(n),
+            TsType::TsTypeQuery(ref n) => emit!(n),
+            TsType::TsTypeLit(ref n) => emit!(n),
+            TsType::TsArrayType(ref n) => emit!(n),
+            TsType::TsTupleType(ref n) => emit!(n),
+            TsType::TsUnionType(ref n) => emit!(n),
+            TsType::TsIntersectionType(ref n) => emit!(n),
+            TsType::TsConditionalType(ref n) => emit!(n),
+            TsType::TsParenthesizedType(ref n) => emit!(n),
+            TsType::TsTypeOperator(ref n) => emit!(n),
+            TsType::TsIndexedAccessType(ref n) => emit!(n),
+            TsType::TsMappedType(ref n) => emit!(n),
+            TsType::TsLitType(ref n) => emit!(n),
+        }
+    }
+
+    #[emitter]
+    pub fn emit_ts_keyword_type(&mut self, node: &TsKeywordType) -> Result {
+        keyword!(node.span, node.kind.as_str());
+    }
+
+    #[emitter]
+    pub fn emit_ts_this_type(&mut self, node: &TsThisType) -> Result {
+        keyword!(node.span, "this");
+    }
+
+    #[emitter]
+    pub fn emit_ts_fn_type(&mut self, node: &TsFnType) -> Result {
+        emit!(node.type_params);
+        self.emit_list(node.span, Some(&node.params), ListFormat::Parameters)?;
+        formatting_space!();
+        punct!("=>");
+        formatting_space!();
+        emit!(node.type_ann.type_ann);
+    }
+
+    #[emitter]
+    pub fn emit_ts_constructor_type(&mut self, node: &TsConstructorType) -> Result {
+        keyword!("new");
+        space!();
+        emit!(node.type_params);
+        self.emit_list(node.span, Some(&node.params), ListFormat::Parameters)?;
+        formatting_space!();
+        punct!("=>");
+        formatting_space!();
+        emit!(node.type_ann.type_ann);
+    }
+
+    #[emitter]
+    pub fn emit_ts_type_ref(&mut self, node: &TsTypeRef) -> Result {
+        emit!(node.type_name);
+        emit!(node.type_params);
+    }
+
+    #[emitter]
+    pub fn emit_ts_type_query(&mut self, node: &TsTypeQuery) -> Result {
+        keyword!("typeof");
+        space!();
+        emit!(node.expr_name);
+    }
+
+    #[emitter]
+    pub fn emit_ts_type_lit(&mut self, node: &TsTypeLit) -> Result {
+        punct!("{");
+        self.emit_list(
+            node.span,
+            Some(&node.members),
+            ListFormat::MultiLineTypeLiteralMembers,
+        )?;
+        punct!("}");
+    }
+
+    #[emitter]
+    pub fn emit_ts_type_element(&mut self, node: &TsTypeElement) -> Result {
+        match *node {
+            TsTypeElement::TsPropertySignature(ref n) => emit!(n),
+            TsTypeElement::TsMethodSignature(ref n) => emit!(n),
+            TsTypeElement::TsCallSignatureDecl(ref n) => emit!(n),
+            TsTypeElement::TsConstructSignatureDecl(ref n) => emit!(n),
+            TsTypeElement::TsIndexSignature(ref n) => emit!(n),
+        }
+        semi!();
+    }
+
+    #[emitter]
+    pub fn emit_ts_property_signature(&mut self, node: &TsPropertySignature) -> Result {
+        if node.readonly {
+            keyword!("readonly");
+            space!();
+        }
+        emit!(node.key);
+        if node.optional {
+            punct!("?");
+        }
+        opt!(node.type_ann);
+    }
+
+    #[emitter]
+    pub fn emit_ts_method_signature(&mut self, node: &TsMethodSignature) -> Result {
+        emit!(node.key);
+        if node.optional {
+            punct!("?");
+        }
+        emit!(node.type_params);
+        self.emit_list(node.span, Some(&node.params), ListFormat::Parameters)?;
+        opt!(node.type_ann);
+    }
+
+    #[emitter]
+    pub fn emit_ts_call_signature_decl(&mut self, node: &TsCallSignatureDecl) -> Result {
+        emit!(node.type_params);
+        self.emit_list(node.span, Some(&node.params), ListFormat::Parameters)?;
+        opt!(node.type_ann);
+    }
+
+    #[emitter]
+    pub fn emit_ts_construct_signature_decl(&mut self, node: &TsConstructSignatureDecl) -> Result {
+        keyword!("new");
+        space!();
+        emit!(node.type_params);
+        self.emit_list(node.span, Some(&node.params), ListFormat::Parameters)?;
+        opt!(node.type_ann);
+    }
+
+    #[emitter]
+    pub fn emit_ts_index_signature(&mut self, node: &TsIndexSignature) -> Result {
+        if node.readonly {
+            keyword!("readonly");
+            space!();
+        }
+        self.emit_list(
+            node.span,
+            Some(&node.params),
+            ListFormat::IndexSignatureParameters,
+        )?;
+        opt!(node.type_ann);
+    }
+
+    #[emitter]
+    pub fn emit_ts_array_type(&mut self, node: &TsArrayType) -> Result {
+        emit!(node.elem_type);
+        punct!("[");
+        punct!("]");
+    }
+
+    #[emitter]
+    pub fn emit_ts_tuple_type(&mut self, node: &TsTupleType) -> Result {
+        punct!("[");
+        self.emit_list(
+            node.span,
+            Some(&node.elem_types),
+            ListFormat::TupleTypeElements,
+        )?;
+        punct!("]");
+    }
+
+    #[emitter]
+    pub fn emit_ts_union_type(&mut self, node: &TsUnionType) -> Result {
+        self.emit_list(
+            node.span,
+            Some(&node.types),
+            ListFormat::UnionTypeConstituents,
+        )?;
+    }
+
+    #[emitter]
+    pub fn emit_ts_intersection_type(&mut self, node: &TsIntersectionType) -> Result {
+        self.emit_list(
+            node.span,
+            Some(&node.types),
+            ListFormat::IntersectionTypeConstituents,
+        )?;
+    }
+
+    #[emitter]
+    pub fn emit_ts_conditional_type(&mut self, node: &TsConditionalType) -> Result {
+        emit!(node.check_type);
+        space!();
+        keyword!("extends");
+        space!();
+        emit!(node.extends_type);
+        formatting_space!();
+        punct!("?");
+        formatting_space!();
+        emit!(node.true_type);
+        formatting_space!();
+        punct!(":");
+        formatting_space!();
+        emit!(node.false_type);
+    }
+
+    #[emitter]
+    pub fn emit_ts_parenthesized_type(&mut self, node: &TsParenthesizedType) -> Result {
+        punct!("(");
+        emit!(node.type_ann);
+        punct!(")");
+    }
+
+    #[emitter]
+    pub fn emit_ts_type_operator(&mut self, node: &TsTypeOperator) -> Result {
+        keyword!(node.op.as_str());
+        space!();
+        emit!(node.type_ann);
+    }
+
+    #[emitter]
+    pub fn emit_ts_indexed_access_type(&mut self, node: &TsIndexedAccessType) -> Result {
+        emit!(node.obj_type);
+        punct!("[");
+        emit!(node.index_type);
+        punct!("]");
+    }
+
+    #[emitter]
+    pub fn emit_ts_mapped_type(&mut self, node: &TsMappedType) -> Result {
+        punct!("{");
+        formatting_space!();
+        if node.readonly {
+            keyword!("readonly");
+            space!();
+        }
+
+        punct!("[");
+        emit!(node.type_param.name);
+        if let Some(ref constraint) = node.type_param.constraint {
+            space!();
+            keyword!("in");
+            space!();
+            emit!(constraint);
+        }
+        punct!("]");
+
+        if node.optional {
+            punct!("?");
+        }
+        if let Some(ref type_ann) = node.type_ann {
+            punct!(":");
+            formatting_space!();
+            emit!(type_ann);
+        }
+        formatting_space!();
+        punct!("}");
+    }
+
+    #[emitter]
+    pub fn emit_ts_lit_type(&mut self, node: &TsLitType) -> Result {
+        match node.lit {
+            TsLit::Number(ref n) => emit!(n),
+            TsLit::Str(ref n) => emit!(n),
+            TsLit::Bool(Bool { value, span }) => {
+                if value {
+                    keyword!(span, "true")
+                } else {
+                    keyword!(span, "false")
+                }
+            }
+        }
+    }
+
+    #[emitter]
+    pub fn emit_ts_expr_with_type_args(&mut self, node: &TsExprWithTypeArgs) -> Result {
+        emit!(node.expr);
+        emit!(node.type_params);
+    }
+
+    #[emitter]
+    pub fn emit_ts_interface_decl(&mut self, node: &TsInterfaceDecl) -> Result {
+        if node.declare {
+            keyword!("declare");
+            space!();
+        }
+
+        keyword!("interface");
+        space!();
+        emit!(node.id);
+        emit!(node.type_params);
+
+        if !node.extends.is_empty() {
+            space!();
+            keyword!("extends");
+            space!();
+            self.emit_list(
+                node.span,
+                Some(&node.extends),
+                ListFormat::HeritageClauseTypes,
+            )?;
+        }
+
+        formatting_space!();
+        emit!(node.body);
+    }
+
+    #[emitter]
+    pub fn emit_ts_interface_body(&mut self, node: &TsInterfaceBody) -> Result {
+        punct!("{");
+        self.emit_list(node.span, Some(&node.body), ListFormat::InterfaceMembers)?;
+        punct!("}");
+    }
+
+    #[emitter]
+    pub fn emit_ts_type_alias_decl(&mut self, node: &TsTypeAliasDecl) -> Result {
+        if node.declare {
+            keyword!("declare");
+            space!();
+        }
+
+        keyword!("type");
+        space!();
+        emit!(node.id);
+        emit!(node.type_params);
+        formatting_space!();
+        punct!("=");
+        formatting_space!();
+        emit!(node.type_ann);
+        semi!();
+    }
+
+    #[emitter]
+    pub fn emit_ts_enum_decl(&mut self, node: &TsEnumDecl) -> Result {
+        if node.declare {
+            keyword!("declare");
+            space!();
+        }
+        if node.is_const {
+            keyword!("const");
+            space!();
+        }
+
+        keyword!("enum");
+        space!();
+        emit!(node.id);
+        formatting_space!();
+
+        punct!("{");
+        self.emit_list(node.span, Some(&node.members), ListFormat::EnumMembers)?;
+        punct!("}");
+    }
+
+    #[emitter]
+    pub fn emit_ts_enum_member(&mut self, node: &TsEnumMember) -> Result {
+        emit!(node.id);
+
+        if let Some(ref init) = node.init {
+            formatting_space!();
+            punct!("=");
+            formatting_space!();
+            emit!(init);
+        }
+    }
+
+    #[emitter]
+    pub fn emit_ts_enum_member_id(&mut self, node: &TsEnumMemberId) -> Result {
+        match *node {
+            TsEnumMemberId::Ident(ref n) => emit!(n),
+            TsEnumMemberId::Str(ref n) => emit!(n),
+        }
+    }
+
+    #[emitter]
+    pub fn emit_ts_module_decl(&mut self, node: &TsModuleDecl) -> Result {
+        if node.declare {
+            keyword!("declare");
+            space!();
+        }
+
+        // `namespace` and `module` are interchangeable for identifiers, but
+        // ambient external modules must use `module`.
+        match node.id {
+            TsModuleName::Ident(_) => keyword!("namespace"),
+            TsModuleName::Str(_) => keyword!("module"),
+        }
+        space!();
+        emit!(node.id);
+
+        match node.body {
+            Some(ref body) => {
+                formatting_space!();
+                emit!(body);
+            }
+            None => semi!(),
+        }
+    }
+
+    #[emitter]
+    pub fn emit_ts_module_name(&mut self, node: &TsModuleName) -> Result {
+        match *node {
+            TsModuleName::Ident(ref n) => emit!(n),
+            TsModuleName::Str(ref n) => emit!(n),
+        }
+    }
+
+    #[emitter]
+    pub fn emit_ts_module_block(&mut self, node: &TsModuleBlock) -> Result {
+        punct!("{");
+        self.emit_list(
+            node.span,
+            Some(&node.body),
+            ListFormat::MultiLineBlockStatements,
+        )?;
+        punct!("}");
+    }
+
+    #[emitter]
+    pub fn emit_ts_as_expr(&mut self, node: &TsAsExpr) -> Result {
+        emit!(node.expr);
+        space!();
+        keyword!("as");
+        space!();
+        emit!(node.type_ann);
+    }
+}
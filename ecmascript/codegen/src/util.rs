@@ -150,6 +150,7 @@ impl StartsWithAlphaNum for Expr {
             Expr::Ident(_)
             | Expr::Lit(Lit::Bool(_))
             | Expr::Lit(Lit::Num(_))
+            | Expr::Lit(Lit::BigInt(_))
             | Expr::Lit(Lit::Null(_))
             | Expr::Await(_)
             | Expr::Fn(_)
@@ -176,7 +177,10 @@ impl StartsWithAlphaNum for Expr {
             Expr::Call(CallExpr {
                 callee: ref left, ..
             })
-            | Expr::Member(MemberExpr { obj: ref left, .. }) => left.starts_with_alpha_num(),
+            | Expr::Member(MemberExpr { obj: ref left, .. })
+            | Expr::TsAs(TsAsExpr { expr: ref left, .. }) => left.starts_with_alpha_num(),
+
+            Expr::OptChain(OptChainExpr { ref expr, .. }) => expr.starts_with_alpha_num(),
 
             Expr::Unary(UnaryExpr { op, .. }) => match op {
                 op!("void") | op!("delete") | op!("typeof") => true,
@@ -189,6 +193,10 @@ impl StartsWithAlphaNum for Expr {
             Expr::Tpl(_) | Expr::Update(_) | Expr::Array(_) | Expr::Object(_) | Expr::Paren(_) => {
                 false
             }
+
+            Expr::JSXElement(_) | Expr::JSXFragment(_) => false,
+
+            Expr::PrivateName(_) => false,
         }
     }
 }
@@ -224,7 +232,7 @@ impl StartsWithAlphaNum for ExprOrSpread {
 impl StartsWithAlphaNum for ExprOrSuper {
     fn starts_with_alpha_num(&self) -> bool {
         match *self {
-            ExprOrSuper::Super(_) => true,
+            ExprOrSuper::Super(_) | ExprOrSuper::Import(_) => true,
             ExprOrSuper::Expr(ref e) => return e.starts_with_alpha_num(),
         }
     }
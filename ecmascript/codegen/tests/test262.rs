@@ -20,7 +20,7 @@ use std::{
 use swc_common::{sync::Lrc, Fold, FoldWith, Span};
 use swc_ecma_ast::*;
 use swc_ecma_codegen::Emitter;
-use swc_ecma_parser::{Parser, Session, SourceFileInput};
+use swc_ecma_parser::{Parser, Session, SourceFileInput, Syntax};
 use test::{test_main, Options, ShouldPanic::No, TestDesc, TestDescAndFn, TestFn, TestName};
 use testing::NormalizedOutput;
 
@@ -152,8 +152,8 @@ fn error_tests(tests: &mut Vec<TestDescAndFn>) -> Result<(), io::Error> {
                     Session {
                         logger: &logger,
                         handler: &handler,
-                        cfg: Default::default(),
                     },
+                    Syntax::default(),
                     (&*src).into(),
                 );
 
@@ -170,7 +170,7 @@ fn error_tests(tests: &mut Vec<TestDescAndFn>) -> Result<(), io::Error> {
                             &mut src_map_builder,
                         ),
                         handlers,
-                        pos_of_leading_comments: Default::default(),
+                        comments: None,
                     };
 
                     // Parse source
@@ -222,10 +222,20 @@ impl Fold<Str> for Normalizer {
         Str {
             span: Default::default(),
             has_escape: false,
+            raw: None,
             ..s
         }
     }
 }
+impl Fold<Number> for Normalizer {
+    fn fold(&mut self, n: Number) -> Number {
+        Number {
+            span: Default::default(),
+            raw: None,
+            ..n
+        }
+    }
+}
 impl Fold<Expr> for Normalizer {
     fn fold(&mut self, e: Expr) -> Expr {
         let e = e.fold_children(self);
@@ -273,11 +283,13 @@ impl Fold<PropName> for Normalizer {
                 span: Default::default(),
                 value: sym,
                 has_escape: false,
+                raw: None,
             }),
             PropName::Num(num) => PropName::Str(Str {
                 span: Default::default(),
                 value: num.to_string().into(),
                 has_escape: false,
+                raw: None,
             }),
             _ => n,
         }
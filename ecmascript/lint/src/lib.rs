@@ -0,0 +1,200 @@
+//! Linter for ecmascript.
+//!
+//! A [Rule] checks a module, typically using a [Visit] implementation, and
+//! returns problems it found. [Linter] runs rules of a [Registry] with
+//! severities configured by [LintConfig], and reports problems to a handler
+//! with the name of the rule as the code of diagnostics.
+//!
+//! ```ignore
+//! let config: LintConfig = serde_json::from_str(r#"{
+//!     "rules": { "no-debugger": "warn", "no-unreachable": "off" }
+//! }"#)?;
+//! let linter = Linter::new(Registry::builtin(), &config)?;
+//! let summary = linter.lint(&module, &handler);
+//! ```
+//!
+//! [Visit]: swc_common::Visit
+#![feature(box_patterns)]
+#![feature(specialization)]
+
+extern crate serde;
+#[cfg(test)]
+extern crate serde_json;
+#[cfg(test)]
+extern crate slog;
+extern crate swc_common;
+extern crate swc_ecma_ast as ast;
+#[cfg(test)]
+extern crate swc_ecma_parser;
+#[cfg(test)]
+extern crate testing;
+
+use ast::Module;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, error::Error, fmt};
+use swc_common::{
+    errors::{DiagnosticId, Handler},
+    Span,
+};
+
+pub mod rules;
+#[cfg(test)]
+mod tests;
+
+pub trait Rule: Send + Sync {
+    /// Name used in configs, like `no-debugger`.
+    fn name(&self) -> &'static str;
+
+    /// Severity used if the rule is not configured.
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    /// Returns problems of `module` in the order of their positions.
+    fn check(&self, module: &Module) -> Vec<Problem>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    pub span: Span,
+    pub message: String,
+}
+
+impl Problem {
+    pub fn new<S: Into<String>>(span: Span, message: S) -> Self {
+        Problem {
+            span,
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Off,
+    Warn,
+    Error,
+}
+
+/// `lint` of `.swcrc`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LintConfig {
+    /// Severities keyed by names of rules.
+    pub rules: HashMap<String, Severity>,
+}
+
+/// Rules available to [Linter].
+#[derive(Default)]
+pub struct Registry {
+    rules: Vec<Box<Rule>>,
+}
+
+impl Registry {
+    /// Creates a registry with rules in [rules].
+    pub fn builtin() -> Self {
+        let mut registry = Registry::default();
+        registry.register(rules::NoDebugger);
+        registry.register(rules::NoDupeKeys);
+        registry.register(rules::NoUnreachable);
+        registry
+    }
+
+    /// Adds `rule`, replacing a rule with the same name.
+    pub fn register<R: Rule + 'static>(&mut self, rule: R) {
+        match self.rules.iter().position(|r| r.name() == rule.name()) {
+            Some(idx) => self.rules[idx] = Box::new(rule),
+            None => self.rules.push(Box::new(rule)),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Rule> {
+        self.rules.iter().find(|r| r.name() == name).map(|r| &**r)
+    }
+
+    /// Returns rules in the order of registration.
+    pub fn rules<'a>(&'a self) -> impl Iterator<Item = &'a Rule> + 'a {
+        self.rules.iter().map(|r| &**r)
+    }
+}
+
+/// Runs enabled rules of a [Registry].
+pub struct Linter {
+    registry: Registry,
+    severities: Vec<Severity>,
+}
+
+impl Linter {
+    /// Fails if `config` configures a rule which is not in `registry`.
+    pub fn new(registry: Registry, config: &LintConfig) -> Result<Self, UnknownRule> {
+        if let Some(name) = config.rules.keys().find(|name| registry.get(name).is_none()) {
+            return Err(UnknownRule(name.clone()));
+        }
+
+        let severities = registry
+            .rules()
+            .map(|rule| {
+                config
+                    .rules
+                    .get(rule.name())
+                    .cloned()
+                    .unwrap_or_else(|| rule.default_severity())
+            })
+            .collect();
+
+        Ok(Linter {
+            registry,
+            severities,
+        })
+    }
+
+    /// Reports problems of `module` to `handler`, grouped by rules.
+    pub fn lint(&self, module: &Module, handler: &Handler) -> Summary {
+        let mut summary = Summary::default();
+
+        for (rule, &severity) in self.registry.rules().zip(&self.severities) {
+            if severity == Severity::Off {
+                continue;
+            }
+
+            for problem in rule.check(module) {
+                let db = match severity {
+                    Severity::Error => {
+                        summary.errors += 1;
+                        handler.error(&problem.message)
+                    }
+                    Severity::Warn => {
+                        summary.warnings += 1;
+                        handler.warn(&problem.message)
+                    }
+                    Severity::Off => unreachable!(),
+                };
+                db.span(problem.span)
+                    .code(DiagnosticId::Lint(rule.name().into()))
+                    .emit();
+            }
+        }
+
+        summary
+    }
+}
+
+/// Numbers of problems reported by [Linter::lint].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Summary {
+    pub errors: usize,
+    pub warnings: usize,
+}
+
+/// Returned by [Linter::new].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownRule(pub String);
+
+impl fmt::Display for UnknownRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown lint rule: {}", self.0)
+    }
+}
+
+impl Error for UnknownRule {}
@@ -0,0 +1,9 @@
+//! Built-in rules, which behave like rules of eslint with the same names.
+
+pub use self::{
+    no_debugger::NoDebugger, no_dupe_keys::NoDupeKeys, no_unreachable::NoUnreachable,
+};
+
+mod no_debugger;
+mod no_dupe_keys;
+mod no_unreachable;
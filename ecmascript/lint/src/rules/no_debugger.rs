@@ -0,0 +1,31 @@
+use crate::{Problem, Rule};
+use ast::*;
+use swc_common::{Visit, VisitWith};
+
+/// Disallows `debugger` statements.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoDebugger;
+
+impl Rule for NoDebugger {
+    fn name(&self) -> &'static str {
+        "no-debugger"
+    }
+
+    fn check(&self, module: &Module) -> Vec<Problem> {
+        let mut v = Visitor::default();
+        module.visit_with(&mut v);
+        v.problems
+    }
+}
+
+#[derive(Default)]
+struct Visitor {
+    problems: Vec<Problem>,
+}
+
+impl Visit<DebuggerStmt> for Visitor {
+    fn visit(&mut self, s: &DebuggerStmt) {
+        self.problems
+            .push(Problem::new(s.span, "unexpected `debugger` statement"));
+    }
+}
@@ -0,0 +1,104 @@
+use crate::{Problem, Rule};
+use ast::*;
+use std::collections::HashMap;
+use swc_common::{Spanned, Visit, VisitWith};
+
+/// Disallows duplicate keys in object literals, like `{ a: 1, a: 2 }`.
+///
+/// A getter and a setter may share a key. Computed keys are not checked.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoDupeKeys;
+
+impl Rule for NoDupeKeys {
+    fn name(&self) -> &'static str {
+        "no-dupe-keys"
+    }
+
+    fn check(&self, module: &Module) -> Vec<Problem> {
+        let mut v = Visitor::default();
+        module.visit_with(&mut v);
+        v.problems
+    }
+}
+
+#[derive(Default)]
+struct Visitor {
+    problems: Vec<Problem>,
+}
+
+/// Kinds of properties defined for a key.
+#[derive(Default)]
+struct Defined {
+    init: bool,
+    get: bool,
+    set: bool,
+}
+
+impl Visit<ObjectLit> for Visitor {
+    fn visit(&mut self, obj: &ObjectLit) {
+        let mut keys: HashMap<String, Defined> = HashMap::new();
+
+        for prop in &obj.props {
+            let (key, span, kind) = match *prop {
+                PropOrSpread::Prop(box Prop::Shorthand(ref i)) => {
+                    (i.sym.to_string(), i.span, Kind::Init)
+                }
+                PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp { ref key, .. }))
+                | PropOrSpread::Prop(box Prop::Method(MethodProp { ref key, .. })) => {
+                    match key_of(key) {
+                        Some(k) => (k, key.span(), Kind::Init),
+                        None => continue,
+                    }
+                }
+                PropOrSpread::Prop(box Prop::Getter(GetterProp { ref key, .. })) => {
+                    match key_of(key) {
+                        Some(k) => (k, key.span(), Kind::Get),
+                        None => continue,
+                    }
+                }
+                PropOrSpread::Prop(box Prop::Setter(SetterProp { ref key, .. })) => {
+                    match key_of(key) {
+                        Some(k) => (k, key.span(), Kind::Set),
+                        None => continue,
+                    }
+                }
+                _ => continue,
+            };
+
+            let defined = keys.entry(key).or_insert_with(Default::default);
+            let dup = match kind {
+                Kind::Init => defined.init || defined.get || defined.set,
+                Kind::Get => defined.init || defined.get,
+                Kind::Set => defined.init || defined.set,
+            };
+            match kind {
+                Kind::Init => defined.init = true,
+                Kind::Get => defined.get = true,
+                Kind::Set => defined.set = true,
+            }
+            if dup {
+                self.problems
+                    .push(Problem::new(span, "duplicate key in object literal"));
+            }
+        }
+
+        obj.visit_children(self);
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Kind {
+    Init,
+    Get,
+    Set,
+}
+
+/// Returns the key as a string, so `1`, `0x1` and `"1"` are the same key.
+fn key_of(name: &PropName) -> Option<String> {
+    match *name {
+        PropName::Ident(ref i) => Some(i.sym.to_string()),
+        PropName::Str(ref s) => Some(s.value.to_string()),
+        PropName::Num(ref n) => Some(n.value.to_string()),
+        PropName::Computed(..) => None,
+    }
+}
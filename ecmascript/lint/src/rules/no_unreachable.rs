@@ -0,0 +1,81 @@
+use crate::{Problem, Rule};
+use ast::*;
+use swc_common::{Spanned, Visit, VisitWith};
+
+/// Disallows statements after `return`, `throw`, `break` and `continue` in
+/// the same list of statements.
+///
+/// Function declarations and `var` declarations without initializers are
+/// hoisted, so they are allowed. Only the first unreachable statement of a
+/// list is reported.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoUnreachable;
+
+impl Rule for NoUnreachable {
+    fn name(&self) -> &'static str {
+        "no-unreachable"
+    }
+
+    fn check(&self, module: &Module) -> Vec<Problem> {
+        let mut v = Visitor::default();
+        module.visit_with(&mut v);
+        // Statements are checked before their children.
+        v.problems.sort_by_key(|p| p.span.lo());
+        v.problems
+    }
+}
+
+#[derive(Default)]
+struct Visitor {
+    problems: Vec<Problem>,
+}
+
+impl Visitor {
+    fn check<'a, I>(&mut self, stmts: I)
+    where
+        I: IntoIterator<Item = &'a Stmt>,
+    {
+        let mut terminated = false;
+        for stmt in stmts {
+            if terminated && !is_hoisted(stmt) {
+                self.problems
+                    .push(Problem::new(stmt.span(), "unreachable code"));
+                return;
+            }
+
+            match *stmt {
+                Stmt::Return(..) | Stmt::Throw(..) | Stmt::Break(..) | Stmt::Continue(..) => {
+                    terminated = true
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+impl Visit<Vec<Stmt>> for Visitor {
+    fn visit(&mut self, stmts: &Vec<Stmt>) {
+        self.check(stmts);
+        stmts.visit_children(self);
+    }
+}
+
+impl Visit<Vec<ModuleItem>> for Visitor {
+    fn visit(&mut self, items: &Vec<ModuleItem>) {
+        self.check(items.iter().filter_map(|item| match *item {
+            ModuleItem::Stmt(ref s) => Some(s),
+            ModuleItem::ModuleDecl(..) => None,
+        }));
+        items.visit_children(self);
+    }
+}
+
+fn is_hoisted(stmt: &Stmt) -> bool {
+    match *stmt {
+        Stmt::Decl(Decl::Fn(..)) | Stmt::Empty(..) => true,
+        Stmt::Decl(Decl::Var(ref v)) => {
+            v.kind == VarDeclKind::Var && v.decls.iter().all(|d| d.init.is_none())
+        }
+        _ => false,
+    }
+}
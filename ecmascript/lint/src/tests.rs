@@ -0,0 +1,180 @@
+use super::*;
+use swc_common::{
+    errors::{HandlerFlags, Level},
+    FileName,
+};
+use swc_ecma_parser::{Parser, Session, SourceFileInput, Syntax};
+
+/// Parses `src` and passes the module to `op`, with snippets of spans.
+fn with_module<F, Ret>(src: &str, op: F) -> Ret
+where
+    F: FnOnce(&Module, &Fn(Span) -> String) -> Ret,
+{
+    ::testing::run_test(|logger, cm, handler| {
+        let fm = cm.new_source_file(FileName::Real("input.js".into()), src.into());
+        let module = Parser::new(
+            Session {
+                handler: &handler,
+                logger: &logger,
+            },
+            Syntax::default(),
+            SourceFileInput::from(&*fm),
+        )
+        .parse_module()?;
+
+        Ok(op(&module, &|span| cm.span_to_snippet(span).unwrap()))
+    })
+    .unwrap()
+}
+
+/// Returns snippets of problems found by `rule`.
+fn check<R: Rule>(rule: R, src: &str) -> Vec<String> {
+    with_module(src, |module, snippet| {
+        rule.check(module)
+            .into_iter()
+            .map(|p| snippet(p.span))
+            .collect()
+    })
+}
+
+#[test]
+fn no_debugger() {
+    assert_eq!(
+        check(rules::NoDebugger, "debugger; function f() { debugger }"),
+        vec!["debugger;", "debugger"]
+    );
+}
+
+#[test]
+fn no_dupe_keys() {
+    assert_eq!(
+        check(
+            rules::NoDupeKeys,
+            "var a = { a: 1, 'a': 2, b, b() {}, 1: 0, 0x1: 0, [a]: 1, [a]: 2 };"
+        ),
+        vec!["'a'", "b", "0x1"]
+    );
+}
+
+#[test]
+fn no_dupe_keys_accessors() {
+    assert_eq!(
+        check(
+            rules::NoDupeKeys,
+            "var a = { get a() {}, set a(v) {}, get b() {}, get b() {}, c: 1, set c(v) {} };"
+        ),
+        vec!["b", "c"]
+    );
+}
+
+#[test]
+fn no_dupe_keys_nested() {
+    assert_eq!(
+        check(rules::NoDupeKeys, "var a = { a: { b: 1, b: 2 } };"),
+        vec!["b"]
+    );
+}
+
+#[test]
+fn no_unreachable() {
+    assert_eq!(
+        check(
+            rules::NoUnreachable,
+            "function f() { return; var a; function g() {} a(); b(); }
+             for (;;) { if (a) { break; } else { continue; c(); } }
+             throw a; d();"
+        ),
+        vec!["a();", "c();", "d();"]
+    );
+}
+
+#[test]
+fn no_unreachable_var_with_init() {
+    assert_eq!(
+        check(rules::NoUnreachable, "function f() { throw a; var b = 1; }"),
+        vec!["var b = 1;"]
+    );
+}
+
+#[test]
+fn config() {
+    let config: LintConfig = serde_json::from_str(
+        r#"{ "rules": { "no-debugger": "warn", "no-unreachable": "off" } }"#,
+    )
+    .unwrap();
+
+    assert_eq!(config.rules["no-debugger"], Severity::Warn);
+    assert_eq!(config.rules["no-unreachable"], Severity::Off);
+    assert!(serde_json::from_str::<LintConfig>(r#"{ "rules": { "a": "on" } }"#).is_err());
+}
+
+#[test]
+fn unknown_rule() {
+    let config: LintConfig =
+        serde_json::from_str(r#"{ "rules": { "no-console": "error" } }"#).unwrap();
+
+    assert_eq!(
+        Linter::new(Registry::builtin(), &config).err(),
+        Some(UnknownRule("no-console".into()))
+    );
+}
+
+#[test]
+fn lint() {
+    let config: LintConfig = serde_json::from_str(
+        r#"{ "rules": { "no-debugger": "warn", "no-unreachable": "off" } }"#,
+    )
+    .unwrap();
+    let linter = Linter::new(Registry::builtin(), &config).unwrap();
+    let (handler, buffer) = Handler::with_buffer(HandlerFlags {
+        can_emit_warnings: true,
+        ..Default::default()
+    });
+
+    let summary = with_module(
+        "debugger; var a = { a: 1, a: 2 }; throw a; b();",
+        |module, _| linter.lint(module, &handler),
+    );
+    let diagnostics = buffer.take();
+
+    assert_eq!(
+        summary,
+        Summary {
+            errors: 1,
+            warnings: 1
+        }
+    );
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].level(), Level::Warning);
+    assert_eq!(diagnostics[0].message(), "unexpected `debugger` statement");
+    assert_eq!(diagnostics[1].level(), Level::Error);
+    assert_eq!(diagnostics[1].message(), "duplicate key in object literal");
+}
+
+#[test]
+fn register() {
+    struct NoDebugger;
+
+    impl Rule for NoDebugger {
+        fn name(&self) -> &'static str {
+            "no-debugger"
+        }
+
+        fn default_severity(&self) -> Severity {
+            Severity::Off
+        }
+
+        fn check(&self, _: &Module) -> Vec<Problem> {
+            vec![]
+        }
+    }
+
+    let mut registry = Registry::builtin();
+    registry.register(NoDebugger);
+
+    assert_eq!(registry.rules().count(), 3);
+    assert_eq!(
+        registry.get("no-debugger").unwrap().default_severity(),
+        Severity::Off
+    );
+}
@@ -0,0 +1,34 @@
+use ast::*;
+use swc_common::Fold;
+use swc_ecma_transforms::util::{is_block_scoped, StmtLike};
+
+/// Merges nested blocks into the enclosing statement list and drops empty
+/// statements.
+pub(super) struct Blocks;
+
+impl<T: StmtLike> Fold<Vec<T>> for Blocks
+where
+    Self: Fold<T>,
+{
+    fn fold(&mut self, stmts: Vec<T>) -> Vec<T> {
+        let mut buf = Vec::with_capacity(stmts.len());
+
+        for stmt_like in stmts {
+            let stmt_like = self.fold(stmt_like);
+            match stmt_like.try_into_stmt() {
+                Ok(Stmt::Empty(..)) => {}
+                Ok(Stmt::Block(block)) => {
+                    if block.stmts.iter().any(is_block_scoped) {
+                        buf.push(T::from_stmt(Stmt::Block(block)));
+                    } else {
+                        buf.extend(block.stmts.into_iter().map(T::from_stmt));
+                    }
+                }
+                Ok(stmt) => buf.push(T::from_stmt(stmt)),
+                Err(stmt_like) => buf.push(stmt_like),
+            }
+        }
+
+        buf
+    }
+}
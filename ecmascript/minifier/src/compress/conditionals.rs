@@ -0,0 +1,319 @@
+use super::util::expr_stmt;
+use ast::*;
+use swc_common::{Fold, FoldWith, Spanned};
+use swc_ecma_transforms::util::{hoisted_decls, is_block_scoped, ExprExt, IsEmpty, Known, StmtLike};
+
+/// Folds `if` statements with a known test or without a body, and replaces
+/// others with conditional and logical expressions.
+///
+/// The simplifier handles known tests only if they are pure.
+pub(super) struct Conditionals;
+
+impl<T: StmtLike> Fold<Vec<T>> for Conditionals
+where
+    Self: Fold<T>,
+{
+    fn fold(&mut self, stmts: Vec<T>) -> Vec<T> {
+        let mut buf: Vec<T> = Vec::with_capacity(stmts.len());
+
+        for stmt_like in stmts {
+            let stmt_like = self.fold(stmt_like);
+            let stmt = match stmt_like.try_into_stmt() {
+                // `if (a) return b; return c;` -> `return a ? b : c;`
+                Ok(Stmt::Return(ReturnStmt {
+                    span,
+                    arg: Some(alt),
+                })) => match buf.pop().map(T::try_into_stmt) {
+                    Some(Ok(Stmt::If(IfStmt {
+                        test,
+                        cons:
+                            box Stmt::Return(ReturnStmt {
+                                arg: Some(cons), ..
+                            }),
+                        alt: None,
+                        ..
+                    }))) => Stmt::Return(ReturnStmt {
+                        span,
+                        arg: Some(cond(test, cons, alt)),
+                    }),
+                    prev => {
+                        match prev {
+                            Some(Ok(stmt)) => buf.push(T::from_stmt(stmt)),
+                            Some(Err(stmt_like)) => buf.push(stmt_like),
+                            None => {}
+                        }
+                        Stmt::Return(ReturnStmt {
+                            span,
+                            arg: Some(alt),
+                        })
+                    }
+                },
+                Ok(stmt) => stmt,
+                Err(stmt_like) => {
+                    buf.push(stmt_like);
+                    continue;
+                }
+            };
+
+            buf.push(T::from_stmt(stmt));
+        }
+
+        buf
+    }
+}
+
+impl Fold<Stmt> for Conditionals {
+    fn fold(&mut self, stmt: Stmt) -> Stmt {
+        let stmt = stmt.fold_children(self);
+
+        match stmt {
+            Stmt::If(IfStmt {
+                span,
+                test,
+                cons,
+                alt,
+            }) => {
+                // `if (a(), true) b()` -> `{ a(), true; b() }`
+                if let (_, Known(val)) = test.as_bool() {
+                    let (node, dead) = if val { (Some(cons), alt) } else { (alt, Some(cons)) };
+
+                    let mut stmts = vec![expr_stmt(test)];
+                    stmts.extend(dead.map(|stmt| hoisted_decls(*stmt)).unwrap_or_default());
+                    stmts.extend(node.map(|stmt| *stmt));
+                    return Stmt::Block(BlockStmt { span, stmts });
+                }
+
+                // `if (a()) {}` -> `a()`
+                if cons.is_empty() && alt.is_empty() {
+                    return Stmt::Expr(ExprStmt { span, expr: test });
+                }
+
+                optimize_if(IfStmt {
+                    span,
+                    test,
+                    cons: unwrap_block(cons),
+                    alt: alt.map(unwrap_block),
+                })
+            }
+
+            _ => stmt,
+        }
+    }
+}
+
+impl Fold<CondExpr> for Conditionals {
+    fn fold(&mut self, expr: CondExpr) -> CondExpr {
+        let expr = expr.fold_children(self);
+
+        match expr.test {
+            // `!a ? b : c` -> `a ? c : b`
+            box Expr::Unary(UnaryExpr {
+                op: op!("!"), arg, ..
+            }) => CondExpr {
+                span: expr.span,
+                test: arg,
+                cons: expr.alt,
+                alt: expr.cons,
+            },
+            test => CondExpr { test, ..expr },
+        }
+    }
+}
+
+fn optimize_if(stmt: IfStmt) -> Stmt {
+    let IfStmt {
+        span,
+        test,
+        cons,
+        alt,
+    } = stmt;
+
+    let alt = if alt.is_empty() { None } else { alt };
+
+    // `if (a) ; else b()` -> `if (!a) b()`
+    let (test, cons, alt) = match alt {
+        Some(alt) => {
+            if cons.is_empty() {
+                (negate(test), alt, None)
+            } else {
+                match test {
+                    // `if (!a) b(); else c()` -> `if (a) c(); else b()`
+                    box Expr::Unary(UnaryExpr {
+                        op: op!("!"), arg, ..
+                    }) => (arg, alt, Some(cons)),
+                    test => (test, cons, Some(alt)),
+                }
+            }
+        }
+        None => (test, cons, None),
+    };
+
+    match (cons, alt) {
+        // `if (a) { if (b) c() }` -> `if (a && b) c()`
+        (
+            box Stmt::If(IfStmt {
+                test: inner,
+                cons,
+                alt: None,
+                ..
+            }),
+            None,
+        ) => optimize_if(IfStmt {
+            span,
+            test: logical(test, op!("&&"), inner),
+            cons,
+            alt: None,
+        }),
+
+        // `if (a) b(); else c()` -> `a ? b() : c()`
+        (
+            box Stmt::Expr(ExprStmt { expr: cons, .. }),
+            Some(box Stmt::Expr(ExprStmt { expr: alt, .. })),
+        ) => Stmt::Expr(ExprStmt {
+            span,
+            expr: cond(test, cons, alt),
+        }),
+
+        // `if (a) return b; else return c` -> `return a ? b : c`
+        (
+            box Stmt::Return(ReturnStmt {
+                arg: Some(cons), ..
+            }),
+            Some(box Stmt::Return(ReturnStmt { arg: Some(alt), .. })),
+        ) => Stmt::Return(ReturnStmt {
+            span,
+            arg: Some(cond(test, cons, alt)),
+        }),
+
+        // `if (a) throw b; else throw c` -> `throw a ? b : c`
+        (
+            box Stmt::Throw(ThrowStmt { arg: cons, .. }),
+            Some(box Stmt::Throw(ThrowStmt { arg: alt, .. })),
+        ) => Stmt::Throw(ThrowStmt {
+            span,
+            arg: cond(test, cons, alt),
+        }),
+
+        // `if (a) b()` -> `a && b()`
+        // `if (!a) b()` -> `a || b()`
+        (box Stmt::Expr(ExprStmt { expr: cons, .. }), None) => Stmt::Expr(ExprStmt {
+            span,
+            expr: match test {
+                box Expr::Unary(UnaryExpr {
+                    op: op!("!"), arg, ..
+                }) => logical(arg, op!("||"), cons),
+                test => logical(test, op!("&&"), cons),
+            },
+        }),
+
+        (cons, alt) => Stmt::If(IfStmt {
+            span,
+            test,
+            cons,
+            alt,
+        }),
+    }
+}
+
+/// `{ a() }` -> `a()`
+fn unwrap_block(stmt: Box<Stmt>) -> Box<Stmt> {
+    match stmt {
+        box Stmt::Block(BlockStmt { span, mut stmts }) => {
+            if stmts.len() == 1 && !is_block_scoped(&stmts[0]) {
+                box stmts.pop().unwrap()
+            } else {
+                box Stmt::Block(BlockStmt { span, stmts })
+            }
+        }
+        _ => stmt,
+    }
+}
+
+/// Negates `expr`, which is used as a boolean.
+fn negate(expr: Box<Expr>) -> Box<Expr> {
+    match expr {
+        // `!!a` is `a` as a boolean.
+        box Expr::Unary(UnaryExpr {
+            op: op!("!"), arg, ..
+        }) => arg,
+
+        box Expr::Bin(BinExpr {
+            span,
+            op,
+            left,
+            right,
+        }) => {
+            let negated = match op {
+                op!("==") => op!("!="),
+                op!("!=") => op!("=="),
+                op!("===") => op!("!=="),
+                op!("!==") => op!("==="),
+                // `!(a < b)` is not `a >= b` because of NaN.
+                _ => {
+                    return box Expr::Unary(UnaryExpr {
+                        span,
+                        op: op!("!"),
+                        arg: box Expr::Bin(BinExpr {
+                            span,
+                            op,
+                            left,
+                            right,
+                        }),
+                    })
+                }
+            };
+            box Expr::Bin(BinExpr {
+                span,
+                op: negated,
+                left,
+                right,
+            })
+        }
+
+        _ => box Expr::Unary(UnaryExpr {
+            span: expr.span(),
+            op: op!("!"),
+            arg: expr,
+        }),
+    }
+}
+
+/// Creates `&&` or `||`.
+fn logical(left: Box<Expr>, op: BinaryOp, right: Box<Expr>) -> Box<Expr> {
+    // `a && (b && c)` -> `a && b && c`
+    let right = match right {
+        box Expr::Bin(BinExpr {
+            span,
+            op: right_op,
+            left: middle,
+            right,
+        }) => {
+            if right_op == op {
+                return logical(logical(left, op, middle), op, right);
+            }
+            box Expr::Bin(BinExpr {
+                span,
+                op: right_op,
+                left: middle,
+                right,
+            })
+        }
+        right => right,
+    };
+
+    box Expr::Bin(BinExpr {
+        span: left.span().to(right.span()),
+        op,
+        left,
+        right,
+    })
+}
+
+fn cond(test: Box<Expr>, cons: Box<Expr>, alt: Box<Expr>) -> Box<Expr> {
+    box Expr::Cond(CondExpr {
+        span: test.span().to(alt.span()),
+        test,
+        cons,
+        alt,
+    })
+}
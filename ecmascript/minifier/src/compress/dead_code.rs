@@ -0,0 +1,106 @@
+use super::util::expr_stmt;
+use ast::*;
+use swc_common::{Fold, FoldWith};
+use swc_ecma_transforms::util::{hoisted_decls, ExprExt, Known, StmtLike};
+
+/// Removes unreachable statements and loops which never run.
+///
+/// Declarations in removed statements are kept if they are hoisted.
+pub(super) struct DeadCode;
+
+impl<T: StmtLike> Fold<Vec<T>> for DeadCode
+where
+    Self: Fold<T>,
+{
+    fn fold(&mut self, stmts: Vec<T>) -> Vec<T> {
+        let mut buf = Vec::with_capacity(stmts.len());
+
+        let mut stmts = stmts.into_iter();
+        while let Some(stmt_like) = stmts.next() {
+            let stmt_like = self.fold(stmt_like);
+            let stmt = match stmt_like.try_into_stmt() {
+                Ok(stmt) => stmt,
+                Err(stmt_like) => {
+                    buf.push(stmt_like);
+                    continue;
+                }
+            };
+
+            let terminates = always_terminates(&stmt);
+            buf.push(T::from_stmt(stmt));
+            if terminates {
+                for stmt_like in stmts {
+                    match stmt_like.try_into_stmt() {
+                        Ok(stmt) => buf.extend(hoisted_decls(stmt).into_iter().map(T::from_stmt)),
+                        Err(stmt_like) => buf.push(stmt_like),
+                    }
+                }
+                return buf;
+            }
+        }
+
+        buf
+    }
+}
+
+impl Fold<Stmt> for DeadCode {
+    fn fold(&mut self, stmt: Stmt) -> Stmt {
+        let stmt = stmt.fold_children(self);
+
+        match stmt {
+            // `while (false) body` -> `false`
+            Stmt::While(WhileStmt { span, test, body }) => match test.as_bool() {
+                (_, Known(false)) => {
+                    let mut stmts = vec![expr_stmt(test)];
+                    stmts.extend(hoisted_decls(*body));
+                    Stmt::Block(BlockStmt { span, stmts })
+                }
+                _ => Stmt::While(WhileStmt { span, test, body }),
+            },
+
+            // `for (init; false; update) body` -> `{ init; false }`
+            Stmt::For(ForStmt {
+                span,
+                init,
+                test: Some(test),
+                update,
+                body,
+            }) => match test.as_bool() {
+                (_, Known(false)) => {
+                    let mut stmts = vec![];
+                    match init {
+                        Some(VarDeclOrExpr::VarDecl(var)) => stmts.push(Stmt::Decl(Decl::Var(var))),
+                        Some(VarDeclOrExpr::Expr(expr)) => stmts.push(expr_stmt(expr)),
+                        None => {}
+                    }
+                    stmts.push(expr_stmt(test));
+                    stmts.extend(hoisted_decls(*body));
+                    Stmt::Block(BlockStmt { span, stmts })
+                }
+                _ => Stmt::For(ForStmt {
+                    span,
+                    init,
+                    test: Some(test),
+                    update,
+                    body,
+                }),
+            },
+
+            _ => stmt,
+        }
+    }
+}
+
+/// Returns true if statements following `stmt` are unreachable.
+fn always_terminates(stmt: &Stmt) -> bool {
+    match *stmt {
+        Stmt::Return(..) | Stmt::Throw(..) | Stmt::Break(..) | Stmt::Continue(..) => true,
+        Stmt::Block(BlockStmt { ref stmts, .. }) => stmts.iter().any(always_terminates),
+        Stmt::If(IfStmt {
+            ref cons,
+            alt: Some(ref alt),
+            ..
+        }) => always_terminates(cons) && always_terminates(alt),
+        _ => false,
+    }
+}
@@ -0,0 +1,301 @@
+use super::util::Bindings;
+use ast::*;
+use swc_common::{Fold, FoldWith, Span, VisitWith};
+
+/// Evaluates calls to built-in functions and methods of literals with constant
+/// arguments.
+///
+/// `Math.max(1, 2)` -> `2`, `'abc'.charCodeAt(0)` -> `97`
+///
+/// Results which are longer than the call are kept as calls.
+#[derive(Default)]
+pub(super) struct Evaluate {
+    /// Used to check if `Math` and `String` are globals.
+    bindings: Bindings,
+}
+
+impl Fold<Module> for Evaluate {
+    fn fold(&mut self, module: Module) -> Module {
+        self.bindings = Bindings::default();
+        module.visit_with(&mut self.bindings);
+
+        module.fold_children(self)
+    }
+}
+
+impl Fold<Expr> for Evaluate {
+    fn fold(&mut self, expr: Expr) -> Expr {
+        let expr = expr.fold_children(self);
+
+        let value = match expr {
+            Expr::Call(CallExpr {
+                span,
+                callee:
+                    ExprOrSuper::Expr(box Expr::Member(MemberExpr {
+                        obj: ExprOrSuper::Expr(ref obj),
+                        prop: box Expr::Ident(ref prop),
+                        computed: false,
+                        ..
+                    })),
+                ref args,
+            }) => self.eval(span, obj, &prop.sym, args),
+            _ => None,
+        };
+
+        value.unwrap_or(expr)
+    }
+}
+
+impl Evaluate {
+    fn eval(&self, span: Span, obj: &Expr, method: &str, args: &[ExprOrSpread]) -> Option<Expr> {
+        let args = args
+            .iter()
+            .map(|arg| match arg.spread {
+                Some(..) => None,
+                None => Some(&*arg.expr),
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let max_len = call_len(obj, method, &args)?;
+
+        let value = match *obj {
+            Expr::Ident(ref obj) => {
+                if self.bindings.count(&obj.sym) != 0 {
+                    return None;
+                }
+
+                match (&*obj.sym, method) {
+                    ("String", "fromCharCode") => str_lit(
+                        span,
+                        args.iter()
+                            .map(|arg| num_arg(arg).and_then(from_char_code))
+                            .collect::<Option<String>>()?,
+                    )?,
+                    ("Math", _) => {
+                        let args = args
+                            .iter()
+                            .map(|arg| num_arg(arg))
+                            .collect::<Option<Vec<_>>>()?;
+                        num_expr(span, math(method, &args)?)
+                    }
+                    _ => return None,
+                }
+            }
+            Expr::Lit(Lit::Str(ref s)) => string_method(span, &s.value, method, &args)?,
+            _ => return None,
+        };
+
+        if len(&value)? <= max_len {
+            Some(value)
+        } else {
+            None
+        }
+    }
+}
+
+/// Methods of `Math`.
+fn math(method: &str, args: &[f64]) -> Option<f64> {
+    // `-0` is compared differently.
+    if args.iter().any(|&n| n == 0.0 && n.is_sign_negative()) {
+        return None;
+    }
+
+    let value = match (method, args) {
+        ("max", _) if !args.is_empty() => args.iter().cloned().fold(args[0], f64::max),
+        ("min", _) if !args.is_empty() => args.iter().cloned().fold(args[0], f64::min),
+        ("abs", &[n]) => n.abs(),
+        ("ceil", &[n]) => n.ceil(),
+        ("floor", &[n]) => n.floor(),
+        ("trunc", &[n]) => n.trunc(),
+        ("sign", &[n]) => {
+            if n == 0.0 {
+                n
+            } else {
+                n.signum()
+            }
+        }
+        // `Math.round` rounds halves towards `+Infinity`.
+        ("round", &[n]) => {
+            if n < 0.0 && n.round() - n == -0.5 {
+                return None;
+            }
+            n.round()
+        }
+        ("pow", &[base, exp]) => base.powf(exp),
+        _ => return None,
+    };
+
+    if value.is_finite() {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+/// Methods of string literals. Indices are in UTF-16 code units as in
+/// ecmascript.
+fn string_method(span: Span, s: &str, method: &str, args: &[&Expr]) -> Option<Expr> {
+    let units: Vec<u16> = s.encode_utf16().collect();
+
+    let value = match method {
+        "charAt" | "charCodeAt" => {
+            let idx = match args.first() {
+                Some(arg) => to_integer(num_arg(arg)?),
+                None => 0.0,
+            };
+            let unit = if 0.0 <= idx && idx < units.len() as f64 {
+                Some(units[idx as usize])
+            } else {
+                None
+            };
+
+            if method == "charAt" {
+                let s = match unit {
+                    Some(unit) => from_char_code(f64::from(unit))?.to_string(),
+                    None => String::new(),
+                };
+                return str_lit(span, s);
+            }
+
+            // `NaN` if out of range.
+            f64::from(unit?)
+        }
+        "indexOf" | "lastIndexOf" => {
+            let search: Vec<u16> = match args {
+                [arg] => str_arg(arg)?.encode_utf16().collect(),
+                _ => return None,
+            };
+
+            let pos = if search.is_empty() {
+                if method == "indexOf" {
+                    Some(0)
+                } else {
+                    Some(units.len())
+                }
+            } else if method == "indexOf" {
+                units.windows(search.len()).position(|w| w == &*search)
+            } else {
+                units.windows(search.len()).rposition(|w| w == &*search)
+            };
+
+            match pos {
+                Some(pos) => pos as f64,
+                None => -1.0,
+            }
+        }
+        // Case mappings of unicode differ between versions.
+        "toUpperCase" | "toLowerCase" if args.is_empty() && s.is_ascii() => {
+            let s = if method == "toUpperCase" {
+                s.to_ascii_uppercase()
+            } else {
+                s.to_ascii_lowercase()
+            };
+            return str_lit(span, s);
+        }
+        _ => return None,
+    };
+
+    Some(num_expr(span, value))
+}
+
+/// `ToUint16` followed by conversion to a character.
+fn from_char_code(n: f64) -> Option<char> {
+    if !n.is_finite() {
+        return None;
+    }
+
+    let code = ((n.trunc() as i64 % 0x1_0000) + 0x1_0000) % 0x1_0000;
+    // Lone surrogates can't be represented.
+    if 0xD800 <= code && code <= 0xDFFF {
+        return None;
+    }
+    ::std::char::from_u32(code as u32)
+}
+
+fn to_integer(n: f64) -> f64 {
+    if n.is_nan() {
+        0.0
+    } else {
+        n.trunc()
+    }
+}
+
+fn num_arg(expr: &Expr) -> Option<f64> {
+    match *expr {
+        Expr::Lit(Lit::Num(Number { value, .. })) => Some(value),
+        Expr::Unary(UnaryExpr {
+            op: op!(unary, "-"),
+            arg: box Expr::Lit(Lit::Num(Number { value, .. })),
+            ..
+        }) => Some(-value),
+        _ => None,
+    }
+}
+
+fn str_arg(expr: &Expr) -> Option<&str> {
+    match *expr {
+        Expr::Lit(Lit::Str(Str { ref value, .. })) => Some(&**value),
+        _ => None,
+    }
+}
+
+/// Creates a number, which is negated if negative.
+fn num_expr(span: Span, value: f64) -> Expr {
+    let span = mark!(span);
+    let lit = Expr::Lit(Lit::Num(Number {
+        span,
+        value: value.abs(),
+        raw: None,
+    }));
+
+    if value.is_sign_negative() {
+        Expr::Unary(UnaryExpr {
+            span,
+            op: op!(unary, "-"),
+            arg: box lit,
+        })
+    } else {
+        lit
+    }
+}
+
+/// Returns `None` if the string would need escapes, which are not emitted by
+/// the code generator.
+fn str_lit(span: Span, value: String) -> Option<Expr> {
+    if value
+        .chars()
+        .any(|c| c == '\'' || c == '\\' || c.is_control() || c == '\u{2028}' || c == '\u{2029}')
+    {
+        return None;
+    }
+
+    Some(Expr::Lit(Lit::Str(Str {
+        span: mark!(span),
+        value: value.into(),
+        has_escape: false,
+        raw: None,
+    })))
+}
+
+/// Length of `expr` when printed.
+fn len(expr: &Expr) -> Option<usize> {
+    match *expr {
+        Expr::Ident(ref i) => Some(i.sym.len()),
+        Expr::Lit(Lit::Str(ref s)) => Some(s.value.len() + 2),
+        Expr::Lit(Lit::Num(ref n)) => Some(n.value.to_string().len()),
+        Expr::Unary(UnaryExpr {
+            op: op!(unary, "-"),
+            ref arg,
+            ..
+        }) => len(arg).map(|len| len + 1),
+        _ => None,
+    }
+}
+
+/// Length of `obj.method(args)` when printed.
+fn call_len(obj: &Expr, method: &str, args: &[&Expr]) -> Option<usize> {
+    let mut n = len(obj)? + 1 + method.len() + 2 + args.len().saturating_sub(1);
+    for arg in args {
+        n += len(arg)?;
+    }
+    Some(n)
+}
@@ -0,0 +1,507 @@
+use ast::*;
+use super::util::{undefined, Bindings};
+use std::collections::HashMap;
+use swc_atoms::JsWord;
+use swc_common::{Fold, FoldWith, Span, Spanned, Visit, VisitWith};
+use swc_ecma_transforms::util::{ExprExt, StmtLike};
+
+/// Inlines IIFEs and functions which are called only once.
+///
+/// Only functions whose body is a single `return` are inlined, so the result
+/// is always smaller. Functions using `this` or `arguments`, and functions
+/// whose name escapes, are kept.
+pub(super) struct Inline;
+
+impl Fold<Module> for Inline {
+    fn fold(&mut self, module: Module) -> Module {
+        let module = module.fold_children(self);
+
+        Module {
+            body: inline_fns(module.body),
+            ..module
+        }
+    }
+}
+
+/// Function declarations in blocks are hoisted to the function in loose mode,
+/// so only the bodies of functions are handled.
+impl Fold<Function> for Inline {
+    fn fold(&mut self, f: Function) -> Function {
+        let f = f.fold_children(self);
+
+        Function {
+            body: f.body.map(|body| BlockStmt {
+                stmts: inline_fns(body.stmts),
+                ..body
+            }),
+            ..f
+        }
+    }
+}
+
+impl Fold<Expr> for Inline {
+    fn fold(&mut self, expr: Expr) -> Expr {
+        let expr = expr.fold_children(self);
+
+        match expr {
+            // `(function (a) { return a + 1 })(2)` -> `2 + 1`
+            Expr::Call(CallExpr {
+                span,
+                callee: ExprOrSuper::Expr(callee),
+                args,
+            }) => {
+                let inlined = {
+                    let callee = match *callee {
+                        Expr::Paren(ParenExpr { ref expr, .. }) => &**expr,
+                        ref callee => callee,
+                    };
+                    Inlinable::from_callee(callee).and_then(|f| f.apply(span, &args))
+                };
+
+                match inlined {
+                    Some(expr) => *expr,
+                    None => Expr::Call(CallExpr {
+                        span,
+                        callee: ExprOrSuper::Expr(callee),
+                        args,
+                    }),
+                }
+            }
+
+            _ => expr,
+        }
+    }
+}
+
+impl Fold<CallExpr> for Inline {
+    fn fold(&mut self, e: CallExpr) -> CallExpr {
+        CallExpr {
+            callee: fold_callee(self, e.callee),
+            args: e.args.fold_with(self),
+            ..e
+        }
+    }
+}
+
+/// Folds the callee of a call with `folder`, which may inline it.
+///
+/// `(function () { return a.b })()()` calls `a.b` with `undefined` as `this`,
+/// so an inlined callee becomes `(0, a.b)` instead of `a.b`.
+fn fold_callee<F>(folder: &mut F, callee: ExprOrSuper) -> ExprOrSuper
+where
+    ExprOrSuper: FoldWith<F>,
+{
+    let was_call = match callee {
+        ExprOrSuper::Expr(ref e) => is_call(e),
+        _ => false,
+    };
+
+    match callee.fold_with(folder) {
+        ExprOrSuper::Expr(callee) => {
+            if !was_call || !is_member(&callee) {
+                return ExprOrSuper::Expr(callee);
+            }
+
+            let span = callee.span();
+            let zero = box Expr::Lit(Lit::Num(Number {
+                span,
+                value: 0.0,
+                raw: None,
+            }));
+            ExprOrSuper::Expr(box Expr::Seq(SeqExpr {
+                span,
+                exprs: vec![zero, callee],
+            }))
+        }
+        callee => callee,
+    }
+}
+
+fn is_call(e: &Expr) -> bool {
+    match *e {
+        Expr::Call(..) => true,
+        Expr::Paren(ParenExpr { ref expr, .. }) => is_call(expr),
+        _ => false,
+    }
+}
+
+/// `(a.b)()` is also called with `this` of `a`.
+fn is_member(e: &Expr) -> bool {
+    match *e {
+        Expr::Member(..) => true,
+        Expr::Paren(ParenExpr { ref expr, .. }) => is_member(expr),
+        _ => false,
+    }
+}
+
+/// Inlines function declarations in `stmts` which are called only once.
+fn inline_fns<T>(stmts: Vec<T>) -> Vec<T>
+where
+    T: StmtLike + VisitWith<Bindings> + VisitWith<Refs> + FoldWith<Replacer>,
+{
+    let mut candidates = vec![];
+    // Names declared by `stmts` itself.
+    let mut top_level = Bindings::default();
+
+    let mut stmts: Vec<T> = stmts
+        .into_iter()
+        .enumerate()
+        .map(|(i, stmt_like)| match stmt_like.try_into_stmt() {
+            Ok(stmt) => {
+                match stmt {
+                    Stmt::Decl(Decl::Fn(FnDecl {
+                        ref ident,
+                        ref function,
+                        ..
+                    })) => {
+                        top_level.add(ident);
+                        if let Some(f) = Inlinable::from_fn(function, None) {
+                            candidates.push((i, ident.sym.clone(), f));
+                        }
+                    }
+                    Stmt::Decl(Decl::Class(ClassDecl { ref ident, .. })) => top_level.add(ident),
+                    Stmt::Decl(Decl::Var(ref var)) => {
+                        for decl in &var.decls {
+                            decl.name.visit_with(&mut top_level);
+                        }
+                    }
+                    _ => {}
+                }
+                T::from_stmt(stmt)
+            }
+            Err(stmt_like) => stmt_like,
+        })
+        .collect();
+
+    if candidates.is_empty() {
+        return stmts;
+    }
+
+    let mut bindings = Bindings::default();
+    stmts.visit_with(&mut bindings);
+    // Names which may be shadowed at the call site.
+    let is_shadowed = |sym: &JsWord| bindings.count(sym) > top_level.count(sym);
+
+    let names: Vec<_> = candidates.iter().map(|c| c.1.clone()).collect();
+    let mut refs = Refs {
+        names: names.iter().map(|name| (name.clone(), 0)).collect(),
+    };
+    stmts.visit_with(&mut refs);
+
+    let mut removed = vec![];
+    for (i, name, f) in candidates {
+        // The declaration and the call.
+        if refs.names[&name] != 2 || is_shadowed(&name) {
+            continue;
+        }
+        if f.free.iter().any(|sym| is_shadowed(sym) || names.contains(sym)) {
+            continue;
+        }
+
+        let mut replacer = Replacer {
+            name,
+            f: Some(f),
+        };
+        stmts = stmts
+            .into_iter()
+            .enumerate()
+            .map(|(j, stmt_like)| {
+                if i == j {
+                    stmt_like
+                } else {
+                    stmt_like.fold_with(&mut replacer)
+                }
+            })
+            .collect();
+        if replacer.f.is_none() {
+            removed.push(i);
+        }
+    }
+
+    stmts
+        .into_iter()
+        .enumerate()
+        .filter(|&(i, _)| !removed.contains(&i))
+        .map(|(_, stmt_like)| stmt_like)
+        .collect()
+}
+
+/// A function which can be replaced with its return value.
+struct Inlinable {
+    params: Vec<Ident>,
+    /// Number of references to each parameter.
+    uses: Vec<usize>,
+    value: Box<Expr>,
+    /// Names used by `value` other than parameters.
+    free: Vec<JsWord>,
+}
+
+impl Inlinable {
+    fn from_callee(callee: &Expr) -> Option<Self> {
+        match *callee {
+            Expr::Fn(FnExpr {
+                ref ident,
+                ref function,
+            }) => Inlinable::from_fn(function, ident.as_ref()),
+            Expr::Arrow(ArrowExpr {
+                ref params,
+                ref body,
+                generator_token: None,
+                async_token: None,
+                ..
+            }) => match *body {
+                BlockStmtOrExpr::BlockStmt(ref body) => Inlinable::from_body(params, body),
+                BlockStmtOrExpr::Expr(ref value) => Inlinable::new(params, value.clone()),
+            },
+            _ => None,
+        }
+    }
+
+    fn from_fn(f: &Function, ident: Option<&Ident>) -> Option<Self> {
+        if f.generator_token.is_some() || f.async_token.is_some() {
+            return None;
+        }
+
+        let inlinable = match f.body {
+            Some(ref body) => {
+                let params: Vec<_> = f.params.iter().map(|param| param.pat.clone()).collect();
+                Inlinable::from_body(&params, body)?
+            }
+            None => return None,
+        };
+        // Recursive function expressions.
+        if let Some(ident) = ident {
+            if inlinable.free.contains(&ident.sym) {
+                return None;
+            }
+        }
+        Some(inlinable)
+    }
+
+    fn from_body(params: &[Pat], body: &BlockStmt) -> Option<Self> {
+        if body.stmts.len() > 1 {
+            return None;
+        }
+        let value = match body.stmts.first() {
+            None => undefined(body.span),
+            Some(&Stmt::Return(ReturnStmt { ref arg, span })) => {
+                arg.clone().unwrap_or_else(|| undefined(span))
+            }
+            // `function () { a() }` returns `undefined`.
+            Some(&Stmt::Expr(ExprStmt { ref expr, .. })) => box Expr::Seq(SeqExpr {
+                span: body.span,
+                exprs: vec![expr.clone(), undefined(body.span)],
+            }),
+            Some(_) => return None,
+        };
+
+        Inlinable::new(params, value)
+    }
+
+    fn new(params: &[Pat], value: Box<Expr>) -> Option<Self> {
+        let params = params
+            .iter()
+            .map(|pat| match *pat {
+                Pat::Ident(ref i) => Some(i.clone()),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        let mut usage = Usage::default();
+        value.visit_with(&mut usage);
+        if usage.bail {
+            return None;
+        }
+
+        let uses = params
+            .iter()
+            .map(|p| usage.ids.iter().filter(|id| **id == p.sym).count())
+            .collect();
+        let free = usage
+            .ids
+            .into_iter()
+            .filter(|id| params.iter().all(|p| p.sym != *id))
+            .collect();
+
+        Some(Inlinable {
+            params,
+            uses,
+            value,
+            free,
+        })
+    }
+
+    /// Returns the value of the function called with `args`.
+    fn apply(&self, span: Span, args: &[ExprOrSpread]) -> Option<Box<Expr>> {
+        if args.iter().any(|arg| arg.spread.is_some()) {
+            return None;
+        }
+        // Unused arguments are dropped.
+        if args
+            .iter()
+            .skip(self.params.len())
+            .any(|arg| arg.expr.may_have_side_effects())
+        {
+            return None;
+        }
+
+        let mut subst = Subst { values: vec![] };
+        for (i, param) in self.params.iter().enumerate() {
+            let value = match args.get(i) {
+                Some(arg) => {
+                    match *arg.expr {
+                        // Parameters are evaluated in order, before the body.
+                        Expr::Lit(..) => {}
+                        Expr::Ident(..) if !self.value.may_have_side_effects() => {}
+                        _ => return None,
+                    }
+                    if self.uses[i] > 1 {
+                        return None;
+                    }
+                    arg.expr.clone()
+                }
+                None => undefined(span),
+            };
+            subst.values.push((param.sym.clone(), value));
+        }
+
+        Some(self.value.clone().fold_with(&mut subst))
+    }
+}
+
+/// Collects references in a return value. `bail` is set if the value can't be
+/// moved out of the function.
+#[derive(Default)]
+struct Usage {
+    ids: Vec<JsWord>,
+    bail: bool,
+}
+
+impl Visit<Expr> for Usage {
+    fn visit(&mut self, expr: &Expr) {
+        match *expr {
+            Expr::Ident(Ident {
+                sym: js_word!("arguments"),
+                ..
+            })
+            | Expr::This(..)
+            | Expr::Assign(..)
+            | Expr::Update(..)
+            | Expr::Fn(..)
+            | Expr::Arrow(..)
+            | Expr::Class(..)
+            | Expr::Yield(..)
+            | Expr::Await(..)
+            | Expr::MetaProp(..) => self.bail = true,
+            Expr::Ident(ref i) => self.ids.push(i.sym.clone()),
+            _ => expr.visit_children(self),
+        }
+    }
+}
+
+impl Visit<MemberExpr> for Usage {
+    fn visit(&mut self, e: &MemberExpr) {
+        e.obj.visit_with(self);
+        if e.computed {
+            e.prop.visit_with(self);
+        }
+    }
+}
+
+/// `{ a }` can't be replaced with `{ 1 }`, and methods have their own scopes.
+impl Visit<Prop> for Usage {
+    fn visit(&mut self, prop: &Prop) {
+        match *prop {
+            Prop::KeyValue(..) => prop.visit_children(self),
+            _ => self.bail = true,
+        }
+    }
+}
+
+/// Replaces parameters with arguments.
+struct Subst {
+    values: Vec<(JsWord, Box<Expr>)>,
+}
+
+impl Fold<Expr> for Subst {
+    fn fold(&mut self, expr: Expr) -> Expr {
+        match expr {
+            Expr::Ident(i) => match self.values.iter().find(|v| v.0 == i.sym) {
+                Some(&(_, ref value)) => *value.clone(),
+                None => Expr::Ident(i),
+            },
+            _ => expr.fold_children(self),
+        }
+    }
+}
+
+impl Fold<MemberExpr> for Subst {
+    fn fold(&mut self, e: MemberExpr) -> MemberExpr {
+        MemberExpr {
+            obj: e.obj.fold_with(self),
+            prop: if e.computed {
+                e.prop.fold_with(self)
+            } else {
+                e.prop
+            },
+            ..e
+        }
+    }
+}
+
+/// Counts all identifiers with the given names, including declarations and
+/// property names.
+struct Refs {
+    names: HashMap<JsWord, usize>,
+}
+
+impl Visit<Ident> for Refs {
+    fn visit(&mut self, i: &Ident) {
+        if let Some(count) = self.names.get_mut(&i.sym) {
+            *count += 1;
+        }
+    }
+}
+
+/// Replaces the call to a function with its value.
+struct Replacer {
+    name: JsWord,
+    /// `None` if the call is replaced.
+    f: Option<Inlinable>,
+}
+
+impl Fold<Expr> for Replacer {
+    fn fold(&mut self, expr: Expr) -> Expr {
+        let expr = expr.fold_children(self);
+
+        match expr {
+            Expr::Call(CallExpr {
+                span,
+                callee: ExprOrSuper::Expr(box Expr::Ident(ref callee)),
+                ref args,
+            }) if callee.sym == self.name => {
+                let value = match self.f {
+                    Some(ref f) => f.apply(span, args),
+                    None => None,
+                };
+                if let Some(value) = value {
+                    self.f = None;
+                    return *value;
+                }
+            }
+            _ => {}
+        }
+
+        expr
+    }
+}
+
+impl Fold<CallExpr> for Replacer {
+    fn fold(&mut self, e: CallExpr) -> CallExpr {
+        CallExpr {
+            callee: fold_callee(self, e.callee),
+            args: e.args.fold_with(self),
+            ..e
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use ast::*;
+use swc_common::Fold;
+use swc_ecma_transforms::util::StmtLike;
+
+/// Joins consecutive variable declarations of the same kind.
+///
+/// `var a = 1; var b = 2;` -> `var a = 1, b = 2;`
+pub(super) struct JoinVars;
+
+impl<T: StmtLike> Fold<Vec<T>> for JoinVars
+where
+    Self: Fold<T>,
+{
+    fn fold(&mut self, stmts: Vec<T>) -> Vec<T> {
+        let mut buf: Vec<T> = Vec::with_capacity(stmts.len());
+        // Declaration which may be joined with the next statement.
+        let mut prev: Option<VarDecl> = None;
+
+        for stmt_like in stmts {
+            let stmt_like = self.fold(stmt_like);
+            match stmt_like.try_into_stmt() {
+                Ok(Stmt::Decl(Decl::Var(var))) => {
+                    prev = match prev {
+                        Some(mut prev) => {
+                            if prev.kind == var.kind {
+                                prev.span = prev.span.to(var.span);
+                                prev.decls.extend(var.decls);
+                                Some(prev)
+                            } else {
+                                buf.push(T::from_stmt(Stmt::Decl(Decl::Var(prev))));
+                                Some(var)
+                            }
+                        }
+                        None => Some(var),
+                    };
+                }
+
+                // `var a; for (var i = 0;;)` -> `for (var a, i = 0;;)`
+                Ok(Stmt::For(ForStmt {
+                    span,
+                    init,
+                    test,
+                    update,
+                    body,
+                })) => {
+                    let init = match (prev.take(), init) {
+                        (Some(prev), None) => {
+                            if prev.kind == VarDeclKind::Var {
+                                Some(VarDeclOrExpr::VarDecl(prev))
+                            } else {
+                                buf.push(T::from_stmt(Stmt::Decl(Decl::Var(prev))));
+                                None
+                            }
+                        }
+                        (Some(mut prev), Some(VarDeclOrExpr::VarDecl(var))) => {
+                            if prev.kind == VarDeclKind::Var && var.kind == VarDeclKind::Var {
+                                prev.span = prev.span.to(var.span);
+                                prev.decls.extend(var.decls);
+                                Some(VarDeclOrExpr::VarDecl(prev))
+                            } else {
+                                buf.push(T::from_stmt(Stmt::Decl(Decl::Var(prev))));
+                                Some(VarDeclOrExpr::VarDecl(var))
+                            }
+                        }
+                        (prev, init) => {
+                            if let Some(prev) = prev {
+                                buf.push(T::from_stmt(Stmt::Decl(Decl::Var(prev))));
+                            }
+                            init
+                        }
+                    };
+
+                    buf.push(T::from_stmt(Stmt::For(ForStmt {
+                        span,
+                        init,
+                        test,
+                        update,
+                        body,
+                    })));
+                }
+
+                Ok(stmt) => {
+                    if let Some(prev) = prev.take() {
+                        buf.push(T::from_stmt(Stmt::Decl(Decl::Var(prev))));
+                    }
+                    buf.push(T::from_stmt(stmt));
+                }
+                Err(stmt_like) => {
+                    if let Some(prev) = prev.take() {
+                        buf.push(T::from_stmt(Stmt::Decl(Decl::Var(prev))));
+                    }
+                    buf.push(stmt_like);
+                }
+            }
+        }
+
+        if let Some(prev) = prev {
+            buf.push(T::from_stmt(Stmt::Decl(Decl::Var(prev))));
+        }
+
+        buf
+    }
+}
@@ -0,0 +1,162 @@
+use self::{
+    blocks::Blocks, conditionals::Conditionals, dead_code::DeadCode, evaluate::Evaluate,
+    inline::Inline, join_vars::JoinVars, properties::Properties, pure_funcs::PureFuncs,
+    sequences::Sequences, side_effects::SideEffects, unused::Unused,
+};
+use ast::*;
+use crate::{
+    option::CompressOptions,
+    stats::{count_nodes, Stats},
+};
+use std::sync::{Arc, Mutex};
+use swc_common::Fold;
+use swc_ecma_transforms::{fixer, simplifier};
+
+mod blocks;
+mod conditionals;
+mod dead_code;
+mod evaluate;
+mod inline;
+mod join_vars;
+mod properties;
+mod pure_funcs;
+mod sequences;
+mod side_effects;
+#[cfg(test)]
+mod tests;
+mod unused;
+mod util;
+
+/// Number of passes when [CompressOptions::passes] is zero.
+///
+/// Passes which create new nodes may not reach a fixed point.
+const MAX_PASSES: usize = 10;
+
+/// Creates a compressor, which repeats optimizations until the module stops
+/// changing.
+pub fn compressor(options: CompressOptions) -> impl Fold<Module> {
+    Compressor {
+        options,
+        stats: None,
+    }
+}
+
+/// Creates a compressor which records what each rule does into `stats`.
+///
+/// This is slower than [compressor], as modules are compared after each
+/// rule.
+pub fn compressor_with_stats(
+    options: CompressOptions,
+    stats: Arc<Mutex<Stats>>,
+) -> impl Fold<Module> {
+    Compressor {
+        options,
+        stats: Some(stats),
+    }
+}
+
+#[derive(Debug)]
+struct Compressor {
+    options: CompressOptions,
+    stats: Option<Arc<Mutex<Stats>>>,
+}
+
+impl Fold<Module> for Compressor {
+    fn fold(&mut self, mut module: Module) -> Module {
+        let passes = match self.options.passes {
+            0 => MAX_PASSES,
+            passes => passes,
+        };
+
+        if let Some(ref stats) = self.stats {
+            stats.lock().unwrap().nodes_before += count_nodes(&module);
+        }
+
+        for _ in 0..passes {
+            let prev = module.clone();
+            module = self.optimize(module);
+            if let Some(ref stats) = self.stats {
+                stats.lock().unwrap().passes += 1;
+            }
+            if module == prev {
+                break;
+            }
+        }
+
+        if let Some(ref stats) = self.stats {
+            stats.lock().unwrap().nodes_after += count_nodes(&module);
+        }
+
+        module
+    }
+}
+
+impl Compressor {
+    /// Runs a pass.
+    fn optimize(&mut self, module: Module) -> Module {
+        let mut module = self.apply("simplifier", module, simplifier());
+
+        if self.options.evaluate {
+            module = self.apply("evaluate", module, Evaluate::default());
+        }
+        if self.options.inline {
+            module = self.apply("inline", module, Inline);
+        }
+        if self.options.conditionals {
+            module = self.apply("conditionals", module, Conditionals);
+        }
+        if self.options.dead_code {
+            module = self.apply("dead_code", module, DeadCode);
+        }
+        if self.options.drop_console || !self.options.pure_funcs.is_empty() {
+            let pass = PureFuncs::new(self.options.drop_console, self.options.pure_funcs.clone());
+            module = self.apply("pure_funcs", module, pass);
+        }
+        if self.options.unused {
+            let pass = Unused::new(
+                self.options.top_level,
+                self.options.keep_fargs,
+                self.options.keep_fnames.clone(),
+                self.options.keep_classnames.clone(),
+            );
+            module = self.apply("unused", module, pass);
+        }
+        if self.options.side_effects {
+            module = self.apply("side_effects", module, SideEffects);
+        }
+        module = self.apply("blocks", module, Blocks);
+        if self.options.sequences != 0 {
+            let pass = Sequences {
+                max: self.options.sequences,
+            };
+            module = self.apply("sequences", module, pass);
+        }
+        if self.options.join_vars {
+            module = self.apply("join_vars", module, JoinVars);
+        }
+        if self.options.properties {
+            let pass = Properties {
+                shorthand: self.options.ecma >= 2015,
+            };
+            module = self.apply("properties", module, pass);
+        }
+
+        fixer().fold(module)
+    }
+
+    /// Applies a rule, recording its effect if stats are requested.
+    fn apply<F>(&self, rule: &'static str, module: Module, mut pass: F) -> Module
+    where
+        F: Fold<Module>,
+    {
+        match self.stats {
+            Some(ref stats) => {
+                let prev = module.clone();
+                let module = pass.fold(module);
+                stats.lock().unwrap().record(rule, &prev, &module);
+                module
+            }
+            None => pass.fold(module),
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use crate::is_ident;
+use ast::*;
+use swc_common::{Fold, FoldWith};
+
+/// Shortens property names.
+///
+/// `a['b']` -> `a.b`, `{ 'b': 1 }` -> `{ b: 1 }` and `({ b: b } = c)` ->
+/// `({ b } = c)`. `{ b: b }` -> `{ b }` only if `shorthand` is set.
+pub(super) struct Properties {
+    /// Shorthand properties of object literals require es2015.
+    pub shorthand: bool,
+}
+
+impl Fold<MemberExpr> for Properties {
+    fn fold(&mut self, e: MemberExpr) -> MemberExpr {
+        let e = e.fold_children(self);
+
+        let prop = match *e.prop {
+            Expr::Lit(Lit::Str(ref s)) if e.computed && is_ident(&s.value) => {
+                Ident::new(s.value.clone(), s.span)
+            }
+            _ => return e,
+        };
+        MemberExpr {
+            prop: box Expr::Ident(prop),
+            computed: false,
+            ..e
+        }
+    }
+}
+
+/// `{ [a]: 1 }` is not touched, as `{ ['__proto__']: 1 }` defines a property
+/// while `{ __proto__: 1 }` sets the prototype.
+impl Fold<PropName> for Properties {
+    fn fold(&mut self, name: PropName) -> PropName {
+        match name {
+            PropName::Str(ref s) if is_ident(&s.value) => {
+                PropName::Ident(Ident::new(s.value.clone(), s.span))
+            }
+            _ => name.fold_children(self),
+        }
+    }
+}
+
+impl Fold<Prop> for Properties {
+    fn fold(&mut self, prop: Prop) -> Prop {
+        let prop = prop.fold_children(self);
+        if !self.shorthand {
+            return prop;
+        }
+
+        match prop {
+            // `{ __proto__ }` defines a property.
+            Prop::KeyValue(KeyValueProp {
+                key: PropName::Ident(ref key),
+                value: box Expr::Ident(ref value),
+            }) if key.sym == value.sym && &*key.sym != "__proto__" => {
+                Prop::Shorthand(value.clone())
+            }
+            _ => prop,
+        }
+    }
+}
+
+impl Fold<ObjectPatProp> for Properties {
+    fn fold(&mut self, prop: ObjectPatProp) -> ObjectPatProp {
+        let prop = prop.fold_children(self);
+
+        match prop {
+            ObjectPatProp::KeyValue(KeyValuePatProp {
+                key: PropName::Ident(ref key),
+                value: box Pat::Ident(ref value),
+            }) if key.sym == value.sym => ObjectPatProp::Assign(AssignPatProp {
+                span: key.span.to(value.span),
+                key: value.clone(),
+                value: None,
+            }),
+            _ => prop,
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use super::util::{undefined, Bindings};
+use ast::*;
+use swc_common::{Fold, FoldWith, Spanned, VisitWith};
+use swc_ecma_transforms::util::extract_side_effects_to;
+
+/// Drops calls to `console` and calls to functions listed in `pure_funcs`
+/// whose values are unused.
+///
+/// Arguments of pure functions are kept if they have side effects, while
+/// calls to `console` are dropped with their arguments.
+pub(super) struct PureFuncs {
+    drop_console: bool,
+    /// Names like `Math.floor`.
+    pure_funcs: Vec<String>,
+    /// Used to check if `console` is a global.
+    bindings: Bindings,
+}
+
+impl PureFuncs {
+    pub fn new(drop_console: bool, pure_funcs: Vec<String>) -> Self {
+        PureFuncs {
+            drop_console,
+            pure_funcs,
+            bindings: Default::default(),
+        }
+    }
+
+    /// Returns side effects of `expr` if it's a call to a pure function.
+    fn drop_pure_call(&self, expr: Box<Expr>) -> Result<Vec<Box<Expr>>, Box<Expr>> {
+        let pure = match *expr {
+            Expr::Call(CallExpr {
+                callee: ExprOrSuper::Expr(ref callee),
+                ..
+            }) => self.is_pure(callee),
+            _ => false,
+        };
+        if !pure {
+            return Err(expr);
+        }
+
+        let mut exprs = vec![];
+        if let box Expr::Call(CallExpr { args, .. }) = expr {
+            for arg in args {
+                match arg.spread {
+                    // `f(...a)` iterates `a`.
+                    Some(..) => exprs.push(box Expr::Array(ArrayLit {
+                        span: arg.expr.span(),
+                        elems: vec![Some(arg)],
+                    })),
+                    None => extract_side_effects_to(&mut exprs, arg.expr),
+                }
+            }
+        }
+        Ok(exprs)
+    }
+
+    fn is_pure(&self, callee: &Expr) -> bool {
+        match name(callee) {
+            Some(name) => self.pure_funcs.iter().any(|f| *f == name),
+            None => false,
+        }
+    }
+}
+
+impl Fold<Module> for PureFuncs {
+    fn fold(&mut self, module: Module) -> Module {
+        self.bindings = Bindings::default();
+        module.visit_with(&mut self.bindings);
+
+        module.fold_children(self)
+    }
+}
+
+/// `console.log(a)` -> `void 0`
+impl Fold<Expr> for PureFuncs {
+    fn fold(&mut self, expr: Expr) -> Expr {
+        let expr = expr.fold_children(self);
+        if !self.drop_console {
+            return expr;
+        }
+
+        let is_console = match expr {
+            Expr::Call(CallExpr {
+                callee:
+                    ExprOrSuper::Expr(box Expr::Member(MemberExpr {
+                        obj: ExprOrSuper::Expr(box Expr::Ident(ref obj)),
+                        ..
+                    })),
+                ..
+            }) => &*obj.sym == "console" && self.bindings.count(&obj.sym) == 0,
+            _ => false,
+        };
+
+        if is_console {
+            *undefined(expr.span())
+        } else {
+            expr
+        }
+    }
+}
+
+impl Fold<Stmt> for PureFuncs {
+    fn fold(&mut self, stmt: Stmt) -> Stmt {
+        let stmt = stmt.fold_children(self);
+
+        match stmt {
+            // `f(a(), 1);` -> `a();`
+            Stmt::Expr(ExprStmt { span, expr }) => {
+                let expr_span = expr.span();
+                match self.drop_pure_call(expr) {
+                    Ok(mut exprs) => match exprs.len() {
+                        0 => Stmt::Empty(EmptyStmt { span }),
+                        1 => Stmt::Expr(ExprStmt {
+                            span,
+                            expr: exprs.pop().unwrap(),
+                        }),
+                        _ => Stmt::Expr(ExprStmt {
+                            span,
+                            expr: box Expr::Seq(SeqExpr {
+                                span: expr_span,
+                                exprs,
+                            }),
+                        }),
+                    },
+                    Err(expr) => Stmt::Expr(ExprStmt { span, expr }),
+                }
+            }
+            _ => stmt,
+        }
+    }
+}
+
+/// `(f(a()), b)` -> `(a(), b)`
+impl Fold<SeqExpr> for PureFuncs {
+    fn fold(&mut self, e: SeqExpr) -> SeqExpr {
+        let SeqExpr { span, mut exprs } = e.fold_children(self);
+        let last = exprs.pop().expect("SeqExpr.exprs must not be empty");
+
+        let mut buf = Vec::with_capacity(exprs.len() + 1);
+        for expr in exprs {
+            match self.drop_pure_call(expr) {
+                Ok(effects) => buf.extend(effects),
+                Err(expr) => buf.push(expr),
+            }
+        }
+        buf.push(last);
+
+        SeqExpr { span, exprs: buf }
+    }
+}
+
+/// Returns `a.b.c` for `a.b.c`.
+fn name(expr: &Expr) -> Option<String> {
+    match *expr {
+        Expr::Ident(ref i) => Some(i.sym.to_string()),
+        Expr::Member(MemberExpr {
+            obj: ExprOrSuper::Expr(ref obj),
+            prop: box Expr::Ident(ref prop),
+            computed: false,
+            ..
+        }) => name(obj).map(|obj| format!("{}.{}", obj, prop.sym)),
+        _ => None,
+    }
+}
@@ -0,0 +1,135 @@
+use super::util::expr_stmt;
+use ast::*;
+use std::mem;
+use swc_common::{Fold, Spanned};
+use swc_ecma_transforms::util::StmtLike;
+
+/// Joins consecutive expression statements with the comma operator, and
+/// moves them into the following statement if possible.
+///
+/// `a(); b(); return c` -> `return a(), b(), c`
+pub(super) struct Sequences {
+    /// Maximum number of expressions in a sequence.
+    pub max: usize,
+}
+
+impl<T: StmtLike> Fold<Vec<T>> for Sequences
+where
+    Self: Fold<T>,
+{
+    fn fold(&mut self, stmts: Vec<T>) -> Vec<T> {
+        let mut buf = Vec::with_capacity(stmts.len());
+        // Pending expression statements.
+        let mut exprs = vec![];
+
+        for stmt_like in stmts {
+            let stmt_like = self.fold(stmt_like);
+            let stmt = match stmt_like.try_into_stmt() {
+                Ok(stmt) => stmt,
+                Err(stmt_like) => {
+                    flush(&mut buf, &mut exprs);
+                    buf.push(stmt_like);
+                    continue;
+                }
+            };
+
+            match stmt {
+                // Directives like `'use strict'`.
+                Stmt::Expr(ExprStmt {
+                    expr: box Expr::Lit(Lit::Str(..)),
+                    ..
+                }) => {}
+                Stmt::Expr(ExprStmt { expr, .. }) => {
+                    if exprs.len() >= self.max {
+                        flush(&mut buf, &mut exprs);
+                    }
+                    push_expr(&mut exprs, expr);
+                    continue;
+                }
+                _ => {}
+            }
+
+            let stmt = prepend(&mut exprs, stmt);
+            flush(&mut buf, &mut exprs);
+            buf.push(T::from_stmt(stmt));
+        }
+        flush(&mut buf, &mut exprs);
+
+        buf
+    }
+}
+
+/// Moves `exprs` into `stmt` if it starts with an expression. `exprs` is left
+/// as-is otherwise.
+fn prepend(exprs: &mut Vec<Box<Expr>>, stmt: Stmt) -> Stmt {
+    if exprs.is_empty() {
+        return stmt;
+    }
+
+    match stmt {
+        Stmt::Return(ReturnStmt {
+            span,
+            arg: Some(arg),
+        }) => Stmt::Return(ReturnStmt {
+            span,
+            arg: Some(seq(exprs, Some(arg))),
+        }),
+        Stmt::Throw(ThrowStmt { span, arg }) => Stmt::Throw(ThrowStmt {
+            span,
+            arg: seq(exprs, Some(arg)),
+        }),
+        Stmt::If(IfStmt {
+            span,
+            test,
+            cons,
+            alt,
+        }) => Stmt::If(IfStmt {
+            span,
+            test: seq(exprs, Some(test)),
+            cons,
+            alt,
+        }),
+        Stmt::Switch(SwitchStmt {
+            span,
+            discriminant,
+            cases,
+        }) => Stmt::Switch(SwitchStmt {
+            span,
+            discriminant: seq(exprs, Some(discriminant)),
+            cases,
+        }),
+        _ => stmt,
+    }
+}
+
+/// Takes `exprs` and creates a sequence ending with `last`.
+fn seq(exprs: &mut Vec<Box<Expr>>, last: Option<Box<Expr>>) -> Box<Expr> {
+    let mut exprs = mem::replace(exprs, vec![]);
+    if let Some(last) = last {
+        push_expr(&mut exprs, last);
+    }
+    if exprs.len() == 1 {
+        return exprs.pop().unwrap();
+    }
+
+    let span = exprs[0].span().to(exprs[exprs.len() - 1].span());
+    box Expr::Seq(SeqExpr { span, exprs })
+}
+
+/// Adds `expr` to `exprs`, flattening sequences.
+fn push_expr(exprs: &mut Vec<Box<Expr>>, expr: Box<Expr>) {
+    match *expr {
+        Expr::Seq(SeqExpr { exprs: seq, .. })
+        | Expr::Paren(ParenExpr {
+            expr: box Expr::Seq(SeqExpr { exprs: seq, .. }),
+            ..
+        }) => exprs.extend(seq),
+        expr => exprs.push(box expr),
+    }
+}
+
+fn flush<T: StmtLike>(buf: &mut Vec<T>, exprs: &mut Vec<Box<Expr>>) {
+    if !exprs.is_empty() {
+        buf.push(T::from_stmt(expr_stmt(seq(exprs, None))));
+    }
+}
@@ -0,0 +1,88 @@
+use ast::*;
+use swc_common::{Fold, FoldWith, Spanned};
+use swc_ecma_transforms::util::extract_side_effects_to;
+
+/// Drops unused values, keeping their side effects.
+pub(super) struct SideEffects;
+
+impl Fold<Stmt> for SideEffects {
+    fn fold(&mut self, stmt: Stmt) -> Stmt {
+        let stmt = stmt.fold_children(self);
+
+        match stmt {
+            // Directives like `'use strict'`.
+            Stmt::Expr(ExprStmt {
+                expr: box Expr::Lit(Lit::Str(..)),
+                ..
+            }) => stmt,
+
+            // `a(), 1, b;` -> `a();`
+            Stmt::Expr(ExprStmt { span, expr }) => {
+                let expr_span = expr.span();
+                let mut exprs = vec![];
+                extract_side_effects_to(&mut exprs, expr);
+
+                match exprs.len() {
+                    0 => Stmt::Empty(EmptyStmt { span }),
+                    1 => Stmt::Expr(ExprStmt {
+                        span,
+                        expr: exprs.pop().unwrap(),
+                    }),
+                    _ => Stmt::Expr(ExprStmt {
+                        span,
+                        expr: box Expr::Seq(SeqExpr {
+                            span: expr_span,
+                            exprs,
+                        }),
+                    }),
+                }
+            }
+
+            _ => stmt,
+        }
+    }
+}
+
+/// `(a(), 1, b)` -> `(a(), b)`
+impl Fold<SeqExpr> for SideEffects {
+    fn fold(&mut self, e: SeqExpr) -> SeqExpr {
+        let SeqExpr { span, mut exprs } = e.fold_children(self);
+        let last = exprs.pop().expect("SeqExpr.exprs must not be empty");
+
+        let mut buf = Vec::with_capacity(exprs.len() + 1);
+        for expr in exprs {
+            extract_side_effects_to(&mut buf, expr);
+        }
+        buf.push(last);
+
+        SeqExpr { span, exprs: buf }
+    }
+}
+
+/// Keeps sequences in callees, as `(0, a.b)()` calls `a.b` without `this`.
+impl Fold<CallExpr> for SideEffects {
+    fn fold(&mut self, e: CallExpr) -> CallExpr {
+        let CallExpr { span, callee, args } = e;
+
+        let callee = match callee {
+            ExprOrSuper::Expr(box Expr::Paren(ParenExpr {
+                expr: box Expr::Seq(seq),
+                ..
+            }))
+            | ExprOrSuper::Expr(box Expr::Seq(seq)) => {
+                let exprs = seq.exprs.into_iter().map(|e| e.fold_with(self)).collect();
+                ExprOrSuper::Expr(box Expr::Seq(SeqExpr {
+                    span: seq.span,
+                    exprs,
+                }))
+            }
+            _ => callee.fold_with(self),
+        };
+
+        CallExpr {
+            span,
+            callee,
+            args: args.fold_with(self),
+        }
+    }
+}
@@ -0,0 +1,396 @@
+use super::{compressor, compressor_with_stats};
+use crate::{
+    option::{CompressOptions, KeepNames},
+    stats::Stats,
+};
+use std::sync::{Arc, Mutex};
+
+macro_rules! test_compress {
+    ($test_name:ident, $input:expr, $expected:expr) => {
+        test_compress!($test_name, $input, $expected, Default::default());
+    };
+    ($test_name:ident, $input:expr, $expected:expr, $options:expr) => {
+        test!(compressor($options), $test_name, $input, $expected);
+    };
+}
+
+test_compress!(
+    if_impure_test,
+    "if (a(), true) b(); else c();",
+    "a(), b();"
+);
+
+test_compress!(if_empty, "if (a()) {} else {}", "a();");
+
+test_compress!(
+    unreachable,
+    "function f() {
+        return g();
+        var a = 1;
+        function g() {}
+        h();
+    }",
+    "function f() {
+        return g();
+        var a;
+        function g() {}
+    }",
+    CompressOptions {
+        inline: false,
+        unused: false,
+        ..Default::default()
+    }
+);
+
+test_compress!(
+    unreachable_after_if,
+    "function f(a) {
+        if (a) return 1; else throw 2;
+        b();
+    }",
+    "function f(a) {
+        if (a) return 1; else throw 2;
+    }"
+);
+
+test_compress!(
+    loop_never_runs,
+    "while (false) { var a = b(); } for (c(); false;) d();",
+    "var a; c();"
+);
+
+test_compress!(unused_values, "a(), 1, b; !c(); 'use strict';", "a(), c(); 'use strict';");
+
+test_compress!(seq_callee, "(0, a.b)();", "(0, a.b)();");
+
+test_compress!(
+    blocks,
+    "{ a(); { b(); } } { let c = d(); }",
+    "a(), b(); { let c = d(); }"
+);
+
+/// Each pass enables the others.
+test_compress!(
+    fixpoint,
+    "function f() { if (1) { return; } else { g(); } while (false); }",
+    "function f() { return; }"
+);
+
+test_compress!(sequences, "a(); b = 1; c();", "a(), b = 1, c();");
+
+test_compress!(
+    sequences_into_stmt,
+    "function f() { a(); return b; } if (c(), d) e(); else throw g();",
+    "function f() { return a(), b; } if (c(), d) e(); else throw g();"
+);
+
+test_compress!(
+    sequences_max,
+    "a(); b(); c();",
+    "a(), b(); c();",
+    CompressOptions {
+        sequences: 2,
+        ..Default::default()
+    }
+);
+
+test_compress!(directive, "'use strict'; a(); b();", "'use strict'; a(), b();");
+
+test_compress!(
+    join_vars,
+    "var a = 1; var b = 2; let c; let d; const e = 3; var f; for (var i = 0;;) ;",
+    "var a = 1, b = 2; let c, d; const e = 3; for (var f, i = 0;;) ;"
+);
+
+test_compress!(if_to_cond, "if (a) b(); else c();", "a ? b() : c();");
+
+test_compress!(if_to_and, "if (a) b();", "a && b();");
+
+test_compress!(if_to_or, "if (!a) b();", "a || b();");
+
+test_compress!(if_assign, "if (a) b = 1;", "a && (b = 1);");
+
+test_compress!(if_empty_cons, "if (a) {} else { b(); }", "a || b();");
+
+test_compress!(if_negated, "if (!a) b(); else c();", "a ? c() : b();");
+
+test_compress!(if_negated_eq, "if (a === 1) {} else b();", "a !== 1 && b();");
+
+/// `!(a < 1)` is not `a >= 1` if `a` is `NaN`.
+test_compress!(if_negated_lt, "if (a < 1) {} else b();", "a < 1 || b();");
+
+test_compress!(if_nested, "if (a) { if (b) { c(); } }", "a && b && c();");
+
+test_compress!(
+    if_nested_else,
+    "if (a) { if (b) c(); } else d();",
+    "a ? b && c() : d();"
+);
+
+test_compress!(
+    dangling_else,
+    "if (a) { if (b) for (;;) c(); } else d();",
+    "if (a) { if (b) for (;;) c(); } else d();"
+);
+
+test_compress!(
+    if_seq,
+    "if (a) { b(); c(); } else d();",
+    "a ? (b(), c()) : d();"
+);
+
+test_compress!(
+    if_return,
+    "function f() { if (a) return b; else return c; }
+    function g() { if (a) return b; return c; }",
+    "function f() { return a ? b : c; }
+    function g() { return a ? b : c; }"
+);
+
+test_compress!(if_throw, "if (a) throw b; else throw c;", "throw a ? b : c;");
+
+test_compress!(cond_negated, "x = !a ? b : c;", "x = a ? c : b;");
+
+test_compress!(
+    if_nested_loop,
+    "if (a) if (b) for (;;) c();",
+    "if (a && b) for (;;) c();"
+);
+
+test_compress!(
+    inline_iife,
+    "x = (function (a, b) { return a + b; })(1, c);",
+    "x = 1 + c;"
+);
+
+test_compress!(inline_iife_stmt, "(function () { a(); })();", "a();");
+
+test_compress!(inline_arrow, "x = ((a) => a * 2)(y);", "x = y * 2;");
+
+test_compress!(
+    inline_single_use,
+    "function f(a) { return a + 1; } x = f(2);",
+    "x = 3;"
+);
+
+test_compress!(
+    inline_used_twice,
+    "function f() { return a; } x = f(); y = f();",
+    "function f() { return a; } x = f(), y = f();"
+);
+
+test_compress!(
+    inline_escape,
+    "function f() { return a; } x = f(); y = f.name;",
+    "function f() { return a; } x = f(), y = f.name;"
+);
+
+test_compress!(
+    inline_this,
+    "x = (function () { return this.a; })(); y = (function () { return this; })();",
+    "x = function () { return this.a; }(), y = function () { return this; }();"
+);
+
+/// `a.b` and `c.d` are called without `this`.
+test_compress!(
+    inline_callee,
+    "(function () { return a.b; })()(); function f() { return c.d; } f()();",
+    "(0, a.b)(), (0, c.d)();"
+);
+
+test_compress!(
+    inline_arguments,
+    "function f() { return arguments; } x = f();",
+    "function f() { return arguments; } x = f();"
+);
+
+/// `a` in `g` is the parameter.
+test_compress!(
+    inline_shadowed,
+    "function f() { return a; } function g(a) { return f(); }",
+    "function f() { return a; } function g(a) { return f(); }",
+    CompressOptions {
+        unused: false,
+        ..Default::default()
+    }
+);
+
+test_compress!(
+    iife_stmt,
+    "(function () { return this; })();",
+    "(function () { return this; })();"
+);
+
+test_compress!(
+    evaluate_builtins,
+    "a = String.fromCharCode(72, 105); b = Math.max(1, 2, -3); c = Math.min(1, -2);
+    d = Math.floor(-1.5); e = Math.pow(2, 10);",
+    "a = 'Hi', b = 2, c = -2, d = -2, e = 1024;"
+);
+
+test_compress!(
+    evaluate_string_methods,
+    "a = 'abc'.charCodeAt(0); b = 'abc'.charAt(1); c = 'abc'.indexOf('c');
+    d = 'abca'.lastIndexOf('d'); e = 'abc'.toUpperCase();",
+    "a = 97, b = 'b', c = 2, d = -1, e = 'ABC';"
+);
+
+/// Indices are in UTF-16 code units.
+test_compress!(
+    evaluate_utf16,
+    "a = '\u{1F600}a'.charCodeAt(2); b = '\u{1F600}'.charAt(0);",
+    "a = 97, b = '\u{1F600}'.charAt(0);"
+);
+
+test_compress!(
+    evaluate_unknown,
+    "a = 'abc'.charCodeAt(5); b = Math.max(); c = Math.sqrt(2); d = Math.max(a, 1);",
+    "a = 'abc'.charCodeAt(5), b = Math.max(), c = Math.sqrt(2), d = Math.max(a, 1);"
+);
+
+test_compress!(
+    evaluate_shadowed,
+    "function f(Math) { return Math.max(1, 2); }",
+    "function f(Math) { return Math.max(1, 2); }"
+);
+
+test_compress!(
+    unused_vars,
+    "function f() { var a = 1, b = g(), c = 2; let d; return c; }",
+    "function f() { g(); var c = 2; return c; }"
+);
+
+test_compress!(
+    unused_fns,
+    "function f() { function g() {} function h() {} class A {} class B extends C {} return h; }",
+    "function f() { function h() {} class B extends C {} return h; }"
+);
+
+test_compress!(
+    unused_params,
+    "function f(a, b, c) { return a; } x = (a, b) => b;",
+    "function f(a) { return a; } x = (a, b) => b;",
+    CompressOptions {
+        keep_fargs: false,
+        ..Default::default()
+    }
+);
+
+test_compress!(
+    keep_fargs,
+    "function f(a, b, c) { return a; } x = (a, b) => a;",
+    "function f(a, b, c) { return a; } x = (a, b) => a;"
+);
+
+test_compress!(
+    unused_shadowed,
+    "function f(a) { return function (b) { var a = b; return a; }; }",
+    "function f(a) { return function (b) { var a = b; return a; }; }"
+);
+
+test_compress!(
+    unused_destructuring,
+    "function f([a, b, c], d) { var { e, g } = { g: 1 }, { h, i } = d; return [c, g]; }",
+    "function f([, , c], d) { var { g } = { g: 1 }, { h, i } = d; return [c, g]; }"
+);
+
+test_compress!(
+    unused_eval,
+    "function f(a) { var b = 1; eval(a); }",
+    "function f(a) { var b = 1; eval(a); }"
+);
+
+test_compress!(
+    unused_top_level,
+    "var a = 1, b = c(); function d() {} export var e = 1; export function g() {}",
+    "c(); export var e = 1; export function g() {}",
+    CompressOptions {
+        top_level: true,
+        ..Default::default()
+    }
+);
+
+test_compress!(
+    unused_top_level_disabled,
+    "var a = 1; function d() {}",
+    "var a = 1; function d() {}"
+);
+
+test_compress!(
+    unused_fn_names,
+    "x = function f() {}; y = function g() { return g; }; z = class A {};",
+    "x = function () {}, y = function g() { return g; }, z = class {};"
+);
+
+test_compress!(
+    keep_fnames,
+    "x = function f() {}; z = class A {};",
+    "x = function f() {}, z = class {};",
+    CompressOptions {
+        keep_fnames: KeepNames::All,
+        ..Default::default()
+    }
+);
+
+test_compress!(
+    pure_funcs,
+    "console.log(a()); x = console.error('b'); foo.bar(c(), 1); y = foo.bar(1); baz();",
+    "x = void 0, c(), y = foo.bar(1);",
+    CompressOptions {
+        drop_console: true,
+        pure_funcs: vec!["foo.bar".into(), "baz".into()],
+        ..Default::default()
+    }
+);
+
+test_compress!(
+    drop_console_shadowed,
+    "function f(console) { console.log(1); }",
+    "function f(console) { console.log(1); }",
+    CompressOptions {
+        drop_console: true,
+        ..Default::default()
+    }
+);
+
+test_compress!(
+    properties,
+    "x = a['b']; y = a['b c']; z = a['if']; w = { 'a': 1, 'b c': 2, '1': 3 };",
+    "x = a.b, y = a['b c'], z = a.if, w = { a: 1, 'b c': 2, '1': 3 };"
+);
+
+test_compress!(
+    shorthand,
+    "x = { 'a': a, b: b, c: d, __proto__: __proto__ };",
+    "x = { a, b, c: d, __proto__: __proto__ };",
+    CompressOptions {
+        ecma: 2015,
+        ..Default::default()
+    }
+);
+
+test_compress!(shorthand_es5, "x = { 'a': a };", "x = { a: a };");
+
+test_compress!(shorthand_pat, "({ a: a, b: c } = d);", "({ a, b: c } = d);");
+
+#[test]
+fn stats() {
+    let stats = Arc::new(Mutex::new(Stats::default()));
+
+    test_transform!(
+        compressor_with_stats(Default::default(), stats.clone()),
+        "function f() { return 1; a(); }",
+        "function f() { return 1; }"
+    );
+
+    // The second pass changes nothing.
+    let stats = stats.lock().unwrap();
+    assert_eq!(stats.passes, 2);
+    assert_eq!(stats.nodes_before - stats.nodes_after, 3);
+
+    let simplifier = &stats.rules[0];
+    assert_eq!(simplifier.rule, "simplifier");
+    assert_eq!(simplifier.changes, 1);
+    assert_eq!(simplifier.nodes_removed, 3);
+    assert!(stats.rules[1..].iter().all(|r| r.changes == 0));
+}
@@ -0,0 +1,549 @@
+use super::util::expr_stmt;
+use ast::*;
+use crate::option::KeepNames;
+use std::collections::{HashMap, HashSet};
+use swc_atoms::JsWord;
+use swc_common::{Fold, FoldWith, Span, Visit, VisitWith};
+use swc_ecma_transforms::util::{pat_ids, ExprExt, StmtLike};
+
+/// Drops bindings which are never referenced, keeping side effects of their
+/// initializers.
+///
+/// Bindings are tracked by name. A binding is unused if its name appears only
+/// in its declarations, so shadowing and property names only make this more
+/// conservative.
+pub(super) struct Unused {
+    /// Drop bindings of the module.
+    top_level: bool,
+    /// Keep unused trailing parameters.
+    keep_fargs: bool,
+    keep_fnames: KeepNames,
+    keep_classnames: KeepNames,
+    /// Unused bindings of the function being folded. As they appear only in
+    /// their declarations, names of outer functions are not shadowed.
+    unused: HashSet<JsWord>,
+}
+
+impl Unused {
+    pub fn new(
+        top_level: bool,
+        keep_fargs: bool,
+        keep_fnames: KeepNames,
+        keep_classnames: KeepNames,
+    ) -> Self {
+        Unused {
+            top_level,
+            keep_fargs,
+            keep_fnames,
+            keep_classnames,
+            unused: Default::default(),
+        }
+    }
+
+    /// Folds `node` with unused bindings declared by it.
+    fn fold_scope<N, F>(&mut self, node: N, op: F) -> N
+    where
+        N: VisitWith<Decls> + VisitWith<Occurrences>,
+        F: FnOnce(&mut Self, N) -> N,
+    {
+        let outer = self.unused.clone();
+
+        let mut occurrences = Occurrences::default();
+        node.visit_with(&mut occurrences);
+        if !occurrences.has_eval {
+            let mut decls = Decls::default();
+            node.visit_with(&mut decls);
+
+            for (sym, count) in decls.counts {
+                if occurrences.counts.get(&sym) == Some(&count) {
+                    self.unused.insert(sym);
+                }
+            }
+        }
+
+        let node = op(self, node);
+        self.unused = outer;
+        node
+    }
+
+    fn is_unused(&self, pat: &Pat) -> bool {
+        match *pat {
+            Pat::Ident(ref i) => self.unused.contains(&i.sym),
+            _ => false,
+        }
+    }
+
+    /// Drops unused trailing parameters unless `keep_fargs` is set.
+    fn fold_params(&self, params: Vec<Pat>) -> Vec<Pat> {
+        let mut params: Vec<_> = params.into_iter().map(|p| self.trim_array_pat(p)).collect();
+        while !self.keep_fargs && params.last().map_or(false, |p| self.is_unused(p)) {
+            params.pop();
+        }
+        params
+    }
+
+    /// Drops unused trailing parameters of a function, except decorated ones,
+    /// unless `keep_fargs` is set.
+    fn fold_fn_params(&self, params: Vec<Param>) -> Vec<Param> {
+        let mut params: Vec<_> = params
+            .into_iter()
+            .map(|param| Param {
+                pat: self.trim_array_pat(param.pat),
+                ..param
+            })
+            .collect();
+        while !self.keep_fargs
+            && params.last().map_or(false, |param| {
+                param.decorators.is_empty() && self.is_unused(&param.pat)
+            })
+        {
+            params.pop();
+        }
+        params
+    }
+
+    /// `[a, b] = c` -> `[, b] = c` if `a` is unused.
+    ///
+    /// Elements are replaced with holes, so the iterator is advanced the same
+    /// number of times.
+    fn trim_array_pat(&self, pat: Pat) -> Pat {
+        match pat {
+            Pat::Array(ArrayPat { span, elems }) => {
+                let len = elems.len();
+                Pat::Array(ArrayPat {
+                    span,
+                    elems: elems
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, elem)| match elem {
+                            Some(ref pat) if i + 1 != len && self.is_unused(pat) => None,
+                            elem => elem,
+                        })
+                        .collect(),
+                })
+            }
+            _ => pat,
+        }
+    }
+
+    /// Drops unused properties of an object pattern, which is destructuring
+    /// an object without getters.
+    ///
+    /// Properties are kept if there's a rest element as it contains the
+    /// others.
+    fn trim_obj_pat(&self, pat: Pat) -> Pat {
+        match pat {
+            Pat::Object(ObjectPat { span, props }) => {
+                let has_rest = props.iter().any(|p| match *p {
+                    ObjectPatProp::Rest(..) => true,
+                    _ => false,
+                });
+                if has_rest {
+                    return Pat::Object(ObjectPat { span, props });
+                }
+
+                let props = props
+                    .into_iter()
+                    .filter(|p| match *p {
+                        ObjectPatProp::Assign(AssignPatProp {
+                            ref key, ref value, ..
+                        }) => {
+                            !self.unused.contains(&key.sym)
+                                || value.as_ref().map_or(false, |v| v.may_have_side_effects())
+                        }
+                        ObjectPatProp::KeyValue(KeyValuePatProp {
+                            key: PropName::Computed(..),
+                            ..
+                        }) => true,
+                        ObjectPatProp::KeyValue(KeyValuePatProp { ref value, .. }) => {
+                            !self.is_unused(value)
+                        }
+                        ObjectPatProp::Rest(..) => true,
+                    })
+                    .collect();
+                Pat::Object(ObjectPat { span, props })
+            }
+            _ => pat,
+        }
+    }
+}
+
+impl Fold<Module> for Unused {
+    fn fold(&mut self, module: Module) -> Module {
+        if self.top_level {
+            self.fold_scope(module, |u, module| module.fold_children(u))
+        } else {
+            module.fold_children(self)
+        }
+    }
+}
+
+impl Fold<Function> for Unused {
+    fn fold(&mut self, f: Function) -> Function {
+        self.fold_scope(f, |u, f| {
+            let f = f.fold_children(u);
+            Function {
+                params: u.fold_fn_params(f.params),
+                ..f
+            }
+        })
+    }
+}
+
+impl Fold<ArrowExpr> for Unused {
+    fn fold(&mut self, f: ArrowExpr) -> ArrowExpr {
+        self.fold_scope(f, |u, f| {
+            let f = f.fold_children(u);
+            ArrowExpr {
+                params: u.fold_params(f.params),
+                ..f
+            }
+        })
+    }
+}
+
+/// The parameter of a setter is required.
+impl Fold<SetterProp> for Unused {
+    fn fold(&mut self, p: SetterProp) -> SetterProp {
+        let key = p.key.fold_with(self);
+        let param = p.param.fold_with(self);
+        let body = self.fold_scope(p.body, |u, body| body.fold_children(u));
+
+        SetterProp {
+            key,
+            param,
+            body,
+            ..p
+        }
+    }
+}
+
+impl Fold<GetterProp> for Unused {
+    fn fold(&mut self, p: GetterProp) -> GetterProp {
+        let key = p.key.fold_with(self);
+        let body = self.fold_scope(p.body, |u, body| body.fold_children(u));
+
+        GetterProp { key, body, ..p }
+    }
+}
+
+/// `(function a() {})` -> `(function () {})`
+impl Fold<FnExpr> for Unused {
+    fn fold(&mut self, f: FnExpr) -> FnExpr {
+        let FnExpr { ident, function } = f.fold_children(self);
+
+        let ident = match ident {
+            Some(ident) => {
+                if self.keep_fnames.keeps(&ident.sym) || is_referenced(&function, &ident.sym) {
+                    Some(ident)
+                } else {
+                    None
+                }
+            }
+            None => None,
+        };
+        FnExpr { ident, function }
+    }
+}
+
+impl Fold<ClassExpr> for Unused {
+    fn fold(&mut self, c: ClassExpr) -> ClassExpr {
+        let ClassExpr { ident, class } = c.fold_children(self);
+
+        let ident = match ident {
+            Some(ident) => {
+                if self.keep_classnames.keeps(&ident.sym) || is_referenced(&class, &ident.sym) {
+                    Some(ident)
+                } else {
+                    None
+                }
+            }
+            None => None,
+        };
+        ClassExpr { ident, class }
+    }
+}
+
+/// Names of default exports are bindings of the module.
+impl Fold<DefaultDecl> for Unused {
+    fn fold(&mut self, d: DefaultDecl) -> DefaultDecl {
+        match d {
+            DefaultDecl::Fn(FnExpr { ident, function }) => {
+                DefaultDecl::Fn(FnExpr {
+                    ident,
+                    function: function.fold_with(self),
+                })
+            }
+            DefaultDecl::Class(ClassExpr { ident, class }) => {
+                DefaultDecl::Class(ClassExpr {
+                    ident,
+                    class: class.fold_with(self),
+                })
+            }
+            DefaultDecl::Var(v) => DefaultDecl::Var(v.fold_with(self)),
+        }
+    }
+}
+
+impl<T: StmtLike> Fold<Vec<T>> for Unused
+where
+    Self: Fold<T>,
+{
+    fn fold(&mut self, stmts: Vec<T>) -> Vec<T> {
+        let mut buf = Vec::with_capacity(stmts.len());
+
+        for stmt_like in stmts {
+            let stmt_like = self.fold(stmt_like);
+            let stmt = match stmt_like.try_into_stmt() {
+                Ok(stmt) => stmt,
+                Err(stmt_like) => {
+                    buf.push(stmt_like);
+                    continue;
+                }
+            };
+
+            match stmt {
+                Stmt::Decl(Decl::Fn(FnDecl { ref ident, .. }))
+                    if self.unused.contains(&ident.sym) => {}
+                Stmt::Decl(Decl::Class(ClassDecl {
+                    ref ident,
+                    ref class,
+                    ..
+                })) if self.unused.contains(&ident.sym) && !class_has_side_effects(class) => {}
+
+                // `var a = f(), b = 1;` -> `f(); var b = 1;`
+                Stmt::Decl(Decl::Var(VarDecl {
+                    span,
+                    kind,
+                    declare,
+                    decls,
+                })) => {
+                    let mut kept = vec![];
+                    for decl in decls {
+                        if !self.is_unused(&decl.name) {
+                            kept.push(decl);
+                            continue;
+                        }
+
+                        if let Some(init) = decl.init {
+                            if init.may_have_side_effects() {
+                                flush(&mut buf, span, kind, declare, &mut kept);
+                                buf.push(T::from_stmt(expr_stmt(init)));
+                            }
+                        }
+                    }
+                    flush(&mut buf, span, kind, declare, &mut kept);
+                }
+
+                stmt => buf.push(T::from_stmt(stmt)),
+            }
+        }
+
+        buf
+    }
+}
+
+impl Fold<VarDeclarator> for Unused {
+    fn fold(&mut self, decl: VarDeclarator) -> VarDeclarator {
+        let decl = decl.fold_children(self);
+
+        // `var { a, b } = { a: 1, b: 2 }` -> `var { b } = { a: 1, b: 2 }`
+        let is_plain_obj = match decl.init {
+            Some(box Expr::Object(ref obj)) => is_plain(obj),
+            _ => false,
+        };
+        let name = if is_plain_obj {
+            self.trim_obj_pat(decl.name)
+        } else {
+            self.trim_array_pat(decl.name)
+        };
+
+        VarDeclarator { name, ..decl }
+    }
+}
+
+fn flush<T: StmtLike>(
+    buf: &mut Vec<T>,
+    span: Span,
+    kind: VarDeclKind,
+    declare: bool,
+    decls: &mut Vec<VarDeclarator>,
+) {
+    if !decls.is_empty() {
+        buf.push(T::from_stmt(Stmt::Decl(Decl::Var(VarDecl {
+            span,
+            kind,
+            declare,
+            decls: decls.drain(..).collect(),
+        }))));
+    }
+}
+
+/// Returns true if defining `class` may have side effects.
+fn class_has_side_effects(class: &Class) -> bool {
+    class.super_class.is_some()
+        || class.body.iter().any(|m| match *m {
+            ClassMember::Method(ClassMethod {
+                key: PropName::Computed(..),
+                ..
+            })
+            | ClassMember::ClassProp(ClassProp {
+                key: PropName::Computed(..),
+                ..
+            }) => true,
+            // Static initializers run when the class is defined.
+            ClassMember::ClassProp(ClassProp {
+                static_token: Some(..),
+                value: Some(..),
+                ..
+            })
+            | ClassMember::PrivateProp(PrivateProp {
+                static_token: Some(..),
+                value: Some(..),
+                ..
+            })
+            | ClassMember::StaticBlock(..) => true,
+            _ => false,
+        })
+}
+
+/// Returns true if reading properties of `obj` doesn't call getters.
+fn is_plain(obj: &ObjectLit) -> bool {
+    obj.props.iter().all(|p| match *p {
+        PropOrSpread::Prop(box Prop::KeyValue(..))
+        | PropOrSpread::Prop(box Prop::Shorthand(..)) => true,
+        _ => false,
+    })
+}
+
+fn is_referenced<N>(node: &N, sym: &JsWord) -> bool
+where
+    N: VisitWith<Occurrences>,
+{
+    let mut occurrences = Occurrences::default();
+    node.visit_with(&mut occurrences);
+    occurrences.has_eval || occurrences.counts.contains_key(sym)
+}
+
+/// Counts all identifiers, including property names and labels.
+#[derive(Default)]
+pub(super) struct Occurrences {
+    counts: HashMap<JsWord, usize>,
+    /// `eval` and `with` may reference any binding.
+    has_eval: bool,
+}
+
+impl Visit<Ident> for Occurrences {
+    fn visit(&mut self, i: &Ident) {
+        if &*i.sym == "eval" {
+            self.has_eval = true;
+        }
+        *self.counts.entry(i.sym.clone()).or_insert(0) += 1;
+    }
+}
+
+impl Visit<WithStmt> for Occurrences {
+    fn visit(&mut self, s: &WithStmt) {
+        self.has_eval = true;
+        s.visit_children(self)
+    }
+}
+
+/// Counts declarations of a scope, excluding ones in nested functions.
+#[derive(Default)]
+pub(super) struct Decls {
+    counts: HashMap<JsWord, usize>,
+}
+
+impl Decls {
+    fn add(&mut self, i: &Ident) {
+        *self.counts.entry(i.sym.clone()).or_insert(0) += 1;
+    }
+}
+
+impl Visit<Function> for Decls {
+    fn visit(&mut self, f: &Function) {
+        f.params.visit_with(self);
+        if let Some(ref body) = f.body {
+            body.visit_children(&mut InBody(self));
+        }
+    }
+}
+
+impl Visit<ArrowExpr> for Decls {
+    fn visit(&mut self, f: &ArrowExpr) {
+        f.params.visit_with(self);
+        f.body.visit_with(&mut InBody(self));
+    }
+}
+
+/// Bodies of getters and setters.
+impl Visit<BlockStmt> for Decls {
+    fn visit(&mut self, block: &BlockStmt) {
+        block.visit_children(&mut InBody(self));
+    }
+}
+
+/// Imports and exports are kept.
+impl Visit<Module> for Decls {
+    fn visit(&mut self, module: &Module) {
+        for item in &module.body {
+            if let ModuleItem::Stmt(ref stmt) = *item {
+                stmt.visit_with(&mut InBody(self));
+            }
+        }
+    }
+}
+
+impl Visit<Pat> for Decls {
+    fn visit(&mut self, pat: &Pat) {
+        let mut ids = vec![];
+        pat_ids(pat, &mut ids);
+        for i in &ids {
+            self.add(i);
+        }
+    }
+}
+
+/// Visits a body of a scope.
+struct InBody<'a>(&'a mut Decls);
+
+impl<'a> Visit<VarDeclarator> for InBody<'a> {
+    fn visit(&mut self, decl: &VarDeclarator) {
+        decl.name.visit_with(self.0);
+        decl.init.visit_with(self);
+    }
+}
+
+impl<'a> Visit<FnDecl> for InBody<'a> {
+    fn visit(&mut self, f: &FnDecl) {
+        self.0.add(&f.ident);
+    }
+}
+
+impl<'a> Visit<ClassDecl> for InBody<'a> {
+    fn visit(&mut self, c: &ClassDecl) {
+        self.0.add(&c.ident);
+    }
+}
+
+/// Nested functions have their own declarations.
+impl<'a> Visit<Function> for InBody<'a> {
+    fn visit(&mut self, _: &Function) {}
+}
+
+impl<'a> Visit<ArrowExpr> for InBody<'a> {
+    fn visit(&mut self, _: &ArrowExpr) {}
+}
+
+impl<'a> Visit<Class> for InBody<'a> {
+    fn visit(&mut self, _: &Class) {}
+}
+
+impl<'a> Visit<GetterProp> for InBody<'a> {
+    fn visit(&mut self, _: &GetterProp) {}
+}
+
+impl<'a> Visit<SetterProp> for InBody<'a> {
+    fn visit(&mut self, _: &SetterProp) {}
+}
@@ -0,0 +1,85 @@
+use ast::*;
+use std::collections::HashMap;
+use swc_atoms::JsWord;
+use swc_common::{Span, Spanned, Visit, VisitWith};
+
+/// Counts identifiers bound in a subtree.
+#[derive(Default)]
+pub(super) struct Bindings {
+    counts: HashMap<JsWord, usize>,
+}
+
+impl Bindings {
+    pub fn add(&mut self, i: &Ident) {
+        *self.counts.entry(i.sym.clone()).or_insert(0) += 1;
+    }
+
+    pub fn count(&self, sym: &JsWord) -> usize {
+        self.counts.get(sym).cloned().unwrap_or(0)
+    }
+}
+
+/// Assignment targets are also counted, which only makes users of this more
+/// conservative.
+impl Visit<Pat> for Bindings {
+    fn visit(&mut self, pat: &Pat) {
+        if let Pat::Ident(ref i) = *pat {
+            self.add(i);
+        }
+        pat.visit_children(self)
+    }
+}
+
+impl Visit<FnDecl> for Bindings {
+    fn visit(&mut self, f: &FnDecl) {
+        self.add(&f.ident);
+        f.visit_children(self)
+    }
+}
+
+impl Visit<FnExpr> for Bindings {
+    fn visit(&mut self, f: &FnExpr) {
+        if let Some(ref i) = f.ident {
+            self.add(i);
+        }
+        f.visit_children(self)
+    }
+}
+
+impl Visit<ClassDecl> for Bindings {
+    fn visit(&mut self, c: &ClassDecl) {
+        self.add(&c.ident);
+        c.visit_children(self)
+    }
+}
+
+impl Visit<ClassExpr> for Bindings {
+    fn visit(&mut self, c: &ClassExpr) {
+        if let Some(ref i) = c.ident {
+            self.add(i);
+        }
+        c.visit_children(self)
+    }
+}
+
+/// `expr;`
+pub(super) fn expr_stmt(expr: Box<Expr>) -> Stmt {
+    Stmt::Expr(ExprStmt {
+        span: expr.span(),
+        expr,
+    })
+}
+
+/// `void 0`
+pub(super) fn undefined(span: Span) -> Box<Expr> {
+    let span = mark!(span);
+    box Expr::Unary(UnaryExpr {
+        span,
+        op: op!("void"),
+        arg: box Expr::Lit(Lit::Num(Number {
+            span,
+            value: 0.0,
+            raw: None,
+        })),
+    })
+}
@@ -0,0 +1,119 @@
+//! Minifier for ecmascript.
+//!
+//! The compressor is built on top of the simplifier of
+//! `swc_ecma_transforms`.
+#![feature(box_patterns)]
+#![feature(box_syntax)]
+#![feature(nll)]
+#![feature(specialization)]
+
+extern crate regex;
+extern crate serde;
+#[cfg(test)]
+extern crate serde_json;
+#[macro_use(js_word)]
+extern crate swc_atoms;
+extern crate swc_common;
+extern crate swc_ecma_ast as ast;
+#[macro_use(mark)]
+extern crate swc_ecma_transforms;
+#[cfg(test)]
+extern crate testing;
+
+pub use self::{
+    compress::{compressor, compressor_with_stats},
+    mangle::{mangler, mangler_with_cache},
+    name_cache::NameCache,
+    option::{CompressOptions, KeepNames, MangleOptions, MinifyOptions, PropertiesOptions},
+    stats::{RuleStats, Stats},
+};
+use ast::Module;
+use std::sync::{Arc, Mutex};
+use swc_common::Fold;
+
+#[cfg(test)]
+#[macro_use]
+mod tests;
+mod compress;
+mod mangle;
+mod name_cache;
+pub mod option;
+mod stats;
+
+/// Creates a pass which minifies a module according to `options`.
+pub fn minifier(options: MinifyOptions) -> Minifier {
+    Minifier {
+        options,
+        cache: None,
+        stats: None,
+    }
+}
+
+/// Created by [minifier].
+#[derive(Debug)]
+pub struct Minifier {
+    options: MinifyOptions,
+    cache: Option<Arc<Mutex<NameCache>>>,
+    stats: Option<Arc<Mutex<Stats>>>,
+}
+
+impl Minifier {
+    /// Shares names chosen by the mangler with other modules using `cache`.
+    pub fn with_name_cache(self, cache: Arc<Mutex<NameCache>>) -> Self {
+        Minifier {
+            cache: Some(cache),
+            ..self
+        }
+    }
+
+    /// Records what the compressor does into `stats`.
+    pub fn with_stats(self, stats: Arc<Mutex<Stats>>) -> Self {
+        Minifier {
+            stats: Some(stats),
+            ..self
+        }
+    }
+}
+
+impl Fold<Module> for Minifier {
+    fn fold(&mut self, module: Module) -> Module {
+        let keep_quoted = self
+            .options
+            .mangle
+            .as_ref()
+            .and_then(|m| m.properties.as_ref())
+            .map_or(false, |p| p.keep_quoted);
+
+        let module = match self.options.compress.clone() {
+            Some(mut options) => {
+                // Quotes are used by the mangler to keep properties.
+                if keep_quoted {
+                    options.properties = false;
+                }
+                match self.stats.as_ref() {
+                    Some(stats) => compressor_with_stats(options, stats.clone()).fold(module),
+                    None => compressor(options).fold(module),
+                }
+            }
+            None => module,
+        };
+
+        match (self.options.mangle.as_ref(), self.cache.as_ref()) {
+            (Some(options), Some(cache)) => {
+                mangler_with_cache(options.clone(), cache.clone()).fold(module)
+            }
+            (Some(options), None) => mangler(options.clone()).fold(module),
+            (None, _) => module,
+        }
+    }
+}
+
+/// Returns true if `s` can be written as `a.s`.
+fn is_ident(s: &str) -> bool {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '$' || c == '_' => {}
+        _ => return false,
+    }
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '$' || c == '_')
+}
@@ -0,0 +1,450 @@
+use ast::*;
+use std::{
+    collections::{HashMap, HashSet},
+    mem,
+};
+use swc_atoms::JsWord;
+use swc_common::{Visit, VisitWith};
+use swc_ecma_transforms::util::pat_ids;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum ScopeKind {
+    /// Functions and the module, which contain `var` declarations.
+    Fn,
+    Block,
+}
+
+#[derive(Debug)]
+pub(super) struct Scope {
+    pub parent: Option<usize>,
+    pub kind: ScopeKind,
+    /// Names declared in this scope, in order of declaration.
+    pub decls: Vec<JsWord>,
+    /// Number of references to each declaration, including the declaration
+    /// itself.
+    pub refs: HashMap<JsWord, usize>,
+    /// Bindings of outer scopes referenced by this scope or its children, with
+    /// the scopes declaring them. Globals are `None`.
+    pub uses: HashSet<(Option<usize>, JsWord)>,
+    /// Declarations naming functions, including variables initialized with
+    /// functions as names are inferred from them.
+    pub fn_names: HashSet<JsWord>,
+    /// Declarations naming classes.
+    pub class_names: HashSet<JsWord>,
+    /// `eval` or `with` in this scope or its children can access any binding
+    /// by name.
+    pub has_eval: bool,
+}
+
+impl Scope {
+    fn new(parent: Option<usize>, kind: ScopeKind) -> Self {
+        Scope {
+            parent,
+            kind,
+            decls: vec![],
+            refs: Default::default(),
+            uses: Default::default(),
+            fn_names: Default::default(),
+            class_names: Default::default(),
+            has_eval: false,
+        }
+    }
+
+    pub fn declares(&self, sym: &JsWord) -> bool {
+        self.refs.contains_key(sym)
+    }
+}
+
+/// Returns the scopes of `module` in pre-order. The first one is the module
+/// itself.
+pub(super) fn analyze(module: &Module) -> Vec<Scope> {
+    let mut a = Analyzer {
+        scopes: vec![Scope::new(None, ScopeKind::Fn)],
+        cur: 0,
+        refs: vec![],
+    };
+    module.visit_with(&mut a);
+    a.resolve();
+
+    a.scopes
+}
+
+/// Returns the scope declaring `sym` as seen from `scope`, or `None` if it's a
+/// global.
+pub(super) fn find_decl(scopes: &[Scope], mut scope: usize, sym: &JsWord) -> Option<usize> {
+    loop {
+        if scopes[scope].declares(sym) {
+            return Some(scope);
+        }
+        scope = scopes[scope].parent?;
+    }
+}
+
+/// Declarations are hoisted, so references are resolved after visiting the
+/// whole module.
+///
+/// The renamer opens scopes for the same nodes in the same order.
+struct Analyzer {
+    scopes: Vec<Scope>,
+    cur: usize,
+    /// References and the scopes containing them.
+    refs: Vec<(usize, JsWord)>,
+}
+
+impl Analyzer {
+    fn with_scope<F>(&mut self, kind: ScopeKind, op: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        let parent = self.cur;
+        self.scopes.push(Scope::new(Some(parent), kind));
+        self.cur = self.scopes.len() - 1;
+        op(self);
+        self.cur = parent;
+    }
+
+    /// Declares `i` in `scope` and references it.
+    fn declare(&mut self, scope: usize, i: &Ident) {
+        {
+            let scope = &mut self.scopes[scope];
+            if !scope.declares(&i.sym) {
+                scope.decls.push(i.sym.clone());
+                scope.refs.insert(i.sym.clone(), 0);
+            }
+        }
+        self.add_ref(i);
+    }
+
+    /// Declares identifiers bound by `pat`. They are referenced when `pat` is
+    /// visited.
+    fn declare_pat(&mut self, scope: usize, pat: &Pat) {
+        let mut ids = vec![];
+        pat_ids(pat, &mut ids);
+        let scope = &mut self.scopes[scope];
+        for i in ids {
+            if !scope.declares(&i.sym) {
+                scope.decls.push(i.sym.clone());
+                scope.refs.insert(i.sym, 0);
+            }
+        }
+    }
+
+    /// Returns the nearest function scope, which contains `var` declarations.
+    fn fn_scope(&self) -> usize {
+        let mut scope = self.cur;
+        while self.scopes[scope].kind != ScopeKind::Fn {
+            scope = self.scopes[scope].parent.expect("module is a function scope");
+        }
+        scope
+    }
+
+    fn add_ref(&mut self, i: &Ident) {
+        self.refs.push((self.cur, i.sym.clone()));
+    }
+
+    fn mark_eval(&mut self) {
+        let mut scope = Some(self.cur);
+        while let Some(s) = scope {
+            self.scopes[s].has_eval = true;
+            scope = self.scopes[s].parent;
+        }
+    }
+
+    /// `ident` is the name of a function expression, which is only visible in
+    /// the function.
+    fn visit_fn(&mut self, ident: Option<&Ident>, f: &Function) {
+        self.with_scope(ScopeKind::Fn, |a| {
+            let cur = a.cur;
+            if let Some(ident) = ident {
+                a.declare(cur, ident);
+                a.scopes[cur].fn_names.insert(ident.sym.clone());
+            }
+            for param in &f.params {
+                a.declare_pat(cur, &param.pat);
+            }
+            f.params.visit_with(a);
+            if let Some(ref body) = f.body {
+                body.stmts.visit_with(a);
+            }
+        })
+    }
+
+    fn resolve(&mut self) {
+        for (scope, sym) in mem::replace(&mut self.refs, vec![]) {
+            let decl = find_decl(&self.scopes, scope, &sym);
+
+            let mut s = Some(scope);
+            while let Some(cur) = s {
+                if Some(cur) == decl {
+                    break;
+                }
+                self.scopes[cur].uses.insert((decl, sym.clone()));
+                s = self.scopes[cur].parent;
+            }
+
+            if let Some(decl) = decl {
+                *self.scopes[decl].refs.get_mut(&sym).unwrap() += 1;
+            }
+        }
+    }
+}
+
+impl Visit<Function> for Analyzer {
+    fn visit(&mut self, f: &Function) {
+        self.visit_fn(None, f)
+    }
+}
+
+impl Visit<FnExpr> for Analyzer {
+    fn visit(&mut self, f: &FnExpr) {
+        self.visit_fn(f.ident.as_ref(), &f.function)
+    }
+}
+
+/// Function declarations are block scoped in strict mode.
+impl Visit<FnDecl> for Analyzer {
+    fn visit(&mut self, f: &FnDecl) {
+        let cur = self.cur;
+        self.declare(cur, &f.ident);
+        self.scopes[cur].fn_names.insert(f.ident.sym.clone());
+        f.function.visit_with(self)
+    }
+}
+
+impl Visit<ArrowExpr> for Analyzer {
+    fn visit(&mut self, f: &ArrowExpr) {
+        self.with_scope(ScopeKind::Fn, |a| {
+            let cur = a.cur;
+            for param in &f.params {
+                a.declare_pat(cur, param);
+            }
+            f.params.visit_with(a);
+            match f.body {
+                BlockStmtOrExpr::BlockStmt(ref body) => body.stmts.visit_with(a),
+                BlockStmtOrExpr::Expr(ref body) => body.visit_with(a),
+            }
+        })
+    }
+}
+
+impl Visit<GetterProp> for Analyzer {
+    fn visit(&mut self, p: &GetterProp) {
+        p.key.visit_with(self);
+        self.with_scope(ScopeKind::Fn, |a| p.body.stmts.visit_with(a))
+    }
+}
+
+impl Visit<SetterProp> for Analyzer {
+    fn visit(&mut self, p: &SetterProp) {
+        p.key.visit_with(self);
+        self.with_scope(ScopeKind::Fn, |a| {
+            let cur = a.cur;
+            a.declare_pat(cur, &p.param);
+            p.param.visit_with(a);
+            p.body.stmts.visit_with(a);
+        })
+    }
+}
+
+impl Visit<ClassDecl> for Analyzer {
+    fn visit(&mut self, c: &ClassDecl) {
+        let cur = self.cur;
+        self.declare(cur, &c.ident);
+        self.scopes[cur].class_names.insert(c.ident.sym.clone());
+        c.class.visit_with(self)
+    }
+}
+
+impl Visit<ClassExpr> for Analyzer {
+    fn visit(&mut self, c: &ClassExpr) {
+        match c.ident {
+            Some(ref ident) => self.with_scope(ScopeKind::Block, |a| {
+                let cur = a.cur;
+                a.declare(cur, ident);
+                a.scopes[cur].class_names.insert(ident.sym.clone());
+                c.class.visit_with(a);
+            }),
+            None => c.class.visit_with(self),
+        }
+    }
+}
+
+impl Visit<BlockStmt> for Analyzer {
+    fn visit(&mut self, block: &BlockStmt) {
+        self.with_scope(ScopeKind::Block, |a| block.stmts.visit_with(a))
+    }
+}
+
+impl Visit<CatchClause> for Analyzer {
+    fn visit(&mut self, c: &CatchClause) {
+        self.with_scope(ScopeKind::Block, |a| {
+            if let Some(ref param) = c.param {
+                let cur = a.cur;
+                a.declare_pat(cur, param);
+            }
+            c.visit_children(a);
+        })
+    }
+}
+
+macro_rules! block_scoped {
+    ($T:ty) => {
+        impl Visit<$T> for Analyzer {
+            fn visit(&mut self, node: &$T) {
+                self.with_scope(ScopeKind::Block, |a| node.visit_children(a))
+            }
+        }
+    };
+}
+block_scoped!(ForStmt);
+block_scoped!(ForInStmt);
+block_scoped!(ForOfStmt);
+
+impl Visit<SwitchStmt> for Analyzer {
+    fn visit(&mut self, s: &SwitchStmt) {
+        s.discriminant.visit_with(self);
+        self.with_scope(ScopeKind::Block, |a| s.cases.visit_with(a))
+    }
+}
+
+impl Visit<VarDecl> for Analyzer {
+    fn visit(&mut self, v: &VarDecl) {
+        let scope = if v.kind == VarDeclKind::Var {
+            self.fn_scope()
+        } else {
+            self.cur
+        };
+        for decl in &v.decls {
+            self.declare_pat(scope, &decl.name);
+
+            // `var a = function () {}` is named `a`.
+            if let Pat::Ident(ref i) = decl.name {
+                let names = match decl.init {
+                    Some(box Expr::Fn(..)) | Some(box Expr::Arrow(..)) => {
+                        &mut self.scopes[scope].fn_names
+                    }
+                    Some(box Expr::Class(..)) => &mut self.scopes[scope].class_names,
+                    _ => continue,
+                };
+                names.insert(i.sym.clone());
+            }
+        }
+        v.visit_children(self)
+    }
+}
+
+impl Visit<ImportSpecifier> for Analyzer {
+    fn visit(&mut self, s: &ImportSpecifier) {
+        let local = match *s {
+            ImportSpecifier::Specific(ImportSpecific { ref local, .. })
+            | ImportSpecifier::Default(ImportDefault { ref local, .. })
+            | ImportSpecifier::Namespace(ImportStarAs { ref local, .. }) => local,
+        };
+        self.declare(0, local)
+    }
+}
+
+/// Exported names are not renamed.
+impl Visit<ExportSpecifier> for Analyzer {
+    fn visit(&mut self, _: &ExportSpecifier) {}
+}
+
+/// Names of default exports are declared in the module.
+impl Visit<DefaultDecl> for Analyzer {
+    fn visit(&mut self, d: &DefaultDecl) {
+        match *d {
+            DefaultDecl::Fn(FnExpr {
+                ref ident,
+                ref function,
+            }) => {
+                if let Some(ref ident) = *ident {
+                    self.declare(0, ident);
+                }
+                function.visit_with(self)
+            }
+            DefaultDecl::Class(ClassExpr {
+                ref ident,
+                ref class,
+            }) => {
+                if let Some(ref ident) = *ident {
+                    self.declare(0, ident);
+                }
+                class.visit_with(self)
+            }
+            DefaultDecl::Var(ref v) => v.visit_with(self),
+        }
+    }
+}
+
+impl Visit<Expr> for Analyzer {
+    fn visit(&mut self, expr: &Expr) {
+        match *expr {
+            Expr::Ident(ref i) => self.add_ref(i),
+            Expr::Call(CallExpr {
+                callee: ExprOrSuper::Expr(box Expr::Ident(ref callee)),
+                ..
+            }) if &*callee.sym == "eval" =>
+            {
+                self.mark_eval();
+                expr.visit_children(self)
+            }
+            _ => expr.visit_children(self),
+        }
+    }
+}
+
+impl Visit<WithStmt> for Analyzer {
+    fn visit(&mut self, s: &WithStmt) {
+        self.mark_eval();
+        s.visit_children(self)
+    }
+}
+
+impl Visit<Pat> for Analyzer {
+    fn visit(&mut self, pat: &Pat) {
+        match *pat {
+            Pat::Ident(ref i) => self.add_ref(i),
+            _ => pat.visit_children(self),
+        }
+    }
+}
+
+impl Visit<ObjectPatProp> for Analyzer {
+    fn visit(&mut self, prop: &ObjectPatProp) {
+        match *prop {
+            ObjectPatProp::Assign(AssignPatProp {
+                ref key, ref value, ..
+            }) => {
+                self.add_ref(key);
+                value.visit_with(self)
+            }
+            _ => prop.visit_children(self),
+        }
+    }
+}
+
+impl Visit<Prop> for Analyzer {
+    fn visit(&mut self, prop: &Prop) {
+        match *prop {
+            Prop::Shorthand(ref i) => self.add_ref(i),
+            _ => prop.visit_children(self),
+        }
+    }
+}
+
+impl Visit<MemberExpr> for Analyzer {
+    fn visit(&mut self, e: &MemberExpr) {
+        e.obj.visit_with(self);
+        if e.computed {
+            e.prop.visit_with(self);
+        }
+    }
+}
+
+impl Visit<PropName> for Analyzer {
+    fn visit(&mut self, name: &PropName) {
+        if let PropName::Computed(ref c) = *name {
+            c.expr.visit_with(self);
+        }
+    }
+}
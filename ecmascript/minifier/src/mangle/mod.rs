@@ -0,0 +1,290 @@
+use self::{
+    analyzer::{analyze, Scope},
+    props::mangle_props,
+    renamer::Renamer,
+};
+use crate::{name_cache::NameCache, option::MangleOptions};
+use ast::*;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+};
+use swc_atoms::JsWord;
+use swc_common::{Fold, FoldWith};
+use swc_ecma_transforms::util::pat_ids;
+
+mod analyzer;
+mod props;
+mod renamer;
+#[cfg(test)]
+mod tests;
+
+/// Creates a pass which renames bindings to short names.
+///
+/// Top-level bindings are renamed only if `top_level` is set, and exported
+/// names are kept. Bindings visible to `eval` and `with` are kept, as well as
+/// names of functions and classes matched by `keep_fnames` and
+/// `keep_classnames`.
+///
+/// Properties are renamed only if `properties` is set.
+pub fn mangler(options: MangleOptions) -> impl Fold<Module> {
+    Mangler {
+        options,
+        cache: None,
+    }
+}
+
+/// Creates a mangler which reuses names of top-level bindings stored in
+/// `cache` by other modules, and stores names it chooses.
+///
+/// Globals renamed by other modules are renamed in the same way, so modules
+/// minified separately can reference each other's bindings.
+pub fn mangler_with_cache(
+    options: MangleOptions,
+    cache: Arc<Mutex<NameCache>>,
+) -> impl Fold<Module> {
+    Mangler {
+        options,
+        cache: Some(cache),
+    }
+}
+
+#[derive(Debug)]
+struct Mangler {
+    options: MangleOptions,
+    cache: Option<Arc<Mutex<NameCache>>>,
+}
+
+impl Fold<Module> for Mangler {
+    fn fold(&mut self, module: Module) -> Module {
+        match self.cache {
+            Some(ref cache) => {
+                let mut cache = cache.lock().expect("name cache is poisoned");
+                self.mangle(module, &mut cache)
+            }
+            None => self.mangle(module, &mut NameCache::default()),
+        }
+    }
+}
+
+impl Mangler {
+    fn mangle(&self, module: Module, cache: &mut NameCache) -> Module {
+        let scopes = analyze(&module);
+        let (renames, globals) = self.assign(&module, &scopes, cache);
+        let module = module.fold_with(&mut Renamer::new(&scopes, &renames, &globals));
+
+        match self.options.properties {
+            Some(ref options) => mangle_props(module, options, cache),
+            None => module,
+        }
+    }
+
+    /// Chooses new names for bindings of each scope.
+    ///
+    /// Scopes are processed from the outermost one, so names of bindings used
+    /// by a scope are known when it's processed.
+    ///
+    /// Returns new names of bindings of each scope and new names of globals.
+    fn assign(
+        &self,
+        module: &Module,
+        scopes: &[Scope],
+        cache: &mut NameCache,
+    ) -> (Vec<HashMap<JsWord, JsWord>>, HashMap<JsWord, JsWord>) {
+        let options = &self.options;
+        let reserved: HashSet<JsWord> = options
+            .reserved
+            .iter()
+            .map(|s| JsWord::from(&**s))
+            .collect();
+        let exported = exported_names(module);
+
+        // Bindings which keep their names.
+        let kept: Vec<HashSet<JsWord>> = scopes
+            .iter()
+            .enumerate()
+            .map(|(i, scope)| {
+                scope
+                    .decls
+                    .iter()
+                    .filter(|sym| {
+                        scope.has_eval
+                            || reserved.contains(*sym)
+                            || (i == 0 && (!options.top_level || exported.contains(*sym)))
+                            || (scope.fn_names.contains(*sym) && options.keep_fnames.keeps(sym))
+                            || (scope.class_names.contains(*sym)
+                                && options.keep_classnames.keeps(sym))
+                    })
+                    .cloned()
+                    .collect()
+            })
+            .collect();
+
+        // Kept names of each scope and its children, which can't be used by
+        // bindings referenced from the children. Children come after their
+        // parents.
+        let mut kept_below = kept.clone();
+        for i in (1..scopes.len()).rev() {
+            let parent = scopes[i].parent.expect("only the module has no parent");
+            let below: Vec<_> = kept_below[i].iter().cloned().collect();
+            kept_below[parent].extend(below);
+        }
+
+        // Globals declared by other modules, which are renamed like their
+        // declarations.
+        let globals: HashMap<JsWord, JsWord> = if options.top_level {
+            scopes[0]
+                .uses
+                .iter()
+                .filter_map(|&(decl, ref sym)| match decl {
+                    Some(..) => None,
+                    None => cache
+                        .vars
+                        .get(&**sym)
+                        .map(|name| (sym.clone(), JsWord::from(&**name))),
+                })
+                .filter(|&(_, ref name)| !kept_below[0].contains(name))
+                .collect()
+        } else {
+            HashMap::new()
+        };
+        // Names of globals of other modules can't be used by new top-level
+        // bindings.
+        let cached: HashSet<JsWord> = cache
+            .vars
+            .values()
+            .map(|name| JsWord::from(&**name))
+            .collect();
+
+        let mut renames: Vec<HashMap<JsWord, JsWord>> = Vec::with_capacity(scopes.len());
+        for (i, scope) in scopes.iter().enumerate() {
+            let mut taken = kept_below[i].clone();
+            for &(decl, ref sym) in &scope.uses {
+                taken.insert(match decl {
+                    Some(decl) => renames[decl].get(sym).unwrap_or(sym).clone(),
+                    None => globals.get(sym).unwrap_or(sym).clone(),
+                });
+            }
+
+            let mut decls: Vec<_> = scope
+                .decls
+                .iter()
+                .filter(|sym| !kept[i].contains(*sym))
+                .collect();
+            // Frequently used bindings get shorter names.
+            decls.sort_by(|a, b| scope.refs[*b].cmp(&scope.refs[*a]));
+
+            let mut names = (0..)
+                .map(|n| JsWord::from(name(n)))
+                .filter(|name| {
+                    !taken.contains(name)
+                        && !reserved.contains(name)
+                        && !is_reserved_word(name)
+                        && !(i == 0 && cached.contains(name))
+                });
+            let rename: HashMap<_, _> = decls
+                .into_iter()
+                .map(|sym| {
+                    let reused = match cache.vars.get(&**sym) {
+                        Some(name) if i == 0 => Some(JsWord::from(&**name)),
+                        _ => None,
+                    };
+                    let name = match reused {
+                        Some(ref name) if !taken.contains(name) => name.clone(),
+                        _ => names.next().unwrap(),
+                    };
+                    (sym.clone(), name)
+                })
+                .collect();
+
+            if i == 0 {
+                for (sym, name) in &rename {
+                    cache
+                        .vars
+                        .entry(sym.to_string())
+                        .or_insert_with(|| name.to_string());
+                }
+            }
+            renames.push(rename);
+        }
+
+        (renames, globals)
+    }
+}
+
+/// Returns names exported from `module` itself.
+fn exported_names(module: &Module) -> HashSet<JsWord> {
+    let mut ids = vec![];
+
+    for item in &module.body {
+        match *item {
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { ref decl, .. })) => {
+                match *decl {
+                    Decl::Fn(FnDecl { ref ident, .. })
+                    | Decl::Class(ClassDecl { ref ident, .. }) => ids.push(ident.clone()),
+                    Decl::Var(ref var) => {
+                        for decl in &var.decls {
+                            pat_ids(&decl.name, &mut ids);
+                        }
+                    }
+                    Decl::TsEnum(TsEnumDecl { ref id, .. }) => ids.push(id.clone()),
+                    Decl::TsInterface(..) | Decl::TsTypeAlias(..) | Decl::TsModule(..) => {}
+                }
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+                ref specifiers,
+                src: None,
+                ..
+            })) => ids.extend(specifiers.iter().map(|s| s.orig.clone())),
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                decl:
+                    DefaultDecl::Fn(FnExpr {
+                        ident: Some(ref ident),
+                        ..
+                    }),
+                ..
+            }))
+            | ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                decl:
+                    DefaultDecl::Class(ClassExpr {
+                        ident: Some(ref ident),
+                        ..
+                    }),
+                ..
+            })) => ids.push(ident.clone()),
+            _ => {}
+        }
+    }
+
+    ids.into_iter().map(|i| i.sym).collect()
+}
+
+/// Returns the `n`th shortest identifier.
+fn name(mut n: usize) -> String {
+    const FIRST: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ$_";
+    const REST: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ$_0123456789";
+
+    let mut s = String::new();
+    s.push(FIRST[n % FIRST.len()] as char);
+    n /= FIRST.len();
+    while n > 0 {
+        n -= 1;
+        s.push(REST[n % REST.len()] as char);
+        n /= REST.len();
+    }
+    s
+}
+
+/// Reserved words and names which should not be shadowed.
+fn is_reserved_word(name: &str) -> bool {
+    match name {
+        "arguments" | "await" | "break" | "case" | "catch" | "class" | "const" | "continue"
+        | "debugger" | "default" | "delete" | "do" | "else" | "enum" | "eval" | "export"
+        | "extends" | "false" | "finally" | "for" | "function" | "if" | "implements"
+        | "import" | "in" | "instanceof" | "interface" | "let" | "new" | "null" | "package"
+        | "private" | "protected" | "public" | "return" | "static" | "super" | "switch"
+        | "this" | "throw" | "true" | "try" | "typeof" | "var" | "void" | "while" | "with"
+        | "yield" | "undefined" | "NaN" | "Infinity" => true,
+        _ => false,
+    }
+}
@@ -0,0 +1,278 @@
+use super::{is_reserved_word, name};
+use crate::{is_ident, name_cache::NameCache, option::PropertiesOptions};
+use ast::*;
+use std::collections::{HashMap, HashSet};
+use swc_atoms::JsWord;
+use swc_common::{Fold, FoldWith, Visit, VisitWith};
+
+/// Renames properties of `module` matched by `options`, reusing names stored in
+/// `cache.props`.
+pub(super) fn mangle_props(
+    module: Module,
+    options: &PropertiesOptions,
+    cache: &mut NameCache,
+) -> Module {
+    let mut c = Collector::default();
+    module.visit_with(&mut c);
+
+    let reserved: HashSet<JsWord> = options
+        .reserved
+        .iter()
+        .map(|s| JsWord::from(&**s))
+        .collect();
+    let renamed = |sym: &JsWord| {
+        is_ident(sym)
+            && !is_builtin(sym)
+            && !reserved.contains(sym)
+            && !(options.keep_quoted && c.quoted.contains(sym))
+            && match options.regex {
+                Some(ref re) => re.is_match(sym),
+                None => true,
+            }
+    };
+
+    let mut props: Vec<_> = c.counts.keys().filter(|sym| renamed(sym)).collect();
+    // Frequently used properties get shorter names.
+    props.sort_by(|a, b| {
+        c.counts[*b]
+            .cmp(&c.counts[*a])
+            .then_with(|| (***a).cmp(&***b))
+    });
+
+    let kept: HashSet<JsWord> = c
+        .counts
+        .keys()
+        .filter(|sym| !renamed(sym))
+        .cloned()
+        .collect();
+    // Names used by other modules are not reused for other properties.
+    let cached: HashSet<JsWord> = cache
+        .props
+        .values()
+        .map(|name| JsWord::from(&**name))
+        .collect();
+    let mut names = (0..).map(|n| JsWord::from(name(n))).filter(|name| {
+        !kept.contains(name)
+            && !cached.contains(name)
+            && !reserved.contains(name)
+            && !is_reserved_word(name)
+    });
+
+    let renames: HashMap<JsWord, JsWord> = props
+        .into_iter()
+        .map(|sym| {
+            let reused = cache.props.get(&**sym).map(|name| JsWord::from(&**name));
+            let name = match reused {
+                Some(ref name) if !kept.contains(name) => name.clone(),
+                _ => names.next().unwrap(),
+            };
+            (sym.clone(), name)
+        })
+        .collect();
+
+    for (sym, name) in &renames {
+        cache
+            .props
+            .entry(sym.to_string())
+            .or_insert_with(|| name.to_string());
+    }
+
+    module.fold_with(&mut PropRenamer { renames: &renames })
+}
+
+/// Collects names of properties.
+#[derive(Default)]
+struct Collector {
+    counts: HashMap<JsWord, usize>,
+    /// Names written as string literals.
+    quoted: HashSet<JsWord>,
+}
+
+impl Collector {
+    fn add(&mut self, sym: &JsWord) {
+        *self.counts.entry(sym.clone()).or_insert(0) += 1;
+    }
+
+    fn add_quoted(&mut self, s: &Str) {
+        self.add(&s.value);
+        self.quoted.insert(s.value.clone());
+    }
+}
+
+impl Visit<MemberExpr> for Collector {
+    fn visit(&mut self, e: &MemberExpr) {
+        match *e.prop {
+            Expr::Ident(ref i) if !e.computed => self.add(&i.sym),
+            Expr::Lit(Lit::Str(ref s)) if e.computed => self.add_quoted(s),
+            _ => {}
+        }
+        e.visit_children(self)
+    }
+}
+
+impl Visit<PropName> for Collector {
+    fn visit(&mut self, name: &PropName) {
+        match *name {
+            PropName::Ident(ref i) => self.add(&i.sym),
+            PropName::Str(ref s)
+            | PropName::Computed(ComputedPropName {
+                expr: box Expr::Lit(Lit::Str(ref s)),
+                ..
+            }) => self.add_quoted(s),
+            _ => name.visit_children(self),
+        }
+    }
+}
+
+impl Visit<Prop> for Collector {
+    fn visit(&mut self, prop: &Prop) {
+        if let Prop::Shorthand(ref i) = *prop {
+            self.add(&i.sym);
+        }
+        prop.visit_children(self)
+    }
+}
+
+impl Visit<ObjectPatProp> for Collector {
+    fn visit(&mut self, prop: &ObjectPatProp) {
+        if let ObjectPatProp::Assign(ref p) = *prop {
+            self.add(&p.key.sym);
+        }
+        prop.visit_children(self)
+    }
+}
+
+struct PropRenamer<'a> {
+    renames: &'a HashMap<JsWord, JsWord>,
+}
+
+impl<'a> PropRenamer<'a> {
+    fn rename(&self, i: Ident) -> Ident {
+        match self.renames.get(&i.sym) {
+            Some(sym) => Ident {
+                sym: sym.clone(),
+                ..i
+            },
+            None => i,
+        }
+    }
+
+    fn rename_str(&self, s: Str) -> Str {
+        match self.renames.get(&s.value) {
+            Some(sym) => Str {
+                value: sym.clone(),
+                has_escape: false,
+                raw: None,
+                ..s
+            },
+            None => s,
+        }
+    }
+}
+
+impl<'a> Fold<MemberExpr> for PropRenamer<'a> {
+    fn fold(&mut self, e: MemberExpr) -> MemberExpr {
+        let obj = e.obj.fold_with(self);
+        let prop = if e.computed {
+            match e.prop {
+                box Expr::Lit(Lit::Str(s)) => box Expr::Lit(Lit::Str(self.rename_str(s))),
+                prop => prop.fold_with(self),
+            }
+        } else {
+            match e.prop {
+                box Expr::Ident(i) => box Expr::Ident(self.rename(i)),
+                prop => prop,
+            }
+        };
+
+        MemberExpr { obj, prop, ..e }
+    }
+}
+
+impl<'a> Fold<PropName> for PropRenamer<'a> {
+    fn fold(&mut self, name: PropName) -> PropName {
+        match name {
+            PropName::Ident(i) => PropName::Ident(self.rename(i)),
+            PropName::Str(s) => PropName::Str(self.rename_str(s)),
+            PropName::Computed(ComputedPropName {
+                span,
+                expr: box Expr::Lit(Lit::Str(s)),
+            }) => PropName::Computed(ComputedPropName {
+                span,
+                expr: box Expr::Lit(Lit::Str(self.rename_str(s))),
+            }),
+            _ => name.fold_children(self),
+        }
+    }
+}
+
+impl<'a> Fold<Prop> for PropRenamer<'a> {
+    fn fold(&mut self, prop: Prop) -> Prop {
+        match prop {
+            // `{ a }` -> `{ b: a }`
+            Prop::Shorthand(i) => {
+                let key = self.rename(i.clone());
+                if key.sym == i.sym {
+                    return Prop::Shorthand(i);
+                }
+
+                Prop::KeyValue(KeyValueProp {
+                    key: PropName::Ident(key),
+                    value: box Expr::Ident(i),
+                })
+            }
+            _ => prop.fold_children(self),
+        }
+    }
+}
+
+impl<'a> Fold<ObjectPatProp> for PropRenamer<'a> {
+    fn fold(&mut self, prop: ObjectPatProp) -> ObjectPatProp {
+        match prop {
+            // `{ a = 1 }` -> `{ b: a = 1 }`
+            ObjectPatProp::Assign(AssignPatProp { span, key, value }) => {
+                let value = value.fold_with(self);
+                let renamed = self.rename(key.clone());
+                if renamed.sym == key.sym {
+                    return ObjectPatProp::Assign(AssignPatProp { span, key, value });
+                }
+
+                ObjectPatProp::KeyValue(KeyValuePatProp {
+                    key: PropName::Ident(renamed),
+                    value: match value {
+                        Some(right) => box Pat::Assign(AssignPat {
+                            span,
+                            left: box Pat::Ident(key),
+                            right,
+                        }),
+                        None => box Pat::Ident(key),
+                    },
+                })
+            }
+            _ => prop.fold_children(self),
+        }
+    }
+}
+
+/// Properties of built-in objects, which are not renamed.
+///
+/// Properties of the DOM are not listed, so `regex` or `reserved` should be
+/// used by code accessing them.
+fn is_builtin(name: &str) -> bool {
+    match name {
+        "__proto__" | "add" | "all" | "apply" | "assign" | "bind" | "call" | "catch"
+        | "charAt" | "charCodeAt" | "clear" | "concat" | "configurable" | "constructor"
+        | "create" | "default" | "defineProperty" | "delete" | "done" | "endsWith"
+        | "entries" | "enumerable" | "every" | "exports" | "fill" | "filter" | "finally"
+        | "find" | "findIndex" | "forEach" | "freeze" | "from" | "get" | "has"
+        | "hasOwnProperty" | "includes" | "indexOf" | "isArray" | "join" | "keys"
+        | "lastIndexOf" | "length" | "map" | "match" | "message" | "name" | "next" | "of"
+        | "padEnd" | "padStart" | "parse" | "pop" | "prototype" | "push" | "race"
+        | "reduce" | "reject" | "replace" | "resolve" | "return" | "reverse" | "search"
+        | "set" | "shift" | "size" | "slice" | "some" | "sort" | "splice" | "split"
+        | "stack" | "startsWith" | "stringify" | "substr" | "substring" | "then"
+        | "throw" | "toJSON" | "toLowerCase" | "toString" | "toUpperCase" | "trim"
+        | "unshift" | "value" | "valueOf" | "values" | "writable" => true,
+        _ => false,
+    }
+}
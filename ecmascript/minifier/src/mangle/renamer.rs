@@ -0,0 +1,370 @@
+use super::analyzer::{find_decl, Scope};
+use ast::*;
+use std::collections::HashMap;
+use swc_atoms::JsWord;
+use swc_common::{Fold, FoldWith};
+
+/// Applies new names chosen for each scope.
+///
+/// Scopes are opened for the same nodes as the analyzer, so they are
+/// identified by the order they are entered.
+pub(super) struct Renamer<'a> {
+    scopes: &'a [Scope],
+    renames: &'a [HashMap<JsWord, JsWord>],
+    globals: &'a HashMap<JsWord, JsWord>,
+    cur: usize,
+    /// Index of the last scope entered.
+    last: usize,
+}
+
+impl<'a> Renamer<'a> {
+    pub fn new(
+        scopes: &'a [Scope],
+        renames: &'a [HashMap<JsWord, JsWord>],
+        globals: &'a HashMap<JsWord, JsWord>,
+    ) -> Self {
+        Renamer {
+            scopes,
+            renames,
+            globals,
+            cur: 0,
+            last: 0,
+        }
+    }
+
+    fn with_scope<F, T>(&mut self, op: F) -> T
+    where
+        F: FnOnce(&mut Self) -> T,
+    {
+        let parent = self.cur;
+        self.last += 1;
+        self.cur = self.last;
+        let ret = op(self);
+        self.cur = parent;
+        ret
+    }
+
+    fn rename(&self, i: Ident) -> Ident {
+        let renamed = match find_decl(self.scopes, self.cur, &i.sym) {
+            Some(scope) => self.renames[scope].get(&i.sym),
+            None => self.globals.get(&i.sym),
+        };
+
+        match renamed {
+            Some(sym) => Ident {
+                sym: sym.clone(),
+                ..i
+            },
+            None => i,
+        }
+    }
+
+    /// Folds a function without opening a scope.
+    fn fold_fn(&mut self, f: Function) -> Function {
+        Function {
+            params: f.params.fold_with(self),
+            body: f.body.map(|body| BlockStmt {
+                stmts: body.stmts.fold_with(self),
+                ..body
+            }),
+            ..f
+        }
+    }
+}
+
+impl<'a> Fold<Function> for Renamer<'a> {
+    fn fold(&mut self, f: Function) -> Function {
+        self.with_scope(|r| r.fold_fn(f))
+    }
+}
+
+impl<'a> Fold<FnExpr> for Renamer<'a> {
+    fn fold(&mut self, f: FnExpr) -> FnExpr {
+        self.with_scope(|r| FnExpr {
+            ident: f.ident.map(|i| r.rename(i)),
+            function: r.fold_fn(f.function),
+        })
+    }
+}
+
+impl<'a> Fold<FnDecl> for Renamer<'a> {
+    fn fold(&mut self, f: FnDecl) -> FnDecl {
+        FnDecl {
+            ident: self.rename(f.ident),
+            function: f.function.fold_with(self),
+            ..f
+        }
+    }
+}
+
+impl<'a> Fold<ArrowExpr> for Renamer<'a> {
+    fn fold(&mut self, f: ArrowExpr) -> ArrowExpr {
+        self.with_scope(|r| ArrowExpr {
+            params: f.params.fold_with(r),
+            body: match f.body {
+                BlockStmtOrExpr::BlockStmt(body) => BlockStmtOrExpr::BlockStmt(BlockStmt {
+                    stmts: body.stmts.fold_with(r),
+                    ..body
+                }),
+                BlockStmtOrExpr::Expr(body) => BlockStmtOrExpr::Expr(body.fold_with(r)),
+            },
+            ..f
+        })
+    }
+}
+
+impl<'a> Fold<GetterProp> for Renamer<'a> {
+    fn fold(&mut self, p: GetterProp) -> GetterProp {
+        let GetterProp { span, key, body } = p;
+        let key = key.fold_with(self);
+        let BlockStmt { span: body_span, stmts } = body;
+        let stmts = self.with_scope(|r| stmts.fold_with(r));
+
+        GetterProp {
+            span,
+            key,
+            body: BlockStmt {
+                span: body_span,
+                stmts,
+            },
+        }
+    }
+}
+
+impl<'a> Fold<SetterProp> for Renamer<'a> {
+    fn fold(&mut self, p: SetterProp) -> SetterProp {
+        let SetterProp {
+            span,
+            key,
+            param,
+            body,
+        } = p;
+        let key = key.fold_with(self);
+        let BlockStmt { span: body_span, stmts } = body;
+        let (param, stmts) = self.with_scope(|r| (param.fold_with(r), stmts.fold_with(r)));
+
+        SetterProp {
+            span,
+            key,
+            param,
+            body: BlockStmt {
+                span: body_span,
+                stmts,
+            },
+        }
+    }
+}
+
+impl<'a> Fold<ClassDecl> for Renamer<'a> {
+    fn fold(&mut self, c: ClassDecl) -> ClassDecl {
+        ClassDecl {
+            ident: self.rename(c.ident),
+            class: c.class.fold_with(self),
+            ..c
+        }
+    }
+}
+
+impl<'a> Fold<ClassExpr> for Renamer<'a> {
+    fn fold(&mut self, c: ClassExpr) -> ClassExpr {
+        let ClassExpr { ident, class } = c;
+
+        match ident {
+            Some(ident) => self.with_scope(|r| ClassExpr {
+                ident: Some(r.rename(ident)),
+                class: class.fold_with(r),
+            }),
+            None => ClassExpr {
+                ident: None,
+                class: class.fold_with(self),
+            },
+        }
+    }
+}
+
+macro_rules! block_scoped {
+    ($T:ty) => {
+        impl<'a> Fold<$T> for Renamer<'a> {
+            fn fold(&mut self, node: $T) -> $T {
+                self.with_scope(|r| node.fold_children(r))
+            }
+        }
+    };
+}
+block_scoped!(BlockStmt);
+block_scoped!(CatchClause);
+block_scoped!(ForStmt);
+block_scoped!(ForInStmt);
+block_scoped!(ForOfStmt);
+
+impl<'a> Fold<SwitchStmt> for Renamer<'a> {
+    fn fold(&mut self, s: SwitchStmt) -> SwitchStmt {
+        let SwitchStmt {
+            span,
+            discriminant,
+            cases,
+        } = s;
+        let discriminant = discriminant.fold_with(self);
+        let cases = self.with_scope(|r| cases.fold_with(r));
+
+        SwitchStmt {
+            span,
+            discriminant,
+            cases,
+        }
+    }
+}
+
+impl<'a> Fold<ImportSpecifier> for Renamer<'a> {
+    fn fold(&mut self, s: ImportSpecifier) -> ImportSpecifier {
+        match s {
+            // `import { a } from 'foo'` -> `import { a as b } from 'foo'`
+            ImportSpecifier::Specific(ImportSpecific {
+                span,
+                local,
+                imported,
+            }) => {
+                let renamed = self.rename(local.clone());
+                let imported = if renamed.sym != local.sym {
+                    imported.or(Some(local))
+                } else {
+                    imported
+                };
+
+                ImportSpecifier::Specific(ImportSpecific {
+                    span,
+                    local: renamed,
+                    imported,
+                })
+            }
+            ImportSpecifier::Default(ImportDefault { span, local }) => {
+                ImportSpecifier::Default(ImportDefault {
+                    span,
+                    local: self.rename(local),
+                })
+            }
+            ImportSpecifier::Namespace(ImportStarAs { span, local }) => {
+                ImportSpecifier::Namespace(ImportStarAs {
+                    span,
+                    local: self.rename(local),
+                })
+            }
+        }
+    }
+}
+
+impl<'a> Fold<ExportSpecifier> for Renamer<'a> {
+    fn fold(&mut self, s: ExportSpecifier) -> ExportSpecifier {
+        s
+    }
+}
+
+/// Names of default exports are not renamed.
+impl<'a> Fold<DefaultDecl> for Renamer<'a> {
+    fn fold(&mut self, d: DefaultDecl) -> DefaultDecl {
+        match d {
+            DefaultDecl::Fn(FnExpr { ident, function }) => {
+                DefaultDecl::Fn(FnExpr {
+                    ident,
+                    function: function.fold_with(self),
+                })
+            }
+            DefaultDecl::Class(ClassExpr { ident, class }) => {
+                DefaultDecl::Class(ClassExpr {
+                    ident,
+                    class: class.fold_with(self),
+                })
+            }
+            DefaultDecl::Var(v) => DefaultDecl::Var(v.fold_with(self)),
+        }
+    }
+}
+
+impl<'a> Fold<Expr> for Renamer<'a> {
+    fn fold(&mut self, expr: Expr) -> Expr {
+        match expr {
+            Expr::Ident(i) => Expr::Ident(self.rename(i)),
+            _ => expr.fold_children(self),
+        }
+    }
+}
+
+impl<'a> Fold<Pat> for Renamer<'a> {
+    fn fold(&mut self, pat: Pat) -> Pat {
+        match pat {
+            Pat::Ident(i) => Pat::Ident(self.rename(i)),
+            _ => pat.fold_children(self),
+        }
+    }
+}
+
+impl<'a> Fold<ObjectPatProp> for Renamer<'a> {
+    fn fold(&mut self, prop: ObjectPatProp) -> ObjectPatProp {
+        match prop {
+            // `{ a = 1 }` -> `{ a: b = 1 }`
+            ObjectPatProp::Assign(AssignPatProp { span, key, value }) => {
+                let value = value.fold_with(self);
+                let renamed = self.rename(key.clone());
+                if renamed.sym == key.sym {
+                    return ObjectPatProp::Assign(AssignPatProp { span, key, value });
+                }
+
+                ObjectPatProp::KeyValue(KeyValuePatProp {
+                    key: PropName::Ident(key),
+                    value: match value {
+                        Some(right) => box Pat::Assign(AssignPat {
+                            span,
+                            left: box Pat::Ident(renamed),
+                            right,
+                        }),
+                        None => box Pat::Ident(renamed),
+                    },
+                })
+            }
+            _ => prop.fold_children(self),
+        }
+    }
+}
+
+impl<'a> Fold<Prop> for Renamer<'a> {
+    fn fold(&mut self, prop: Prop) -> Prop {
+        match prop {
+            // `{ a }` -> `{ a: b }`
+            Prop::Shorthand(i) => {
+                let renamed = self.rename(i.clone());
+                if renamed.sym == i.sym {
+                    return Prop::Shorthand(i);
+                }
+
+                Prop::KeyValue(KeyValueProp {
+                    key: PropName::Ident(i),
+                    value: box Expr::Ident(renamed),
+                })
+            }
+            _ => prop.fold_children(self),
+        }
+    }
+}
+
+impl<'a> Fold<MemberExpr> for Renamer<'a> {
+    fn fold(&mut self, e: MemberExpr) -> MemberExpr {
+        MemberExpr {
+            obj: e.obj.fold_with(self),
+            prop: if e.computed {
+                e.prop.fold_with(self)
+            } else {
+                e.prop
+            },
+            ..e
+        }
+    }
+}
+
+impl<'a> Fold<PropName> for Renamer<'a> {
+    fn fold(&mut self, name: PropName) -> PropName {
+        match name {
+            PropName::Computed(c) => PropName::Computed(c.fold_with(self)),
+            _ => name,
+        }
+    }
+}
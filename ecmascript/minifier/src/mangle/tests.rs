@@ -0,0 +1,240 @@
+use super::{mangler, mangler_with_cache};
+use crate::{
+    minifier,
+    name_cache::NameCache,
+    option::{KeepNames, MangleOptions, MinifyOptions, PropertiesOptions},
+};
+use regex::Regex;
+use std::sync::{Arc, Mutex};
+
+macro_rules! test_mangle {
+    ($test_name:ident, $input:expr, $expected:expr) => {
+        test_mangle!($test_name, $input, $expected, Default::default());
+    };
+    ($test_name:ident, $input:expr, $expected:expr, $options:expr) => {
+        test!(mangler($options), $test_name, $input, $expected);
+    };
+}
+
+fn top_level() -> MangleOptions {
+    MangleOptions {
+        top_level: true,
+        ..Default::default()
+    }
+}
+
+fn properties(keep_quoted: bool) -> MangleOptions {
+    MangleOptions {
+        properties: Some(PropertiesOptions {
+            regex: Some(Regex::new("^_").unwrap()),
+            keep_quoted,
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+test_mangle!(
+    params_and_vars,
+    "function foo(bar, baz) { var qux = bar + baz; return qux; }",
+    "function foo(a, b) { var c = a + b; return c; }"
+);
+
+test_mangle!(
+    globals,
+    "function foo(bar) { return function (baz) { return bar + baz + a; }; }",
+    "function foo(b) { return function (c) { return b + c + a; }; }"
+);
+
+test_mangle!(
+    block_scoped,
+    "function foo() { let bar = 1; { let bar = 2; g(bar); } return bar; }",
+    "function foo() { let a = 1; { let a = 2; g(a); } return a; }"
+);
+
+test_mangle!(
+    props_and_labels,
+    "function foo(bar) { bar: for (;;) break bar; return bar.bar; }",
+    "function foo(a) { bar: for (;;) break bar; return a.bar; }"
+);
+
+test_mangle!(
+    shorthand,
+    "function foo({ bar, baz = 1 }) { return { bar, baz }; }",
+    "function foo({ bar: a, baz: b = 1 }) { return { bar: a, baz: b }; }"
+);
+
+test_mangle!(
+    eval,
+    "function foo(bar) { eval('bar'); } function baz(bar) { return bar; }",
+    "function foo(bar) { eval('bar'); } function baz(a) { return a; }"
+);
+
+test_mangle!(
+    eval_in_child,
+    "function foo(bar) { return function (baz) { eval(''); }; }",
+    "function foo(bar) { return function (baz) { eval(''); }; }"
+);
+
+test_mangle!(
+    top_level_disabled,
+    "var foo = 1; function bar() { return foo; }",
+    "var foo = 1; function bar() { return foo; }"
+);
+
+test_mangle!(
+    top_level_enabled,
+    "var foo = 1; function bar() { return foo; } bar();",
+    "var a = 1; function b() { return a; } b();",
+    top_level()
+);
+
+test_mangle!(
+    top_level_exports,
+    "export var foo = 1; var bar = 2; export { bar as baz }; var qux = foo + bar;",
+    "export var foo = 1; var bar = 2; export { bar as baz }; var a = foo + bar;",
+    top_level()
+);
+
+test_mangle!(
+    top_level_imports,
+    "import { foo } from 'x'; import bar from 'y'; foo(bar);",
+    "import { foo as a } from 'x'; import b from 'y'; a(b);",
+    top_level()
+);
+
+test_mangle!(
+    reserved,
+    "function foo(bar, baz) { return bar + baz; }",
+    "function foo(bar, b) { return bar + b; }",
+    MangleOptions {
+        reserved: vec!["a".into(), "bar".into()],
+        ..Default::default()
+    }
+);
+
+test_mangle!(
+    keep_fnames,
+    "function foo() {
+        function bar() {}
+        var baz = function () {};
+        class Qux {}
+        return [bar, baz, Qux];
+    }",
+    "function foo() {
+        function bar() {}
+        var baz = function () {};
+        class a {}
+        return [bar, baz, a];
+    }",
+    MangleOptions {
+        keep_fnames: KeepNames::All,
+        ..Default::default()
+    }
+);
+
+test_mangle!(
+    keep_classnames,
+    "function foo() {
+        function bar() {}
+        var baz = function () {};
+        class Qux {}
+        return [bar, baz, Qux];
+    }",
+    "function foo() {
+        function a() {}
+        var b = function () {};
+        class Qux {}
+        return [a, b, Qux];
+    }",
+    MangleOptions {
+        keep_classnames: KeepNames::Matching(Regex::new("^Q").unwrap()),
+        ..Default::default()
+    }
+);
+
+#[test]
+fn name_cache() {
+    let cache = Arc::new(Mutex::new(NameCache::default()));
+
+    test_transform!(
+        mangler_with_cache(top_level(), cache.clone()),
+        "var foo = 1; function bar(baz) { return foo + baz; }",
+        "var a = 1; function b(b) { return a + b; }"
+    );
+    test_transform!(
+        mangler_with_cache(top_level(), cache.clone()),
+        "var qux = bar(foo); function f(foo) { return foo; }",
+        "var c = b(a); function d(a) { return a; }"
+    );
+
+    let vars = &cache.lock().unwrap().vars;
+    assert_eq!(vars["foo"], "a");
+    assert_eq!(vars["bar"], "b");
+    assert_eq!(vars["qux"], "c");
+    assert_eq!(vars["f"], "d");
+}
+
+#[test]
+fn name_cache_reuse() {
+    let mut cache = NameCache::default();
+    cache.vars.insert("bar".into(), "a".into());
+    let cache = Arc::new(Mutex::new(cache));
+
+    test_transform!(
+        mangler_with_cache(top_level(), cache),
+        "var foo = 1, bar = 2;",
+        "var b = 1, a = 2;"
+    );
+}
+
+test_mangle!(
+    properties,
+    "function foo(bar) { bar._x = bar._y + bar.z; return { _x: 1, _y, z: 2 }; }",
+    "function foo(a) { a.a = a.b + a.z; return { a: 1, b: _y, z: 2 }; }",
+    properties(false)
+);
+
+test_mangle!(
+    properties_quoted,
+    "x._a = 1; x['_b'] = 2; y = { '_a': 3, _c: x._b };",
+    "x.a = 1; x['b'] = 2; y = { 'a': 3, c: x.b };",
+    properties(false)
+);
+
+test_mangle!(
+    properties_keep_quoted,
+    "x._a = 1; x['_b'] = 2; y = { '_a': 3, _c: x._b };",
+    "x._a = 1; x['_b'] = 2; y = { '_a': 3, a: x._b };",
+    properties(true)
+);
+
+/// The compressor keeps quotes used by the mangler.
+#[test]
+fn properties_keep_quoted_minified() {
+    test_transform!(
+        minifier(MinifyOptions {
+            mangle: Some(properties(true)),
+            ..Default::default()
+        }),
+        "x['_b'] = x._c;",
+        "x['_b'] = x.a;"
+    );
+}
+
+#[test]
+fn properties_name_cache() {
+    let mut cache = NameCache::default();
+    cache.props.insert("_x".into(), "b".into());
+    let cache = Arc::new(Mutex::new(cache));
+
+    test_transform!(
+        mangler_with_cache(properties(false), cache.clone()),
+        "o._x = o._y;",
+        "o.b = o.a;"
+    );
+
+    let props = &cache.lock().unwrap().props;
+    assert_eq!(props["_x"], "b");
+    assert_eq!(props["_y"], "a");
+}
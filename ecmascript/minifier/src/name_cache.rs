@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Names chosen by the mangler, shared by modules minified separately.
+///
+/// Chunks of a build are loaded into the same global scope, so a global
+/// declared by a chunk should be renamed in the same way by the chunks
+/// referencing it. Pass the cache to `mangler_with_cache` or
+/// `Minifier::with_name_cache` for each chunk, and store it with serde to reuse
+/// it in the next build. Names stored before mangling, like a rename map of
+/// properties used by another build, are reused.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NameCache {
+    /// New names of top-level bindings, which are used only if `top_level` is
+    /// set.
+    pub vars: BTreeMap<String, String>,
+
+    /// New names of properties. Templates rendered by servers can read this to
+    /// access renamed properties.
+    pub props: BTreeMap<String, String>,
+}
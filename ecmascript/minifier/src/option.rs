@@ -0,0 +1,488 @@
+//! Options in the shape of terser and uglify-js.
+//!
+//! `compress` and `mangle` are `true`, `false` or an object. Options of terser
+//! which are not implemented are accepted and ignored, so existing configs
+//! can be used as-is.
+
+use regex::Regex;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MinifyOptions {
+    /// `None` disables the compressor.
+    #[serde(with = "bool_or_object")]
+    pub compress: Option<CompressOptions>,
+
+    /// `None` disables the mangler.
+    #[serde(with = "bool_or_object")]
+    pub mangle: Option<MangleOptions>,
+}
+
+impl Default for MinifyOptions {
+    fn default() -> Self {
+        MinifyOptions {
+            compress: Some(Default::default()),
+            mangle: Some(Default::default()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CompressOptions {
+    /// Maximum number of passes. `0` means until the module stops changing.
+    pub passes: usize,
+
+    /// Optimize `if` statements.
+    pub conditionals: bool,
+
+    /// Inline functions which are called only once, and IIFEs.
+    pub inline: bool,
+
+    /// Remove unreachable code.
+    pub dead_code: bool,
+
+    /// Evaluate calls to built-in functions with constant arguments.
+    pub evaluate: bool,
+
+    /// Drop expressions whose values are unused and which don't have side
+    /// effects.
+    pub side_effects: bool,
+
+    /// Drop unreferenced bindings, and trailing parameters unless
+    /// `keep_fargs` is set.
+    pub unused: bool,
+
+    /// Keep unreferenced parameters, as dropping them changes `length` of
+    /// functions.
+    pub keep_fargs: bool,
+
+    /// Drop unreferenced bindings of the module.
+    #[serde(rename = "toplevel")]
+    pub top_level: bool,
+
+    /// Keep names of function expressions even if they are unreferenced.
+    pub keep_fnames: KeepNames,
+
+    /// Keep names of class expressions even if they are unreferenced.
+    pub keep_classnames: KeepNames,
+
+    /// Drop calls to `console.*`.
+    pub drop_console: bool,
+
+    /// Functions like `Math.floor` whose calls can be dropped if their values
+    /// are unused.
+    pub pure_funcs: Vec<String>,
+
+    /// Maximum number of expression statements joined into a sequence. `0`
+    /// disables joining.
+    pub sequences: usize,
+
+    /// Join consecutive `var` statements.
+    pub join_vars: bool,
+
+    /// Rewrite `a['b']` as `a.b` and unquote keys like `{ 'b': 1 }`.
+    pub properties: bool,
+
+    /// Version of ecmascript the output may use, like `5` or `2015`.
+    /// Properties like `{ a: a }` are written as `{ a }` since `2015`.
+    pub ecma: usize,
+}
+
+impl Default for CompressOptions {
+    fn default() -> Self {
+        RawCompressOptions::default().into()
+    }
+}
+
+impl<'de> Deserialize<'de> for CompressOptions {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        RawCompressOptions::deserialize(deserializer).map(From::from)
+    }
+}
+
+/// Same as terser.
+const DEFAULT_SEQUENCES: usize = 200;
+
+/// `compress` as written by users.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct RawCompressOptions {
+    /// `false` disables options which are not specified.
+    defaults: Option<bool>,
+
+    passes: Option<usize>,
+
+    conditionals: Option<bool>,
+
+    /// terser accepts levels from `0` to `3`.
+    inline: Option<BoolOrNumber>,
+
+    dead_code: Option<bool>,
+
+    evaluate: Option<bool>,
+
+    side_effects: Option<bool>,
+
+    unused: Option<bool>,
+
+    /// Enabled by default, even if `defaults` is `false`.
+    keep_fargs: Option<bool>,
+
+    /// Disabled by default, as bindings of scripts are globals.
+    #[serde(alias = "topLevel")]
+    toplevel: Option<bool>,
+
+    keep_fnames: KeepNames,
+
+    keep_classnames: KeepNames,
+
+    drop_console: bool,
+
+    pure_funcs: Vec<String>,
+
+    sequences: Option<BoolOrNumber>,
+
+    join_vars: Option<bool>,
+
+    properties: Option<bool>,
+
+    /// terser also accepts `6` for `2015` and so on.
+    ecma: Option<usize>,
+}
+
+impl From<RawCompressOptions> for CompressOptions {
+    fn from(raw: RawCompressOptions) -> Self {
+        let default = raw.defaults.unwrap_or(true);
+
+        CompressOptions {
+            passes: raw.passes.unwrap_or(0),
+            conditionals: raw.conditionals.unwrap_or(default),
+            inline: match raw.inline {
+                Some(BoolOrNumber::Num(n)) => n != 0,
+                Some(BoolOrNumber::Bool(b)) => b,
+                None => default,
+            },
+            dead_code: raw.dead_code.unwrap_or(default),
+            evaluate: raw.evaluate.unwrap_or(default),
+            side_effects: raw.side_effects.unwrap_or(default),
+            unused: raw.unused.unwrap_or(default),
+            keep_fargs: raw.keep_fargs.unwrap_or(true),
+            top_level: raw.toplevel.unwrap_or(false),
+            keep_fnames: raw.keep_fnames,
+            keep_classnames: raw.keep_classnames,
+            drop_console: raw.drop_console,
+            pure_funcs: raw.pure_funcs,
+            sequences: match raw.sequences {
+                Some(BoolOrNumber::Num(n)) => n,
+                Some(BoolOrNumber::Bool(true)) => DEFAULT_SEQUENCES,
+                Some(BoolOrNumber::Bool(false)) => 0,
+                None if default => DEFAULT_SEQUENCES,
+                None => 0,
+            },
+            join_vars: raw.join_vars.unwrap_or(default),
+            properties: raw.properties.unwrap_or(default),
+            ecma: match raw.ecma {
+                Some(n @ 6..=11) => n + 2009,
+                Some(n) => n,
+                None => 5,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MangleOptions {
+    /// Rename top-level bindings. Exported names are kept.
+    #[serde(rename = "toplevel", alias = "topLevel")]
+    pub top_level: bool,
+
+    /// Names which are not renamed nor used as new names.
+    pub reserved: Vec<String>,
+
+    /// Keep names of functions, which are used by `Function.prototype.name`.
+    pub keep_fnames: KeepNames,
+
+    /// Keep names of classes.
+    pub keep_classnames: KeepNames,
+
+    /// Rename properties. `None` disables it, as it breaks code accessing
+    /// properties dynamically.
+    #[serde(with = "bool_or_object")]
+    pub properties: Option<PropertiesOptions>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PropertiesOptions {
+    /// Rename only properties matching this regex.
+    #[serde(with = "regex_opt")]
+    pub regex: Option<Regex>,
+
+    /// Keep properties written as string literals, like `a['b']` and
+    /// `{ 'b': 1 }`.
+    pub keep_quoted: bool,
+
+    /// Properties which are not renamed nor used as new names.
+    pub reserved: Vec<String>,
+}
+
+/// `true`, `false` or a regex matched against names.
+#[derive(Debug, Clone)]
+pub enum KeepNames {
+    All,
+    None,
+    Matching(Regex),
+}
+
+impl Default for KeepNames {
+    fn default() -> Self {
+        KeepNames::None
+    }
+}
+
+impl KeepNames {
+    pub fn keeps(&self, name: &str) -> bool {
+        match *self {
+            KeepNames::All => true,
+            KeepNames::None => false,
+            KeepNames::Matching(ref re) => re.is_match(name),
+        }
+    }
+}
+
+impl Serialize for KeepNames {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            KeepNames::All => serializer.serialize_bool(true),
+            KeepNames::None => serializer.serialize_bool(false),
+            KeepNames::Matching(ref re) => serializer.serialize_str(re.as_str()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for KeepNames {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(match BoolOrString::deserialize(deserializer)? {
+            BoolOrString::Bool(true) => KeepNames::All,
+            BoolOrString::Bool(false) => KeepNames::None,
+            BoolOrString::Str(s) => {
+                KeepNames::Matching(Regex::new(&s).map_err(de::Error::custom)?)
+            }
+        })
+    }
+}
+
+/// `true`, `false` or an object.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum BoolOrObject<T> {
+    Bool(bool),
+    Obj(T),
+}
+
+/// `true`, `false` or a number.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum BoolOrNumber {
+    Bool(bool),
+    Num(usize),
+}
+
+/// `true`, `false` or a string.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum BoolOrString {
+    Bool(bool),
+    Str(String),
+}
+
+mod bool_or_object {
+    use super::BoolOrObject;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<T, S>(v: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Serialize,
+        S: Serializer,
+    {
+        match *v {
+            Some(ref v) => v.serialize(serializer),
+            None => serializer.serialize_bool(false),
+        }
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        T: Default + Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Ok(match BoolOrObject::deserialize(deserializer)? {
+            BoolOrObject::Bool(true) => Some(T::default()),
+            BoolOrObject::Bool(false) => None,
+            BoolOrObject::Obj(v) => Some(v),
+        })
+    }
+}
+
+mod regex_opt {
+    use regex::Regex;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(v: &Option<Regex>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *v {
+            Some(ref re) => serializer.serialize_str(re.as_str()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Regex>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match Option::<String>::deserialize(deserializer)? {
+            Some(s) => Regex::new(&s).map(Some).map_err(de::Error::custom),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> MinifyOptions {
+        serde_json::from_str(s).expect("failed to parse options")
+    }
+
+    #[test]
+    fn bool_or_object() {
+        let options = parse(r#"{ "compress": false, "mangle": true }"#);
+        assert!(options.compress.is_none());
+        assert!(options.mangle.is_some());
+
+        let options = parse(r#"{ "compress": { "passes": 2 } }"#);
+        assert_eq!(options.compress.unwrap().passes, 2);
+        assert!(options.mangle.is_some());
+    }
+
+    #[test]
+    fn defaults() {
+        let c = parse(r#"{ "compress": { "defaults": false, "dead_code": true } }"#)
+            .compress
+            .unwrap();
+        assert!(c.dead_code);
+        assert!(!c.conditionals);
+        assert!(!c.side_effects);
+        assert!(!c.unused);
+        assert!(!c.top_level);
+        assert!(c.keep_fargs);
+
+        let c = parse(r#"{ "compress": { "keep_fargs": false } }"#).compress.unwrap();
+        assert!(!c.keep_fargs);
+    }
+
+    #[test]
+    fn pure_funcs() {
+        let c = parse(r#"{ "compress": { "drop_console": true, "pure_funcs": ["Math.floor"] } }"#)
+            .compress
+            .unwrap();
+        assert!(c.drop_console);
+        assert_eq!(c.pure_funcs, vec!["Math.floor".to_string()]);
+
+        let c = parse(r#"{ "compress": {} }"#).compress.unwrap();
+        assert!(!c.drop_console);
+        assert!(c.pure_funcs.is_empty());
+    }
+
+    #[test]
+    fn sequences() {
+        let c = |s: &str| parse(s).compress.unwrap();
+        assert_eq!(c(r#"{ "compress": {} }"#).sequences, 200);
+        assert_eq!(c(r#"{ "compress": { "sequences": false } }"#).sequences, 0);
+        assert_eq!(c(r#"{ "compress": { "sequences": 3 } }"#).sequences, 3);
+        assert_eq!(
+            c(r#"{ "compress": { "defaults": false, "sequences": true } }"#).sequences,
+            200
+        );
+    }
+
+    #[test]
+    fn ecma() {
+        let c = |s: &str| parse(s).compress.unwrap();
+        assert_eq!(c(r#"{ "compress": {} }"#).ecma, 5);
+        assert_eq!(c(r#"{ "compress": { "ecma": 6 } }"#).ecma, 2015);
+        assert_eq!(c(r#"{ "compress": { "ecma": 2017 } }"#).ecma, 2017);
+        assert!(c(r#"{ "compress": {} }"#).properties);
+        assert!(!c(r#"{ "compress": { "defaults": false } }"#).properties);
+    }
+
+    #[test]
+    fn mangle() {
+        let m = parse(r#"{ "mangle": { "toplevel": true, "reserved": ["$"] } }"#)
+            .mangle
+            .unwrap();
+        assert!(m.top_level);
+        assert_eq!(m.reserved, vec!["$".to_string()]);
+
+        assert!(
+            parse(r#"{ "mangle": { "topLevel": true } }"#)
+                .mangle
+                .unwrap()
+                .top_level
+        );
+        assert!(!parse(r#"{ "mangle": {} }"#).mangle.unwrap().top_level);
+    }
+
+    #[test]
+    fn keep_names() {
+        let m = parse(r#"{ "mangle": { "keep_fnames": true, "keep_classnames": "^Foo" } }"#)
+            .mangle
+            .unwrap();
+        assert!(m.keep_fnames.keeps("a"));
+        assert!(m.keep_classnames.keeps("FooBar"));
+        assert!(!m.keep_classnames.keeps("Bar"));
+
+        let invalid = r#"{ "mangle": { "keep_fnames": "(" } }"#;
+        assert!(serde_json::from_str::<MinifyOptions>(invalid).is_err());
+    }
+
+    #[test]
+    fn properties() {
+        let m = |s: &str| parse(s).mangle.unwrap();
+        assert!(m(r#"{ "mangle": {} }"#).properties.is_none());
+        assert!(m(r#"{ "mangle": { "properties": true } }"#).properties.is_some());
+
+        let p = m(r#"{ "mangle": { "properties": { "regex": "^_", "keep_quoted": true } } }"#)
+            .properties
+            .unwrap();
+        assert!(p.regex.unwrap().is_match("_a"));
+        assert!(p.keep_quoted);
+    }
+
+    #[test]
+    fn unknown_options() {
+        let options = parse(
+            r#"{
+                "ecma": 5,
+                "compress": { "pure_getters": true, "unsafe_math": true },
+                "mangle": { "safari10": true },
+                "output": { "comments": false }
+            }"#,
+        );
+        assert!(options.compress.is_some());
+    }
+}
@@ -0,0 +1,78 @@
+use ast::*;
+use serde::Serialize;
+use swc_common::{Visit, VisitWith};
+
+/// Statistics recorded by the compressor, which tell how much each rule
+/// contributes to the output.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct Stats {
+    /// Number of passes run.
+    pub passes: usize,
+
+    /// Number of expressions and statements before compression.
+    pub nodes_before: usize,
+
+    /// Number of expressions and statements after compression.
+    pub nodes_after: usize,
+
+    /// Rules in the order they first ran.
+    pub rules: Vec<RuleStats>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleStats {
+    pub rule: &'static str,
+
+    /// Number of passes in which the rule changed the module.
+    pub changes: usize,
+
+    /// Number of expressions and statements removed. Negative if the rule
+    /// creates more nodes than it removes, like `inline`.
+    pub nodes_removed: isize,
+}
+
+impl Stats {
+    /// Records a run of `rule`, which changed `before` into `after`.
+    pub(crate) fn record(&mut self, rule: &'static str, before: &Module, after: &Module) {
+        let pos = match self.rules.iter().position(|r| r.rule == rule) {
+            Some(pos) => pos,
+            None => {
+                self.rules.push(RuleStats {
+                    rule,
+                    changes: 0,
+                    nodes_removed: 0,
+                });
+                self.rules.len() - 1
+            }
+        };
+
+        let r = &mut self.rules[pos];
+        if before != after {
+            r.changes += 1;
+        }
+        r.nodes_removed += count_nodes(before) as isize - count_nodes(after) as isize;
+    }
+}
+
+/// Counts expressions and statements.
+pub(crate) fn count_nodes(module: &Module) -> usize {
+    let mut counter = Counter(0);
+    module.visit_with(&mut counter);
+    counter.0
+}
+
+struct Counter(usize);
+
+impl Visit<Expr> for Counter {
+    fn visit(&mut self, e: &Expr) {
+        self.0 += 1;
+        e.visit_children(self)
+    }
+}
+
+impl Visit<Stmt> for Counter {
+    fn visit(&mut self, s: &Stmt) {
+        self.0 += 1;
+        s.visit_children(self)
+    }
+}
@@ -0,0 +1,21 @@
+pub(crate) use testing::Tester;
+
+#[cfg(test)]
+macro_rules! test_transform {
+    ($tr:expr, $input:expr, $expected:expr) => {{
+        crate::tests::Tester::run(Default::default(), |tester| {
+            tester.test_transform(::swc_ecma_transforms::fixer, $tr, $input, $expected, false)
+        });
+    }};
+}
+
+/// Test transformation.
+#[cfg(test)]
+macro_rules! test {
+    ($tr:expr, $test_name:ident, $input:expr, $expected:expr) => {
+        #[test]
+        fn $test_name() {
+            test_transform!($tr, $input, $expected)
+        }
+    };
+}
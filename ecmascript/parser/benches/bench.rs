@@ -6,7 +6,7 @@ extern crate test;
 extern crate testing;
 
 use swc_common::FileName;
-use swc_ecma_parser::{Parser, Session, SourceFileInput};
+use swc_ecma_parser::{Parser, Session, SourceFileInput, Syntax};
 use test::Bencher;
 
 /// Copied from ratel-rust
@@ -91,8 +91,8 @@ fn bench_parse_module(b: &mut Bencher) {
             Session {
                 logger: &logger,
                 handler: &handler,
-                cfg: Default::default(),
             },
+            Syntax::default(),
             SourceFileInput::from(&*fm),
         );
 
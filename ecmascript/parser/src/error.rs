@@ -1,8 +1,5 @@
 use self::SyntaxError::*;
-use std::{
-    borrow::Cow,
-    fmt::{self, Debug, Formatter},
-};
+use std::borrow::Cow;
 use swc_atoms::JsWord;
 use swc_common::{
     errors::{DiagnosticBuilder, Handler},
@@ -10,40 +7,40 @@ use swc_common::{
 };
 use token::Token;
 
-#[derive(Copy, Clone)]
-pub(crate) struct Eof<'a> {
-    pub last: Span,
-    pub handler: &'a Handler,
-}
-
-impl<'a> From<Eof<'a>> for DiagnosticBuilder<'a> {
-    fn from(Eof { handler, last }: Eof<'a>) -> Self {
-        handler.error("Unexpected eof").span(last)
-    }
-}
-
-impl<'a> Debug for Eof<'a> {
-    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        Debug::fmt("<eof>", f)
-    }
-}
-
 pub(crate) struct ErrorToDiag<'a> {
     pub handler: &'a Handler,
     pub span: Span,
     pub error: SyntaxError,
 }
 
+/// A syntax error, which is emitted to the handler by the parser.
 #[derive(Debug, Clone, PartialEq)]
-pub(crate) struct Error {
-    pub span: Span,
-    pub error: SyntaxError,
+pub struct Error {
+    pub(crate) span: Span,
+    pub(crate) error: SyntaxError,
+}
+
+impl Error {
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    /// Creates a diagnostic which reports this error to `handler`.
+    pub fn into_diagnostic(self, handler: &Handler) -> DiagnosticBuilder {
+        ErrorToDiag {
+            handler,
+            span: self.span,
+            error: self.error,
+        }
+        .into()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) enum SyntaxError {
     LegacyDecimal,
     LegacyOctal,
+    LegacyOctalBigInt,
     InvalidIdentChar,
     NonUtf8Char {
         val: u32,
@@ -59,6 +56,9 @@ pub(crate) enum SyntaxError {
         word: JsWord,
     },
     UnterminatedRegxp,
+    UnknownRegExpFlag(char),
+    DuplicateRegExpFlag(char),
+    EscapeInRegExpFlags,
     UnterminatedTpl,
     IdentAfterNum,
     UnexpectedChar {
@@ -88,6 +88,7 @@ pub(crate) enum SyntaxError {
 
     /// Unexpected token
     Unexpected,
+    Eof,
     Expected(&'static Token),
     ExpectedSemiForExprStmt {
         expr: Span,
@@ -113,6 +114,12 @@ pub(crate) enum SyntaxError {
     AsyncGenerator,
     NonTopLevelImportExport,
     ImportExportInScript,
+    ImportMetaInScript,
+    /// `import()` or `import(a, b)`
+    DynImportArgs,
+    OptChainInNew,
+    TaggedTplInOptChain,
+    NullishMixedWithLogical,
     PatVarWithoutInit,
     WithInStrict,
     ReturnNotAllowed,
@@ -122,6 +129,43 @@ pub(crate) enum SyntaxError {
     YieldParamInGen,
 
     AwaitForStmt,
+
+    UnterminatedJSXContents,
+    /// Name of the opening tag.
+    JSXExpectedClosingTag {
+        tag: JsWord,
+    },
+    JSXExpectedClosingTagForLtGt,
+    /// `<a b={} />`
+    EmptyJSXAttr,
+    InvalidJSXAttrValue,
+    /// `<a></a><b></b>`
+    AdjacentJSXElements,
+
+    /// `readonly a(): void`
+    TsReadonlyMethod,
+    /// `(a: T)` which is not followed by `=>`
+    TsTypeAnnInParenExpr,
+
+    /// `@dec;` or `@dec export default 1`
+    TrailingDecorator,
+    /// `export @dec class Foo {}` with legacy decorators
+    DecoratorAfterExport,
+    /// `@a export @b class Foo {}`
+    DecoratorsBeforeAndAfterExport,
+    /// `constructor(@dec a) {}` without legacy decorators
+    DecoratorOnParam,
+
+    /// `#a` outside of class bodies
+    PrivateNameOutsideClass,
+    /// `# a`
+    SpaceAfterHash,
+    /// `#a` which is not followed by `in`
+    LonePrivateName,
+    /// `#constructor() {}`
+    PrivateConstructor,
+    /// `delete this.#a`
+    DeletePrivateName,
 }
 
 impl<'a> From<ErrorToDiag<'a>> for Error {
@@ -140,6 +184,7 @@ impl<'a> From<ErrorToDiag<'a>> for DiagnosticBuilder<'a> {
         let msg: Cow<'static, _> = match e.error {
             LegacyDecimal => "Legacy decimal escape is not permitted in strict mode".into(),
             LegacyOctal => "Legacy octal escape is not permitted in strict mode".into(),
+            LegacyOctalBigInt => "A bigint literal cannot start with `0` unless it is `0n`".into(),
             InvalidIdentChar => "Invalid character in identifier".into(),
             NonUtf8Char { val } => format!("Not an utf-8 character: {}", val).into(),
             ExpectedDigit { radix } => format!(
@@ -160,6 +205,9 @@ impl<'a> From<ErrorToDiag<'a>> for DiagnosticBuilder<'a> {
                 format!("Unexpected escape sequence in reserved word: {}", word).into()
             }
             UnterminatedRegxp => "Unterminated regexp literal".into(),
+            UnknownRegExpFlag(c) => format!("Unknown regexp flag: {}", c).into(),
+            DuplicateRegExpFlag(c) => format!("Duplicate regexp flag: {}", c).into(),
+            EscapeInRegExpFlags => "Regexp flags cannot contain escapes".into(),
             UnterminatedTpl => "Unterminated template".into(),
             IdentAfterNum => "Identifier cannot follow number".into(),
             UnexpectedChar { c } => format!("Unexpected character {:?}", c).into(),
@@ -181,6 +229,7 @@ impl<'a> From<ErrorToDiag<'a>> for DiagnosticBuilder<'a> {
             LineBreakInThrow => "LineBreak cannot follow 'throw'".into(),
             LineBreakBeforeArrow => "Unexpected line break between arrow head and arrow".into(),
             Unexpected => "Unexpected token".into(),
+            Eof => "Unexpected eof".into(),
             Expected(token) => format!("Expected {:?}", token).into(),
             ExpectedSemiForExprStmt { .. } => "Expected ';', '}' or <eof>".into(),
 
@@ -208,6 +257,13 @@ impl<'a> From<ErrorToDiag<'a>> for DiagnosticBuilder<'a> {
             ImportExportInScript => {
                 "'import', and 'export' cannot be used outside of module code".into()
             }
+            ImportMetaInScript => "'import.meta' cannot be used outside of module code".into(),
+            DynImportArgs => "'import()' requires exactly one argument".into(),
+            OptChainInNew => "Optional chaining cannot appear in the callee of new".into(),
+            TaggedTplInOptChain => "Tagged template cannot be used in optional chain".into(),
+            NullishMixedWithLogical => {
+                "'??' cannot be mixed with '||' or '&&' without parentheses".into()
+            }
 
             PatVarWithoutInit => "Destructuring bindings require initializers".into(),
             WithInStrict => "With statement are not allowed in strict mode".into(),
@@ -217,6 +273,43 @@ impl<'a> From<ErrorToDiag<'a>> for DiagnosticBuilder<'a> {
             LabelledGenerator => "Generator cannot be labelled".into(),
             YieldParamInGen => "'yield' cannot be used as a parameter within generator".into(),
             AwaitForStmt => "for await syntax is valid only for for-of statement".into(),
+
+            UnterminatedJSXContents => "Unterminated JSX contents".into(),
+            JSXExpectedClosingTag { ref tag } => {
+                format!("Expected corresponding JSX closing tag for <{}>", tag).into()
+            }
+            JSXExpectedClosingTagForLtGt => "Expected corresponding JSX closing tag for <>".into(),
+            EmptyJSXAttr => "JSX attributes must only be assigned a non-empty expression".into(),
+            InvalidJSXAttrValue => {
+                "JSX value should be either an expression or a quoted JSX text".into()
+            }
+            AdjacentJSXElements => "Adjacent JSX elements must be wrapped in an enclosing tag. \
+                                    Did you want a JSX fragment <>...</>?"
+                .into(),
+
+            TsReadonlyMethod => {
+                "'readonly' modifier can only appear on a property declaration".into()
+            }
+            TsTypeAnnInParenExpr => {
+                "Type annotations are only allowed in parameters of arrow functions".into()
+            }
+
+            TrailingDecorator => "Decorators must be followed by a class or a class member".into(),
+            DecoratorAfterExport => {
+                "Legacy decorators must come before the 'export' keyword".into()
+            }
+            DecoratorsBeforeAndAfterExport => {
+                "Decorators can come either before or after 'export', but not both".into()
+            }
+            DecoratorOnParam => "Only legacy decorators can decorate parameters".into(),
+
+            PrivateNameOutsideClass => "Private names can only be used in class bodies".into(),
+            SpaceAfterHash => "Unexpected space after '#'".into(),
+            LonePrivateName => "Private names are only allowed in property accesses (`obj.#a`) \
+                                or in `in` expressions (`#a in obj`)"
+                .into(),
+            PrivateConstructor => "Classes can't have a private member named '#constructor'".into(),
+            DeletePrivateName => "Private fields can't be deleted".into(),
         };
 
         let d = e.handler.error(&msg).span(e.span);
@@ -1,140 +1,133 @@
-use super::util::CharExt;
-use std::str;
 use swc_common::{BytePos, SourceFile};
 
-/// Used inside lexer.
-pub(super) struct LexerInput<I: Input> {
-    cur: Option<(BytePos, char)>,
+/// Source code of a file, backed by a `&str`.
+#[derive(Debug, Clone)]
+pub struct StringInput<'a> {
+    src: &'a str,
+    /// Position of `src[0]`.
+    start_pos: BytePos,
+    /// Byte index of the current character in `src`.
+    idx: usize,
     last_pos: BytePos,
-    input: I,
 }
 
-impl<I: Input> LexerInput<I> {
-    pub fn new(input: I) -> Self {
-        let mut i = LexerInput {
-            last_pos: input.start_pos(),
-            cur: None,
-            input,
-        };
-        i.input.record_new_line(i.last_pos);
-        i
-    }
-
-    pub fn bump(&mut self) {
-        let is_new_line = match self.cur.take() {
-            Some((p, prev_c)) => {
-                self.last_pos = BytePos(p.0 + prev_c.len_utf8() as u32);
-                prev_c.is_line_break()
-            }
-            None => unreachable!("bump is called without knowing current character"),
-        };
-        // TODO: Handle \r\n
-
-        self.cur = self.input.next();
-        if is_new_line {
-            match self.cur {
-                Some((p, _)) => self.input.record_new_line(p),
-                None => {}
-            }
-        }
-    }
-
-    pub fn peek(&mut self) -> Option<char> {
-        self.input.peek().map(|(_, c)| c)
-    }
-
-    /// Get char at `cur + 2`.
-    pub fn peek_ahead(&mut self) -> Option<char> {
-        self.input.peek_ahead().map(|(_, c)| c)
-    }
-
-    pub fn current(&mut self) -> Option<char> {
-        match self.cur {
-            Some((_, c)) => Some(c),
-            None => {
-                let next = self.input.next();
-                self.cur = next;
-                self.cur.map(|(_, c)| c)
-            }
+impl<'a> StringInput<'a> {
+    /// `start_pos` is the position of the first character of `src`.
+    pub fn new(src: &'a str, start_pos: BytePos) -> Self {
+        StringInput {
+            src,
+            start_pos,
+            idx: 0,
+            last_pos: start_pos,
         }
     }
 
-    pub fn cur_pos(&mut self) -> BytePos {
-        self.current();
-        self.cur.map(|(p, _)| p).unwrap_or(self.last_pos)
+    fn rest(&self) -> &'a str {
+        &self.src[self.idx..]
     }
 
-    pub fn last_pos(&self) -> BytePos {
-        self.last_pos
+    fn idx_of(&self, pos: BytePos) -> usize {
+        (pos.0 - self.start_pos.0) as usize
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct SourceFileInput<'a> {
-    fm: &'a SourceFile,
-    start_pos: BytePos,
-    iter: str::CharIndices<'a>,
-}
+/// Lexing a `SourceFile` is lexing its source code.
+pub type SourceFileInput<'a> = StringInput<'a>;
 
-impl<'a> From<&'a SourceFile> for SourceFileInput<'a> {
+impl<'a> From<&'a SourceFile> for StringInput<'a> {
     fn from(fm: &'a SourceFile) -> Self {
         let src = match fm.src {
             Some(ref s) => s,
             None => unreachable!("Cannot lex SourceFile without source: {}", fm.name),
         };
 
-        SourceFileInput {
-            start_pos: fm.start_pos,
-            iter: src.char_indices(),
-            fm,
-        }
+        StringInput::new(src, fm.start_pos)
     }
 }
 
-impl<'a> Iterator for SourceFileInput<'a> {
-    type Item = (BytePos, char);
+impl<'a> Input for StringInput<'a> {
+    fn cur(&mut self) -> Option<char> {
+        self.rest().chars().next()
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        self.iter
-            .next()
-            .map(|(i, c)| (BytePos(i as u32 + self.start_pos.0), c))
+    fn peek(&mut self) -> Option<char> {
+        self.rest().chars().nth(1)
     }
-}
-impl<'a> Input for SourceFileInput<'a> {
-    fn peek(&mut self) -> Option<(BytePos, char)> {
-        self.clone().nth(0)
+
+    fn peek_ahead(&mut self) -> Option<char> {
+        self.rest().chars().nth(2)
+    }
+
+    fn bump(&mut self) {
+        let c = match self.cur() {
+            Some(c) => c,
+            None => unreachable!("bump is called without knowing current character"),
+        };
+        self.idx += c.len_utf8();
+        self.last_pos = BytePos(self.start_pos.0 + self.idx as u32);
     }
 
-    fn peek_ahead(&mut self) -> Option<(BytePos, char)> {
-        self.clone().nth(1)
+    fn cur_pos(&mut self) -> BytePos {
+        BytePos(self.start_pos.0 + self.idx as u32)
     }
-    fn uncons_while<F>(&mut self, f: F) -> Option<&str>
+
+    fn last_pos(&self) -> BytePos {
+        self.last_pos
+    }
+
+    fn slice(&mut self, start: BytePos, end: BytePos) -> &str {
+        let (lo, hi) = (self.idx_of(start), self.idx_of(end));
+        &self.src[lo..hi]
+    }
+
+    fn uncons_while<F>(&mut self, mut pred: F) -> &str
     where
         F: FnMut(char) -> bool,
     {
-        //TODO?
-        None
-    }
-    fn record_new_line(&self, pos: BytePos) {
-        // FIXME: Handling of lb in js and rust is different.
-        // self.fm.next_line(pos)
-    }
-    fn start_pos(&self) -> BytePos {
-        self.fm.start_pos
+        let rest = self.rest();
+        let len = rest
+            .char_indices()
+            .find(|&(_, c)| !pred(c))
+            .map(|(i, _)| i)
+            .unwrap_or(rest.len());
+
+        if len != 0 {
+            self.idx += len;
+            self.last_pos = BytePos(self.start_pos.0 + self.idx as u32);
+        }
+        &rest[..len]
     }
 }
 
-pub trait Input: Iterator<Item = (BytePos, char)> {
-    fn peek(&mut self) -> Option<(BytePos, char)>;
+/// Source code consumed by the lexer.
+///
+/// Tokens are sliced from the input instead of being collected char by char,
+/// so implementors should be able to return a `&str` of any range they
+/// have passed.
+pub trait Input {
+    /// Returns the current character, or `None` on eof.
+    fn cur(&mut self) -> Option<char>;
+
+    /// Returns the character after the current one.
+    fn peek(&mut self) -> Option<char>;
+
+    /// Returns the character at `cur + 2`.
+    fn peek_ahead(&mut self) -> Option<char>;
+
+    /// Moves to the next character. This should not be called on eof.
+    fn bump(&mut self);
+
+    /// Returns the start of the current character, or the end of the input.
+    fn cur_pos(&mut self) -> BytePos;
 
-    fn peek_ahead(&mut self) -> Option<(BytePos, char)>;
-    fn record_new_line(&self, _pos: BytePos) {}
+    /// Returns the end of the previous character.
+    fn last_pos(&self) -> BytePos;
 
-    fn start_pos(&self) -> BytePos;
+    /// Returns the source code in `start..end`, which is already consumed.
+    fn slice(&mut self, start: BytePos, end: BytePos) -> &str;
 
-    ///Takes items from stream, testing each one with predicate. returns the
-    /// range of items which passed predicate.
-    fn uncons_while<F>(&mut self, f: F) -> Option<&str>
+    /// Takes characters while `pred` returns true, and returns them.
+    fn uncons_while<F>(&mut self, pred: F) -> &str
     where
         F: FnMut(char) -> bool;
 }
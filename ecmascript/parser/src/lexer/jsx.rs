@@ -0,0 +1,408 @@
+//! JSX lexing, ported from the jsx plugin of babylon.
+
+use super::{input::Input, util::CharExt, LexResult, Lexer};
+use error::SyntaxError;
+use std::char;
+use token::*;
+
+impl<'a, I: Input> Lexer<'a, I> {
+    /// Reads a token in children of a JSX element.
+    pub(super) fn read_jsx_token(&mut self) -> LexResult<Option<Token>> {
+        let start = self.cur_pos();
+        let mut value = String::new();
+
+        loop {
+            let c = match self.cur() {
+                Some(c) => c,
+                None => self.error(start, SyntaxError::UnterminatedJSXContents)?,
+            };
+
+            match c {
+                '<' | '{' => {
+                    let end = self.cur_pos();
+                    if end != start {
+                        return Ok(Some(Token::JSXText {
+                            value: value.into(),
+                            raw: self.input.slice(start, end).into(),
+                        }));
+                    }
+
+                    if c == '<' && self.state.is_expr_allowed {
+                        self.bump();
+                        return Ok(Some(Token::JSXTagStart));
+                    }
+                    return self.read_token();
+                }
+                '&' => {
+                    value.push_str(&self.read_jsx_entity());
+                }
+                '\r' if self.peek() == Some('\n') => {
+                    self.bump();
+                    self.bump();
+                    value.push('\n');
+                }
+                _ => {
+                    self.bump();
+                    value.push(c);
+                }
+            }
+        }
+    }
+
+    /// Reads a token in a JSX tag, e.g. `<a b="c">` or `</a>`.
+    pub(super) fn read_jsx_tag_token(&mut self, in_opening: bool) -> LexResult<Option<Token>> {
+        let c = match self.cur() {
+            Some(c) => c,
+            None => return Ok(None),
+        };
+
+        if c.is_ident_start() {
+            return self.read_jsx_name().map(Some);
+        }
+        if c == '>' {
+            self.bump();
+            return Ok(Some(Token::JSXTagEnd));
+        }
+        if in_opening && (c == '\'' || c == '"') {
+            return self.read_jsx_str().map(Some);
+        }
+
+        self.read_token()
+    }
+
+    /// Reads an identifier which may contain `-`, e.g. `data-id`.
+    fn read_jsx_name(&mut self) -> LexResult<Token> {
+        debug_assert!(self.cur().map(|c| c.is_ident_start()).unwrap_or(false));
+
+        let name = self.input.uncons_while(|c| c.is_ident_part() || c == '-');
+
+        Ok(Token::JSXName { name: name.into() })
+    }
+
+    /// Reads an attribute value. Escapes are not allowed in JSX strings, but
+    /// entities are.
+    fn read_jsx_str(&mut self) -> LexResult<Token> {
+        let start = self.cur_pos();
+        let quote = self.cur().unwrap();
+        self.bump();
+
+        let mut out = String::new();
+        loop {
+            match self.cur() {
+                Some(c) if c == quote => {
+                    self.bump();
+                    return Ok(Token::Str {
+                        value: out.into(),
+                        has_escape: false,
+                    });
+                }
+                Some('&') => out.push_str(&self.read_jsx_entity()),
+                Some(c) => {
+                    self.bump();
+                    out.push(c);
+                }
+                None => self.error(start, SyntaxError::UnterminatedStrLit)?,
+            }
+        }
+    }
+
+    /// Reads `&amp;`, `&#38;` or `&#x26;`.
+    ///
+    /// An unknown entity is returned as is.
+    fn read_jsx_entity(&mut self) -> String {
+        debug_assert_eq!(self.cur(), Some('&'));
+        self.bump();
+
+        let mut s = String::new();
+        while let Some(c) = self.cur() {
+            // Entities are short, so give up after 10 characters like babylon.
+            if s.len() >= 10 || !(c.is_ascii_alphanumeric() || c == '#') {
+                break;
+            }
+            self.bump();
+            s.push(c);
+        }
+
+        let mut raw = format!("&{}", s);
+        if !self.is(';') {
+            return raw;
+        }
+        self.bump();
+        raw.push(';');
+
+        let decoded = if s.starts_with("#x") {
+            u32::from_str_radix(&s[2..], 16)
+                .ok()
+                .and_then(char::from_u32)
+        } else if s.starts_with('#') {
+            s[1..].parse().ok().and_then(char::from_u32)
+        } else {
+            xhtml_entity(&s)
+        };
+
+        match decoded {
+            Some(c) => c.to_string(),
+            None => raw,
+        }
+    }
+}
+
+/// Named character references of XHTML.
+fn xhtml_entity(name: &str) -> Option<char> {
+    Some(match name {
+        "Aacute" => '\u{c1}',
+        "aacute" => '\u{e1}',
+        "Acirc" => '\u{c2}',
+        "acirc" => '\u{e2}',
+        "acute" => '\u{b4}',
+        "AElig" => '\u{c6}',
+        "aelig" => '\u{e6}',
+        "Agrave" => '\u{c0}',
+        "agrave" => '\u{e0}',
+        "alefsym" => '\u{2135}',
+        "Alpha" => '\u{391}',
+        "alpha" => '\u{3b1}',
+        "amp" => '\u{26}',
+        "and" => '\u{2227}',
+        "ang" => '\u{2220}',
+        "apos" => '\u{27}',
+        "Aring" => '\u{c5}',
+        "aring" => '\u{e5}',
+        "asymp" => '\u{2248}',
+        "Atilde" => '\u{c3}',
+        "atilde" => '\u{e3}',
+        "Auml" => '\u{c4}',
+        "auml" => '\u{e4}',
+        "bdquo" => '\u{201e}',
+        "Beta" => '\u{392}',
+        "beta" => '\u{3b2}',
+        "brvbar" => '\u{a6}',
+        "bull" => '\u{2022}',
+        "cap" => '\u{2229}',
+        "Ccedil" => '\u{c7}',
+        "ccedil" => '\u{e7}',
+        "cedil" => '\u{b8}',
+        "cent" => '\u{a2}',
+        "Chi" => '\u{3a7}',
+        "chi" => '\u{3c7}',
+        "circ" => '\u{2c6}',
+        "clubs" => '\u{2663}',
+        "cong" => '\u{2245}',
+        "copy" => '\u{a9}',
+        "crarr" => '\u{21b5}',
+        "cup" => '\u{222a}',
+        "curren" => '\u{a4}',
+        "Dagger" => '\u{2021}',
+        "dagger" => '\u{2020}',
+        "dArr" => '\u{21d3}',
+        "darr" => '\u{2193}',
+        "deg" => '\u{b0}',
+        "Delta" => '\u{394}',
+        "delta" => '\u{3b4}',
+        "diams" => '\u{2666}',
+        "divide" => '\u{f7}',
+        "Eacute" => '\u{c9}',
+        "eacute" => '\u{e9}',
+        "Ecirc" => '\u{ca}',
+        "ecirc" => '\u{ea}',
+        "Egrave" => '\u{c8}',
+        "egrave" => '\u{e8}',
+        "empty" => '\u{2205}',
+        "emsp" => '\u{2003}',
+        "ensp" => '\u{2002}',
+        "Epsilon" => '\u{395}',
+        "epsilon" => '\u{3b5}',
+        "equiv" => '\u{2261}',
+        "Eta" => '\u{397}',
+        "eta" => '\u{3b7}',
+        "ETH" => '\u{d0}',
+        "eth" => '\u{f0}',
+        "Euml" => '\u{cb}',
+        "euml" => '\u{eb}',
+        "euro" => '\u{20ac}',
+        "exist" => '\u{2203}',
+        "fnof" => '\u{192}',
+        "forall" => '\u{2200}',
+        "frac12" => '\u{bd}',
+        "frac14" => '\u{bc}',
+        "frac34" => '\u{be}',
+        "frasl" => '\u{2044}',
+        "Gamma" => '\u{393}',
+        "gamma" => '\u{3b3}',
+        "ge" => '\u{2265}',
+        "gt" => '\u{3e}',
+        "hArr" => '\u{21d4}',
+        "harr" => '\u{2194}',
+        "hearts" => '\u{2665}',
+        "hellip" => '\u{2026}',
+        "Iacute" => '\u{cd}',
+        "iacute" => '\u{ed}',
+        "Icirc" => '\u{ce}',
+        "icirc" => '\u{ee}',
+        "iexcl" => '\u{a1}',
+        "Igrave" => '\u{cc}',
+        "igrave" => '\u{ec}',
+        "image" => '\u{2111}',
+        "infin" => '\u{221e}',
+        "int" => '\u{222b}',
+        "Iota" => '\u{399}',
+        "iota" => '\u{3b9}',
+        "iquest" => '\u{bf}',
+        "isin" => '\u{2208}',
+        "Iuml" => '\u{cf}',
+        "iuml" => '\u{ef}',
+        "Kappa" => '\u{39a}',
+        "kappa" => '\u{3ba}',
+        "Lambda" => '\u{39b}',
+        "lambda" => '\u{3bb}',
+        "lang" => '\u{2329}',
+        "laquo" => '\u{ab}',
+        "lArr" => '\u{21d0}',
+        "larr" => '\u{2190}',
+        "lceil" => '\u{2308}',
+        "ldquo" => '\u{201c}',
+        "le" => '\u{2264}',
+        "lfloor" => '\u{230a}',
+        "lowast" => '\u{2217}',
+        "loz" => '\u{25ca}',
+        "lrm" => '\u{200e}',
+        "lsaquo" => '\u{2039}',
+        "lsquo" => '\u{2018}',
+        "lt" => '\u{3c}',
+        "macr" => '\u{af}',
+        "mdash" => '\u{2014}',
+        "micro" => '\u{b5}',
+        "middot" => '\u{b7}',
+        "minus" => '\u{2212}',
+        "Mu" => '\u{39c}',
+        "mu" => '\u{3bc}',
+        "nabla" => '\u{2207}',
+        "nbsp" => '\u{a0}',
+        "ndash" => '\u{2013}',
+        "ne" => '\u{2260}',
+        "ni" => '\u{220b}',
+        "not" => '\u{ac}',
+        "notin" => '\u{2209}',
+        "nsub" => '\u{2284}',
+        "Ntilde" => '\u{d1}',
+        "ntilde" => '\u{f1}',
+        "Nu" => '\u{39d}',
+        "nu" => '\u{3bd}',
+        "Oacute" => '\u{d3}',
+        "oacute" => '\u{f3}',
+        "Ocirc" => '\u{d4}',
+        "ocirc" => '\u{f4}',
+        "OElig" => '\u{152}',
+        "oelig" => '\u{153}',
+        "Ograve" => '\u{d2}',
+        "ograve" => '\u{f2}',
+        "oline" => '\u{203e}',
+        "Omega" => '\u{3a9}',
+        "omega" => '\u{3c9}',
+        "Omicron" => '\u{39f}',
+        "omicron" => '\u{3bf}',
+        "oplus" => '\u{2295}',
+        "or" => '\u{2228}',
+        "ordf" => '\u{aa}',
+        "ordm" => '\u{ba}',
+        "Oslash" => '\u{d8}',
+        "oslash" => '\u{f8}',
+        "Otilde" => '\u{d5}',
+        "otilde" => '\u{f5}',
+        "otimes" => '\u{2297}',
+        "Ouml" => '\u{d6}',
+        "ouml" => '\u{f6}',
+        "para" => '\u{b6}',
+        "part" => '\u{2202}',
+        "permil" => '\u{2030}',
+        "perp" => '\u{22a5}',
+        "Phi" => '\u{3a6}',
+        "phi" => '\u{3c6}',
+        "Pi" => '\u{3a0}',
+        "pi" => '\u{3c0}',
+        "piv" => '\u{3d6}',
+        "plusmn" => '\u{b1}',
+        "pound" => '\u{a3}',
+        "Prime" => '\u{2033}',
+        "prime" => '\u{2032}',
+        "prod" => '\u{220f}',
+        "prop" => '\u{221d}',
+        "Psi" => '\u{3a8}',
+        "psi" => '\u{3c8}',
+        "quot" => '\u{22}',
+        "radic" => '\u{221a}',
+        "rang" => '\u{232a}',
+        "raquo" => '\u{bb}',
+        "rArr" => '\u{21d2}',
+        "rarr" => '\u{2192}',
+        "rceil" => '\u{2309}',
+        "rdquo" => '\u{201d}',
+        "real" => '\u{211c}',
+        "reg" => '\u{ae}',
+        "rfloor" => '\u{230b}',
+        "Rho" => '\u{3a1}',
+        "rho" => '\u{3c1}',
+        "rlm" => '\u{200f}',
+        "rsaquo" => '\u{203a}',
+        "rsquo" => '\u{2019}',
+        "sbquo" => '\u{201a}',
+        "Scaron" => '\u{160}',
+        "scaron" => '\u{161}',
+        "sdot" => '\u{22c5}',
+        "sect" => '\u{a7}',
+        "shy" => '\u{ad}',
+        "Sigma" => '\u{3a3}',
+        "sigma" => '\u{3c3}',
+        "sigmaf" => '\u{3c2}',
+        "sim" => '\u{223c}',
+        "spades" => '\u{2660}',
+        "sub" => '\u{2282}',
+        "sube" => '\u{2286}',
+        "sum" => '\u{2211}',
+        "sup" => '\u{2283}',
+        "sup1" => '\u{b9}',
+        "sup2" => '\u{b2}',
+        "sup3" => '\u{b3}',
+        "supe" => '\u{2287}',
+        "szlig" => '\u{df}',
+        "Tau" => '\u{3a4}',
+        "tau" => '\u{3c4}',
+        "there4" => '\u{2234}',
+        "Theta" => '\u{398}',
+        "theta" => '\u{3b8}',
+        "thetasym" => '\u{3d1}',
+        "thinsp" => '\u{2009}',
+        "THORN" => '\u{de}',
+        "thorn" => '\u{fe}',
+        "tilde" => '\u{2dc}',
+        "times" => '\u{d7}',
+        "trade" => '\u{2122}',
+        "Uacute" => '\u{da}',
+        "uacute" => '\u{fa}',
+        "uArr" => '\u{21d1}',
+        "uarr" => '\u{2191}',
+        "Ucirc" => '\u{db}',
+        "ucirc" => '\u{fb}',
+        "Ugrave" => '\u{d9}',
+        "ugrave" => '\u{f9}',
+        "uml" => '\u{a8}',
+        "upsih" => '\u{3d2}',
+        "Upsilon" => '\u{3a5}',
+        "upsilon" => '\u{3c5}',
+        "Uuml" => '\u{dc}',
+        "uuml" => '\u{fc}',
+        "weierp" => '\u{2118}',
+        "Xi" => '\u{39e}',
+        "xi" => '\u{3be}',
+        "Yacute" => '\u{dd}',
+        "yacute" => '\u{fd}',
+        "yen" => '\u{a5}',
+        "Yuml" => '\u{178}',
+        "yuml" => '\u{ff}',
+        "Zeta" => '\u{396}',
+        "zeta" => '\u{3b6}',
+        "zwj" => '\u{200d}',
+        "zwnj" => '\u{200c}',
+        _ => return None,
+    })
+}
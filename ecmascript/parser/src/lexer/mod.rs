@@ -5,17 +5,20 @@
 #![allow(unused_mut)]
 #![allow(unused_variables)]
 pub use self::input::Input;
-use self::{input::LexerInput, state::State, util::*};
+use self::{state::State, util::*};
 use ast::Str;
+use either::Either;
 use error::SyntaxError;
 use std::char;
 use swc_atoms::JsWord;
-use swc_common::{BytePos, Span};
+use swc_common::{BytePos, Comment, Comments, Span};
 use token::*;
 use Context;
 use Session;
+use Syntax;
 
 pub mod input;
+mod jsx;
 mod number;
 mod state;
 #[cfg(test)]
@@ -24,25 +27,71 @@ pub mod util;
 
 pub(crate) type LexResult<T> = Result<T, ::error::Error>;
 
-pub(crate) struct Lexer<'a, I: Input> {
+/// Tokenizer of ecmascript, which is an iterator of tokens.
+///
+/// Tools like syntax highlighters can use this without parsing. Like the
+/// parser does, `/` is read as a division or a regular expression depending on
+/// the previous tokens.
+pub struct Lexer<'a, I: Input> {
     session: Session<'a>,
-    pub ctx: Context,
-    input: LexerInput<I>,
+    syntax: Syntax,
+    pub(crate) ctx: Context,
+    /// Comments are recorded only if this is `Some`.
+    pub(crate) comments: Option<&'a Comments>,
+    input: I,
     state: State,
+    /// Leading comments of the token being read.
+    leading_comments_buffer: Vec<Comment>,
+    /// End of the previous token, where trailing comments are stored.
+    prev_hi: BytePos,
 }
 
 impl<'a, I: Input> Lexer<'a, I> {
-    pub fn new(session: Session<'a>, input: I) -> Self {
+    pub fn new(session: Session<'a>, syntax: Syntax, input: I) -> Self {
         Lexer {
             session,
-            input: LexerInput::new(input),
+            syntax,
+            input,
             state: Default::default(),
             ctx: Default::default(),
+            comments: None,
+            leading_comments_buffer: vec![],
+            prev_hi: BytePos(0),
         }
     }
 
+    /// Creates a lexer which records comments to `comments`.
+    pub fn with_comments(
+        session: Session<'a>,
+        syntax: Syntax,
+        input: I,
+        comments: &'a Comments,
+    ) -> Self {
+        Lexer {
+            comments: Some(comments),
+            ..Lexer::new(session, syntax, input)
+        }
+    }
+
+    pub const fn syntax(&self) -> Syntax {
+        self.syntax
+    }
+
+    /// Returns the source code of `span`, which should be already consumed.
+    pub(crate) fn src_of(&mut self, span: Span) -> JsWord {
+        self.input.slice(span.lo(), span.hi()).into()
+    }
+
+    /// Reads `#!` and the rest of the line.
+    fn read_shebang(&mut self) -> Token {
+        self.input.bump(); // '#'
+        self.input.bump(); // '!'
+        let s = self.input.uncons_while(|c| !c.is_line_break());
+        Shebang(s.into())
+    }
+
     fn read_token(&mut self) -> LexResult<Option<Token>> {
-        let c = match self.input.current() {
+        let c = match self.input.cur() {
             Some(c) => c,
             None => return Ok(None),
         };
@@ -64,7 +113,7 @@ impl<'a, I: Input> Lexer<'a, I> {
                     }
                 };
                 if '0' <= next && next <= '9' {
-                    return self.read_number(true).map(Token::Num).map(Some);
+                    return self.read_number(true).map(num_token).map(Some);
                 }
 
                 self.input.bump(); // 1st `.`
@@ -79,7 +128,27 @@ impl<'a, I: Input> Lexer<'a, I> {
                 return Ok(Some(tok!('.')));
             }
 
-            '(' | ')' | ';' | ',' | '[' | ']' | '{' | '}' | '@' | '?' => {
+            '?' if self.syntax.nullish_coalescing() && self.input.peek() == Some('?') => {
+                self.input.bump();
+                self.input.bump();
+                return Ok(Some(tok!("??")));
+            }
+
+            // `?.` followed by a digit is a conditional, like `a?.5:b`.
+            '?' if self.syntax.optional_chaining()
+                && self.input.peek() == Some('.')
+                && !self
+                    .input
+                    .peek_ahead()
+                    .map(|c| '0' <= c && c <= '9')
+                    .unwrap_or(false) =>
+            {
+                self.input.bump();
+                self.input.bump();
+                return Ok(Some(tok!("?.")));
+            }
+
+            '(' | ')' | ';' | ',' | '[' | ']' | '{' | '}' | '@' | '#' | '?' => {
                 // These tokens are emitted directly.
                 self.input.bump();
                 return Ok(Some(match c {
@@ -92,6 +161,7 @@ impl<'a, I: Input> Lexer<'a, I> {
                     '{' => LBrace,
                     '}' => RBrace,
                     '@' => At,
+                    '#' => Hash,
                     '?' => QuestionMark,
                     _ => unreachable!(),
                 }));
@@ -105,7 +175,7 @@ impl<'a, I: Input> Lexer<'a, I> {
             ':' => {
                 self.input.bump();
 
-                if self.session.cfg.fn_bind && self.input.current() == Some(':') {
+                if self.syntax.fn_bind() && self.input.cur() == Some(':') {
                     self.input.bump();
                     return Ok(Some(tok!("::")));
                 }
@@ -120,12 +190,12 @@ impl<'a, I: Input> Lexer<'a, I> {
                     Some('x') | Some('X') => 16,
                     Some('o') | Some('O') => 8,
                     Some('b') | Some('B') => 2,
-                    _ => return self.read_number(false).map(Num).map(Some),
+                    _ => return self.read_number(false).map(num_token).map(Some),
                 };
 
-                return self.read_radix_number(radix).map(Num).map(Some);
+                return self.read_radix_number(radix).map(num_token).map(Some);
             }
-            '1'...'9' => return self.read_number(false).map(Num).map(Some),
+            '1'...'9' => return self.read_number(false).map(num_token).map(Some),
 
             '"' | '\'' => return self.read_str_lit().map(Some),
 
@@ -138,13 +208,13 @@ impl<'a, I: Input> Lexer<'a, I> {
 
                 // check for **
                 if is_mul {
-                    if self.input.current() == Some('*') {
+                    if self.input.cur() == Some('*') {
                         self.input.bump();
                         token = BinOp(Exp)
                     }
                 }
 
-                if self.input.current() == Some('=') {
+                if self.input.cur() == Some('=') {
                     self.input.bump();
                     token = match token {
                         BinOp(Mul) => AssignOp(MulAssign),
@@ -163,7 +233,7 @@ impl<'a, I: Input> Lexer<'a, I> {
                 let token = if c == '&' { BitAnd } else { BitOr };
 
                 // '|=', '&='
-                if self.input.current() == Some('=') {
+                if self.input.cur() == Some('=') {
                     self.input.bump();
                     return Ok(Some(AssignOp(match token {
                         BitAnd => BitAndAssign,
@@ -173,7 +243,7 @@ impl<'a, I: Input> Lexer<'a, I> {
                 }
 
                 // '||', '&&'
-                if self.input.current() == Some(c) {
+                if self.input.cur() == Some(c) {
                     self.input.bump();
                     return Ok(Some(BinOp(match token {
                         BitAnd => LogicalAnd,
@@ -187,7 +257,7 @@ impl<'a, I: Input> Lexer<'a, I> {
             '^' => {
                 // Bitwise xor
                 self.input.bump();
-                if self.input.current() == Some('=') {
+                if self.input.cur() == Some('=') {
                     self.input.bump();
                     AssignOp(BitXorAssign)
                 } else {
@@ -199,7 +269,7 @@ impl<'a, I: Input> Lexer<'a, I> {
                 self.input.bump();
 
                 // '++', '--'
-                if self.input.current() == Some(c) {
+                if self.input.cur() == Some(c) {
                     self.input.bump();
 
                     // Handle -->
@@ -217,7 +287,7 @@ impl<'a, I: Input> Lexer<'a, I> {
                     } else {
                         MinusMinus
                     }
-                } else if self.input.current() == Some('=') {
+                } else if self.input.cur() == Some('=') {
                     self.input.bump();
                     AssignOp(if c == '+' { AddAssign } else { SubAssign })
                 } else {
@@ -225,16 +295,20 @@ impl<'a, I: Input> Lexer<'a, I> {
                 }
             }
 
+            '<' if self.syntax.jsx() && self.state.is_expr_allowed => {
+                self.input.bump();
+                return Ok(Some(Token::JSXTagStart));
+            }
             '<' | '>' => return self.read_token_lt_gt(),
 
             '!' | '=' => {
                 self.input.bump();
 
-                if self.input.current() == Some('=') {
+                if self.input.cur() == Some('=') {
                     // "=="
                     self.input.bump();
 
-                    if self.input.current() == Some('=') {
+                    if self.input.cur() == Some('=') {
                         self.input.bump();
                         if c == '!' {
                             BinOp(NotEqEq)
@@ -248,7 +322,7 @@ impl<'a, I: Input> Lexer<'a, I> {
                             BinOp(EqEq)
                         }
                     }
-                } else if c == '=' && self.input.current() == Some('>') {
+                } else if c == '=' && self.input.cur() == Some('>') {
                     // "=>"
                     self.input.bump();
 
@@ -362,6 +436,7 @@ impl<'a, I: Input> Lexer<'a, I> {
 
                 return Ok(Some(value as char));
             }
+            '8' | '9' if in_template => self.error(start, SyntaxError::InvalidStrEscape)?,
             _ => c,
         };
         self.input.bump();
@@ -436,19 +511,14 @@ impl<'a, I: Input> Lexer<'a, I> {
     /// See https://tc39.github.io/ecma262/#sec-names-and-keywords
     fn read_ident_or_keyword(&mut self) -> LexResult<Token> {
         assert!(self.cur().is_some());
-        let start = self.cur_pos();
 
         let (word, has_escape) = self.read_word_as_str()?;
 
-        // Note: ctx is store in lexer because of this error.
-        // 'await' and 'yield' may have semantic of reserved word, which means lexer
-        // should know context or parser should handle this error. Our approach to this
-        // problem is former one.
-        if has_escape && self.ctx.is_reserved_word(&word) {
-            self.error(
-                start,
-                SyntaxError::EscapeInReservedWord { word: word.into() },
-            )?
+        // Keywords with escapes are not keywords, but they are still valid as
+        // property names like `a.\u0069f`. So they are lexed as identifiers,
+        // and the parser rejects them where an identifier is expected.
+        if has_escape {
+            Ok(Word(Ident(word)))
         } else {
             Ok(Word(word.into()))
         }
@@ -456,7 +526,7 @@ impl<'a, I: Input> Lexer<'a, I> {
 
     fn may_read_word_as_str(&mut self) -> LexResult<(Option<(JsWord, bool)>)> {
         match self.cur() {
-            Some(c) if c.is_ident_start() => self.read_word_as_str().map(Some),
+            Some(c) if c == '\\' || c.is_ident_start() => self.read_word_as_str().map(Some),
             _ => Ok(None),
         }
     }
@@ -465,17 +535,21 @@ impl<'a, I: Input> Lexer<'a, I> {
     fn read_word_as_str(&mut self) -> LexResult<(JsWord, bool)> {
         assert!(self.cur().is_some());
 
+        // Words without escapes are sliced from the input.
+        let head: JsWord = self.input.uncons_while(|c| c.is_ident_part()).into();
+        if !self.is('\\') {
+            return Ok((head, false));
+        }
+
         let mut has_escape = false;
-        let mut word = String::new();
-        let mut first = true;
+        let mut first = head.is_empty();
+        let mut word = String::from(&*head);
 
         while let Some(c) = self.cur() {
             let start = self.cur_pos();
-            // TODO: optimize (cow / chunk)
             match c {
                 c if c.is_ident_part() => {
-                    self.bump();
-                    word.push(c);
+                    word.push_str(self.input.uncons_while(|c| c.is_ident_part()));
                 }
                 // unicode escape
                 '\\' => {
@@ -484,6 +558,7 @@ impl<'a, I: Input> Lexer<'a, I> {
                         self.error_span(pos_span(start), SyntaxError::ExpectedUnicodeEscape)?
                     }
                     let c = self.read_unicode_escape(start)?;
+                    has_escape = true;
                     let valid = if first {
                         c.is_ident_start()
                     } else {
@@ -559,8 +634,6 @@ impl<'a, I: Input> Lexer<'a, I> {
         let mut out = String::new();
         let mut has_escape = false;
 
-        //TODO: Optimize (Cow, Chunk)
-
         while let Some(c) = self.cur() {
             match c {
                 c if c == quote => {
@@ -575,10 +648,10 @@ impl<'a, I: Input> Lexer<'a, I> {
                     has_escape = true
                 }
                 c if c.is_line_break() => self.error(start, SyntaxError::UnterminatedStrLit)?,
-                _ => {
-                    out.push(c);
-                    self.bump();
-                }
+                _ => out.push_str(
+                    self.input
+                        .uncons_while(|c| c != quote && c != '\\' && !c.is_line_break()),
+                ),
             }
         }
 
@@ -592,8 +665,6 @@ impl<'a, I: Input> Lexer<'a, I> {
         self.bump();
 
         let (mut escaped, mut in_class) = (false, false);
-        // TODO: Optimize (chunk, cow)
-        let mut content = String::new();
         let content_start = self.cur_pos();
 
         while let Some(c) = self.cur() {
@@ -616,9 +687,10 @@ impl<'a, I: Input> Lexer<'a, I> {
                 escaped = c == '\\';
             }
             self.bump();
-            content.push(c);
         }
-        let content_span = Span::new(content_start, self.cur_pos(), Default::default());
+        let content_end = self.cur_pos();
+        let content_span = Span::new(content_start, content_end, Default::default());
+        let content: JsWord = self.input.slice(content_start, content_end).into();
 
         // input is terminated without following `/`
         if !self.is('/') {
@@ -627,34 +699,59 @@ impl<'a, I: Input> Lexer<'a, I> {
 
         self.bump(); // '/'
 
-        // Spec says "It is a Syntax Error if IdentifierPart contains a Unicode escape
-        // sequence." TODO: check for escape
-
-        // Need to use `read_word` because '\uXXXX' sequences are allowed
-        // here (don't ask).
+        // Flags are read as a word, so that escapes and other characters of
+        // identifiers are not lexed as the next token.
         let flags_start = self.cur_pos();
         let flags = self.may_read_word_as_str()?.map(|(value, has_escape)| Str {
             span: Span::new(flags_start, self.cur_pos(), Default::default()),
             value,
             has_escape,
+            raw: None,
         });
+        if let Some(ref flags) = flags {
+            self.validate_regexp_flags(flags)?;
+        }
 
         Ok(Regex(
             Str {
                 span: content_span,
-                value: content.into(),
+                value: content,
                 // TODO
                 has_escape: false,
+                raw: None,
             },
             flags,
         ))
     }
 
+    /// Flags of es2018 are allowed, and each of them can be used only once.
+    fn validate_regexp_flags(&mut self, flags: &Str) -> LexResult<()> {
+        // Spec says "It is a Syntax Error if IdentifierPart contains a Unicode escape
+        // sequence."
+        if flags.has_escape {
+            self.error_span(flags.span, SyntaxError::EscapeInRegExpFlags)?
+        }
+
+        let mut seen = String::with_capacity(flags.value.len());
+        for c in flags.value.chars() {
+            match c {
+                'g' | 'i' | 'm' | 's' | 'u' | 'y' => {}
+                _ => self.error_span(flags.span, SyntaxError::UnknownRegExpFlag(c))?,
+            }
+            if seen.contains(c) {
+                self.error_span(flags.span, SyntaxError::DuplicateRegExpFlag(c))?
+            }
+            seen.push(c);
+        }
+
+        Ok(())
+    }
+
     fn read_tmpl_token(&mut self, start_of_tpl: BytePos) -> LexResult<Token> {
         let start = self.cur_pos();
 
-        // TODO: Optimize
         let mut out = String::new();
+        let mut cooked_err = None;
 
         while let Some(c) = self.cur() {
             if c == '`' || (c == '$' && self.peek() == Some('{')) {
@@ -669,13 +766,29 @@ impl<'a, I: Input> Lexer<'a, I> {
                     }
                 }
 
-                // TODO: Handle error
-                return Ok(Template(out));
+                let raw = self
+                    .input
+                    .slice(start, self.cur_pos())
+                    .replace("\r\n", "\n")
+                    .replace('\r', "\n");
+                let cooked = match cooked_err {
+                    Some(err) => Err(err),
+                    None => Ok(out),
+                };
+                return Ok(Template { raw, cooked });
             }
 
             if c == '\\' {
-                let ch = self.read_escaped_char(true)?;
-                out.extend(ch);
+                // The rest of an invalid escape is read as text, as only the raw
+                // text is used.
+                match self.read_escaped_char(true) {
+                    Ok(ch) => out.extend(ch),
+                    Err(err) => {
+                        if cooked_err.is_none() {
+                            cooked_err = Some(err);
+                        }
+                    }
+                }
             } else if c.is_line_break() {
                 self.state.had_line_break = true;
                 let c = if c == '\r' && self.peek() == Some('\n') {
@@ -686,9 +799,14 @@ impl<'a, I: Input> Lexer<'a, I> {
                 };
                 self.bump();
                 out.push(c);
-            } else {
+            } else if c == '$' {
                 self.bump();
                 out.push(c);
+            } else {
+                out.push_str(
+                    self.input
+                        .uncons_while(|c| c != '`' && c != '$' && c != '\\' && !c.is_line_break()),
+                );
             }
         }
 
@@ -703,3 +821,10 @@ impl<'a, I: Input> Lexer<'a, I> {
 fn pos_span(p: BytePos) -> Span {
     Span::new(p, p, Default::default())
 }
+
+fn num_token(v: Either<f64, JsWord>) -> Token {
+    match v {
+        Either::Left(value) => Num(value),
+        Either::Right(digits) => BigInt(digits),
+    }
+}
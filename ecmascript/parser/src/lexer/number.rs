@@ -4,12 +4,15 @@
 //! See https://tc39.github.io/ecma262/#sec-literals-numeric-literals
 
 use super::*;
+use either::Either;
 use error::SyntaxError;
 use std::fmt::Display;
 
 impl<'a, I: Input> Lexer<'a, I> {
-    /// Reads an integer, octal integer, or floating-point number
-    pub(super) fn read_number(&mut self, starts_with_dot: bool) -> LexResult<f64> {
+    /// Reads an integer, octal integer, or floating-point number.
+    ///
+    /// Returns the digits of a bigint literal like `10n` as `Right`.
+    pub(super) fn read_number(&mut self, starts_with_dot: bool) -> LexResult<Either<f64, JsWord>> {
         assert!(self.cur().is_some());
         if starts_with_dot {
             debug_assert_eq!(
@@ -28,6 +31,9 @@ impl<'a, I: Input> Lexer<'a, I> {
         } else {
             // Use read_number_no_dot to support long numbers.
             let val = self.read_number_no_dot(10)?;
+            if self.cur() == Some('n') {
+                return self.read_big_int_suffix(start);
+            }
             if starts_with_zero {
                 // TODO: I guess it would be okay if I don't use -ffast-math
                 // (or something like that), but needs review.
@@ -42,7 +48,7 @@ impl<'a, I: Input> Lexer<'a, I> {
                     if start.0 != self.last_pos().0 - 1 {
                         // `-1` is utf 8 length of `0`
 
-                        return self.make_legacy_octal(start, 0f64);
+                        return self.make_legacy_octal(start, 0f64).map(Either::Left);
                     }
                 } else {
                     // strict mode hates non-zero decimals starting with zero.
@@ -62,7 +68,7 @@ impl<'a, I: Input> Lexer<'a, I> {
                         let val = format!("{}", val)
                             .parse()
                             .expect("failed to parse numeric value as f64");
-                        return self.make_legacy_octal(start, val);
+                        return self.make_legacy_octal(start, val).map(Either::Left);
                     }
                 }
             }
@@ -128,10 +134,10 @@ impl<'a, I: Input> Lexer<'a, I> {
 
         self.ensure_not_ident()?;
 
-        Ok(val)
+        Ok(Either::Left(val))
     }
 
-    pub(super) fn read_radix_number(&mut self, radix: u8) -> LexResult<f64> {
+    pub(super) fn read_radix_number(&mut self, radix: u8) -> LexResult<Either<f64, JsWord>> {
         debug_assert!(
             radix == 2 || radix == 8 || radix == 16,
             "radix should be one of 2, 8, 16, but got {}",
@@ -139,13 +145,35 @@ impl<'a, I: Input> Lexer<'a, I> {
         );
         debug_assert_eq!(self.cur(), Some('0'));
 
-        let start = self.bump(); // 0
+        let start = self.cur_pos();
+        self.bump(); // 0
         self.bump(); // x
 
         let val = self.read_number_no_dot(radix)?;
+        if self.cur() == Some('n') {
+            return self.read_big_int_suffix(start);
+        }
+        self.ensure_not_ident()?;
+
+        Ok(Either::Left(val))
+    }
+
+    /// Consumes `n` and returns the digits in `start..n`, including the
+    /// radix prefix.
+    ///
+    /// `0n` is a valid bigint, but `00n` and `01n` are not.
+    fn read_big_int_suffix(&mut self, start: BytePos) -> LexResult<Either<f64, JsWord>> {
+        debug_assert_eq!(self.cur(), Some('n'));
+
+        let end = self.cur_pos();
+        let digits: JsWord = self.input.slice(start, end).into();
+        if digits.starts_with('0') && digits[1..].starts_with(|c: char| c.is_digit(10)) {
+            self.error(start, SyntaxError::LegacyOctalBigInt)?
+        }
+        self.bump(); // n
         self.ensure_not_ident()?;
 
-        Ok(val)
+        Ok(Either::Right(digits))
     }
 
     /// This can read long integers like
@@ -223,7 +251,7 @@ impl<'a, I: Input> Lexer<'a, I> {
         let mut total: Ret = Default::default();
 
         while let Some(c) = self.cur() {
-            if self.session.cfg.num_sep {
+            if self.syntax.num_sep() {
                 // let prev: char = unimplemented!("prev");
                 // let next = self.input.peek();
 
@@ -283,14 +311,26 @@ mod tests {
         F: FnOnce(&mut Lexer<SourceFileInput>) -> Ret,
     {
         ::with_test_sess(s, |sess, fm| {
-            let mut l = Lexer::new(sess, fm.into());
+            let mut l = Lexer::new(sess, Default::default(), fm.into());
             Ok(f(&mut l))
         })
         .unwrap()
     }
 
     fn num(s: &'static str) -> f64 {
-        lex(s, |l| l.read_number(s.starts_with(".")).unwrap())
+        lex(s, |l| {
+            l.read_number(s.starts_with("."))
+                .unwrap()
+                .left()
+                .expect("read_number returned a bigint")
+        })
+    }
+
+    fn big_int(s: &'static str) -> JsWord {
+        lex(s, |l| match l.next().map(|ts| ts.token) {
+            Some(BigInt(digits)) => digits,
+            tok => panic!("expected a bigint, got {:?}", tok),
+        })
     }
 
     fn int(radix: u8, s: &'static str) -> u32 {
@@ -343,10 +383,46 @@ mod tests {
     fn read_radix_number() {
         assert_eq!(
             0o73 as f64,
-            lex("0o73", |l| l.read_radix_number(8).unwrap())
+            lex("0o73", |l| l.read_radix_number(8).unwrap().left().unwrap())
         );
     }
 
+    #[test]
+    fn big_int_keeps_digits() {
+        assert_eq!(&*big_int("0n"), "0");
+        assert_eq!(
+            &*big_int("123456789012345678901234567890n"),
+            "123456789012345678901234567890"
+        );
+        assert_eq!(&*big_int("0xFFn"), "0xFF");
+        assert_eq!(&*big_int("0b101n"), "0b101");
+    }
+
+    #[test]
+    fn big_int_invalid() {
+        for case in &["01n", "00n", "1.5n", "1e3n", "1nn"] {
+            let res = panic::catch_unwind(|| {
+                ::with_test_sess(case, |sess, input| {
+                    let l = Lexer::new(sess, Default::default(), input);
+                    Ok(l.map(|ts| ts.token).collect::<Vec<_>>())
+                })
+                .unwrap()
+            });
+            match res {
+                Ok(tokens) => assert!(
+                    tokens.iter().any(|t| match *t {
+                        Token::Error(..) => true,
+                        _ => false,
+                    }),
+                    "{} should not be lexed as a bigint: {:?}",
+                    case,
+                    tokens
+                ),
+                Err(..) => {}
+            }
+        }
+    }
+
     /// Valid even on strict mode.
     const VALID_CASES: &[&str] = &[".0", "0.e-1", "0e8", ".8e1", "0.8e1", "1.18e1"];
     const INVALID_CASES_ON_STRICT: &[&str] = &["08e1", "08.1", "08.8e1", "08", "01"];
@@ -370,8 +446,8 @@ mod tests {
                 });
 
             let vec = panic::catch_unwind(|| {
-                ::with_test_sess(case, |mut sess, input| {
-                    let mut l = Lexer::new(sess, input);
+                ::with_test_sess(case, |sess, input| {
+                    let mut l = Lexer::new(sess, Default::default(), input);
                     l.ctx.strict = strict;
                     Ok(l.map(|ts| ts.token).collect::<Vec<_>>())
                 })
@@ -25,8 +25,21 @@ pub(super) struct State {
 impl<'a, I: Input> Iterator for Lexer<'a, I> {
     type Item = TokenAndSpan;
     fn next(&mut self) -> Option<Self::Item> {
-        self.state.had_line_break = self.state.is_first;
+        let is_first = self.state.is_first;
+        self.state.had_line_break = is_first;
         self.state.is_first = false;
+        self.prev_hi = self.last_pos();
+
+        // `#!` is allowed only at the start of the input, before any space.
+        if is_first && self.input.cur() == Some('#') && self.input.peek() == Some('!') {
+            let start = self.cur_pos();
+            let token = self.read_shebang();
+            return Some(TokenAndSpan {
+                token,
+                had_line_break: true,
+                span: self.span(start),
+            });
+        }
 
         // skip spaces before getting next character, if we are allowed to.
         if self.state.can_skip_space() {
@@ -54,6 +67,13 @@ impl<'a, I: Input> Iterator for Lexer<'a, I> {
         }) = self.state.context.current()
         {
             self.read_tmpl_token(start_pos_of_tpl).map(Some)
+        } else if self.syntax.jsx() {
+            match self.state.context.current() {
+                Some(Type::JSXExpr) => self.read_jsx_token(),
+                Some(Type::JSXOpeningTag) => self.read_jsx_tag_token(true),
+                Some(Type::JSXClosingTag) => self.read_jsx_tag_token(false),
+                _ => self.read_token(),
+            }
         } else {
             self.read_token()
         };
@@ -67,6 +87,12 @@ impl<'a, I: Input> Iterator for Lexer<'a, I> {
             self.state.update(&self.session.logger, start, &token)
         }
 
+        if let Some(comments) = self.comments {
+            for comment in self.leading_comments_buffer.drain(..) {
+                comments.add_leading(start, comment);
+            }
+        }
+
         token.map(|token| {
             // Attatch span to token.
             TokenAndSpan {
@@ -102,7 +128,7 @@ impl State {
 
     pub fn last_was_tpl_element(&self) -> bool {
         match self.token_type {
-            Some(Template(..)) => true,
+            Some(Template { .. }) => true,
             _ => false,
         }
     }
@@ -208,11 +234,18 @@ impl State {
                 }
 
                 tok!('{') => {
-                    let next_ctxt = if context.is_brace_block(prev, had_line_break, is_expr_allowed)
-                    {
-                        Type::BraceStmt
-                    } else {
-                        Type::BraceExpr
+                    let next_ctxt = match context.current() {
+                        // `<a b={c} />`
+                        Some(Type::JSXOpeningTag) => Type::BraceExpr,
+                        // `<a>{b}</a>`
+                        Some(Type::JSXExpr) => Type::TplQuasi,
+                        _ => {
+                            if context.is_brace_block(prev, had_line_break, is_expr_allowed) {
+                                Type::BraceStmt
+                            } else {
+                                Type::BraceExpr
+                            }
+                        }
                     };
                     context.push(logger, next_ctxt);
                     true
@@ -243,6 +276,32 @@ impl State {
                 // remains unchanged.
                 tok!("++") | tok!("--") => is_expr_allowed,
 
+                Token::JSXTagStart => {
+                    context.push(logger, Type::JSXExpr);
+                    context.push(logger, Type::JSXOpeningTag);
+                    return false;
+                }
+
+                // `</a>`
+                tok!('/') if prev == Some(Token::JSXTagStart) => {
+                    context.pop(logger);
+                    context.pop(logger);
+                    context.push(logger, Type::JSXClosingTag);
+                    return false;
+                }
+
+                Token::JSXTagEnd => {
+                    let out = context.pop(logger);
+                    // `<a />` or `</a>`
+                    if (out == Some(Type::JSXOpeningTag) && prev == Some(tok!('/')))
+                        || out == Some(Type::JSXClosingTag)
+                    {
+                        context.pop(logger);
+                        return context.current() == Some(Type::JSXExpr);
+                    }
+                    return true;
+                }
+
                 tok!('`') => {
                     // If we are in template, ` terminates template.
                     if let Some(Type::Tpl { .. }) = context.current() {
@@ -351,4 +410,11 @@ enum Type {
     },
     #[kind(is_expr)]
     FnExpr,
+    /// `<a b="c">`
+    JSXOpeningTag,
+    /// `</a>`
+    JSXClosingTag,
+    /// Children of a JSX element.
+    #[kind(is_expr, preserve_space)]
+    JSXExpr,
 }
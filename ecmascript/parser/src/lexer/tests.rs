@@ -1,6 +1,10 @@
-use super::{input::SourceFileInput, *};
+use super::{
+    input::{SourceFileInput, StringInput},
+    *,
+};
 use error::{Error, SyntaxError};
 use std::{ops::Range, str};
+use swc_common::{CommentKind, Comments};
 
 fn sp(r: Range<usize>) -> Span {
     Span::new(
@@ -15,7 +19,7 @@ where
     F: FnOnce(&mut Lexer<SourceFileInput>) -> Result<Ret, ()>,
 {
     ::with_test_sess(s, |sess, fm| {
-        let mut l = Lexer::new(sess, fm);
+        let mut l = Lexer::new(sess, Default::default(), fm);
         f(&mut l)
     })
 }
@@ -277,6 +281,7 @@ fn regexp_unary_void() {
                     value: "test".into(),
                     span: sp(6..10),
                     has_escape: false,
+                    raw: None,
                 },
                 None,
             )
@@ -293,6 +298,7 @@ fn regexp_unary_void() {
                     span: sp(7..11),
                     value: "test".into(),
                     has_escape: false,
+                    raw: None,
                 },
                 None,
             )
@@ -368,11 +374,13 @@ fn simple_regex() {
                     span: sp(5..7),
                     value: "42".into(),
                     has_escape: false,
+                    raw: None,
                 },
                 Some(Str {
                     span: sp(8..9),
                     value: "i".into(),
                     has_escape: false,
+                    raw: None,
                 }),
             )
             .span(4..9),
@@ -386,6 +394,7 @@ fn simple_regex() {
                 span: sp(1..3),
                 value: "42".into(),
                 has_escape: false,
+                raw: None,
             },
             None,
         )
@@ -413,11 +422,13 @@ fn complex_regex() {
                     span: Default::default(),
                     value: "42".into(),
                     has_escape: false,
+                    raw: None,
                 },
                 Some(Str {
                     span: Default::default(),
                     value: "i".into(),
                     has_escape: false,
+                    raw: None,
                 }),
             ),
         ],
@@ -425,6 +436,40 @@ fn complex_regex() {
     )
 }
 
+#[test]
+fn regexp_flags() {
+    let error = |s: &'static str| match lex_tokens(s).pop() {
+        Some(Token::Error(e)) => Some(e.error),
+        _ => None,
+    };
+
+    assert_eq!(error("/a/gimsuy"), None);
+    assert_eq!(error("/a/gig"), Some(SyntaxError::DuplicateRegExpFlag('g')));
+    assert_eq!(error("/a/x"), Some(SyntaxError::UnknownRegExpFlag('x')));
+    assert_eq!(error(r"/a/\u0067"), Some(SyntaxError::EscapeInRegExpFlags));
+}
+
+#[test]
+fn regexp_slash_in_class() {
+    assert_eq_ignore_span!(
+        vec![Regex(
+            Str {
+                span: Default::default(),
+                value: "[/]\\/".into(),
+                has_escape: false,
+                raw: None,
+            },
+            Some(Str {
+                span: Default::default(),
+                value: "g".into(),
+                has_escape: false,
+                raw: None,
+            }),
+        )],
+        lex_tokens(r"/[/]\//g")
+    )
+}
+
 #[test]
 fn simple_div() {
     assert_eq!(
@@ -508,6 +553,7 @@ fn after_if() {
                     span: sp(9..10),
                     value: "y".into(),
                     has_escape: false,
+                    raw: None,
                 },
                 None,
             )
@@ -570,6 +616,7 @@ fn migrated_0002() {
                     span: sp(10..12),
                     value: "42".into(),
                     has_escape: false,
+                    raw: None,
                 },
                 None,
             )
@@ -610,6 +657,7 @@ fn migrated_0004() {
                     span: sp(16..18),
                     value: "42".into(),
                     has_escape: false,
+                    raw: None,
                 },
                 None,
             )
@@ -654,6 +702,7 @@ fn migrated_0006() {
                     span: sp(4..6),
                     value: "42".into(),
                     has_escape: false,
+                    raw: None,
                 },
                 None,
             )
@@ -685,7 +734,14 @@ fn str_lit() {
 fn tpl_empty() {
     assert_eq!(
         lex_tokens(r#"``"#),
-        vec![tok!('`'), Template("".into()), tok!('`')]
+        vec![
+            tok!('`'),
+            Template {
+                raw: "".into(),
+                cooked: Ok("".into()),
+            },
+            tok!('`'),
+        ]
     )
 }
 
@@ -695,16 +751,57 @@ fn tpl() {
         lex_tokens(r#"`${a}`"#),
         vec![
             tok!('`'),
-            Template("".into()),
+            Template {
+                raw: "".into(),
+                cooked: Ok("".into()),
+            },
             tok!("${"),
             Word(Ident("a".into())),
             tok!('}'),
-            Template("".into()),
+            Template {
+                raw: "".into(),
+                cooked: Ok("".into()),
+            },
+            tok!('`'),
+        ]
+    )
+}
+
+#[test]
+fn tpl_raw_and_cooked() {
+    assert_eq!(
+        lex_tokens("`a\\n\r\nb`"),
+        vec![
+            tok!('`'),
+            Template {
+                raw: "a\\n\nb".into(),
+                cooked: Ok("a\n\nb".into()),
+            },
             tok!('`'),
         ]
     )
 }
 
+#[test]
+fn tpl_invalid_escape() {
+    let tokens = lex_tokens(r"`\unicode ${a}\01`");
+    match tokens[1] {
+        Template {
+            ref raw,
+            cooked: Err(..),
+        } => assert_eq!(raw, r"\unicode "),
+        ref t => panic!("expected a template with invalid escape, got {:?}", t),
+    }
+    match tokens[5] {
+        Template {
+            ref raw,
+            cooked: Err(..),
+        } => assert_eq!(raw, r"\01"),
+        ref t => panic!("expected a template with invalid escape, got {:?}", t),
+    }
+    assert_eq!(tokens[6], tok!('`'));
+}
+
 #[test]
 fn comment() {
     assert_eq!(
@@ -733,3 +830,71 @@ a"),
         }]
     );
 }
+
+#[test]
+fn comments() {
+    let comments = Comments::default();
+    let tokens = ::with_test_sess("/* a */ x; // b\n// c\ny", |sess, fm| {
+        let mut l = Lexer::new(sess, Default::default(), fm);
+        l.comments = Some(&comments);
+        Ok(l.collect::<Vec<_>>())
+    })
+    .unwrap();
+
+    let texts = |comments: Vec<::swc_common::Comment>| -> Vec<_> {
+        comments.into_iter().map(|c| (c.kind, c.text)).collect()
+    };
+    assert_eq!(
+        texts(comments.leading_comments(tokens[0].span.lo())),
+        vec![(CommentKind::Block, " a ".to_string())]
+    );
+    assert_eq!(
+        texts(comments.trailing_comments(tokens[1].span.hi())),
+        vec![(CommentKind::Line, " b".to_string())]
+    );
+    assert_eq!(
+        texts(comments.leading_comments(tokens[2].span.lo())),
+        vec![(CommentKind::Line, " c".to_string())]
+    );
+    assert!(!comments.has_trailing(tokens[0].span.hi()));
+}
+
+#[test]
+fn ident_with_escape_in_middle() {
+    assert_eq!(lex_tokens(r"a\u0062c"), vec![Word(Ident("abc".into()))]);
+}
+
+#[test]
+fn escaped_keyword_is_ident() {
+    assert_eq!(lex_tokens(r"\u0069f"), vec![Word(Ident("if".into()))]);
+    assert_eq!(lex_tokens(r"\u{69}\u{66}"), vec![Word(Ident("if".into()))]);
+    assert_eq!(lex_tokens("if"), vec![Word(Keyword(If))]);
+}
+
+#[test]
+fn ident_id_start() {
+    // U+309B is `ID_Start`, but not `XID_Start`.
+    assert_eq!(
+        lex_tokens("\u{309b}a\u{309b}"),
+        vec![Word(Ident("\u{309b}a\u{309b}".into()))]
+    );
+    assert_eq!(lex_tokens(r"\u309B"), vec![Word(Ident("\u{309b}".into()))]);
+}
+
+#[test]
+fn string_input() {
+    let mut input = StringInput::new("ab\u{e9}c d", BytePos(10));
+    assert_eq!(input.uncons_while(|c| c.is_alphabetic()), "ab\u{e9}c");
+    assert_eq!(input.cur_pos(), BytePos(15));
+    assert_eq!(input.last_pos(), BytePos(15));
+    assert_eq!(input.cur(), Some(' '));
+    assert_eq!(input.peek(), Some('d'));
+
+    input.bump();
+    assert_eq!(input.slice(BytePos(12), BytePos(16)), "\u{e9}c ");
+    assert_eq!(input.uncons_while(|c| c == ' '), "");
+
+    input.bump();
+    assert_eq!(input.cur(), None);
+    assert_eq!(input.cur_pos(), BytePos(17));
+}
@@ -7,7 +7,7 @@
 //! [babylon/util/identifier.js]:https://github.com/babel/babel/blob/master/packages/babylon/src/util/identifier.js
 use super::{input::Input, LexResult, Lexer};
 use error::{ErrorToDiag, SyntaxError};
-use swc_common::{BytePos, Span};
+use swc_common::{BytePos, Comment, CommentKind, Span};
 use unicode_xid::UnicodeXID;
 
 // pub const BACKSPACE: char = 8 as char;
@@ -48,7 +48,7 @@ impl<'a, I: Input> Lexer<'a, I> {
     }
 
     pub(super) fn cur(&mut self) -> Option<char> {
-        self.input.current()
+        self.input.cur()
     }
     pub(super) fn peek(&mut self) -> Option<char> {
         self.input.peek()
@@ -116,40 +116,54 @@ impl<'a, I: Input> Lexer<'a, I> {
 
     pub(super) fn skip_line_comment(&mut self, start_skip: usize) {
         let start = self.cur_pos();
+        let is_trailing = !self.state.had_line_break;
         for _ in 0..start_skip {
             self.bump();
         }
+        let text_start = self.cur_pos();
 
+        let mut end = None;
         while let Some(c) = self.cur() {
+            let pos = self.cur_pos();
             self.bump();
             if c.is_line_break() {
                 self.state.had_line_break = true;
             }
             match c {
                 '\n' | '\r' | '\u{2028}' | '\u{2029}' => {
+                    end = Some(pos);
                     break;
                 }
                 _ => {}
             }
         }
-        // TODO: push comment
+
+        // Line break is not a part of the comment.
+        let end = end.unwrap_or_else(|| self.last_pos());
+        let span = Span::new(start, end, Default::default());
+        self.store_comment(CommentKind::Line, span, is_trailing, text_start, end);
     }
 
     /// Expects current char to be '/' and next char to be '*'.
     pub(super) fn skip_block_comment(&mut self) -> LexResult<()> {
         let start = self.cur_pos();
+        let is_trailing = !self.state.had_line_break;
 
         debug_assert_eq!(self.cur(), Some('/'));
         debug_assert_eq!(self.peek(), Some('*'));
 
         self.bump();
         self.bump();
+        let text_start = self.cur_pos();
 
         let mut was_star = false;
 
         while let Some(c) = self.cur() {
             if was_star && self.eat('/') {
-                // TODO: push comment
+                let span = self.span(start);
+                // `*/` is not a part of the text.
+                let text_end = span.hi() - BytePos(2);
+                self.store_comment(CommentKind::Block, span, is_trailing, text_start, text_end);
                 return Ok(());
             }
             if c.is_line_break() {
@@ -162,8 +176,47 @@ impl<'a, I: Input> Lexer<'a, I> {
 
         self.error(start, SyntaxError::UnterminatedBlockComment)?
     }
+
+    /// Records a comment if comments are requested.
+    ///
+    /// A comment is trailing if there's no line break between the previous
+    /// token and the comment. The text in `text_start..text_end` is copied
+    /// only if it's recorded.
+    fn store_comment(
+        &mut self,
+        kind: CommentKind,
+        span: Span,
+        is_trailing: bool,
+        text_start: BytePos,
+        text_end: BytePos,
+    ) {
+        let comments = match self.comments {
+            Some(comments) => comments,
+            None => return,
+        };
+
+        let text = self.input.slice(text_start, text_end).into();
+        let comment = Comment { kind, span, text };
+        if is_trailing {
+            comments.add_trailing(self.prev_hi, comment);
+        } else {
+            self.leading_comments_buffer.push(comment);
+        }
+    }
 }
 
+/// Characters in `ID_Start` but not in `XID_Start`.
+///
+/// `XID_*` is `ID_*` modified to be closed under NFKC, which excludes these.
+/// As `ID_Start` is a subset of `ID_Continue`, they can be used anywhere in an
+/// identifier.
+const NOT_XID: &[char] = &[
+    '\u{037a}', '\u{0e33}', '\u{0eb3}', '\u{309b}', '\u{309c}', '\u{fc5e}', '\u{fc5f}',
+    '\u{fc60}', '\u{fc61}', '\u{fc62}', '\u{fc63}', '\u{fdfa}', '\u{fdfb}', '\u{fe70}',
+    '\u{fe72}', '\u{fe74}', '\u{fe76}', '\u{fe78}', '\u{fe7a}', '\u{fe7c}', '\u{fe7e}',
+    '\u{ff9e}', '\u{ff9f}',
+];
+
 /// Implemented for `char`.
 pub trait CharExt: Copy {
     fn to_char(self) -> Option<char>;
@@ -176,18 +229,23 @@ pub trait CharExt: Copy {
             Some(c) => c,
             None => return false,
         };
-        // TODO: Use Unicode ID instead of XID.
-        c == '$' || c == '_' || UnicodeXID::is_xid_start(c)
+        c == '$' || c == '_' || UnicodeXID::is_xid_start(c) || NOT_XID.contains(&c)
     }
 
     /// Test whether a given character is part of an identifier.
+    ///
+    /// https://tc39.github.io/ecma262/#prod-IdentifierPart
     fn is_ident_part(self) -> bool {
         let c = match self.to_char() {
             Some(c) => c,
             None => return false,
         };
-        // TODO: Use Unicode ID instead of XID.
-        c == '$' || c == '_' || c == '\u{200c}' || c == '\u{200d}' || UnicodeXID::is_xid_continue(c)
+        c == '$'
+            || c == '_'
+            || c == '\u{200c}'
+            || c == '\u{200d}'
+            || UnicodeXID::is_xid_continue(c)
+            || NOT_XID.contains(&c)
     }
 
     /// See https://tc39.github.io/ecma262/#sec-line-terminators
@@ -16,6 +16,11 @@
 //!   |          ^^^^^
 //! ```
 //!
+//! ## Tokenizer
+//!
+//! `Lexer` is an iterator of `token::TokenAndSpan`, and can be used without
+//! the parser.
+//!
 //! # Example
 //!
 //! ```
@@ -28,7 +33,7 @@
 //!     sync::Lrc,
 //!     FileName, FilePathMapping, SourceMap,
 //! };
-//! use swc_ecma_parser::{Parser, Session, SourceFileInput};
+//! use swc_ecma_parser::{Parser, Session, SourceFileInput, Syntax};
 //!
 //! fn main() {
 //!     swc_common::GLOBALS.set(&swc_common::Globals::new(), || {
@@ -40,7 +45,6 @@
 //!         let session = Session {
 //!             handler: &handler,
 //!             logger: &logger,
-//!             cfg: Default::default(),
 //!         };
 //!
 //!         // Real usage
@@ -53,7 +57,11 @@
 //!             "function foo() {}".into(),
 //!         );
 //!
-//!         let mut parser = Parser::new(session, SourceFileInput::from(&*fm));
+//!         let mut parser = Parser::new(
+//!             session,
+//!             Syntax::Es(Default::default()),
+//!             SourceFileInput::from(&*fm),
+//!         );
 //!
 //!         let _module = parser.parse_module().expect("failed to parser module");
 //!     });
@@ -88,7 +96,11 @@ extern crate swc_ecma_ast as ast;
 extern crate testing;
 extern crate unicode_xid;
 pub use self::{
-    lexer::input::{Input, SourceFileInput},
+    error::Error,
+    lexer::{
+        input::{Input, SourceFileInput, StringInput},
+        Lexer,
+    },
     parser::*,
 };
 use slog::Logger;
@@ -99,15 +111,157 @@ mod macros;
 mod error;
 mod lexer;
 mod parser;
-mod token;
+pub mod token;
+
+/// Syntax of a file, with experimental features enabled per file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Syntax {
+    /// Ecmascript with the given proposals.
+    Es(EsConfig),
+    /// Typescript, which is a superset of ecmascript.
+    Typescript(TsConfig),
+}
+
+impl Default for Syntax {
+    fn default() -> Self {
+        Syntax::Es(Default::default())
+    }
+}
+
+impl Syntax {
+    /// Should we parse type annotations, interfaces and so on?
+    pub fn typescript(self) -> bool {
+        match self {
+            Syntax::Typescript(..) => true,
+            Syntax::Es(..) => false,
+        }
+    }
+
+    pub fn jsx(self) -> bool {
+        match self {
+            Syntax::Es(EsConfig { jsx, .. }) => jsx,
+            Syntax::Typescript(..) => false,
+        }
+    }
+
+    pub fn num_sep(self) -> bool {
+        match self {
+            Syntax::Es(EsConfig { num_sep, .. }) => num_sep,
+            // Typescript supports them since 2.7, but the lexer doesn't yet.
+            Syntax::Typescript(..) => false,
+        }
+    }
+
+    pub fn fn_bind(self) -> bool {
+        match self {
+            Syntax::Es(EsConfig { fn_bind, .. }) => fn_bind,
+            Syntax::Typescript(..) => false,
+        }
+    }
+
+    pub fn decorators(self) -> Option<Decorators> {
+        match self {
+            Syntax::Es(EsConfig { decorators, .. }) => decorators,
+            Syntax::Typescript(TsConfig { decorators: true, .. }) => Some(Decorators::Legacy),
+            Syntax::Typescript(..) => None,
+        }
+    }
+
+    pub fn class_props(self) -> bool {
+        match self {
+            Syntax::Es(EsConfig { class_props, .. }) => class_props,
+            Syntax::Typescript(..) => true,
+        }
+    }
+
+    pub fn dynamic_import(self) -> bool {
+        match self {
+            Syntax::Es(EsConfig { dynamic_import, .. })
+            | Syntax::Typescript(TsConfig { dynamic_import, .. }) => dynamic_import,
+        }
+    }
+
+    pub fn import_meta(self) -> bool {
+        match self {
+            Syntax::Es(EsConfig { import_meta, .. }) => import_meta,
+            Syntax::Typescript(..) => false,
+        }
+    }
+
+    pub fn optional_chaining(self) -> bool {
+        match self {
+            Syntax::Es(EsConfig {
+                optional_chaining, ..
+            }) => optional_chaining,
+            Syntax::Typescript(..) => false,
+        }
+    }
 
+    pub fn nullish_coalescing(self) -> bool {
+        match self {
+            Syntax::Es(EsConfig {
+                nullish_coalescing, ..
+            }) => nullish_coalescing,
+            Syntax::Typescript(..) => false,
+        }
+    }
+}
+
+/// Proposals and extensions of ecmascript.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub struct Config {
+pub struct EsConfig {
     /// Support numeric separator.
     pub num_sep: bool,
 
     /// Support function bind expression.
     pub fn_bind: bool,
+
+    /// Support jsx.
+    pub jsx: bool,
+
+    /// Support decorators, placed according to the given rules.
+    pub decorators: Option<Decorators>,
+
+    /// Support class fields and private names like `#a`.
+    pub class_props: bool,
+
+    /// Support `import(specifier)`.
+    pub dynamic_import: bool,
+
+    /// Support `import.meta`.
+    pub import_meta: bool,
+
+    /// Support `a?.b`, `a?.[b]` and `a?.()`.
+    pub optional_chaining: bool,
+
+    /// Support `a ?? b`.
+    pub nullish_coalescing: bool,
+}
+
+/// Options for typescript syntax.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TsConfig {
+    /// Support decorators of `experimentalDecorators`.
+    pub decorators: bool,
+
+    /// Support `import(specifier)`.
+    pub dynamic_import: bool,
+}
+
+/// Where decorators can be placed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decorators {
+    /// Rules of typescript's `experimentalDecorators` and babel's legacy
+    /// decorators.
+    ///
+    /// Decorators come before `export` (`@dec export class Foo {}`), and
+    /// parameters of methods can be decorated.
+    Legacy,
+    /// Rules of the current tc39 proposal.
+    ///
+    /// Decorators come either before or after `export`, but not both, and
+    /// parameters can't be decorated.
+    Proposal,
 }
 
 /// Syntatic context.
@@ -127,11 +281,13 @@ struct Context {
     in_function: bool,
 
     in_parameters: bool,
+
+    /// If true, private names like `#a` can be used.
+    in_class: bool,
 }
 
 #[derive(Clone, Copy)]
 pub struct Session<'a> {
-    pub cfg: Config,
     pub logger: &'a Logger,
     pub handler: &'a Handler,
 }
@@ -150,7 +306,6 @@ where
             Session {
                 handler: &handler,
                 logger: &logger,
-                cfg: Default::default(),
             },
             (&*fm).into(),
         )
@@ -9,9 +9,18 @@ macro_rules! tok {
     ('?') => {
         Token::QuestionMark
     };
+    ("?.") => {
+        Token::QuestionDot
+    };
     (':') => {
         Token::Colon
     };
+    ('@') => {
+        Token::At
+    };
+    ('#') => {
+        Token::Hash
+    };
     ("::") => {
         Token::ColonColon
     };
@@ -27,6 +36,12 @@ macro_rules! tok {
     ("${") => {
         Token::DollarLBrace
     };
+    ("jsx tag start") => {
+        Token::JSXTagStart
+    };
+    ("jsx tag end") => {
+        Token::JSXTagEnd
+    };
 
     ('+') => {
         Token::BinOp(Add)
@@ -52,6 +67,15 @@ macro_rules! tok {
     ('~') => {
         Token::Tilde
     };
+    ('|') => {
+        Token::BinOp(BitOr)
+    };
+    ('&') => {
+        Token::BinOp(BitAnd)
+    };
+    ("??") => {
+        Token::BinOp(NullishCoalescing)
+    };
     ('<') => {
         Token::BinOp(Lt)
     };
@@ -119,6 +143,9 @@ macro_rules! tok {
     ("debugger") => {
         Token::Word(Keyword(Debugger))
     };
+    ("declare") => {
+        Token::Word(Word::Ident(js_word!("declare")))
+    };
     ("default") => {
         Token::Word(Keyword(Default_))
     };
@@ -131,6 +158,9 @@ macro_rules! tok {
     ("else") => {
         Token::Word(Keyword(Else))
     };
+    ("enum") => {
+        Token::Word(Word::Ident(js_word!("enum")))
+    };
     ("export") => {
         Token::Word(Keyword(Export))
     };
@@ -158,12 +188,30 @@ macro_rules! tok {
     ("in") => {
         Token::Word(Keyword(In))
     };
+    ("implements") => {
+        Token::Word(Word::Ident(js_word!("implements")))
+    };
     ("import") => {
         Token::Word(Keyword(Import))
     };
+    ("interface") => {
+        Token::Word(Word::Ident(js_word!("interface")))
+    };
+    ("keyof") => {
+        Token::Word(Word::Ident(js_word!("keyof")))
+    };
     ("let") => {
         Token::Word(Keyword(Let))
     };
+    ("meta") => {
+        Token::Word(Word::Ident(js_word!("meta")))
+    };
+    ("module") => {
+        Token::Word(Word::Ident(js_word!("module")))
+    };
+    ("namespace") => {
+        Token::Word(Word::Ident(js_word!("namespace")))
+    };
     ("new") => {
         Token::Word(Keyword(New))
     };
@@ -173,6 +221,9 @@ macro_rules! tok {
     ("of") => {
         Token::Word(Ident(js_word!("of")))
     };
+    ("readonly") => {
+        Token::Word(Word::Ident(js_word!("readonly")))
+    };
     ("return") => {
         Token::Word(Keyword(Return))
     };
@@ -200,9 +251,15 @@ macro_rules! tok {
     ("try") => {
         Token::Word(Keyword(Try))
     };
+    ("type") => {
+        Token::Word(Word::Ident(js_word!("type")))
+    };
     ("typeof") => {
         Token::Word(Keyword(TypeOf))
     };
+    ("unique") => {
+        Token::Word(Word::Ident(js_word!("unique")))
+    };
     ("var") => {
         Token::Word(Keyword(Var))
     };
@@ -1,6 +1,7 @@
 //! Parser for function expression and function declaration.
 
 use super::{ident::MaybeOptionalIdentParser, *};
+use either::Either;
 
 #[parser]
 impl<'a, I: Input> Parser<'a, I> {
@@ -25,44 +26,67 @@ impl<'a, I: Input> Parser<'a, I> {
         self.parse_fn(None)
     }
 
-    pub(super) fn parse_default_async_fn(&mut self) -> PResult<'a, ExportDefaultDecl> {
+    pub(super) fn parse_default_async_fn(&mut self) -> PResult<'a, DefaultDecl> {
         let start = cur_pos!();
         expect!("async");
         self.parse_fn(Some(start))
     }
 
-    pub(super) fn parse_default_fn(&mut self) -> PResult<'a, ExportDefaultDecl> {
+    pub(super) fn parse_default_fn(&mut self) -> PResult<'a, DefaultDecl> {
         self.parse_fn(None)
     }
 
-    pub(super) fn parse_class_decl(&mut self) -> PResult<'a, Decl> {
-        self.parse_class()
+    pub(super) fn parse_class_decl(&mut self, decorators: Vec<Decorator>) -> PResult<'a, Decl> {
+        self.parse_class(decorators)
     }
 
-    pub(super) fn parse_class_expr(&mut self) -> PResult<'a, (Box<Expr>)> {
-        self.parse_class()
+    pub(super) fn parse_class_expr(
+        &mut self,
+        decorators: Vec<Decorator>,
+    ) -> PResult<'a, (Box<Expr>)> {
+        self.parse_class(decorators)
     }
 
-    pub(super) fn parse_default_class(&mut self) -> PResult<'a, ExportDefaultDecl> {
-        self.parse_class()
+    pub(super) fn parse_default_class(
+        &mut self,
+        decorators: Vec<Decorator>,
+    ) -> PResult<'a, DefaultDecl> {
+        self.parse_class(decorators)
     }
 
-    fn parse_class<T>(&mut self) -> PResult<'a, T>
+    fn parse_class<T>(&mut self, decorators: Vec<Decorator>) -> PResult<'a, T>
     where
         T: OutputType,
         Self: MaybeOptionalIdentParser<'a, T::Ident>,
     {
         self.strict_mode().parse_with(|p| {
-            let start = cur_pos!();
+            let start = match decorators.first() {
+                Some(dec) => dec.span.lo(),
+                None => cur_pos!(),
+            };
             expect!("class");
 
             let ident = p.parse_maybe_opt_binding_ident()?;
+            let type_params = p.try_parse_ts_type_params()?;
 
             let super_class = if eat!("extends") {
                 p.parse_lhs_expr().map(Some)?
             } else {
                 None
             };
+            let super_type_params = if super_class.is_some() {
+                p.try_parse_ts_type_args()?
+            } else {
+                None
+            };
+
+            let mut implements = vec![];
+            if p.syntax().typescript() && eat!("implements") {
+                implements.push(p.parse_ts_expr_with_type_args()?);
+                while eat!(',') {
+                    implements.push(p.parse_ts_expr_with_type_args()?);
+                }
+            }
 
             expect!('{');
             let body = p.parse_class_body()?;
@@ -73,27 +97,39 @@ impl<'a, I: Input> Parser<'a, I> {
                 Class {
                     span: Span::new(start, end, Default::default()),
                     super_class,
+                    decorators,
                     body,
+                    type_params,
+                    super_type_params,
+                    implements,
                 },
             ))
         })
     }
 
-    fn parse_class_body(&mut self) -> PResult<'a, (Vec<ClassMethod>)> {
+    fn parse_class_body(&mut self) -> PResult<'a, (Vec<ClassMember>)> {
+        let ctx = Context {
+            in_class: true,
+            ..self.ctx()
+        };
+
         let mut elems = vec![];
         while !eof!() && !is!('}') {
             if eat_exact!(';') {
                 continue;
             }
 
-            elems.push(self.parse_class_element()?);
+            elems.push(self.with_ctx(ctx).parse_class_element()?);
         }
         Ok(elems)
     }
 
-    fn parse_class_element(&mut self) -> PResult<'a, ClassMethod> {
+    fn parse_class_element(&mut self) -> PResult<'a, ClassMember> {
         // ignore semi
 
+        let start = cur_pos!();
+        let decorators = self.parse_decorators()?;
+
         let static_token = {
             let start = cur_pos!();
             if eat!("static") {
@@ -103,23 +139,12 @@ impl<'a, I: Input> Parser<'a, I> {
             }
         };
 
-        let mut mtd = self.parse_method_def(static_token)?;
-
-        match mtd.key {
-            PropName::Ident(Ident {
-                sym: js_word!("constructor"),
-                ..
-            })
-            | PropName::Str(Str {
-                value: js_word!("constructor"),
-                ..
-            }) => {
-                mtd.kind = ClassMethodKind::Constructor;
-            }
-            _ => {}
+        // `static { }`
+        if static_token.is_some() && decorators.is_empty() && is!('{') {
+            return self.parse_static_block(start).map(ClassMember::StaticBlock);
         }
 
-        Ok(mtd)
+        self.parse_class_member(start, decorators, static_token)
     }
 
     fn parse_fn<T>(&mut self, start_of_async: Option<BytePos>) -> PResult<'a, T>
@@ -158,15 +183,26 @@ impl<'a, I: Input> Parser<'a, I> {
         };
 
         self.with_ctx(ctx).parse_with(|p| {
+            let type_params = p.try_parse_ts_type_params()?;
+
             expect!('(');
             let params_ctx = Context {
                 in_parameters: true,
                 ..p.ctx()
             };
-            let params = p.with_ctx(params_ctx).parse_formal_params()?;
+            let params = p.with_ctx(params_ctx).parse_params(false)?;
             expect!(')');
 
-            let body = p.parse_fn_body(async_token.is_some(), generator_token.is_some())?;
+            let return_type = p.try_parse_ts_type_ann()?;
+
+            // `declare function foo(): void;` and overloads have no body.
+            let body = if p.syntax().typescript() && !T::is_fn_expr() && !is!('{') {
+                expect!(';');
+                None
+            } else {
+                p.parse_fn_body(async_token.is_some(), generator_token.is_some())
+                    .map(Some)?
+            };
 
             Ok(T::finish_fn(
                 ident,
@@ -176,6 +212,8 @@ impl<'a, I: Input> Parser<'a, I> {
                     generator_token,
                     params,
                     body,
+                    type_params,
+                    return_type,
                 },
             ))
         })
@@ -190,7 +228,7 @@ impl<'a, I: Input> Parser<'a, I> {
         generator_token: Option<Span>,
     ) -> PResult<'a, Function>
     where
-        F: FnOnce(&mut Self) -> PResult<'a, (Vec<Pat>)>,
+        F: FnOnce(&mut Self) -> PResult<'a, (Vec<Param>)>,
     {
         let ctx = Context {
             in_async: async_token.is_some(),
@@ -198,6 +236,8 @@ impl<'a, I: Input> Parser<'a, I> {
             ..self.ctx()
         };
         self.with_ctx(ctx).parse_with(|p| {
+            let type_params = p.try_parse_ts_type_params()?;
+
             expect!('(');
 
             let arg_ctx = Context {
@@ -208,130 +248,314 @@ impl<'a, I: Input> Parser<'a, I> {
 
             expect!(')');
 
+            let return_type = p.try_parse_ts_type_ann()?;
+
             let body = p.parse_fn_body(async_token.is_some(), generator_token.is_some())?;
 
             Ok(Function {
                 span: span!(start),
                 params,
-                body,
+                body: Some(body),
                 async_token,
                 generator_token,
+                type_params,
+                return_type,
             })
         })
     }
 
-    fn parse_method_def(&mut self, static_token: Option<Span>) -> PResult<'a, ClassMethod> {
-        let is_static = static_token.is_some();
-        let start = static_token.map(|s| s.lo()).unwrap_or(cur_pos!());
-
-        if eat!('*') {
-            let span_of_gen = span!(start);
-            let key = self.parse_prop_name()?;
-            return self
-                .parse_fn_args_body(
-                    start,
-                    Parser::parse_unique_formal_params,
-                    None,
-                    Some(span_of_gen),
-                )
-                .map(|function| ClassMethod {
-                    span: span!(start),
-                    static_token,
-                    key,
-                    function,
-                    kind: ClassMethodKind::Method,
-                });
+    fn parse_class_member(
+        &mut self,
+        start: BytePos,
+        decorators: Vec<Decorator>,
+        static_token: Option<Span>,
+    ) -> PResult<'a, ClassMember> {
+        let generator_token = {
+            let start = cur_pos!();
+            if eat!('*') {
+                Some(span!(start))
+            } else {
+                None
+            }
+        };
+        if generator_token.is_some() {
+            let key = self.parse_class_member_key()?;
+            let function =
+                self.parse_fn_args_body(start, |p| p.parse_params(true), None, generator_token)?;
+            return Ok(self.finish_class_method(
+                start,
+                decorators,
+                static_token,
+                key,
+                function,
+                ClassMethodKind::Method,
+            ));
         }
 
-        // Handle static(){}
+        // `static() {}` and `static = 1;`
         if let Some(static_token) = static_token {
-            if is!('(') {
-                return self
-                    .parse_fn_args_body(start, Parser::parse_unique_formal_params, None, None)
-                    .map(|function| ClassMethod {
-                        span: span!(start),
-                        static_token: None,
-                        key: PropName::Ident(Ident {
-                            span: static_token,
-                            sym: js_word!("static"),
-                        }),
-                        function,
-                        kind: ClassMethodKind::Method,
-                    });
+            if is!('(') || (self.syntax().typescript() && is!('<')) || self.is_class_prop_end() {
+                let key = Either::Right(PropName::Ident(Ident {
+                    span: static_token,
+                    sym: js_word!("static"),
+                }));
+                return self.parse_class_method_or_prop(start, decorators, None, key);
             }
         }
 
-        let key = self.parse_prop_name()?;
+        let key = self.parse_class_member_key()?;
 
-        // Handle `a(){}` (and async(){} / get(){} / set(){})
-        if is!('(') {
-            return self
-                .parse_fn_args_body(start, Parser::parse_unique_formal_params, None, None)
-                .map(|function| ClassMethod {
+        // Handle `a(){}` and `a = 1;` (and async(){} / get(){} / set(){})
+        if is!('(') || (self.syntax().typescript() && is!('<')) || self.is_class_prop_end() {
+            return self.parse_class_method_or_prop(start, decorators, static_token, key);
+        }
+
+        // get a(){}
+        // set a(v){}
+        // async a(){}
+
+        let had_line_break = self.input.had_line_break_before_cur();
+        let modifier = match key {
+            Either::Right(PropName::Ident(ref ident)) => match ident.sym {
+                js_word!("get") => Some((ClassMethodKind::Getter, None)),
+                js_word!("set") => Some((ClassMethodKind::Setter, None)),
+                js_word!("async") if !had_line_break => {
+                    Some((ClassMethodKind::Method, Some(ident.span)))
+                }
+                _ => None,
+            },
+            _ => None,
+        };
+        let (kind, async_token) = match modifier {
+            Some(modifier) => modifier,
+            // `a \n b` has two properties.
+            None => return self.parse_class_method_or_prop(start, decorators, static_token, key),
+        };
+
+        let key = self.parse_class_member_key()?;
+        let function = match kind {
+            ClassMethodKind::Getter => self.parse_fn_args_body(start, |_| Ok(vec![]), None, None)?,
+            ClassMethodKind::Setter => self.parse_fn_args_body(
+                start,
+                |p| p.parse_param(true).map(|param| vec![param]),
+                None,
+                None,
+            )?,
+            _ => self.parse_fn_args_body(start, |p| p.parse_params(true), async_token, None)?,
+        };
+
+        Ok(self.finish_class_method(start, decorators, static_token, key, function, kind))
+    }
+
+    /// `#a` or a property name.
+    fn parse_class_member_key(&mut self) -> PResult<'a, Either<PrivateName, PropName>> {
+        if !is!('#') {
+            return self.parse_prop_name().map(Either::Right);
+        }
+
+        let name = self.parse_private_name()?;
+        if name.id.sym == js_word!("constructor") {
+            syntax_error!(name.span, SyntaxError::PrivateConstructor)
+        }
+        Ok(Either::Left(name))
+    }
+
+    /// Returns true if the current token follows the key of a property, like
+    /// `=` or `;` after `a` in `a = 1;`.
+    fn is_class_prop_end(&mut self) -> bool {
+        eof!() || is_one_of!('=', ';', '}') || (self.syntax().typescript() && is!(':'))
+    }
+
+    /// Parses the rest of a method or a property after its key.
+    fn parse_class_method_or_prop(
+        &mut self,
+        start: BytePos,
+        decorators: Vec<Decorator>,
+        static_token: Option<Span>,
+        key: Either<PrivateName, PropName>,
+    ) -> PResult<'a, ClassMember> {
+        if is!('(') || (self.syntax().typescript() && is!('<')) {
+            let function = self.parse_fn_args_body(start, |p| p.parse_params(true), None, None)?;
+            return Ok(self.finish_class_method(
+                start,
+                decorators,
+                static_token,
+                key,
+                function,
+                ClassMethodKind::Method,
+            ));
+        }
+
+        if !self.syntax().class_props() {
+            unexpected!()
+        }
+
+        let type_ann = self.try_parse_ts_type_ann()?;
+
+        // Initializers are evaluated like methods.
+        let ctx = Context {
+            include_in_expr: true,
+            in_async: false,
+            in_generator: false,
+            ..self.ctx()
+        };
+        let value = if eat!('=') {
+            Some(self.with_ctx(ctx).parse_assignment_expr()?)
+        } else {
+            None
+        };
+        let span = span!(start);
+        expect!(';');
+
+        Ok(match key {
+            Either::Left(key) => ClassMember::PrivateProp(PrivateProp {
+                span,
+                key,
+                value,
+                static_token,
+                decorators,
+                type_ann,
+            }),
+            Either::Right(key) => ClassMember::ClassProp(ClassProp {
+                span,
+                key,
+                value,
+                static_token,
+                decorators,
+                type_ann,
+            }),
+        })
+    }
+
+    fn finish_class_method(
+        &mut self,
+        start: BytePos,
+        decorators: Vec<Decorator>,
+        static_token: Option<Span>,
+        key: Either<PrivateName, PropName>,
+        function: Function,
+        kind: ClassMethodKind,
+    ) -> ClassMember {
+        let key = match key {
+            Either::Left(key) => {
+                return ClassMember::PrivateMethod(PrivateMethod {
                     span: span!(start),
-                    static_token,
                     key,
                     function,
-                    kind: ClassMethodKind::Method,
+                    kind,
+                    static_token,
+                    decorators,
                 });
-        }
+            }
+            Either::Right(key) => key,
+        };
 
-        let ident = match key {
-            PropName::Ident(ident) => ident,
-            _ => unexpected!(),
+        let kind = match key {
+            PropName::Ident(Ident {
+                sym: js_word!("constructor"),
+                ..
+            })
+            | PropName::Str(Str {
+                value: js_word!("constructor"),
+                ..
+            }) => ClassMethodKind::Constructor,
+            _ => kind,
         };
 
-        // get a(){}
-        // set a(v){}
-        // async a(){}
+        ClassMember::Method(ClassMethod {
+            span: span!(start),
+            key,
+            function,
+            kind,
+            static_token,
+            decorators,
+        })
+    }
 
-        match ident.sym {
-            js_word!("get") | js_word!("set") | js_word!("async") => {
-                let key = self.parse_prop_name()?;
-
-                return match ident.sym {
-                    js_word!("get") => self
-                        .parse_fn_args_body(start, |_| Ok(vec![]), None, None)
-                        .map(|function| ClassMethod {
-                            span: span!(start),
-                            static_token,
-                            key,
-                            function,
-                            kind: ClassMethodKind::Getter,
-                        }),
-                    js_word!("set") => self
-                        .parse_fn_args_body(
-                            start,
-                            |p| p.parse_formal_param().map(|pat| vec![pat]),
-                            None,
-                            None,
-                        )
-                        .map(|function| ClassMethod {
-                            span: span!(start),
-                            key,
-                            static_token,
-                            function,
-                            kind: ClassMethodKind::Setter,
-                        }),
-                    js_word!("async") => self
-                        .parse_fn_args_body(
-                            start,
-                            Parser::parse_unique_formal_params,
-                            Some(ident.span),
-                            None,
-                        )
-                        .map(|function| ClassMethod {
-                            span: span!(start),
-                            static_token,
-                            key,
-                            function,
-                            kind: ClassMethodKind::Method,
-                        }),
-                    _ => unreachable!(),
-                };
-            }
-            _ => unexpected!(),
+    /// `static { }`
+    fn parse_static_block(&mut self, start: BytePos) -> PResult<'a, StaticBlock> {
+        // `await`, `yield` and `return` are not allowed.
+        let ctx = Context {
+            in_async: false,
+            in_generator: false,
+            in_function: false,
+            ..self.ctx()
+        };
+        let body = self.with_ctx(ctx).parse_block(false)?;
+
+        Ok(StaticBlock {
+            span: span!(start),
+            body,
+        })
+    }
+
+    /// Parses decorators if they are enabled.
+    pub(super) fn parse_decorators(&mut self) -> PResult<'a, (Vec<Decorator>)> {
+        if self.syntax().decorators().is_none() {
+            return Ok(vec![]);
         }
+
+        let mut decorators = vec![];
+        while is!('@') {
+            decorators.push(self.parse_decorator()?);
+        }
+        Ok(decorators)
+    }
+
+    /// `@(expr)`, `@a.b.c` or `@a.b.c(args)`
+    fn parse_decorator(&mut self) -> PResult<'a, Decorator> {
+        let start = cur_pos!();
+        assert_and_bump!('@');
+        let expr_start = cur_pos!();
+
+        if eat!('(') {
+            let expr = self.include_in_expr(true).parse_expr()?;
+            expect!(')');
+            return Ok(Decorator {
+                span: span!(start),
+                expr: box Expr::Paren(ParenExpr {
+                    span: span!(expr_start),
+                    expr,
+                }),
+            });
+        }
+
+        // This is not `parse_lhs_expr`, because `@dec [computed]() {}` is not
+        // a member expression.
+        let mut expr = box Expr::Ident(self.parse_ident_ref()?);
+        while eat!('.') {
+            let prop = self.parse_ident_name()?;
+            expr = box Expr::Member(MemberExpr {
+                span: span!(expr_start),
+                obj: ExprOrSuper::Expr(expr),
+                prop: box Expr::Ident(prop),
+                computed: false,
+            });
+        }
+        if is!('(') {
+            let args = self.parse_args()?;
+            expr = box Expr::Call(CallExpr {
+                span: span!(expr_start),
+                callee: ExprOrSuper::Expr(expr),
+                args,
+            });
+        }
+
+        Ok(Decorator {
+            span: span!(start),
+            expr,
+        })
+    }
+
+    /// Parses decorators of a parameter, which are allowed only by the legacy
+    /// rules.
+    pub(super) fn parse_param_decorators(&mut self) -> PResult<'a, (Vec<Decorator>)> {
+        let start = cur_pos!();
+        let decorators = self.parse_decorators()?;
+        if !decorators.is_empty() && self.syntax().decorators() != Some(Decorators::Legacy) {
+            syntax_error!(span!(start), SyntaxError::DecoratorOnParam)
+        }
+        Ok(decorators)
     }
 
     pub(super) fn parse_fn_body<T>(&mut self, is_async: bool, is_generator: bool) -> PResult<'a, T>
@@ -384,14 +608,14 @@ impl OutputType for Box<Expr> {
     }
 }
 
-impl OutputType for ExportDefaultDecl {
+impl OutputType for DefaultDecl {
     type Ident = Option<Ident>;
 
     fn finish_fn(ident: Option<Ident>, function: Function) -> Self {
-        ExportDefaultDecl::Fn(FnExpr { ident, function })
+        DefaultDecl::Fn(FnExpr { ident, function })
     }
     fn finish_class(ident: Option<Ident>, class: Class) -> Self {
-        ExportDefaultDecl::Class(ClassExpr { ident, class })
+        DefaultDecl::Class(ClassExpr { ident, class })
     }
 }
 
@@ -399,10 +623,18 @@ impl OutputType for Decl {
     type Ident = Ident;
 
     fn finish_fn(ident: Ident, function: Function) -> Self {
-        Decl::Fn(FnDecl { ident, function })
+        Decl::Fn(FnDecl {
+            ident,
+            declare: false,
+            function,
+        })
     }
     fn finish_class(ident: Ident, class: Class) -> Self {
-        Decl::Class(ClassDecl { ident, class })
+        Decl::Class(ClassDecl {
+            ident,
+            declare: false,
+            class,
+        })
     }
 }
 
@@ -460,10 +692,182 @@ mod tests {
                     class: Class {
                         span,
                         body: vec![],
+                        decorators: vec![],
                         super_class: Some(expr("a")),
+                        type_params: None,
+                        super_type_params: None,
+                        implements: vec![],
                     },
                 }),
             })
         );
     }
+
+    fn module_with(decorators: Decorators, s: &'static str) -> Result<Module, ::testing::StdErr> {
+        ::with_test_sess(s, |sess, input| {
+            let syntax = Syntax::Es(::EsConfig {
+                decorators: Some(decorators),
+                ..Default::default()
+            });
+            Parser::new(sess, syntax, input).parse_module()
+        })
+    }
+
+    fn ident(sym: &str) -> Ident {
+        Ident::new(sym.into(), span)
+    }
+
+    fn decorator(expr: Box<Expr>) -> Decorator {
+        Decorator { span, expr }
+    }
+
+    #[test]
+    fn legacy_decorators() {
+        let module = module_with(
+            Decorators::Legacy,
+            "@a export class B { @c.d() e(@f g) {} }",
+        )
+        .unwrap_or_else(|output| panic!("failed to parse decorators:\n{}", output));
+
+        assert_eq_ignore_span!(
+            module.body,
+            vec![ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                span,
+                decl: Decl::Class(ClassDecl {
+                    ident: ident("B"),
+                    declare: false,
+                    class: Class {
+                        span,
+                        decorators: vec![decorator(box Expr::Ident(ident("a")))],
+                        body: vec![ClassMember::Method(ClassMethod {
+                            span,
+                            decorators: vec![decorator(box Expr::Call(CallExpr {
+                                span,
+                                callee: ExprOrSuper::Expr(box Expr::Member(MemberExpr {
+                                    span,
+                                    obj: ExprOrSuper::Expr(box Expr::Ident(ident("c"))),
+                                    prop: box Expr::Ident(ident("d")),
+                                    computed: false,
+                                })),
+                                args: vec![],
+                            }))],
+                            static_token: None,
+                            key: PropName::Ident(ident("e")),
+                            function: Function {
+                                span,
+                                params: vec![Param {
+                                    span,
+                                    decorators: vec![decorator(box Expr::Ident(ident("f")))],
+                                    pat: Pat::Ident(ident("g")),
+                                }],
+                                body: Some(BlockStmt {
+                                    span,
+                                    stmts: vec![],
+                                }),
+                                async_token: None,
+                                generator_token: None,
+                                type_params: None,
+                                return_type: None,
+                            },
+                            kind: ClassMethodKind::Method,
+                        })],
+                        super_class: None,
+                        type_params: None,
+                        super_type_params: None,
+                        implements: vec![],
+                    },
+                }),
+            }))]
+        );
+    }
+
+    #[test]
+    fn decorator_placement() {
+        assert!(module_with(Decorators::Legacy, "export @a class B {}").is_err());
+        assert!(module_with(Decorators::Proposal, "export @a class B {}").is_ok());
+        assert!(module_with(Decorators::Proposal, "@a export default class {}").is_ok());
+        assert!(module_with(Decorators::Proposal, "@a export @b class C {}").is_err());
+        assert!(module_with(Decorators::Proposal, "class A { b(@c d) {} }").is_err());
+        assert!(module_with(Decorators::Legacy, "@a;").is_err());
+        assert!(module_with(Decorators::Legacy, "@a export default 1;").is_err());
+    }
+
+    fn module(s: &'static str) -> Result<Module, ::testing::StdErr> {
+        ::with_test_sess(s, |sess, input| {
+            let syntax = Syntax::Es(::EsConfig {
+                class_props: true,
+                ..Default::default()
+            });
+            Parser::new(sess, syntax, input).parse_module()
+        })
+    }
+
+    fn class_body(s: &'static str) -> Vec<ClassMember> {
+        let module =
+            module(s).unwrap_or_else(|output| panic!("failed to parse class:\n{}", output));
+        match module.body.into_iter().next() {
+            Some(ModuleItem::Stmt(Stmt::Decl(Decl::Class(ClassDecl { class, .. })))) => class.body,
+            _ => unreachable!("expected a class declaration"),
+        }
+    }
+
+    #[test]
+    fn class_fields() {
+        assert_eq_ignore_span!(
+            class_body("class A { static b = 1; #c; }"),
+            vec![
+                ClassMember::ClassProp(ClassProp {
+                    span,
+                    key: PropName::Ident(ident("b")),
+                    value: Some(expr("1")),
+                    static_token: Some(span),
+                    decorators: vec![],
+                    type_ann: None,
+                }),
+                ClassMember::PrivateProp(PrivateProp {
+                    span,
+                    key: PrivateName {
+                        span,
+                        id: ident("c"),
+                    },
+                    value: None,
+                    static_token: None,
+                    decorators: vec![],
+                    type_ann: None,
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn class_member_names() {
+        assert!(module("class A { static() {} static; get; get\n a() {} async }").is_ok());
+        assert!(module("class A { #a() {} get #b() {} static async *#c() {} }").is_ok());
+        assert!(module("class A { static { this.b = 1; } }").is_ok());
+        assert!(module("class A { #constructor() {} }").is_err());
+        assert!(module("class A { static async { } }").is_err());
+    }
+
+    #[test]
+    fn private_names() {
+        assert!(module("class A { #a; b() { return #a in this && this.#a; } }").is_ok());
+        assert!(module("class A { #a; b() { return () => this.#a; } }").is_ok());
+        assert!(module("this.#a;").is_err());
+        assert!(module("class A { #a; b() { # a in this; } }").is_err());
+        assert!(module("class A { #a; b() { #a; } }").is_err());
+        assert!(module("class A { #a; b() { 1 + #a in this; } }").is_err());
+        assert!(module("class A { #a; b() { delete this.#a; } }").is_err());
+    }
+
+    #[test]
+    fn class_props_flag() {
+        let module = |s| {
+            ::with_test_sess(s, |sess, input| {
+                Parser::new(sess, Default::default(), input).parse_module()
+            })
+        };
+        assert!(module("class A { static() {} get a() {} }").is_ok());
+        assert!(module("class A { a = 1; }").is_err());
+        assert!(module("class A { #a() {} }").is_err());
+    }
 }
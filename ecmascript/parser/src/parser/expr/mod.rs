@@ -1,4 +1,5 @@
 use super::{pat::PatType, util::ExprExt, *};
+use either::Either;
 use swc_common::Spanned;
 
 mod ops;
@@ -49,7 +50,11 @@ impl<'a, I: Input> Parser<'a, I> {
         match *cond {
             // if cond is conditional expression but not left-hand-side expression,
             // just return it.
-            Expr::Cond(..) | Expr::Bin(..) | Expr::Unary(..) | Expr::Update(..) => return Ok(cond),
+            Expr::Cond(..)
+            | Expr::Bin(..)
+            | Expr::Unary(..)
+            | Expr::Update(..)
+            | Expr::TsAs(..) => return Ok(cond),
             _ => {}
         }
 
@@ -142,13 +147,18 @@ impl<'a, I: Input> Parser<'a, I> {
         }
 
         if is!("class") {
-            return self.parse_class_expr();
+            return self.parse_class_expr(vec![]);
         }
 
         // Literals
         if {
             match *cur!()? {
-                tok!("null") | tok!("true") | tok!("false") | Num(..) | Token::Str { .. } => true,
+                tok!("null")
+                | tok!("true")
+                | tok!("false")
+                | Num(..)
+                | BigInt(..)
+                | Token::Str { .. } => true,
                 _ => false,
             }
         } {
@@ -174,6 +184,13 @@ impl<'a, I: Input> Parser<'a, I> {
             }
         }
 
+        if self.syntax().jsx() && is!("jsx tag start") {
+            return Ok(match self.parse_jsx_element()? {
+                Either::Left(fragment) => box Expr::JSXFragment(fragment),
+                Either::Right(element) => box Expr::JSXElement(box element),
+            });
+        }
+
         if is!('`') {
             // parse template literal
             return Ok(box Expr::Tpl(self.parse_tpl_lit(None)?));
@@ -199,6 +216,8 @@ impl<'a, I: Input> Parser<'a, I> {
                     params,
                     async_token: Some(id.span),
                     generator_token: None,
+                    type_params: None,
+                    return_type: None,
                 }));
             } else if can_be_arrow && !self.input.had_line_break_before_cur() && eat!("=>") {
                 let params = vec![id.into()];
@@ -210,6 +229,8 @@ impl<'a, I: Input> Parser<'a, I> {
                     params,
                     async_token: None,
                     generator_token: None,
+                    type_params: None,
+                    return_type: None,
                 }));
             } else {
                 return Ok(box Expr::Ident(id));
@@ -306,12 +327,43 @@ impl<'a, I: Input> Parser<'a, I> {
             let base = ExprOrSuper::Super(span!(start));
             return self.parse_subscripts(base, true);
         }
+        if self.syntax().import_meta() && is!("import") && peeked_is!('.') {
+            let meta = self.parse_import_meta()?;
+            return self.parse_subscripts(ExprOrSuper::Expr(meta), true);
+        }
         let obj = self.parse_primary_expr()?;
         return_if_arrow!(obj);
 
         self.parse_subscripts(ExprOrSuper::Expr(obj), true)
     }
 
+    /// Parses `import.meta`.
+    fn parse_import_meta(&mut self) -> PResult<'a, (Box<Expr>)> {
+        let start = cur_pos!();
+        assert_and_bump!("import");
+        let span_of_import = span!(start);
+        if !self.ctx().module {
+            syntax_error!(span_of_import, SyntaxError::ImportMetaInScript)
+        }
+        expect!('.');
+
+        let start_of_meta = cur_pos!();
+        if !eat!("meta") {
+            unexpected!()
+        }
+
+        Ok(box Expr::MetaProp(MetaPropExpr {
+            meta: Ident {
+                span: span_of_import,
+                sym: js_word!("import"),
+            },
+            prop: Ident {
+                span: span!(start_of_meta),
+                sym: js_word!("meta"),
+            },
+        }))
+    }
+
     /// Parse `NewExpresion`.
     /// This includes `MemberExpression`.
     fn parse_new_expr(&mut self) -> PResult<'a, (Box<Expr>)> {
@@ -368,19 +420,33 @@ impl<'a, I: Input> Parser<'a, I> {
         // But as all patterns of javascript is subset of
         // expressions, we can parse both as expression.
 
-        let expr_or_spreads = self.include_in_expr(true).parse_args_or_pats()?;
+        let items = self.include_in_expr(true).parse_args_or_pats()?;
+
+        // `(a: T): U => a`
+        let has_typed_param = items.iter().any(|item| match *item {
+            PatOrExprOrSpread::Pat(..) => true,
+            PatOrExprOrSpread::ExprOrSpread(..) => false,
+        });
+        let return_type = if self.syntax().typescript()
+            && is!(':')
+            && (items.is_empty() || has_typed_param)
+        {
+            self.parse_ts_type_ann().map(Some)?
+        } else {
+            None
+        };
 
         // we parse arrow function at here, to handle it efficiently.
-        if is!("=>") {
+        if is!("=>") || return_type.is_some() {
             if self.input.had_line_break_before_cur() {
                 syntax_error!(span!(start), SyntaxError::LineBreakBeforeArrow);
             }
             if !can_be_arrow {
                 unexpected!();
             }
-            assert_and_bump!("=>");
+            expect!("=>");
 
-            let params = self.parse_exprs_as_params(expr_or_spreads)?;
+            let params = self.parse_exprs_as_params(items)?;
 
             let body: BlockStmtOrExpr = self.parse_fn_body(false, false)?;
             return Ok(box Expr::Arrow(ArrowExpr {
@@ -389,11 +455,23 @@ impl<'a, I: Input> Parser<'a, I> {
                 generator_token: None,
                 params,
                 body,
+                type_params: None,
+                return_type,
             }));
         }
 
         // It was not head of arrow function.
 
+        let mut expr_or_spreads = Vec::with_capacity(items.len());
+        for item in items {
+            match item {
+                PatOrExprOrSpread::ExprOrSpread(expr) => expr_or_spreads.push(expr),
+                PatOrExprOrSpread::Pat(pat) => {
+                    syntax_error!(pat.span(), SyntaxError::TsTypeAnnInParenExpr)
+                }
+            }
+        }
+
         if expr_or_spreads.len() == 0 {
             syntax_error!(
                 Span::new(start, last_pos!(), Default::default()),
@@ -483,21 +561,31 @@ impl<'a, I: Input> Parser<'a, I> {
     fn parse_tpl_element(&mut self, is_tagged: bool) -> PResult<'a, TplElement> {
         let start = cur_pos!();
 
-        let raw = match *cur!()? {
-            Template(_) => match bump!() {
-                Template(s) => s,
+        let (raw, cooked) = match *cur!()? {
+            Template { .. } => match bump!() {
+                Template { raw, cooked } => (raw, cooked),
                 _ => unreachable!(),
             },
             _ => unexpected!(),
         };
+        // Invalid escapes are allowed in tagged templates, as the tag can see the raw
+        // text. The cooked text is `undefined` in that case.
+        let cooked = match cooked {
+            Ok(cooked) => Some(cooked),
+            Err(err) => {
+                if is_tagged {
+                    None
+                } else {
+                    syntax_error!(err.span, err.error)
+                }
+            }
+        };
         let tail = is!('`');
         Ok(TplElement {
             span: span!(start),
             raw,
             tail,
-
-            // FIXME
-            cooked: None,
+            cooked,
         })
     }
 
@@ -526,7 +614,11 @@ impl<'a, I: Input> Parser<'a, I> {
         // member expression
         // $obj.name
         if eat!('.') {
-            let prop: Box<Expr> = box self.parse_ident_name().map(Expr::from)?;
+            let prop: Box<Expr> = if is!('#') {
+                box self.parse_private_name().map(Expr::PrivateName)?
+            } else {
+                box self.parse_ident_name().map(Expr::from)?
+            };
             return Ok((
                 box Expr::Member(MemberExpr {
                     span: span!(start),
@@ -539,6 +631,55 @@ impl<'a, I: Input> Parser<'a, I> {
             ));
         }
 
+        // $obj?.name, $obj?.[name()], $obj?.()
+        if is!("?.") {
+            if no_call {
+                syntax_error!(SyntaxError::OptChainInNew)
+            }
+            match obj {
+                ExprOrSuper::Expr(..) => {}
+                ExprOrSuper::Super(..) | ExprOrSuper::Import(..) => unexpected!(),
+            }
+            assert_and_bump!("?.");
+
+            let expr = if eat!('[') {
+                let prop = self.include_in_expr(true).parse_expr()?;
+                expect!(']');
+                Expr::Member(MemberExpr {
+                    span: span!(start),
+                    obj,
+                    prop,
+                    computed: true,
+                })
+            } else if is!('(') {
+                let args = self.parse_args()?;
+                Expr::Call(CallExpr {
+                    span: span!(start),
+                    callee: obj,
+                    args,
+                })
+            } else {
+                let prop: Box<Expr> = if is!('#') {
+                    box self.parse_private_name().map(Expr::PrivateName)?
+                } else {
+                    box self.parse_ident_name().map(Expr::from)?
+                };
+                Expr::Member(MemberExpr {
+                    span: span!(start),
+                    obj,
+                    prop,
+                    computed: false,
+                })
+            };
+            return Ok((
+                box Expr::OptChain(OptChainExpr {
+                    span: span!(start),
+                    expr: box expr,
+                }),
+                true,
+            ));
+        }
+
         // $obj[name()]
         if eat!('[') {
             let prop = self.include_in_expr(true).parse_expr()?;
@@ -570,13 +711,16 @@ impl<'a, I: Input> Parser<'a, I> {
             ExprOrSuper::Expr(expr) => {
                 // MemberExpression[?Yield, ?Await] TemplateLiteral[?Yield, ?Await, +Tagged]
                 if is!('`') {
+                    if expr.is_in_opt_chain() {
+                        syntax_error!(SyntaxError::TaggedTplInOptChain)
+                    }
                     let tpl = self.parse_tpl_lit(Some(expr))?;
                     return Ok((box Expr::Tpl(tpl), true));
                 }
 
                 Ok((expr, false))
             }
-            ExprOrSuper::Super(..) => {
+            ExprOrSuper::Super(..) | ExprOrSuper::Import(..) => {
                 if no_call {
                     unexpected!()
                 }
@@ -594,6 +738,23 @@ impl<'a, I: Input> Parser<'a, I> {
             return self.parse_subscripts(obj, false);
         }
 
+        // `import(src)` is a call which can't be a callee of `new`.
+        if self.syntax().dynamic_import() && is!("import") && peeked_is!('(') {
+            assert_and_bump!("import");
+            let callee = ExprOrSuper::Import(span!(start));
+            let args = self.parse_args()?;
+            if args.len() != 1 || args[0].spread.is_some() {
+                syntax_error!(span!(start), SyntaxError::DynImportArgs)
+            }
+
+            let call_expr = box Expr::Call(CallExpr {
+                span: span!(start),
+                callee,
+                args,
+            });
+            return self.parse_subscripts(ExprOrSuper::Expr(call_expr), false);
+        }
+
         let callee = self.parse_new_expr()?;
         return_if_arrow!(callee);
 
@@ -635,11 +796,80 @@ impl<'a, I: Input> Parser<'a, I> {
     pub(super) fn parse_expr_or_pat(&mut self) -> PResult<'a, (Box<Expr>)> {
         self.parse_expr()
     }
-    pub(super) fn parse_args_or_pats(&mut self) -> PResult<'a, (Vec<ExprOrSpread>)> {
-        self.parse_args()
+
+    /// Parses arguments of a call or parameters of an arrow function.
+    ///
+    /// In typescript, an element followed by a type annotation can only be a
+    /// parameter, so it's parsed as a pattern.
+    pub(super) fn parse_args_or_pats(&mut self) -> PResult<'a, (Vec<PatOrExprOrSpread>)> {
+        if !self.syntax().typescript() {
+            return self.parse_args().map(|args| {
+                args.into_iter()
+                    .map(PatOrExprOrSpread::ExprOrSpread)
+                    .collect()
+            });
+        }
+
+        expect!('(');
+
+        let mut first = true;
+        let mut items = vec![];
+
+        while !eof!() && !is!(')') {
+            if first {
+                first = false;
+            } else {
+                expect!(',');
+                // Handle trailing comma.
+                if is!(')') {
+                    break;
+                }
+            }
+
+            let start = cur_pos!();
+            let arg = self.include_in_expr(true).parse_expr_or_spread()?;
+            if !is!(':') {
+                items.push(PatOrExprOrSpread::ExprOrSpread(arg));
+                continue;
+            }
+
+            // `(a: T)`, `(...a: T[])` or `(a: T = b)`
+            let ExprOrSpread { spread, expr } = arg;
+            let pat_start = expr.span().lo();
+            let pat = self.reparse_expr_as_pat(PatType::BindingPat, expr)?;
+            let pat = self.parse_ts_typed_pat(pat_start, pat, false)?;
+            let pat = match spread {
+                Some(dot3_token) => Pat::Rest(RestPat {
+                    dot3_token,
+                    arg: box pat,
+                }),
+                None => pat,
+            };
+            let pat = if eat!('=') {
+                let right = self.include_in_expr(true).parse_assignment_expr()?;
+                Pat::Assign(AssignPat {
+                    span: span!(start),
+                    left: box pat,
+                    right,
+                })
+            } else {
+                pat
+            };
+            items.push(PatOrExprOrSpread::Pat(pat));
+        }
+
+        expect!(')');
+        Ok(items)
     }
 }
 
+/// An element of `parse_args_or_pats()`.
+pub(super) enum PatOrExprOrSpread {
+    /// An element with a type annotation.
+    Pat(Pat),
+    ExprOrSpread(ExprOrSpread),
+}
+
 /// simple leaf methods.
 #[parser]
 impl<'a, I: Input> Parser<'a, I> {
@@ -676,7 +906,7 @@ impl<'a, I: Input> Parser<'a, I> {
     }
 
     /// 12.2.5 Array Initializer
-    fn parse_lit(&mut self) -> PResult<'a, Lit> {
+    pub(super) fn parse_lit(&mut self) -> PResult<'a, Lit> {
         let start = cur_pos!();
 
         let v = match *cur!()? {
@@ -693,15 +923,30 @@ impl<'a, I: Input> Parser<'a, I> {
                 Lit::Bool(Bool { span, value })
             }
             Token::Str { .. } => match bump!() {
-                Token::Str { value, has_escape } => Lit::Str(Str {
-                    span: span!(start),
-                    value,
-                    has_escape,
-                }),
+                Token::Str { value, has_escape } => {
+                    let span = span!(start);
+                    Lit::Str(Str {
+                        span,
+                        value,
+                        has_escape,
+                        raw: Some(self.input.src_of(span)),
+                    })
+                }
                 _ => unreachable!(),
             },
             Num(..) => match bump!() {
-                Num(value) => Lit::Num(Number {
+                Num(value) => {
+                    let span = span!(start);
+                    Lit::Num(Number {
+                        span,
+                        value,
+                        raw: Some(self.input.src_of(span)),
+                    })
+                }
+                _ => unreachable!(),
+            },
+            BigInt(..) => match bump!() {
+                BigInt(value) => Lit::BigInt(BigInt {
                     span: span!(start),
                     value,
                 }),
@@ -6,7 +6,11 @@ use swc_common::Spanned;
 impl<'a, I: Input> Parser<'a, I> {
     /// Name from spec: 'LogicalORExpression'
     pub(super) fn parse_bin_expr(&mut self) -> PResult<'a, (Box<Expr>)> {
-        let left = self.parse_unary_expr()?;
+        let left = if is!('#') {
+            self.parse_private_in_expr()?
+        } else {
+            self.parse_unary_expr()?
+        };
 
         return_if_arrow!(left);
         self.parse_bin_op_recursively(left, 0)
@@ -22,6 +26,24 @@ impl<'a, I: Input> Parser<'a, I> {
         left: Box<Expr>,
         min_prec: u8,
     ) -> PResult<'a, (Box<Expr>)> {
+        // `a as T` has the same precedence as relational operators.
+        if self.syntax().typescript()
+            && op!("<").precedence() > min_prec
+            && !self.input.had_line_break_before_cur()
+            && is!("as")
+        {
+            let start = left.span().lo();
+            assert_and_bump!("as");
+            let type_ann = self.parse_ts_type()?;
+            let expr = box Expr::TsAs(TsAsExpr {
+                span: span!(start),
+                expr: left,
+                type_ann,
+            });
+
+            return self.parse_bin_op_recursively(expr, min_prec);
+        }
+
         let op = match {
             // Return left on eof
             match cur!() {
@@ -75,7 +97,12 @@ impl<'a, I: Input> Parser<'a, I> {
         }
 
         let right = {
-            let left_of_right = self.parse_unary_expr()?;
+            // `a && #b in c` is valid, but `a + #b in c` is not.
+            let left_of_right = if is!('#') && op.precedence() < op!("in").precedence() {
+                self.parse_private_in_expr()?
+            } else {
+                self.parse_unary_expr()?
+            };
             self.parse_bin_op_recursively(
                 left_of_right,
                 if op == op!("**") {
@@ -87,6 +114,24 @@ impl<'a, I: Input> Parser<'a, I> {
             )?
         };
 
+        // `a ?? b || c` and `a || b ?? c` are not allowed.
+        {
+            let is_mixed = |e: &Expr| match *e {
+                Expr::Bin(BinExpr { op: op!("??"), .. }) => {
+                    op == op!("||") || op == op!("&&")
+                }
+                Expr::Bin(BinExpr { op: op!("||"), .. })
+                | Expr::Bin(BinExpr { op: op!("&&"), .. }) => op == op!("??"),
+                _ => false,
+            };
+            if is_mixed(&left) || is_mixed(&right) {
+                syntax_error!(
+                    span!(left.span().lo()),
+                    SyntaxError::NullishMixedWithLogical
+                )
+            }
+        }
+
         let node = box Expr::Bin(BinExpr {
             span: span!(left.span().lo()),
             op,
@@ -98,6 +143,30 @@ impl<'a, I: Input> Parser<'a, I> {
         Ok(expr)
     }
 
+    /// Parses `#a in obj`, the only expression which starts with a private
+    /// name.
+    fn parse_private_in_expr(&mut self) -> PResult<'a, (Box<Expr>)> {
+        let start = cur_pos!();
+        let left = self.parse_private_name()?;
+
+        if !self.ctx().include_in_expr || !is!("in") {
+            syntax_error!(left.span, SyntaxError::LonePrivateName)
+        }
+        assert_and_bump!("in");
+
+        let right = {
+            let left_of_right = self.parse_unary_expr()?;
+            self.parse_bin_op_recursively(left_of_right, op!("in").precedence())?
+        };
+
+        Ok(box Expr::Bin(BinExpr {
+            span: span!(start),
+            op: op!("in"),
+            left: box Expr::PrivateName(left),
+            right,
+        }))
+    }
+
     /// Parse unary expression and update expression.
     ///
     /// spec: 'UnaryExpression'
@@ -138,6 +207,15 @@ impl<'a, I: Input> Parser<'a, I> {
                 _ => unreachable!(),
             };
             let arg = self.parse_unary_expr()?;
+            if op == op!("delete") {
+                if let Expr::Member(MemberExpr {
+                    prop: box Expr::PrivateName(..),
+                    ..
+                }) = *arg
+                {
+                    syntax_error!(arg.span(), SyntaxError::DeletePrivateName)
+                }
+            }
             return Ok(box Expr::Unary(UnaryExpr {
                 span: span!(start),
                 op,
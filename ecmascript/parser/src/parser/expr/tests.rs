@@ -131,6 +131,8 @@ fn arrow_fn_no_args() {
             generator_token: None,
             params: vec![],
             body: BlockStmtOrExpr::Expr(expr("1")),
+            type_params: None,
+            return_type: None,
         })
     );
 }
@@ -147,6 +149,8 @@ fn arrow_fn() {
                 sym: "a".into(),
             })],
             body: BlockStmtOrExpr::Expr(expr("1")),
+            type_params: None,
+            return_type: None,
         })
     );
 }
@@ -166,6 +170,8 @@ fn arrow_fn_rest() {
                 }),
             })],
             body: BlockStmtOrExpr::Expr(expr("1")),
+            type_params: None,
+            return_type: None,
         })
     );
 }
@@ -182,6 +188,8 @@ fn arrow_fn_no_paren() {
                 sym: "a".into(),
             })],
             body: BlockStmtOrExpr::Expr(expr("1")),
+            type_params: None,
+            return_type: None,
         })
     );
 }
@@ -238,3 +246,50 @@ fn array_lit() {
         })
     );
 }
+
+#[test]
+fn big_int_lit() {
+    assert_eq_ignore_span!(
+        expr("0x1Fn"),
+        box Expr::Lit(Lit::BigInt(BigInt {
+            span,
+            value: "0x1F".into(),
+        }))
+    );
+}
+
+#[test]
+fn tagged_tpl_with_invalid_escape() {
+    assert_eq_ignore_span!(
+        expr(r"a`\u{g}${ {b: `${c}`}.b }\n`"),
+        box Expr::Tpl(TplLit {
+            span,
+            tag: Some(box Expr::Ident(Ident {
+                span,
+                sym: "a".into(),
+            })),
+            exprs: vec![expr("{b: `${c}`}.b")],
+            quasis: vec![
+                TplElement {
+                    span,
+                    tail: false,
+                    cooked: None,
+                    raw: r"\u{g}".into(),
+                },
+                TplElement {
+                    span,
+                    tail: true,
+                    cooked: Some("\n".into()),
+                    raw: r"\n".into(),
+                },
+            ],
+        })
+    );
+}
+
+#[test]
+fn tpl_with_invalid_escape() {
+    test_parser(r"`\u{g}`", |p| {
+        p.parse_expr().expect_err("parsed an invalid escape in a template")
+    });
+}
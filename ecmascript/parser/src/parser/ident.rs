@@ -34,6 +34,31 @@ impl<'a, I: Input> Parser<'a, I> {
         Ok(Ident::new(w.into(), span!(start)))
     }
 
+    /// `#a`, which can be used only in class bodies.
+    pub(super) fn parse_private_name(&mut self) -> PResult<'a, PrivateName> {
+        if !self.syntax().class_props() {
+            unexpected!()
+        }
+
+        let start = cur_pos!();
+        assert_and_bump!('#');
+
+        if !self.ctx().in_class {
+            syntax_error!(span!(start), SyntaxError::PrivateNameOutsideClass)
+        }
+
+        let hash_end = last_pos!();
+        if cur_pos!() != hash_end {
+            syntax_error!(span!(start), SyntaxError::SpaceAfterHash)
+        }
+
+        let id = self.parse_ident_name()?;
+        Ok(PrivateName {
+            span: span!(start),
+            id,
+        })
+    }
+
     /// Identifier
     ///
     /// In strict mode, "yield" is SyntaxError if matched.
@@ -54,6 +79,20 @@ impl<'a, I: Input> Parser<'a, I> {
             // It is a Syntax Error if this phrase is contained in strict mode code and the
             // StringValue of IdentifierName is: "implements", "interface", "let",
             // "package", "private", "protected",  "public", "static", or "yield".
+            // Keywords are lexed as identifiers only if they contain escapes.
+            if let Ident(ref word) = w {
+                let is_keyword = match Word::from(word.clone()) {
+                    Ident(..) => false,
+                    _ => true,
+                };
+                if is_keyword && p.ctx().is_reserved_word(word) {
+                    syntax_error!(
+                        p.input.prev_span(),
+                        SyntaxError::EscapeInReservedWord { word: word.clone() }
+                    )
+                }
+            }
+
             match w {
                 Ident(js_word!("enum")) => {
                     syntax_error!(p.input.prev_span(), SyntaxError::InvalidIdentInStrict)
@@ -1,7 +1,9 @@
 use lexer::{Input, Lexer};
+use swc_atoms::JsWord;
 use swc_common::{BytePos, Span, DUMMY_SP};
 use token::*;
 use Context;
+use Syntax;
 
 /// This struct is responsible for managing current token and peeked token.
 pub(super) struct ParserInput<'a, I: Input> {
@@ -55,6 +57,39 @@ impl<'a, I: Input> ParserInput<'a, I> {
         prev.token
     }
 
+    /// Eats a leading `>` of the current token, which should start with `>`.
+    ///
+    /// The lexer produces `>>` for `Array<Array<T>>`, so type arguments need
+    /// to split such tokens.
+    pub fn bump_gt(&mut self) {
+        let rest = match self.cur {
+            Some(TokenAndSpan { ref token, .. }) => match *token {
+                BinOp(Gt) => None,
+                BinOp(GtEq) => Some(AssignOp(Assign)),
+                BinOp(RShift) => Some(BinOp(Gt)),
+                BinOp(ZeroFillRShift) => Some(BinOp(RShift)),
+                AssignOp(RShiftAssign) => Some(BinOp(GtEq)),
+                AssignOp(ZeroFillRShiftAssign) => Some(AssignOp(RShiftAssign)),
+                _ => unreachable!("bump_gt() should be called only if cur starts with `>`"),
+            },
+            None => unreachable!("bump_gt() should be called only if cur starts with `>`"),
+        };
+
+        match rest {
+            None => {
+                self.bump();
+            }
+            Some(token) => {
+                let cur = self.cur.as_mut().unwrap();
+                let gt_end = cur.span.lo() + BytePos(1);
+                self.prev_span = cur.span.with_hi(gt_end);
+                cur.span = cur.span.with_lo(gt_end);
+                cur.token = token;
+                cur.had_line_break = false;
+            }
+        }
+    }
+
     pub fn knows_cur(&self) -> bool {
         self.cur.is_some()
     }
@@ -164,4 +199,13 @@ impl<'a, I: Input> ParserInput<'a, I> {
     pub fn set_ctx(&mut self, ctx: Context) {
         self.iter.ctx = ctx;
     }
+
+    /// Returns the source code of `span`, which should be already consumed.
+    pub fn src_of(&mut self, span: Span) -> JsWord {
+        self.iter.src_of(span)
+    }
+
+    pub const fn syntax(&self) -> Syntax {
+        self.iter.syntax()
+    }
 }
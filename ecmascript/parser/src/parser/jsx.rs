@@ -0,0 +1,553 @@
+//! JSX, ported from the jsx plugin of babylon.
+
+use super::*;
+use either::Either;
+
+#[parser]
+impl<'a, I: Input> Parser<'a, I> {
+    /// Parses a JSX element or a JSX fragment. `<` should be the current
+    /// token.
+    pub(super) fn parse_jsx_element(&mut self) -> PResult<'a, Either<JSXFragment, JSXElement>> {
+        debug_assert!(self.syntax().jsx());
+
+        let start = cur_pos!();
+        assert_and_bump!("jsx tag start");
+
+        self.parse_jsx_element_at(start)
+    }
+
+    /// Parses `div` in `<div>` and `data-id` in `<div data-id="1">`.
+    fn parse_jsx_ident(&mut self) -> PResult<'a, Ident> {
+        let start = cur_pos!();
+
+        let name = match *cur!()? {
+            Token::JSXName { .. } => match bump!() {
+                Token::JSXName { name } => name,
+                _ => unreachable!(),
+            },
+            _ => unexpected!(),
+        };
+
+        Ok(Ident::new(name, span!(start)))
+    }
+
+    /// Parses `a` or `svg:rect`.
+    fn parse_jsx_namespaced_name(&mut self) -> PResult<'a, JSXAttrName> {
+        let ns = self.parse_jsx_ident()?;
+        if !eat!(':') {
+            return Ok(JSXAttrName::Ident(ns));
+        }
+
+        let name = self.parse_jsx_ident()?;
+        Ok(JSXAttrName::JSXNamespacedName(JSXNamespacedName { ns, name }))
+    }
+
+    /// Parses `a`, `svg:rect` or `Foo.Bar`.
+    fn parse_jsx_element_name(&mut self) -> PResult<'a, JSXElementName> {
+        let mut node = match self.parse_jsx_namespaced_name()? {
+            JSXAttrName::Ident(i) => JSXElementName::Ident(i),
+            JSXAttrName::JSXNamespacedName(n) => return Ok(JSXElementName::JSXNamespacedName(n)),
+        };
+
+        while eat!('.') {
+            let prop = self.parse_jsx_ident()?;
+            let obj = match node {
+                JSXElementName::Ident(i) => JSXObject::Ident(i),
+                JSXElementName::JSXMemberExpr(e) => JSXObject::JSXMemberExpr(box e),
+                JSXElementName::JSXNamespacedName(..) => unreachable!(),
+            };
+            node = JSXElementName::JSXMemberExpr(JSXMemberExpr { obj, prop });
+        }
+
+        Ok(node)
+    }
+
+    /// Parses `"a"`, `{a}` or `<a />` in `<b c=... />`.
+    fn parse_jsx_attr_value(&mut self) -> PResult<'a, JSXAttrValue> {
+        let start = cur_pos!();
+
+        if is!('{') {
+            let node = self.parse_jsx_expr_container()?;
+            let is_empty = match node.expr {
+                JSXExpr::JSXEmptyExpr(..) => true,
+                JSXExpr::Expr(..) => false,
+            };
+            if is_empty {
+                syntax_error!(span!(start), SyntaxError::EmptyJSXAttr)
+            }
+            return Ok(JSXAttrValue::JSXExprContainer(node));
+        }
+
+        if is!("jsx tag start") {
+            return Ok(match self.parse_jsx_element()? {
+                Either::Left(fragment) => JSXAttrValue::JSXFragment(fragment),
+                Either::Right(element) => JSXAttrValue::JSXElement(box element),
+            });
+        }
+
+        let is_str = match *cur!()? {
+            Token::Str { .. } => true,
+            _ => false,
+        };
+        if is_str {
+            return self.parse_lit().map(JSXAttrValue::Lit);
+        }
+
+        syntax_error!(SyntaxError::InvalidJSXAttrValue)
+    }
+
+    /// Parses `{}` or `{expr}`.
+    fn parse_jsx_expr_container(&mut self) -> PResult<'a, JSXExprContainer> {
+        let start = cur_pos!();
+        assert_and_bump!('{');
+
+        let expr = if is!('}') {
+            JSXExpr::JSXEmptyExpr(JSXEmptyExpr {
+                span: Span::new(last_pos!(), cur_pos!(), Default::default()),
+            })
+        } else {
+            JSXExpr::Expr(self.include_in_expr(true).parse_expr()?)
+        };
+        expect!('}');
+
+        Ok(JSXExprContainer {
+            span: span!(start),
+            expr,
+        })
+    }
+
+    /// Parses `{...children}` in children of an element.
+    fn parse_jsx_spread_child(&mut self) -> PResult<'a, JSXSpreadChild> {
+        let start = cur_pos!();
+        assert_and_bump!('{');
+        assert_and_bump!("...");
+
+        let expr = self.include_in_expr(true).parse_expr()?;
+        expect!('}');
+
+        Ok(JSXSpreadChild {
+            span: span!(start),
+            expr,
+        })
+    }
+
+    /// Parses `a`, `a="b"` or `{...props}`.
+    fn parse_jsx_attr(&mut self) -> PResult<'a, JSXAttrOrSpread> {
+        let start = cur_pos!();
+
+        if eat!('{') {
+            let dot3_start = cur_pos!();
+            expect!("...");
+            let dot3_token = span!(dot3_start);
+            let expr = self.include_in_expr(true).parse_assignment_expr()?;
+            expect!('}');
+
+            return Ok(JSXAttrOrSpread::SpreadElement(SpreadElement {
+                dot3_token,
+                expr,
+            }));
+        }
+
+        let name = self.parse_jsx_namespaced_name()?;
+        let value = if eat!('=') {
+            Some(self.parse_jsx_attr_value()?)
+        } else {
+            None
+        };
+
+        Ok(JSXAttrOrSpread::JSXAttr(box JSXAttr {
+            span: span!(start),
+            name,
+            value,
+        }))
+    }
+
+    /// Parses an opening tag after `<`.
+    fn parse_jsx_opening_element_at(
+        &mut self,
+        start: BytePos,
+    ) -> PResult<'a, Either<JSXOpeningFragment, JSXOpeningElement>> {
+        if eat!("jsx tag end") {
+            return Ok(Either::Left(JSXOpeningFragment { span: span!(start) }));
+        }
+
+        let name = self.parse_jsx_element_name()?;
+
+        let mut attrs = vec![];
+        while !eof!() && !is!('/') && !is!("jsx tag end") {
+            attrs.push(self.parse_jsx_attr()?);
+        }
+        let self_closing = eat!('/');
+        expect!("jsx tag end");
+
+        Ok(Either::Right(JSXOpeningElement {
+            name,
+            span: span!(start),
+            attrs,
+            self_closing,
+        }))
+    }
+
+    /// Parses a closing tag after `</`.
+    fn parse_jsx_closing_element_at(
+        &mut self,
+        start: BytePos,
+    ) -> PResult<'a, Either<JSXClosingFragment, JSXClosingElement>> {
+        if eat!("jsx tag end") {
+            return Ok(Either::Left(JSXClosingFragment { span: span!(start) }));
+        }
+
+        let name = self.parse_jsx_element_name()?;
+        expect!("jsx tag end");
+
+        Ok(Either::Right(JSXClosingElement {
+            span: span!(start),
+            name,
+        }))
+    }
+
+    fn parse_jsx_text(&mut self) -> PResult<'a, JSXText> {
+        let start = cur_pos!();
+
+        let (value, raw) = match bump!() {
+            Token::JSXText { value, raw } => (value, raw),
+            _ => unreachable!("parse_jsx_text should not be called"),
+        };
+
+        Ok(JSXText {
+            span: span!(start),
+            value,
+            raw,
+        })
+    }
+
+    /// Parses an element after `<`.
+    fn parse_jsx_element_at(
+        &mut self,
+        start: BytePos,
+    ) -> PResult<'a, Either<JSXFragment, JSXElement>> {
+        let opening = self.parse_jsx_opening_element_at(start)?;
+
+        let mut children = vec![];
+        let mut closing = None;
+
+        let self_closing = match opening {
+            Either::Right(ref el) => el.self_closing,
+            _ => false,
+        };
+
+        if !self_closing {
+            loop {
+                let child_start = cur_pos!();
+
+                if eat!("jsx tag start") {
+                    if eat!('/') {
+                        closing = Some(self.parse_jsx_closing_element_at(child_start)?);
+                        break;
+                    }
+
+                    children.push(match self.parse_jsx_element_at(child_start)? {
+                        Either::Left(fragment) => JSXElementChild::JSXFragment(fragment),
+                        Either::Right(element) => JSXElementChild::JSXElement(box element),
+                    });
+                    continue;
+                }
+
+                if is!('{') {
+                    if peeked_is!("...") {
+                        children.push(self.parse_jsx_spread_child().map(JSXElementChild::from)?);
+                    } else {
+                        children.push(self.parse_jsx_expr_container().map(JSXElementChild::from)?);
+                    }
+                    continue;
+                }
+
+                let is_text = match *cur!()? {
+                    Token::JSXText { .. } => true,
+                    _ => false,
+                };
+                if is_text {
+                    children.push(self.parse_jsx_text().map(JSXElementChild::from)?);
+                    continue;
+                }
+
+                unexpected!()
+            }
+        }
+
+        let span = span!(start);
+
+        let node = match (opening, closing) {
+            (Either::Left(opening), Some(Either::Left(closing))) => Either::Left(JSXFragment {
+                span,
+                opening,
+                children,
+                closing,
+            }),
+            (Either::Left(..), Some(Either::Right(closing))) => {
+                syntax_error!(closing.span, SyntaxError::JSXExpectedClosingTagForLtGt)
+            }
+            (Either::Right(opening), None) => Either::Right(JSXElement {
+                span,
+                opening,
+                children,
+                closing: None,
+            }),
+            (Either::Right(opening), Some(Either::Right(closing))) => {
+                let tag = get_qualified_jsx_name(&opening.name);
+                if tag != get_qualified_jsx_name(&closing.name) {
+                    syntax_error!(closing.span, SyntaxError::JSXExpectedClosingTag { tag })
+                }
+
+                Either::Right(JSXElement {
+                    span,
+                    opening,
+                    children,
+                    closing: Some(closing),
+                })
+            }
+            (Either::Right(opening), Some(Either::Left(closing))) => syntax_error!(
+                closing.span,
+                SyntaxError::JSXExpectedClosingTag {
+                    tag: get_qualified_jsx_name(&opening.name),
+                }
+            ),
+            (Either::Left(..), None) => unreachable!("fragment cannot be self-closing"),
+        };
+
+        if is!('<') {
+            syntax_error!(SyntaxError::AdjacentJSXElements)
+        }
+
+        Ok(node)
+    }
+}
+
+/// Returns `Foo.Bar` for `<Foo.Bar>`.
+fn get_qualified_jsx_name(name: &JSXElementName) -> JsWord {
+    fn get_qualified_obj_name(obj: &JSXObject) -> JsWord {
+        match *obj {
+            JSXObject::Ident(ref i) => i.sym.clone(),
+            JSXObject::JSXMemberExpr(ref e) => {
+                format!("{}.{}", get_qualified_obj_name(&e.obj), e.prop.sym).into()
+            }
+        }
+    }
+
+    match *name {
+        JSXElementName::Ident(ref i) => i.sym.clone(),
+        JSXElementName::JSXNamespacedName(ref n) => format!("{}:{}", n.ns.sym, n.name.sym).into(),
+        JSXElementName::JSXMemberExpr(ref e) => {
+            format!("{}.{}", get_qualified_obj_name(&e.obj), e.prop.sym).into()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swc_common::DUMMY_SP as span;
+
+    fn jsx(s: &'static str) -> Box<Expr> {
+        ::with_test_sess(s, |sess, input| {
+            let syntax = Syntax::Es(::EsConfig {
+                jsx: true,
+                ..Default::default()
+            });
+            Parser::new(sess, syntax, input).parse_expr()
+        })
+        .unwrap_or_else(|output| panic!("failed to parse jsx:\n{}", output))
+    }
+
+    fn ident(sym: &str) -> Ident {
+        Ident::new(sym.into(), span)
+    }
+
+    fn text(s: &str) -> JSXElementChild {
+        JSXElementChild::JSXText(JSXText {
+            span,
+            value: s.into(),
+            raw: s.into(),
+        })
+    }
+
+    #[test]
+    fn self_closing() {
+        assert_eq_ignore_span!(
+            jsx("<a />"),
+            box Expr::JSXElement(box JSXElement {
+                span,
+                opening: JSXOpeningElement {
+                    name: JSXElementName::Ident(ident("a")),
+                    span,
+                    attrs: vec![],
+                    self_closing: true,
+                },
+                children: vec![],
+                closing: None,
+            })
+        );
+    }
+
+    #[test]
+    fn children() {
+        assert_eq_ignore_span!(
+            jsx("<a>foo {bar}<b/></a>"),
+            box Expr::JSXElement(box JSXElement {
+                span,
+                opening: JSXOpeningElement {
+                    name: JSXElementName::Ident(ident("a")),
+                    span,
+                    attrs: vec![],
+                    self_closing: false,
+                },
+                children: vec![
+                    text("foo "),
+                    JSXElementChild::JSXExprContainer(JSXExprContainer {
+                        span,
+                        expr: JSXExpr::Expr(box Expr::Ident(ident("bar"))),
+                    }),
+                    JSXElementChild::JSXElement(box JSXElement {
+                        span,
+                        opening: JSXOpeningElement {
+                            name: JSXElementName::Ident(ident("b")),
+                            span,
+                            attrs: vec![],
+                            self_closing: true,
+                        },
+                        children: vec![],
+                        closing: None,
+                    }),
+                ],
+                closing: Some(JSXClosingElement {
+                    span,
+                    name: JSXElementName::Ident(ident("a")),
+                }),
+            })
+        );
+    }
+
+    #[test]
+    fn attrs() {
+        assert_eq_ignore_span!(
+            jsx("<a data-b=\"&amp;\" c={1} {...d} e />"),
+            box Expr::JSXElement(box JSXElement {
+                span,
+                opening: JSXOpeningElement {
+                    name: JSXElementName::Ident(ident("a")),
+                    span,
+                    attrs: vec![
+                        JSXAttrOrSpread::JSXAttr(box JSXAttr {
+                            span,
+                            name: JSXAttrName::Ident(ident("data-b")),
+                            value: Some(JSXAttrValue::Lit(Lit::Str(Str {
+                                span,
+                                value: "&".into(),
+                                has_escape: false,
+                                raw: Some("\"&amp;\"".into()),
+                            }))),
+                        }),
+                        JSXAttrOrSpread::JSXAttr(box JSXAttr {
+                            span,
+                            name: JSXAttrName::Ident(ident("c")),
+                            value: Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+                                span,
+                                expr: JSXExpr::Expr(box Expr::Lit(Lit::Num(Number {
+                                    span,
+                                    value: 1.0,
+                                    raw: Some("1".into()),
+                                }))),
+                            })),
+                        }),
+                        JSXAttrOrSpread::SpreadElement(SpreadElement {
+                            dot3_token: span,
+                            expr: box Expr::Ident(ident("d")),
+                        }),
+                        JSXAttrOrSpread::JSXAttr(box JSXAttr {
+                            span,
+                            name: JSXAttrName::Ident(ident("e")),
+                            value: None,
+                        }),
+                    ],
+                    self_closing: true,
+                },
+                children: vec![],
+                closing: None,
+            })
+        );
+    }
+
+    #[test]
+    fn entities() {
+        match *jsx("<a>&lt;&#x41;&#66;&unknown;</a>") {
+            Expr::JSXElement(ref el) => assert_eq_ignore_span!(
+                el.children,
+                vec![JSXElementChild::JSXText(JSXText {
+                    span,
+                    value: "<AB&unknown;".into(),
+                    raw: "&lt;&#x41;&#66;&unknown;".into(),
+                })]
+            ),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn names() {
+        match *jsx("<svg:rect></svg:rect>") {
+            Expr::JSXElement(ref el) => assert_eq_ignore_span!(
+                el.opening.name,
+                JSXElementName::JSXNamespacedName(JSXNamespacedName {
+                    ns: ident("svg"),
+                    name: ident("rect"),
+                })
+            ),
+            _ => unreachable!(),
+        }
+
+        match *jsx("<A.B.C />") {
+            Expr::JSXElement(ref el) => assert_eq_ignore_span!(
+                el.opening.name,
+                JSXElementName::JSXMemberExpr(JSXMemberExpr {
+                    obj: JSXObject::JSXMemberExpr(box JSXMemberExpr {
+                        obj: JSXObject::Ident(ident("A")),
+                        prop: ident("B"),
+                    }),
+                    prop: ident("C"),
+                })
+            ),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn fragment() {
+        assert_eq_ignore_span!(
+            jsx("<>a</>"),
+            box Expr::JSXFragment(JSXFragment {
+                span,
+                opening: JSXOpeningFragment { span },
+                children: vec![text("a")],
+                closing: JSXClosingFragment { span },
+            })
+        );
+    }
+
+    #[test]
+    fn mismatched_closing_tag() {
+        ::with_test_sess("<a></b>", |sess, input| {
+            let syntax = Syntax::Es(::EsConfig {
+                jsx: true,
+                ..Default::default()
+            });
+            Parser::new(sess, syntax, input).parse_expr()
+        })
+        .expect_err("should fail");
+    }
+
+    #[test]
+    fn lt_without_jsx() {
+        // `<` is an operator if jsx is disabled.
+        test_parser("a < b > c", |p| p.parse_expr()).unwrap();
+    }
+}
@@ -147,12 +147,7 @@ macro_rules! cur {
         if is_err_token {
             match $p.input.bump() {
                 $crate::token::Token::Error(e) => {
-                    ::swc_common::errors::DiagnosticBuilder::from($crate::error::ErrorToDiag {
-                        handler: &$p.session.handler,
-                        span: e.span,
-                        error: e.error,
-                    })
-                    .emit();
+                    $p.emit_error(e);
                     let err: Result<!, _> = Err(());
                     err?
                 }
@@ -160,16 +155,14 @@ macro_rules! cur {
             }
         }
 
-        match $p.input.cur() {
-            Some(c) => Ok(c),
-            None => {
-                ::swc_common::errors::DiagnosticBuilder::from($crate::error::Eof {
-                    last,
-                    handler: &$p.session.handler,
-                })
-                .emit();
-                Err(())
-            }
+        if $p.input.cur().is_none() {
+            $p.emit_error($crate::error::Error {
+                span: last,
+                error: $crate::error::SyntaxError::Eof,
+            });
+            Err(())
+        } else {
+            Ok($p.input.cur().unwrap())
         }
     }};
 }
@@ -185,17 +178,15 @@ Current token is {:?}",
 
         let pos = cur_pos!($p);
         let last = Span::new(pos, pos, Default::default());
-        match $p.input.peek() {
-            Some(c) => Ok(c),
-            None => {
-                ::swc_common::errors::DiagnosticBuilder::from($crate::error::Eof {
-                    //TODO: Use whole span
-                    last,
-                    handler: &$p.session.handler,
-                })
-                .emit();
-                Err(())
-            }
+        if $p.input.peek().is_none() {
+            //TODO: Use whole span
+            $p.emit_error($crate::error::Error {
+                span: last,
+                error: $crate::error::SyntaxError::Eof,
+            });
+            Err(())
+        } else {
+            Ok($p.input.peek().unwrap())
         }
     }};
 }
@@ -264,12 +255,9 @@ macro_rules! syntax_error {
     };
 
     ($p:expr, $span:expr, $err:expr) => {{
-        ::swc_common::errors::DiagnosticBuilder::from($crate::error::ErrorToDiag {
-            handler: $p.session.handler,
-            span: $span,
-            error: $err,
-        })
-        .emit();
+        let span = $span;
+        let error = $err;
+        $p.emit_error($crate::error::Error { span, error });
         let res: Result<!, _> = Err(());
         res?
     }};
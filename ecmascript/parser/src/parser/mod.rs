@@ -2,15 +2,17 @@
 #![deny(non_snake_case)]
 use self::{input::ParserInput, util::ParseObject};
 use ast::*;
-use error::SyntaxError;
+use error::{Error, SyntaxError};
 use lexer::{Input, Lexer};
 use parser_macros::parser;
 use std::ops::{Deref, DerefMut};
 use swc_atoms::JsWord;
-use swc_common::{BytePos, Span};
+use swc_common::{BytePos, Comments, Span};
 use token::*;
 use Context;
+use Decorators;
 use Session;
+use Syntax;
 
 #[macro_use]
 mod macros;
@@ -18,9 +20,11 @@ mod class_and_fn;
 mod expr;
 mod ident;
 pub mod input;
+mod jsx;
 mod object;
 mod pat;
 mod stmt;
+mod typescript;
 mod util;
 
 /// When error ocurred, error is emiited and parser returnes Err(()).
@@ -38,48 +42,137 @@ struct State {
     labels: Vec<JsWord>,
     /// Start position of an assignment expression.
     potential_arrow_start: Option<BytePos>,
+    /// Errors which are returned instead of being emitted.
+    errors: Option<Vec<Error>>,
 }
 
 #[parser]
 impl<'a, I: Input> Parser<'a, I> {
-    pub fn new(session: Session<'a>, input: I) -> Self {
+    pub fn new(session: Session<'a>, syntax: Syntax, input: I) -> Self {
         Parser {
             session,
-            input: ParserInput::new(Lexer::new(session, input)),
+            input: ParserInput::new(Lexer::new(session, syntax, input)),
             state: Default::default(),
         }
     }
 
-    pub fn parse_script(&mut self) -> PResult<'a, (Vec<Stmt>)> {
+    /// Creates a parser which records comments to `comments`.
+    pub fn with_comments(
+        session: Session<'a>,
+        syntax: Syntax,
+        input: I,
+        comments: &'a Comments,
+    ) -> Self {
+        Parser {
+            session,
+            input: ParserInput::new(Lexer::with_comments(session, syntax, input, comments)),
+            state: Default::default(),
+        }
+    }
+
+    /// Parses a module if `module` is true, and a script otherwise.
+    pub fn parse_program(&mut self, module: bool) -> PResult<'a, Program> {
+        if module {
+            self.parse_module().map(Program::Module)
+        } else {
+            self.parse_script().map(Program::Script)
+        }
+    }
+
+    pub fn parse_script(&mut self) -> PResult<'a, Script> {
         let ctx = Context {
             module: false,
             ..self.ctx()
         };
         self.set_ctx(ctx);
 
-        self.parse_block_body(true, true, None)
+        let start = cur_pos!();
+        let shebang = self.parse_shebang();
+        self.parse_block_body(true, true, None).map(|body| Script {
+            span: span!(start),
+            body,
+            shebang,
+        })
     }
 
     pub fn parse_module(&mut self) -> PResult<'a, Module> {
-        //TOOD: parse() -> PResult<'a, Program>
-        let ctx = Context {
-            module: true,
-            strict: true,
-            ..self.ctx()
-        };
-        // module code is always in strict mode
+        let ctx = self.module_ctx();
         self.set_ctx(ctx);
 
         let start = cur_pos!();
+        let shebang = self.parse_shebang();
         self.parse_block_body(true, true, None).map(|body| Module {
             span: span!(start),
             body,
+            shebang,
         })
     }
 
+    /// Parses a module, skipping statements with syntax errors.
+    ///
+    /// Parsing continues from the next statement after an error. Errors are
+    /// returned instead of being emitted to the handler.
+    pub fn parse_module_recovering(&mut self) -> (Module, Vec<Error>) {
+        let ctx = self.module_ctx();
+        self.set_ctx(ctx);
+        self.state.errors = Some(vec![]);
+
+        let start = cur_pos!();
+        let shebang = self.parse_shebang();
+        let body = self.parse_module_items_recovering();
+        let module = Module {
+            span: span!(start),
+            body,
+            shebang,
+        };
+        (module, self.state.errors.take().unwrap_or_default())
+    }
+
+    /// Parses `#!/usr/bin/env node`, which is lexed only at the start of the
+    /// input.
+    fn parse_shebang(&mut self) -> Option<JsWord> {
+        let is_shebang = match self.input.cur() {
+            Some(&Shebang(..)) => true,
+            _ => false,
+        };
+        if !is_shebang {
+            return None;
+        }
+
+        match bump!() {
+            Shebang(value) => Some(value),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Emits `error` to the handler, or records it if errors are returned to
+    /// the caller.
+    fn emit_error(&mut self, error: Error) {
+        match self.state.errors {
+            Some(ref mut errors) => errors.push(error),
+            None => error.into_diagnostic(self.session.handler).emit(),
+        }
+    }
+
+    /// Context of the top level of a module.
+    fn module_ctx(&self) -> Context {
+        Context {
+            module: true,
+            // module code is always in strict mode
+            strict: true,
+            // top-level await
+            in_async: true,
+            ..self.ctx()
+        }
+    }
+
     const fn ctx(&self) -> Context {
         self.input.get_ctx()
     }
+
+    fn syntax(&self) -> Syntax {
+        self.input.syntax()
+    }
 }
 
 #[cfg(test)]
@@ -87,7 +180,10 @@ pub fn test_parser<F, Ret>(s: &'static str, f: F) -> Ret
 where
     F: for<'a> FnOnce(&'a mut Parser<'a, ::SourceFileInput>) -> Ret,
 {
-    ::with_test_sess(s, |sess, input| Ok(f(&mut Parser::new(sess, input)))).unwrap()
+    ::with_test_sess(s, |sess, input| {
+        Ok(f(&mut Parser::new(sess, Default::default(), input)))
+    })
+    .unwrap()
 }
 
 #[test]
@@ -99,3 +195,113 @@ fn module_legacy() {
         let _ = res.expect_err("!");
     });
 }
+
+#[test]
+fn top_level_await() {
+    test_parser("await a;", |p| p.parse_module().expect("failed to parse a module"));
+    test_parser("await a;", |p| p.parse_script().expect_err("parsed await in a script"));
+}
+
+#[test]
+fn module_only_syntax() {
+    test_parser("import a from 'a';", |p| {
+        p.parse_script().expect_err("parsed import in a script")
+    });
+    test_parser("with (a) {}", |p| {
+        p.parse_module().expect_err("parsed with statement in a module")
+    });
+    test_parser("with (a) {}", |p| p.parse_script().expect("failed to parse a script"));
+}
+
+#[test]
+fn dynamic_import_and_import_meta() {
+    let parse = |syntax: Syntax, s: &'static str, module: bool| {
+        ::with_test_sess(s, |sess, input| {
+            Parser::new(sess, syntax, input).parse_program(module)
+        })
+        .is_ok()
+    };
+    let es = Syntax::Es(::EsConfig {
+        dynamic_import: true,
+        import_meta: true,
+        ..Default::default()
+    });
+
+    assert!(parse(es, "import('a').then(b);", true));
+    assert!(parse(es, "function a() { return import(b); }", true));
+    assert!(parse(es, "import a from 'a'; a(import.meta.url);", true));
+    assert!(parse(es, "import('a');", false));
+    assert!(!parse(es, "import.meta;", false));
+    assert!(!parse(es, "import();", true));
+    assert!(!parse(es, "import(a, b);", true));
+    assert!(!parse(es, "new import(a);", true));
+
+    assert!(!parse(Default::default(), "import('a');", true));
+    assert!(!parse(Default::default(), "import.meta;", true));
+}
+
+#[test]
+fn optional_chaining_and_nullish_coalescing() {
+    let parse = |syntax: Syntax, s: &'static str| {
+        ::with_test_sess(s, |sess, input| {
+            Parser::new(sess, syntax, input).parse_module()
+        })
+        .is_ok()
+    };
+    let es = Syntax::Es(::EsConfig {
+        optional_chaining: true,
+        nullish_coalescing: true,
+        ..Default::default()
+    });
+
+    assert!(parse(es, "a?.b.c; a?.[b](); a?.(b)?.c;"));
+    assert!(parse(es, "a ? .5 : b; a?.5:b;"));
+    assert!(parse(es, "a ?? b ?? c; (a || b) ?? c; a ?? (b && c);"));
+    assert!(!parse(es, "a?.b = c;"));
+    assert!(!parse(es, "a?.b.c++;"));
+    assert!(!parse(es, "new a?.b();"));
+    assert!(!parse(es, "a?.b`c`;"));
+    assert!(!parse(es, "a ?? b || c;"));
+    assert!(!parse(es, "a && b ?? c;"));
+
+    assert!(!parse(Default::default(), "a?.b;"));
+    assert!(!parse(Default::default(), "a ?? b;"));
+}
+
+#[test]
+fn shebang() {
+    let module = test_parser("#!/usr/bin/env node\nfoo();", |p| {
+        p.parse_module().expect("failed to parse a module")
+    });
+    assert_eq!(module.shebang, Some("/usr/bin/env node".into()));
+    assert_eq!(module.body.len(), 1);
+
+    let script = test_parser("#!node", |p| p.parse_script().expect("failed to parse a script"));
+    assert_eq!(script.shebang, Some("node".into()));
+
+    test_parser(" #!node", |p| p.parse_module().expect_err("parsed #! after a space"));
+    test_parser("foo();\n#!node", |p| {
+        p.parse_module().expect_err("parsed #! after a statement")
+    });
+}
+
+#[test]
+fn escaped_keywords() {
+    let parse = |s: &'static str, module: bool| {
+        ::with_test_sess(s, |sess, input| {
+            Parser::new(sess, Default::default(), input).parse_program(module)
+        })
+        .is_ok()
+    };
+
+    assert!(parse(r"var \u{61}b = 1; ab;", true));
+    assert!(parse(r"a.\u0069f; ({ \u0069f: 1 });", true));
+    assert!(parse(r"var l\u0065t, yi\u0065ld;", false));
+    assert!(!parse(r"var \u0069f;", false));
+    assert!(!parse(r"\u0069f (a) {}", false));
+    assert!(!parse(r"n\u0075ll;", false));
+    assert!(!parse(r"var l\u0065t;", true));
+    assert!(!parse(r"var \u0061wait;", true));
+    assert!(!parse(r"var \u0030a;", false));
+    assert!(!parse(r"var a\u002F;", false));
+}
@@ -39,18 +39,26 @@ impl<'a, I: Input> Parser<'a, I> {
 
         let v = match *cur!()? {
             Token::Str { .. } => match bump!() {
-                Token::Str { value, has_escape } => PropName::Str(Str {
-                    span: span!(start),
-                    value,
-                    has_escape,
-                }),
+                Token::Str { value, has_escape } => {
+                    let span = span!(start);
+                    PropName::Str(Str {
+                        span,
+                        value,
+                        has_escape,
+                        raw: Some(self.input.src_of(span)),
+                    })
+                }
                 _ => unreachable!(),
             },
             Num(_) => match bump!() {
-                Num(value) => PropName::Num(Number {
-                    span: span!(start),
-                    value,
-                }),
+                Num(value) => {
+                    let span = span!(start);
+                    PropName::Num(Number {
+                        span,
+                        value,
+                        raw: Some(self.input.src_of(span)),
+                    })
+                }
                 _ => unreachable!(),
             },
             Word(..) => match bump!() {
@@ -62,12 +70,12 @@ impl<'a, I: Input> Parser<'a, I> {
             },
             LBracket => {
                 bump!();
-                let expr = self
-                    .include_in_expr(true)
-                    .parse_assignment_expr()
-                    .map(PropName::Computed)?;
+                let expr = self.include_in_expr(true).parse_assignment_expr()?;
                 expect!(']');
-                expr
+                PropName::Computed(ComputedPropName {
+                    span: span!(start),
+                    expr,
+                })
             }
             _ => unexpected!(),
         };
@@ -132,7 +140,7 @@ impl<'a, I: Input> ParseObject<'a, (Box<Expr>)> for Parser<'a, I> {
         }
 
         // Handle `a(){}` (and async(){} / get(){} / set(){})
-        if is!('(') {
+        if is!('(') || (self.syntax().typescript() && is!('<')) {
             return self
                 .parse_fn_args_body(start, Parser::parse_unique_formal_params, None, None)
                 .map(|function| box Prop::Method(MethodProp { key, function }))
@@ -177,13 +185,13 @@ impl<'a, I: Input> ParseObject<'a, (Box<Expr>)> for Parser<'a, I> {
                             PropOrSpread::Prop(box Prop::Getter(GetterProp {
                                 span: span!(start),
                                 key,
-                                body,
+                                body: body.unwrap(),
                             }))
                         }),
                     js_word!("set") => self
                         .parse_fn_args_body(
                             start,
-                            |p| p.parse_formal_param().map(|pat| vec![pat]),
+                            |p| p.parse_param(false).map(|param| vec![param]),
                             None,
                             None,
                         )
@@ -192,8 +200,8 @@ impl<'a, I: Input> ParseObject<'a, (Box<Expr>)> for Parser<'a, I> {
                             PropOrSpread::Prop(box Prop::Setter(SetterProp {
                                 span: span!(start),
                                 key,
-                                body,
-                                param: params.into_iter().next().unwrap(),
+                                body: body.unwrap(),
+                                param: params.into_iter().next().unwrap().pat,
                             }))
                         }),
                     js_word!("async") => self
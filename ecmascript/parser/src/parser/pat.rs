@@ -1,5 +1,5 @@
 //! 13.3.3 Destructuring Binding Patterns
-use super::{util::ExprExt, *};
+use super::{expr::PatOrExprOrSpread, util::ExprExt, *};
 use std::iter;
 use swc_common::Spanned;
 
@@ -110,12 +110,51 @@ impl<'a, I: Input> Parser<'a, I> {
 
     /// spec: 'FormalParameter'
     pub(super) fn parse_formal_param(&mut self) -> PResult<'a, Pat> {
-        self.parse_binding_element()
+        if !self.syntax().typescript() {
+            return self.parse_binding_element();
+        }
+
+        // `a?: T = b`
+        let start = cur_pos!();
+        let pat = self.parse_binding_pat_or_ident()?;
+        let pat = self.parse_ts_typed_pat(start, pat, true)?;
+
+        if eat!('=') {
+            let right = self.include_in_expr(true).parse_assignment_expr()?;
+            return Ok(Pat::Assign(AssignPat {
+                span: span!(start),
+                left: box pat,
+                right,
+            }));
+        }
+
+        Ok(pat)
     }
 
+    /// Parses a parameter of a function or a method.
+    ///
+    /// Only parameters of class methods can be decorated.
+    pub(super) fn parse_param(&mut self, allow_decorators: bool) -> PResult<'a, Param> {
+        let start = cur_pos!();
+        let decorators = if allow_decorators {
+            self.parse_param_decorators()?
+        } else {
+            vec![]
+        };
+        let pat = self.parse_formal_param()?;
+
+        Ok(Param {
+            span: span!(start),
+            decorators,
+            pat,
+        })
+    }
+
+    /// Parses parameters which may be decorated if `allow_decorators` is
+    /// true.
     ///
     /// spec: 'FormalParameterList'
-    pub(super) fn parse_formal_params(&mut self) -> PResult<'a, (Vec<Pat>)> {
+    pub(super) fn parse_params(&mut self, allow_decorators: bool) -> PResult<'a, (Vec<Param>)> {
         let mut first = true;
         let mut params = vec![];
 
@@ -130,29 +169,58 @@ impl<'a, I: Input> Parser<'a, I> {
                 }
             }
 
+            let param_start = cur_pos!();
+            let decorators = if allow_decorators {
+                self.parse_param_decorators()?
+            } else {
+                vec![]
+            };
+
             let start = cur_pos!();
 
             if eat!("...") {
                 let dot3_token = span!(start);
 
+                let pat_start = cur_pos!();
                 let pat = self.parse_binding_pat_or_ident()?;
+                let pat = if self.syntax().typescript() {
+                    self.parse_ts_typed_pat(pat_start, pat, false)?
+                } else {
+                    pat
+                };
                 let pat = Pat::Rest(RestPat {
                     dot3_token,
                     arg: box pat,
                 });
-                params.push(pat);
+                params.push(Param {
+                    span: span!(param_start),
+                    decorators,
+                    pat,
+                });
                 break;
             } else {
-                params.push(self.parse_binding_element()?);
+                let pat = self.parse_formal_param()?;
+                params.push(Param {
+                    span: span!(param_start),
+                    decorators,
+                    pat,
+                });
             }
         }
 
         Ok(params)
     }
 
-    pub(super) fn parse_unique_formal_params(&mut self) -> PResult<'a, (Vec<Pat>)> {
+    /// Parses parameters of an arrow function or a function type, which can't
+    /// be decorated.
+    pub(super) fn parse_formal_params(&mut self) -> PResult<'a, (Vec<Pat>)> {
+        let params = self.parse_params(false)?;
+        Ok(params.into_iter().map(|param| param.pat).collect())
+    }
+
+    pub(super) fn parse_unique_formal_params(&mut self) -> PResult<'a, (Vec<Param>)> {
         // FIXME: This is wrong.
-        self.parse_formal_params()
+        self.parse_params(false)
     }
 }
 ///
@@ -229,6 +297,7 @@ impl<'a, I: Input> Parser<'a, I> {
 
                 Expr::Member(..)
                 | Expr::Call(..)
+                | Expr::OptChain(..)
                 | Expr::New(..)
                 | Expr::Lit(..)
                 | Expr::Ident(..)
@@ -410,7 +479,7 @@ impl<'a, I: Input> Parser<'a, I> {
 
     pub(super) fn parse_exprs_as_params(
         &mut self,
-        mut exprs: Vec<ExprOrSpread>,
+        mut exprs: Vec<PatOrExprOrSpread>,
     ) -> PResult<'a, (Vec<Pat>)> {
         let pat_ty = PatType::BindingPat;
 
@@ -423,10 +492,17 @@ impl<'a, I: Input> Parser<'a, I> {
 
         for expr in exprs.drain(..len - 1) {
             match expr {
-                ExprOrSpread {
-                    spread: Some(..), ..
-                } => syntax_error!(expr.span(), SyntaxError::NonLastRestParam),
-                ExprOrSpread { expr, .. } => params.push(self.reparse_expr_as_pat(pat_ty, expr)?),
+                PatOrExprOrSpread::ExprOrSpread(ExprOrSpread {
+                    spread: Some(..),
+                    ref expr,
+                }) => syntax_error!(expr.span(), SyntaxError::NonLastRestParam),
+                PatOrExprOrSpread::Pat(ref pat @ Pat::Rest(..)) => {
+                    syntax_error!(pat.span(), SyntaxError::NonLastRestParam)
+                }
+                PatOrExprOrSpread::ExprOrSpread(ExprOrSpread { expr, .. }) => {
+                    params.push(self.reparse_expr_as_pat(pat_ty, expr)?)
+                }
+                PatOrExprOrSpread::Pat(pat) => params.push(pat),
             }
         }
 
@@ -434,16 +510,19 @@ impl<'a, I: Input> Parser<'a, I> {
         let expr = exprs.into_iter().next().unwrap();
         let last = match expr {
             // Rest
-            ExprOrSpread {
+            PatOrExprOrSpread::ExprOrSpread(ExprOrSpread {
                 spread: Some(dot3_token),
                 expr,
-            } => self.reparse_expr_as_pat(pat_ty, expr).map(|pat| {
+            }) => self.reparse_expr_as_pat(pat_ty, expr).map(|pat| {
                 Pat::Rest(RestPat {
                     dot3_token,
                     arg: box pat,
                 })
             })?,
-            ExprOrSpread { expr, .. } => self.reparse_expr_as_pat(pat_ty, expr)?,
+            PatOrExprOrSpread::ExprOrSpread(ExprOrSpread { expr, .. }) => {
+                self.reparse_expr_as_pat(pat_ty, expr)?
+            }
+            PatOrExprOrSpread::Pat(pat) => pat,
         };
         params.push(last);
 
@@ -54,12 +54,63 @@ impl<'a, I: Input> Parser<'a, I> {
         Ok(stmts)
     }
 
+    /// Parses module items until eof, skipping statements with syntax errors.
+    pub(super) fn parse_module_items_recovering(&mut self) -> Vec<ModuleItem> {
+        let mut items = vec![];
+        while self.input.cur().is_some() {
+            match self.parse_stmt_like(true, true) {
+                Ok(item) => items.push(item),
+                Err(()) => self.skip_to_next_stmt(),
+            }
+        }
+        items
+    }
+
+    /// Skips tokens until the start of the next statement.
+    ///
+    /// A statement ends with `;` or an unmatched `}`, or before a line break
+    /// outside of brackets.
+    fn skip_to_next_stmt(&mut self) {
+        let mut depth = 0usize;
+        let mut is_first = true;
+        loop {
+            if !is_first && depth == 0 && self.input.had_line_break_before_cur() {
+                return;
+            }
+            is_first = false;
+
+            let (opens, closes, is_semi) = match self.input.cur() {
+                None => return,
+                Some(&LBrace) | Some(&LParen) | Some(&LBracket) => (true, false, false),
+                Some(&RBrace) | Some(&RParen) | Some(&RBracket) => (false, true, false),
+                Some(&Semi) => (false, false, true),
+                Some(_) => (false, false, false),
+            };
+
+            if let Token::Error(e) = self.input.bump() {
+                // Errors of the lexer are reported even if they are skipped.
+                self.emit_error(e);
+            }
+
+            if opens {
+                depth += 1;
+            } else if closes {
+                if depth == 0 {
+                    return;
+                }
+                depth -= 1;
+            } else if is_semi && depth == 0 {
+                return;
+            }
+        }
+    }
+
     pub fn parse_stmt(&mut self, top_level: bool) -> PResult<'a, Stmt> {
-        self.parse_stmt_internal(false, top_level)
+        self.parse_stmt_internal(false, top_level, vec![])
     }
 
     fn parse_stmt_list_item(&mut self, top_level: bool) -> PResult<'a, Stmt> {
-        self.parse_stmt_internal(true, top_level)
+        self.parse_stmt_internal(true, top_level, vec![])
     }
 
     /// Parse a statement, declaration or module item.
@@ -68,14 +119,30 @@ impl<'a, I: Input> Parser<'a, I> {
         Self: StmtLikeParser<'a, Type>,
         Type: IsDirective + From<Stmt>,
     {
-        if is_one_of!("import", "export") {
-            return self.handle_import_export(top_level);
+        let decorators = self.parse_decorators()?;
+
+        // `import()` and `import.meta` are expressions.
+        let is_import_expr = is!("import") && (peeked_is!('(') || peeked_is!('.'));
+        if is_one_of!("import", "export") && !is_import_expr {
+            return self.handle_import_export(top_level, decorators);
         }
-        self.parse_stmt_internal(include_decl, top_level)
+        self.parse_stmt_internal(include_decl, top_level, decorators)
             .map(From::from)
     }
 
-    fn parse_stmt_internal(&mut self, include_decl: bool, top_level: bool) -> PResult<'a, Stmt> {
+    fn parse_stmt_internal(
+        &mut self,
+        include_decl: bool,
+        top_level: bool,
+        decorators: Vec<Decorator>,
+    ) -> PResult<'a, Stmt> {
+        if !decorators.is_empty() && !is!("class") {
+            syntax_error!(
+                span!(decorators[0].span.lo()),
+                SyntaxError::TrailingDecorator
+            )
+        }
+
         let start = cur_pos!();
 
         if is_one_of!("break", "continue") {
@@ -124,7 +191,7 @@ impl<'a, I: Input> Parser<'a, I> {
             if !include_decl {
                 unexpected!()
             }
-            return self.parse_class_decl().map(Stmt::from);
+            return self.parse_class_decl(decorators).map(Stmt::from);
         }
 
         if is!("if") {
@@ -155,6 +222,12 @@ impl<'a, I: Input> Parser<'a, I> {
             return self.parse_while_stmt();
         }
 
+        if include_decl {
+            if let Some(decl) = self.try_parse_ts_decl()? {
+                return Ok(Stmt::Decl(decl));
+            }
+        }
+
         if is!("var") || (include_decl && is!("const")) {
             let v = self.parse_var_stmt(false)?;
             return Ok(Stmt::Decl(Decl::Var(v)));
@@ -211,7 +284,10 @@ impl<'a, I: Input> Parser<'a, I> {
         };
 
         if eat!(';') {
-            Ok(Stmt::Expr(expr))
+            Ok(Stmt::Expr(ExprStmt {
+                span: span!(start),
+                expr,
+            }))
         } else {
             syntax_error!(SyntaxError::ExpectedSemiForExprStmt { expr: expr.span() });
         }
@@ -400,7 +476,7 @@ impl<'a, I: Input> Parser<'a, I> {
         }
     }
 
-    fn parse_var_stmt(&mut self, for_loop: bool) -> PResult<'a, VarDecl> {
+    pub(super) fn parse_var_stmt(&mut self, for_loop: bool) -> PResult<'a, VarDecl> {
         let start = cur_pos!();
         let kind = match bump!() {
             tok!("const") => VarDeclKind::Const,
@@ -423,6 +499,7 @@ impl<'a, I: Input> Parser<'a, I> {
         Ok(VarDecl {
             span: span!(start),
             kind,
+            declare: false,
             decls,
         })
     }
@@ -430,6 +507,11 @@ impl<'a, I: Input> Parser<'a, I> {
     fn parse_var_declarator(&mut self, for_loop: bool) -> PResult<'a, VarDeclarator> {
         let start = cur_pos!();
         let name = self.parse_binding_pat_or_ident()?;
+        let name = if self.syntax().typescript() {
+            self.parse_ts_typed_pat(start, name, false)?
+        } else {
+            name
+        };
 
         //FIXME: This is wrong. Should check in/of only on first loop.
         let init = if !for_loop || !is_one_of!("in", "of") {
@@ -438,7 +520,11 @@ impl<'a, I: Input> Parser<'a, I> {
             } else {
                 // Destructuring bindings require initializers.
                 match name {
-                    Pat::Ident(..) => None,
+                    Pat::Ident(..)
+                    | Pat::TsTyped(TsTypedPat {
+                        pat: box Pat::Ident(..),
+                        ..
+                    }) => None,
                     _ => syntax_error!(span!(start), SyntaxError::PatVarWithoutInit),
                 }
             }
@@ -693,11 +779,14 @@ pub(super) trait IsDirective {
     fn as_ref(&self) -> Option<&Stmt>;
     fn is_use_strict(&self) -> bool {
         match self.as_ref() {
-            Some(&Stmt::Expr(box Expr::Lit(Lit::Str(Str {
-                ref value,
-                has_escape: false,
+            Some(&Stmt::Expr(ExprStmt {
+                expr: box Expr::Lit(Lit::Str(Str {
+                    ref value,
+                    has_escape: false,
+                    ..
+                })),
                 ..
-            })))) => value == "use strict",
+            })) => value == "use strict",
             _ => false,
         }
     }
@@ -710,12 +799,16 @@ impl IsDirective for Stmt {
 }
 
 pub(super) trait StmtLikeParser<'a, Type: IsDirective> {
-    fn handle_import_export(&mut self, top_level: bool) -> PResult<'a, Type>;
+    fn handle_import_export(
+        &mut self,
+        top_level: bool,
+        decorators: Vec<Decorator>,
+    ) -> PResult<'a, Type>;
 }
 
 #[parser]
 impl<'a, I: Input> StmtLikeParser<'a, Stmt> for Parser<'a, I> {
-    fn handle_import_export(&mut self, top_level: bool) -> PResult<'a, Stmt> {
+    fn handle_import_export(&mut self, top_level: bool, _: Vec<Decorator>) -> PResult<'a, Stmt> {
         syntax_error!(SyntaxError::ImportExportInScript);
     }
 }
@@ -738,9 +831,42 @@ mod tests {
         })
     }
 
+    #[test]
+    fn recover() {
+        use swc_common::{errors::Handler, FileName, FilePathMapping, SourceMap};
+
+        ::swc_common::GLOBALS.set(&::swc_common::Globals::new(), || {
+            let cm = SourceMap::new(FilePathMapping::empty());
+            let fm = cm.new_source_file(
+                FileName::Custom("test.js".into()),
+                "a;\nvar 1 = 2;\n1 + ;\nb;".into(),
+            );
+            let (handler, emitted) = Handler::with_buffer(Default::default());
+            let logger = ::slog::Logger::root(::slog::Discard, o!());
+            let session = ::Session {
+                handler: &handler,
+                logger: &logger,
+            };
+
+            let (module, errors) =
+                Parser::new(session, Default::default(), ::SourceFileInput::from(&*fm))
+                    .parse_module_recovering();
+
+            assert_eq!(module.body.len(), 2);
+            assert_eq!(errors.len(), 2);
+            assert!(emitted.is_empty());
+        });
+    }
+
     #[test]
     fn expr_stmt() {
-        assert_eq_ignore_span!(stmt("a + b + c"), Stmt::Expr(expr("a + b + c")))
+        assert_eq_ignore_span!(
+            stmt("a + b + c"),
+            Stmt::Expr(ExprStmt {
+                span,
+                expr: expr("a + b + c"),
+            })
+        )
     }
     #[test]
     fn throw_this() {
@@ -763,6 +889,7 @@ mod tests {
                 left: VarDeclOrPat::VarDecl(VarDecl {
                     span,
                     kind: VarDeclKind::Const,
+                    declare: false,
                     decls: vec![VarDeclarator {
                         span,
                         init: None,
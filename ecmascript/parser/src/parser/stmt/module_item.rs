@@ -11,13 +11,16 @@ impl<'a, I: Input> Parser<'a, I> {
         match *cur!()? {
             Token::Str { .. } => match bump!() {
                 Token::Str { value, has_escape } => {
+                    let str_span = span!(str_start);
+                    let raw = Some(self.input.src_of(str_span));
                     expect!(';');
                     return Ok(ModuleDecl::Import(ImportDecl {
                         span: span!(start),
                         src: Str {
-                            span: span!(str_start),
+                            span: str_span,
                             value,
                             has_escape,
+                            raw,
                         },
                         specifiers: vec![],
                     }));
@@ -126,21 +129,18 @@ impl<'a, I: Input> Parser<'a, I> {
         self.with_ctx(ctx).parse_binding_ident()
     }
 
-    fn parse_export(&mut self) -> PResult<'a, ModuleDecl> {
-        let start = cur_pos!();
+    fn parse_export(&mut self, decorators: Vec<Decorator>) -> PResult<'a, ModuleDecl> {
+        let start = match decorators.first() {
+            Some(dec) => dec.span.lo(),
+            None => cur_pos!(),
+        };
         assert_and_bump!("export");
+        let is_default = eat!("default");
+        let decorators = self.parse_decorators_after_export(decorators)?;
 
-        if eat!('*') {
-            let src = self.parse_from_clause_and_semi()?;
-            return Ok(ModuleDecl::ExportAll(ExportAll {
-                span: span!(start),
-                src,
-            }));
-        }
-
-        if eat!("default") {
+        if is_default {
             let decl = if is!("class") {
-                self.parse_default_class()?
+                self.parse_default_class(decorators)?
             } else if is!("async")
                 && peeked_is!("function")
                 && !self.input.has_linebreak_between_cur_and_peeked()
@@ -151,14 +151,35 @@ impl<'a, I: Input> Parser<'a, I> {
             } else {
                 let expr = self.include_in_expr(true).parse_assignment_expr()?;
                 expect!(';');
-                return Ok(ModuleDecl::ExportDefaultExpr(expr));
+                return Ok(ModuleDecl::ExportDefaultExpr(ExportDefaultExpr {
+                    span: span!(start),
+                    expr,
+                }));
             };
 
-            return Ok(ModuleDecl::ExportDefaultDecl(decl));
+            return Ok(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                span: span!(start),
+                decl,
+            }));
+        }
+
+        if eat!('*') {
+            let src = self.parse_from_clause_and_semi()?;
+            return Ok(ModuleDecl::ExportAll(ExportAll {
+                span: span!(start),
+                src,
+            }));
+        }
+
+        if let Some(decl) = self.try_parse_ts_decl()? {
+            return Ok(ModuleDecl::ExportDecl(ExportDecl {
+                span: span!(start),
+                decl,
+            }));
         }
 
         let decl = if is!("class") {
-            self.parse_class_decl()?
+            self.parse_class_decl(decorators)?
         } else if is!("async")
             && peeked_is!("function")
             && !self.input.has_linebreak_between_cur_and_peeked()
@@ -210,7 +231,37 @@ impl<'a, I: Input> Parser<'a, I> {
             }));
         };
 
-        return Ok(ModuleDecl::ExportDecl(decl));
+        return Ok(ModuleDecl::ExportDecl(ExportDecl {
+            span: span!(start),
+            decl,
+        }));
+    }
+
+    /// Parses decorators between `export` and `class`, and merges them with
+    /// ones before `export`.
+    fn parse_decorators_after_export(
+        &mut self,
+        before: Vec<Decorator>,
+    ) -> PResult<'a, (Vec<Decorator>)> {
+        let start = cur_pos!();
+        let after = self.parse_decorators()?;
+        if !after.is_empty() {
+            if self.syntax().decorators() == Some(Decorators::Legacy) {
+                syntax_error!(span!(start), SyntaxError::DecoratorAfterExport)
+            }
+            if !before.is_empty() {
+                syntax_error!(span!(start), SyntaxError::DecoratorsBeforeAndAfterExport)
+            }
+        }
+
+        let decorators = if after.is_empty() { before } else { after };
+        if !decorators.is_empty() && !is!("class") {
+            syntax_error!(
+                span!(decorators[0].span.lo()),
+                SyntaxError::TrailingDecorator
+            )
+        }
+        Ok(decorators)
     }
 
     fn parse_export_specifier(&mut self) -> PResult<'a, ExportSpecifier> {
@@ -238,11 +289,14 @@ impl<'a, I: Input> Parser<'a, I> {
         match *cur!()? {
             Token::Str { .. } => match bump!() {
                 Token::Str { value, has_escape } => {
+                    let span = span!(start);
+                    let raw = Some(self.input.src_of(span));
                     expect!(';');
                     Ok(Str {
                         value,
                         has_escape,
-                        span: span!(start),
+                        span,
+                        raw,
                     })
                 }
                 _ => unreachable!(),
@@ -263,16 +317,26 @@ impl IsDirective for ModuleItem {
 
 #[parser]
 impl<'a, I: Input> StmtLikeParser<'a, ModuleItem> for Parser<'a, I> {
-    fn handle_import_export(&mut self, top_level: bool) -> PResult<'a, ModuleItem> {
+    fn handle_import_export(
+        &mut self,
+        top_level: bool,
+        decorators: Vec<Decorator>,
+    ) -> PResult<'a, ModuleItem> {
         if !top_level {
             syntax_error!(SyntaxError::NonTopLevelImportExport);
         }
 
         let start = cur_pos!();
         let decl = if is!("import") {
+            if !decorators.is_empty() {
+                syntax_error!(
+                    span!(decorators[0].span.lo()),
+                    SyntaxError::TrailingDecorator
+                )
+            }
             self.parse_import()?
         } else if is!("export") {
-            self.parse_export()?
+            self.parse_export(decorators)?
         } else {
             unreachable!(
                 "handle_import_export should not be called if current token isn't import nor \
@@ -0,0 +1,1309 @@
+//! Parser for typescript syntax.
+//!
+//! Methods in this file assume that `Syntax::Typescript` is enabled, except
+//! `try_parse_*` methods which check it by themselves.
+use super::*;
+
+#[parser]
+impl<'a, I: Input> Parser<'a, I> {
+    /// `: T`
+    pub(super) fn parse_ts_type_ann(&mut self) -> PResult<'a, TsTypeAnn> {
+        let start = cur_pos!();
+        expect!(':');
+        let type_ann = self.parse_ts_type()?;
+
+        Ok(TsTypeAnn {
+            span: span!(start),
+            type_ann,
+        })
+    }
+
+    pub(super) fn try_parse_ts_type_ann(&mut self) -> PResult<'a, (Option<TsTypeAnn>)> {
+        if self.syntax().typescript() && is!(':') {
+            self.parse_ts_type_ann().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Wraps `pat` with `Pat::TsTyped` if `?` or a type annotation follows.
+    pub(super) fn parse_ts_typed_pat(
+        &mut self,
+        start: BytePos,
+        pat: Pat,
+        allow_optional: bool,
+    ) -> PResult<'a, Pat> {
+        let optional = allow_optional && eat!('?');
+        let type_ann = if is!(':') {
+            self.parse_ts_type_ann().map(Some)?
+        } else {
+            None
+        };
+
+        if !optional && type_ann.is_none() {
+            return Ok(pat);
+        }
+
+        Ok(Pat::TsTyped(TsTypedPat {
+            span: span!(start),
+            pat: box pat,
+            optional,
+            type_ann,
+        }))
+    }
+
+    pub(super) fn try_parse_ts_type_params(&mut self) -> PResult<'a, (Option<TsTypeParamDecl>)> {
+        if self.syntax().typescript() && is!('<') {
+            self.parse_ts_type_params().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// `<T, U extends V = W>`
+    fn parse_ts_type_params(&mut self) -> PResult<'a, TsTypeParamDecl> {
+        let start = cur_pos!();
+        assert_and_bump!('<');
+
+        let mut params = vec![];
+        while !eof!() && !self.is_ts_gt() {
+            params.push(self.parse_ts_type_param()?);
+            if !eat!(',') {
+                break;
+            }
+        }
+        self.expect_ts_gt()?;
+
+        Ok(TsTypeParamDecl {
+            span: span!(start),
+            params,
+        })
+    }
+
+    fn parse_ts_type_param(&mut self) -> PResult<'a, TsTypeParam> {
+        let start = cur_pos!();
+        let name = self.parse_binding_ident()?;
+
+        let constraint = if eat!("extends") {
+            self.parse_ts_type().map(Some)?
+        } else {
+            None
+        };
+        let default = if eat!('=') {
+            self.parse_ts_type().map(Some)?
+        } else {
+            None
+        };
+
+        Ok(TsTypeParam {
+            span: span!(start),
+            name,
+            constraint,
+            default,
+        })
+    }
+
+    pub(super) fn try_parse_ts_type_args(
+        &mut self,
+    ) -> PResult<'a, (Option<TsTypeParamInstantiation>)> {
+        if self.syntax().typescript() && is!('<') {
+            self.parse_ts_type_args().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// `<string, T>`
+    pub(super) fn parse_ts_type_args(&mut self) -> PResult<'a, TsTypeParamInstantiation> {
+        let start = cur_pos!();
+        assert_and_bump!('<');
+
+        let mut params = vec![];
+        while !eof!() && !self.is_ts_gt() {
+            params.push(self.parse_ts_type()?);
+            if !eat!(',') {
+                break;
+            }
+        }
+        self.expect_ts_gt()?;
+
+        Ok(TsTypeParamInstantiation {
+            span: span!(start),
+            params,
+        })
+    }
+
+    /// Returns true if the current token starts with `>`.
+    fn is_ts_gt(&mut self) -> bool {
+        match self.input.cur() {
+            Some(&BinOp(Gt))
+            | Some(&BinOp(GtEq))
+            | Some(&BinOp(RShift))
+            | Some(&BinOp(ZeroFillRShift))
+            | Some(&AssignOp(RShiftAssign))
+            | Some(&AssignOp(ZeroFillRShiftAssign)) => true,
+            _ => false,
+        }
+    }
+
+    /// Eats `>`, splitting tokens like `>>`.
+    fn expect_ts_gt(&mut self) -> PResult<'a, ()> {
+        if self.is_ts_gt() {
+            self.input.bump_gt();
+        } else {
+            expect!('>');
+        }
+
+        Ok(())
+    }
+
+    /// `Foo` or `a.b.Foo`
+    fn parse_ts_entity_name(&mut self) -> PResult<'a, TsEntityName> {
+        let mut name = TsEntityName::Ident(self.parse_ident_name()?);
+        while eat!('.') {
+            let right = self.parse_ident_name()?;
+            name = TsEntityName::TsQualifiedName(box TsQualifiedName { left: name, right });
+        }
+
+        Ok(name)
+    }
+
+    /// `Foo<T>` used in `extends` and `implements` clauses.
+    pub(super) fn parse_ts_expr_with_type_args(&mut self) -> PResult<'a, TsExprWithTypeArgs> {
+        let start = cur_pos!();
+        let expr = self.parse_ts_entity_name()?;
+        let type_params = if is!('<') {
+            self.parse_ts_type_args().map(Some)?
+        } else {
+            None
+        };
+
+        Ok(TsExprWithTypeArgs {
+            span: span!(start),
+            expr,
+            type_params,
+        })
+    }
+
+    pub(super) fn parse_ts_type(&mut self) -> PResult<'a, (Box<TsType>)> {
+        let start = cur_pos!();
+        let ty = self.parse_ts_non_conditional_type()?;
+
+        if !self.input.had_line_break_before_cur() && eat!("extends") {
+            let extends_type = self.parse_ts_non_conditional_type()?;
+            expect!('?');
+            let true_type = self.parse_ts_type()?;
+            expect!(':');
+            let false_type = self.parse_ts_type()?;
+
+            return Ok(box TsType::TsConditionalType(TsConditionalType {
+                span: span!(start),
+                check_type: ty,
+                extends_type,
+                true_type,
+                false_type,
+            }));
+        }
+
+        Ok(ty)
+    }
+
+    fn parse_ts_non_conditional_type(&mut self) -> PResult<'a, (Box<TsType>)> {
+        let start = cur_pos!();
+
+        // `<T>(a: T) => T`
+        if is!('<') {
+            let type_params = self.parse_ts_type_params().map(Some)?;
+            expect!('(');
+            let params = self.parse_formal_params()?;
+            expect!(')');
+            return self.parse_ts_fn_type_rest(start, type_params, params);
+        }
+
+        // `new (a: T) => U`
+        if eat!("new") {
+            let type_params = if is!('<') {
+                self.parse_ts_type_params().map(Some)?
+            } else {
+                None
+            };
+            expect!('(');
+            let params = self.parse_formal_params()?;
+            expect!(')');
+            let type_ann = self.parse_ts_fn_type_ret()?;
+
+            return Ok(box TsType::TsConstructorType(TsConstructorType {
+                span: span!(start),
+                type_params,
+                params,
+                type_ann,
+            }));
+        }
+
+        self.parse_ts_union_type_or_higher()
+    }
+
+    /// `=> T` of a function type.
+    fn parse_ts_fn_type_ret(&mut self) -> PResult<'a, TsTypeAnn> {
+        let arrow_start = cur_pos!();
+        expect!("=>");
+        let type_ann = self.parse_ts_type()?;
+
+        Ok(TsTypeAnn {
+            span: span!(arrow_start),
+            type_ann,
+        })
+    }
+
+    /// Parses `=> T` after parameters of a function type.
+    fn parse_ts_fn_type_rest(
+        &mut self,
+        start: BytePos,
+        type_params: Option<TsTypeParamDecl>,
+        params: Vec<Pat>,
+    ) -> PResult<'a, (Box<TsType>)> {
+        let type_ann = self.parse_ts_fn_type_ret()?;
+
+        Ok(box TsType::TsFnType(TsFnType {
+            span: span!(start),
+            type_params,
+            params,
+            type_ann,
+        }))
+    }
+
+    /// `| A | B`
+    fn parse_ts_union_type_or_higher(&mut self) -> PResult<'a, (Box<TsType>)> {
+        let start = cur_pos!();
+        // Leading `|` is allowed.
+        let _ = eat!('|');
+
+        let first = self.parse_ts_intersection_type_or_higher()?;
+        if !is!('|') {
+            return Ok(first);
+        }
+
+        let mut types = vec![first];
+        while eat!('|') {
+            types.push(self.parse_ts_intersection_type_or_higher()?);
+        }
+
+        Ok(box TsType::TsUnionType(TsUnionType {
+            span: span!(start),
+            types,
+        }))
+    }
+
+    /// `& A & B`
+    fn parse_ts_intersection_type_or_higher(&mut self) -> PResult<'a, (Box<TsType>)> {
+        let start = cur_pos!();
+        // Leading `&` is allowed.
+        let _ = eat!('&');
+
+        let first = self.parse_ts_type_operator_or_higher()?;
+        if !is!('&') {
+            return Ok(first);
+        }
+
+        let mut types = vec![first];
+        while eat!('&') {
+            types.push(self.parse_ts_type_operator_or_higher()?);
+        }
+
+        Ok(box TsType::TsIntersectionType(TsIntersectionType {
+            span: span!(start),
+            types,
+        }))
+    }
+
+    /// `keyof T`, `unique symbol` or `readonly T[]`
+    fn parse_ts_type_operator_or_higher(&mut self) -> PResult<'a, (Box<TsType>)> {
+        let start = cur_pos!();
+
+        let op = match *cur!()? {
+            tok!("keyof") => Some(TsTypeOperatorOp::KeyOf),
+            tok!("unique") => Some(TsTypeOperatorOp::Unique),
+            tok!("readonly") => Some(TsTypeOperatorOp::ReadOnly),
+            _ => None,
+        };
+
+        match op {
+            Some(op) => {
+                bump!();
+                let type_ann = self.parse_ts_type_operator_or_higher()?;
+
+                Ok(box TsType::TsTypeOperator(TsTypeOperator {
+                    span: span!(start),
+                    op,
+                    type_ann,
+                }))
+            }
+            None => self.parse_ts_postfix_type_or_higher(),
+        }
+    }
+
+    /// `T[]` or `T[K]`
+    fn parse_ts_postfix_type_or_higher(&mut self) -> PResult<'a, (Box<TsType>)> {
+        let start = cur_pos!();
+        let mut ty = self.parse_ts_non_array_type()?;
+
+        while !self.input.had_line_break_before_cur() && eat!('[') {
+            ty = if eat!(']') {
+                box TsType::TsArrayType(TsArrayType {
+                    span: span!(start),
+                    elem_type: ty,
+                })
+            } else {
+                let index_type = self.parse_ts_type()?;
+                expect!(']');
+
+                box TsType::TsIndexedAccessType(TsIndexedAccessType {
+                    span: span!(start),
+                    obj_type: ty,
+                    index_type,
+                })
+            };
+        }
+
+        Ok(ty)
+    }
+
+    fn parse_ts_non_array_type(&mut self) -> PResult<'a, (Box<TsType>)> {
+        let start = cur_pos!();
+
+        let kind = match *cur!()? {
+            Word(Ident(ref w)) => match *w {
+                js_word!("any") => Some(TsKeywordTypeKind::Any),
+                js_word!("unknown") => Some(TsKeywordTypeKind::Unknown),
+                js_word!("number") => Some(TsKeywordTypeKind::Number),
+                js_word!("object") => Some(TsKeywordTypeKind::Object),
+                js_word!("boolean") => Some(TsKeywordTypeKind::Boolean),
+                js_word!("bigint") => Some(TsKeywordTypeKind::BigInt),
+                js_word!("string") => Some(TsKeywordTypeKind::String),
+                js_word!("symbol") => Some(TsKeywordTypeKind::Symbol),
+                js_word!("undefined") => Some(TsKeywordTypeKind::Undefined),
+                js_word!("never") => Some(TsKeywordTypeKind::Never),
+                _ => None,
+            },
+            tok!("void") => Some(TsKeywordTypeKind::Void),
+            tok!("null") => Some(TsKeywordTypeKind::Null),
+            _ => None,
+        };
+        if let Some(kind) = kind {
+            bump!();
+            return Ok(box TsType::TsKeywordType(TsKeywordType {
+                span: span!(start),
+                kind,
+            }));
+        }
+
+        if eat!("this") {
+            return Ok(box TsType::TsThisType(TsThisType { span: span!(start) }));
+        }
+
+        if eat!("typeof") {
+            let expr_name = self.parse_ts_entity_name()?;
+            return Ok(box TsType::TsTypeQuery(TsTypeQuery {
+                span: span!(start),
+                expr_name,
+            }));
+        }
+
+        if is!('{') {
+            return self.parse_ts_type_lit_or_mapped_type();
+        }
+
+        if is!('[') {
+            return self.parse_ts_tuple_type();
+        }
+
+        if is!('(') {
+            return self.parse_ts_paren_or_fn_type();
+        }
+
+        // Literal types
+        if {
+            match *cur!()? {
+                tok!("true") | tok!("false") | Num(..) | Token::Str { .. } | tok!('-') => true,
+                _ => false,
+            }
+        } {
+            return self.parse_ts_lit_type();
+        }
+
+        if is!(IdentName) {
+            let type_name = self.parse_ts_entity_name()?;
+            let type_params = if !self.input.had_line_break_before_cur() && is!('<') {
+                self.parse_ts_type_args().map(Some)?
+            } else {
+                None
+            };
+
+            return Ok(box TsType::TsTypeRef(TsTypeRef {
+                span: span!(start),
+                type_name,
+                type_params,
+            }));
+        }
+
+        unexpected!()
+    }
+
+    /// `'a'`, `1`, `-1` or `true`
+    fn parse_ts_lit_type(&mut self) -> PResult<'a, (Box<TsType>)> {
+        let start = cur_pos!();
+
+        let lit = if eat!('-') {
+            match *cur!()? {
+                Num(..) => match bump!() {
+                    Num(value) => TsLit::Number(Number {
+                        span: span!(start),
+                        value: -value,
+                        // Source text of the number does not contain `-`.
+                        raw: None,
+                    }),
+                    _ => unreachable!(),
+                },
+                _ => unexpected!(),
+            }
+        } else {
+            match self.parse_lit()? {
+                Lit::Bool(b) => TsLit::Bool(b),
+                Lit::Num(n) => TsLit::Number(n),
+                Lit::Str(s) => TsLit::Str(s),
+                _ => unreachable!("parse_ts_lit_type should be called only for literal types"),
+            }
+        };
+
+        Ok(box TsType::TsLitType(TsLitType {
+            span: span!(start),
+            lit,
+        }))
+    }
+
+    /// `[T, U]`
+    fn parse_ts_tuple_type(&mut self) -> PResult<'a, (Box<TsType>)> {
+        let start = cur_pos!();
+        assert_and_bump!('[');
+
+        let mut elem_types = vec![];
+        while !eof!() && !is!(']') {
+            elem_types.push(self.parse_ts_type()?);
+            if !eat!(',') {
+                break;
+            }
+        }
+        expect!(']');
+
+        Ok(box TsType::TsTupleType(TsTupleType {
+            span: span!(start),
+            elem_types,
+        }))
+    }
+
+    /// `(T)` or `(a: T) => U`
+    ///
+    /// As the parser can't look ahead more than one token, a parenthesized
+    /// identifier is parsed as a type first and converted into a parameter if
+    /// `:`, `?`, `,` or `) =>` follows.
+    fn parse_ts_paren_or_fn_type(&mut self) -> PResult<'a, (Box<TsType>)> {
+        let start = cur_pos!();
+        assert_and_bump!('(');
+
+        // `() => T` or `(...a: T) => U`
+        if is_one_of!(')', "...") {
+            let params = self.parse_formal_params()?;
+            expect!(')');
+            return self.parse_ts_fn_type_rest(start, None, params);
+        }
+
+        let ty = self.parse_ts_type()?;
+        let ident = match *ty {
+            TsType::TsTypeRef(TsTypeRef {
+                type_name: TsEntityName::Ident(ref ident),
+                type_params: None,
+                ..
+            }) => Some(ident.clone()),
+            _ => None,
+        };
+
+        match ident {
+            Some(ident) if is_one_of!(':', '?', ',') || (is!(')') && peeked_is!("=>")) => {
+                let param_start = ident.span.lo();
+                let first = self.parse_ts_typed_pat(param_start, Pat::Ident(ident), true)?;
+
+                let mut params = vec![first];
+                if eat!(',') {
+                    params.extend(self.parse_formal_params()?);
+                }
+                expect!(')');
+                self.parse_ts_fn_type_rest(start, None, params)
+            }
+            _ => {
+                expect!(')');
+                Ok(box TsType::TsParenthesizedType(TsParenthesizedType {
+                    span: span!(start),
+                    type_ann: ty,
+                }))
+            }
+        }
+    }
+
+    /// `{ a: T }` or `{ [K in keyof T]: U }`
+    fn parse_ts_type_lit_or_mapped_type(&mut self) -> PResult<'a, (Box<TsType>)> {
+        let start = cur_pos!();
+        assert_and_bump!('{');
+
+        let mut members = vec![];
+
+        // A mapped type is the only member of its braces.
+        let first_start = cur_pos!();
+        let readonly = is!("readonly") && peeked_is!('[');
+        if readonly {
+            bump!();
+        }
+        if eat!('[') {
+            if is!(IdentName) && peeked_is!("in") {
+                return self.parse_ts_mapped_type_rest(start, readonly);
+            }
+
+            members.push(self.parse_ts_bracket_member(first_start, readonly)?);
+            self.eat_ts_member_separator()?;
+        }
+
+        let body = self.parse_ts_type_members()?;
+        members.extend(body);
+
+        Ok(box TsType::TsTypeLit(TsTypeLit {
+            span: span!(start),
+            members,
+        }))
+    }
+
+    /// Parses a mapped type after `[`.
+    fn parse_ts_mapped_type_rest(
+        &mut self,
+        start: BytePos,
+        readonly: bool,
+    ) -> PResult<'a, (Box<TsType>)> {
+        let param_start = cur_pos!();
+        let name = self.parse_ident_name()?;
+        expect!("in");
+        let constraint = self.parse_ts_type().map(Some)?;
+        let type_param = TsTypeParam {
+            span: span!(param_start),
+            name,
+            constraint,
+            default: None,
+        };
+        expect!(']');
+
+        let optional = eat!('?');
+        let type_ann = if eat!(':') {
+            self.parse_ts_type().map(Some)?
+        } else {
+            None
+        };
+        let _ = eat!(';') || eat!(',');
+        expect!('}');
+
+        Ok(box TsType::TsMappedType(TsMappedType {
+            span: span!(start),
+            readonly,
+            type_param,
+            optional,
+            type_ann,
+        }))
+    }
+
+    /// Parses members until `}`, and eats `}`.
+    fn parse_ts_type_members(&mut self) -> PResult<'a, (Vec<TsTypeElement>)> {
+        let mut members = vec![];
+        while !eof!() && !is!('}') {
+            members.push(self.parse_ts_type_member()?);
+            self.eat_ts_member_separator()?;
+        }
+        expect!('}');
+
+        Ok(members)
+    }
+
+    /// Members are separated by `;` or `,`, which can be omitted before a line
+    /// break or `}`.
+    fn eat_ts_member_separator(&mut self) -> PResult<'a, ()> {
+        if !eat!(',') {
+            expect!(';');
+        }
+
+        Ok(())
+    }
+
+    fn parse_ts_type_member(&mut self) -> PResult<'a, TsTypeElement> {
+        let start = cur_pos!();
+
+        // `(a: T): U` or `<T>(a: T): U`
+        if is_one_of!('(', '<') {
+            let (type_params, params, type_ann) = self.parse_ts_signature()?;
+            return Ok(TsTypeElement::TsCallSignatureDecl(TsCallSignatureDecl {
+                span: span!(start),
+                type_params,
+                params,
+                type_ann,
+            }));
+        }
+
+        // `new (a: T): U`
+        if is!("new") && { peeked_is!('(') || peeked_is!('<') } {
+            assert_and_bump!("new");
+            let (type_params, params, type_ann) = self.parse_ts_signature()?;
+            return Ok(TsTypeElement::TsConstructSignatureDecl(
+                TsConstructSignatureDecl {
+                    span: span!(start),
+                    type_params,
+                    params,
+                    type_ann,
+                },
+            ));
+        }
+
+        // `readonly` is a modifier only if a property name follows.
+        let readonly = is!("readonly") && {
+            match *peek!()? {
+                Word(..) | Token::Str { .. } | Num(..) | LBracket => true,
+                _ => false,
+            }
+        };
+        if readonly {
+            bump!();
+        }
+
+        if eat!('[') {
+            return self.parse_ts_bracket_member(start, readonly);
+        }
+
+        let key = self.parse_prop_name()?;
+        self.parse_ts_property_or_method_signature(start, readonly, key)
+    }
+
+    /// Parses an index signature or a member with a computed key after `[`.
+    fn parse_ts_bracket_member(
+        &mut self,
+        start: BytePos,
+        readonly: bool,
+    ) -> PResult<'a, TsTypeElement> {
+        // Start of `[`.
+        let key_start = self.input.prev_span().lo();
+        let is_index_signature = is!(IdentName) && peeked_is!(':');
+        if is_index_signature {
+            let param_start = cur_pos!();
+            let name = self.parse_ident_name()?;
+            let param = self.parse_ts_typed_pat(param_start, Pat::Ident(name), false)?;
+            expect!(']');
+            let type_ann = self.try_parse_ts_type_ann()?;
+
+            return Ok(TsTypeElement::TsIndexSignature(TsIndexSignature {
+                span: span!(start),
+                readonly,
+                params: vec![param],
+                type_ann,
+            }));
+        }
+
+        let expr = self.include_in_expr(true).parse_assignment_expr()?;
+        expect!(']');
+        let key = PropName::Computed(ComputedPropName {
+            span: span!(key_start),
+            expr,
+        });
+        self.parse_ts_property_or_method_signature(start, readonly, key)
+    }
+
+    fn parse_ts_property_or_method_signature(
+        &mut self,
+        start: BytePos,
+        readonly: bool,
+        key: PropName,
+    ) -> PResult<'a, TsTypeElement> {
+        let optional = eat!('?');
+
+        if is_one_of!('(', '<') {
+            if readonly {
+                syntax_error!(span!(start), SyntaxError::TsReadonlyMethod)
+            }
+
+            let (type_params, params, type_ann) = self.parse_ts_signature()?;
+            return Ok(TsTypeElement::TsMethodSignature(TsMethodSignature {
+                span: span!(start),
+                key,
+                optional,
+                type_params,
+                params,
+                type_ann,
+            }));
+        }
+
+        let type_ann = self.try_parse_ts_type_ann()?;
+        Ok(TsTypeElement::TsPropertySignature(TsPropertySignature {
+            span: span!(start),
+            readonly,
+            key,
+            optional,
+            type_ann,
+        }))
+    }
+
+    /// `<T>(a: T): U` of call signatures and methods.
+    fn parse_ts_signature(
+        &mut self,
+    ) -> PResult<'a, (Option<TsTypeParamDecl>, Vec<Pat>, Option<TsTypeAnn>)> {
+        let type_params = self.try_parse_ts_type_params()?;
+        expect!('(');
+        let params = self.parse_formal_params()?;
+        expect!(')');
+        let type_ann = self.try_parse_ts_type_ann()?;
+
+        Ok((type_params, params, type_ann))
+    }
+}
+
+/// Declarations.
+#[parser]
+impl<'a, I: Input> Parser<'a, I> {
+    /// Parses a declaration which only exists in typescript, or a declaration
+    /// prefixed with `declare`.
+    ///
+    /// Returns `None` if the current token does not start such declaration.
+    pub(super) fn try_parse_ts_decl(&mut self) -> PResult<'a, (Option<Decl>)> {
+        if !self.syntax().typescript() {
+            return Ok(None);
+        }
+
+        let start = cur_pos!();
+
+        // `const enum Foo {}`
+        if is!("const") && peeked_is!("enum") {
+            assert_and_bump!("const");
+            assert_and_bump!("enum");
+            return self
+                .parse_ts_enum_decl(start, false, true)
+                .map(Decl::TsEnum)
+                .map(Some);
+        }
+
+        if is!("declare") && self.is_ts_next_word_on_same_line() {
+            assert_and_bump!("declare");
+            return self.parse_ts_declare_decl(start).map(Some);
+        }
+
+        self.try_parse_ts_decl_with_keyword(start, false)
+    }
+
+    /// Parses a declaration after `declare`.
+    fn parse_ts_declare_decl(&mut self, start: BytePos) -> PResult<'a, Decl> {
+        if is!("const") && peeked_is!("enum") {
+            assert_and_bump!("const");
+            assert_and_bump!("enum");
+            return self
+                .parse_ts_enum_decl(start, true, true)
+                .map(Decl::TsEnum);
+        }
+
+        if is_one_of!("var", "let", "const") {
+            let v = self.parse_var_stmt(false)?;
+            return Ok(Decl::Var(VarDecl {
+                span: span!(start),
+                declare: true,
+                ..v
+            }));
+        }
+
+        if is!("function") {
+            return match self.parse_fn_decl()? {
+                Decl::Fn(f) => Ok(Decl::Fn(FnDecl { declare: true, ..f })),
+                _ => unreachable!(),
+            };
+        }
+
+        if is!("class") {
+            return match self.parse_class_decl(vec![])? {
+                Decl::Class(c) => Ok(Decl::Class(ClassDecl { declare: true, ..c })),
+                _ => unreachable!(),
+            };
+        }
+
+        match self.try_parse_ts_decl_with_keyword(start, true)? {
+            Some(decl) => Ok(decl),
+            None => unexpected!(),
+        }
+    }
+
+    /// `interface`, `type`, `enum`, `namespace` and `module`.
+    fn try_parse_ts_decl_with_keyword(
+        &mut self,
+        start: BytePos,
+        declare: bool,
+    ) -> PResult<'a, (Option<Decl>)> {
+        if is!("enum") {
+            assert_and_bump!("enum");
+            return self
+                .parse_ts_enum_decl(start, declare, false)
+                .map(Decl::TsEnum)
+                .map(Some);
+        }
+
+        if is!("interface") && self.is_ts_next_word_on_same_line() {
+            assert_and_bump!("interface");
+            return self
+                .parse_ts_interface_decl(start, declare)
+                .map(Decl::TsInterface)
+                .map(Some);
+        }
+
+        if is!("type") && self.is_ts_next_word_on_same_line() {
+            assert_and_bump!("type");
+            return self
+                .parse_ts_type_alias_decl(start, declare)
+                .map(Decl::TsTypeAlias)
+                .map(Some);
+        }
+
+        if is!("namespace") && self.is_ts_next_word_on_same_line() {
+            assert_and_bump!("namespace");
+            return self
+                .parse_ts_module_decl(start, declare)
+                .map(Decl::TsModule)
+                .map(Some);
+        }
+
+        // `module Foo {}` or `declare module 'foo' {}`
+        if is!("module") && {
+            let is_name = match self.input.peek() {
+                Some(&Word(..)) | Some(&Token::Str { .. }) => true,
+                _ => false,
+            };
+            is_name && !self.input.has_linebreak_between_cur_and_peeked()
+        } {
+            assert_and_bump!("module");
+            return self
+                .parse_ts_module_decl(start, declare)
+                .map(Decl::TsModule)
+                .map(Some);
+        }
+
+        Ok(None)
+    }
+
+    /// Contextual keywords like `type` start a declaration only if an
+    /// identifier follows on the same line.
+    fn is_ts_next_word_on_same_line(&mut self) -> bool {
+        let is_word = match self.input.peek() {
+            Some(&Word(..)) => true,
+            _ => false,
+        };
+
+        is_word && !self.input.has_linebreak_between_cur_and_peeked()
+    }
+
+    /// Parses an enum after `enum`.
+    fn parse_ts_enum_decl(
+        &mut self,
+        start: BytePos,
+        declare: bool,
+        is_const: bool,
+    ) -> PResult<'a, TsEnumDecl> {
+        let id = self.parse_binding_ident()?;
+
+        expect!('{');
+        let mut members = vec![];
+        while !eof!() && !is!('}') {
+            members.push(self.parse_ts_enum_member()?);
+            if !eat!(',') {
+                break;
+            }
+        }
+        expect!('}');
+
+        Ok(TsEnumDecl {
+            span: span!(start),
+            declare,
+            is_const,
+            id,
+            members,
+        })
+    }
+
+    fn parse_ts_enum_member(&mut self) -> PResult<'a, TsEnumMember> {
+        let start = cur_pos!();
+
+        let id = match *cur!()? {
+            Token::Str { .. } => match self.parse_lit()? {
+                Lit::Str(s) => TsEnumMemberId::Str(s),
+                _ => unreachable!(),
+            },
+            _ => self.parse_ident_name().map(TsEnumMemberId::Ident)?,
+        };
+        let init = if eat!('=') {
+            self.include_in_expr(true)
+                .parse_assignment_expr()
+                .map(Some)?
+        } else {
+            None
+        };
+
+        Ok(TsEnumMember {
+            span: span!(start),
+            id,
+            init,
+        })
+    }
+
+    /// Parses an interface after `interface`.
+    fn parse_ts_interface_decl(
+        &mut self,
+        start: BytePos,
+        declare: bool,
+    ) -> PResult<'a, TsInterfaceDecl> {
+        let id = self.parse_binding_ident()?;
+        let type_params = self.try_parse_ts_type_params()?;
+
+        let mut extends = vec![];
+        if eat!("extends") {
+            extends.push(self.parse_ts_expr_with_type_args()?);
+            while eat!(',') {
+                extends.push(self.parse_ts_expr_with_type_args()?);
+            }
+        }
+
+        let body_start = cur_pos!();
+        expect!('{');
+        let body = self.parse_ts_type_members()?;
+
+        Ok(TsInterfaceDecl {
+            span: span!(start),
+            declare,
+            id,
+            type_params,
+            extends,
+            body: TsInterfaceBody {
+                span: span!(body_start),
+                body,
+            },
+        })
+    }
+
+    /// Parses a type alias after `type`.
+    fn parse_ts_type_alias_decl(
+        &mut self,
+        start: BytePos,
+        declare: bool,
+    ) -> PResult<'a, TsTypeAliasDecl> {
+        let id = self.parse_binding_ident()?;
+        let type_params = self.try_parse_ts_type_params()?;
+        expect!('=');
+        let type_ann = self.parse_ts_type()?;
+        expect!(';');
+
+        Ok(TsTypeAliasDecl {
+            span: span!(start),
+            declare,
+            id,
+            type_params,
+            type_ann,
+        })
+    }
+
+    /// Parses a namespace after `namespace` or `module`.
+    fn parse_ts_module_decl(
+        &mut self,
+        start: BytePos,
+        declare: bool,
+    ) -> PResult<'a, TsModuleDecl> {
+        let id = match *cur!()? {
+            Token::Str { .. } => match self.parse_lit()? {
+                Lit::Str(s) => TsModuleName::Str(s),
+                _ => unreachable!(),
+            },
+            _ => self.parse_binding_ident().map(TsModuleName::Ident)?,
+        };
+
+        let body = if is!('{') {
+            let body_start = cur_pos!();
+            assert_and_bump!('{');
+            let body = self.parse_block_body(false, true, Some(&RBrace))?;
+            Some(TsModuleBlock {
+                span: span!(body_start),
+                body,
+            })
+        } else {
+            // `declare module 'foo';`
+            expect!(';');
+            None
+        };
+
+        Ok(TsModuleDecl {
+            span: span!(start),
+            declare,
+            id,
+            body,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swc_common::DUMMY_SP as span;
+
+    fn ts_module(s: &'static str) -> Module {
+        ::with_test_sess(s, |sess, input| {
+            Parser::new(sess, Syntax::Typescript(Default::default()), input).parse_module()
+        })
+        .unwrap_or_else(|output| panic!("failed to parse typescript:\n{}", output))
+    }
+
+    fn ts_decl(s: &'static str) -> Decl {
+        match ts_module(s).body.pop() {
+            Some(ModuleItem::Stmt(Stmt::Decl(decl))) => decl,
+            item => unreachable!("expected a declaration, got {:?}", item),
+        }
+    }
+
+    fn ts_expr(s: &'static str) -> Box<Expr> {
+        match ts_module(s).body.pop() {
+            Some(ModuleItem::Stmt(Stmt::Expr(ExprStmt { expr, .. }))) => expr,
+            item => unreachable!("expected an expression statement, got {:?}", item),
+        }
+    }
+
+    fn ident(sym: &str) -> Ident {
+        Ident::new(sym.into(), span)
+    }
+
+    fn keyword(kind: TsKeywordTypeKind) -> Box<TsType> {
+        box TsType::TsKeywordType(TsKeywordType { span, kind })
+    }
+
+    fn type_ref(sym: &str) -> Box<TsType> {
+        box TsType::TsTypeRef(TsTypeRef {
+            span,
+            type_name: TsEntityName::Ident(ident(sym)),
+            type_params: None,
+        })
+    }
+
+    fn type_ann(type_ann: Box<TsType>) -> Option<TsTypeAnn> {
+        Some(TsTypeAnn { span, type_ann })
+    }
+
+    fn type_alias(s: &'static str) -> Box<TsType> {
+        match ts_decl(s) {
+            Decl::TsTypeAlias(TsTypeAliasDecl { type_ann, .. }) => type_ann,
+            decl => unreachable!("expected a type alias, got {:?}", decl),
+        }
+    }
+
+    #[test]
+    fn var_type_ann() {
+        assert_eq_ignore_span!(
+            ts_decl("let a: string;"),
+            Decl::Var(VarDecl {
+                span,
+                kind: VarDeclKind::Let,
+                declare: false,
+                decls: vec![VarDeclarator {
+                    span,
+                    name: Pat::TsTyped(TsTypedPat {
+                        span,
+                        pat: box Pat::Ident(ident("a")),
+                        optional: false,
+                        type_ann: type_ann(keyword(TsKeywordTypeKind::String)),
+                    }),
+                    init: None,
+                }],
+            })
+        );
+    }
+
+    #[test]
+    fn union_of_arrays() {
+        assert_eq_ignore_span!(
+            type_alias("type A = B[] | null;"),
+            box TsType::TsUnionType(TsUnionType {
+                span,
+                types: vec![
+                    box TsType::TsArrayType(TsArrayType {
+                        span,
+                        elem_type: type_ref("B"),
+                    }),
+                    keyword(TsKeywordTypeKind::Null),
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn nested_type_args() {
+        assert_eq_ignore_span!(
+            type_alias("type A = B<C<D>>;"),
+            box TsType::TsTypeRef(TsTypeRef {
+                span,
+                type_name: TsEntityName::Ident(ident("B")),
+                type_params: Some(TsTypeParamInstantiation {
+                    span,
+                    params: vec![box TsType::TsTypeRef(TsTypeRef {
+                        span,
+                        type_name: TsEntityName::Ident(ident("C")),
+                        type_params: Some(TsTypeParamInstantiation {
+                            span,
+                            params: vec![type_ref("D")],
+                        }),
+                    })],
+                }),
+            })
+        );
+    }
+
+    #[test]
+    fn fn_type_and_paren_type() {
+        assert_eq_ignore_span!(
+            type_alias("type A = (a: B) => C;"),
+            box TsType::TsFnType(TsFnType {
+                span,
+                type_params: None,
+                params: vec![Pat::TsTyped(TsTypedPat {
+                    span,
+                    pat: box Pat::Ident(ident("a")),
+                    optional: false,
+                    type_ann: type_ann(type_ref("B")),
+                })],
+                type_ann: TsTypeAnn {
+                    span,
+                    type_ann: type_ref("C"),
+                },
+            })
+        );
+
+        assert_eq_ignore_span!(
+            type_alias("type A = (B);"),
+            box TsType::TsParenthesizedType(TsParenthesizedType {
+                span,
+                type_ann: type_ref("B"),
+            })
+        );
+    }
+
+    #[test]
+    fn interface() {
+        assert_eq_ignore_span!(
+            ts_decl("interface A extends B { readonly a?: string; b(): void }"),
+            Decl::TsInterface(TsInterfaceDecl {
+                span,
+                declare: false,
+                id: ident("A"),
+                type_params: None,
+                extends: vec![TsExprWithTypeArgs {
+                    span,
+                    expr: TsEntityName::Ident(ident("B")),
+                    type_params: None,
+                }],
+                body: TsInterfaceBody {
+                    span,
+                    body: vec![
+                        TsTypeElement::TsPropertySignature(TsPropertySignature {
+                            span,
+                            readonly: true,
+                            key: PropName::Ident(ident("a")),
+                            optional: true,
+                            type_ann: type_ann(keyword(TsKeywordTypeKind::String)),
+                        }),
+                        TsTypeElement::TsMethodSignature(TsMethodSignature {
+                            span,
+                            key: PropName::Ident(ident("b")),
+                            optional: false,
+                            type_params: None,
+                            params: vec![],
+                            type_ann: type_ann(keyword(TsKeywordTypeKind::Void)),
+                        }),
+                    ],
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn const_enum() {
+        assert_eq_ignore_span!(
+            ts_decl("const enum A { B, C = 1 }"),
+            Decl::TsEnum(TsEnumDecl {
+                span,
+                declare: false,
+                is_const: true,
+                id: ident("A"),
+                members: vec![
+                    TsEnumMember {
+                        span,
+                        id: TsEnumMemberId::Ident(ident("B")),
+                        init: None,
+                    },
+                    TsEnumMember {
+                        span,
+                        id: TsEnumMemberId::Ident(ident("C")),
+                        init: Some(box Expr::Lit(Lit::Num(Number {
+                            span,
+                            value: 1.0,
+                            raw: Some("1".into()),
+                        }))),
+                    },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn declare_fn_without_body() {
+        match ts_decl("declare function a<T>(b: T): T;") {
+            Decl::Fn(FnDecl {
+                declare: true,
+                function:
+                    Function {
+                        body: None,
+                        type_params: Some(..),
+                        return_type: Some(..),
+                        ..
+                    },
+                ..
+            }) => {}
+            decl => unreachable!("unexpected declaration: {:?}", decl),
+        }
+    }
+
+    #[test]
+    fn type_is_an_ident() {
+        // `type` starts a type alias only if an identifier follows on the same line.
+        assert_eq_ignore_span!(ts_expr("type\nFoo"), box Expr::Ident(ident("Foo")));
+    }
+
+    #[test]
+    fn as_expr() {
+        assert_eq_ignore_span!(
+            ts_expr("a as any"),
+            box Expr::TsAs(TsAsExpr {
+                span,
+                expr: box Expr::Ident(ident("a")),
+                type_ann: keyword(TsKeywordTypeKind::Any),
+            })
+        );
+    }
+
+    #[test]
+    fn arrow_with_typed_params() {
+        match *ts_expr("(a: string, ...b: number[]): number => 1") {
+            Expr::Arrow(ArrowExpr {
+                ref params,
+                return_type: Some(..),
+                ..
+            }) => assert_eq!(params.len(), 2),
+            ref expr => unreachable!("expected an arrow function, got {:?}", expr),
+        }
+    }
+}
@@ -170,9 +170,10 @@ pub(super) trait ExprExt {
                 expr.is_valid_simple_assignment_target(strict)
             }
 
-            Expr::Member(..) => true,
+            // `a?.b.c = 1` is not valid.
+            Expr::Member(..) => !self.is_in_opt_chain(),
 
-            Expr::New(..) | Expr::Call(..) => false,
+            Expr::New(..) | Expr::Call(..) | Expr::OptChain(..) => false,
             // TODO: Spec only mentions `new.target`
             Expr::MetaProp(..) => false,
 
@@ -187,6 +188,29 @@ pub(super) trait ExprExt {
             Expr::Yield(..) | Expr::Arrow(..) | Expr::Assign(..) => false,
 
             Expr::Seq(..) => false,
+
+            Expr::JSXElement(..) | Expr::JSXFragment(..) => false,
+
+            Expr::PrivateName(..) => false,
+
+            // `(a as any) = 1`
+            Expr::TsAs(TsAsExpr { ref expr, .. }) => expr.is_valid_simple_assignment_target(strict),
+        }
+    }
+
+    /// Returns true if `self` is `a?.b` or a subscript of it, like `a?.b.c`.
+    fn is_in_opt_chain(&self) -> bool {
+        match *self.as_expr() {
+            Expr::OptChain(..) => true,
+            Expr::Member(MemberExpr {
+                obj: ExprOrSuper::Expr(ref obj),
+                ..
+            })
+            | Expr::Call(CallExpr {
+                callee: ExprOrSuper::Expr(ref obj),
+                ..
+            }) => obj.is_in_opt_chain(),
+            _ => false,
         }
     }
 }
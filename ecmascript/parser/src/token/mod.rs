@@ -11,7 +11,7 @@ use swc_common::{Fold, Span};
 
 #[derive(Kind, Debug, Clone, PartialEq, Fold)]
 #[kind(functions(starts_expr = "bool", before_expr = "bool"))]
-pub(crate) enum Token {
+pub enum Token {
     /// Identifier, "null", "true", "false".
     ///
     /// Contains `null` and ``
@@ -24,6 +24,8 @@ pub(crate) enum Token {
 
     /// '@'
     At,
+    /// '#'
+    Hash,
     /// '.'
     Dot,
 
@@ -59,7 +61,15 @@ pub(crate) enum Token {
     /// '`'
     #[kind(starts_expr)]
     BackQuote,
-    Template(String),
+    /// Text of a template literal between `` ` ``, `${` and `}`.
+    Template {
+        /// Source text, with line breaks normalized to `\n`.
+        raw: String,
+        /// Text with escapes processed, or the error of an invalid escape. An
+        /// invalid escape is allowed only in tagged templates.
+        #[fold(ignore)]
+        cooked: Result<String, ::error::Error>,
+    },
     /// ':'
     #[kind(before_expr)]
     Colon,
@@ -80,6 +90,8 @@ pub(crate) enum Token {
     /// '?'
     #[kind(before_expr)]
     QuestionMark,
+    /// '?.'
+    QuestionDot,
 
     /// `++`
     #[kind(before_expr, starts_expr)]
@@ -109,6 +121,32 @@ pub(crate) enum Token {
     #[kind(starts_expr)]
     Num(f64),
 
+    /// Bigint literal like `10n`, with digits and the radix prefix
+    /// preserved.
+    #[kind(starts_expr)]
+    BigInt(JsWord),
+
+    /// Identifier of a JSX tag or attribute, which may contain `-`.
+    JSXName {
+        name: JsWord,
+    },
+    /// `#!` at the start of a file, with the text after it like
+    /// `/usr/bin/env node`.
+    Shebang(JsWord),
+
+    /// Text between JSX tags.
+    #[kind(before_expr)]
+    JSXText {
+        /// Text with entities decoded.
+        value: JsWord,
+        raw: JsWord,
+    },
+    /// `<` which starts a JSX tag.
+    #[kind(starts_expr)]
+    JSXTagStart,
+    /// `>` which ends a JSX tag.
+    JSXTagEnd,
+
     Error(#[fold(ignore)] ::error::Error),
 }
 
@@ -171,6 +209,8 @@ pub enum BinOpToken {
     LogicalOr,
     /// `&&`
     LogicalAnd,
+    /// `??`
+    NullishCoalescing,
 }
 
 impl BinOpToken {
@@ -180,7 +220,7 @@ impl BinOpToken {
 }
 
 #[derive(Debug, Clone, PartialEq)]
-pub(crate) struct TokenAndSpan {
+pub struct TokenAndSpan {
     pub token: Token,
     /// Had a line break before this token?
     pub had_line_break: bool,
@@ -504,6 +544,7 @@ impl From<BinOpToken> for BinaryOp {
             BinOpToken::BitAnd => BitAnd,
             BinOpToken::LogicalOr => LogicalOr,
             BinOpToken::LogicalAnd => LogicalAnd,
+            BinOpToken::NullishCoalescing => NullishCoalescing,
             BinOpToken::Exp => Exp,
         }
     }
@@ -0,0 +1,96 @@
+//! Serialization of parsed modules, which is implemented by `swc_ecma_ast`.
+extern crate serde_json;
+extern crate swc_common;
+extern crate swc_ecma_ast;
+extern crate swc_ecma_parser;
+extern crate testing;
+use serde_json::Value;
+use swc_common::FileName;
+use swc_ecma_ast::*;
+use swc_ecma_parser::{EsConfig, Parser, Session, Syntax};
+
+fn parse(src: &str) -> Module {
+    ::testing::run_test(|logger, cm, handler| {
+        let fm = cm.new_source_file(FileName::Custom("test.js".into()), src.into());
+
+        Parser::new(
+            Session {
+                logger: &logger,
+                handler: &handler,
+            },
+            Syntax::Es(EsConfig {
+                jsx: true,
+                ..Default::default()
+            }),
+            (&*fm).into(),
+        )
+        .parse_module()
+    })
+    .expect("failed to parse")
+}
+
+fn round_trip(src: &str) {
+    let module = parse(src);
+    let json = serde_json::to_string(&module).expect("failed to serialize");
+    let deserialized: Module = serde_json::from_str(&json)
+        .unwrap_or_else(|err| panic!("failed to deserialize {}: {}", json, err));
+
+    assert_eq!(module, deserialized, "{}", src);
+}
+
+#[test]
+fn round_trip_stmts() {
+    round_trip("#!/usr/bin/env node\nfoo();");
+    round_trip("a + b * c; x = y ? /re/g : null; new Foo(...args); a.b[c]();");
+    round_trip("label: while (x) { try { throw y; } catch (e) {} finally {} }");
+    round_trip("for (const [a, ...b] of c) { if (a) break; else continue; }");
+    round_trip("for (var i = 0; i < 1; i++) for (var k in o); do ; while (0); debugger;");
+    round_trip("switch (a) { case 1: default: } function h() { return; }");
+}
+
+#[test]
+fn round_trip_exprs() {
+    round_trip("var o = { a, 'b': 1, [c]: 2, 3: 4, get d() {}, set d(v) {}, e() {} };");
+    round_trip("`a${b}c`; tag`\\unicode`; 'use\\x20strict'; 0x1F; .5e1;");
+    round_trip("async function* f(a = 1, { b }, [c]) { yield* g; await h; }");
+    round_trip("var f = async (a, b) => a, g = () => { return typeof this; };");
+    round_trip("let { a: [b = 1], ...c } = d; [e, f] = g;");
+}
+
+#[test]
+fn round_trip_module_decls() {
+    round_trip("import foo, { bar as baz } from 'mod'; import * as ns from 'ns';");
+    round_trip("export default class extends A { constructor() { super(); } }");
+    round_trip("export default a + b; export const x = 1; export * from 'y';");
+    round_trip("export { x as y, z }; export function g() {}");
+}
+
+#[test]
+fn round_trip_jsx() {
+    round_trip("<A.B c='d' e={f} {...g}>{h} text<i:j /><></></A.B>;");
+}
+
+#[test]
+fn estree() {
+    let module = serde_json::to_value(&parse("a + 1;")).unwrap();
+    let stmt = &module["body"][0];
+    assert_eq!(stmt["type"], "ExpressionStatement");
+
+    let expr = &stmt["expression"];
+    assert_eq!(expr["type"], "BinaryExpression");
+    assert_eq!(expr["operator"], "+");
+    assert_eq!(expr["span"]["start"], 0);
+    assert_eq!(expr["span"]["end"], 5);
+    assert_eq!(expr["left"]["type"], "Identifier");
+    assert_eq!(expr["left"]["name"], "a");
+    assert_eq!(expr["right"]["type"], "NumericLiteral");
+    assert_eq!(expr["right"]["raw"], "1");
+}
+
+#[test]
+fn wrong_type() {
+    let mut json = serde_json::to_value(&parse("a;")).unwrap();
+    json["body"][0]["expression"]["type"] = Value::from("Unknown");
+
+    assert!(serde_json::from_value::<Module>(json).is_err());
+}
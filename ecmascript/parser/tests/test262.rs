@@ -9,14 +9,16 @@ extern crate swc_ecma_parser;
 extern crate test;
 extern crate testing;
 use std::{
+    collections::HashSet,
     env,
-    fs::{read_dir, File},
+    fs::{self, read_dir, File},
     io::{self, Read},
-    path::Path,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
 };
 use swc_common::{FileName, Fold, FoldWith, Span};
 use swc_ecma_ast::*;
-use swc_ecma_parser::{PResult, Parser, Session, SourceFileInput};
+use swc_ecma_parser::{PResult, Parser, Session, SourceFileInput, Syntax};
 use test::{test_main, Options, ShouldPanic::No, TestDesc, TestDescAndFn, TestFn, TestName};
 use testing::{NormalizedOutput, StdErr};
 
@@ -260,7 +262,7 @@ fn identity_tests(tests: &mut Vec<TestDescAndFn>) -> Result<(), io::Error> {
     Ok(())
 }
 
-fn parse_script(file_name: &Path, s: &str) -> Result<Vec<Stmt>, NormalizedOutput> {
+fn parse_script(file_name: &Path, s: &str) -> Result<Script, NormalizedOutput> {
     with_parser(file_name, s, |p| p.parse_script())
 }
 fn parse_module<'a>(file_name: &Path, s: &str) -> Result<Module, NormalizedOutput> {
@@ -278,8 +280,8 @@ where
             Session {
                 logger: &logger,
                 handler: &handler,
-                cfg: Default::default(),
             },
+            Syntax::default(),
             (&*fm).into(),
         ));
 
@@ -305,6 +307,134 @@ fn error() {
     test_main(&args, tests, Options::new());
 }
 
+/// Tests which are known to fail, as `{category}/{file name}`.
+const BASELINE: &str = "tests/test262-baseline.txt";
+
+/// Outcomes of tests in a category of test262-parser-tests.
+#[derive(Debug, Default)]
+struct Report {
+    passed: usize,
+    /// Failed tests, as `{category}/{file name}`.
+    failed: Vec<String>,
+}
+
+/// Runs tests in `root/category`.
+///
+/// Tests in `pass` should be parsed into the same ast as `pass-explicit`,
+/// and tests in `fail` and `early` should be rejected by the parser.
+fn run_category(root: &Path, category: &str) -> io::Result<Report> {
+    let mut report = Report::default();
+
+    let dir = root.join(category);
+    let mut paths: Vec<PathBuf> = read_dir(&dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<_, _>>()?;
+    paths.sort();
+
+    for path in paths {
+        let file_name = path.file_name().unwrap().to_string_lossy().into_owned();
+        let input = fs::read_to_string(&path)?;
+        let module = file_name.contains("module");
+
+        let ok = match category {
+            "pass" => {
+                let explicit = fs::read_to_string(root.join("pass-explicit").join(&file_name))?;
+                panic::catch_unwind(AssertUnwindSafe(|| {
+                    if module {
+                        let src = parse_module(&path, &input).map(normalize);
+                        let expected = parse_module(&path, &explicit).map(normalize);
+                        src.is_ok() && src == expected
+                    } else {
+                        let src = parse_script(&path, &input).map(normalize);
+                        let expected = parse_script(&path, &explicit).map(normalize);
+                        src.is_ok() && src == expected
+                    }
+                }))
+                .unwrap_or(false)
+            }
+            _ => panic::catch_unwind(AssertUnwindSafe(|| {
+                if module {
+                    parse_module(&path, &input).is_err()
+                } else {
+                    parse_script(&path, &input).is_err()
+                }
+            }))
+            .unwrap_or(false),
+        };
+
+        if ok {
+            report.passed += 1;
+        } else {
+            report.failed.push(format!("{}/{}", category, file_name));
+        }
+    }
+
+    Ok(report)
+}
+
+/// Runs all tests of test262-parser-tests, and fails if a test not listed in
+/// the baseline fails.
+///
+/// Early errors are only reported as most of them are not implemented yet.
+/// Run with `UPDATE=1` to write failed tests to the baseline.
+#[test]
+fn conformance() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("test262-parser");
+    if !root.join("pass").is_dir() {
+        eprintln!("{} is not checked out", root.display());
+        return;
+    }
+    let baseline_path = Path::new(env!("CARGO_MANIFEST_DIR")).join(BASELINE);
+
+    let mut failed = vec![];
+    for &(category, checked) in &[("pass", true), ("fail", true), ("early", false)] {
+        let report = run_category(&root, category).unwrap();
+        eprintln!(
+            "{}: {} passed, {} failed",
+            category,
+            report.passed,
+            report.failed.len()
+        );
+        if checked {
+            failed.extend(report.failed);
+        }
+    }
+
+    if env::var("UPDATE").is_ok() {
+        let header: String = fs::read_to_string(&baseline_path)
+            .unwrap_or_default()
+            .lines()
+            .take_while(|line| line.starts_with('#') || line.is_empty())
+            .map(|line| format!("{}\n", line))
+            .collect();
+        failed.sort();
+        fs::write(&baseline_path, header + &failed.join("\n") + "\n").unwrap();
+        return;
+    }
+
+    let baseline: HashSet<String> = fs::read_to_string(&baseline_path)
+        .unwrap()
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(String::from)
+        .collect();
+
+    for test in &baseline {
+        if !failed.contains(test) {
+            eprintln!("{} passes now; remove it from {}", test, BASELINE);
+        }
+    }
+    let regressions: Vec<_> = failed.iter().filter(|t| !baseline.contains(*t)).collect();
+    assert!(
+        regressions.is_empty(),
+        "tests not listed in {} failed:\n{:#?}",
+        BASELINE,
+        regressions
+    );
+}
+
 pub fn normalize<T>(t: T) -> T
 where
     Normalizer: Fold<T>,
@@ -324,10 +454,20 @@ impl Fold<Str> for Normalizer {
         Str {
             span: Default::default(),
             has_escape: false,
+            raw: None,
             ..s
         }
     }
 }
+impl Fold<Number> for Normalizer {
+    fn fold(&mut self, n: Number) -> Number {
+        Number {
+            span: Default::default(),
+            raw: None,
+            ..n
+        }
+    }
+}
 impl Fold<Expr> for Normalizer {
     fn fold(&mut self, e: Expr) -> Expr {
         let e = e.fold_children(self);
@@ -375,11 +515,13 @@ impl Fold<PropName> for Normalizer {
                 span: Default::default(),
                 value: sym,
                 has_escape: false,
+                raw: None,
             }),
             PropName::Num(num) => PropName::Str(Str {
                 span: Default::default(),
                 value: num.to_string().into(),
                 has_escape: false,
+                raw: None,
             }),
             _ => n,
         }
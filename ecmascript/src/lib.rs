@@ -1,4 +1,8 @@
 pub extern crate swc_ecma_ast as ast;
+#[cfg(feature = "bundler")]
+pub extern crate swc_ecma_bundler as bundler;
 pub extern crate swc_ecma_codegen as codegen;
+pub extern crate swc_ecma_lint as lint;
+pub extern crate swc_ecma_minifier as minifier;
 pub extern crate swc_ecma_parser as parser;
 pub extern crate swc_ecma_transforms as transforms;
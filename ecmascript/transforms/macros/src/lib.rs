@@ -0,0 +1,177 @@
+//! Quasi-quotation of javascript, which is used to build ast nodes in
+//! `swc_ecma_transforms`.
+//!
+//! `quote_expr!` and `quote_stmt!` parse a snippet at compile time and expand
+//! to an expression which creates the ast of it. All spans of the created
+//! nodes are the given span.
+//!
+//! ```rust,ignore
+//! let call: Expr = quote_expr!(span, "$helper.call(this, $arg)", helper = helper, arg = arg);
+//! let stmt: Stmt = quote_stmt!(span, "var $name = $init;", name = ident, init = init);
+//! ```
+//!
+//! `$name` in a snippet is replaced with the value of `name = value`. The
+//! value should be a `Box<Expr>` if `$name` is used as an expression, and an
+//! `Ident` otherwise, like the name of a variable or a function. Identifiers
+//! starting with `$` which are not given are kept as they are.
+//!
+//! The expanded code uses `::ast` (`swc_ecma_ast`), `::swc_atoms` and
+//! `::swc_common`, and typescript and jsx are not supported.
+
+extern crate proc_macro;
+extern crate proc_macro2;
+#[macro_use]
+extern crate quote;
+#[macro_use]
+extern crate slog;
+extern crate swc_atoms;
+extern crate swc_common;
+extern crate swc_ecma_ast as ast;
+extern crate swc_ecma_parser;
+#[macro_use]
+extern crate syn;
+
+use self::to_code::{Ctx, ToCode};
+use ast::{Expr, ExprStmt, ParenExpr, Stmt};
+use proc_macro2::TokenStream;
+use swc_common::{errors::Handler, FileName, FilePathMapping, Globals, SourceMap, GLOBALS};
+use swc_ecma_parser::{Parser, Session, SourceFileInput, Syntax};
+use syn::{punctuated::Punctuated, synom::Synom, token, Ident, LitStr};
+
+mod to_code;
+
+/// Creates an `Expr` from a javascript expression.
+///
+/// # Usage
+/// ```rust,ignore
+/// quote_expr!(span, "$obj.call(this)", obj = obj);
+/// ```
+#[proc_macro]
+pub fn quote_expr(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input: Input = syn::parse(input).expect("failed to parse input of quote_expr!()");
+
+    // Parenthesized so that `{ .. }` is parsed as an object.
+    let stmt = parse_stmt(&format!("({})", input.src.value()));
+    let expr = match stmt {
+        Stmt::Expr(ExprStmt { expr, .. }) => match *expr {
+            Expr::Paren(ParenExpr { expr, .. }) => expr,
+            _ => panic!("quote_expr!() expects an expression"),
+        },
+        _ => panic!("quote_expr!() expects an expression"),
+    };
+
+    input.expand(&*expr).into()
+}
+
+/// Creates a `Stmt` from a javascript statement.
+///
+/// # Usage
+/// ```rust,ignore
+/// quote_stmt!(span, "var $name = $init;", name = name, init = init);
+/// ```
+#[proc_macro]
+pub fn quote_stmt(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input: Input = syn::parse(input).expect("failed to parse input of quote_stmt!()");
+    let stmt = parse_stmt(&input.src.value());
+
+    input.expand(&stmt).into()
+}
+
+/// `span, "src", name = value, ..`
+struct Input {
+    span: syn::Expr,
+    src: LitStr,
+    vars: Punctuated<Var, token::Comma>,
+}
+
+/// `name = value`
+struct Var {
+    name: Ident,
+    value: syn::Expr,
+}
+
+impl Synom for Input {
+    named!(parse -> Self, do_parse!(
+        span: syn!(syn::Expr) >>
+        syn!(token::Comma) >>
+        src: syn!(LitStr) >>
+        vars: option!(do_parse!(
+            syn!(token::Comma) >>
+            vars: call!(Punctuated::<Var, token::Comma>::parse_terminated) >>
+            (vars)
+        )) >>
+        (Input {
+            span,
+            src,
+            vars: vars.unwrap_or_default(),
+        })
+    ));
+}
+
+impl Synom for Var {
+    named!(parse -> Self, do_parse!(
+        name: syn!(Ident) >>
+        syn!(token::Eq) >>
+        value: syn!(syn::Expr) >>
+        (Var { name, value })
+    ));
+}
+
+impl Input {
+    fn expand<T: ToCode>(self, node: &T) -> TokenStream {
+        let Input { span, vars, .. } = self;
+        let (names, values): (Vec<_>, Vec<_>) = vars
+            .into_iter()
+            .map(|var| (var.name, var.value))
+            .unzip();
+
+        // The first pass counts uses of variables, so that values which are
+        // used more than once can be cloned.
+        let mut cx = Ctx::new(names);
+        node.to_code(&mut cx);
+        cx.prepare();
+        let code = node.to_code(&mut cx);
+
+        let names = cx.names();
+        let types = cx.types();
+        quote!({
+            let (__span, #(#names,)*): (::swc_common::Span, #(#types,)*) =
+                (#span, #(#values,)*);
+            #code
+        })
+    }
+}
+
+/// Parses `src` as a script which consists of a statement.
+fn parse_stmt(src: &str) -> Stmt {
+    GLOBALS.set(&Globals::new(), || {
+        let cm = SourceMap::new(FilePathMapping::empty());
+        let fm = cm.new_source_file(FileName::Custom("quote.js".into()), src.into());
+        let (handler, errors) = Handler::with_buffer(Default::default());
+        let logger = slog::Logger::root(slog::Discard, o!());
+        let session = Session {
+            handler: &handler,
+            logger: &logger,
+        };
+
+        let script = Parser::new(
+            session,
+            Syntax::Es(Default::default()),
+            SourceFileInput::from(&*fm),
+        )
+        .parse_script();
+        let errors = errors.take();
+        match script {
+            Ok(mut script) if errors.is_empty() => {
+                if script.body.len() != 1 {
+                    panic!("expected a statement, got {} statements", script.body.len())
+                }
+                script.body.remove(0)
+            }
+            _ => {
+                let messages: Vec<_> = errors.iter().map(|err| err.message()).collect();
+                panic!("failed to parse `{}`: {}", src, messages.join(", "))
+            }
+        }
+    })
+}
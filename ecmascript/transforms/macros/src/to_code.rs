@@ -0,0 +1,420 @@
+//! Conversion of nodes to code which creates them.
+use ast::*;
+use proc_macro2::TokenStream;
+use swc_atoms::JsWord;
+use swc_common::Span;
+
+pub(crate) trait ToCode {
+    /// Returns an expression which creates `self`.
+    fn to_code(&self, cx: &mut Ctx) -> TokenStream;
+}
+
+/// Variables of a snippet, like `$name`.
+pub(crate) struct Ctx {
+    vars: Vec<Var>,
+}
+
+struct Var {
+    name: syn::Ident,
+    kind: Option<VarKind>,
+    uses: usize,
+    /// True if the value is cloned on each use.
+    clone: bool,
+}
+
+/// Type of the value of a variable.
+#[derive(Clone, Copy, PartialEq)]
+enum VarKind {
+    /// `Box<Expr>`
+    Expr,
+    /// `Ident`
+    Ident,
+}
+
+impl Ctx {
+    pub(crate) fn new(names: Vec<syn::Ident>) -> Self {
+        Ctx {
+            vars: names
+                .into_iter()
+                .map(|name| Var {
+                    name,
+                    kind: None,
+                    uses: 0,
+                    clone: false,
+                })
+                .collect(),
+        }
+    }
+
+    /// Checks uses counted by a pass, and prepares `self` for the next pass.
+    pub(crate) fn prepare(&mut self) {
+        for var in &mut self.vars {
+            if var.uses == 0 {
+                panic!("`${}` is not used in the snippet", var.name)
+            }
+            var.clone = var.uses > 1;
+            var.uses = 0;
+        }
+    }
+
+    pub(crate) fn names(&self) -> Vec<syn::Ident> {
+        self.vars.iter().map(|var| var.name.clone()).collect()
+    }
+
+    pub(crate) fn types(&self) -> Vec<TokenStream> {
+        self.vars
+            .iter()
+            .map(|var| match var.kind {
+                Some(VarKind::Expr) => quote!(Box<::ast::Expr>),
+                Some(VarKind::Ident) => quote!(::ast::Ident),
+                None => unreachable!("prepare() checks that all variables are used"),
+            })
+            .collect()
+    }
+
+    /// Returns the value of `sym` if it's a variable.
+    fn var(&mut self, sym: &JsWord, kind: VarKind) -> Option<TokenStream> {
+        if !sym.starts_with('$') {
+            return None;
+        }
+        let var = self.vars.iter_mut().find(|var| var.name == &sym[1..])?;
+
+        match var.kind {
+            Some(k) if k != kind => panic!(
+                "`${}` is used as both an expression and an identifier",
+                var.name
+            ),
+            _ => var.kind = Some(kind),
+        }
+        var.uses += 1;
+
+        let name = &var.name;
+        Some(if var.clone {
+            quote!(#name.clone())
+        } else {
+            quote!(#name)
+        })
+    }
+}
+
+impl ToCode for Span {
+    fn to_code(&self, _: &mut Ctx) -> TokenStream {
+        quote!(__span)
+    }
+}
+
+impl ToCode for bool {
+    fn to_code(&self, _: &mut Ctx) -> TokenStream {
+        let value = *self;
+        quote!(#value)
+    }
+}
+
+impl ToCode for f64 {
+    fn to_code(&self, _: &mut Ctx) -> TokenStream {
+        // Numeric literals are not negative.
+        if self.is_infinite() {
+            return quote!(::std::f64::INFINITY);
+        }
+        let value = *self;
+        quote!(#value)
+    }
+}
+
+impl ToCode for String {
+    fn to_code(&self, _: &mut Ctx) -> TokenStream {
+        let value = &**self;
+        quote!(::std::string::String::from(#value))
+    }
+}
+
+impl ToCode for JsWord {
+    fn to_code(&self, _: &mut Ctx) -> TokenStream {
+        let value = &**self;
+        quote!(::swc_atoms::JsWord::from(#value))
+    }
+}
+
+impl<T: ToCode> ToCode for Option<T> {
+    fn to_code(&self, cx: &mut Ctx) -> TokenStream {
+        match *self {
+            Some(ref value) => {
+                let value = value.to_code(cx);
+                quote!(Some(#value))
+            }
+            None => quote!(None),
+        }
+    }
+}
+
+impl<T: ToCode> ToCode for Vec<T> {
+    fn to_code(&self, cx: &mut Ctx) -> TokenStream {
+        let items: Vec<_> = self.iter().map(|item| item.to_code(cx)).collect();
+        quote!(vec![#(#items),*])
+    }
+}
+
+impl ToCode for Ident {
+    fn to_code(&self, cx: &mut Ctx) -> TokenStream {
+        if let Some(var) = cx.var(&self.sym, VarKind::Ident) {
+            return var;
+        }
+
+        let sym = self.sym.to_code(cx);
+        let span = self.span.to_code(cx);
+        quote!(::ast::Ident::new(#sym, #span))
+    }
+}
+
+/// Matches `$e` and converts the field of each variant.
+macro_rules! variants {
+    ($e:expr, $cx:ident, $T:ident { $($V:ident),* }) => {
+        match *$e {
+            $(
+                $T::$V(ref v) => {
+                    let v = v.to_code($cx);
+                    quote!(::ast::$T::$V(#v))
+                }
+            )*
+        }
+    };
+}
+
+macro_rules! structs {
+    ($($T:ident { $($field:ident),* })*) => {
+        $(
+            impl ToCode for $T {
+                fn to_code(&self, cx: &mut Ctx) -> TokenStream {
+                    let fields: Vec<TokenStream> = vec![$({
+                        let value = self.$field.to_code(cx);
+                        quote!($field: #value)
+                    }),*];
+                    quote!(::ast::$T { #(#fields),* })
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! enums {
+    ($($T:ident { $($V:ident),* })*) => {
+        $(
+            impl ToCode for $T {
+                fn to_code(&self, cx: &mut Ctx) -> TokenStream {
+                    variants!(self, cx, $T { $($V),* })
+                }
+            }
+        )*
+    };
+}
+
+/// Enums without fields.
+macro_rules! unit_enums {
+    ($($T:ident { $($V:ident),* })*) => {
+        $(
+            impl ToCode for $T {
+                fn to_code(&self, _: &mut Ctx) -> TokenStream {
+                    match *self {
+                        $($T::$V => quote!(::ast::$T::$V),)*
+                    }
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! boxes {
+    ($($T:ty),*) => {
+        $(
+            impl ToCode for Box<$T> {
+                fn to_code(&self, cx: &mut Ctx) -> TokenStream {
+                    let value = (**self).to_code(cx);
+                    quote!(Box::new(#value))
+                }
+            }
+        )*
+    };
+}
+
+/// Nodes of typescript and jsx, which are not parsed.
+macro_rules! unsupported {
+    ($($T:ty),*) => {
+        $(
+            impl ToCode for $T {
+                fn to_code(&self, _: &mut Ctx) -> TokenStream {
+                    unreachable!("{} in a snippet", stringify!($T))
+                }
+            }
+        )*
+    };
+}
+
+impl ToCode for Expr {
+    fn to_code(&self, cx: &mut Ctx) -> TokenStream {
+        if let Expr::Ident(ref i) = *self {
+            if let Some(var) = cx.var(&i.sym, VarKind::Expr) {
+                return quote!(*#var);
+            }
+        }
+
+        variants!(self, cx, Expr {
+            This, Array, Object, Fn, Unary, Update, Bin, Assign, Member, Cond, Call, OptChain, New,
+            Seq, Ident, Lit, Tpl, Arrow, Class, Yield, MetaProp, Await, Paren, JSXElement,
+            JSXFragment, TsAs, PrivateName
+        })
+    }
+}
+
+impl ToCode for Box<Expr> {
+    fn to_code(&self, cx: &mut Ctx) -> TokenStream {
+        if let Expr::Ident(ref i) = **self {
+            if let Some(var) = cx.var(&i.sym, VarKind::Expr) {
+                return var;
+            }
+        }
+
+        let expr = (**self).to_code(cx);
+        quote!(Box::new(#expr))
+    }
+}
+
+structs!(
+    ThisExpr { span }
+    ArrayLit { span, elems }
+    ObjectLit { span, props }
+    SpreadElement { dot3_token, expr }
+    UnaryExpr { span, op, arg }
+    UpdateExpr { span, op, prefix, arg }
+    BinExpr { span, op, left, right }
+    FnExpr { ident, function }
+    ClassExpr { ident, class }
+    AssignExpr { span, op, left, right }
+    MemberExpr { span, obj, prop, computed }
+    CondExpr { span, test, cons, alt }
+    CallExpr { span, callee, args }
+    OptChainExpr { span, expr }
+    NewExpr { span, callee, args }
+    SeqExpr { span, exprs }
+    ArrowExpr { span, params, body, generator_token, async_token, type_params, return_type }
+    YieldExpr { span, arg, delegate }
+    MetaPropExpr { meta, prop }
+    AwaitExpr { span, arg }
+    TplLit { span, tag, exprs, quasis }
+    TplElement { span, tail, cooked, raw }
+    ParenExpr { span, expr }
+    ExprOrSpread { spread, expr }
+
+    BlockStmt { span, stmts }
+    ExprStmt { span, expr }
+    EmptyStmt { span }
+    DebuggerStmt { span }
+    WithStmt { span, obj, body }
+    ReturnStmt { span, arg }
+    LabeledStmt { span, label, body }
+    BreakStmt { span, label }
+    ContinueStmt { span, label }
+    IfStmt { span, test, cons, alt }
+    SwitchStmt { span, discriminant, cases }
+    ThrowStmt { span, arg }
+    TryStmt { span, block, handler, finalizer }
+    WhileStmt { span, test, body }
+    DoWhileStmt { span, test, body }
+    ForStmt { span, init, test, update, body }
+    ForInStmt { span, left, right, body }
+    ForOfStmt { span, await_token, left, right, body }
+    SwitchCase { span, test, cons }
+    CatchClause { span, param, body }
+
+    FnDecl { ident, declare, function }
+    ClassDecl { ident, declare, class }
+    VarDecl { span, kind, declare, decls }
+    VarDeclarator { span, name, init }
+
+    ArrayPat { span, elems }
+    ObjectPat { span, props }
+    AssignPat { span, left, right }
+    RestPat { dot3_token, arg }
+    KeyValuePatProp { key, value }
+    AssignPatProp { span, key, value }
+
+    KeyValueProp { key, value }
+    AssignProp { key, value }
+    GetterProp { span, key, body }
+    SetterProp { span, key, param, body }
+    MethodProp { key, function }
+    ComputedPropName { span, expr }
+
+    Class { span, body, super_class, decorators, type_params, super_type_params, implements }
+    ClassMethod { span, key, function, kind, static_token, decorators }
+    PrivateMethod { span, key, function, kind, static_token, decorators }
+    ClassProp { span, key, value, static_token, decorators, type_ann }
+    PrivateProp { span, key, value, static_token, decorators, type_ann }
+    PrivateName { span, id }
+    StaticBlock { span, body }
+    Decorator { span, expr }
+
+    Function { params, span, body, generator_token, async_token, type_params, return_type }
+    Param { span, decorators, pat }
+
+    Str { span, value, has_escape, raw }
+    Bool { span, value }
+    Null { span }
+    Regex { span, exp, flags }
+    Number { span, value, raw }
+    BigInt { span, value }
+);
+
+enums!(
+    PropOrSpread { Prop, Spread }
+    ExprOrSuper { Super, Import, Expr }
+    BlockStmtOrExpr { BlockStmt, Expr }
+    PatOrExpr { Pat, Expr }
+
+    Stmt {
+        Expr, Block, Empty, Debugger, With, Return, Labeled, Break, Continue, If, Switch, Throw,
+        Try, While, DoWhile, For, ForIn, ForOf, Decl
+    }
+    VarDeclOrPat { VarDecl, Pat }
+    VarDeclOrExpr { VarDecl, Expr }
+
+    Decl { Class, Fn, Var, TsInterface, TsTypeAlias, TsEnum, TsModule }
+    Pat { Ident, Array, Rest, Object, Assign, Expr, TsTyped }
+    ObjectPatProp { KeyValue, Assign, Rest }
+    Prop { Shorthand, KeyValue, Assign, Getter, Setter, Method }
+    PropName { Ident, Str, Num, Computed }
+    ClassMember { Method, PrivateMethod, ClassProp, PrivateProp, StaticBlock }
+    Lit { Str, Bool, Null, Num, BigInt, Regex }
+);
+
+unit_enums!(
+    BinaryOp {
+        EqEq, NotEq, EqEqEq, NotEqEq, Lt, LtEq, Gt, GtEq, LShift, RShift, ZeroFillRShift, Add, Sub,
+        Mul, Div, Mod, BitOr, BitXor, BitAnd, LogicalOr, LogicalAnd, In, InstanceOf, Exp,
+        NullishCoalescing
+    }
+    AssignOp {
+        Assign, AddAssign, SubAssign, MulAssign, DivAssign, ModAssign, LShiftAssign, RShiftAssign,
+        ZeroFillRShiftAssign, BitOrAssign, BitXorAssign, BitAndAssign, ExpAssign
+    }
+    UpdateOp { PlusPlus, MinusMinus }
+    UnaryOp { Minus, Plus, Bang, Tilde, TypeOf, Void, Delete }
+    VarDeclKind { Var, Let, Const }
+    ClassMethodKind { Constructor, Method, Getter, Setter }
+);
+
+boxes!(Stmt, Pat, Prop);
+
+unsupported!(
+    Box<JSXElement>,
+    JSXFragment,
+    TsAsExpr,
+    TsTypedPat,
+    TsInterfaceDecl,
+    TsTypeAliasDecl,
+    TsEnumDecl,
+    TsModuleDecl,
+    TsTypeAnn,
+    TsTypeParamDecl,
+    TsTypeParamInstantiation,
+    TsExprWithTypeArgs
+);
@@ -0,0 +1,118 @@
+use ast::*;
+use crate::scope::{FoldScoped, ScopeKind, ScopedFold, Scopes};
+use std::collections::{hash_map::Entry, HashMap};
+use swc_atoms::JsWord;
+use swc_common::{FoldWith, Mark, Span, SyntaxContext};
+
+#[cfg(test)]
+mod tests;
+
+/// Name and syntax context of a binding.
+pub type Id = (JsWord, SyntaxContext);
+
+/// Finds bindings of `module` and references to them.
+///
+/// Bindings are identified by their names and syntax contexts, so bindings
+/// which shadow another binding with the same name are merged into one unless
+/// [resolver](crate::resolver) is applied first.
+pub fn analyze(module: &Module) -> Analysis {
+    let mut folder = ScopedFold::new(Analyzer::default());
+    module.clone().fold_with(&mut folder);
+
+    let mut analysis = Analysis::default();
+    for (id, mut binding) in folder.folder.bindings {
+        // Declarations are counted as references while folding.
+        binding.refs = binding.refs.saturating_sub(binding.decls.len());
+
+        match analysis.bindings.entry(id) {
+            Entry::Occupied(mut e) => {
+                let merged = e.get_mut();
+                merged.decls.extend(binding.decls);
+                merged.refs += binding.refs;
+            }
+            Entry::Vacant(e) => {
+                e.insert(binding);
+            }
+        }
+    }
+    analysis.free = folder.folder.free;
+
+    analysis
+}
+
+/// Bindings of a module, returned by [analyze].
+#[derive(Debug, Default, Clone)]
+pub struct Analysis {
+    bindings: HashMap<Id, Binding>,
+    free: HashMap<JsWord, usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Binding {
+    /// Kind of the scope which declares the binding.
+    pub scope: ScopeKind,
+    /// Spans of identifiers declaring the binding, like `a` in `var a` and
+    /// `function a() {}`.
+    pub decls: Vec<Span>,
+    /// Number of references to the binding, excluding declarations.
+    pub refs: usize,
+}
+
+impl Analysis {
+    /// Returns the binding `ident` refers to.
+    pub fn get(&self, ident: &Ident) -> Option<&Binding> {
+        self.bindings.get(&(ident.sym.clone(), ident.span.ctxt()))
+    }
+
+    pub fn bindings(&self) -> impl Iterator<Item = (&Id, &Binding)> {
+        self.bindings.iter()
+    }
+
+    /// Number of references to a variable which is not declared in the
+    /// module, like `window`.
+    pub fn free_refs(&self, sym: &JsWord) -> usize {
+        self.free.get(sym).cloned().unwrap_or(0)
+    }
+}
+
+#[derive(Debug, Default)]
+struct Analyzer {
+    /// Bindings in order of the first occurrence, which are not merged yet.
+    bindings: Vec<(Id, Binding)>,
+    /// Indices of `bindings`, keyed by bindings and marks of scopes declaring
+    /// them.
+    indices: HashMap<(Id, Mark), usize>,
+    free: HashMap<JsWord, usize>,
+}
+
+impl FoldScoped<Ident> for Analyzer {
+    fn fold_scoped(&mut self, scopes: &mut Scopes, i: Ident) -> Ident {
+        let scope = match scopes.resolve(&i) {
+            Some(depth) => &scopes.scopes()[depth],
+            None => {
+                *self.free.entry(i.sym.clone()).or_insert(0) += 1;
+                return i;
+            }
+        };
+
+        let id = (i.sym.clone(), i.span.ctxt());
+        let bindings = &mut self.bindings;
+        let idx = *self
+            .indices
+            .entry((id.clone(), scope.mark))
+            .or_insert_with(|| {
+                bindings.push((
+                    id,
+                    Binding {
+                        scope: scope.kind,
+                        decls: scope.decls(&i).to_vec(),
+                        refs: 0,
+                    },
+                ));
+                bindings.len() - 1
+            });
+        self.bindings[idx].1.refs += 1;
+
+        i
+    }
+}
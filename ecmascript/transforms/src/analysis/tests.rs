@@ -0,0 +1,51 @@
+use super::*;
+
+#[test]
+fn bindings() {
+    crate::tests::Tester::run(crate::tests::syntax(), |tester| {
+        let module = tester.apply_transform(
+            crate::fixer(),
+            "input.js",
+            "var a = 1; function f(b) { let c = b; use(a, c, a.b); } f();",
+        )?;
+
+        let analysis = analyze(&module);
+        let binding = |sym: &str| analysis.get(&quote_ident!(sym)).cloned().unwrap();
+
+        assert_eq!(binding("a").scope, ScopeKind::Module);
+        assert_eq!(binding("a").decls.len(), 1);
+        assert_eq!(binding("a").refs, 2);
+        assert_eq!(binding("f").scope, ScopeKind::Module);
+        assert_eq!(binding("f").refs, 1);
+        assert_eq!(binding("b").scope, ScopeKind::Fn);
+        assert_eq!(binding("b").refs, 1);
+        assert_eq!(binding("c").scope, ScopeKind::Block);
+        assert_eq!(binding("c").refs, 1);
+        assert_eq!(analysis.bindings().count(), 4);
+
+        assert_eq!(analysis.free_refs(&"use".into()), 1);
+        assert_eq!(analysis.free_refs(&"b".into()), 0);
+
+        Ok(())
+    });
+}
+
+#[test]
+fn shadowed() {
+    crate::tests::Tester::run(crate::tests::syntax(), |tester| {
+        let module = tester.apply_transform(
+            crate::fixer(),
+            "input.js",
+            "var a; function f(a) { return a; }",
+        )?;
+
+        let analysis = analyze(&module);
+        let a = analysis.get(&quote_ident!("a")).unwrap();
+        // Merged into the binding of the module.
+        assert_eq!(a.scope, ScopeKind::Module);
+        assert_eq!(a.decls.len(), 2);
+        assert_eq!(a.refs, 1);
+
+        Ok(())
+    });
+}
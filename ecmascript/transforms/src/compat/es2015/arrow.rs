@@ -1,5 +1,6 @@
+use crate::util::{prepend, ExprFactory};
 use ast::*;
-use swc_common::{Fold, FoldWith};
+use swc_common::{Fold, FoldWith, Spanned, DUMMY_SP};
 
 /// Compile ES2015 arrow functions to ES5
 ///
@@ -52,16 +53,291 @@ use swc_common::{Fold, FoldWith};
 #[derive(Debug, Clone, Copy)]
 pub struct Arrow;
 
-impl Fold<Expr> for Arrow {
-    fn fold(&mut self, e: Expr) -> Expr {
-        let e = e.fold_children(self);
+impl Fold<Module> for Arrow {
+    fn fold(&mut self, module: Module) -> Module {
+        let mut folder = ArrowFolder::default();
+        let mut module = module.fold_children(&mut folder);
+        if let Some(decl) = folder.take_captures() {
+            module.body.insert(0, ModuleItem::Stmt(decl));
+        }
+        module
+    }
+}
+
+/// Converts arrow functions of a scope which has its own `this`, like a
+/// function or a module.
+#[derive(Debug, Default)]
+struct ArrowFolder {
+    /// True while folding an arrow function which belongs to this scope.
+    in_arrow: bool,
+    /// Arrow functions are bound with `.bind(this)` instead of capturing
+    /// `this`, because initializers of class properties don't have a body to
+    /// declare `_this` in.
+    bind_this: bool,
+    /// `_this`, if an arrow function uses `this`.
+    this: Option<Ident>,
+    /// `_arguments`, if an arrow function uses `arguments`.
+    arguments: Option<Ident>,
+}
+
+impl ArrowFolder {
+    /// `var _this = this, _arguments = arguments;`
+    fn take_captures(&mut self) -> Option<Stmt> {
+        let this = self
+            .this
+            .take()
+            .map(|name| (name, Expr::This(ThisExpr { span: DUMMY_SP })));
+        let arguments = self
+            .arguments
+            .take()
+            .map(|name| (name, Expr::Ident(quote_ident!("arguments"))));
+
+        let decls: Vec<_> = this
+            .into_iter()
+            .chain(arguments)
+            .map(|(name, init)| VarDeclarator {
+                span: DUMMY_SP,
+                name: Pat::Ident(name),
+                init: Some(box init),
+            })
+            .collect();
+        if decls.is_empty() {
+            return None;
+        }
+
+        Some(Stmt::Decl(Decl::Var(VarDecl {
+            span: DUMMY_SP,
+            kind: VarDeclKind::Var,
+            declare: false,
+            decls,
+        })))
+    }
+
+    /// Folds the body of a function, which is a new scope.
+    fn fold_body(body: BlockStmt, folder: &mut ArrowFolder) -> BlockStmt {
+        let mut body = body.fold_with(folder);
+        if let Some(decl) = folder.take_captures() {
+            prepend(&mut body.stmts, decl);
+        }
+        body
+    }
+
+    fn fold_arrow(&mut self, arrow: ArrowExpr) -> Expr {
+        if self.bind_this && !self.in_arrow {
+            // `function () {}.bind(this)`
+            let function = into_function(arrow).fold_with(self);
 
+            return Expr::Call(CallExpr {
+                span: function.span,
+                callee: MemberExpr {
+                    span: function.span,
+                    obj: ExprOrSuper::Expr(box Expr::Fn(FnExpr {
+                        ident: None,
+                        function,
+                    })),
+                    prop: box Expr::Ident(quote_ident!("bind")),
+                    computed: false,
+                }
+                .as_callee(),
+                args: vec![ThisExpr { span: DUMMY_SP }.as_arg()],
+            });
+        }
+
+        let in_arrow = self.in_arrow;
+        self.in_arrow = true;
+        let arrow = arrow.fold_children(self);
+        self.in_arrow = in_arrow;
+
+        Expr::Fn(FnExpr {
+            ident: None,
+            function: into_function(arrow),
+        })
+    }
+}
+
+fn into_function(arrow: ArrowExpr) -> Function {
+    let body = match arrow.body {
+        BlockStmtOrExpr::BlockStmt(block) => block,
+        BlockStmtOrExpr::Expr(expr) => BlockStmt {
+            span: expr.span(),
+            stmts: vec![Stmt::Return(ReturnStmt {
+                span: expr.span(),
+                arg: Some(expr),
+            })],
+        },
+    };
+
+    Function {
+        span: arrow.span,
+        params: arrow
+            .params
+            .into_iter()
+            .map(|pat| Param {
+                span: pat.span(),
+                decorators: vec![],
+                pat,
+            })
+            .collect(),
+        body: Some(body),
+        generator_token: arrow.generator_token,
+        async_token: arrow.async_token,
+        type_params: arrow.type_params,
+        return_type: arrow.return_type,
+    }
+}
+
+impl Fold<Expr> for ArrowFolder {
+    fn fold(&mut self, e: Expr) -> Expr {
         match e {
-            Expr::Arrow(ArrowExpr { .. }) => unimplemented!("Arrow function"),
-            _ => e,
+            Expr::This(..) if self.in_arrow => {
+                Expr::Ident(self.this.get_or_insert_with(|| quote_ident!("_this")).clone())
+            }
+            Expr::Ident(ref i) if self.in_arrow && i.sym == js_word!("arguments") => Expr::Ident(
+                self.arguments
+                    .get_or_insert_with(|| quote_ident!("_arguments"))
+                    .clone(),
+            ),
+            Expr::Arrow(arrow) => self.fold_arrow(arrow),
+            _ => e.fold_children(self),
+        }
+    }
+}
+
+/// Keeps `a.arguments`.
+impl Fold<MemberExpr> for ArrowFolder {
+    fn fold(&mut self, e: MemberExpr) -> MemberExpr {
+        MemberExpr {
+            obj: e.obj.fold_with(self),
+            prop: if e.computed {
+                e.prop.fold_with(self)
+            } else {
+                e.prop
+            },
+            ..e
+        }
+    }
+}
+
+impl Fold<Function> for ArrowFolder {
+    fn fold(&mut self, f: Function) -> Function {
+        let mut folder = ArrowFolder::default();
+        let params = f.params.fold_with(&mut folder);
+        let body = f.body.map(|body| ArrowFolder::fold_body(body, &mut folder));
+
+        Function { params, body, ..f }
+    }
+}
+
+impl Fold<GetterProp> for ArrowFolder {
+    fn fold(&mut self, p: GetterProp) -> GetterProp {
+        GetterProp {
+            key: p.key.fold_with(self),
+            body: ArrowFolder::fold_body(p.body, &mut ArrowFolder::default()),
+            ..p
+        }
+    }
+}
+
+impl Fold<SetterProp> for ArrowFolder {
+    fn fold(&mut self, p: SetterProp) -> SetterProp {
+        let mut folder = ArrowFolder::default();
+        let param = p.param.fold_with(&mut folder);
+        let body = ArrowFolder::fold_body(p.body, &mut folder);
+
+        SetterProp {
+            key: p.key.fold_with(self),
+            param,
+            body,
+            ..p
+        }
+    }
+}
+
+impl Fold<ClassProp> for ArrowFolder {
+    fn fold(&mut self, p: ClassProp) -> ClassProp {
+        let mut folder = ArrowFolder {
+            bind_this: true,
+            ..Default::default()
+        };
+
+        ClassProp {
+            key: p.key.fold_with(self),
+            decorators: p.decorators.fold_with(self),
+            value: p.value.fold_with(&mut folder),
+            ..p
+        }
+    }
+}
+
+impl Fold<PrivateProp> for ArrowFolder {
+    fn fold(&mut self, p: PrivateProp) -> PrivateProp {
+        let mut folder = ArrowFolder {
+            bind_this: true,
+            ..Default::default()
+        };
+
+        PrivateProp {
+            decorators: p.decorators.fold_with(self),
+            value: p.value.fold_with(&mut folder),
+            ..p
         }
     }
 }
 
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use super::*;
+
+    test!(
+        Arrow,
+        basic,
+        "var a = () => {}; var b = (c) => c;",
+        "var a = function () {}; var b = function (c) { return c; };"
+    );
+
+    test!(
+        Arrow,
+        this_and_arguments,
+        r#"function foo() {
+  "use strict";
+  bar(() => this.a(arguments, () => this));
+  baz(function () { return () => this; });
+}"#,
+        r#"function foo() {
+  "use strict";
+  var _this = this, _arguments = arguments;
+  bar(function () {
+    return _this.a(_arguments, function () {
+      return _this;
+    });
+  });
+  baz(function () {
+    var _this = this;
+    return function () {
+      return _this;
+    };
+  });
+}"#
+    );
+
+    test!(
+        Arrow,
+        no_capture,
+        "var a = (b) => b.arguments;",
+        "var a = function (b) { return b.arguments; };"
+    );
+
+    test!(
+        Arrow,
+        class_prop,
+        "class A { a = () => this.b(() => this); }",
+        r#"class A {
+  a = function () {
+    var _this = this;
+    return this.b(function () {
+      return _this;
+    });
+  }.bind(this);
+}"#
+    );
+}
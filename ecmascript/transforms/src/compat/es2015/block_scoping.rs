@@ -0,0 +1,1216 @@
+use crate::util::{pat_ids, var_ids, StmtLike};
+use ast::*;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{self, Display, Formatter},
+    mem,
+    sync::{Arc, Mutex},
+};
+use swc_atoms::JsWord;
+use swc_common::{Fold, FoldWith, Span, Visit, VisitWith, DUMMY_SP};
+
+/// Compile ES2015 block scoped declarations to ES5
+///
+/// `let` and `const` are converted to `var`, and bindings which would
+/// conflict with another binding of the function are renamed.
+///
+///# Example
+///
+///## In
+///
+/// ```js
+/// let a = 1;
+/// {
+///   let a = 2;
+///   for (let i = 0; i < 3; i++) {
+///     fns.push(() => i + a);
+///   }
+/// }
+/// ```
+///
+///## Out
+///
+/// ```js
+/// var a = 1;
+/// {
+///   var _a = 2;
+///   var _loop = function (i) {
+///     fns.push(() => i + _a);
+///   };
+///   for (var i = 0; i < 3; i++) _loop(i);
+/// }
+/// ```
+///
+/// Code which can't be compiled without changing its behavior, like a
+/// variable used before its declaration, is reported to `errors`.
+#[derive(Debug, Clone, Default)]
+pub struct BlockScoping {
+    pub errors: Arc<Mutex<Vec<BlockScopingError>>>,
+}
+
+/// Code which behaves differently after [BlockScoping].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockScopingError {
+    pub span: Span,
+    pub msg: String,
+}
+
+impl Display for BlockScopingError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(&self.msg)
+    }
+}
+
+impl Fold<Module> for BlockScoping {
+    fn fold(&mut self, module: Module) -> Module {
+        let mut folder = Folder::default();
+
+        folder.push_scope(ScopeKind::Fn, count_names(&module));
+        for item in &module.body {
+            match *item {
+                ModuleItem::Stmt(ref stmt) => folder.declare_stmt(stmt, false),
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ref export)) => {
+                    folder.declare_decl(&export.decl, false)
+                }
+                _ => {}
+            }
+        }
+        for id in var_ids(&module) {
+            folder.declare(id.sym, true);
+        }
+        let module = module.fold_children(&mut folder);
+        folder.scopes.pop();
+
+        self.errors.lock().unwrap().extend(folder.errors);
+        module
+    }
+}
+
+#[derive(Debug, Default)]
+struct Folder {
+    scopes: Vec<Scope>,
+    /// Names created by this pass, which are not reused even in another
+    /// function.
+    generated: HashSet<JsWord>,
+    /// `var _loop = function () {}` which should be inserted before the
+    /// statement being folded.
+    pending: Vec<Stmt>,
+    errors: Vec<BlockScopingError>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ScopeKind {
+    /// A function or a module, which contains `var`s.
+    Fn,
+    Block,
+}
+
+#[derive(Debug)]
+struct Scope {
+    kind: ScopeKind,
+    bindings: HashMap<JsWord, Binding>,
+    /// Occurrences of names in the scope.
+    counts: HashMap<JsWord, usize>,
+    /// Depth of loops in the function, for `ScopeKind::Fn`.
+    loop_depth: usize,
+}
+
+#[derive(Debug)]
+struct Binding {
+    /// Name after renaming.
+    sym: JsWord,
+    /// False in the temporal dead zone of `let`, `const` and classes.
+    initialized: bool,
+}
+
+impl Folder {
+    fn push_scope(&mut self, kind: ScopeKind, counts: HashMap<JsWord, usize>) {
+        self.scopes.push(Scope {
+            kind,
+            bindings: Default::default(),
+            counts,
+            loop_depth: 0,
+        })
+    }
+
+    fn cur_fn(&mut self) -> &mut Scope {
+        self.scopes
+            .iter_mut()
+            .rev()
+            .find(|scope| scope.kind == ScopeKind::Fn)
+            .expect("block scoping: a scope of a function should exist")
+    }
+
+    /// Declares `sym` without renaming it.
+    fn declare(&mut self, sym: JsWord, initialized: bool) {
+        let scope = self.scopes.last_mut().unwrap();
+        scope.bindings.insert(
+            sym.clone(),
+            Binding {
+                sym,
+                initialized,
+            },
+        );
+    }
+
+    /// Declares bindings of `let` and `const`, which are renamed if the name
+    /// is used outside of the current block.
+    fn declare_block_scoped(&mut self, sym: JsWord) {
+        let scope = self.scopes.last().unwrap();
+        let renamed = if scope.kind == ScopeKind::Block {
+            let inside = scope.counts.get(&sym).cloned().unwrap_or(0);
+            let total = self.cur_fn().counts.get(&sym).cloned().unwrap_or(0);
+            if total > inside {
+                Some(self.fresh_name(&format!("_{}", sym)))
+            } else {
+                None
+            }
+        } else {
+            None
+        };
+
+        self.scopes.last_mut().unwrap().bindings.insert(
+            sym.clone(),
+            Binding {
+                sym: renamed.unwrap_or(sym),
+                initialized: false,
+            },
+        );
+    }
+
+    fn declare_decl(&mut self, decl: &Decl, initialized: bool) {
+        match *decl {
+            Decl::Var(ref v) if v.kind != VarDeclKind::Var => {
+                for d in &v.decls {
+                    let mut ids = vec![];
+                    pat_ids(&d.name, &mut ids);
+                    for id in ids {
+                        self.declare_block_scoped(id.sym);
+                    }
+                }
+            }
+            Decl::Class(ref c) => self.declare(c.ident.sym.clone(), initialized),
+            Decl::Fn(ref f) => self.declare(f.ident.sym.clone(), true),
+            _ => {}
+        }
+    }
+
+    /// Declares bindings created by a statement of the current scope.
+    fn declare_stmt(&mut self, stmt: &Stmt, initialized: bool) {
+        if let Stmt::Decl(ref decl) = *stmt {
+            self.declare_decl(decl, initialized)
+        }
+    }
+
+    /// Declares parameters and the top-level bindings of a function body.
+    fn declare_fn<T>(&mut self, params: &[Pat], body: Option<&T>, stmts: &[Stmt])
+    where
+        T: VisitWith<crate::util::VarCollector>,
+    {
+        for param in params {
+            let mut ids = vec![];
+            pat_ids(param, &mut ids);
+            for id in ids {
+                self.declare(id.sym, true);
+            }
+        }
+        for id in body.map(|body| var_ids(body)).unwrap_or_default() {
+            self.declare(id.sym, true);
+        }
+        for stmt in stmts {
+            self.declare_stmt(stmt, false);
+        }
+    }
+
+    /// `_a`, `_a2`, `_a3`, ...
+    fn fresh_name(&mut self, base: &str) -> JsWord {
+        let mut i = 1;
+        loop {
+            let name: JsWord = if i == 1 {
+                base.into()
+            } else {
+                format!("{}{}", base, i).into()
+            };
+            i += 1;
+
+            if self.cur_fn().counts.contains_key(&name) || self.generated.contains(&name) {
+                continue;
+            }
+            self.generated.insert(name.clone());
+            return name;
+        }
+    }
+
+    /// Returns the renamed name of a reference.
+    fn resolve(&mut self, i: &Ident) -> Option<JsWord> {
+        let mut crossed_fn = false;
+        for scope in self.scopes.iter().rev() {
+            if let Some(binding) = scope.bindings.get(&i.sym) {
+                // A function may be called after the declaration.
+                if !binding.initialized && !crossed_fn {
+                    self.errors.push(BlockScopingError {
+                        span: i.span,
+                        msg: format!("`{}` is used before its declaration", i.sym),
+                    });
+                }
+                return Some(binding.sym.clone());
+            }
+            if scope.kind == ScopeKind::Fn {
+                crossed_fn = true;
+            }
+        }
+
+        None
+    }
+
+    fn mark_initialized(&mut self, pat: &Pat) {
+        let mut ids = vec![];
+        pat_ids(pat, &mut ids);
+        for id in ids {
+            for scope in self.scopes.iter_mut().rev() {
+                if let Some(binding) = scope.bindings.get_mut(&id.sym) {
+                    binding.initialized = true;
+                    break;
+                }
+            }
+        }
+    }
+
+    fn fold_stmts<T>(&mut self, stmts: Vec<T>) -> Vec<T>
+    where
+        T: StmtLike + FoldWith<Self>,
+    {
+        let mut buf = Vec::with_capacity(stmts.len());
+        for stmt in stmts {
+            let start = self.pending.len();
+            let stmt = stmt.fold_with(self);
+            buf.extend(self.pending.drain(start..).map(T::from_stmt));
+            buf.push(stmt);
+        }
+        buf
+    }
+
+    /// Folds the head of `for in` and `for of`, which can't have initializers.
+    fn fold_loop_left(&mut self, left: VarDeclOrPat) -> VarDeclOrPat {
+        let loop_depth = mem::replace(&mut self.cur_fn().loop_depth, 0);
+        let left = left.fold_with(self);
+        self.cur_fn().loop_depth = loop_depth;
+        left
+    }
+
+    /// Folds the body of a loop.
+    fn fold_loop_body(&mut self, body: Box<Stmt>) -> Box<Stmt> {
+        self.cur_fn().loop_depth += 1;
+        let body = body.fold_with(self);
+        self.cur_fn().loop_depth -= 1;
+        body
+    }
+
+    /// Wraps `body` with a function if `captured` is true, so that each
+    /// iteration has its own bindings.
+    ///
+    /// `params` are the renamed bindings of the loop head.
+    fn wrap_loop_body(
+        &mut self,
+        span: Span,
+        captured: bool,
+        params: Vec<JsWord>,
+        pending: usize,
+        body: Box<Stmt>,
+        allow_assign: bool,
+    ) -> Box<Stmt> {
+        if !captured {
+            return body;
+        }
+
+        let mut checker = LoopBodyChecker {
+            params: if allow_assign { vec![] } else { params.clone() },
+            ..Default::default()
+        };
+        body.visit_with(&mut checker);
+        if let Some(reason) = checker.unsupported {
+            self.errors.push(BlockScopingError {
+                span,
+                msg: format!(
+                    "a loop with closures which capture its bindings can't use {}",
+                    reason
+                ),
+            });
+            return body;
+        }
+
+        let body = body.fold_with(&mut ContinueToReturn { loop_depth: 0 });
+        let mut stmts = self.pending.split_off(pending);
+        match *body {
+            Stmt::Block(block) => stmts.extend(block.stmts),
+            body => stmts.push(body),
+        }
+
+        let name = Ident::new(self.fresh_name("_loop"), DUMMY_SP);
+        let params: Vec<Ident> = params
+            .into_iter()
+            .map(|sym| Ident::new(sym, DUMMY_SP))
+            .collect();
+
+        // `var _loop = function (i) {};`
+        self.pending.push(Stmt::Decl(Decl::Var(VarDecl {
+            span: DUMMY_SP,
+            kind: VarDeclKind::Var,
+            declare: false,
+            decls: vec![VarDeclarator {
+                span: DUMMY_SP,
+                name: Pat::Ident(name.clone()),
+                init: Some(box Expr::Fn(FnExpr {
+                    ident: None,
+                    function: Function {
+                        span: DUMMY_SP,
+                        params: params
+                            .iter()
+                            .map(|p| Param {
+                                span: DUMMY_SP,
+                                decorators: vec![],
+                                pat: Pat::Ident(p.clone()),
+                            })
+                            .collect(),
+                        body: Some(BlockStmt {
+                            span: DUMMY_SP,
+                            stmts,
+                        }),
+                        generator_token: None,
+                        async_token: None,
+                        type_params: None,
+                        return_type: None,
+                    },
+                })),
+            }],
+        })));
+
+        // `_loop(i);`
+        box Stmt::Expr(ExprStmt {
+            span: DUMMY_SP,
+            expr: box Expr::Call(CallExpr {
+                span: DUMMY_SP,
+                callee: ExprOrSuper::Expr(box Expr::Ident(name)),
+                args: params
+                    .into_iter()
+                    .map(|p| ExprOrSpread {
+                        spread: None,
+                        expr: box Expr::Ident(p),
+                    })
+                    .collect(),
+            }),
+        })
+    }
+
+    /// Declares bindings of a loop head, and returns their names.
+    fn declare_loop_head(&mut self, decl: Option<&VarDecl>) -> Vec<JsWord> {
+        let decl = match decl {
+            Some(decl) if decl.kind != VarDeclKind::Var => decl,
+            _ => return vec![],
+        };
+
+        let mut ids = vec![];
+        for d in &decl.decls {
+            pat_ids(&d.name, &mut ids);
+        }
+        ids.into_iter()
+            .map(|id| {
+                self.declare_block_scoped(id.sym.clone());
+                id.sym
+            })
+            .collect()
+    }
+
+    fn renamed(&self, names: &[JsWord]) -> Vec<JsWord> {
+        let scope = self.scopes.last().unwrap();
+        names
+            .iter()
+            .map(|name| scope.bindings[name].sym.clone())
+            .collect()
+    }
+}
+
+impl Fold<Vec<Stmt>> for Folder {
+    fn fold(&mut self, stmts: Vec<Stmt>) -> Vec<Stmt> {
+        self.fold_stmts(stmts)
+    }
+}
+
+impl Fold<Vec<ModuleItem>> for Folder {
+    fn fold(&mut self, items: Vec<ModuleItem>) -> Vec<ModuleItem> {
+        self.fold_stmts(items)
+    }
+}
+
+impl Fold<Ident> for Folder {
+    fn fold(&mut self, i: Ident) -> Ident {
+        match self.resolve(&i) {
+            Some(sym) => Ident { sym, ..i },
+            None => i,
+        }
+    }
+}
+
+impl Fold<VarDecl> for Folder {
+    fn fold(&mut self, v: VarDecl) -> VarDecl {
+        if v.kind == VarDeclKind::Var {
+            return v.fold_children(self);
+        }
+
+        let kind = v.kind;
+        let in_loop = self.cur_fn().loop_depth != 0;
+        let decls = v
+            .decls
+            .into_iter()
+            .map(|d| {
+                let init = d.init.fold_with(self);
+                self.mark_initialized(&d.name);
+                let name = d.name.fold_with(self);
+
+                // `let a;` in a loop is reset on each iteration.
+                let init = match init {
+                    None if in_loop && kind == VarDeclKind::Let => {
+                        Some(box Expr::Unary(UnaryExpr {
+                            span: DUMMY_SP,
+                            op: op!("void"),
+                            arg: box Expr::Lit(Lit::Num(Number {
+                                span: DUMMY_SP,
+                                value: 0.0,
+                                raw: None,
+                            })),
+                        }))
+                    }
+                    init => init,
+                };
+
+                VarDeclarator { name, init, ..d }
+            })
+            .collect();
+
+        VarDecl {
+            kind: VarDeclKind::Var,
+            decls,
+            ..v
+        }
+    }
+}
+
+impl Fold<ClassDecl> for Folder {
+    fn fold(&mut self, c: ClassDecl) -> ClassDecl {
+        self.mark_initialized(&Pat::Ident(c.ident.clone()));
+        c.fold_children(self)
+    }
+}
+
+impl Fold<BlockStmt> for Folder {
+    fn fold(&mut self, block: BlockStmt) -> BlockStmt {
+        self.push_scope(ScopeKind::Block, count_names(&block));
+        for stmt in &block.stmts {
+            self.declare_stmt(stmt, false);
+        }
+        let block = block.fold_children(self);
+        self.scopes.pop();
+        block
+    }
+}
+
+impl Fold<SwitchStmt> for Folder {
+    fn fold(&mut self, s: SwitchStmt) -> SwitchStmt {
+        let discriminant = s.discriminant.fold_with(self);
+
+        // Cases share a scope.
+        self.push_scope(ScopeKind::Block, count_names(&s.cases));
+        for case in &s.cases {
+            for stmt in &case.cons {
+                self.declare_stmt(stmt, false);
+            }
+        }
+        let cases = s.cases.fold_with(self);
+        self.scopes.pop();
+
+        SwitchStmt {
+            discriminant,
+            cases,
+            ..s
+        }
+    }
+}
+
+impl Fold<CatchClause> for Folder {
+    fn fold(&mut self, c: CatchClause) -> CatchClause {
+        self.push_scope(ScopeKind::Block, Default::default());
+        if let Some(ref param) = c.param {
+            let mut ids = vec![];
+            pat_ids(param, &mut ids);
+            for id in ids {
+                self.declare(id.sym, true);
+            }
+        }
+        let c = c.fold_children(self);
+        self.scopes.pop();
+        c
+    }
+}
+
+impl Fold<ForStmt> for Folder {
+    fn fold(&mut self, s: ForStmt) -> ForStmt {
+        self.push_scope(ScopeKind::Block, count_names(&s));
+        let names = self.declare_loop_head(match s.init {
+            Some(VarDeclOrExpr::VarDecl(ref v)) => Some(v),
+            _ => None,
+        });
+
+        let captured = loop_captures(names.clone(), &s.body);
+
+        let init = s.init.fold_with(self);
+        let test = s.test.fold_with(self);
+        let update = s.update.fold_with(self);
+        let pending = self.pending.len();
+        let body = self.fold_loop_body(s.body);
+        let params = self.renamed(&names);
+        self.scopes.pop();
+
+        // Changes to bindings of the head should be visible to the next iteration.
+        let body = self.wrap_loop_body(s.span, captured, params, pending, body, false);
+
+        ForStmt {
+            init,
+            test,
+            update,
+            body,
+            ..s
+        }
+    }
+}
+
+impl Fold<ForInStmt> for Folder {
+    fn fold(&mut self, s: ForInStmt) -> ForInStmt {
+        self.push_scope(ScopeKind::Block, count_names(&s));
+        let names = self.declare_loop_head(match s.left {
+            VarDeclOrPat::VarDecl(ref v) => Some(v),
+            _ => None,
+        });
+
+        let captured = loop_captures(names.clone(), &s.body);
+
+        let right = s.right.fold_with(self);
+        let left = self.fold_loop_left(s.left);
+        let pending = self.pending.len();
+        let body = self.fold_loop_body(s.body);
+        let params = self.renamed(&names);
+        self.scopes.pop();
+
+        let body = self.wrap_loop_body(s.span, captured, params, pending, body, true);
+
+        ForInStmt {
+            left,
+            right,
+            body,
+            ..s
+        }
+    }
+}
+
+impl Fold<ForOfStmt> for Folder {
+    fn fold(&mut self, s: ForOfStmt) -> ForOfStmt {
+        self.push_scope(ScopeKind::Block, count_names(&s));
+        let names = self.declare_loop_head(match s.left {
+            VarDeclOrPat::VarDecl(ref v) => Some(v),
+            _ => None,
+        });
+
+        let captured = loop_captures(names.clone(), &s.body);
+
+        let right = s.right.fold_with(self);
+        let left = self.fold_loop_left(s.left);
+        let pending = self.pending.len();
+        let body = self.fold_loop_body(s.body);
+        let params = self.renamed(&names);
+        self.scopes.pop();
+
+        let body = self.wrap_loop_body(s.span, captured, params, pending, body, true);
+
+        ForOfStmt {
+            left,
+            right,
+            body,
+            ..s
+        }
+    }
+}
+
+impl Fold<WhileStmt> for Folder {
+    fn fold(&mut self, s: WhileStmt) -> WhileStmt {
+        let captured = loop_captures(vec![], &s.body);
+        let test = s.test.fold_with(self);
+        let pending = self.pending.len();
+        let body = self.fold_loop_body(s.body);
+        let body = self.wrap_loop_body(s.span, captured, vec![], pending, body, true);
+
+        WhileStmt { test, body, ..s }
+    }
+}
+
+impl Fold<DoWhileStmt> for Folder {
+    fn fold(&mut self, s: DoWhileStmt) -> DoWhileStmt {
+        let captured = loop_captures(vec![], &s.body);
+        let pending = self.pending.len();
+        let body = self.fold_loop_body(s.body);
+        let body = self.wrap_loop_body(s.span, captured, vec![], pending, body, true);
+        let test = s.test.fold_with(self);
+
+        DoWhileStmt { test, body, ..s }
+    }
+}
+
+impl Fold<Function> for Folder {
+    fn fold(&mut self, f: Function) -> Function {
+        self.push_scope(ScopeKind::Fn, count_names(&f));
+        let params: Vec<_> = f.params.iter().map(|p| p.pat.clone()).collect();
+        let stmts = f.body.as_ref().map(|b| &*b.stmts).unwrap_or(&[]);
+        self.declare_fn(&params, f.body.as_ref(), stmts);
+
+        let params = f.params.fold_with(self);
+        // The body is not a block scope.
+        let body = f.body.map(|body| BlockStmt {
+            stmts: body.stmts.fold_with(self),
+            ..body
+        });
+        self.scopes.pop();
+
+        Function { params, body, ..f }
+    }
+}
+
+impl Fold<ArrowExpr> for Folder {
+    fn fold(&mut self, f: ArrowExpr) -> ArrowExpr {
+        self.push_scope(ScopeKind::Fn, count_names(&f));
+        match f.body {
+            BlockStmtOrExpr::BlockStmt(ref body) => {
+                self.declare_fn(&f.params, Some(body), &body.stmts)
+            }
+            BlockStmtOrExpr::Expr(..) => self.declare_fn::<BlockStmt>(&f.params, None, &[]),
+        }
+
+        let params = f.params.fold_with(self);
+        let body = match f.body {
+            BlockStmtOrExpr::BlockStmt(body) => BlockStmtOrExpr::BlockStmt(BlockStmt {
+                stmts: body.stmts.fold_with(self),
+                ..body
+            }),
+            BlockStmtOrExpr::Expr(expr) => BlockStmtOrExpr::Expr(expr.fold_with(self)),
+        };
+        self.scopes.pop();
+
+        ArrowExpr { params, body, ..f }
+    }
+}
+
+impl Fold<GetterProp> for Folder {
+    fn fold(&mut self, p: GetterProp) -> GetterProp {
+        let key = p.key.fold_with(self);
+
+        self.push_scope(ScopeKind::Fn, count_names(&p.body));
+        self.declare_fn(&[], Some(&p.body), &p.body.stmts);
+        let body = BlockStmt {
+            stmts: p.body.stmts.fold_with(self),
+            ..p.body
+        };
+        self.scopes.pop();
+
+        GetterProp { key, body, ..p }
+    }
+}
+
+impl Fold<SetterProp> for Folder {
+    fn fold(&mut self, p: SetterProp) -> SetterProp {
+        let key = p.key.fold_with(self);
+
+        self.push_scope(ScopeKind::Fn, count_names(&p));
+        self.declare_fn(&[p.param.clone()], Some(&p.body), &p.body.stmts);
+        let param = p.param.fold_with(self);
+        let body = BlockStmt {
+            stmts: p.body.stmts.fold_with(self),
+            ..p.body
+        };
+        self.scopes.pop();
+
+        SetterProp {
+            key,
+            param,
+            body,
+            ..p
+        }
+    }
+}
+
+/// The name is only visible inside the function.
+impl Fold<FnExpr> for Folder {
+    fn fold(&mut self, e: FnExpr) -> FnExpr {
+        self.push_scope(ScopeKind::Block, Default::default());
+        if let Some(ref i) = e.ident {
+            self.declare(i.sym.clone(), true);
+        }
+        let function = e.function.fold_with(self);
+        self.scopes.pop();
+
+        FnExpr { function, ..e }
+    }
+}
+
+/// The name is only visible inside the class.
+impl Fold<ClassExpr> for Folder {
+    fn fold(&mut self, e: ClassExpr) -> ClassExpr {
+        self.push_scope(ScopeKind::Block, Default::default());
+        if let Some(ref i) = e.ident {
+            self.declare(i.sym.clone(), true);
+        }
+        let class = e.class.fold_with(self);
+        self.scopes.pop();
+
+        ClassExpr { class, ..e }
+    }
+}
+
+/// `{ a }` => `{ a: _a }`
+impl Fold<Prop> for Folder {
+    fn fold(&mut self, p: Prop) -> Prop {
+        match p {
+            Prop::Shorthand(i) => {
+                let value = i.clone().fold_with(self);
+                if value.sym == i.sym {
+                    return Prop::Shorthand(i);
+                }
+
+                Prop::KeyValue(KeyValueProp {
+                    key: PropName::Ident(i),
+                    value: box Expr::Ident(value),
+                })
+            }
+            _ => p.fold_children(self),
+        }
+    }
+}
+
+/// `{ a = 1 } = b` => `{ a: _a = 1 } = b`
+impl Fold<ObjectPatProp> for Folder {
+    fn fold(&mut self, p: ObjectPatProp) -> ObjectPatProp {
+        match p {
+            ObjectPatProp::Assign(AssignPatProp { span, key, value }) => {
+                let value = value.fold_with(self);
+                let renamed = key.clone().fold_with(self);
+                if renamed.sym == key.sym {
+                    return ObjectPatProp::Assign(AssignPatProp { span, key, value });
+                }
+
+                let pat = Pat::Ident(renamed);
+                ObjectPatProp::KeyValue(KeyValuePatProp {
+                    key: PropName::Ident(key),
+                    value: box match value {
+                        Some(right) => Pat::Assign(AssignPat {
+                            span,
+                            left: box pat,
+                            right,
+                        }),
+                        None => pat,
+                    },
+                })
+            }
+            _ => p.fold_children(self),
+        }
+    }
+}
+
+impl Fold<MemberExpr> for Folder {
+    fn fold(&mut self, e: MemberExpr) -> MemberExpr {
+        MemberExpr {
+            obj: e.obj.fold_with(self),
+            prop: if e.computed {
+                e.prop.fold_with(self)
+            } else {
+                e.prop
+            },
+            ..e
+        }
+    }
+}
+
+impl Fold<PropName> for Folder {
+    fn fold(&mut self, n: PropName) -> PropName {
+        match n {
+            PropName::Computed(..) => n.fold_children(self),
+            _ => n,
+        }
+    }
+}
+
+impl Fold<LabeledStmt> for Folder {
+    fn fold(&mut self, s: LabeledStmt) -> LabeledStmt {
+        LabeledStmt {
+            body: s.body.fold_with(self),
+            ..s
+        }
+    }
+}
+
+macro_rules! ignore {
+    ($($T:ty),*) => {
+        $(
+            impl Fold<$T> for Folder {
+                fn fold(&mut self, n: $T) -> $T {
+                    n
+                }
+            }
+        )*
+    };
+}
+
+// Labels and names of module exports which are not bindings.
+ignore!(BreakStmt, ContinueStmt, MetaPropExpr, ImportDecl, ExportSpecifier);
+
+/// Counts names which may be bindings, excluding properties and labels.
+fn count_names<T>(node: &T) -> HashMap<JsWord, usize>
+where
+    T: VisitWith<NameCounter>,
+{
+    let mut v = NameCounter::default();
+    node.visit_with(&mut v);
+    v.counts
+}
+
+#[derive(Default)]
+struct NameCounter {
+    counts: HashMap<JsWord, usize>,
+}
+
+impl Visit<Ident> for NameCounter {
+    fn visit(&mut self, i: &Ident) {
+        *self.counts.entry(i.sym.clone()).or_insert(0) += 1;
+    }
+}
+
+impl Visit<MemberExpr> for NameCounter {
+    fn visit(&mut self, e: &MemberExpr) {
+        e.obj.visit_with(self);
+        if e.computed {
+            e.prop.visit_with(self);
+        }
+    }
+}
+
+impl Visit<PropName> for NameCounter {
+    fn visit(&mut self, n: &PropName) {
+        if let PropName::Computed(ref c) = *n {
+            c.visit_with(self);
+        }
+    }
+}
+
+impl Visit<LabeledStmt> for NameCounter {
+    fn visit(&mut self, s: &LabeledStmt) {
+        s.body.visit_with(self);
+    }
+}
+
+impl Visit<BreakStmt> for NameCounter {
+    fn visit(&mut self, _: &BreakStmt) {}
+}
+
+impl Visit<ContinueStmt> for NameCounter {
+    fn visit(&mut self, _: &ContinueStmt) {}
+}
+
+/// Returns true if a closure in the body of a loop captures a binding of the
+/// loop, which are `names` of the head and `let`s and `const`s of the body.
+fn loop_captures(names: Vec<JsWord>, body: &Stmt) -> bool {
+    let mut names = names;
+    names.extend(block_scoped_names(body));
+    captures(body, &names)
+}
+
+/// Returns names of `let` and `const` in `node`, excluding nested functions.
+fn block_scoped_names(node: &Stmt) -> Vec<JsWord> {
+    struct Collector(Vec<JsWord>);
+
+    impl Visit<VarDecl> for Collector {
+        fn visit(&mut self, v: &VarDecl) {
+            if v.kind != VarDeclKind::Var {
+                let mut ids = vec![];
+                for d in &v.decls {
+                    pat_ids(&d.name, &mut ids);
+                }
+                self.0.extend(ids.into_iter().map(|id| id.sym));
+            }
+            v.visit_children(self);
+        }
+    }
+
+    impl Visit<Function> for Collector {
+        fn visit(&mut self, _: &Function) {}
+    }
+
+    impl Visit<ArrowExpr> for Collector {
+        fn visit(&mut self, _: &ArrowExpr) {}
+    }
+
+    let mut v = Collector(vec![]);
+    node.visit_with(&mut v);
+    v.0
+}
+
+/// Returns true if a function in `node` uses one of `names`.
+fn captures(node: &Stmt, names: &[JsWord]) -> bool {
+    struct Finder<'a> {
+        names: &'a [JsWord],
+        fn_depth: usize,
+        found: bool,
+    }
+
+    impl<'a> Visit<Ident> for Finder<'a> {
+        fn visit(&mut self, i: &Ident) {
+            if self.fn_depth != 0 && self.names.contains(&i.sym) {
+                self.found = true;
+            }
+        }
+    }
+
+    impl<'a> Visit<Function> for Finder<'a> {
+        fn visit(&mut self, f: &Function) {
+            self.fn_depth += 1;
+            f.visit_children(self);
+            self.fn_depth -= 1;
+        }
+    }
+
+    impl<'a> Visit<ArrowExpr> for Finder<'a> {
+        fn visit(&mut self, f: &ArrowExpr) {
+            self.fn_depth += 1;
+            f.visit_children(self);
+            self.fn_depth -= 1;
+        }
+    }
+
+    if names.is_empty() {
+        return false;
+    }
+    let mut v = Finder {
+        names,
+        fn_depth: 0,
+        found: false,
+    };
+    node.visit_with(&mut v);
+    v.found
+}
+
+/// Finds code in a loop body which behaves differently if the body is moved
+/// into a function.
+#[derive(Default)]
+struct LoopBodyChecker {
+    /// Bindings of the loop head which must not be assigned.
+    params: Vec<JsWord>,
+    fn_depth: usize,
+    arrow_depth: usize,
+    /// Loops and switches in the body.
+    breakable_depth: usize,
+    /// Labels in the body.
+    labels: Vec<JsWord>,
+    unsupported: Option<&'static str>,
+}
+
+impl LoopBodyChecker {
+    fn in_body(&self) -> bool {
+        self.fn_depth == 0 && self.arrow_depth == 0
+    }
+
+    fn report(&mut self, reason: &'static str) {
+        if self.unsupported.is_none() {
+            self.unsupported = Some(reason);
+        }
+    }
+
+    fn check_assign(&mut self, pat_ids: Vec<Ident>) {
+        if pat_ids.iter().any(|id| self.params.contains(&id.sym)) {
+            self.report("assignments to bindings of the loop head");
+        }
+    }
+}
+
+impl Visit<Function> for LoopBodyChecker {
+    fn visit(&mut self, f: &Function) {
+        self.fn_depth += 1;
+        f.visit_children(self);
+        self.fn_depth -= 1;
+    }
+}
+
+impl Visit<ArrowExpr> for LoopBodyChecker {
+    fn visit(&mut self, f: &ArrowExpr) {
+        self.arrow_depth += 1;
+        f.visit_children(self);
+        self.arrow_depth -= 1;
+    }
+}
+
+impl Visit<Stmt> for LoopBodyChecker {
+    fn visit(&mut self, s: &Stmt) {
+        if !self.in_body() {
+            return s.visit_children(self);
+        }
+
+        match *s {
+            Stmt::Return(..) => self.report("`return`"),
+            Stmt::Break(BreakStmt { label: None, .. }) if self.breakable_depth == 0 => {
+                self.report("`break`")
+            }
+            Stmt::Continue(ContinueStmt {
+                label: Some(ref label),
+                ..
+            })
+            | Stmt::Break(BreakStmt {
+                label: Some(ref label),
+                ..
+            }) if !self.labels.contains(&label.sym) => self.report("labeled jumps out of it"),
+            Stmt::Labeled(LabeledStmt { ref label, .. }) => {
+                self.labels.push(label.sym.clone());
+                s.visit_children(self);
+                self.labels.pop();
+            }
+            Stmt::Switch(..) => {
+                self.breakable_depth += 1;
+                s.visit_children(self);
+                self.breakable_depth -= 1;
+            }
+            Stmt::For(..) | Stmt::ForIn(..) | Stmt::ForOf(..) | Stmt::While(..)
+            | Stmt::DoWhile(..) => {
+                self.breakable_depth += 1;
+                s.visit_children(self);
+                self.breakable_depth -= 1;
+            }
+            _ => s.visit_children(self),
+        }
+    }
+}
+
+impl Visit<Expr> for LoopBodyChecker {
+    fn visit(&mut self, e: &Expr) {
+        match *e {
+            Expr::This(..) if self.fn_depth == 0 => self.report("`this`"),
+            Expr::Ident(ref i) if self.fn_depth == 0 && i.sym == js_word!("arguments") => {
+                self.report("`arguments`")
+            }
+            Expr::Yield(..) if self.fn_depth == 0 => self.report("`yield`"),
+            Expr::Await(..) if self.in_body() => self.report("`await`"),
+            Expr::Assign(AssignExpr { ref left, .. }) => {
+                let mut ids = vec![];
+                match *left {
+                    PatOrExpr::Pat(ref pat) => pat_ids(pat, &mut ids),
+                    PatOrExpr::Expr(box Expr::Ident(ref i)) => ids.push(i.clone()),
+                    PatOrExpr::Expr(..) => {}
+                }
+                self.check_assign(ids);
+            }
+            Expr::Update(UpdateExpr {
+                arg: box Expr::Ident(ref i),
+                ..
+            }) => self.check_assign(vec![i.clone()]),
+            _ => {}
+        }
+
+        e.visit_children(self)
+    }
+}
+
+/// Replaces `continue` of the loop with `return`.
+struct ContinueToReturn {
+    loop_depth: usize,
+}
+
+impl Fold<Stmt> for ContinueToReturn {
+    fn fold(&mut self, s: Stmt) -> Stmt {
+        match s {
+            Stmt::Continue(ContinueStmt { span, label: None }) if self.loop_depth == 0 => {
+                Stmt::Return(ReturnStmt { span, arg: None })
+            }
+            Stmt::For(..) | Stmt::ForIn(..) | Stmt::ForOf(..) | Stmt::While(..)
+            | Stmt::DoWhile(..) => {
+                self.loop_depth += 1;
+                let s = s.fold_children(self);
+                self.loop_depth -= 1;
+                s
+            }
+            _ => s.fold_children(self),
+        }
+    }
+}
+
+impl Fold<Function> for ContinueToReturn {
+    fn fold(&mut self, f: Function) -> Function {
+        f
+    }
+}
+
+impl Fold<ArrowExpr> for ContinueToReturn {
+    fn fold(&mut self, f: ArrowExpr) -> ArrowExpr {
+        f
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        BlockScoping::default(),
+        let_to_var,
+        "let a = 1; const b = 2; { let c = a + b; }",
+        "var a = 1; var b = 2; { var c = a + b; }"
+    );
+
+    test!(
+        BlockScoping::default(),
+        shadowed,
+        "let a = 1; { let a = 2; b({ a }); } function f() { { let a; } return a; }",
+        "var a = 1; { var _a = 2; b({ a: _a }); } function f() { { var _a2; } return a; }"
+    );
+
+    test!(
+        BlockScoping::default(),
+        closure_in_loop,
+        r#"for (let i = 0; i < 3; i++) {
+  if (i === 1) continue;
+  fns.push(function () { return i; });
+}"#,
+        r#"var _loop = function (i) {
+  if (i === 1) return;
+  fns.push(function () { return i; });
+};
+for (var i = 0; i < 3; i++) _loop(i);"#
+    );
+
+    test!(
+        BlockScoping::default(),
+        let_in_loop,
+        "while (a) { let b; c(b); }",
+        "while (a) { var b = void 0; c(b); }"
+    );
+
+    #[test]
+    fn errors() {
+        fn errors(src: &str) -> Vec<String> {
+            let pass = BlockScoping::default();
+            let errors = pass.errors.clone();
+            crate::tests::Tester::run(crate::tests::syntax(), |tester| {
+                tester.apply_transform(pass, "input.js", src)?;
+                Ok(())
+            });
+            let errors = errors.lock().unwrap();
+            errors.iter().map(|err| err.to_string()).collect()
+        }
+
+        assert_eq!(
+            errors("{ a; let a; }"),
+            vec!["`a` is used before its declaration"]
+        );
+        assert_eq!(errors("function f() { return a; } let a;"), Vec::<String>::new());
+    }
+}
@@ -4,8 +4,10 @@ use std::{
     iter,
     sync::{atomic::Ordering, Arc},
 };
+use swc_atoms::JsWord;
 use swc_common::{Fold, FoldWith, Span, Spanned, DUMMY_SP};
 
+pub(crate) mod props;
 #[cfg(test)]
 mod tests;
 
@@ -75,6 +77,7 @@ impl Fold<Stmt> for Classes {
                 Stmt::Decl(Decl::Var(VarDecl {
                     span,
                     kind: VarDeclKind::Var,
+                    declare: false,
                     decls: vec![VarDeclarator {
                         span,
                         init: Some(box rhs),
@@ -116,8 +119,10 @@ impl Classes {
         fn determine_super_ident(sc: &Expr) -> Ident {
             match *sc {
                 Expr::Ident(ref i) => quote_ident!(i.span, format!("_{}", i.sym)),
-                Expr::Member(ref member) => determine_super_ident(&member.prop),
-                _ => unimplemented!("determine_super_ident({:?})", sc),
+                Expr::Member(ref member) if !member.computed => {
+                    determine_super_ident(&member.prop)
+                }
+                _ => quote_ident!("_super"),
             }
         }
         // Ident of the super class *inside* function.
@@ -125,7 +130,11 @@ impl Classes {
 
         let (params, args) = if let Some(ref super_ident) = super_ident {
             (
-                vec![Pat::Ident(super_ident.clone())],
+                vec![Param {
+                    span: DUMMY_SP,
+                    decorators: vec![],
+                    pat: Pat::Ident(super_ident.clone()),
+                }],
                 vec![class.super_class.clone().unwrap().as_arg()],
             )
         } else {
@@ -146,7 +155,9 @@ impl Classes {
                     async_token: None,
                     generator_token: None,
                     params,
-                    body,
+                    body: Some(body),
+                    type_params: None,
+                    return_type: None,
                 },
             })
             .as_callee(),
@@ -171,63 +182,103 @@ impl Classes {
                 .possible_constructor_return
                 .store(true, Ordering::SeqCst);
 
-            stmts.push(Stmt::Expr(box Expr::Call(CallExpr {
+            stmts.push(
+                Expr::Call(CallExpr {
+                    span: DUMMY_SP,
+                    callee: quote_ident!("_inherits").as_callee(),
+                    args: vec![
+                        class_name.clone().as_arg(),
+                        super_class_ident.clone().as_arg(),
+                    ],
+                })
+                .into_stmt(),
+            );
+        }
+
+        let (body, props) = props::lower(&self.helpers, &class_name, class.body);
+        class.body = body;
+        if !props.vars.is_empty() {
+            stmts.push(Stmt::Decl(Decl::Var(VarDecl {
                 span: DUMMY_SP,
-                callee: quote_ident!("_inherits").as_callee(),
-                args: vec![
-                    class_name.clone().as_arg(),
-                    super_class_ident.clone().as_arg(),
-                ],
+                kind: VarDeclKind::Var,
+                declare: false,
+                decls: props.vars,
             })));
         }
+        let mut instance_props = props.instance;
 
         // Process constructor
         {
             let constructor = {
                 // Find constuctor
-                let pos = class.body.iter().position(|m| match m.kind {
-                    ClassMethodKind::Constructor => true,
+                let pos = class.body.iter().position(|m| match *m {
+                    ClassMember::Method(ClassMethod {
+                        kind: ClassMethodKind::Constructor,
+                        ..
+                    }) => true,
                     _ => false,
                 });
                 match pos {
-                    Some(pos) => Some(class.body.remove(pos)),
+                    Some(pos) => match class.body.remove(pos) {
+                        ClassMember::Method(constructor) => Some(constructor),
+                        _ => unreachable!(),
+                    },
                     _ => None,
                 }
             };
-            let mut function = constructor.map(|c| c.function).unwrap_or_else(|| Function {
-                async_token: None,
-                generator_token: None,
-                span: class_name.span,
-                params: vec![],
-                body: BlockStmt {
-                    span: DUMMY_SP,
-                    stmts: vec![],
-                },
+            let mut function = constructor
+                .map(|c| {
+                    c.function.fold_with(&mut SuperCallFolder {
+                        class_name: &class_name,
+                        is_static: false,
+                        helpers: self.helpers.clone(),
+                    })
+                })
+                .unwrap_or_else(|| Function {
+                    async_token: None,
+                    generator_token: None,
+                    span: class_name.span,
+                    params: vec![],
+                    body: None,
+                    type_params: None,
+                    return_type: None,
+                });
+            let mut body = function.body.take().unwrap_or_else(|| BlockStmt {
+                span: DUMMY_SP,
+                stmts: vec![],
             });
 
             // inject _classCallCheck(this, Bar);
-            function.body.stmts = iter::once(Stmt::Expr(box Expr::Call(CallExpr {
-                span: DUMMY_SP,
-                callee: Expr::Ident(quote_ident!("_classCallCheck")).as_callee(),
-                args: vec![
-                    Expr::This(ThisExpr { span: DUMMY_SP }).as_arg(),
-                    Expr::Ident(class_name.clone()).as_arg(),
-                ],
-            })))
-            .chain(function.body.stmts)
+            self.helpers.class_call_check.store(true, Ordering::SeqCst);
+            body.stmts = iter::once(
+                Expr::Call(CallExpr {
+                    span: DUMMY_SP,
+                    callee: Expr::Ident(quote_ident!("_classCallCheck")).as_callee(),
+                    args: vec![
+                        Expr::This(ThisExpr { span: DUMMY_SP }).as_arg(),
+                        Expr::Ident(class_name.clone()).as_arg(),
+                    ],
+                })
+                .into_stmt(),
+            )
+            .chain(body.stmts)
             .collect();
 
             if super_class_ident.is_some() {
                 // inject possibleReturnCheck
-                let super_call_pos = function.body.stmts.iter().position(|c| match *c {
-                    Stmt::Expr(box Expr::Call(CallExpr {
-                        callee: ExprOrSuper::Super(..),
+                let super_call_pos = body.stmts.iter().position(|c| match *c {
+                    Stmt::Expr(ExprStmt {
+                        expr:
+                            box Expr::Call(CallExpr {
+                                callee: ExprOrSuper::Super(..),
+                                ..
+                            }),
                         ..
-                    })) => true,
+                    }) => true,
                     _ => false,
                 });
                 // is super() call last?
-                let is_last = super_call_pos == Some(function.body.stmts.len() - 1);
+                let is_last = super_call_pos == Some(body.stmts.len() - 1);
 
                 // possible return value from super() call
                 let possible_return_value = box Expr::Call(CallExpr {
@@ -254,12 +305,16 @@ impl Classes {
                             args: if let Some(super_call_pos) = super_call_pos {
                                 // Code like `super(foo, bar)` should be result in
                                 // `.call(this, foo, bar)`
-                                match function.body.stmts[super_call_pos] {
-                                    Stmt::Expr(box Expr::Call(CallExpr {
-                                        callee: ExprOrSuper::Super(..),
-                                        ref args,
+                                match body.stmts[super_call_pos] {
+                                    Stmt::Expr(ExprStmt {
+                                        expr:
+                                            box Expr::Call(CallExpr {
+                                                callee: ExprOrSuper::Super(..),
+                                                ref args,
+                                                ..
+                                            }),
                                         ..
-                                    })) => iter::once(ThisExpr { span: DUMMY_SP }.as_arg())
+                                    }) => iter::once(ThisExpr { span: DUMMY_SP }.as_arg())
                                         .chain(args.into_iter().cloned())
                                         .collect(),
                                     _ => unreachable!(),
@@ -276,38 +331,60 @@ impl Classes {
                     }],
                 });
 
+                // `var _this = _possibleConstructorReturn(..)`
+                let var_this = Stmt::Decl(Decl::Var(VarDecl {
+                    span: DUMMY_SP,
+                    kind: VarDeclKind::Var,
+                    declare: false,
+                    decls: vec![VarDeclarator {
+                        span: DUMMY_SP,
+                        name: quote_ident!("_this").into(),
+                        init: Some(possible_return_value.clone()),
+                    }],
+                }));
+                let return_this = Stmt::Return(ReturnStmt {
+                    span: DUMMY_SP,
+                    arg: Some(box Expr::Ident(quote_ident!("_this"))),
+                });
+
+                // Fields are initialized right after `super()`.
                 match super_call_pos {
                     Some(super_call_pos) => {
-                        if !is_last {
-                            function.body.stmts[super_call_pos] = Stmt::Decl(Decl::Var(VarDecl {
-                                span: DUMMY_SP,
-                                kind: VarDeclKind::Var,
-                                decls: vec![VarDeclarator {
-                                    span: DUMMY_SP,
-                                    name: quote_ident!("_this").into(),
-                                    init: Some(possible_return_value),
-                                }],
-                            }));
-
-                            function.body.stmts.push(Stmt::Return(ReturnStmt {
-                                span: DUMMY_SP,
-                                arg: Some(box Expr::Ident(quote_ident!("_this"))),
-                            }));
+                        if !is_last || !instance_props.is_empty() {
+                            body.stmts[super_call_pos] = var_this;
+                            let rest = body.stmts.split_off(super_call_pos + 1);
+                            body.stmts.extend(instance_props.drain(..));
+                            body.stmts.extend(rest);
+                            body.stmts.push(return_this);
                         } else {
-                            function.body.stmts[super_call_pos] = Stmt::Return(ReturnStmt {
+                            body.stmts[super_call_pos] = Stmt::Return(ReturnStmt {
                                 span: DUMMY_SP,
                                 arg: Some(possible_return_value),
                             });
                         }
                     }
 
-                    _ => function.body.stmts.push(Stmt::Return(ReturnStmt {
+                    _ if !instance_props.is_empty() => {
+                        body.stmts.push(var_this);
+                        body.stmts.extend(instance_props.drain(..));
+                        body.stmts.push(return_this);
+                    }
+
+                    _ => body.stmts.push(Stmt::Return(ReturnStmt {
                         span: DUMMY_SP,
                         arg: Some(possible_return_value),
                     })),
                 }
+            } else {
+                // Fields are initialized before the body, after
+                // `_classCallCheck`.
+                let rest = body.stmts.split_off(1);
+                body.stmts.extend(instance_props.drain(..));
+                body.stmts.extend(rest);
             }
 
+            function.body = Some(body);
+
             // TODO: Handle
             //
             //     console.log('foo');
@@ -318,12 +395,22 @@ impl Classes {
 
             stmts.push(Stmt::Decl(Decl::Fn(FnDecl {
                 ident: class_name.clone(),
+                declare: false,
                 function,
             })));
         }
 
         // convert class methods
-        stmts.extend(self.fold_class_methods(class_name.clone(), class.body));
+        let methods = class
+            .body
+            .into_iter()
+            .map(|member| match member {
+                ClassMember::Method(m) => m,
+                _ => unreachable!("members other than methods are lowered by props::lower"),
+            })
+            .collect();
+        stmts.extend(self.fold_class_methods(class_name.clone(), methods));
+        stmts.extend(props.statics);
 
         // `return Foo`
         stmts.push(Stmt::Return(ReturnStmt {
@@ -335,23 +422,9 @@ impl Classes {
     }
 
     fn fold_class_methods(&mut self, class_name: Ident, methods: Vec<ClassMethod>) -> Vec<Stmt> {
-        /// { key: "prop" }
-        fn mk_prop_key(key: &PropName) -> Prop {
-            Prop::KeyValue(KeyValueProp {
-                key: PropName::Ident(quote_ident!(key.span(), "key")),
-                value: match *key {
-                    PropName::Ident(ref i) => {
-                        box Expr::Lit(Lit::Str(quote_str!(i.span, i.sym.clone())))
-                    }
-                    PropName::Str(ref s) => box Expr::Lit(Lit::Str(s.clone())),
-                    _ => unimplemented!("PropName {:?}", key),
-                },
-            })
-        }
-
         fn mk_arg_obj_for_create_class(props: Vec<Expr>) -> ExprOrSpread {
             if props.is_empty() {
-                return quote_expr!(DUMMY_SP, null).as_arg();
+                return quote_expr!(DUMMY_SP, "null").as_arg();
             }
             Expr::Array(ArrayLit {
                 span: DUMMY_SP,
@@ -366,23 +439,52 @@ impl Classes {
             methods: ExprOrSpread,
             static_methods: Option<ExprOrSpread>,
         ) -> Stmt {
-            Stmt::Expr(box Expr::Call(CallExpr {
+            Expr::Call(CallExpr {
                 span: DUMMY_SP,
                 callee: quote_ident!("_createClass").as_callee(),
                 args: iter::once(class_name.as_arg())
                     .chain(iter::once(methods))
                     .chain(static_methods)
                     .collect(),
-            }))
+            })
+            .into_stmt()
         }
 
         let (mut props, mut static_props) = (vec![], vec![]);
 
         for m in methods {
-            let prop_name = match m.key {
-                PropName::Ident(ref i) => i,
-                _ => unimplemented!("non-ident prop name: {:?}", m.key),
+            let append_to: &mut Vec<Descriptor> = if m.static_token.is_some() {
+                &mut static_props
+            } else {
+                &mut props
+            };
+
+            let function = m.function.fold_with(&mut SuperCallFolder {
+                class_name: &class_name,
+                is_static: m.static_token.is_some(),
+                helpers: self.helpers.clone(),
+            });
+
+            // `get a() {}` and `set a(v) {}` share a descriptor.
+            let pos = match m.kind {
+                ClassMethodKind::Getter | ClassMethodKind::Setter => {
+                    let sym = prop_name_sym(&m.key);
+                    append_to.iter().position(|d| {
+                        d.value.is_none() && sym.is_some() && prop_name_sym(&d.key) == sym
+                    })
+                }
+                _ => None,
             };
+            let pos = pos.unwrap_or_else(|| {
+                append_to.push(Descriptor {
+                    key: m.key.clone(),
+                    value: None,
+                    get: None,
+                    set: None,
+                });
+                append_to.len() - 1
+            });
+            let desc = &mut append_to[pos];
 
             match m.kind {
                 ClassMethodKind::Constructor => unreachable!(),
@@ -390,41 +492,56 @@ impl Classes {
                 //  Foo.staticMethod
                 //  Foo.prototype.method
                 ClassMethodKind::Method => {
-                    let append_to: &mut Vec<_> = if let Some(_static_token) = m.static_token {
-                        &mut static_props
-                    } else {
-                        &mut props
+                    let ident = match m.key {
+                        PropName::Ident(ref i) => Some(i.clone()),
+                        _ => None,
                     };
-
-                    let function = m.function.fold_with(&mut SuperCallFolder {
-                        class_name: &class_name,
-                        helpers: self.helpers.clone(),
-                    });
-
-                    let value = box Expr::Fn(FnExpr {
-                        ident: Some(prop_name.clone()),
+                    desc.value = Some(box Expr::Fn(FnExpr { ident, function }));
+                }
+                ClassMethodKind::Getter => {
+                    desc.get = Some(box Expr::Fn(FnExpr {
+                        ident: Some(quote_ident!("get")),
+                        function,
+                    }));
+                }
+                ClassMethodKind::Setter => {
+                    desc.set = Some(box Expr::Fn(FnExpr {
+                        ident: Some(quote_ident!("set")),
                         function,
-                    });
-
-                    append_to.push(Expr::Object(ObjectLit {
-                        span: DUMMY_SP,
-                        props: vec![
-                            PropOrSpread::Prop(box mk_prop_key(&m.key)),
-                            PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
-                                key: PropName::Ident(quote_ident!("value")),
-                                value,
-                            })),
-                        ],
                     }));
                 }
-
-                _ => unimplemented!("Class getter / setter"),
             }
         }
 
         if props.is_empty() && static_props.is_empty() {
             return vec![];
         }
+        self.helpers.create_class.store(true, Ordering::SeqCst);
+
+        /// `{ key: "prop", value: function prop() {} }`
+        fn mk_descriptor(desc: Descriptor) -> Expr {
+            let mut obj_props = vec![PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                key: PropName::Ident(quote_ident!(desc.key.span(), "key")),
+                value: mk_key(desc.key),
+            }))];
+            let fields = vec![("value", desc.value), ("get", desc.get), ("set", desc.set)];
+            for (name, value) in fields {
+                if let Some(value) = value {
+                    obj_props.push(PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                        key: PropName::Ident(quote_ident!(name)),
+                        value,
+                    })));
+                }
+            }
+
+            Expr::Object(ObjectLit {
+                span: DUMMY_SP,
+                props: obj_props,
+            })
+        }
+
+        let props = props.into_iter().map(mk_descriptor).collect();
+        let static_props: Vec<_> = static_props.into_iter().map(mk_descriptor).collect();
 
         vec![mk_create_class_call(
             class_name,
@@ -438,6 +555,33 @@ impl Classes {
     }
 }
 
+/// An element of arrays passed to `_createClass`.
+struct Descriptor {
+    key: PropName,
+    value: Option<Box<Expr>>,
+    get: Option<Box<Expr>>,
+    set: Option<Box<Expr>>,
+}
+
+/// `"prop"` of `{ key: "prop" }`
+fn mk_key(key: PropName) -> Box<Expr> {
+    match key {
+        PropName::Ident(i) => box Expr::Lit(Lit::Str(quote_str!(i.span, i.sym))),
+        PropName::Str(s) => box Expr::Lit(Lit::Str(s)),
+        PropName::Num(n) => box Expr::Lit(Lit::Num(n)),
+        PropName::Computed(c) => c.expr,
+    }
+}
+
+/// Name of a non-computed property.
+fn prop_name_sym(key: &PropName) -> Option<JsWord> {
+    match *key {
+        PropName::Ident(ref i) => Some(i.sym.clone()),
+        PropName::Str(ref s) => Some(s.value.clone()),
+        PropName::Num(..) | PropName::Computed(..) => None,
+    }
+}
+
 /// Creates
 ///
 /// ```js
@@ -482,44 +626,32 @@ fn get_prototype_of(obj: &Expr) -> Expr {
 ///
 /// _get(Child.prototype.__proto__ || Object.getPrototypeOf(Child.prototype),
 /// 'foo', this).call(this, a);
+///
+/// `Child.__proto__` is used instead of `Child.prototype.__proto__` for static
+/// methods.
 #[derive(Debug)]
 struct SuperCallFolder<'a> {
     class_name: &'a Ident,
+    is_static: bool,
     helpers: Arc<Helpers>,
 }
 
-struct SuperCalleeFolder<'a> {
-    class_name: &'a Ident,
-    did_work: bool,
-}
-
-impl<'a> Fold<Expr> for SuperCalleeFolder<'a> {
-    fn fold(&mut self, n: Expr) -> Expr {
-        let n = n.fold_children(self);
-
-        match n {
-            Expr::Member(MemberExpr {
-                obj: ExprOrSuper::Super(super_token),
-                prop,
-                ..
-            }) => self.super_to_get_call(super_token, prop),
-
-            _ => n,
-        }
-    }
-}
-
-impl<'a> SuperCalleeFolder<'a> {
-    fn super_to_get_call(&mut self, super_token: Span, prop: Box<Expr>) -> Expr {
-        self.did_work = true;
+impl<'a> SuperCallFolder<'a> {
+    fn super_to_get_call(&mut self, super_token: Span, prop: Box<Expr>, computed: bool) -> Expr {
+        self.helpers.get.store(true, Ordering::SeqCst);
         let super_token = mark!(super_token);
 
-        let proto_arg = get_prototype_of(&Expr::Member(MemberExpr {
-            span: super_token,
-            obj: ExprOrSuper::Expr(box Expr::Ident(self.class_name.clone())),
-            prop: box Expr::Ident(quote_ident!("prototype")),
-            computed: false,
-        }))
+        let class_name = Expr::Ident(self.class_name.clone());
+        let proto_arg = if self.is_static {
+            get_prototype_of(&class_name)
+        } else {
+            get_prototype_of(&Expr::Member(MemberExpr {
+                span: super_token,
+                obj: ExprOrSuper::Expr(box class_name),
+                prop: box Expr::Ident(quote_ident!("prototype")),
+                computed: false,
+            }))
+        }
         .as_arg();
 
         let prop_arg = match *prop {
@@ -527,13 +659,13 @@ impl<'a> SuperCalleeFolder<'a> {
                 sym: ref value,
                 span,
                 ..
-            }) => Expr::Lit(Lit::Str(Str {
+            }) if !computed => Expr::Lit(Lit::Str(Str {
                 span,
                 value: value.clone(),
                 has_escape: false,
+                raw: None,
             })),
-            ref e @ Expr::Lit(Lit::Str(Str { .. })) => e.clone(),
-            _ => unimplemented!("non-ident / non-string super field"),
+            _ => *prop,
         }
         .as_arg();
 
@@ -549,40 +681,48 @@ impl<'a> SuperCalleeFolder<'a> {
 
 impl<'a> Fold<Expr> for SuperCallFolder<'a> {
     fn fold(&mut self, n: Expr) -> Expr {
-        let mut callee_folder = SuperCalleeFolder {
-            class_name: self.class_name,
-            did_work: false,
-        };
-
-        let was_call = match n {
-            Expr::Call(..) => true,
-            _ => false,
-        };
-
-        let n = n.fold_with(&mut callee_folder);
-        if callee_folder.did_work {
-            self.helpers.get.store(true, Ordering::SeqCst);
+        match n {
+            // `super.foo(a)`
+            Expr::Call(CallExpr {
+                span,
+                callee:
+                    ExprOrSuper::Expr(box Expr::Member(MemberExpr {
+                        obj: ExprOrSuper::Super(super_token),
+                        prop,
+                        computed,
+                        ..
+                    })),
+                args,
+            }) => {
+                let prop = if computed { prop.fold_with(self) } else { prop };
+                let args = args.fold_with(self);
+                let callee = self.super_to_get_call(super_token, prop, computed);
 
-            if was_call {
-                match n {
-                    Expr::Call(CallExpr { span, callee, args }) => {
-                        return Expr::Call(CallExpr {
-                            span: DUMMY_SP,
-                            callee: MemberExpr {
-                                span: DUMMY_SP,
-                                obj: callee,
-                                prop: box Expr::Ident(quote_ident!("call")),
-                                computed: false,
-                            }
-                            .as_callee(),
-                            args: iter::once(ThisExpr { span }.as_arg()).chain(args).collect(),
-                        });
+                Expr::Call(CallExpr {
+                    span: DUMMY_SP,
+                    callee: MemberExpr {
+                        span: DUMMY_SP,
+                        obj: ExprOrSuper::Expr(box callee),
+                        prop: box Expr::Ident(quote_ident!("call")),
+                        computed: false,
                     }
-                    _ => unreachable!(),
-                }
+                    .as_callee(),
+                    args: iter::once(ThisExpr { span }.as_arg()).chain(args).collect(),
+                })
             }
-        }
 
-        n
+            // `super.foo`
+            Expr::Member(MemberExpr {
+                obj: ExprOrSuper::Super(super_token),
+                prop,
+                computed,
+                ..
+            }) => {
+                let prop = if computed { prop.fold_with(self) } else { prop };
+                self.super_to_get_call(super_token, prop, computed)
+            }
+
+            _ => n.fold_children(self),
+        }
     }
 }
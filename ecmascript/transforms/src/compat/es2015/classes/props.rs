@@ -0,0 +1,664 @@
+use super::{mk_key, SuperCallFolder};
+use ast::*;
+use crate::{
+    compat::{helpers::Helpers, to_bin_op},
+    util::ExprFactory,
+};
+use std::{
+    collections::HashMap,
+    iter,
+    sync::{atomic::Ordering, Arc},
+};
+use swc_atoms::JsWord;
+use swc_common::{Fold, FoldWith, Mark, Span, Spanned, DUMMY_SP};
+
+/// Class members other than methods, lowered to statements.
+///
+/// A private name `#x` becomes a `WeakMap` from instances (or the class for
+/// static members) to descriptors.
+#[derive(Default)]
+pub(crate) struct Props {
+    /// Private names, temporaries and computed keys, declared before the
+    /// constructor.
+    pub vars: Vec<VarDeclarator>,
+    /// Initializes instances, after `super()` for derived classes.
+    pub instance: Vec<Stmt>,
+    /// Initializes the class after methods are defined.
+    pub statics: Vec<Stmt>,
+}
+
+/// Collects fields, private methods and static blocks of `body`.
+///
+/// Returns the remaining methods, with private names replaced.
+///
+/// Declared names are unique in the class and marked with a fresh mark, so
+/// `hygiene` renames them if classes sharing a scope declare the same names.
+pub(crate) fn lower(
+    helpers: &Arc<Helpers>,
+    class_name: &Ident,
+    body: Vec<ClassMember>,
+) -> (Vec<ClassMember>, Props) {
+    let mut props = Props::default();
+    let mark = Mark::fresh(Mark::root());
+
+    let mut names = HashMap::new();
+    for member in &body {
+        let key = match *member {
+            ClassMember::PrivateProp(PrivateProp { ref key, .. })
+            | ClassMember::PrivateMethod(PrivateMethod { ref key, .. }) => key,
+            _ => continue,
+        };
+        if names.contains_key(&key.id.sym) {
+            continue;
+        }
+
+        // `_x = new WeakMap()`
+        let map = quote_ident!(key.span.apply_mark(mark), format!("_{}", key.id.sym));
+        props.vars.push(VarDeclarator {
+            span: DUMMY_SP,
+            name: Pat::Ident(map.clone()),
+            init: Some(box Expr::New(NewExpr {
+                span: DUMMY_SP,
+                callee: box Expr::Ident(quote_ident!("WeakMap")),
+                args: Some(vec![]),
+                type_args: None,
+            })),
+        });
+        names.insert(key.id.sym.clone(), map);
+    }
+
+    // Inner classes are already lowered, so every private name in `body`
+    // belongs to this class or to an outer one.
+    let body = if names.is_empty() {
+        body
+    } else {
+        body.fold_with(&mut PrivateFolder {
+            helpers,
+            names: &names,
+            vars: &mut props.vars,
+            mark,
+        })
+    };
+
+    let mut methods = vec![];
+    // Private methods are installed before fields are initialized.
+    let mut private_methods: Vec<PrivateMethodDesc> = vec![];
+    let (mut instance, mut statics) = (vec![], vec![]);
+
+    for member in body {
+        match member {
+            ClassMember::Method(..) => methods.push(member),
+
+            ClassMember::ClassProp(p) => {
+                let is_static = p.static_token.is_some();
+                let key = match p.key {
+                    // Computed keys are evaluated once, with the class.
+                    PropName::Computed(key) => {
+                        box Expr::Ident(declare(&mut props.vars, mark, "key", Some(key.expr)))
+                    }
+                    key => mk_key(key),
+                };
+                let value = init_value(helpers, class_name, is_static, p.value);
+
+                // `_defineProperty(this, "x", 1)`
+                helpers.define_property.store(true, Ordering::SeqCst);
+                let stmt = Expr::Call(CallExpr {
+                    span: p.span,
+                    callee: quote_ident!("_defineProperty").as_callee(),
+                    args: vec![
+                        receiver(class_name, is_static).as_arg(),
+                        ExprOrSpread {
+                            spread: None,
+                            expr: key,
+                        },
+                        ExprOrSpread {
+                            spread: None,
+                            expr: value,
+                        },
+                    ],
+                })
+                .into_stmt();
+                if is_static {
+                    statics.push(stmt)
+                } else {
+                    instance.push(stmt)
+                }
+            }
+
+            ClassMember::PrivateProp(p) => {
+                let is_static = p.static_token.is_some();
+                let map = names[&p.key.id.sym].clone();
+                let value = init_value(helpers, class_name, is_static, p.value);
+
+                // `_x.set(this, { writable: true, value: 1 })`
+                let desc = Expr::Object(ObjectLit {
+                    span: DUMMY_SP,
+                    props: vec![
+                        key_value(
+                            "writable",
+                            box Expr::Lit(Lit::Bool(Bool {
+                                span: DUMMY_SP,
+                                value: true,
+                            })),
+                        ),
+                        key_value("value", value),
+                    ],
+                });
+                let stmt = map_call(
+                    p.span,
+                    map,
+                    "set",
+                    vec![receiver(class_name, is_static).as_arg(), desc.as_arg()],
+                )
+                .into_stmt();
+                if is_static {
+                    statics.push(stmt)
+                } else {
+                    instance.push(stmt)
+                }
+            }
+
+            ClassMember::PrivateMethod(m) => {
+                let is_static = m.static_token.is_some();
+                let function = m.function.fold_with(&mut SuperCallFolder {
+                    class_name,
+                    is_static,
+                    helpers: helpers.clone(),
+                });
+                let name = match m.kind {
+                    ClassMethodKind::Constructor => unreachable!(),
+                    ClassMethodKind::Method => "value",
+                    ClassMethodKind::Getter => "get",
+                    ClassMethodKind::Setter => "set",
+                };
+                let prop = key_value(name, box Expr::Fn(FnExpr { ident: None, function }));
+
+                // `get #a() {}` and `set #a(v) {}` share a descriptor.
+                let map = names[&m.key.id.sym].clone();
+                let pos = private_methods.iter().position(|d| d.map.sym == map.sym);
+                match pos {
+                    Some(pos) => private_methods[pos].props.push(prop),
+                    None => private_methods.push(PrivateMethodDesc {
+                        map,
+                        is_static,
+                        props: vec![prop],
+                    }),
+                }
+            }
+
+            // `(function () {}).call(Foo)`
+            ClassMember::StaticBlock(StaticBlock { span, body }) => {
+                let function = Function {
+                    span,
+                    async_token: None,
+                    generator_token: None,
+                    params: vec![],
+                    body: Some(body),
+                    type_params: None,
+                    return_type: None,
+                }
+                .fold_with(&mut SuperCallFolder {
+                    class_name,
+                    is_static: true,
+                    helpers: helpers.clone(),
+                });
+
+                statics.push(
+                    Expr::Call(CallExpr {
+                        span,
+                        callee: MemberExpr {
+                            span,
+                            obj: ExprOrSuper::Expr(
+                                box Expr::Fn(FnExpr {
+                                    ident: None,
+                                    function,
+                                })
+                                .wrap_with_paren(),
+                            ),
+                            prop: box Expr::Ident(quote_ident!("call")),
+                            computed: false,
+                        }
+                        .as_callee(),
+                        args: vec![class_name.clone().as_arg()],
+                    })
+                    .into_stmt(),
+                );
+            }
+        }
+    }
+
+    let (mut instance_methods, mut static_methods) = (vec![], vec![]);
+    for desc in private_methods {
+        // Instances share the descriptor of a method.
+        let obj = box Expr::Object(ObjectLit {
+            span: DUMMY_SP,
+            props: desc.props,
+        });
+        let obj = declare(&mut props.vars, mark, "desc", Some(obj));
+
+        // `_a.set(this, _desc)`
+        let stmt = map_call(
+            DUMMY_SP,
+            desc.map,
+            "set",
+            vec![
+                receiver(class_name, desc.is_static).as_arg(),
+                obj.as_arg(),
+            ],
+        )
+        .into_stmt();
+        if desc.is_static {
+            static_methods.push(stmt)
+        } else {
+            instance_methods.push(stmt)
+        }
+    }
+
+    props.instance = instance_methods.into_iter().chain(instance).collect();
+    props.statics = static_methods.into_iter().chain(statics).collect();
+
+    (methods, props)
+}
+
+/// Merged descriptor of a private method or accessors.
+struct PrivateMethodDesc {
+    map: Ident,
+    is_static: bool,
+    props: Vec<PropOrSpread>,
+}
+
+/// `this`, or the class for static members.
+fn receiver(class_name: &Ident, is_static: bool) -> Expr {
+    if is_static {
+        Expr::Ident(class_name.clone())
+    } else {
+        Expr::This(ThisExpr { span: DUMMY_SP })
+    }
+}
+
+/// Lowers `super` in a field initializer. `this` in a static initializer is
+/// the class.
+fn init_value(
+    helpers: &Arc<Helpers>,
+    class_name: &Ident,
+    is_static: bool,
+    value: Option<Box<Expr>>,
+) -> Box<Expr> {
+    let value = match value {
+        Some(value) => value.fold_with(&mut SuperCallFolder {
+            class_name,
+            is_static,
+            helpers: helpers.clone(),
+        }),
+        // `void 0`
+        None => {
+            return box Expr::Unary(UnaryExpr {
+                span: DUMMY_SP,
+                op: op!("void"),
+                arg: box Expr::Lit(Lit::Num(Number {
+                    span: DUMMY_SP,
+                    value: 0.0,
+                    raw: None,
+                })),
+            });
+        }
+    };
+
+    if is_static {
+        value.fold_with(&mut ThisToClass { class_name })
+    } else {
+        value
+    }
+}
+
+fn key_value(key: &str, value: Box<Expr>) -> PropOrSpread {
+    PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+        key: PropName::Ident(quote_ident!(key)),
+        value,
+    }))
+}
+
+/// `_x.set(args)`
+fn map_call(span: Span, map: Ident, method: &str, args: Vec<ExprOrSpread>) -> Expr {
+    Expr::Call(CallExpr {
+        span,
+        callee: MemberExpr {
+            span,
+            obj: ExprOrSuper::Expr(box Expr::Ident(map)),
+            prop: box Expr::Ident(quote_ident!(method)),
+            computed: false,
+        }
+        .as_callee(),
+        args,
+    })
+}
+
+/// Declares `_{name}{n}`, which is unique in the class.
+fn declare(
+    vars: &mut Vec<VarDeclarator>,
+    mark: Mark,
+    name: &str,
+    init: Option<Box<Expr>>,
+) -> Ident {
+    let ident = quote_ident!(DUMMY_SP.apply_mark(mark), format!("_{}{}", name, vars.len()));
+    vars.push(VarDeclarator {
+        span: DUMMY_SP,
+        name: Pat::Ident(ident.clone()),
+        init,
+    });
+    ident
+}
+
+/// Replaces usages of private names declared by a class.
+///
+/// # In
+///
+/// ```js
+/// this.#a += 1;
+/// ```
+///
+/// # Out
+///
+/// ```js
+/// _classPrivateFieldSet(this, _a, _classPrivateFieldGet(this, _a) + 1);
+/// ```
+struct PrivateFolder<'a> {
+    helpers: &'a Arc<Helpers>,
+    names: &'a HashMap<JsWord, Ident>,
+    /// Temporaries are declared here.
+    vars: &'a mut Vec<VarDeclarator>,
+    mark: Mark,
+}
+
+impl<'a> PrivateFolder<'a> {
+    /// `_x` for `obj.#x`, if `#x` is declared by the class.
+    fn map_of(&self, e: &Expr) -> Option<Ident> {
+        match *e {
+            Expr::Member(MemberExpr {
+                obj: ExprOrSuper::Expr(..),
+                prop: box Expr::PrivateName(ref name),
+                computed: false,
+                ..
+            }) => self.names.get(&name.id.sym).cloned(),
+            _ => None,
+        }
+    }
+
+    /// Returns `obj`, and an expression which evaluates to the same value
+    /// once `obj` is evaluated.
+    fn alias(&mut self, obj: Box<Expr>) -> (Box<Expr>, Box<Expr>) {
+        if let Expr::This(..) = *obj {
+            return (obj.clone(), obj);
+        }
+
+        let tmp = declare(self.vars, self.mark, "ref", None);
+        let assign = box Expr::Assign(AssignExpr {
+            span: obj.span(),
+            left: PatOrExpr::Pat(box Pat::Ident(tmp.clone())),
+            op: op!("="),
+            right: obj,
+        });
+        (assign, box Expr::Ident(tmp))
+    }
+
+    /// `_classPrivateFieldGet(obj, _x)`
+    fn get(&self, span: Span, obj: Box<Expr>, map: Ident) -> Box<Expr> {
+        self.helpers
+            .class_private_field_get
+            .store(true, Ordering::SeqCst);
+
+        box Expr::Call(CallExpr {
+            span,
+            callee: quote_ident!("_classPrivateFieldGet").as_callee(),
+            args: vec![
+                ExprOrSpread {
+                    spread: None,
+                    expr: obj,
+                },
+                map.as_arg(),
+            ],
+        })
+    }
+
+    /// `_classPrivateFieldSet(obj, _x, value)`
+    fn set(&self, span: Span, obj: Box<Expr>, map: Ident, value: Box<Expr>) -> Expr {
+        self.helpers
+            .class_private_field_set
+            .store(true, Ordering::SeqCst);
+
+        Expr::Call(CallExpr {
+            span,
+            callee: quote_ident!("_classPrivateFieldSet").as_callee(),
+            args: vec![
+                ExprOrSpread {
+                    spread: None,
+                    expr: obj,
+                },
+                map.as_arg(),
+                ExprOrSpread {
+                    spread: None,
+                    expr: value,
+                },
+            ],
+        })
+    }
+}
+
+/// `obj` of `obj.#x`.
+fn member_obj(e: Expr) -> Box<Expr> {
+    match e {
+        Expr::Member(MemberExpr {
+            obj: ExprOrSuper::Expr(obj),
+            ..
+        }) => obj,
+        _ => unreachable!("{:?} is not a private member", e),
+    }
+}
+
+impl<'a> Fold<Expr> for PrivateFolder<'a> {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let span = e.span();
+
+        match e {
+            // `#x in obj`
+            Expr::Bin(BinExpr {
+                left: box Expr::PrivateName(name),
+                op: op!("in"),
+                right,
+                ..
+            }) => {
+                let right = right.fold_with(self);
+                match self.names.get(&name.id.sym) {
+                    Some(map) => map_call(
+                        span,
+                        map.clone(),
+                        "has",
+                        vec![ExprOrSpread {
+                            spread: None,
+                            expr: right,
+                        }],
+                    ),
+                    None => Expr::Bin(BinExpr {
+                        span,
+                        left: box Expr::PrivateName(name),
+                        op: op!("in"),
+                        right,
+                    }),
+                }
+            }
+
+            // `obj.#x = 1`, `obj.#x += 1`
+            Expr::Assign(AssignExpr { left, op, right, .. }) => {
+                let map = match left {
+                    PatOrExpr::Pat(box Pat::Expr(ref e)) | PatOrExpr::Expr(ref e) => self.map_of(e),
+                    _ => None,
+                };
+                let map = match map {
+                    Some(map) => map,
+                    None => {
+                        return Expr::Assign(AssignExpr {
+                            span,
+                            left,
+                            op,
+                            right,
+                        })
+                        .fold_children(self);
+                    }
+                };
+                let obj = match left {
+                    PatOrExpr::Pat(box Pat::Expr(e)) | PatOrExpr::Expr(e) => member_obj(*e),
+                    _ => unreachable!(),
+                };
+                let obj = obj.fold_with(self);
+                let right = right.fold_with(self);
+
+                if op == op!("=") {
+                    return self.set(span, obj, map, right);
+                }
+
+                let (obj, obj_ref) = self.alias(obj);
+                let value = box Expr::Bin(BinExpr {
+                    span,
+                    left: self.get(span, obj_ref, map.clone()),
+                    op: to_bin_op(op),
+                    right,
+                });
+                self.set(span, obj, map, value)
+            }
+
+            // `obj.#x++`
+            Expr::Update(UpdateExpr {
+                op, prefix, arg, ..
+            }) => {
+                let map = match self.map_of(&arg) {
+                    Some(map) => map,
+                    None => {
+                        return Expr::Update(UpdateExpr {
+                            span,
+                            op,
+                            prefix,
+                            arg,
+                        })
+                        .fold_children(self);
+                    }
+                };
+                let (obj, obj_ref) = self.alias(member_obj(*arg).fold_with(self));
+
+                // `+_classPrivateFieldGet(obj, _x)`
+                let old = box Expr::Unary(UnaryExpr {
+                    span,
+                    op: op!(unary, "+"),
+                    arg: self.get(span, obj_ref, map.clone()),
+                });
+                // The old value is the result of postfix operators.
+                let (old, tmp) = if prefix {
+                    (old, None)
+                } else {
+                    let tmp = declare(self.vars, self.mark, "old", None);
+                    let old = box Expr::Assign(AssignExpr {
+                        span,
+                        left: PatOrExpr::Pat(box Pat::Ident(tmp.clone())),
+                        op: op!("="),
+                        right: old,
+                    });
+                    (old, Some(tmp))
+                };
+
+                let value = box Expr::Bin(BinExpr {
+                    span,
+                    left: old,
+                    op: if op == op!("++") {
+                        op!(bin, "+")
+                    } else {
+                        op!(bin, "-")
+                    },
+                    right: box Expr::Lit(Lit::Num(Number {
+                        span: DUMMY_SP,
+                        value: 1.0,
+                        raw: None,
+                    })),
+                });
+                let set = self.set(span, obj, map, value);
+
+                match tmp {
+                    Some(tmp) => Expr::Seq(SeqExpr {
+                        span,
+                        exprs: vec![box set, box Expr::Ident(tmp)],
+                    }),
+                    None => set,
+                }
+            }
+
+            // `obj.#m(a)`
+            Expr::Call(CallExpr {
+                callee: ExprOrSuper::Expr(callee),
+                args,
+                ..
+            }) => {
+                let map = match self.map_of(&callee) {
+                    Some(map) => map,
+                    None => {
+                        return Expr::Call(CallExpr {
+                            span,
+                            callee: ExprOrSuper::Expr(callee),
+                            args,
+                        })
+                        .fold_children(self);
+                    }
+                };
+                let (obj, obj_ref) = self.alias(member_obj(*callee).fold_with(self));
+                let args = args.fold_with(self);
+
+                // `_classPrivateFieldGet(obj, _m).call(obj, a)`
+                Expr::Call(CallExpr {
+                    span,
+                    callee: MemberExpr {
+                        span,
+                        obj: ExprOrSuper::Expr(self.get(span, obj, map)),
+                        prop: box Expr::Ident(quote_ident!("call")),
+                        computed: false,
+                    }
+                    .as_callee(),
+                    args: iter::once(ExprOrSpread {
+                        spread: None,
+                        expr: obj_ref,
+                    })
+                    .chain(args)
+                    .collect(),
+                })
+            }
+
+            // `obj.#x`
+            Expr::Member(..) => match self.map_of(&e) {
+                Some(map) => {
+                    let obj = member_obj(e).fold_with(self);
+                    *self.get(span, obj, map)
+                }
+                None => e.fold_children(self),
+            },
+
+            _ => e.fold_children(self),
+        }
+    }
+}
+
+/// Replaces `this` with the class.
+struct ThisToClass<'a> {
+    class_name: &'a Ident,
+}
+
+impl<'a> Fold<Expr> for ThisToClass<'a> {
+    fn fold(&mut self, e: Expr) -> Expr {
+        match e {
+            Expr::This(..) => Expr::Ident(self.class_name.clone()),
+            _ => e.fold_children(self),
+        }
+    }
+}
+
+/// `this` of functions is not the class.
+impl<'a> Fold<Function> for ThisToClass<'a> {
+    fn fold(&mut self, f: Function) -> Function {
+        f
+    }
+}
@@ -1,4 +1,20 @@
 use super::*;
+use crate::compat::helpers::InjectHelpers;
+use swc_common::{sync::Lrc, FilePathMapping, SourceMap};
+
+/// `test_exec!` doesn't inject helpers.
+fn tr() -> impl Fold<Module> {
+    let helpers = Arc::new(Helpers::default());
+
+    Classes {
+        helpers: helpers.clone(),
+    }
+    .then(InjectHelpers {
+        cm: Lrc::new(SourceMap::new(FilePathMapping::empty())),
+        helpers,
+        external: false,
+    })
+}
 
 test!(
     Classes::default(),
@@ -318,3 +334,219 @@ var Child = function (_Parent) {
   return Child;
 }(Parent);"#
 );
+
+test!(
+    Classes::default(),
+    accessors,
+    r#"class Foo {
+  get a() { return 1; }
+  set a(v) {}
+  static get b() {}
+  ['c' + d]() {}
+}"#,
+    r#"var Foo = function () {
+  function Foo() {
+    _classCallCheck(this, Foo);
+  }
+
+  _createClass(Foo, [{
+    key: "a",
+    get: function get() {
+      return 1;
+    },
+    set: function set(v) {}
+  }, {
+    key: 'c' + d,
+    value: function () {}
+  }], [{
+    key: "b",
+    get: function get() {}
+  }]);
+
+  return Foo;
+}();"#
+);
+
+test!(
+    Classes::default(),
+    static_super,
+    r#"class Child extends Parent {
+  static foo() {
+    super.foo(super[bar]);
+  }
+}"#,
+    r#"var Child = function (_Parent) {
+  _inherits(Child, _Parent);
+
+  function Child() {
+    _classCallCheck(this, Child);
+
+    return _possibleConstructorReturn(this, (Child.__proto__ || Object.getPrototypeOf(Child)).apply(this, arguments));
+  }
+
+  _createClass(Child, null, [{
+    key: "foo",
+    value: function foo() {
+      _get(Child.__proto__ || Object.getPrototypeOf(Child), "foo", this).call(this, _get(Child.__proto__ || Object.getPrototypeOf(Child), bar, this));
+    }
+  }]);
+
+  return Child;
+}(Parent);"#
+);
+
+test!(
+    Classes::default(),
+    class_props,
+    r#"class Foo {
+  a = 1;
+  static b = this;
+  ['c' + 1] = 2;
+  d;
+  static {
+    this.e = 3;
+  }
+}"#,
+    r#"var Foo = function () {
+  var _key0 = 'c' + 1;
+
+  function Foo() {
+    _classCallCheck(this, Foo);
+
+    _defineProperty(this, "a", 1);
+    _defineProperty(this, _key0, 2);
+    _defineProperty(this, "d", void 0);
+  }
+
+  _defineProperty(Foo, "b", Foo);
+  (function () {
+    this.e = 3;
+  }).call(Foo);
+
+  return Foo;
+}();"#
+);
+
+test!(
+    Classes::default(),
+    derived_class_props,
+    r#"class Child extends Parent {
+  a = 1;
+  constructor() {
+    super();
+    foo();
+  }
+}"#,
+    r#"var Child = function (_Parent) {
+  _inherits(Child, _Parent);
+
+  function Child() {
+    _classCallCheck(this, Child);
+
+    var _this = _possibleConstructorReturn(this, (Child.__proto__ || Object.getPrototypeOf(Child)).call(this));
+    _defineProperty(this, "a", 1);
+    foo();
+    return _this;
+  }
+
+  return Child;
+}(Parent);"#
+);
+
+test!(
+    Classes::default(),
+    private_names,
+    r#"class Foo {
+  #a = 1;
+  #b() {
+    return this.#a;
+  }
+  inc(o) {
+    o.#a += 1;
+    return #a in o;
+  }
+}"#,
+    r#"var Foo = function () {
+  var _a = new WeakMap(), _b = new WeakMap(), _ref2, _desc3 = {
+    value: function () {
+      return _classPrivateFieldGet(this, _a);
+    }
+  };
+
+  function Foo() {
+    _classCallCheck(this, Foo);
+
+    _b.set(this, _desc3);
+    _a.set(this, { writable: true, value: 1 });
+  }
+
+  _createClass(Foo, [{
+    key: "inc",
+    value: function inc(o) {
+      _classPrivateFieldSet(_ref2 = o, _a, _classPrivateFieldGet(_ref2, _a) + 1);
+      return _a.has(o);
+    }
+  }]);
+
+  return Foo;
+}();"#
+);
+
+test_exec!(
+    tr(),
+    exec_private_names,
+    r#"class Counter {
+  #count = 0;
+  static #instances = 0;
+  static created = [];
+  static {
+    this.created.push('block');
+  }
+
+  constructor() {
+    Counter.#instances++;
+  }
+
+  get #double() {
+    return this.#count * 2;
+  }
+
+  static #secret() {
+    return 42;
+  }
+
+  inc() {
+    return ++this.#count;
+  }
+
+  post() {
+    return this.#count++;
+  }
+
+  double() {
+    return this.#double;
+  }
+
+  static instances() {
+    return Counter.#instances;
+  }
+
+  static secret() {
+    return Counter.#secret();
+  }
+
+  static isCounter(o) {
+    return #count in o;
+  }
+}
+
+const c = new Counter();
+assert.equal(c.inc(), 1);
+assert.equal(c.post(), 1);
+assert.equal(c.double(), 4);
+assert.equal(Counter.instances(), 1);
+assert.equal(Counter.secret(), 42);
+assert.ok(Counter.isCounter(c));
+assert.ok(!Counter.isCounter({}));
+assert.deepEqual(Counter.created, ['block']);"#
+);
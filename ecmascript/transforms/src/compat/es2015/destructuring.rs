@@ -0,0 +1,789 @@
+use crate::{
+    compat::helpers::Helpers,
+    util::{pat_ids, prepend, ExprFactory, StmtLike},
+};
+use ast::*;
+use std::{
+    collections::HashSet,
+    sync::{atomic::Ordering, Arc},
+};
+use swc_atoms::JsWord;
+use swc_common::{Fold, FoldWith, Visit, VisitWith, DUMMY_SP};
+
+/// Compile ES2015 destructuring to ES5
+///
+/// Array patterns are compiled to index accesses, so values should be arrays
+/// or array-like objects.
+///
+///# Example
+///
+///## In
+///
+/// ```js
+/// var { a, b: [c, d = 1], ...e } = obj;
+/// [a, b] = [b, a];
+/// ```
+///
+///## Out
+///
+/// ```js
+/// var a = obj.a,
+///     _ref = obj.b,
+///     c = _ref[0],
+///     _ref2 = _ref[1],
+///     d = _ref2 === void 0 ? 1 : _ref2,
+///     e = _objectWithoutProperties(obj, ["a", "b"]);
+/// var _ref3;
+/// _ref3 = [b, a], a = _ref3[0], b = _ref3[1];
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Destructuring {
+    pub helpers: Arc<Helpers>,
+}
+
+impl Fold<Module> for Destructuring {
+    fn fold(&mut self, module: Module) -> Module {
+        let mut used = UsedNames::default();
+        module.visit_with(&mut used);
+
+        let mut folder = Folder {
+            helpers: self.helpers.clone(),
+            used: used.0,
+            vars: vec![],
+        };
+        module.fold_children(&mut folder)
+    }
+}
+
+struct Folder {
+    helpers: Arc<Helpers>,
+    /// Names in the module, which are not used for temporary variables.
+    used: HashSet<JsWord>,
+    /// Temporary variables which should be declared before the statement
+    /// being folded.
+    vars: Vec<Ident>,
+}
+
+/// A binding or an assignment target, and its value.
+type Assignment = (Pat, Box<Expr>);
+
+impl Folder {
+    /// `_ref`, `_ref2`, `_ref3`, ...
+    fn tmp(&mut self) -> Ident {
+        let mut i = 1;
+        loop {
+            let name: JsWord = if i == 1 {
+                "_ref".into()
+            } else {
+                format!("_ref{}", i).into()
+            };
+            i += 1;
+
+            if self.used.insert(name.clone()) {
+                let tmp = Ident::new(name, DUMMY_SP);
+                self.vars.push(tmp.clone());
+                return tmp;
+            }
+        }
+    }
+
+    /// Returns an expression which can be evaluated more than once without
+    /// side effects.
+    ///
+    /// `bound` are names assigned by the pattern, which may change while the
+    /// pattern is being destructured.
+    fn to_ref(
+        &mut self,
+        bound: &[JsWord],
+        value: Box<Expr>,
+        out: &mut Vec<Assignment>,
+    ) -> Box<Expr> {
+        match *value {
+            Expr::Ident(ref i) if !bound.contains(&i.sym) => return value,
+            _ => {}
+        }
+
+        let tmp = self.tmp();
+        out.push((Pat::Ident(tmp.clone()), value));
+        box Expr::Ident(tmp)
+    }
+
+    /// Converts `pat = value` into assignments of identifiers and
+    /// expressions.
+    fn destructure(
+        &mut self,
+        bound: &[JsWord],
+        pat: Pat,
+        value: Box<Expr>,
+        out: &mut Vec<Assignment>,
+    ) {
+        match pat {
+            Pat::Ident(..) | Pat::Expr(..) => out.push((pat, value)),
+            Pat::TsTyped(TsTypedPat { pat, .. }) => self.destructure(bound, *pat, value, out),
+
+            // `a === void 0 ? b : a`
+            Pat::Assign(AssignPat { left, right, .. }) => {
+                let value = self.to_ref(bound, value, out);
+                let value = box Expr::Cond(CondExpr {
+                    span: DUMMY_SP,
+                    test: box Expr::Bin(BinExpr {
+                        span: DUMMY_SP,
+                        op: op!("==="),
+                        left: value.clone(),
+                        right: void_0(),
+                    }),
+                    cons: right,
+                    alt: value,
+                });
+                self.destructure(bound, *left, value, out)
+            }
+
+            Pat::Array(ArrayPat { elems, .. }) => {
+                let obj = self.to_ref(bound, value, out);
+
+                for (i, elem) in elems.into_iter().enumerate() {
+                    let index = box Expr::Lit(Lit::Num(Number {
+                        span: DUMMY_SP,
+                        value: i as f64,
+                        raw: None,
+                    }));
+
+                    match elem {
+                        // A hole.
+                        None => {}
+                        // `_ref.slice(1)`
+                        Some(Pat::Rest(RestPat { arg, .. })) => {
+                            let value = box Expr::Call(CallExpr {
+                                span: DUMMY_SP,
+                                callee: member(obj.clone(), quote_ident!("slice")).as_callee(),
+                                args: vec![index.as_arg()],
+                            });
+                            self.destructure(bound, *arg, value, out)
+                        }
+                        // `_ref[0]`
+                        Some(elem) => {
+                            let value = box Expr::Member(MemberExpr {
+                                span: DUMMY_SP,
+                                obj: ExprOrSuper::Expr(obj.clone()),
+                                prop: index,
+                                computed: true,
+                            });
+                            self.destructure(bound, elem, value, out)
+                        }
+                    }
+                }
+            }
+
+            Pat::Object(ObjectPat { props, .. }) => {
+                let obj = self.to_ref(bound, value, out);
+                let has_rest = props.iter().any(|prop| match *prop {
+                    ObjectPatProp::Rest(..) => true,
+                    _ => false,
+                });
+                // Keys which are excluded from the rest element.
+                let mut keys = vec![];
+
+                for prop in props {
+                    match prop {
+                        ObjectPatProp::KeyValue(KeyValuePatProp { key, value: pat }) => {
+                            let (value, key) = self.prop(obj.clone(), key, has_rest, out);
+                            keys.push(key);
+                            self.destructure(bound, *pat, value, out)
+                        }
+                        ObjectPatProp::Assign(AssignPatProp { span, key, value }) => {
+                            keys.push(box Expr::Lit(Lit::Str(quote_str!(key.sym.clone()))));
+
+                            let member = box member(obj.clone(), key.clone());
+                            let pat = match value {
+                                Some(right) => Pat::Assign(AssignPat {
+                                    span,
+                                    left: box Pat::Ident(key),
+                                    right,
+                                }),
+                                None => Pat::Ident(key),
+                            };
+                            self.destructure(bound, pat, member, out)
+                        }
+                        // `_objectWithoutProperties(_ref, ["a", "b"])`
+                        ObjectPatProp::Rest(RestPat { arg, .. }) => {
+                            self.helpers
+                                .object_without_properties
+                                .store(true, Ordering::SeqCst);
+
+                            let keys = Expr::Array(ArrayLit {
+                                span: DUMMY_SP,
+                                elems: keys
+                                    .drain(..)
+                                    .map(|key| Some(key.as_arg()))
+                                    .collect(),
+                            });
+                            let value = box Expr::Call(CallExpr {
+                                span: DUMMY_SP,
+                                callee: quote_ident!("_objectWithoutProperties").as_callee(),
+                                args: vec![obj.clone().as_arg(), keys.as_arg()],
+                            });
+                            self.destructure(bound, *arg, value, out)
+                        }
+                    }
+                }
+            }
+
+            Pat::Rest(..) => unreachable!("rest pattern outside of array patterns and parameters"),
+        }
+    }
+
+    /// Returns `obj[key]` and the key as a string.
+    ///
+    /// Computed keys are stored in a temporary variable if `has_rest` is
+    /// true, because they are used twice.
+    fn prop(
+        &mut self,
+        obj: Box<Expr>,
+        key: PropName,
+        has_rest: bool,
+        out: &mut Vec<Assignment>,
+    ) -> (Box<Expr>, Box<Expr>) {
+        let (prop, computed, key) = match key {
+            PropName::Ident(i) => {
+                let key = box Expr::Lit(Lit::Str(quote_str!(i.sym.clone())));
+                (box Expr::Ident(i), false, key)
+            }
+            PropName::Str(s) => {
+                let key = box Expr::Lit(Lit::Str(s));
+                (key.clone(), true, key)
+            }
+            PropName::Num(n) => {
+                let key = box Expr::Lit(Lit::Str(quote_str!(format!("{}", n.value))));
+                (box Expr::Lit(Lit::Num(n)), true, key)
+            }
+            PropName::Computed(ComputedPropName { expr, .. }) => {
+                let expr = if has_rest {
+                    self.to_ref(&[], expr, out)
+                } else {
+                    expr
+                };
+                // `_ref2 + ""`
+                let key = box Expr::Bin(BinExpr {
+                    span: DUMMY_SP,
+                    op: op!(bin, "+"),
+                    left: expr.clone(),
+                    right: box Expr::Lit(Lit::Str(quote_str!(""))),
+                });
+                (expr, true, key)
+            }
+        };
+
+        let value = box Expr::Member(MemberExpr {
+            span: DUMMY_SP,
+            obj: ExprOrSuper::Expr(obj),
+            prop,
+            computed,
+        });
+        (value, key)
+    }
+
+    /// Converts declarators with patterns.
+    fn flatten_var_decl(&mut self, decl: VarDecl) -> VarDecl {
+        if !decl.decls.iter().any(|d| is_pattern(&d.name)) {
+            return decl;
+        }
+
+        // Temporary variables are declared by the declaration itself.
+        let vars = self.vars.len();
+        let mut decls = vec![];
+        for d in decl.decls {
+            match d.init {
+                Some(init) if is_pattern(&d.name) => {
+                    let bound = bound_names(&d.name);
+                    let mut out = vec![];
+                    self.destructure(&bound, d.name, init, &mut out);
+                    decls.extend(out.into_iter().map(|(name, init)| VarDeclarator {
+                        span: d.span,
+                        name,
+                        init: Some(init),
+                    }));
+                }
+                _ => decls.push(d),
+            }
+        }
+        self.vars.truncate(vars);
+
+        VarDecl { decls, ..decl }
+    }
+
+    /// Converts `pat = right` into a sequence of assignments.
+    ///
+    /// The result is the value of `right` only if `keep_value` is true.
+    fn assign(&mut self, pat: Pat, right: Box<Expr>, keep_value: bool) -> Expr {
+        let bound = bound_names(&pat);
+        let mut out = vec![];
+
+        let value = if keep_value {
+            let tmp = self.tmp();
+            out.push((Pat::Ident(tmp.clone()), right));
+            self.destructure(&bound, pat, box Expr::Ident(tmp.clone()), &mut out);
+            Some(box Expr::Ident(tmp))
+        } else {
+            self.destructure(&bound, pat, right, &mut out);
+            None
+        };
+
+        let mut exprs: Vec<_> = out
+            .into_iter()
+            .map(|(target, value)| {
+                box Expr::Assign(AssignExpr {
+                    span: DUMMY_SP,
+                    op: op!("="),
+                    left: match target {
+                        Pat::Expr(expr) => PatOrExpr::Expr(expr),
+                        target => PatOrExpr::Pat(box target),
+                    },
+                    right: value,
+                })
+            })
+            .chain(value)
+            .collect();
+
+        if exprs.len() == 1 {
+            return *exprs.pop().unwrap();
+        }
+        Expr::Seq(SeqExpr {
+            span: DUMMY_SP,
+            exprs,
+        })
+    }
+
+    /// Replaces patterns in parameters with temporary variables, and returns
+    /// `var` declarators which destructure them.
+    fn flatten_params(&mut self, params: Vec<Pat>) -> (Vec<Pat>, Vec<VarDeclarator>) {
+        let vars = self.vars.len();
+        let mut decls = vec![];
+
+        let params = params
+            .into_iter()
+            .map(|pat| match pat {
+                Pat::Ident(..)
+                | Pat::Rest(RestPat {
+                    arg: box Pat::Ident(..),
+                    ..
+                }) => pat,
+                Pat::Rest(RestPat { dot3_token, arg }) => {
+                    let tmp = self.tmp();
+                    decls.push(VarDeclarator {
+                        span: DUMMY_SP,
+                        name: *arg,
+                        init: Some(box Expr::Ident(tmp.clone())),
+                    });
+                    Pat::Rest(RestPat {
+                        dot3_token,
+                        arg: box Pat::Ident(tmp),
+                    })
+                }
+                pat => {
+                    let tmp = self.tmp();
+                    decls.push(VarDeclarator {
+                        span: DUMMY_SP,
+                        name: pat,
+                        init: Some(box Expr::Ident(tmp.clone())),
+                    });
+                    Pat::Ident(tmp)
+                }
+            })
+            .collect();
+        self.vars.truncate(vars);
+
+        (params, decls)
+    }
+
+    /// `kind pat = tmp;` or `pat = tmp;` at the start of `body`.
+    fn prepend_destructuring(
+        &mut self,
+        kind: Option<VarDeclKind>,
+        pat: Pat,
+        tmp: Ident,
+        body: &mut Vec<Stmt>,
+    ) {
+        let stmt = match kind {
+            Some(kind) => Stmt::Decl(Decl::Var(self.flatten_var_decl(VarDecl {
+                span: DUMMY_SP,
+                kind,
+                declare: false,
+                decls: vec![VarDeclarator {
+                    span: DUMMY_SP,
+                    name: pat,
+                    init: Some(box Expr::Ident(tmp)),
+                }],
+            }))),
+            None => self.assign(pat, box Expr::Ident(tmp), false).into_stmt(),
+        };
+        body.insert(0, stmt);
+    }
+
+    fn fold_stmts<T>(&mut self, stmts: Vec<T>) -> Vec<T>
+    where
+        T: StmtLike + FoldWith<Self>,
+    {
+        let mut buf = Vec::with_capacity(stmts.len());
+        for stmt in stmts {
+            let vars = self.vars.len();
+            let stmt = stmt.fold_with(self);
+            if self.vars.len() > vars {
+                buf.push(T::from_stmt(var_decl(self.vars.drain(vars..))));
+            }
+            buf.push(stmt);
+        }
+        buf
+    }
+
+    /// Replaces a pattern in the head of `for in` and `for of` with a
+    /// temporary variable, which is destructured in `body`.
+    fn fold_loop_head(&mut self, left: VarDeclOrPat, body: Box<Stmt>) -> (VarDeclOrPat, Box<Stmt>) {
+        let (kind, pat) = match left {
+            VarDeclOrPat::VarDecl(mut decl) => {
+                if decl.decls.len() != 1 || !is_pattern(&decl.decls[0].name) {
+                    return (VarDeclOrPat::VarDecl(decl), body);
+                }
+                (Some(decl.kind), decl.decls.remove(0).name)
+            }
+            VarDeclOrPat::Pat(pat) => {
+                if !is_pattern(&pat) {
+                    return (VarDeclOrPat::Pat(pat), body);
+                }
+                (None, pat)
+            }
+        };
+
+        let tmp = self.tmp();
+        self.vars.pop();
+
+        let mut stmts = match *body {
+            Stmt::Block(block) => block.stmts,
+            body => vec![body],
+        };
+        self.prepend_destructuring(kind, pat, tmp.clone(), &mut stmts);
+
+        let left = VarDecl {
+            span: DUMMY_SP,
+            kind: kind.unwrap_or(VarDeclKind::Var),
+            declare: false,
+            decls: vec![VarDeclarator {
+                span: DUMMY_SP,
+                name: Pat::Ident(tmp),
+                init: None,
+            }],
+        };
+        let body = box Stmt::Block(BlockStmt {
+            span: DUMMY_SP,
+            stmts,
+        });
+        (VarDeclOrPat::VarDecl(left), body)
+    }
+}
+
+impl Fold<Vec<Stmt>> for Folder {
+    fn fold(&mut self, stmts: Vec<Stmt>) -> Vec<Stmt> {
+        self.fold_stmts(stmts)
+    }
+}
+
+impl Fold<Vec<ModuleItem>> for Folder {
+    fn fold(&mut self, items: Vec<ModuleItem>) -> Vec<ModuleItem> {
+        self.fold_stmts(items)
+    }
+}
+
+impl Fold<VarDecl> for Folder {
+    fn fold(&mut self, decl: VarDecl) -> VarDecl {
+        let decl = decl.fold_children(self);
+        self.flatten_var_decl(decl)
+    }
+}
+
+impl Fold<Expr> for Folder {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children(self);
+
+        match e {
+            Expr::Assign(AssignExpr {
+                op: op!("="),
+                left: PatOrExpr::Pat(box ref pat),
+                ..
+            }) if is_pattern(pat) => {}
+            _ => return e,
+        }
+
+        match e {
+            Expr::Assign(AssignExpr {
+                left: PatOrExpr::Pat(box pat),
+                right,
+                ..
+            }) => self.assign(pat, right, true).wrap_with_paren(),
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// The value of `[a, b] = c;` is not used.
+impl Fold<ExprStmt> for Folder {
+    fn fold(&mut self, s: ExprStmt) -> ExprStmt {
+        match *s.expr {
+            Expr::Assign(AssignExpr {
+                op: op!("="),
+                left: PatOrExpr::Pat(box ref pat),
+                ..
+            }) if is_pattern(pat) => {}
+            _ => return s.fold_children(self),
+        }
+
+        let expr = match *s.expr {
+            Expr::Assign(e) => match e.fold_children(self) {
+                AssignExpr {
+                    left: PatOrExpr::Pat(box pat),
+                    right,
+                    ..
+                } => self.assign(pat, right, false),
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+
+        ExprStmt {
+            expr: box expr,
+            ..s
+        }
+    }
+}
+
+impl Fold<Function> for Folder {
+    fn fold(&mut self, f: Function) -> Function {
+        let f = f.fold_children(self);
+        if !f.params.iter().any(|p| is_param_pattern(&p.pat)) {
+            return f;
+        }
+
+        let (spans, pats): (Vec<_>, Vec<_>) = f
+            .params
+            .into_iter()
+            .map(|p| ((p.span, p.decorators), p.pat))
+            .unzip();
+        let (pats, decls) = self.flatten_params(pats);
+        let params = spans
+            .into_iter()
+            .zip(pats)
+            .map(|((span, decorators), pat)| Param {
+                span,
+                decorators,
+                pat,
+            })
+            .collect();
+
+        let body = f.body.map(|mut body| {
+            let decl = self.flatten_var_decl(VarDecl {
+                span: DUMMY_SP,
+                kind: VarDeclKind::Var,
+                declare: false,
+                decls,
+            });
+            prepend(&mut body.stmts, Stmt::Decl(Decl::Var(decl)));
+            body
+        });
+
+        Function { params, body, ..f }
+    }
+}
+
+impl Fold<ArrowExpr> for Folder {
+    fn fold(&mut self, f: ArrowExpr) -> ArrowExpr {
+        let f = f.fold_children(self);
+        if !f.params.iter().any(is_param_pattern) {
+            return f;
+        }
+
+        let (params, decls) = self.flatten_params(f.params);
+        let decl = Stmt::Decl(Decl::Var(self.flatten_var_decl(VarDecl {
+            span: DUMMY_SP,
+            kind: VarDeclKind::Var,
+            declare: false,
+            decls,
+        })));
+        let body = match f.body {
+            BlockStmtOrExpr::BlockStmt(mut body) => {
+                prepend(&mut body.stmts, decl);
+                body
+            }
+            BlockStmtOrExpr::Expr(expr) => BlockStmt {
+                span: DUMMY_SP,
+                stmts: vec![
+                    decl,
+                    Stmt::Return(ReturnStmt {
+                        span: DUMMY_SP,
+                        arg: Some(expr),
+                    }),
+                ],
+            },
+        };
+
+        ArrowExpr {
+            params,
+            body: BlockStmtOrExpr::BlockStmt(body),
+            ..f
+        }
+    }
+}
+
+impl Fold<CatchClause> for Folder {
+    fn fold(&mut self, c: CatchClause) -> CatchClause {
+        let mut c = c.fold_children(self);
+        match c.param {
+            Some(ref pat) if is_pattern(pat) => {}
+            _ => return c,
+        }
+
+        let tmp = self.tmp();
+        self.vars.pop();
+        let pat = c.param.take().unwrap();
+        self.prepend_destructuring(Some(VarDeclKind::Let), pat, tmp.clone(), &mut c.body.stmts);
+
+        CatchClause {
+            param: Some(Pat::Ident(tmp)),
+            ..c
+        }
+    }
+}
+
+impl Fold<ForInStmt> for Folder {
+    fn fold(&mut self, s: ForInStmt) -> ForInStmt {
+        let s = s.fold_children(self);
+        let (left, body) = self.fold_loop_head(s.left, s.body);
+
+        ForInStmt { left, body, ..s }
+    }
+}
+
+impl Fold<ForOfStmt> for Folder {
+    fn fold(&mut self, s: ForOfStmt) -> ForOfStmt {
+        let s = s.fold_children(self);
+        let (left, body) = self.fold_loop_head(s.left, s.body);
+
+        ForOfStmt { left, body, ..s }
+    }
+}
+
+/// Returns true if `pat` is not a simple identifier.
+fn is_pattern(pat: &Pat) -> bool {
+    match *pat {
+        Pat::Ident(..) | Pat::Expr(..) => false,
+        Pat::TsTyped(TsTypedPat { ref pat, .. }) => is_pattern(pat),
+        _ => true,
+    }
+}
+
+/// Returns true if `pat` is a parameter which should be destructured in the
+/// function body. `...rest` is kept.
+fn is_param_pattern(pat: &Pat) -> bool {
+    match *pat {
+        Pat::Rest(RestPat { ref arg, .. }) => is_pattern(arg),
+        _ => is_pattern(pat),
+    }
+}
+
+fn bound_names(pat: &Pat) -> Vec<JsWord> {
+    let mut ids = vec![];
+    pat_ids(pat, &mut ids);
+    ids.into_iter().map(|id| id.sym).collect()
+}
+
+/// `obj.prop`
+fn member(obj: Box<Expr>, prop: Ident) -> Expr {
+    Expr::Member(MemberExpr {
+        span: DUMMY_SP,
+        obj: ExprOrSuper::Expr(obj),
+        prop: box Expr::Ident(prop),
+        computed: false,
+    })
+}
+
+/// `void 0`
+fn void_0() -> Box<Expr> {
+    box Expr::Unary(UnaryExpr {
+        span: DUMMY_SP,
+        op: op!("void"),
+        arg: box Expr::Lit(Lit::Num(Number {
+            span: DUMMY_SP,
+            value: 0.0,
+            raw: None,
+        })),
+    })
+}
+
+/// `var a, b;`
+fn var_decl<I: Iterator<Item = Ident>>(ids: I) -> Stmt {
+    Stmt::Decl(Decl::Var(VarDecl {
+        span: DUMMY_SP,
+        kind: VarDeclKind::Var,
+        declare: false,
+        decls: ids
+            .map(|id| VarDeclarator {
+                span: DUMMY_SP,
+                name: Pat::Ident(id),
+                init: None,
+            })
+            .collect(),
+    }))
+}
+
+#[derive(Default)]
+struct UsedNames(HashSet<JsWord>);
+
+impl Visit<Ident> for UsedNames {
+    fn visit(&mut self, i: &Ident) {
+        self.0.insert(i.sym.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        Destructuring::default(),
+        var_decl,
+        "var { a, b: [c, d = 1], ...e } = obj;",
+        r#"var a = obj.a, _ref = obj.b, c = _ref[0], _ref2 = _ref[1],
+    d = _ref2 === void 0 ? 1 : _ref2, e = _objectWithoutProperties(obj, ["a", "b"]);"#
+    );
+
+    test!(
+        Destructuring::default(),
+        array,
+        "var [, a, ...b] = c();",
+        "var _ref = c(), a = _ref[1], b = _ref.slice(2);"
+    );
+
+    test!(
+        Destructuring::default(),
+        assign,
+        "[a, b] = [b, a]; x = { c: d[0] } = e;",
+        r#"var _ref;
+_ref = [b, a], a = _ref[0], b = _ref[1];
+var _ref2;
+x = (_ref2 = e, d[0] = _ref2.c, _ref2);"#
+    );
+
+    test!(
+        Destructuring::default(),
+        params,
+        "function f(a, { b }, [c] = []) { 'use strict'; }",
+        r#"function f(a, _ref, _ref2) {
+  'use strict';
+  var b = _ref.b, _ref3 = _ref2 === void 0 ? [] : _ref2, c = _ref3[0];
+}"#
+    );
+
+    test!(
+        Destructuring::default(),
+        for_of,
+        "for (const [k, v] of entries) f(k, v);",
+        "for (const _ref of entries) { const k = _ref[0], v = _ref[1]; f(k, v); }"
+    );
+}
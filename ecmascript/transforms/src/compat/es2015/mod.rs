@@ -1,6 +1,14 @@
 pub use self::{
-    arrow::Arrow, classes::Classes, instanceof::InstanceOf, shorthand_property::Shorthand,
-    spread::Spread, sticky_regex::StickyRegex, template_literal::TemplateLiteral,
+    arrow::Arrow,
+    block_scoping::{BlockScoping, BlockScopingError},
+    classes::Classes,
+    destructuring::Destructuring,
+    instanceof::InstanceOf,
+    regenerator::Regenerator,
+    shorthand_property::Shorthand,
+    spread::Spread,
+    sticky_regex::StickyRegex,
+    template_literal::TemplateLiteral,
     typeof_symbol::TypeOfSymbol,
 };
 
@@ -10,8 +18,11 @@ use std::sync::Arc;
 use swc_common::Fold;
 
 mod arrow;
-mod classes;
+mod block_scoping;
+pub(super) mod classes;
+mod destructuring;
 mod instanceof;
+mod regenerator;
 mod shorthand_property;
 mod spread;
 mod sticky_regex;
@@ -23,9 +34,17 @@ pub fn es2015(helpers: &Arc<Helpers>) -> impl Fold<Module> {
     Classes {
         helpers: helpers.clone(),
     }
+    .then(Arrow)
+    .then(Destructuring {
+        helpers: helpers.clone(),
+    })
+    .then(BlockScoping::default())
     .then(Spread {
         helpers: helpers.clone(),
     })
+    .then(TemplateLiteral {
+        helpers: helpers.clone(),
+    })
     .then(StickyRegex)
     .then(Shorthand)
     .then(InstanceOf {
@@ -34,4 +53,7 @@ pub fn es2015(helpers: &Arc<Helpers>) -> impl Fold<Module> {
     .then(TypeOfSymbol {
         helpers: helpers.clone(),
     })
+    .then(Regenerator {
+        helpers: helpers.clone(),
+    })
 }
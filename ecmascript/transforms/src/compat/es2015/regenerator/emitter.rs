@@ -0,0 +1,1578 @@
+use super::{contains_leap, contains_yield, Renamer};
+use crate::{
+    compat::helpers::Helpers,
+    util::{pat_ids, ExprFactory},
+};
+use ast::*;
+use std::{
+    iter, mem,
+    sync::{atomic::Ordering, Arc},
+};
+use swc_atoms::JsWord;
+use swc_common::{Fold, FoldWith, Visit, VisitWith, DUMMY_SP};
+
+/// A location in the listing, which starts a case of the state machine.
+///
+/// Locations are used before they are marked, so they are emitted as
+/// placeholders and replaced with the index of the case by `Emitter::finish`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct Loc(usize);
+
+/// Targets of `break` and `continue`.
+enum Leap {
+    Loop {
+        label: Option<JsWord>,
+        break_loc: Loc,
+        continue_loc: Loc,
+    },
+    Switch {
+        break_loc: Loc,
+    },
+    Labeled {
+        label: JsWord,
+        break_loc: Loc,
+    },
+}
+
+/// Compiles statements of a generator function into a listing of
+/// statements, which is split into cases of a switch statement.
+///
+/// Statements and expressions containing `yield` are exploded into simpler
+/// ones, storing intermediate values in temporary properties of the context
+/// (`_context.t0`) as local variables don't survive across `yield`s.
+pub(super) struct Emitter {
+    /// `_context`
+    context: Ident,
+    helpers: Arc<Helpers>,
+    listing: Vec<Stmt>,
+    /// The index of the first statement of each location.
+    marks: Vec<Option<usize>>,
+    /// Number of temporary properties.
+    temps: usize,
+    /// `[tryLoc, catchLoc, finallyLoc, afterLoc]` of try statements.
+    try_entries: Vec<[Option<Loc>; 4]>,
+    leaps: Vec<Leap>,
+    /// Variables declared by patterns of catch clauses.
+    pub vars: Vec<Ident>,
+}
+
+impl Emitter {
+    pub fn new(context: Ident, helpers: Arc<Helpers>) -> Self {
+        Emitter {
+            context,
+            helpers,
+            listing: vec![],
+            marks: vec![],
+            temps: 0,
+            try_entries: vec![],
+            leaps: vec![],
+            vars: vec![],
+        }
+    }
+
+    /// Returns the state machine and the locations of try statements.
+    ///
+    /// ```js
+    /// while (1) switch (_context.prev = _context.next) {
+    ///     case 0:
+    ///         // ...
+    ///     case "end":
+    ///         return _context.stop();
+    /// }
+    /// ```
+    pub fn finish(self) -> (Stmt, Option<Box<Expr>>) {
+        let marks: Vec<_> = self
+            .marks
+            .iter()
+            .map(|mark| mark.expect("location is not marked"))
+            .collect();
+        let mut resolver = LocResolver { marks: &marks };
+
+        let mut starts = marks.clone();
+        starts.push(0);
+        starts.sort();
+        starts.dedup();
+
+        let mut listing = self.listing;
+        let mut cases = vec![];
+        for start in starts.into_iter().rev() {
+            let cons = listing.split_off(start);
+            cases.push(SwitchCase {
+                span: DUMMY_SP,
+                test: Some(num(start)),
+                cons: cons.fold_with(&mut resolver),
+            });
+        }
+        cases.reverse();
+        cases.push(SwitchCase {
+            span: DUMMY_SP,
+            test: Some(box Expr::Lit(Lit::Str(quote_str!("end")))),
+            cons: vec![Stmt::Return(ReturnStmt {
+                span: DUMMY_SP,
+                arg: Some(box Expr::Call(CallExpr {
+                    span: DUMMY_SP,
+                    callee: member(box Expr::Ident(self.context.clone()), "stop").as_callee(),
+                    args: vec![],
+                })),
+            })],
+        });
+
+        let context = box Expr::Ident(self.context.clone());
+        let machine = Stmt::While(WhileStmt {
+            span: DUMMY_SP,
+            test: num(1),
+            body: box Stmt::Switch(SwitchStmt {
+                span: DUMMY_SP,
+                discriminant: box Expr::Assign(AssignExpr {
+                    span: DUMMY_SP,
+                    op: op!("="),
+                    left: PatOrExpr::Expr(member(context.clone(), "prev")),
+                    right: member(context, "next"),
+                }),
+                cases,
+            }),
+        });
+
+        if self.try_entries.is_empty() {
+            return (machine, None);
+        }
+        let try_locs = self
+            .try_entries
+            .into_iter()
+            .map(|locs| {
+                let elems = locs
+                    .iter()
+                    .map(|loc| loc.map(|loc| num(marks[loc.0]).as_arg()))
+                    .collect::<Vec<_>>();
+                // Trailing holes are not required.
+                let len = elems.iter().rposition(Option::is_some).unwrap() + 1;
+
+                Some(
+                    Expr::Array(ArrayLit {
+                        span: DUMMY_SP,
+                        elems: elems.into_iter().take(len).collect(),
+                    })
+                    .as_arg(),
+                )
+            })
+            .collect();
+
+        (
+            machine,
+            Some(box Expr::Array(ArrayLit {
+                span: DUMMY_SP,
+                elems: try_locs,
+            })),
+        )
+    }
+
+    fn loc(&mut self) -> Loc {
+        self.marks.push(None);
+        Loc(self.marks.len() - 1)
+    }
+
+    /// Starts `loc` at the next statement.
+    fn mark(&mut self, loc: Loc) {
+        debug_assert_eq!(self.marks[loc.0], None, "location is marked twice");
+        self.marks[loc.0] = Some(self.listing.len());
+    }
+
+    /// Marks `loc` which may be reached without a jump, so `_context.prev`
+    /// should be updated for try statements.
+    fn mark_and_update_prev(&mut self, loc: Loc) {
+        self.mark(loc);
+        let prev = self.context_prop("prev");
+        let loc = self.loc_expr(loc);
+        self.emit_assign(prev, loc);
+    }
+
+    fn loc_expr(&self, loc: Loc) -> Box<Expr> {
+        box Expr::Ident(quote_ident!(format!("#{}", loc.0)))
+    }
+
+    fn emit(&mut self, stmt: Stmt) {
+        self.listing.push(stmt)
+    }
+
+    fn emit_assign(&mut self, left: Box<Expr>, right: Box<Expr>) {
+        self.emit(assign(PatOrExpr::Expr(left), right).into_stmt())
+    }
+
+    /// `_context.name`
+    fn context_prop(&self, name: &str) -> Box<Expr> {
+        member(box Expr::Ident(self.context.clone()), name)
+    }
+
+    /// `_context.t0`
+    fn temp(&mut self) -> Box<Expr> {
+        self.temps += 1;
+        self.context_prop(&format!("t{}", self.temps - 1))
+    }
+
+    /// Stores `e` in a new temporary property if `cond` is true, so that
+    /// it's evaluated before the following `yield`.
+    fn save_if(&mut self, e: Box<Expr>, cond: bool) -> Box<Expr> {
+        if !cond || self.is_saved(&e) {
+            return e;
+        }
+        let temp = self.temp();
+        self.emit_assign(temp.clone(), e);
+        temp
+    }
+
+    /// Returns true if `e` is a literal or a temporary property, which
+    /// doesn't change by `yield`.
+    fn is_saved(&self, e: &Expr) -> bool {
+        match *e {
+            Expr::Lit(..) | Expr::This(..) => true,
+            Expr::Member(MemberExpr {
+                obj: ExprOrSuper::Expr(box Expr::Ident(ref obj)),
+                prop: box Expr::Ident(ref prop),
+                computed: false,
+                ..
+            }) => obj.sym == self.context.sym && &*prop.sym != "sent",
+            _ => false,
+        }
+    }
+
+    /// `_context.next = loc; break;`
+    fn jump(&mut self, loc: Loc) {
+        let next = self.context_prop("next");
+        let loc = self.loc_expr(loc);
+        self.emit_assign(next, loc);
+        self.emit(Stmt::Break(BreakStmt {
+            span: DUMMY_SP,
+            label: None,
+        }));
+    }
+
+    /// `if (test) { _context.next = loc; break; }`
+    fn jump_if(&mut self, test: Box<Expr>, loc: Loc) {
+        let next = self.context_prop("next");
+        let loc = self.loc_expr(loc);
+        self.emit(Stmt::If(IfStmt {
+            span: DUMMY_SP,
+            test,
+            cons: box Stmt::Block(BlockStmt {
+                span: DUMMY_SP,
+                stmts: vec![
+                    assign(PatOrExpr::Expr(next), loc).into_stmt(),
+                    Stmt::Break(BreakStmt {
+                        span: DUMMY_SP,
+                        label: None,
+                    }),
+                ],
+            }),
+            alt: None,
+        }))
+    }
+
+    fn jump_if_not(&mut self, test: Box<Expr>, loc: Loc) {
+        let test = match *test {
+            Expr::Ident(..) | Expr::Member(..) | Expr::Call(..) | Expr::Lit(..) => test,
+            _ => box test.wrap_with_paren(),
+        };
+        self.jump_if(
+            box Expr::Unary(UnaryExpr {
+                span: DUMMY_SP,
+                op: op!("!"),
+                arg: test,
+            }),
+            loc,
+        )
+    }
+
+    /// `return _context.abrupt("break", loc);`
+    fn emit_abrupt(&mut self, kind: &str, arg: Option<Box<Expr>>) {
+        let abrupt = self.context_prop("abrupt");
+        self.emit(Stmt::Return(ReturnStmt {
+            span: DUMMY_SP,
+            arg: Some(box Expr::Call(CallExpr {
+                span: DUMMY_SP,
+                callee: abrupt.as_callee(),
+                args: iter::once(Expr::Lit(Lit::Str(quote_str!(kind))).as_arg())
+                    .chain(arg.map(|arg| arg.as_arg()))
+                    .collect(),
+            })),
+        }))
+    }
+
+    fn break_loc(&self, label: Option<&JsWord>) -> Loc {
+        for leap in self.leaps.iter().rev() {
+            match (leap, label) {
+                (&Leap::Loop { break_loc, .. }, None) | (&Leap::Switch { break_loc }, None) => {
+                    return break_loc
+                }
+                (
+                    &Leap::Loop {
+                        label: Some(ref l),
+                        break_loc,
+                        ..
+                    },
+                    Some(label),
+                )
+                | (
+                    &Leap::Labeled {
+                        label: ref l,
+                        break_loc,
+                    },
+                    Some(label),
+                ) if l == label => return break_loc,
+                _ => {}
+            }
+        }
+        unreachable!("break without a target")
+    }
+
+    fn continue_loc(&self, label: Option<&JsWord>) -> Loc {
+        for leap in self.leaps.iter().rev() {
+            match *leap {
+                Leap::Loop {
+                    label: ref l,
+                    continue_loc,
+                    ..
+                } => match label {
+                    None => return continue_loc,
+                    Some(label) if l.as_ref() == Some(label) => return continue_loc,
+                    _ => {}
+                },
+                _ => {}
+            }
+        }
+        unreachable!("continue without a target")
+    }
+
+    /// Emits `stmt`. `label` is the label of `stmt` if it's a loop.
+    pub fn explode_stmt(&mut self, stmt: Stmt, label: Option<JsWord>) {
+        if let Stmt::Empty(..) = stmt {
+            return;
+        }
+        if !contains_leap(&stmt) {
+            self.emit(stmt);
+            return;
+        }
+
+        match stmt {
+            Stmt::Block(BlockStmt { stmts, .. }) => {
+                for stmt in stmts {
+                    self.explode_stmt(stmt, None);
+                }
+            }
+
+            Stmt::Expr(ExprStmt { expr, .. }) => self.explode_expr_stmt(expr),
+
+            Stmt::If(IfStmt {
+                test, cons, alt, ..
+            }) => {
+                let else_loc = alt.as_ref().map(|_| self.loc());
+                let after = self.loc();
+
+                let test = self.explode_expr(test);
+                self.jump_if_not(test, else_loc.unwrap_or(after));
+                self.explode_stmt(*cons, None);
+
+                if let Some(alt) = alt {
+                    self.jump(after);
+                    self.mark(else_loc.unwrap());
+                    self.explode_stmt(*alt, None);
+                }
+                self.mark(after);
+            }
+
+            Stmt::While(WhileStmt { test, body, .. }) => {
+                let before = self.loc();
+                let after = self.loc();
+
+                self.mark(before);
+                let test = self.explode_expr(test);
+                self.jump_if_not(test, after);
+                self.explode_loop_body(label, *body, after, before);
+                self.jump(before);
+                self.mark(after);
+            }
+
+            Stmt::DoWhile(DoWhileStmt { test, body, .. }) => {
+                let first = self.loc();
+                let test_loc = self.loc();
+                let after = self.loc();
+
+                self.mark(first);
+                self.explode_loop_body(label, *body, after, test_loc);
+                self.mark(test_loc);
+                let test = self.explode_expr(test);
+                self.jump_if(test, first);
+                self.mark(after);
+            }
+
+            Stmt::For(ForStmt {
+                init,
+                test,
+                update,
+                body,
+                ..
+            }) => {
+                let head = self.loc();
+                let update_loc = self.loc();
+                let after = self.loc();
+
+                match init {
+                    Some(VarDeclOrExpr::Expr(init)) => self.explode_expr_stmt(init),
+                    Some(VarDeclOrExpr::VarDecl(..)) => {
+                        unreachable!("variables should be hoisted before exploding")
+                    }
+                    None => {}
+                }
+                self.mark(head);
+                if let Some(test) = test {
+                    let test = self.explode_expr(test);
+                    self.jump_if_not(test, after);
+                }
+                self.explode_loop_body(label, *body, after, update_loc);
+                self.mark(update_loc);
+                if let Some(update) = update {
+                    self.explode_expr_stmt(update);
+                }
+                self.jump(head);
+                self.mark(after);
+            }
+
+            // _context.t0 = _regeneratorRuntime.keys(obj);
+            // if ((_context.t1 = _context.t0()).done) ...
+            // left = _context.t1.value;
+            Stmt::ForIn(ForInStmt {
+                left, right, body, ..
+            }) => {
+                let right = self.explode_expr(right);
+                let keys = self.runtime_call("keys", right);
+                let iter = self.save_if(keys, true);
+                let next = box Expr::Call(CallExpr {
+                    span: DUMMY_SP,
+                    callee: iter.as_callee(),
+                    args: vec![],
+                });
+                self.explode_iteration(label, left, next, *body)
+            }
+
+            // _context.t0 = _regeneratorRuntime.values(iterable);
+            // if ((_context.t1 = _context.t0.next()).done) ...
+            // left = _context.t1.value;
+            Stmt::ForOf(ForOfStmt {
+                left, right, body, ..
+            }) => {
+                let right = self.explode_expr(right);
+                let values = self.runtime_call("values", right);
+                let iter = self.save_if(values, true);
+                let next = box Expr::Call(CallExpr {
+                    span: DUMMY_SP,
+                    callee: member(iter, "next").as_callee(),
+                    args: vec![],
+                });
+                self.explode_iteration(label, left, next, *body)
+            }
+
+            Stmt::Labeled(LabeledStmt { label, body, .. }) => match *body {
+                Stmt::While(..)
+                | Stmt::DoWhile(..)
+                | Stmt::For(..)
+                | Stmt::ForIn(..)
+                | Stmt::ForOf(..) => self.explode_stmt(*body, Some(label.sym)),
+                body => {
+                    let after = self.loc();
+                    self.leaps.push(Leap::Labeled {
+                        label: label.sym,
+                        break_loc: after,
+                    });
+                    self.explode_stmt(body, None);
+                    self.leaps.pop();
+                    self.mark(after);
+                }
+            },
+
+            Stmt::Break(BreakStmt { label, .. }) => {
+                let loc = self.break_loc(label.as_ref().map(|l| &l.sym));
+                let loc = self.loc_expr(loc);
+                self.emit_abrupt("break", Some(loc))
+            }
+
+            Stmt::Continue(ContinueStmt { label, .. }) => {
+                let loc = self.continue_loc(label.as_ref().map(|l| &l.sym));
+                let loc = self.loc_expr(loc);
+                self.emit_abrupt("continue", Some(loc))
+            }
+
+            Stmt::Return(ReturnStmt { arg, .. }) => {
+                let arg = arg.map(|arg| self.explode_expr(arg));
+                self.emit_abrupt("return", arg)
+            }
+
+            Stmt::Throw(ThrowStmt { span, arg }) => {
+                let arg = self.explode_expr(arg);
+                self.emit(Stmt::Throw(ThrowStmt { span, arg }))
+            }
+
+            Stmt::Switch(SwitchStmt {
+                discriminant,
+                cases,
+                ..
+            }) => {
+                let discriminant = self.explode_expr(discriminant);
+                let discriminant = self.save_if(discriminant, true);
+                let after = self.loc();
+                let locs: Vec<_> = cases.iter().map(|_| self.loc()).collect();
+
+                let mut default = None;
+                let mut conses = Vec::with_capacity(cases.len());
+                for (i, case) in cases.into_iter().enumerate() {
+                    match case.test {
+                        Some(test) => {
+                            let test = self.explode_expr(test);
+                            self.jump_if(
+                                box Expr::Bin(BinExpr {
+                                    span: DUMMY_SP,
+                                    op: op!("==="),
+                                    left: discriminant.clone(),
+                                    right: test,
+                                }),
+                                locs[i],
+                            );
+                        }
+                        None => default = Some(locs[i]),
+                    }
+                    conses.push(case.cons);
+                }
+                self.jump(default.unwrap_or(after));
+
+                self.leaps.push(Leap::Switch { break_loc: after });
+                for (loc, cons) in locs.into_iter().zip(conses) {
+                    self.mark(loc);
+                    for stmt in cons {
+                        self.explode_stmt(stmt, None);
+                    }
+                }
+                self.leaps.pop();
+                self.mark(after);
+            }
+
+            Stmt::Try(TryStmt {
+                block,
+                handler,
+                finalizer,
+                ..
+            }) => {
+                let first = self.loc();
+                let catch_loc = handler.as_ref().map(|_| self.loc());
+                let finally_loc = finalizer.as_ref().map(|_| self.loc());
+                let after = self.loc();
+
+                self.try_entries.push([
+                    Some(first),
+                    catch_loc,
+                    finally_loc,
+                    finally_loc.map(|_| after),
+                ]);
+
+                self.mark(first);
+                for stmt in block.stmts {
+                    self.explode_stmt(stmt, None);
+                }
+
+                if let Some(handler) = handler {
+                    self.jump(finally_loc.unwrap_or(after));
+                    self.mark_and_update_prev(catch_loc.unwrap());
+
+                    // _context.t0 = _context["catch"](first);
+                    let catch = box Expr::Call(CallExpr {
+                        span: DUMMY_SP,
+                        callee: Expr::Member(MemberExpr {
+                            span: DUMMY_SP,
+                            obj: ExprOrSuper::Expr(box Expr::Ident(self.context.clone())),
+                            prop: box Expr::Lit(Lit::Str(quote_str!("catch"))),
+                            computed: true,
+                        })
+                        .as_callee(),
+                        args: vec![self.loc_expr(first).as_arg()],
+                    });
+                    let body = match handler.param {
+                        Some(Pat::Ident(param)) => {
+                            let temp = self.save_if(catch, true);
+                            // The parameter is a temporary property, as it's used across cases.
+                            handler.body.fold_with(&mut Renamer::new(param.sym, temp, false))
+                        }
+                        Some(param) => {
+                            pat_ids(&param, &mut self.vars);
+                            self.emit(assign(PatOrExpr::Pat(box param), catch).into_stmt());
+                            handler.body
+                        }
+                        None => {
+                            self.emit(catch.into_stmt());
+                            handler.body
+                        }
+                    };
+                    for stmt in body.stmts {
+                        self.explode_stmt(stmt, None);
+                    }
+                }
+
+                if let Some(finalizer) = finalizer {
+                    let finally_loc = finally_loc.unwrap();
+                    self.mark_and_update_prev(finally_loc);
+                    for stmt in finalizer.stmts {
+                        self.explode_stmt(stmt, None);
+                    }
+
+                    // return _context.finish(finallyLoc);
+                    let finish = self.context_prop("finish");
+                    let loc = self.loc_expr(finally_loc);
+                    self.emit(Stmt::Return(ReturnStmt {
+                        span: DUMMY_SP,
+                        arg: Some(box Expr::Call(CallExpr {
+                            span: DUMMY_SP,
+                            callee: finish.as_callee(),
+                            args: vec![loc.as_arg()],
+                        })),
+                    }));
+                }
+                self.mark(after);
+            }
+
+            // Each statement of the body is wrapped, as cases of the state
+            // machine can't be inside of `with`.
+            //
+            // _context.t0 = obj;
+            // with (_context.t0) { ... }
+            Stmt::With(WithStmt { span, obj, body }) => {
+                let obj = self.explode_expr(obj);
+                let obj = self.save_if(obj, true);
+
+                let start = self.listing.len();
+                self.explode_stmt(*body, None);
+                for stmt in &mut self.listing[start..] {
+                    let body = mem::replace(stmt, Stmt::Empty(EmptyStmt { span: DUMMY_SP }));
+                    *stmt = Stmt::With(WithStmt {
+                        span,
+                        obj: obj.clone(),
+                        body: box body,
+                    });
+                }
+            }
+
+            _ => unreachable!("declarations should be hoisted before exploding"),
+        }
+    }
+
+    fn explode_loop_body(&mut self, label: Option<JsWord>, body: Stmt, after: Loc, cont: Loc) {
+        self.leaps.push(Leap::Loop {
+            label,
+            break_loc: after,
+            continue_loc: cont,
+        });
+        self.explode_stmt(body, None);
+        self.leaps.pop();
+    }
+
+    /// Loop of `for in` and `for of`, where `next` returns an iterator
+    /// result.
+    fn explode_iteration(
+        &mut self,
+        label: Option<JsWord>,
+        left: VarDeclOrPat,
+        next: Box<Expr>,
+        body: Stmt,
+    ) {
+        let head = self.loc();
+        let after = self.loc();
+
+        self.mark(head);
+        let result = self.temp();
+        self.jump_if(
+            member(
+                box assign(PatOrExpr::Expr(result.clone()), next).wrap_with_paren(),
+                "done",
+            ),
+            after,
+        );
+        let left = match left {
+            VarDeclOrPat::Pat(pat) => pat,
+            VarDeclOrPat::VarDecl(..) => {
+                unreachable!("variables should be hoisted before exploding")
+            }
+        };
+        self.emit(assign(PatOrExpr::Pat(box left), member(result, "value")).into_stmt());
+
+        self.explode_loop_body(label, body, after, head);
+        self.jump(head);
+        self.mark(after);
+    }
+
+    /// `_regeneratorRuntime.name(arg)`
+    fn runtime_call(&mut self, name: &str, arg: Box<Expr>) -> Box<Expr> {
+        box Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            callee: member(box Expr::Ident(quote_ident!("_regeneratorRuntime")), name)
+                .as_callee(),
+            args: vec![arg.as_arg()],
+        })
+    }
+
+    fn explode_expr_stmt(&mut self, e: Box<Expr>) {
+        let e = self.explode_expr(e);
+        match *e {
+            // The value of `yield a;`
+            Expr::Member(MemberExpr {
+                obj: ExprOrSuper::Expr(box Expr::Ident(ref obj)),
+                computed: false,
+                ..
+            }) if obj.sym == self.context.sym => {}
+            Expr::Lit(..) => {}
+            _ => self.emit(e.into_stmt()),
+        }
+    }
+
+    /// Emits statements which evaluate `e` until its last `yield`, and returns
+    /// an expression which can be used instead of `e` after that.
+    fn explode_expr(&mut self, e: Box<Expr>) -> Box<Expr> {
+        if !contains_yield(&e) {
+            return e;
+        }
+
+        match *e {
+            Expr::Paren(ParenExpr { expr, .. }) => self.explode_expr(expr),
+
+            Expr::Yield(YieldExpr { arg, delegate, .. }) => {
+                let arg = arg.map(|arg| self.explode_expr(arg));
+                let after = self.loc();
+
+                if delegate {
+                    // return _context.delegateYield(iterable, "t0", after);
+                    let result = self.temp();
+                    let name = match *result {
+                        Expr::Member(MemberExpr {
+                            prop: box Expr::Ident(ref name),
+                            ..
+                        }) => name.sym.clone(),
+                        _ => unreachable!(),
+                    };
+                    let delegate_yield = self.context_prop("delegateYield");
+                    let loc = self.loc_expr(after);
+                    self.emit(Stmt::Return(ReturnStmt {
+                        span: DUMMY_SP,
+                        arg: Some(box Expr::Call(CallExpr {
+                            span: DUMMY_SP,
+                            callee: delegate_yield.as_callee(),
+                            args: vec![
+                                arg.unwrap().as_arg(),
+                                Expr::Lit(Lit::Str(quote_str!(name))).as_arg(),
+                                loc.as_arg(),
+                            ],
+                        })),
+                    }));
+                    self.mark(after);
+                    return result;
+                }
+
+                // _context.next = after;
+                // return arg;
+                let next = self.context_prop("next");
+                let loc = self.loc_expr(after);
+                self.emit_assign(next, loc);
+                self.emit(Stmt::Return(ReturnStmt {
+                    span: DUMMY_SP,
+                    arg,
+                }));
+                self.mark(after);
+                self.context_prop("sent")
+            }
+
+            Expr::Member(MemberExpr {
+                span,
+                obj,
+                prop,
+                computed,
+            }) => {
+                let (obj, prop) = self.explode_member(obj, prop, computed, false);
+                box Expr::Member(MemberExpr {
+                    span,
+                    obj,
+                    prop,
+                    computed,
+                })
+            }
+
+            Expr::Call(CallExpr {
+                span,
+                callee: ExprOrSuper::Expr(callee),
+                args,
+            }) => {
+                let args_have_yield = args.iter().any(|arg| contains_yield(&arg.expr));
+
+                match *callee {
+                    Expr::Member(MemberExpr {
+                        span: member_span,
+                        obj,
+                        prop,
+                        computed,
+                    }) => {
+                        let (obj, prop) = self.explode_member(obj, prop, computed, args_have_yield);
+                        let callee = box Expr::Member(MemberExpr {
+                            span: member_span,
+                            obj: obj.clone(),
+                            prop,
+                            computed,
+                        });
+                        if !args_have_yield {
+                            return box Expr::Call(CallExpr {
+                                span,
+                                callee: callee.as_callee(),
+                                args,
+                            });
+                        }
+
+                        // The method is read before the arguments are evaluated.
+                        //
+                        // _context.t1 = _context.t0.foo;
+                        // ...
+                        // _context.t1.call(_context.t0, _context.sent)
+                        let callee = self.save_if(callee, true);
+                        let this = match obj {
+                            ExprOrSuper::Expr(obj) => obj,
+                            ExprOrSuper::Super(span) => box Expr::This(ThisExpr { span }),
+                            ExprOrSuper::Import(..) => unreachable!("`import` is not an object"),
+                        };
+                        let args = self.explode_args(args);
+                        box Expr::Call(CallExpr {
+                            span,
+                            callee: member(callee, "call").as_callee(),
+                            args: iter::once(this.as_arg()).chain(args).collect(),
+                        })
+                    }
+                    callee => {
+                        let callee = self.explode_expr(box callee);
+                        let callee = self.save_if(callee, args_have_yield);
+                        let args = self.explode_args(args);
+
+                        // `(0, _context.t0)()` calls the function without `this`.
+                        let callee = match *callee {
+                            Expr::Member(..) => box Expr::Seq(SeqExpr {
+                                span: DUMMY_SP,
+                                exprs: vec![num(0), callee],
+                            })
+                            .wrap_with_paren(),
+                            _ => callee,
+                        };
+                        box Expr::Call(CallExpr {
+                            span,
+                            callee: callee.as_callee(),
+                            args,
+                        })
+                    }
+                }
+            }
+
+            Expr::New(NewExpr { span, callee, args }) => {
+                let args_have_yield = args
+                    .iter()
+                    .flatten()
+                    .any(|arg| contains_yield(&arg.expr));
+                let callee = self.explode_expr(callee);
+                let callee = self.save_if(callee, args_have_yield);
+                let args = args.map(|args| self.explode_args(args));
+
+                box Expr::New(NewExpr { span, callee, args })
+            }
+
+            Expr::Array(ArrayLit { span, elems }) => {
+                let holes: Vec<_> = elems.iter().map(Option::is_none).collect();
+                let mut elems = self
+                    .explode_args(elems.into_iter().flatten().collect())
+                    .into_iter();
+
+                box Expr::Array(ArrayLit {
+                    span,
+                    elems: holes
+                        .into_iter()
+                        .map(|hole| if hole { None } else { elems.next() })
+                        .collect(),
+                })
+            }
+
+            Expr::Object(ObjectLit { span, props }) => {
+                let last = props.iter().rposition(|prop| contains_yield(prop));
+                let mut buf = Vec::with_capacity(props.len());
+
+                for (i, prop) in props.into_iter().enumerate() {
+                    let leap_after = last.map_or(false, |last| i < last);
+
+                    let prop = match prop {
+                        PropOrSpread::Spread(SpreadElement { dot3_token, expr }) => {
+                            let expr = self.explode_expr(expr);
+                            PropOrSpread::Spread(SpreadElement {
+                                dot3_token,
+                                expr: self.save_if(expr, leap_after),
+                            })
+                        }
+                        PropOrSpread::Prop(box Prop::Shorthand(ident)) => {
+                            if leap_after {
+                                let value = self.save_if(box Expr::Ident(ident.clone()), true);
+                                PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                                    key: PropName::Ident(ident),
+                                    value,
+                                }))
+                            } else {
+                                PropOrSpread::Prop(box Prop::Shorthand(ident))
+                            }
+                        }
+                        PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp { key, value })) => {
+                            let key =
+                                self.explode_prop_name(key, leap_after || contains_yield(&value));
+                            let value = self.explode_expr(value);
+                            PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                                key,
+                                value: self.save_if(value, leap_after),
+                            }))
+                        }
+                        // Only keys of methods are evaluated with the object.
+                        PropOrSpread::Prop(box Prop::Method(MethodProp { key, function })) => {
+                            let key = self.explode_prop_name(key, leap_after);
+                            PropOrSpread::Prop(box Prop::Method(MethodProp { key, function }))
+                        }
+                        PropOrSpread::Prop(box Prop::Getter(GetterProp { span, key, body })) => {
+                            let key = self.explode_prop_name(key, leap_after);
+                            PropOrSpread::Prop(box Prop::Getter(GetterProp { span, key, body }))
+                        }
+                        PropOrSpread::Prop(box Prop::Setter(SetterProp {
+                            span,
+                            key,
+                            param,
+                            body,
+                        })) => {
+                            let key = self.explode_prop_name(key, leap_after);
+                            PropOrSpread::Prop(box Prop::Setter(SetterProp {
+                                span,
+                                key,
+                                param,
+                                body,
+                            }))
+                        }
+                        // `{ a = b }` is only valid in patterns.
+                        prop => prop,
+                    };
+                    buf.push(prop);
+                }
+
+                box Expr::Object(ObjectLit { span, props: buf })
+            }
+
+            Expr::Seq(SeqExpr { span, mut exprs }) => {
+                let last = exprs.pop().unwrap();
+                for e in exprs {
+                    self.explode_expr_stmt(e);
+                }
+                let last = self.explode_expr(last);
+                match *last {
+                    Expr::Seq(..) => box Expr::Seq(SeqExpr {
+                        span,
+                        exprs: vec![last],
+                    }),
+                    _ => last,
+                }
+            }
+
+            Expr::Unary(UnaryExpr { span, op, arg }) => box Expr::Unary(UnaryExpr {
+                span,
+                op,
+                arg: self.explode_expr(arg),
+            }),
+
+            Expr::Update(UpdateExpr {
+                span,
+                op,
+                prefix,
+                arg,
+            }) => box Expr::Update(UpdateExpr {
+                span,
+                op,
+                prefix,
+                arg: self.explode_expr(arg),
+            }),
+
+            Expr::Bin(BinExpr {
+                span,
+                op,
+                left,
+                right,
+            }) => match op {
+                op!("&&") | op!("||") | op!("??") => {
+                    // _context.t0 = left;
+                    // if (!_context.t0) { _context.next = after; break; }
+                    // _context.t0 = right;
+                    let result = self.temp();
+                    let after = self.loc();
+
+                    let left = self.explode_expr(left);
+                    self.emit_assign(result.clone(), left);
+                    match op {
+                        op!("&&") => self.jump_if_not(result.clone(), after),
+                        op!("||") => self.jump_if(result.clone(), after),
+                        _ => self.jump_if(
+                            box Expr::Bin(BinExpr {
+                                span: DUMMY_SP,
+                                op: op!("!="),
+                                left: result.clone(),
+                                right: box Expr::Lit(Lit::Null(Null { span: DUMMY_SP })),
+                            }),
+                            after,
+                        ),
+                    }
+                    let right = self.explode_expr(right);
+                    self.emit_assign(result.clone(), right);
+                    self.mark(after);
+                    result
+                }
+                _ => {
+                    let mut exprs = self.explode_exprs(vec![left, right]).into_iter();
+                    box Expr::Bin(BinExpr {
+                        span,
+                        op,
+                        left: exprs.next().unwrap(),
+                        right: exprs.next().unwrap(),
+                    })
+                }
+            },
+
+            Expr::Cond(CondExpr {
+                test, cons, alt, ..
+            }) => {
+                let result = self.temp();
+                let else_loc = self.loc();
+                let after = self.loc();
+
+                let test = self.explode_expr(test);
+                self.jump_if_not(test, else_loc);
+                let cons = self.explode_expr(cons);
+                self.emit_assign(result.clone(), cons);
+                self.jump(after);
+                self.mark(else_loc);
+                let alt = self.explode_expr(alt);
+                self.emit_assign(result.clone(), alt);
+                self.mark(after);
+                result
+            }
+
+            Expr::Assign(AssignExpr {
+                span,
+                op,
+                left,
+                right,
+            }) => {
+                let right_has_yield = contains_yield(&right);
+                let left = match left {
+                    PatOrExpr::Pat(box Pat::Expr(e)) => PatOrExpr::Expr(e),
+                    PatOrExpr::Pat(box Pat::Ident(i)) => PatOrExpr::Expr(box Expr::Ident(i)),
+                    left => left,
+                };
+
+                let left = match left {
+                    PatOrExpr::Expr(box Expr::Member(MemberExpr {
+                        span,
+                        obj,
+                        prop,
+                        computed,
+                    })) => {
+                        let (obj, prop) = self.explode_member(obj, prop, computed, right_has_yield);
+                        box Expr::Member(MemberExpr {
+                            span,
+                            obj,
+                            prop,
+                            computed,
+                        })
+                    }
+                    PatOrExpr::Expr(left) => left,
+                    PatOrExpr::Pat(pat) => {
+                        let right = self.explode_expr(right);
+                        if !contains_yield(&pat) {
+                            return box Expr::Assign(AssignExpr {
+                                span,
+                                op,
+                                left: PatOrExpr::Pat(pat),
+                                right,
+                            });
+                        }
+
+                        // The value of the assignment is the right hand side.
+                        let right = self.save_if(right, true);
+                        self.explode_pat_assign(*pat, right.clone());
+                        return right;
+                    }
+                };
+
+                // `a += yield b` reads `a` before `yield`.
+                if op != op!("=") && right_has_yield {
+                    let value = self.save_if(left.clone(), true);
+                    let right = self.explode_expr(right);
+                    return box Expr::Assign(AssignExpr {
+                        span,
+                        op: op!("="),
+                        left: PatOrExpr::Expr(left),
+                        right: box Expr::Bin(BinExpr {
+                            span,
+                            op: bin_op(op),
+                            left: value,
+                            right,
+                        }),
+                    });
+                }
+
+                let right = self.explode_expr(right);
+                box Expr::Assign(AssignExpr {
+                    span,
+                    op,
+                    left: PatOrExpr::Expr(left),
+                    right,
+                })
+            }
+
+            Expr::Tpl(TplLit {
+                span,
+                tag,
+                exprs,
+                quasis,
+            }) => {
+                let has_tag = tag.is_some();
+                let mut exprs = self.explode_exprs(tag.into_iter().chain(exprs).collect());
+                let tag = if has_tag { Some(exprs.remove(0)) } else { None };
+
+                box Expr::Tpl(TplLit {
+                    span,
+                    tag,
+                    exprs,
+                    quasis,
+                })
+            }
+
+            _ => self.explode_children(*e),
+        }
+    }
+
+    /// Explodes child expressions of `e` in order, for expressions like
+    /// `class extends (yield a) {}` and `super.foo(yield a)` which are not
+    /// handled by [Emitter::explode_expr].
+    fn explode_children(&mut self, e: Expr) -> Box<Expr> {
+        let mut finder = ChildFinder { yields: vec![] };
+        e.visit_children(&mut finder);
+        let last = finder.yields.iter().rposition(|&has_yield| has_yield);
+
+        box e.fold_children(&mut ChildExploder {
+            emitter: self,
+            last,
+            idx: 0,
+        })
+    }
+
+    /// Explodes `exprs`, which are evaluated in order.
+    fn explode_exprs(&mut self, exprs: Vec<Box<Expr>>) -> Vec<Box<Expr>> {
+        let last = exprs.iter().rposition(|e| contains_yield(e));
+
+        let mut buf = Vec::with_capacity(exprs.len());
+        for (i, e) in exprs.into_iter().enumerate() {
+            let e = self.explode_expr(e);
+            let leap_after = last.map_or(false, |last| i < last);
+            buf.push(self.save_if(e, leap_after));
+        }
+        buf
+    }
+
+    fn explode_args(&mut self, args: Vec<ExprOrSpread>) -> Vec<ExprOrSpread> {
+        let (spreads, exprs): (Vec<_>, Vec<_>) =
+            args.into_iter().map(|arg| (arg.spread, arg.expr)).unzip();
+
+        self.explode_exprs(exprs)
+            .into_iter()
+            .zip(spreads)
+            .map(|(expr, spread)| ExprOrSpread { spread, expr })
+            .collect()
+    }
+
+    /// Explodes the expression of a computed key, which is saved if
+    /// `leap_after` is true.
+    fn explode_prop_name(&mut self, key: PropName, leap_after: bool) -> PropName {
+        match key {
+            PropName::Computed(ComputedPropName { span, expr }) => {
+                let expr = self.explode_expr(expr);
+                PropName::Computed(ComputedPropName {
+                    span,
+                    expr: self.save_if(expr, leap_after),
+                })
+            }
+            key => key,
+        }
+    }
+
+    /// Assigns `value` to targets of `pat` one by one, as defaults and
+    /// targets of the pattern contain `yield`.
+    ///
+    /// Like `Destructuring`, elements of arrays are read by index.
+    fn explode_pat_assign(&mut self, pat: Pat, value: Box<Expr>) {
+        match pat {
+            Pat::Ident(i) => self.emit_assign(box Expr::Ident(i), value),
+            Pat::Expr(target) => {
+                let target = match *target {
+                    Expr::Member(MemberExpr {
+                        span,
+                        obj,
+                        prop,
+                        computed,
+                    }) => {
+                        let (obj, prop) = self.explode_member(obj, prop, computed, false);
+                        box Expr::Member(MemberExpr {
+                            span,
+                            obj,
+                            prop,
+                            computed,
+                        })
+                    }
+                    target => box target,
+                };
+                self.emit_assign(target, value)
+            }
+            Pat::TsTyped(TsTypedPat { pat, .. }) => self.explode_pat_assign(*pat, value),
+
+            // _context.t0 = value;
+            // if (_context.t0 === void 0) _context.t0 = right;
+            Pat::Assign(AssignPat { left, right, .. }) => {
+                let temp = self.temp();
+                self.emit_assign(temp.clone(), value);
+
+                let after = self.loc();
+                let is_undefined = box Expr::Bin(BinExpr {
+                    span: DUMMY_SP,
+                    op: op!("==="),
+                    left: temp.clone(),
+                    right: box Expr::Unary(UnaryExpr {
+                        span: DUMMY_SP,
+                        op: op!("void"),
+                        arg: num(0),
+                    }),
+                });
+                self.jump_if_not(is_undefined, after);
+                let right = self.explode_expr(right);
+                self.emit_assign(temp.clone(), right);
+                self.mark(after);
+
+                self.explode_pat_assign(*left, temp)
+            }
+
+            Pat::Array(ArrayPat { elems, .. }) => {
+                let obj = self.temp();
+                self.emit_assign(obj.clone(), value);
+
+                for (i, elem) in elems.into_iter().enumerate() {
+                    match elem {
+                        // A hole.
+                        None => {}
+                        // `_context.t0.slice(1)`
+                        Some(Pat::Rest(RestPat { arg, .. })) => {
+                            let value = box Expr::Call(CallExpr {
+                                span: DUMMY_SP,
+                                callee: member(obj.clone(), "slice").as_callee(),
+                                args: vec![num(i).as_arg()],
+                            });
+                            self.explode_pat_assign(*arg, value)
+                        }
+                        // `_context.t0[0]`
+                        Some(elem) => {
+                            let value = box Expr::Member(MemberExpr {
+                                span: DUMMY_SP,
+                                obj: ExprOrSuper::Expr(obj.clone()),
+                                prop: num(i),
+                                computed: true,
+                            });
+                            self.explode_pat_assign(elem, value)
+                        }
+                    }
+                }
+            }
+
+            Pat::Object(ObjectPat { props, .. }) => {
+                let obj = self.temp();
+                self.emit_assign(obj.clone(), value);
+                // Keys which are excluded from the rest element.
+                let mut keys = vec![];
+
+                for prop in props {
+                    match prop {
+                        ObjectPatProp::KeyValue(KeyValuePatProp { key, value: pat }) => {
+                            let (prop, computed) = match key {
+                                PropName::Ident(i) => (box Expr::Ident(i), false),
+                                PropName::Str(s) => (box Expr::Lit(Lit::Str(s)), true),
+                                PropName::Num(n) => (box Expr::Lit(Lit::Num(n)), true),
+                                PropName::Computed(ComputedPropName { expr, .. }) => {
+                                    let expr = self.explode_expr(expr);
+                                    (self.save_if(expr, true), true)
+                                }
+                            };
+                            keys.push(match *prop {
+                                Expr::Ident(ref i) if !computed => {
+                                    box Expr::Lit(Lit::Str(quote_str!(i.sym.clone())))
+                                }
+                                Expr::Lit(Lit::Str(..)) => prop.clone(),
+                                // `_context.t1 + ""`
+                                _ => box Expr::Bin(BinExpr {
+                                    span: DUMMY_SP,
+                                    op: op!(bin, "+"),
+                                    left: prop.clone(),
+                                    right: box Expr::Lit(Lit::Str(quote_str!(""))),
+                                }),
+                            });
+
+                            let value = box Expr::Member(MemberExpr {
+                                span: DUMMY_SP,
+                                obj: ExprOrSuper::Expr(obj.clone()),
+                                prop,
+                                computed,
+                            });
+                            self.explode_pat_assign(*pat, value)
+                        }
+                        ObjectPatProp::Assign(AssignPatProp { span, key, value }) => {
+                            keys.push(box Expr::Lit(Lit::Str(quote_str!(key.sym.clone()))));
+
+                            let prop = member(obj.clone(), &key.sym);
+                            let pat = match value {
+                                Some(right) => Pat::Assign(AssignPat {
+                                    span,
+                                    left: box Pat::Ident(key),
+                                    right,
+                                }),
+                                None => Pat::Ident(key),
+                            };
+                            self.explode_pat_assign(pat, prop)
+                        }
+                        // `_objectWithoutProperties(_context.t0, ["a", "b"])`
+                        ObjectPatProp::Rest(RestPat { arg, .. }) => {
+                            self.helpers
+                                .object_without_properties
+                                .store(true, Ordering::SeqCst);
+
+                            let keys = Expr::Array(ArrayLit {
+                                span: DUMMY_SP,
+                                elems: keys
+                                    .drain(..)
+                                    .map(|key| Some(key.as_arg()))
+                                    .collect(),
+                            });
+                            let value = box Expr::Call(CallExpr {
+                                span: DUMMY_SP,
+                                callee: quote_ident!("_objectWithoutProperties").as_callee(),
+                                args: vec![obj.clone().as_arg(), keys.as_arg()],
+                            });
+                            self.explode_pat_assign(*arg, value)
+                        }
+                    }
+                }
+            }
+
+            Pat::Rest(..) => unreachable!("rest pattern outside of array patterns and parameters"),
+        }
+    }
+
+    /// Explodes the object and the property of a member expression.
+    ///
+    /// They are saved if `leap_after` is true, which means that the member
+    /// is used after a `yield`.
+    fn explode_member(
+        &mut self,
+        obj: ExprOrSuper,
+        prop: Box<Expr>,
+        computed: bool,
+        leap_after: bool,
+    ) -> (ExprOrSuper, Box<Expr>) {
+        let prop_has_yield = computed && contains_yield(&prop);
+
+        let obj = match obj {
+            ExprOrSuper::Expr(obj) => {
+                let obj = self.explode_expr(obj);
+                ExprOrSuper::Expr(self.save_if(obj, leap_after || prop_has_yield))
+            }
+            ExprOrSuper::Super(span) => ExprOrSuper::Super(span),
+            ExprOrSuper::Import(span) => ExprOrSuper::Import(span),
+        };
+        if !computed {
+            return (obj, prop);
+        }
+
+        let prop = self.explode_expr(prop);
+        (obj, self.save_if(prop, leap_after))
+    }
+}
+
+/// Records whether each child expression contains `yield`, in the order
+/// [ChildExploder] visits them.
+///
+/// Functions and initializers of class properties are not evaluated along
+/// with their parent, so they are skipped.
+struct ChildFinder {
+    yields: Vec<bool>,
+}
+
+impl Visit<Expr> for ChildFinder {
+    fn visit(&mut self, e: &Expr) {
+        self.yields.push(contains_yield(e));
+    }
+}
+
+/// The super class is evaluated before computed keys.
+impl Visit<Class> for ChildFinder {
+    fn visit(&mut self, c: &Class) {
+        c.super_class.visit_with(self);
+        c.body.visit_with(self);
+    }
+}
+
+impl Visit<ClassProp> for ChildFinder {
+    fn visit(&mut self, p: &ClassProp) {
+        p.key.visit_with(self);
+    }
+}
+
+impl Visit<PrivateProp> for ChildFinder {
+    fn visit(&mut self, _: &PrivateProp) {}
+}
+
+impl Visit<StaticBlock> for ChildFinder {
+    fn visit(&mut self, _: &StaticBlock) {}
+}
+
+impl Visit<Function> for ChildFinder {
+    fn visit(&mut self, _: &Function) {}
+}
+
+impl Visit<ArrowExpr> for ChildFinder {
+    fn visit(&mut self, _: &ArrowExpr) {}
+}
+
+/// Explodes child expressions found by [ChildFinder], saving ones evaluated
+/// before the last `yield`.
+struct ChildExploder<'a> {
+    emitter: &'a mut Emitter,
+    /// Index of the last child containing `yield`.
+    last: Option<usize>,
+    idx: usize,
+}
+
+impl<'a> Fold<Expr> for ChildExploder<'a> {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let leap_after = self.last.map_or(false, |last| self.idx < last);
+        self.idx += 1;
+
+        let e = self.emitter.explode_expr(box e);
+        *self.emitter.save_if(e, leap_after)
+    }
+}
+
+impl<'a> Fold<Class> for ChildExploder<'a> {
+    fn fold(&mut self, c: Class) -> Class {
+        let super_class = c.super_class.fold_with(self);
+        let body = c.body.fold_with(self);
+        Class {
+            super_class,
+            body,
+            ..c
+        }
+    }
+}
+
+impl<'a> Fold<ClassProp> for ChildExploder<'a> {
+    fn fold(&mut self, p: ClassProp) -> ClassProp {
+        ClassProp {
+            key: p.key.fold_with(self),
+            ..p
+        }
+    }
+}
+
+impl<'a> Fold<PrivateProp> for ChildExploder<'a> {
+    fn fold(&mut self, p: PrivateProp) -> PrivateProp {
+        p
+    }
+}
+
+impl<'a> Fold<StaticBlock> for ChildExploder<'a> {
+    fn fold(&mut self, b: StaticBlock) -> StaticBlock {
+        b
+    }
+}
+
+impl<'a> Fold<Function> for ChildExploder<'a> {
+    fn fold(&mut self, f: Function) -> Function {
+        f
+    }
+}
+
+impl<'a> Fold<ArrowExpr> for ChildExploder<'a> {
+    fn fold(&mut self, f: ArrowExpr) -> ArrowExpr {
+        f
+    }
+}
+
+/// Replaces placeholders of locations with indices of cases.
+struct LocResolver<'a> {
+    marks: &'a [usize],
+}
+
+impl<'a> Fold<Expr> for LocResolver<'a> {
+    fn fold(&mut self, e: Expr) -> Expr {
+        match e {
+            // An identifier can't start with `#`.
+            Expr::Ident(ref i) if i.sym.starts_with('#') => {
+                let loc: usize = (&*i.sym)[1..].parse().unwrap();
+                *num(self.marks[loc])
+            }
+            _ => e.fold_children(self),
+        }
+    }
+}
+
+fn num(value: usize) -> Box<Expr> {
+    box Expr::Lit(Lit::Num(Number {
+        span: DUMMY_SP,
+        value: value as f64,
+        raw: None,
+    }))
+}
+
+/// `obj.name`
+fn member(obj: Box<Expr>, name: &str) -> Box<Expr> {
+    box Expr::Member(MemberExpr {
+        span: DUMMY_SP,
+        obj: ExprOrSuper::Expr(obj),
+        prop: box Expr::Ident(quote_ident!(name)),
+        computed: false,
+    })
+}
+
+fn assign(left: PatOrExpr, right: Box<Expr>) -> Expr {
+    Expr::Assign(AssignExpr {
+        span: DUMMY_SP,
+        op: op!("="),
+        left,
+        right,
+    })
+}
+
+/// `+` for `+=`
+fn bin_op(op: AssignOp) -> BinaryOp {
+    match op {
+        op!("+=") => op!(bin, "+"),
+        op!("-=") => op!(bin, "-"),
+        op!("*=") => op!("*"),
+        op!("/=") => op!("/"),
+        op!("%=") => op!("%"),
+        op!("<<=") => op!("<<"),
+        op!(">>=") => op!(">>"),
+        op!(">>>=") => op!(">>>"),
+        op!("|=") => op!("|"),
+        op!("^=") => op!("^"),
+        op!("&=") => op!("&"),
+        op!("**=") => op!("**"),
+        op!("=") => unreachable!(),
+    }
+}
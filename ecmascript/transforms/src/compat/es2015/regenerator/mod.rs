@@ -0,0 +1,515 @@
+use self::emitter::Emitter;
+use crate::{
+    compat::helpers::Helpers,
+    util::{pat_ids, ExprFactory},
+};
+use ast::*;
+use std::{
+    collections::HashSet,
+    sync::{atomic::Ordering, Arc},
+};
+use swc_atoms::JsWord;
+use swc_common::{Fold, FoldWith, Visit, VisitWith, DUMMY_SP};
+
+mod emitter;
+#[cfg(test)]
+mod tests;
+
+/// `regenerator-transform`
+///
+/// Generator functions are compiled to state machines, which are run by the
+/// `_regeneratorRuntime` helper.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// function* foo(a) {
+///     var b = yield a;
+///     return b;
+/// }
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// function foo(a) {
+///     var b;
+///     return _regeneratorRuntime.wrap(function (_context) {
+///         while (1) switch (_context.prev = _context.next) {
+///             case 0:
+///                 _context.next = 2;
+///                 return a;
+///             case 2:
+///                 b = _context.sent;
+///                 return _context.abrupt("return", b);
+///             case "end":
+///                 return _context.stop();
+///         }
+///     }, this);
+/// }
+/// ```
+///
+/// Variables of the generator are hoisted to the outer function, so they
+/// survive across `yield`s. This should run after `BlockScoping`.
+/// Async generators are not changed.
+#[derive(Debug, Clone, Default)]
+pub struct Regenerator {
+    pub helpers: Arc<Helpers>,
+}
+
+impl Fold<Function> for Regenerator {
+    fn fold(&mut self, f: Function) -> Function {
+        let mut f = f.fold_children(self);
+        if f.generator_token.is_none() || f.async_token.is_some() {
+            return f;
+        }
+        let body = match f.body.take() {
+            Some(body) => body,
+            None => {
+                return Function {
+                    generator_token: None,
+                    ..f
+                }
+            }
+        };
+        self.helpers.regenerator.store(true, Ordering::SeqCst);
+
+        let span = body.span;
+        let mut stmts = body.stmts;
+        // Directives stay in the outer function.
+        let directives_len = stmts
+            .iter()
+            .position(|stmt| match *stmt {
+                Stmt::Expr(ExprStmt {
+                    expr: box Expr::Lit(Lit::Str(..)),
+                    ..
+                }) => false,
+                _ => true,
+            })
+            .unwrap_or_else(|| stmts.len());
+        let body = stmts.split_off(directives_len);
+
+        // `arguments` of the inner function is not the one of the generator.
+        let mut arguments = Renamer::new(
+            js_word!("arguments"),
+            box Expr::Ident(quote_ident!("_arguments")),
+            true,
+        );
+        let body = body.fold_with(&mut arguments);
+
+        let mut hoister = Hoister::default();
+        let body = body.fold_with(&mut hoister);
+
+        let context = quote_ident!("_context");
+        let mut emitter = Emitter::new(context.clone(), self.helpers.clone());
+        for stmt in body {
+            emitter.explode_stmt(stmt, None);
+        }
+        let vars = emitter.vars.split_off(0);
+        let (machine, try_locs) = emitter.finish();
+
+        let mut names = HashSet::new();
+        let mut decls: Vec<_> = hoister
+            .vars
+            .into_iter()
+            .chain(vars)
+            .filter(|ident| names.insert(ident.sym.clone()))
+            .map(|ident| VarDeclarator {
+                span: ident.span,
+                name: Pat::Ident(ident),
+                init: None,
+            })
+            .collect();
+        if arguments.used {
+            decls.push(VarDeclarator {
+                span: DUMMY_SP,
+                name: Pat::Ident(quote_ident!("_arguments")),
+                init: Some(box Expr::Ident(quote_ident!("arguments"))),
+            });
+        }
+        if !decls.is_empty() {
+            stmts.push(Stmt::Decl(Decl::Var(VarDecl {
+                span: DUMMY_SP,
+                kind: VarDeclKind::Var,
+                declare: false,
+                decls,
+            })));
+        }
+        stmts.extend(hoister.functions);
+
+        // _regeneratorRuntime.wrap(function (_context) {}, this, tryLocsList)
+        let inner = Expr::Fn(FnExpr {
+            ident: None,
+            function: Function {
+                params: vec![Param {
+                    span: DUMMY_SP,
+                    decorators: vec![],
+                    pat: Pat::Ident(context),
+                }],
+                span,
+                body: Some(BlockStmt {
+                    span,
+                    stmts: vec![machine],
+                }),
+                generator_token: None,
+                async_token: None,
+                type_params: None,
+                return_type: None,
+            },
+        });
+        let wrap = Expr::Call(CallExpr {
+            span,
+            callee: member_expr!(span, _regeneratorRuntime.wrap).as_callee(),
+            args: vec![inner.as_arg(), Expr::This(ThisExpr { span }).as_arg()]
+                .into_iter()
+                .chain(try_locs.map(|locs| locs.as_arg()))
+                .collect(),
+        });
+        stmts.push(Stmt::Return(ReturnStmt {
+            span,
+            arg: Some(box wrap),
+        }));
+
+        Function {
+            body: Some(BlockStmt { span, stmts }),
+            generator_token: None,
+            ..f
+        }
+    }
+}
+
+/// Moves declarations in the generator to the outer function.
+///
+/// Variables are initialized by assignments, and function declarations are
+/// moved as is.
+#[derive(Default)]
+struct Hoister {
+    vars: Vec<Ident>,
+    functions: Vec<Stmt>,
+}
+
+impl Hoister {
+    /// Converts `var a = 1, b` to `a = 1`.
+    fn var_to_expr(&mut self, decl: VarDecl) -> Option<Box<Expr>> {
+        let mut exprs = vec![];
+        for d in decl.decls {
+            pat_ids(&d.name, &mut self.vars);
+            if let Some(init) = d.init {
+                exprs.push(box Expr::Assign(AssignExpr {
+                    span: d.span,
+                    op: op!("="),
+                    left: PatOrExpr::Pat(box d.name),
+                    right: init,
+                }));
+            }
+        }
+
+        match exprs.len() {
+            0 => None,
+            1 => exprs.pop(),
+            _ => Some(box Expr::Seq(SeqExpr {
+                span: decl.span,
+                exprs,
+            })),
+        }
+    }
+}
+
+impl Fold<Stmt> for Hoister {
+    fn fold(&mut self, stmt: Stmt) -> Stmt {
+        let stmt = stmt.fold_children(self);
+
+        match stmt {
+            Stmt::Decl(Decl::Var(decl)) => {
+                let span = decl.span;
+                match self.var_to_expr(decl) {
+                    Some(expr) => Stmt::Expr(ExprStmt { span, expr }),
+                    None => Stmt::Empty(EmptyStmt { span }),
+                }
+            }
+            Stmt::Decl(Decl::Fn(decl)) => {
+                let span = decl.function.span;
+                self.functions.push(Stmt::Decl(Decl::Fn(decl)));
+                Stmt::Empty(EmptyStmt { span })
+            }
+            Stmt::Decl(Decl::Class(ClassDecl { ident, class, .. })) => {
+                let span = class.span;
+                self.vars.push(ident.clone());
+                Expr::Assign(AssignExpr {
+                    span,
+                    op: op!("="),
+                    left: PatOrExpr::Pat(box Pat::Ident(ident.clone())),
+                    right: box Expr::Class(ClassExpr {
+                        ident: Some(ident),
+                        class,
+                    }),
+                })
+                .into_stmt()
+            }
+            _ => stmt,
+        }
+    }
+}
+
+impl Fold<ForStmt> for Hoister {
+    fn fold(&mut self, stmt: ForStmt) -> ForStmt {
+        let stmt = stmt.fold_children(self);
+
+        let init = match stmt.init {
+            Some(VarDeclOrExpr::VarDecl(decl)) => self.var_to_expr(decl).map(VarDeclOrExpr::Expr),
+            init => init,
+        };
+        ForStmt { init, ..stmt }
+    }
+}
+
+/// `for (var a in b)`
+impl Fold<VarDeclOrPat> for Hoister {
+    fn fold(&mut self, left: VarDeclOrPat) -> VarDeclOrPat {
+        match left {
+            VarDeclOrPat::VarDecl(mut decl) => {
+                let name = decl.decls.pop().unwrap().name;
+                pat_ids(&name, &mut self.vars);
+                VarDeclOrPat::Pat(name)
+            }
+            _ => left,
+        }
+    }
+}
+
+/// Declarations in nested functions are not hoisted.
+impl Fold<Function> for Hoister {
+    fn fold(&mut self, f: Function) -> Function {
+        f
+    }
+}
+
+impl Fold<ArrowExpr> for Hoister {
+    fn fold(&mut self, f: ArrowExpr) -> ArrowExpr {
+        f
+    }
+}
+
+/// Replaces references to a variable with an expression.
+///
+/// This is used for `arguments` and parameters of catch clauses.
+struct Renamer {
+    from: JsWord,
+    to: Box<Expr>,
+    /// Whether the variable is declared by each nested function, like
+    /// `arguments`.
+    stop_at_functions: bool,
+    used: bool,
+}
+
+impl Renamer {
+    fn new(from: JsWord, to: Box<Expr>, stop_at_functions: bool) -> Self {
+        Renamer {
+            from,
+            to,
+            stop_at_functions,
+            used: false,
+        }
+    }
+}
+
+impl Fold<Expr> for Renamer {
+    fn fold(&mut self, e: Expr) -> Expr {
+        match e {
+            Expr::Ident(ref i) if i.sym == self.from => {
+                self.used = true;
+                *self.to.clone()
+            }
+            _ => e.fold_children(self),
+        }
+    }
+}
+
+impl Fold<Pat> for Renamer {
+    fn fold(&mut self, pat: Pat) -> Pat {
+        match pat {
+            Pat::Ident(ref i) if i.sym == self.from => {
+                self.used = true;
+                Pat::Expr(self.to.clone())
+            }
+            _ => pat.fold_children(self),
+        }
+    }
+}
+
+/// `a.b`
+impl Fold<MemberExpr> for Renamer {
+    fn fold(&mut self, e: MemberExpr) -> MemberExpr {
+        MemberExpr {
+            obj: e.obj.fold_with(self),
+            prop: if e.computed {
+                e.prop.fold_with(self)
+            } else {
+                e.prop
+            },
+            ..e
+        }
+    }
+}
+
+/// `{ a }`
+impl Fold<Prop> for Renamer {
+    fn fold(&mut self, prop: Prop) -> Prop {
+        match prop {
+            Prop::Shorthand(ref i) if i.sym == self.from => {
+                self.used = true;
+                Prop::KeyValue(KeyValueProp {
+                    key: PropName::Ident(i.clone()),
+                    value: self.to.clone(),
+                })
+            }
+            _ => prop.fold_children(self),
+        }
+    }
+}
+
+impl Fold<PropName> for Renamer {
+    fn fold(&mut self, name: PropName) -> PropName {
+        match name {
+            PropName::Computed(..) => name.fold_children(self),
+            _ => name,
+        }
+    }
+}
+
+impl Fold<Function> for Renamer {
+    fn fold(&mut self, f: Function) -> Function {
+        if self.stop_at_functions || declares(&f, &self.from) {
+            return f;
+        }
+        f.fold_children(self)
+    }
+}
+
+/// Getters and setters have the own `arguments`.
+impl Fold<GetterProp> for Renamer {
+    fn fold(&mut self, prop: GetterProp) -> GetterProp {
+        if self.stop_at_functions {
+            return GetterProp {
+                key: prop.key.fold_with(self),
+                ..prop
+            };
+        }
+        prop.fold_children(self)
+    }
+}
+
+impl Fold<SetterProp> for Renamer {
+    fn fold(&mut self, prop: SetterProp) -> SetterProp {
+        if self.stop_at_functions {
+            return SetterProp {
+                key: prop.key.fold_with(self),
+                ..prop
+            };
+        }
+        prop.fold_children(self)
+    }
+}
+
+/// Returns true if `sym` is a parameter or a variable of `f`.
+fn declares(f: &Function, sym: &JsWord) -> bool {
+    let mut ids = vec![];
+    for param in &f.params {
+        pat_ids(&param.pat, &mut ids);
+    }
+    if let Some(ref body) = f.body {
+        body.visit_with(&mut VarFinder { ids: &mut ids });
+    }
+    ids.iter().any(|id| id.sym == *sym)
+}
+
+/// Finds names declared by `var` and function declarations, which are
+/// visible in the whole function.
+struct VarFinder<'a> {
+    ids: &'a mut Vec<Ident>,
+}
+
+impl<'a> Visit<VarDecl> for VarFinder<'a> {
+    fn visit(&mut self, decl: &VarDecl) {
+        for d in &decl.decls {
+            pat_ids(&d.name, self.ids);
+        }
+        decl.visit_children(self)
+    }
+}
+
+impl<'a> Visit<FnDecl> for VarFinder<'a> {
+    fn visit(&mut self, decl: &FnDecl) {
+        self.ids.push(decl.ident.clone());
+    }
+}
+
+impl<'a> Visit<Function> for VarFinder<'a> {
+    fn visit(&mut self, _: &Function) {}
+}
+
+impl<'a> Visit<ArrowExpr> for VarFinder<'a> {
+    fn visit(&mut self, _: &ArrowExpr) {}
+}
+
+/// Returns true if `node` contains `yield` of the generator.
+fn contains_yield<T>(node: &T) -> bool
+where
+    T: VisitWith<YieldFinder>,
+{
+    let mut v = YieldFinder { found: false };
+    node.visit_with(&mut v);
+    v.found
+}
+
+/// Returns true if `node` contains a statement which jumps, which are
+/// `yield`, `return`, `break` and `continue`.
+fn contains_leap<T>(node: &T) -> bool
+where
+    T: VisitWith<YieldFinder> + VisitWith<LeapFinder>,
+{
+    let mut v = LeapFinder { found: false };
+    node.visit_with(&mut v);
+    v.found || contains_yield(node)
+}
+
+struct YieldFinder {
+    found: bool,
+}
+
+impl Visit<YieldExpr> for YieldFinder {
+    fn visit(&mut self, _: &YieldExpr) {
+        self.found = true;
+    }
+}
+
+impl Visit<Function> for YieldFinder {
+    fn visit(&mut self, _: &Function) {}
+}
+
+impl Visit<ArrowExpr> for YieldFinder {
+    fn visit(&mut self, _: &ArrowExpr) {}
+}
+
+struct LeapFinder {
+    found: bool,
+}
+
+impl Visit<Stmt> for LeapFinder {
+    fn visit(&mut self, stmt: &Stmt) {
+        match *stmt {
+            Stmt::Return(..) | Stmt::Break(..) | Stmt::Continue(..) => self.found = true,
+            _ => stmt.visit_children(self),
+        }
+    }
+}
+
+impl Visit<Function> for LeapFinder {
+    fn visit(&mut self, _: &Function) {}
+}
+
+impl Visit<ArrowExpr> for LeapFinder {
+    fn visit(&mut self, _: &ArrowExpr) {}
+}
@@ -0,0 +1,272 @@
+use super::*;
+use crate::compat::helpers::InjectHelpers;
+use swc_common::{sync::Lrc, FilePathMapping, SourceMap};
+
+/// `test_exec!` doesn't inject helpers.
+fn tr() -> impl Fold<Module> {
+    let helpers = Arc::new(Helpers::default());
+
+    Regenerator {
+        helpers: helpers.clone(),
+    }
+    .then(InjectHelpers {
+        cm: Lrc::new(SourceMap::new(FilePathMapping::empty())),
+        helpers,
+        external: false,
+    })
+}
+
+test!(
+    Regenerator::default(),
+    basic,
+    "function* foo(a) { var b = yield a; return b; }",
+    r#"function foo(a) {
+    var b;
+    return _regeneratorRuntime.wrap(function (_context) {
+        while (1) switch (_context.prev = _context.next) {
+            case 0:
+                _context.next = 2;
+                return a;
+            case 2:
+                b = _context.sent;
+                return _context.abrupt("return", b);
+            case "end":
+                return _context.stop();
+        }
+    }, this);
+}"#
+);
+
+test!(
+    Regenerator::default(),
+    loop_continue,
+    "function* foo() { for (var i = 0; i < 3; i++) { if (i === 1) continue; yield i; } }",
+    r#"function foo() {
+    var i;
+    return _regeneratorRuntime.wrap(function (_context) {
+        while (1) switch (_context.prev = _context.next) {
+            case 0:
+                i = 0;
+            case 1:
+                if (!(i < 3)) {
+                    _context.next = 9;
+                    break;
+                }
+                if (!(i === 1)) {
+                    _context.next = 4;
+                    break;
+                }
+                return _context.abrupt("continue", 6);
+            case 4:
+                _context.next = 6;
+                return i;
+            case 6:
+                i++;
+                _context.next = 1;
+                break;
+            case 9:
+            case "end":
+                return _context.stop();
+        }
+    }, this);
+}"#
+);
+
+test!(
+    Regenerator::default(),
+    non_generator_noop,
+    "function foo() { return 1; } async function* bar() { yield 1; }",
+    "function foo() { return 1; } async function* bar() { yield 1; }"
+);
+
+test_exec!(
+    tr(),
+    exec_yield_values,
+    "function* gen(a) {
+    var b = yield a;
+    var c = (yield b + 1) * 2;
+    return [a, b, c];
+}
+var it = gen(1);
+assert.deepStrictEqual(it.next(), { value: 1, done: false });
+assert.deepStrictEqual(it.next(2), { value: 3, done: false });
+assert.deepStrictEqual(it.next(4), { value: [1, 2, 8], done: true });
+assert.deepStrictEqual(it.next(), { value: undefined, done: true });"
+);
+
+test_exec!(
+    tr(),
+    exec_loops,
+    "function* gen() {
+    outer: for (var i = 0; i < 3; i++) {
+        var j = 0;
+        while (true) {
+            if (j++ > i) continue outer;
+            if (i === 2) break outer;
+            yield i * 10 + j;
+        }
+    }
+    do {
+        yield 'do';
+    } while (false);
+    for (var key in { a: 1, b: 2 }) yield key;
+    for (var value of [3, 4]) yield value;
+}
+var values = [];
+for (var v of gen()) values.push(v);
+assert.deepStrictEqual(values, [1, 11, 12, 'do', 'a', 'b', 3, 4]);"
+);
+
+test_exec!(
+    tr(),
+    exec_try,
+    "var log = [];
+function* gen() {
+    try {
+        yield 1;
+        throw new Error('a');
+    } catch (e) {
+        yield e.message;
+    } finally {
+        log.push('finally');
+    }
+    try {
+        return yield 2;
+    } finally {
+        log.push('return');
+    }
+}
+var it = gen();
+assert.deepStrictEqual(it.next(), { value: 1, done: false });
+assert.deepStrictEqual(it.next(), { value: 'a', done: false });
+assert.deepStrictEqual(it.next(), { value: 2, done: false });
+assert.deepStrictEqual(log, ['finally']);
+assert.deepStrictEqual(it.next(3), { value: 3, done: true });
+assert.deepStrictEqual(log, ['finally', 'return']);
+
+it = gen();
+it.next();
+assert.deepStrictEqual(it.throw(new Error('b')), { value: 'b', done: false });
+assert.deepStrictEqual(it.return(4), { value: 4, done: true });
+assert.deepStrictEqual(log, ['finally', 'return', 'finally']);"
+);
+
+test_exec!(
+    tr(),
+    exec_delegate,
+    "function* inner() {
+    var a = yield 1;
+    return a * 2;
+}
+function* outer() {
+    var b = yield* inner();
+    yield b;
+    yield* [3, 4];
+}
+var it = outer();
+assert.deepStrictEqual(it.next(), { value: 1, done: false });
+assert.deepStrictEqual(it.next(5), { value: 10, done: false });
+assert.deepStrictEqual(it.next(), { value: 3, done: false });
+assert.deepStrictEqual(it.next(), { value: 4, done: false });
+assert.deepStrictEqual(it.next(), { value: undefined, done: true });"
+);
+
+test_exec!(
+    tr(),
+    exec_exprs,
+    "function* gen(a) {
+    var obj = {
+        values: [],
+        push: function (v) {
+            this.values.push(v);
+            return this;
+        }
+    };
+    obj.push(yield 'a').push((yield 'b') || 'c');
+    var s = a ? yield 'd' : 'e';
+    switch (yield 'f') {
+        case 1:
+            obj.push('one');
+        case 2:
+            obj.push(s);
+            break;
+        default:
+            obj.push('default');
+    }
+    return [obj.values, arguments.length, this.name];
+}
+var it = gen.call({ name: 'this' }, true, 2);
+assert.strictEqual(it.next().value, 'a');
+assert.strictEqual(it.next(1).value, 'b');
+assert.strictEqual(it.next(0).value, 'd');
+assert.strictEqual(it.next('s').value, 'f');
+assert.deepStrictEqual(it.next(1).value, [[1, 'c', 'one', 's'], 2, 'this']);"
+);
+
+test_exec!(
+    tr(),
+    exec_class,
+    "function* gen() {
+    var C = class extends (yield 'a') {
+        [yield 'b']() {
+            return this.base() + 1;
+        }
+    };
+    return new C().m();
+}
+var it = gen();
+assert.strictEqual(it.next().value, 'a');
+assert.strictEqual(it.next(class { base() { return 1; } }).value, 'b');
+assert.deepStrictEqual(it.next('m'), { value: 2, done: true });"
+);
+
+test_exec!(
+    tr(),
+    exec_with,
+    "function* gen(obj) {
+    with (obj) {
+        a = yield b;
+        yield a + b;
+    }
+}
+var obj = { a: 0, b: 1 };
+var it = gen(obj);
+assert.strictEqual(it.next().value, 1);
+assert.strictEqual(it.next(2).value, 3);
+assert.strictEqual(obj.a, 2);"
+);
+
+test_exec!(
+    tr(),
+    exec_method_key,
+    "function* gen() {
+    var obj = {
+        [yield 'a']() {
+            return 1;
+        },
+        get [yield 'b']() {
+            return 2;
+        },
+    };
+    return obj.m() + obj.g;
+}
+var it = gen();
+assert.strictEqual(it.next().value, 'a');
+assert.strictEqual(it.next('m').value, 'b');
+assert.deepStrictEqual(it.next('g'), { value: 3, done: true });"
+);
+
+test_exec!(
+    tr(),
+    exec_pat_assign,
+    "function* gen(b) {
+    var a, c, d, rest;
+    var value = [a = yield 'a', { c = yield 'c', [yield 'k']: d, ...rest }] = b;
+    return [a, c, d, rest, value === b];
+}
+var it = gen([undefined, { x: 1, y: 2, z: 3 }]);
+assert.strictEqual(it.next().value, 'a');
+assert.strictEqual(it.next(1).value, 'c');
+assert.strictEqual(it.next(2).value, 'k');
+assert.deepStrictEqual(it.next('x').value, [1, 2, 1, { y: 2, z: 3 }, true]);"
+);
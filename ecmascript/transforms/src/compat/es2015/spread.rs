@@ -1,32 +1,88 @@
-use crate::{compat::helpers::Helpers, util::ExprFactory};
+use crate::{
+    compat::{alias_ident, assign, fold_stmts, helpers::Helpers, is_simple},
+    util::{prepend, ExprFactory, StmtLike},
+};
+use ast::*;
 use std::{
     mem,
     sync::{atomic::Ordering, Arc},
 };
-use swc_common::{Fold, FoldWith, Span, DUMMY_SP};
-use ast::*;
+use swc_common::{Fold, FoldWith, Span, Spanned, DUMMY_SP};
 
-/// es2015 - `SpreadElement`
+/// es2015 - `SpreadElement` and rest parameters
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// foo.bar(a, ...b);
+/// [a, ...b];
+/// function f(a, ...b) {}
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// foo.bar.apply(foo, [a].concat(_toConsumableArray(b)));
+/// [a].concat(_toConsumableArray(b));
+/// function f(a) {
+///     var b = Array.prototype.slice.call(arguments, 1);
+/// }
+/// ```
+///
+/// Rest parameters of arrow functions are not supported, as they don't have
+/// their own `arguments`. The arrow function transform should run first.
 #[derive(Debug, Clone, Default)]
 pub struct Spread {
     pub helpers: Arc<Helpers>,
 }
 
-impl Fold<Expr> for Spread {
+impl Fold<Module> for Spread {
+    fn fold(&mut self, module: Module) -> Module {
+        let mut folder = SpreadFolder {
+            helpers: self.helpers.clone(),
+            count: 0,
+            vars: vec![],
+        };
+        module.fold_children(&mut folder)
+    }
+}
+
+struct SpreadFolder {
+    helpers: Arc<Helpers>,
+    /// Number of temporary variables declared so far.
+    count: usize,
+    /// Temporary variables used by the current statement.
+    vars: Vec<VarDeclarator>,
+}
+
+impl<T: StmtLike> Fold<Vec<T>> for SpreadFolder
+where
+    Self: Fold<T>,
+{
+    fn fold(&mut self, stmts: Vec<T>) -> Vec<T> {
+        fold_stmts(self, |f| &mut f.vars, stmts)
+    }
+}
+
+impl Fold<Expr> for SpreadFolder {
     fn fold(&mut self, e: Expr) -> Expr {
         let e = e.fold_children(self);
 
         match e {
-            Expr::Array(ArrayLit { ref elems, .. }) => {
-                if elems.iter().any(|e| match e {
+            Expr::Array(ArrayLit { elems, span }) => {
+                let has_spread = elems.iter().any(|e| match e {
                     Some(ExprOrSpread {
                         spread: Some(_), ..
                     }) => true,
                     _ => false,
-                }) {
-                    unimplemented!("Rest element in arrat literal")
+                });
+                if !has_spread {
+                    return Expr::Array(ArrayLit { elems, span });
                 }
-                return e;
+
+                concat_args(&self.helpers, span, elems)
             }
             Expr::Call(CallExpr {
                 callee: ExprOrSuper::Expr(callee),
@@ -45,15 +101,14 @@ impl Fold<Expr> for Spread {
                 }
                 let span = mark!(span);
 
-                let args_array = concat_args(&self.helpers, span, args);
+                let (callee, this) = self.this_of(callee);
+                let args_array =
+                    concat_args(&self.helpers, span, args.into_iter().map(Some).collect());
                 //
                 // f.apply(undefined, args)
+                // obj.f.apply(obj, args)
                 //
-                callee.apply(
-                    span,
-                    quote_expr!(DUMMY_SP, undefined),
-                    vec![args_array.as_arg()],
-                )
+                callee.apply(span, this, vec![args_array.as_arg()])
             }
             Expr::New(NewExpr {
                 callee,
@@ -75,14 +130,14 @@ impl Fold<Expr> for Spread {
                 let args = concat_args(
                     &self.helpers,
                     span,
-                    vec![quote_expr!(span, null).as_arg()]
+                    vec![Some(quote_expr!(span, "null").as_arg())]
                         .into_iter()
-                        .chain(args)
+                        .chain(args.into_iter().map(Some))
                         .collect(),
                 );
 
                 //
-                // f.apply(undefined, args)
+                // new (Function.prototype.bind.apply(f, [null].concat(args)))()
                 //
 
                 Expr::New(NewExpr {
@@ -98,7 +153,108 @@ impl Fold<Expr> for Spread {
     }
 }
 
-fn concat_args(helpers: &Helpers, span: Span, args: Vec<ExprOrSpread>) -> Expr {
+/// `function f(a, ...b) {}` -> `function f(a) { var b = ...; }`
+impl Fold<Function> for SpreadFolder {
+    fn fold(&mut self, f: Function) -> Function {
+        let mut f = f.fold_children(self);
+
+        let has_rest = match f.params.last() {
+            Some(Param {
+                pat: Pat::Rest(..), ..
+            }) => true,
+            _ => false,
+        };
+        if !has_rest || f.body.is_none() {
+            return f;
+        }
+
+        let span = mark!(f.span);
+        let arg = match f.params.pop() {
+            Some(Param {
+                pat: Pat::Rest(RestPat { arg, .. }),
+                ..
+            }) => arg,
+            _ => unreachable!(),
+        };
+
+        //
+        // Array.prototype.slice.call(arguments, 1)
+        //
+        let mut args = vec![quote_ident!(span, "arguments").as_arg()];
+        if !f.params.is_empty() {
+            args.push(
+                Expr::Lit(Lit::Num(Number {
+                    span,
+                    value: f.params.len() as f64,
+                    raw: None,
+                }))
+                .as_arg(),
+            );
+        }
+        let init = Expr::Call(CallExpr {
+            span,
+            callee: member_expr!(span, Array.prototype.slice.call).as_callee(),
+            args,
+        });
+
+        let decl = Stmt::Decl(Decl::Var(VarDecl {
+            span,
+            kind: VarDeclKind::Var,
+            declare: false,
+            decls: vec![VarDeclarator {
+                span,
+                name: *arg,
+                init: Some(box init),
+            }],
+        }));
+        prepend(&mut f.body.as_mut().unwrap().stmts, decl);
+
+        f
+    }
+}
+
+impl SpreadFolder {
+    /// Returns the callee and the value of `this` for `callee.apply`.
+    fn this_of(&mut self, callee: Box<Expr>) -> (Box<Expr>, Box<Expr>) {
+        match *callee {
+            Expr::Member(MemberExpr {
+                span,
+                obj: ExprOrSuper::Super(..),
+                ..
+            }) => (callee, box Expr::This(ThisExpr { span })),
+            Expr::Member(MemberExpr {
+                obj: ExprOrSuper::Expr(obj),
+                span,
+                prop,
+                computed,
+            }) => {
+                let (obj, this) = if is_simple(&obj) {
+                    (obj.clone(), obj)
+                } else {
+                    self.count += 1;
+                    let i = alias_ident(&mut self.vars, obj.span(), "_obj", self.count - 1);
+                    (
+                        box assign(span, i.clone(), obj).wrap_with_paren(),
+                        box Expr::Ident(i),
+                    )
+                };
+
+                let callee = box Expr::Member(MemberExpr {
+                    span,
+                    obj: ExprOrSuper::Expr(obj),
+                    prop,
+                    computed,
+                });
+                (callee, this)
+            }
+            _ => (callee, box quote_expr!(DUMMY_SP, "undefined")),
+        }
+    }
+}
+
+/// Converts elements of an array literal, which may contain spread elements,
+/// to `[a].concat(_toConsumableArray(b), [c])` or `_toConsumableArray(b)`.
+fn concat_args(helpers: &Helpers, span: Span, args: Vec<Option<ExprOrSpread>>) -> Expr {
     //
     // []
     //
@@ -124,11 +280,12 @@ fn concat_args(helpers: &Helpers, span: Span, args: Vec<ExprOrSpread>) -> Expr {
     }
 
     for arg in args {
-        let ExprOrSpread { expr, spread } = arg;
-
-        match spread {
+        match arg {
             // ...b -> toConsumableArray(b)
-            Some(span) => {
+            Some(ExprOrSpread {
+                expr,
+                spread: Some(span),
+            }) => {
                 //
                 make_arr!();
 
@@ -143,11 +300,19 @@ fn concat_args(helpers: &Helpers, span: Span, args: Vec<ExprOrSpread>) -> Expr {
                     .as_arg(),
                 );
             }
-            None => tmp_arr.push(Some(expr.as_arg())),
+            _ => tmp_arr.push(arg),
         }
     }
     make_arr!();
 
+    // [...b] -> toConsumableArray(b)
+    match first_arr {
+        Some(Expr::Array(ArrayLit { ref elems, .. })) if elems.is_empty() && buf.len() == 1 => {
+            return *buf.pop().unwrap().expr;
+        }
+        _ => {}
+    }
+
     Expr::Call(CallExpr {
         // TODO
         span,
@@ -213,4 +378,55 @@ mod tests {
         "new C(a, b, c, c, d, e)",
         "new C(a, b, c, c, d, e);"
     );
+
+    test!(
+        Spread::default(),
+        call_member,
+        "a.b(...c)",
+        "a.b.apply(a, _toConsumableArray(c));"
+    );
+
+    test!(
+        Spread::default(),
+        call_member_alias,
+        "foo().bar(a, ...c)",
+        "var _obj;
+(_obj = foo()).bar.apply(_obj, [a].concat(_toConsumableArray(c)));"
+    );
+
+    test!(
+        Spread::default(),
+        array,
+        "[a, , ...b, c]",
+        "[a, , ].concat(_toConsumableArray(b), [c]);"
+    );
+
+    test!(
+        Spread::default(),
+        array_single,
+        "[...a]",
+        "_toConsumableArray(a);"
+    );
+
+    test!(
+        Spread::default(),
+        array_noop,
+        "[a, , b]",
+        "[a, , b];"
+    );
+
+    test!(
+        Spread::default(),
+        rest_param,
+        "function f(a, ...b) { 'use strict'; return b; }
+function g(...c) {}",
+        "function f(a) {
+    'use strict';
+    var b = Array.prototype.slice.call(arguments, 1);
+    return b;
+}
+function g() {
+    var c = Array.prototype.slice.call(arguments);
+}"
+    );
 }
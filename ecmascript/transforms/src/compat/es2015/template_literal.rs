@@ -1,19 +1,264 @@
+use crate::{compat::helpers::Helpers, util::ExprFactory};
 use ast::*;
-use swc_common::{Fold, FoldWith};
+use std::{
+    iter,
+    sync::{atomic::Ordering, Arc},
+};
+use swc_common::{Fold, FoldWith, Span, DUMMY_SP};
 
-#[derive(Debug, Clone, Copy, Default)]
-pub struct TemplateLiteral;
+/// es2015 - `TemplateLiteral`
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// `a${b}c${d}`;
+/// tag`a${b}`;
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// function _templateObject() {
+///     var data = _taggedTemplateLiteral(["a", ""]);
+///     _templateObject = function() {
+///         return data;
+///     };
+///     return data;
+/// }
+/// "a".concat(b, "c").concat(d);
+/// tag(_templateObject(), b);
+/// ```
+///
+/// Each expression is converted to a string by its own `concat` call, so
+/// it's converted before the next expression is evaluated, like the template
+/// literal does.
+#[derive(Debug, Clone, Default)]
+pub struct TemplateLiteral {
+    pub helpers: Arc<Helpers>,
+}
+
+impl Fold<Module> for TemplateLiteral {
+    fn fold(&mut self, module: Module) -> Module {
+        let mut folder = TplFolder {
+            helpers: self.helpers.clone(),
+            objects: vec![],
+        };
+        let module = module.fold_children(&mut folder);
+        if folder.objects.is_empty() {
+            return module;
+        }
 
-impl Fold<Expr> for TemplateLiteral {
+        let body = folder
+            .objects
+            .into_iter()
+            .map(ModuleItem::Stmt)
+            .chain(module.body)
+            .collect();
+        Module { body, ..module }
+    }
+}
+
+struct TplFolder {
+    helpers: Arc<Helpers>,
+    /// `function _templateObject() {}` for each tagged template.
+    objects: Vec<Stmt>,
+}
+
+impl Fold<Expr> for TplFolder {
     fn fold(&mut self, e: Expr) -> Expr {
         let e = e.fold_children(self);
 
         match e {
-            Expr::Tpl(TplLit { .. }) => {
-                // TODO
-                unimplemented!("template literal")
+            Expr::Tpl(TplLit {
+                span,
+                tag: Some(tag),
+                exprs,
+                quasis,
+            }) => {
+                let object = self.template_object(quasis);
+                let object = Expr::Call(CallExpr {
+                    span: DUMMY_SP,
+                    callee: object.as_callee(),
+                    args: vec![],
+                });
+
+                Expr::Call(CallExpr {
+                    span,
+                    callee: tag.as_callee(),
+                    args: iter::once(object.as_arg())
+                        .chain(exprs.into_iter().map(|e| e.as_arg()))
+                        .collect(),
+                })
+            }
+            Expr::Tpl(TplLit {
+                span,
+                tag: None,
+                exprs,
+                quasis,
+            }) => {
+                // Only tagged templates can contain invalid escapes.
+                let mut quasis = quasis.into_iter().map(|q| {
+                    let span = q.span;
+                    (span, q.cooked.expect("invalid escape in an untagged template"))
+                });
+
+                let (first_span, first) = quasis.next().unwrap();
+                let mut e = Expr::Lit(Lit::Str(quote_str!(first_span, first)));
+
+                // "a".concat(b, "c")
+                for (expr, (span, quasi)) in exprs.into_iter().zip(quasis) {
+                    let mut args = vec![expr.as_arg()];
+                    if !quasi.is_empty() {
+                        args.push(Expr::Lit(Lit::Str(quote_str!(span, quasi))).as_arg());
+                    }
+
+                    e = Expr::Call(CallExpr {
+                        span,
+                        callee: member(e, span).as_callee(),
+                        args,
+                    });
+                }
+
+                match e {
+                    Expr::Lit(..) => e,
+                    Expr::Call(call) => Expr::Call(CallExpr { span, ..call }),
+                    _ => unreachable!(),
+                }
             }
             _ => e,
         }
     }
 }
+
+impl TplFolder {
+    /// Declares `function _templateObject() {}`, which returns the strings
+    /// array of the template, and returns its name.
+    ///
+    /// The array is created only once, because a tagged template passes the
+    /// same strings to the tag each time when it's evaluated.
+    fn template_object(&mut self, quasis: Vec<TplElement>) -> Ident {
+        self.helpers
+            .tagged_template_literal
+            .store(true, Ordering::SeqCst);
+
+        let name = if self.objects.is_empty() {
+            quote_ident!("_templateObject")
+        } else {
+            quote_ident!(format!("_templateObject{}", self.objects.len() + 1))
+        };
+
+        let has_raw = quasis
+            .iter()
+            .any(|q| q.cooked.as_ref().map(|s| &**s) != Some(&*q.raw));
+        let mut strings = Vec::with_capacity(quasis.len());
+        let mut raws = Vec::with_capacity(quasis.len());
+        for q in quasis {
+            strings.push(Some(
+                match q.cooked {
+                    Some(cooked) => Expr::Lit(Lit::Str(quote_str!(q.span, cooked))),
+                    None => quote_expr!(q.span, "void 0"),
+                }
+                .as_arg(),
+            ));
+            raws.push(Some(Expr::Lit(Lit::Str(quote_str!(q.span, q.raw))).as_arg()));
+        }
+
+        // _taggedTemplateLiteral(["a", ""], ["\\a", ""])
+        let mut args = vec![array(strings).as_arg()];
+        if has_raw {
+            args.push(array(raws).as_arg());
+        }
+        let data = box Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            callee: quote_ident!("_taggedTemplateLiteral").as_callee(),
+            args,
+        });
+
+        self.objects.push(quote_stmt!(
+            DUMMY_SP,
+            "function $name() {
+                var data = $data;
+                $name = function() {
+                    return data;
+                };
+                return data;
+            }",
+            name = name.clone(),
+            data = data,
+        ));
+        name
+    }
+}
+
+/// `e.concat`
+fn member(e: Expr, span: Span) -> Expr {
+    Expr::Member(MemberExpr {
+        span,
+        obj: ExprOrSuper::Expr(box e),
+        prop: box Expr::Ident(quote_ident!(span, "concat")),
+        computed: false,
+    })
+}
+
+fn array(elems: Vec<Option<ExprOrSpread>>) -> Expr {
+    Expr::Array(ArrayLit {
+        span: DUMMY_SP,
+        elems,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        TemplateLiteral::default(),
+        no_exprs,
+        "`a\\nb`;",
+        r#""a\nb";"#
+    );
+
+    test!(
+        TemplateLiteral::default(),
+        concat,
+        "`a${b}c${d}`; `${e}`;",
+        r#""a".concat(b, "c").concat(d); "".concat(e);"#
+    );
+
+    test!(
+        TemplateLiteral::default(),
+        tagged,
+        "tag`a${b}`; foo.bar`\\unicode and \\u{55}`;",
+        r#"function _templateObject() {
+    var data = _taggedTemplateLiteral(["a", ""]);
+    _templateObject = function() {
+        return data;
+    };
+    return data;
+}
+function _templateObject2() {
+    var data = _taggedTemplateLiteral([void 0], ["\\unicode and \\u{55}"]);
+    _templateObject2 = function() {
+        return data;
+    };
+    return data;
+}
+tag(_templateObject(), b);
+foo.bar(_templateObject2());"#
+    );
+
+    test_exec!(
+        TemplateLiteral::default(),
+        exec_to_string,
+        r#"var calls = [];
+var obj = {
+  toString: function () { calls.push("toString"); return "b"; },
+  valueOf: function () { calls.push("valueOf"); return "c"; }
+};
+function f() { calls.push("f"); return 1; }
+assert.strictEqual(`a${obj}${f()}`, "ab1");
+assert.deepStrictEqual(calls, ["toString", "f"]);"#
+    );
+}
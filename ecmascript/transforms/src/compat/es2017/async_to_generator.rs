@@ -0,0 +1,811 @@
+use crate::{
+    compat::{alias_ident, assign, helpers::Helpers, to_bin_op},
+    util::ExprFactory,
+};
+use ast::*;
+use std::{
+    iter,
+    sync::{atomic::Ordering, Arc},
+};
+use swc_common::{Fold, FoldWith, Mark, Span, Spanned, Visit, VisitWith, DUMMY_SP};
+
+/// `@babel/plugin-transform-async-to-generator`
+///
+/// Async functions, methods and arrow functions are compiled to generators,
+/// which are run by the `_asyncToGenerator` helper. `await` is compiled to
+/// `yield`.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// async function foo(a) {
+///     return await bar(a);
+/// }
+/// var baz = async () => await foo(this);
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// function foo(a) {
+///     return _asyncToGenerator(function* () {
+///         return yield bar(a);
+///     }).apply(this, arguments);
+/// }
+/// var baz = () => _asyncToGenerator(function* () {
+///     return yield foo(this);
+/// }).call(this);
+/// ```
+///
+/// As `super` is not allowed in a generator function expression, `super` in
+/// async methods is replaced with arrow functions declared out of the
+/// generator, like babel does. Async generators are not changed.
+#[derive(Debug, Clone, Default)]
+pub struct AsyncToGenerator {
+    pub helpers: Arc<Helpers>,
+}
+
+impl Fold<Function> for AsyncToGenerator {
+    fn fold(&mut self, f: Function) -> Function {
+        if f.async_token.is_none() || f.generator_token.is_some() {
+            return f.fold_children(self);
+        }
+
+        // `super` is hoisted before nested async arrow functions are compiled,
+        // so they use accessors of this function.
+        let (body, supers) = match f.body {
+            Some(body) => {
+                let (body, supers) = hoist_super(body);
+                (Some(body), supers)
+            }
+            None => (None, None),
+        };
+        let f = Function { body, ..f }.fold_children(self);
+
+        match f.body {
+            Some(body) => {
+                let span = body.span;
+                let call = self.wrap(f.span, body, true);
+                let ret = Stmt::Return(ReturnStmt {
+                    span,
+                    arg: Some(box call),
+                });
+
+                Function {
+                    async_token: None,
+                    body: Some(BlockStmt {
+                        span,
+                        stmts: supers.into_iter().chain(iter::once(ret)).collect(),
+                    }),
+                    ..f
+                }
+            }
+            None => Function {
+                async_token: None,
+                ..f
+            },
+        }
+    }
+}
+
+impl Fold<ArrowExpr> for AsyncToGenerator {
+    fn fold(&mut self, f: ArrowExpr) -> ArrowExpr {
+        if f.async_token.is_none() {
+            return f.fold_children(self);
+        }
+
+        let body = match f.body {
+            BlockStmtOrExpr::BlockStmt(body) => body,
+            BlockStmtOrExpr::Expr(expr) => BlockStmt {
+                span: expr.span(),
+                stmts: vec![Stmt::Return(ReturnStmt {
+                    span: expr.span(),
+                    arg: Some(expr),
+                })],
+            },
+        };
+        let (body, supers) = hoist_super(body);
+        let body = body.fold_with(self);
+        let params = f.params.fold_with(self);
+
+        // `arguments` of an arrow function is the one of the enclosing function,
+        // and it may not exist at the top level.
+        let uses_arguments = {
+            let mut v = ArgumentsFinder { found: false };
+            body.visit_with(&mut v);
+            v.found
+        };
+        let call = self.wrap(f.span, body, uses_arguments);
+
+        let body = match supers {
+            // `{ var _superprop_getFoo = () => super.foo; return ...; }`
+            Some(supers) => BlockStmtOrExpr::BlockStmt(BlockStmt {
+                span: f.span,
+                stmts: vec![
+                    supers,
+                    Stmt::Return(ReturnStmt {
+                        span: f.span,
+                        arg: Some(box call),
+                    }),
+                ],
+            }),
+            None => BlockStmtOrExpr::Expr(box call),
+        };
+
+        ArrowExpr {
+            params,
+            async_token: None,
+            body,
+            ..f
+        }
+    }
+}
+
+impl AsyncToGenerator {
+    /// Creates `_asyncToGenerator(function* () { body }).apply(this, arguments)`.
+    ///
+    /// `.call(this)` is used instead if `pass_arguments` is false.
+    fn wrap(&mut self, span: Span, body: BlockStmt, pass_arguments: bool) -> Expr {
+        self.helpers
+            .async_to_generator
+            .store(true, Ordering::SeqCst);
+
+        let generator = Expr::Fn(FnExpr {
+            ident: None,
+            function: Function {
+                params: vec![],
+                span,
+                body: Some(body.fold_with(&mut AwaitToYield)),
+                generator_token: Some(span),
+                async_token: None,
+                type_params: None,
+                return_type: None,
+            },
+        });
+        let wrapped = box Expr::Call(CallExpr {
+            span,
+            callee: quote_ident!(span, "_asyncToGenerator").as_callee(),
+            args: vec![generator.as_arg()],
+        });
+
+        if pass_arguments {
+            quote_expr!(span, "$wrapped.apply(this, arguments)", wrapped = wrapped)
+        } else {
+            quote_expr!(span, "$wrapped.call(this)", wrapped = wrapped)
+        }
+    }
+}
+
+/// Converts `await a` to `(yield a)`.
+///
+/// Nested functions are not changed, as they are compiled before the
+/// function containing them.
+struct AwaitToYield;
+
+impl Fold<Function> for AwaitToYield {
+    fn fold(&mut self, f: Function) -> Function {
+        f
+    }
+}
+
+impl Fold<ArrowExpr> for AwaitToYield {
+    fn fold(&mut self, f: ArrowExpr) -> ArrowExpr {
+        f
+    }
+}
+
+impl Fold<Expr> for AwaitToYield {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children(self);
+
+        match e {
+            // `yield` has lower precedence than `await`.
+            Expr::Await(AwaitExpr { span, arg }) => Expr::Yield(YieldExpr {
+                span,
+                arg: Some(arg),
+                delegate: false,
+            })
+            .wrap_with_paren(),
+            _ => e,
+        }
+    }
+}
+
+impl Fold<ExprStmt> for AwaitToYield {
+    fn fold(&mut self, s: ExprStmt) -> ExprStmt {
+        let s = s.fold_children(self);
+
+        ExprStmt {
+            expr: unwrap_yield(s.expr),
+            ..s
+        }
+    }
+}
+
+impl Fold<ReturnStmt> for AwaitToYield {
+    fn fold(&mut self, s: ReturnStmt) -> ReturnStmt {
+        let s = s.fold_children(self);
+
+        ReturnStmt {
+            arg: s.arg.map(unwrap_yield),
+            ..s
+        }
+    }
+}
+
+impl Fold<VarDeclarator> for AwaitToYield {
+    fn fold(&mut self, d: VarDeclarator) -> VarDeclarator {
+        let d = d.fold_children(self);
+
+        VarDeclarator {
+            init: d.init.map(unwrap_yield),
+            ..d
+        }
+    }
+}
+
+impl Fold<AssignExpr> for AwaitToYield {
+    fn fold(&mut self, e: AssignExpr) -> AssignExpr {
+        let e = e.fold_children(self);
+
+        AssignExpr {
+            right: unwrap_yield(e.right),
+            ..e
+        }
+    }
+}
+
+/// Removes parentheses of `(yield a)` where they are not required.
+fn unwrap_yield(e: Box<Expr>) -> Box<Expr> {
+    match e {
+        box Expr::Paren(ParenExpr {
+            expr: box expr @ Expr::Yield(..),
+            ..
+        }) => box expr,
+        _ => e,
+    }
+}
+
+/// Replaces `super` in `body` with arrow functions, which are declared by the
+/// returned statement.
+fn hoist_super(body: BlockStmt) -> (BlockStmt, Option<Stmt>) {
+    let mut folder = SuperFolder {
+        mark: Mark::fresh(Mark::root()),
+        count: 0,
+        vars: vec![],
+    };
+    let body = body.fold_with(&mut folder);
+    if folder.vars.is_empty() {
+        return (body, None);
+    }
+
+    let decl = Stmt::Decl(Decl::Var(VarDecl {
+        span: DUMMY_SP,
+        kind: VarDeclKind::Var,
+        declare: false,
+        decls: folder.vars,
+    }));
+    (body, Some(decl))
+}
+
+/// Replaces `super.foo` with `_superprop_getFoo()`, where
+/// `_superprop_getFoo = () => super.foo` is declared out of the generator.
+///
+/// # In
+///
+/// ```js
+/// super.foo(a);
+/// super.bar = super[b];
+/// ```
+///
+/// # Out
+///
+/// ```js
+/// var _superprop_getFoo = () => super.foo,
+///     _superprop_get = (_prop) => super[_prop],
+///     _superprop_setBar = (_value) => super.bar = _value;
+///
+/// _superprop_getFoo().call(this, a);
+/// _superprop_setBar(_superprop_get(b));
+/// ```
+struct SuperFolder {
+    /// Marks declared names, which may collide with names of the function.
+    mark: Mark,
+    /// Number of temporary variables declared so far.
+    count: usize,
+    /// Accessors and temporary variables.
+    vars: Vec<VarDeclarator>,
+}
+
+impl SuperFolder {
+    /// Declares `_superprop_getFoo` or `_superprop_setFoo` for `super.foo`,
+    /// and `_superprop_get` or `_superprop_set` for `super[prop]`, if it's not
+    /// declared yet.
+    fn accessor(
+        &mut self,
+        is_setter: bool,
+        super_token: Span,
+        prop: &Expr,
+        computed: bool,
+    ) -> Ident {
+        let kind = if is_setter { "set" } else { "get" };
+        let name = match *prop {
+            Expr::Ident(ref i) if !computed => {
+                let mut chars = i.sym.chars();
+                let first = chars.next().into_iter().flat_map(char::to_uppercase);
+                format!("_superprop_{}{}", kind, first.chain(chars).collect::<String>())
+            }
+            _ => format!("_superprop_{}", kind),
+        };
+        let ident = quote_ident!(DUMMY_SP.apply_mark(self.mark), name);
+        let declared = self.vars.iter().any(|d| match d.name {
+            Pat::Ident(ref i) => i.sym == ident.sym,
+            _ => false,
+        });
+        if declared {
+            return ident;
+        }
+
+        // `super.foo` or `super[_prop]`
+        let (mut params, prop) = if computed {
+            let p = quote_ident!("_prop");
+            (vec![Pat::Ident(p.clone())], box Expr::Ident(p))
+        } else {
+            (vec![], box prop.clone())
+        };
+        let member = Expr::Member(MemberExpr {
+            span: super_token,
+            obj: ExprOrSuper::Super(super_token),
+            prop,
+            computed,
+        });
+        // `super.foo = _value`
+        let body = if is_setter {
+            let value = quote_ident!("_value");
+            params.push(Pat::Ident(value.clone()));
+            Expr::Assign(AssignExpr {
+                span: super_token,
+                left: PatOrExpr::Pat(box Pat::Expr(box member)),
+                op: op!("="),
+                right: box Expr::Ident(value),
+            })
+        } else {
+            member
+        };
+
+        self.vars.push(VarDeclarator {
+            span: DUMMY_SP,
+            name: Pat::Ident(ident.clone()),
+            init: Some(box Expr::Arrow(ArrowExpr {
+                span: super_token,
+                params,
+                body: BlockStmtOrExpr::Expr(box body),
+                generator_token: None,
+                async_token: None,
+                type_params: None,
+                return_type: None,
+            })),
+        });
+        ident
+    }
+
+    /// `_superprop_getFoo()` or `_superprop_get(prop)`
+    fn get(&mut self, super_token: Span, prop: Box<Expr>, computed: bool) -> Expr {
+        let getter = self.accessor(false, super_token, &prop, computed);
+        let args = if computed {
+            vec![ExprOrSpread {
+                spread: None,
+                expr: prop,
+            }]
+        } else {
+            vec![]
+        };
+
+        Expr::Call(CallExpr {
+            span: super_token,
+            callee: getter.as_callee(),
+            args,
+        })
+    }
+
+    /// `_superprop_setFoo(value)` or `_superprop_set(prop, value)`
+    fn set(
+        &mut self,
+        super_token: Span,
+        prop: Box<Expr>,
+        computed: bool,
+        value: Box<Expr>,
+    ) -> Expr {
+        let setter = self.accessor(true, super_token, &prop, computed);
+        let mut args = vec![];
+        if computed {
+            args.push(ExprOrSpread {
+                spread: None,
+                expr: prop,
+            });
+        }
+        args.push(ExprOrSpread {
+            spread: None,
+            expr: value,
+        });
+
+        Expr::Call(CallExpr {
+            span: super_token,
+            callee: setter.as_callee(),
+            args,
+        })
+    }
+
+    fn fold_prop(&mut self, prop: Box<Expr>, computed: bool) -> Box<Expr> {
+        if computed {
+            prop.fold_with(self)
+        } else {
+            prop
+        }
+    }
+
+    /// Returns `prop`, and an expression which evaluates to the same key once
+    /// `prop` is evaluated.
+    fn alias(&mut self, prop: Box<Expr>, computed: bool) -> (Box<Expr>, Box<Expr>) {
+        let is_lit = match *prop {
+            Expr::Lit(..) => true,
+            _ => false,
+        };
+        if !computed || is_lit {
+            return (prop.clone(), prop);
+        }
+
+        let tmp = self.temp("_key");
+        (box assign(prop.span(), tmp.clone(), prop), box Expr::Ident(tmp))
+    }
+
+    fn temp(&mut self, prefix: &str) -> Ident {
+        self.count += 1;
+        let span = DUMMY_SP.apply_mark(self.mark);
+        alias_ident(&mut self.vars, span, prefix, self.count - 1)
+    }
+}
+
+impl Fold<Expr> for SuperFolder {
+    fn fold(&mut self, e: Expr) -> Expr {
+        match e {
+            // `super.foo(a)` -> `_superprop_getFoo().call(this, a)`
+            Expr::Call(CallExpr {
+                span,
+                callee:
+                    ExprOrSuper::Expr(box Expr::Member(MemberExpr {
+                        obj: ExprOrSuper::Super(super_token),
+                        prop,
+                        computed,
+                        ..
+                    })),
+                args,
+            }) => {
+                let prop = self.fold_prop(prop, computed);
+                let callee = self.get(super_token, prop, computed);
+                let args = args.fold_with(self);
+
+                Expr::Call(CallExpr {
+                    span,
+                    callee: MemberExpr {
+                        span,
+                        obj: ExprOrSuper::Expr(box callee),
+                        prop: box Expr::Ident(quote_ident!("call")),
+                        computed: false,
+                    }
+                    .as_callee(),
+                    args: iter::once(ThisExpr { span }.as_arg()).chain(args).collect(),
+                })
+            }
+
+            // `super.foo` -> `_superprop_getFoo()`
+            Expr::Member(MemberExpr {
+                obj: ExprOrSuper::Super(super_token),
+                prop,
+                computed,
+                ..
+            }) => {
+                let prop = self.fold_prop(prop, computed);
+                self.get(super_token, prop, computed)
+            }
+
+            // `super.foo = a` -> `_superprop_setFoo(a)`
+            Expr::Assign(AssignExpr {
+                span,
+                left,
+                op,
+                right,
+            }) => {
+                let is_super = match left {
+                    PatOrExpr::Pat(box Pat::Expr(ref e)) | PatOrExpr::Expr(ref e) => {
+                        is_super_member(e)
+                    }
+                    _ => false,
+                };
+                if !is_super {
+                    return Expr::Assign(AssignExpr {
+                        span,
+                        left,
+                        op,
+                        right,
+                    })
+                    .fold_children(self);
+                }
+                let (super_token, prop, computed) = match left {
+                    PatOrExpr::Pat(box Pat::Expr(e)) | PatOrExpr::Expr(e) => super_member(*e),
+                    _ => unreachable!(),
+                };
+                let prop = self.fold_prop(prop, computed);
+                let right = right.fold_with(self);
+
+                if op == op!("=") {
+                    return self.set(super_token, prop, computed, right);
+                }
+
+                // `_superprop_setFoo(_superprop_getFoo() + a)`
+                let (prop, prop_ref) = self.alias(prop, computed);
+                let value = box Expr::Bin(BinExpr {
+                    span,
+                    left: box self.get(super_token, prop_ref, computed),
+                    op: to_bin_op(op),
+                    right,
+                });
+                self.set(super_token, prop, computed, value)
+            }
+
+            // `super.foo++`
+            Expr::Update(UpdateExpr {
+                span,
+                op,
+                prefix,
+                arg,
+            }) => {
+                if !is_super_member(&arg) {
+                    return Expr::Update(UpdateExpr {
+                        span,
+                        op,
+                        prefix,
+                        arg,
+                    })
+                    .fold_children(self);
+                }
+                let (super_token, prop, computed) = super_member(*arg);
+                let prop = self.fold_prop(prop, computed);
+                let (prop, prop_ref) = self.alias(prop, computed);
+
+                // `+_superprop_getFoo()`
+                let old = box Expr::Unary(UnaryExpr {
+                    span,
+                    op: op!(unary, "+"),
+                    arg: box self.get(super_token, prop_ref, computed),
+                });
+                // The old value is the result of postfix operators.
+                let (old, tmp) = if prefix {
+                    (old, None)
+                } else {
+                    let tmp = self.temp("_old");
+                    (box assign(span, tmp.clone(), old), Some(tmp))
+                };
+
+                let value = box Expr::Bin(BinExpr {
+                    span,
+                    left: old,
+                    op: if op == op!("++") {
+                        op!(bin, "+")
+                    } else {
+                        op!(bin, "-")
+                    },
+                    right: box Expr::Lit(Lit::Num(Number {
+                        span: DUMMY_SP,
+                        value: 1.0,
+                        raw: None,
+                    })),
+                });
+                let set = self.set(super_token, prop, computed, value);
+
+                match tmp {
+                    Some(tmp) => Expr::Seq(SeqExpr {
+                        span,
+                        exprs: vec![box set, box Expr::Ident(tmp)],
+                    }),
+                    None => set,
+                }
+            }
+
+            _ => e.fold_children(self),
+        }
+    }
+}
+
+/// `super` of functions is their own one.
+impl Fold<Function> for SuperFolder {
+    fn fold(&mut self, f: Function) -> Function {
+        f
+    }
+}
+
+/// `super` of methods and fields of a nested class is their own one.
+impl Fold<Class> for SuperFolder {
+    fn fold(&mut self, c: Class) -> Class {
+        c
+    }
+}
+
+impl Fold<GetterProp> for SuperFolder {
+    fn fold(&mut self, p: GetterProp) -> GetterProp {
+        p
+    }
+}
+
+impl Fold<SetterProp> for SuperFolder {
+    fn fold(&mut self, p: SetterProp) -> SetterProp {
+        p
+    }
+}
+
+fn is_super_member(e: &Expr) -> bool {
+    match *e {
+        Expr::Member(MemberExpr {
+            obj: ExprOrSuper::Super(..),
+            ..
+        }) => true,
+        _ => false,
+    }
+}
+
+/// Splits `super.foo` into the `super` token, property and whether it's
+/// computed.
+fn super_member(e: Expr) -> (Span, Box<Expr>, bool) {
+    match e {
+        Expr::Member(MemberExpr {
+            obj: ExprOrSuper::Super(super_token),
+            prop,
+            computed,
+            ..
+        }) => (super_token, prop, computed),
+        _ => unreachable!("{:?} is not a super property", e),
+    }
+}
+
+struct ArgumentsFinder {
+    found: bool,
+}
+
+impl Visit<Ident> for ArgumentsFinder {
+    fn visit(&mut self, i: &Ident) {
+        if i.sym == js_word!("arguments") {
+            self.found = true;
+        }
+    }
+}
+
+/// `arguments` in a function is its own one.
+impl Visit<Function> for ArgumentsFinder {
+    fn visit(&mut self, _: &Function) {}
+}
+
+/// `a.arguments`
+impl Visit<MemberExpr> for ArgumentsFinder {
+    fn visit(&mut self, e: &MemberExpr) {
+        e.obj.visit_with(self);
+        if e.computed {
+            e.prop.visit_with(self);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        AsyncToGenerator::default(),
+        fn_decl,
+        "async function foo(a) { await a; var b = await a.b; return await b(a) + 1; }",
+        "function foo(a) {
+    return _asyncToGenerator(function* () {
+        yield a;
+        var b = yield a.b;
+        return (yield b(a)) + 1;
+    }).apply(this, arguments);
+}"
+    );
+
+    test!(
+        AsyncToGenerator::default(),
+        arrow,
+        "var f = async (a) => await a; var g = async () => { return arguments[0]; };",
+        "var f = (a) => _asyncToGenerator(function* () {
+    return yield a;
+}).call(this);
+var g = () => _asyncToGenerator(function* () {
+    return arguments[0];
+}).apply(this, arguments);"
+    );
+
+    test!(
+        AsyncToGenerator::default(),
+        methods,
+        "class A { async foo() { await this.bar(); } } var o = { async foo() {} };",
+        "class A {
+    foo() {
+        return _asyncToGenerator(function* () {
+            yield this.bar();
+        }).apply(this, arguments);
+    }
+}
+var o = {
+    foo() {
+        return _asyncToGenerator(function* () {}).apply(this, arguments);
+    }
+};"
+    );
+
+    test!(
+        AsyncToGenerator::default(),
+        nested,
+        "async function a() { var b = async function () { await c; }; f(await b()); }",
+        "function a() {
+    return _asyncToGenerator(function* () {
+        var b = function () {
+            return _asyncToGenerator(function* () {
+                yield c;
+            }).apply(this, arguments);
+        };
+        f((yield b()));
+    }).apply(this, arguments);
+}"
+    );
+
+    test!(
+        AsyncToGenerator::default(),
+        super_props,
+        "class A extends B {
+    async foo(a) {
+        super.foo(a);
+        super.bar = await super[a];
+        return () => super.baz++;
+    }
+}",
+        "class A extends B {
+    foo(a) {
+        var _superprop_getFoo = () => super.foo,
+            _superprop_get = (_prop) => super[_prop],
+            _superprop_setBar = (_value) => super.bar = _value,
+            _superprop_getBaz = () => super.baz,
+            _old,
+            _superprop_setBaz = (_value) => super.baz = _value;
+        return _asyncToGenerator(function* () {
+            _superprop_getFoo().call(this, a);
+            _superprop_setBar((yield _superprop_get(a)));
+            return () => (_superprop_setBaz((_old = +_superprop_getBaz()) + 1), _old);
+        }).apply(this, arguments);
+    }
+}"
+    );
+
+    test!(
+        AsyncToGenerator::default(),
+        super_in_arrow,
+        "class A extends B { foo() { return async () => super.foo(await a); } }",
+        "class A extends B {
+    foo() {
+        return () => {
+            var _superprop_getFoo = () => super.foo;
+            return _asyncToGenerator(function* () {
+                return _superprop_getFoo().call(this, (yield a));
+            }).call(this);
+        };
+    }
+}"
+    );
+
+    test!(
+        AsyncToGenerator::default(),
+        generator_noop,
+        "async function* a() { await b; }",
+        "async function* a() { await b; }"
+    );
+}
@@ -0,0 +1,14 @@
+pub use self::async_to_generator::AsyncToGenerator;
+use super::helpers::Helpers;
+use ast::Module;
+use std::sync::Arc;
+use swc_common::Fold;
+
+mod async_to_generator;
+
+/// Compiles es2017 to es2016.
+pub fn es2017(helpers: &Arc<Helpers>) -> impl Fold<Module> {
+    AsyncToGenerator {
+        helpers: helpers.clone(),
+    }
+}
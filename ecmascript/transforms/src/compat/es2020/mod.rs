@@ -0,0 +1,11 @@
+pub use self::{nullish_coalescing::NullishCoalescing, opt_chaining::OptChaining};
+use ast::*;
+use swc_common::Fold;
+
+mod nullish_coalescing;
+mod opt_chaining;
+
+/// Compiles es2020 to es2019.
+pub fn es2020() -> impl Fold<Module> {
+    OptChaining::default().then(NullishCoalescing::default())
+}
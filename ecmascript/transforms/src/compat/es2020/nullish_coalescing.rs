@@ -0,0 +1,120 @@
+use crate::compat::{alias_ident, assign, fold_stmts, is_simple};
+use ast::*;
+use crate::util::{ExprFactory, StmtLike};
+use swc_common::{Fold, FoldWith, Spanned};
+
+/// `@babel/plugin-proposal-nullish-coalescing-operator`
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// a ?? b;
+/// foo() ?? bar;
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// (a != null ? a : b);
+/// var _ref;
+/// ((_ref = foo()) != null ? _ref : bar);
+/// ```
+#[derive(Debug, Default)]
+pub struct NullishCoalescing {
+    /// Number of temporary variables declared so far.
+    count: usize,
+    /// Temporary variables used by the current statement.
+    vars: Vec<VarDeclarator>,
+}
+
+impl<T: StmtLike> Fold<Vec<T>> for NullishCoalescing
+where
+    Self: Fold<T>,
+{
+    fn fold(&mut self, stmts: Vec<T>) -> Vec<T> {
+        fold_stmts(self, |f| &mut f.vars, stmts)
+    }
+}
+
+impl Fold<Expr> for NullishCoalescing {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children(self);
+
+        match e {
+            Expr::Bin(BinExpr {
+                span,
+                left,
+                op: op!("??"),
+                right,
+            }) => {
+                let (test, left) = if is_simple(&left) {
+                    (left.clone(), left)
+                } else {
+                    let left_span = left.span();
+                    self.count += 1;
+                    let i = alias_ident(&mut self.vars, left_span, "_ref", self.count - 1);
+                    (
+                        box assign(left_span, i.clone(), left).wrap_with_paren(),
+                        box Expr::Ident(i),
+                    )
+                };
+
+                Expr::Cond(CondExpr {
+                    span,
+                    test: box Expr::Bin(BinExpr {
+                        span,
+                        op: op!("!="),
+                        left: test,
+                        right: box quote_expr!(span, "null"),
+                    }),
+                    cons: left,
+                    alt: right,
+                })
+                .wrap_with_paren()
+            }
+            _ => e,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        NullishCoalescing::default(),
+        simple,
+        "a ?? b;",
+        "(a != null ? a : b);"
+    );
+
+    test!(
+        NullishCoalescing::default(),
+        complex_left,
+        "foo() ?? bar;",
+        "var _ref;
+((_ref = foo()) != null ? _ref : bar);"
+    );
+
+    test!(
+        NullishCoalescing::default(),
+        chained,
+        "a ?? b() ?? c;",
+        "var _ref;
+((_ref = (a != null ? a : b())) != null ? _ref : c);"
+    );
+
+    test_exec!(
+        NullishCoalescing::default(),
+        exec,
+        r#"var calls = 0;
+function foo() { calls++; return 0; }
+assert.strictEqual(foo() ?? 1, 0);
+assert.strictEqual(calls, 1);
+assert.strictEqual(null ?? 1, 1);
+assert.strictEqual(undefined ?? false, false);
+assert.strictEqual("" ?? 1, "");"#
+    );
+}
@@ -0,0 +1,377 @@
+use crate::compat::{alias_ident, assign, fold_stmts, is_simple};
+use ast::*;
+use crate::util::{ExprFactory, StmtLike};
+use std::mem;
+use swc_common::{Fold, FoldWith, Span, Spanned, DUMMY_SP};
+
+/// `@babel/plugin-proposal-optional-chaining`
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// a?.b.c;
+/// foo.bar?.(baz);
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// (a == null ? void 0 : a.b.c);
+/// var _obj;
+/// ((_obj = foo.bar) == null ? void 0 : _obj.call(foo, baz));
+/// ```
+#[derive(Debug, Default)]
+pub struct OptChaining {
+    /// Number of temporary variables declared so far.
+    count: usize,
+    /// Temporary variables used by the current statement.
+    vars: Vec<VarDeclarator>,
+}
+
+impl<T: StmtLike> Fold<Vec<T>> for OptChaining
+where
+    Self: Fold<T>,
+{
+    fn fold(&mut self, stmts: Vec<T>) -> Vec<T> {
+        fold_stmts(self, |f| &mut f.vars, stmts)
+    }
+}
+
+impl Fold<Expr> for OptChaining {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let e = match e {
+            // `delete a?.b` is true if `a` is nullish.
+            Expr::Unary(UnaryExpr {
+                span,
+                op: op!("delete"),
+                arg,
+            }) => {
+                if is_opt_chain(&arg) {
+                    let (test, arg) = self.lower(*arg);
+                    cond(
+                        span,
+                        test,
+                        box Expr::Lit(Lit::Bool(Bool { span, value: true })),
+                        box Expr::Unary(UnaryExpr {
+                            span,
+                            op: op!("delete"),
+                            arg: box arg,
+                        }),
+                    )
+                } else {
+                    Expr::Unary(UnaryExpr {
+                        span,
+                        op: op!("delete"),
+                        arg,
+                    })
+                }
+            }
+            _ if is_opt_chain(&e) => {
+                let span = e.span();
+                let (test, expr) = self.lower(e);
+                cond(span, test, void_zero(span), box expr)
+            }
+            _ => e,
+        };
+
+        // Chains which come after the lowered `?.` are handled here.
+        e.fold_children(self)
+    }
+}
+
+impl OptChaining {
+    fn alias(&mut self, span: Span) -> Ident {
+        self.count += 1;
+        alias_ident(&mut self.vars, span, "_obj", self.count - 1)
+    }
+
+    /// Returns `obj == null` and an expression which can be used instead of
+    /// `obj` once the test is done.
+    fn nullish_test(&mut self, obj: Box<Expr>) -> (Box<Expr>, Box<Expr>) {
+        let span = obj.span();
+        let (checked, obj) = if is_simple(&obj) {
+            (obj.clone(), obj)
+        } else {
+            let i = self.alias(span);
+            (
+                box assign(span, i.clone(), obj).wrap_with_paren(),
+                box Expr::Ident(i),
+            )
+        };
+
+        let test = box Expr::Bin(BinExpr {
+            span,
+            op: op!("=="),
+            left: checked,
+            right: box quote_expr!(span, "null"),
+        });
+        (test, obj)
+    }
+
+    /// Replaces the `?.` of `e` which is evaluated first with `.`, and returns
+    /// the nullish test which should be done before evaluating `e`.
+    fn lower(&mut self, mut e: Expr) -> (Box<Expr>, Expr) {
+        let test = {
+            let link = innermost(&mut e);
+            let placeholder = quote_expr!(DUMMY_SP, "null");
+            let expr = match mem::replace(link, placeholder) {
+                Expr::OptChain(OptChainExpr { expr, .. }) => *expr,
+                _ => unreachable!("innermost() should return an optional chain"),
+            };
+
+            let (test, expr) = match expr {
+                Expr::Member(MemberExpr {
+                    span,
+                    obj: ExprOrSuper::Expr(obj),
+                    prop,
+                    computed,
+                }) => {
+                    let (test, obj) = self.nullish_test(obj);
+                    let expr = Expr::Member(MemberExpr {
+                        span,
+                        obj: ExprOrSuper::Expr(obj),
+                        prop,
+                        computed,
+                    });
+                    (test, expr)
+                }
+
+                // `a.b?.()` calls `a.b` with `a` as `this`.
+                Expr::Call(CallExpr {
+                    span,
+                    callee:
+                        ExprOrSuper::Expr(box Expr::Member(MemberExpr {
+                            span: member_span,
+                            obj,
+                            prop,
+                            computed,
+                        })),
+                    args,
+                }) => {
+                    let (this, obj) = match obj {
+                        ExprOrSuper::Expr(obj) => {
+                            if is_simple(&obj) {
+                                (obj.clone(), ExprOrSuper::Expr(obj))
+                            } else {
+                                let span = obj.span();
+                                let i = self.alias(span);
+                                let obj = assign(span, i.clone(), obj).wrap_with_paren();
+                                (box Expr::Ident(i), ExprOrSuper::Expr(box obj))
+                            }
+                        }
+                        ExprOrSuper::Super(span) => (
+                            box Expr::This(ThisExpr { span }),
+                            ExprOrSuper::Super(span),
+                        ),
+                        ExprOrSuper::Import(..) => unreachable!("`import.a?.()` is invalid"),
+                    };
+                    let callee = box Expr::Member(MemberExpr {
+                        span: member_span,
+                        obj,
+                        prop,
+                        computed,
+                    });
+
+                    let (test, callee) = self.nullish_test(callee);
+                    let call = Expr::Member(MemberExpr {
+                        span,
+                        obj: ExprOrSuper::Expr(callee),
+                        prop: box Expr::Ident(quote_ident!(span, "call")),
+                        computed: false,
+                    });
+                    let expr = Expr::Call(CallExpr {
+                        span,
+                        callee: call.as_callee(),
+                        args: iter_args(this, args),
+                    });
+                    (test, expr)
+                }
+
+                Expr::Call(CallExpr {
+                    span,
+                    callee: ExprOrSuper::Expr(callee),
+                    args,
+                }) => {
+                    let (test, callee) = self.nullish_test(callee);
+                    let expr = Expr::Call(CallExpr {
+                        span,
+                        callee: ExprOrSuper::Expr(callee),
+                        args,
+                    });
+                    (test, expr)
+                }
+
+                _ => unreachable!("optional chain of {:?}", expr),
+            };
+
+            *link = expr;
+            test
+        };
+
+        (test, e)
+    }
+}
+
+/// Returns `this, ...args`
+fn iter_args(this: Box<Expr>, args: Vec<ExprOrSpread>) -> Vec<ExprOrSpread> {
+    let mut buf = Vec::with_capacity(args.len() + 1);
+    buf.push(ExprOrSpread {
+        spread: None,
+        expr: this,
+    });
+    buf.extend(args);
+    buf
+}
+
+fn void_zero(span: Span) -> Box<Expr> {
+    box Expr::Unary(UnaryExpr {
+        span,
+        op: op!("void"),
+        arg: box Expr::Lit(Lit::Num(Number {
+            span,
+            value: 0.0,
+            raw: None,
+        })),
+    })
+}
+
+fn cond(span: Span, test: Box<Expr>, cons: Box<Expr>, alt: Box<Expr>) -> Expr {
+    Expr::Cond(CondExpr {
+        span,
+        test,
+        cons,
+        alt,
+    })
+    .wrap_with_paren()
+}
+
+/// Returns the object of `e` if `e` is a member, a call or an optional
+/// chain of them.
+fn obj_of(e: &Expr) -> Option<&Expr> {
+    match *e {
+        Expr::Member(MemberExpr {
+            obj: ExprOrSuper::Expr(ref obj),
+            ..
+        })
+        | Expr::Call(CallExpr {
+            callee: ExprOrSuper::Expr(ref obj),
+            ..
+        }) => Some(obj),
+        Expr::OptChain(OptChainExpr { ref expr, .. }) => obj_of(expr),
+        _ => None,
+    }
+}
+
+fn obj_of_mut(e: &mut Expr) -> Option<&mut Expr> {
+    match *e {
+        Expr::Member(MemberExpr {
+            obj: ExprOrSuper::Expr(ref mut obj),
+            ..
+        })
+        | Expr::Call(CallExpr {
+            callee: ExprOrSuper::Expr(ref mut obj),
+            ..
+        }) => Some(obj),
+        Expr::OptChain(OptChainExpr { ref mut expr, .. }) => obj_of_mut(expr),
+        _ => None,
+    }
+}
+
+/// Returns true if `e` is `a?.b` or a subscript of it, like `a?.b.c`.
+fn is_opt_chain(e: &Expr) -> bool {
+    match *e {
+        Expr::OptChain(..) => true,
+        _ => obj_of(e).map(is_opt_chain).unwrap_or(false),
+    }
+}
+
+/// Returns the optional chain in `e` which is evaluated first.
+fn innermost(e: &mut Expr) -> &mut Expr {
+    if obj_of(e).map(is_opt_chain).unwrap_or(false) {
+        return innermost(obj_of_mut(e).unwrap());
+    }
+    debug_assert!(is_opt_chain(e), "innermost() called with {:?}", e);
+    e
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        OptChaining::default(),
+        member,
+        "a?.b.c;",
+        "(a == null ? void 0 : a.b.c);"
+    );
+
+    test!(
+        OptChaining::default(),
+        computed,
+        "foo()?.[bar];",
+        "var _obj;
+((_obj = foo()) == null ? void 0 : _obj[bar]);"
+    );
+
+    test!(
+        OptChaining::default(),
+        call,
+        "a?.(b); a.b?.(c); foo().bar?.();",
+        "(a == null ? void 0 : a(b));
+var _obj;
+((_obj = a.b) == null ? void 0 : _obj.call(a, c));
+var _obj1, _obj2;
+((_obj2 = (_obj1 = foo()).bar) == null ? void 0 : _obj2.call(_obj1));"
+    );
+
+    test!(
+        OptChaining::default(),
+        nested,
+        "a?.b?.c;",
+        "var _obj;
+(a == null ? void 0 : ((_obj = a.b) == null ? void 0 : _obj.c));"
+    );
+
+    test!(
+        OptChaining::default(),
+        paren_ends_chain,
+        "(a?.b).c;",
+        "((a == null ? void 0 : a.b)).c;"
+    );
+
+    test!(
+        OptChaining::default(),
+        delete,
+        "delete a?.b;",
+        "(a == null ? true : delete a.b);"
+    );
+
+    test!(
+        OptChaining::default(),
+        in_function,
+        "function foo() { return bar()?.baz; }",
+        "function foo() {
+    var _obj;
+    return ((_obj = bar()) == null ? void 0 : _obj.baz);
+}"
+    );
+
+    test_exec!(
+        OptChaining::default(),
+        exec,
+        r#"var calls = 0;
+var obj = {
+  a: { b: function () { return this; } },
+  get c() { calls++; return null; }
+};
+assert.strictEqual(obj.a?.b(), obj.a);
+assert.strictEqual(obj.a.b?.(), obj.a);
+assert.strictEqual(obj.c?.d.e.f, undefined);
+assert.strictEqual(calls, 1);
+assert.strictEqual(obj.x?.y, undefined);
+assert.strictEqual(null?.[0], undefined);
+assert.strictEqual(delete obj.c?.d, true);"#
+    );
+}
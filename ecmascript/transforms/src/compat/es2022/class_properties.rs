@@ -0,0 +1,532 @@
+use crate::compat::{alias_ident, assign, es2015::classes::props, fold_stmts, helpers::Helpers};
+use ast::*;
+use crate::util::{ExprFactory, StmtLike};
+use std::{iter, mem, sync::Arc};
+use swc_common::{Fold, FoldWith, Spanned, DUMMY_SP};
+
+/// `@babel/plugin-proposal-class-properties`, with private methods and static
+/// blocks.
+///
+/// Unlike [Classes](crate::compat::es2015::Classes), classes are kept and
+/// only members other than methods are lowered.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// class Foo extends Bar {
+///   #a = 1;
+///   static b = 2;
+/// }
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// var _a = new WeakMap();
+/// class Foo extends Bar {
+///   constructor(...args) {
+///     super(...args);
+///     _a.set(this, { writable: true, value: 1 });
+///   }
+/// }
+/// _defineProperty(Foo, "b", 2);
+/// ```
+pub fn class_properties(helpers: &Arc<Helpers>) -> impl Fold<Module> {
+    ClassProperties {
+        helpers: helpers.clone(),
+        count: 0,
+        vars: vec![],
+    }
+}
+
+struct ClassProperties {
+    helpers: Arc<Helpers>,
+    /// Number of class expressions lowered so far.
+    count: usize,
+    /// Temporary variables used by the current statement.
+    vars: Vec<VarDeclarator>,
+}
+
+impl Fold<Vec<Stmt>> for ClassProperties {
+    fn fold(&mut self, stmts: Vec<Stmt>) -> Vec<Stmt> {
+        let stmts = fold_stmts(self, |f| &mut f.vars, stmts);
+
+        let mut buf = Vec::with_capacity(stmts.len());
+        for stmt in stmts {
+            self.push_stmt(&mut buf, stmt);
+        }
+        buf
+    }
+}
+
+impl Fold<Vec<ModuleItem>> for ClassProperties {
+    fn fold(&mut self, items: Vec<ModuleItem>) -> Vec<ModuleItem> {
+        let items = fold_stmts(self, |f| &mut f.vars, items);
+
+        let mut buf = Vec::with_capacity(items.len());
+        for item in items {
+            match item {
+                ModuleItem::Stmt(stmt) => self.push_stmt(&mut buf, stmt),
+
+                // `export class Foo {}`
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                    span,
+                    decl:
+                        Decl::Class(ClassDecl {
+                            ident,
+                            declare,
+                            class,
+                        }),
+                })) => {
+                    let name = ident.clone();
+                    self.push_decl(&mut buf, &name, class, |class| {
+                        ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                            span,
+                            decl: Decl::Class(ClassDecl {
+                                ident,
+                                declare,
+                                class,
+                            }),
+                        }))
+                    })
+                }
+
+                // `export default class {}`
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                    span,
+                    decl: DefaultDecl::Class(ClassExpr { ident, class }),
+                })) => {
+                    // Static initializers refer to the class by its name.
+                    let name = ident.clone().unwrap_or_else(|| quote_ident!("_Class"));
+                    let ident = if has_props(&class) {
+                        Some(name.clone())
+                    } else {
+                        ident
+                    };
+                    self.push_decl(&mut buf, &name, class, |class| {
+                        ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                            span,
+                            decl: DefaultDecl::Class(ClassExpr { ident, class }),
+                        }))
+                    })
+                }
+
+                _ => buf.push(item),
+            }
+        }
+        buf
+    }
+}
+
+impl Fold<Expr> for ClassProperties {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children(self);
+
+        let class = match e {
+            Expr::Class(class) => class,
+            _ => return e,
+        };
+        if !has_props(&class.class) {
+            return Expr::Class(class);
+        }
+
+        // The name of a class expression is only visible in the class, so
+        // static initializers use a temporary variable.
+        let span = class.class.span;
+        self.count += 1;
+        let tmp = alias_ident(&mut self.vars, span, "_Class", self.count - 1);
+        let (lowered, vars, statics) = self.lower(&tmp, class.class);
+
+        // `(_a = new WeakMap(), _Class = class {}, _defineProperty(_Class, "b", 2), _Class)`
+        let mut exprs = vec![];
+        for VarDeclarator { span, name, init } in vars {
+            if let (&Pat::Ident(ref i), Some(init)) = (&name, init) {
+                exprs.push(box assign(span, i.clone(), init));
+            }
+            self.vars.push(VarDeclarator {
+                span,
+                name,
+                init: None,
+            });
+        }
+        let class = box Expr::Class(ClassExpr {
+            ident: class.ident,
+            class: lowered,
+        });
+        exprs.push(box assign(span, tmp.clone(), class));
+        exprs.extend(statics.into_iter().map(stmt_expr));
+        exprs.push(box Expr::Ident(tmp));
+
+        Expr::Seq(SeqExpr { span, exprs }).wrap_with_paren()
+    }
+}
+
+impl ClassProperties {
+    fn push_stmt<T: StmtLike>(&self, buf: &mut Vec<T>, stmt: Stmt) {
+        match stmt {
+            Stmt::Decl(Decl::Class(ClassDecl {
+                ident,
+                declare,
+                class,
+            })) => {
+                let name = ident.clone();
+                self.push_decl(buf, &name, class, |class| {
+                    T::from_stmt(Stmt::Decl(Decl::Class(ClassDecl {
+                        ident,
+                        declare,
+                        class,
+                    })))
+                })
+            }
+            _ => buf.push(T::from_stmt(stmt)),
+        }
+    }
+
+    /// Pushes the declaration of a class named `name`, preceded by variables
+    /// and followed by static initializers of the class.
+    fn push_decl<T: StmtLike>(
+        &self,
+        buf: &mut Vec<T>,
+        name: &Ident,
+        class: Class,
+        decl: impl FnOnce(Class) -> T,
+    ) {
+        if !has_props(&class) {
+            buf.push(decl(class));
+            return;
+        }
+
+        let (class, vars, statics) = self.lower(name, class);
+        if !vars.is_empty() {
+            buf.push(T::from_stmt(Stmt::Decl(Decl::Var(VarDecl {
+                span: DUMMY_SP,
+                kind: VarDeclKind::Var,
+                declare: false,
+                decls: vars,
+            }))));
+        }
+        buf.push(decl(class));
+        buf.extend(statics.into_iter().map(T::from_stmt));
+    }
+
+    /// Lowers members of `class` other than methods.
+    ///
+    /// Returns the class, variables to declare before it and statements to run
+    /// after it.
+    fn lower(&self, name: &Ident, class: Class) -> (Class, Vec<VarDeclarator>, Vec<Stmt>) {
+        let (mut body, props) = props::lower(&self.helpers, name, class.body);
+        if !props.instance.is_empty() {
+            init_in_constructor(&mut body, class.super_class.is_some(), props.instance);
+        }
+
+        (Class { body, ..class }, props.vars, props.statics)
+    }
+}
+
+/// Returns true if `class` has members other than methods.
+fn has_props(class: &Class) -> bool {
+    class.body.iter().any(|m| match *m {
+        ClassMember::Method(..) => false,
+        _ => true,
+    })
+}
+
+/// Runs `inits` when an instance is created, after `super()` for derived
+/// classes.
+fn init_in_constructor(body: &mut Vec<ClassMember>, is_derived: bool, inits: Vec<Stmt>) {
+    let pos = body.iter().position(|m| match *m {
+        ClassMember::Method(ClassMethod {
+            kind: ClassMethodKind::Constructor,
+            function: Function { body: Some(..), .. },
+            ..
+        }) => true,
+        _ => false,
+    });
+    let pos = match pos {
+        Some(pos) => pos,
+        None => {
+            body.insert(0, default_constructor(is_derived));
+            0
+        }
+    };
+    let stmts = match body[pos] {
+        ClassMember::Method(ClassMethod {
+            function:
+                Function {
+                    body: Some(ref mut block),
+                    ..
+                },
+            ..
+        }) => &mut block.stmts,
+        _ => unreachable!(),
+    };
+
+    if !is_derived {
+        stmts.splice(0..0, inits);
+        return;
+    }
+
+    let super_call = stmts.iter().position(|stmt| match *stmt {
+        Stmt::Expr(ExprStmt {
+            expr:
+                box Expr::Call(CallExpr {
+                    callee: ExprOrSuper::Super(..),
+                    ..
+                }),
+            ..
+        }) => true,
+        _ => false,
+    });
+    match super_call {
+        Some(pos) => {
+            let rest = stmts.split_off(pos + 1);
+            stmts.extend(inits);
+            stmts.extend(rest);
+        }
+        None => {
+            let inits = inits.into_iter().map(stmt_expr).collect();
+            *stmts = mem::replace(stmts, vec![]).fold_with(&mut InitAfterSuper { inits });
+        }
+    }
+}
+
+/// `constructor() {}`, or `constructor(...args) { super(...args); }` for
+/// derived classes.
+fn default_constructor(is_derived: bool) -> ClassMember {
+    let (params, stmts) = if is_derived {
+        let args = quote_ident!("args");
+        let param = Param {
+            span: DUMMY_SP,
+            decorators: vec![],
+            pat: Pat::Rest(RestPat {
+                dot3_token: DUMMY_SP,
+                arg: box Pat::Ident(args.clone()),
+            }),
+        };
+        let super_call = Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            callee: ExprOrSuper::Super(DUMMY_SP),
+            args: vec![ExprOrSpread {
+                spread: Some(DUMMY_SP),
+                expr: box Expr::Ident(args),
+            }],
+        })
+        .into_stmt();
+        (vec![param], vec![super_call])
+    } else {
+        (vec![], vec![])
+    };
+
+    ClassMember::Method(ClassMethod {
+        span: DUMMY_SP,
+        key: PropName::Ident(quote_ident!("constructor")),
+        function: Function {
+            params,
+            span: DUMMY_SP,
+            body: Some(BlockStmt {
+                span: DUMMY_SP,
+                stmts,
+            }),
+            generator_token: None,
+            async_token: None,
+            type_params: None,
+            return_type: None,
+        },
+        kind: ClassMethodKind::Constructor,
+        static_token: None,
+        decorators: vec![],
+    })
+}
+
+/// Initializers lowered by `props::lower` are expression statements.
+fn stmt_expr(stmt: Stmt) -> Box<Expr> {
+    match stmt {
+        Stmt::Expr(ExprStmt { expr, .. }) => expr,
+        _ => unreachable!("{:?} is not an expression statement", stmt),
+    }
+}
+
+/// Initializes an instance right after `super()` calls which are not
+/// statements of the constructor.
+///
+/// `super()` becomes `(super(), _defineProperty(this, "a", 1), this)`.
+struct InitAfterSuper {
+    inits: Vec<Box<Expr>>,
+}
+
+impl Fold<Expr> for InitAfterSuper {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children(self);
+
+        match e {
+            Expr::Call(CallExpr {
+                callee: ExprOrSuper::Super(..),
+                ..
+            }) => {
+                let span = e.span();
+                let exprs = iter::once(box e)
+                    .chain(self.inits.iter().cloned())
+                    .chain(iter::once(box Expr::This(ThisExpr { span })))
+                    .collect();
+                Expr::Seq(SeqExpr { span, exprs })
+            }
+            _ => e,
+        }
+    }
+}
+
+/// `super()` in a nested class calls its own super class.
+impl Fold<Function> for InitAfterSuper {
+    fn fold(&mut self, f: Function) -> Function {
+        f
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hygiene;
+
+    fn tr() -> impl Fold<Module> {
+        class_properties(&Default::default())
+    }
+
+    test!(
+        tr(),
+        fields,
+        r#"class Foo {
+  a = 1;
+  static b = this;
+  ['c' + 1] = 2;
+  d;
+  static {
+    this.e = 3;
+  }
+}"#,
+        r#"var _key0 = 'c' + 1;
+class Foo {
+  constructor() {
+    _defineProperty(this, "a", 1);
+    _defineProperty(this, _key0, 2);
+    _defineProperty(this, "d", void 0);
+  }
+}
+_defineProperty(Foo, "b", Foo);
+(function () {
+  this.e = 3;
+}).call(Foo);"#
+    );
+
+    test!(
+        tr(),
+        private_names,
+        r#"class Foo {
+  #a = 1;
+  #b() {
+    return this.#a;
+  }
+  inc(o) {
+    o.#a += 1;
+    return #a in o;
+  }
+}"#,
+        r#"var _a = new WeakMap(), _b = new WeakMap(), _ref2, _desc3 = {
+  value: function () {
+    return _classPrivateFieldGet(this, _a);
+  }
+};
+class Foo {
+  constructor() {
+    _b.set(this, _desc3);
+    _a.set(this, { writable: true, value: 1 });
+  }
+  inc(o) {
+    _classPrivateFieldSet(_ref2 = o, _a, _classPrivateFieldGet(_ref2, _a) + 1);
+    return _a.has(o);
+  }
+}"#
+    );
+
+    test!(
+        tr(),
+        derived,
+        r#"class A extends B {
+  a = 1;
+}
+class C extends B {
+  c = 2;
+  constructor(c) {
+    foo();
+    super(c);
+    bar();
+  }
+}
+class D extends B {
+  d = 3;
+  constructor() {
+    if (x) super(1);
+    else super(2);
+  }
+}"#,
+        r#"class A extends B {
+  constructor(...args) {
+    super(...args);
+    _defineProperty(this, "a", 1);
+  }
+}
+class C extends B {
+  constructor(c) {
+    foo();
+    super(c);
+    _defineProperty(this, "c", 2);
+    bar();
+  }
+}
+class D extends B {
+  constructor() {
+    if (x) super(1), _defineProperty(this, "d", 3), this;
+    else super(2), _defineProperty(this, "d", 3), this;
+  }
+}"#
+    );
+
+    test!(
+        tr(),
+        class_expr,
+        "var Foo = class { static a = 1; #b; };",
+        r#"var _Class, _b;
+var Foo = (_b = new WeakMap(), _Class = class {
+  constructor() {
+    _b.set(this, { writable: true, value: void 0 });
+  }
+}, _defineProperty(_Class, "a", 1), _Class);"#
+    );
+
+    test!(
+        tr(),
+        export_default,
+        "export default class { static a = 1; }",
+        r#"export default class _Class {}
+_defineProperty(_Class, "a", 1);"#
+    );
+
+    test!(
+        tr().then(hygiene()),
+        private_names_of_classes,
+        "class A { #x = 1; } class B { #x = 2; }",
+        r#"var _x = new WeakMap();
+class A {
+  constructor() {
+    _x.set(this, { writable: true, value: 1 });
+  }
+}
+var _x1 = new WeakMap();
+class B {
+  constructor() {
+    _x1.set(this, { writable: true, value: 2 });
+  }
+}"#
+    );
+}
@@ -0,0 +1,12 @@
+pub use self::class_properties::class_properties;
+use super::helpers::Helpers;
+use ast::*;
+use std::sync::Arc;
+use swc_common::Fold;
+
+mod class_properties;
+
+/// Compiles es2022 to es2021.
+pub fn es2022(helpers: &Arc<Helpers>) -> impl Fold<Module> {
+    class_properties(helpers)
+}
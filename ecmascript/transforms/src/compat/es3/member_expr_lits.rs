@@ -37,6 +37,7 @@ impl Fold<MemberExpr> for MemberExprLit {
                             span: mark!(span),
                             value: sym,
                             has_escape: false,
+                            raw: None,
                         })),
                         ..e
                     };
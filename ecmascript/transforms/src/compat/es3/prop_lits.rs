@@ -45,6 +45,7 @@ impl Fold<PropName> for PropertyLiteral {
                         span: mark!(span),
                         value: sym,
                         has_escape: false,
+                        raw: None,
                     });
                 } else {
                     PropName::Ident(Ident {
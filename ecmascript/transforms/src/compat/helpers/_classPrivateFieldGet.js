@@ -0,0 +1 @@
+function _classPrivateFieldGet(receiver, privateMap) { var descriptor = privateMap.get(receiver); if (!descriptor) { throw new TypeError("attempted to get private field on non-instance"); } if (descriptor.get) { return descriptor.get.call(receiver); } if (!("value" in descriptor)) { throw new TypeError("attempted to get private field which has no getter"); } return descriptor.value; }
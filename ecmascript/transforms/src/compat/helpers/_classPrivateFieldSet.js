@@ -0,0 +1 @@
+function _classPrivateFieldSet(receiver, privateMap, value) { var descriptor = privateMap.get(receiver); if (!descriptor) { throw new TypeError("attempted to set private field on non-instance"); } if (descriptor.set) { descriptor.set.call(receiver, value); } else if (!descriptor.writable) { throw new TypeError("attempted to set read only private field"); } else { descriptor.value = value; } return value; }
@@ -0,0 +1,12 @@
+function _exportStar(from, to) {
+    Object.keys(from).forEach(function (key) {
+        if (key === "default" || key === "__esModule") return;
+        if (Object.prototype.hasOwnProperty.call(to, key)) return;
+        Object.defineProperty(to, key, {
+            enumerable: true,
+            get: function () {
+                return from[key];
+            }
+        });
+    });
+}
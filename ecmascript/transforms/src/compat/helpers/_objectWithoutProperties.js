@@ -0,0 +1,21 @@
+function _objectWithoutProperties(source, excluded) {
+    if (source == null) return {};
+    var target = {};
+    var keys = Object.keys(source);
+    var key, i;
+    for (i = 0; i < keys.length; i++) {
+        key = keys[i];
+        if (excluded.indexOf(key) >= 0) continue;
+        target[key] = source[key];
+    }
+    if (Object.getOwnPropertySymbols) {
+        var symbols = Object.getOwnPropertySymbols(source);
+        for (i = 0; i < symbols.length; i++) {
+            key = symbols[i];
+            if (excluded.indexOf(key) >= 0) continue;
+            if (!Object.prototype.propertyIsEnumerable.call(source, key)) continue;
+            target[key] = source[key];
+        }
+    }
+    return target;
+}
@@ -0,0 +1,352 @@
+var _regeneratorRuntime = (function () {
+    var hasOwn = Object.prototype.hasOwnProperty;
+    // Returned by the compiled function to continue running it.
+    var ContinueSentinel = {};
+
+    function tryCatch(fn, obj, arg) {
+        try {
+            return { type: "normal", arg: fn.call(obj, arg) };
+        } catch (err) {
+            return { type: "throw", arg: err };
+        }
+    }
+
+    function wrap(innerFn, self, tryLocsList) {
+        var context = new Context(tryLocsList || []);
+        var state = "suspendedStart";
+
+        function invoke(method, arg) {
+            if (state === "executing") {
+                throw new Error("Generator is already running");
+            }
+            if (state === "completed") {
+                if (method === "throw") {
+                    throw arg;
+                }
+                return { value: method === "return" ? arg : undefined, done: true };
+            }
+
+            context.method = method;
+            context.arg = arg;
+
+            while (true) {
+                var delegate = context.delegate;
+                if (delegate) {
+                    var delegateResult = maybeInvokeDelegate(delegate, context);
+                    if (delegateResult) {
+                        if (delegateResult === ContinueSentinel) continue;
+                        return delegateResult;
+                    }
+                }
+
+                if (context.method === "next") {
+                    context.sent = context.arg;
+                } else if (context.method === "throw") {
+                    if (state === "suspendedStart") {
+                        state = "completed";
+                        throw context.arg;
+                    }
+                    context.dispatchException(context.arg);
+                } else if (context.method === "return") {
+                    context.abrupt("return", context.arg);
+                }
+
+                state = "executing";
+                var record = tryCatch(innerFn, self, context);
+                if (record.type === "normal") {
+                    state = context.done ? "completed" : "suspendedYield";
+                    if (record.arg === ContinueSentinel) continue;
+                    return { value: record.arg, done: context.done };
+                }
+                // The exception is dispatched by the next iteration.
+                state = "completed";
+                context.method = "throw";
+                context.arg = record.arg;
+            }
+        }
+
+        var generator = {
+            next: function (arg) {
+                return invoke("next", arg);
+            },
+            "throw": function (arg) {
+                return invoke("throw", arg);
+            },
+            "return": function (arg) {
+                return invoke("return", arg);
+            }
+        };
+        if (typeof Symbol === "function" && Symbol.iterator) {
+            generator[Symbol.iterator] = function () {
+                return this;
+            };
+        }
+        return generator;
+    }
+
+    // Calls the iterator of `yield*`, and returns the result which should be
+    // yielded, or ContinueSentinel.
+    function maybeInvokeDelegate(delegate, context) {
+        var method = delegate.iterator[context.method];
+        if (method === undefined) {
+            context.delegate = null;
+            if (context.method === "throw") {
+                if (delegate.iterator["return"]) {
+                    context.method = "return";
+                    context.arg = undefined;
+                    maybeInvokeDelegate(delegate, context);
+                    if (context.method === "throw") return ContinueSentinel;
+                }
+                context.method = "throw";
+                context.arg = new TypeError("The iterator does not provide a 'throw' method");
+            }
+            return ContinueSentinel;
+        }
+
+        var record = tryCatch(method, delegate.iterator, context.arg);
+        if (record.type === "throw") {
+            context.method = "throw";
+            context.arg = record.arg;
+            context.delegate = null;
+            return ContinueSentinel;
+        }
+
+        var info = record.arg;
+        if (!info) {
+            context.method = "throw";
+            context.arg = new TypeError("iterator result is not an object");
+            context.delegate = null;
+            return ContinueSentinel;
+        }
+        if (!info.done) {
+            return info;
+        }
+
+        context[delegate.resultName] = info.value;
+        context.next = delegate.nextLoc;
+        if (context.method !== "return") {
+            context.method = "next";
+            context.arg = undefined;
+        }
+        context.delegate = null;
+        return ContinueSentinel;
+    }
+
+    function pushTryEntry(locs) {
+        var entry = { tryLoc: locs[0] };
+        if (1 in locs) {
+            entry.catchLoc = locs[1];
+        }
+        if (2 in locs) {
+            entry.finallyLoc = locs[2];
+            entry.afterLoc = locs[3];
+        }
+        this.tryEntries.push(entry);
+    }
+
+    function resetTryEntry(entry) {
+        var record = entry.completion || {};
+        record.type = "normal";
+        delete record.arg;
+        entry.completion = record;
+    }
+
+    function Context(tryLocsList) {
+        this.tryEntries = [{ tryLoc: "root" }];
+        tryLocsList.forEach(pushTryEntry, this);
+        this.prev = 0;
+        this.next = 0;
+        this.sent = undefined;
+        this.done = false;
+        this.delegate = null;
+        this.method = "next";
+        this.arg = undefined;
+        this.tryEntries.forEach(resetTryEntry);
+    }
+
+    Context.prototype = {
+        stop: function () {
+            this.done = true;
+            var rootRecord = this.tryEntries[0].completion;
+            if (rootRecord.type === "throw") {
+                throw rootRecord.arg;
+            }
+            return this.rval;
+        },
+
+        dispatchException: function (exception) {
+            if (this.done) {
+                throw exception;
+            }
+            var context = this;
+            var record;
+            function handle(loc, caught) {
+                record.type = "throw";
+                record.arg = exception;
+                context.next = loc;
+                if (caught) {
+                    context.method = "next";
+                    context.arg = undefined;
+                }
+                return !!caught;
+            }
+
+            for (var i = this.tryEntries.length - 1; i >= 0; --i) {
+                var entry = this.tryEntries[i];
+                record = entry.completion;
+                if (entry.tryLoc === "root") {
+                    return handle("end");
+                }
+                if (entry.tryLoc <= this.prev) {
+                    var hasCatch = hasOwn.call(entry, "catchLoc");
+                    var hasFinally = hasOwn.call(entry, "finallyLoc");
+                    if (hasCatch && this.prev < entry.catchLoc) {
+                        return handle(entry.catchLoc, true);
+                    }
+                    if (hasFinally && this.prev < entry.finallyLoc) {
+                        return handle(entry.finallyLoc);
+                    }
+                }
+            }
+        },
+
+        abrupt: function (type, arg) {
+            var finallyEntry = null;
+            for (var i = this.tryEntries.length - 1; i >= 0; --i) {
+                var entry = this.tryEntries[i];
+                if (
+                    entry.tryLoc <= this.prev &&
+                    hasOwn.call(entry, "finallyLoc") &&
+                    this.prev < entry.finallyLoc
+                ) {
+                    finallyEntry = entry;
+                    break;
+                }
+            }
+            // Jumps inside of the try statement don't run the finally block.
+            if (
+                finallyEntry &&
+                (type === "break" || type === "continue") &&
+                finallyEntry.tryLoc <= arg &&
+                arg <= finallyEntry.finallyLoc
+            ) {
+                finallyEntry = null;
+            }
+
+            var record = finallyEntry ? finallyEntry.completion : {};
+            record.type = type;
+            record.arg = arg;
+            if (finallyEntry) {
+                this.method = "next";
+                this.next = finallyEntry.finallyLoc;
+                return ContinueSentinel;
+            }
+            return this.complete(record);
+        },
+
+        complete: function (record, afterLoc) {
+            if (record.type === "throw") {
+                throw record.arg;
+            }
+            if (record.type === "break" || record.type === "continue") {
+                this.next = record.arg;
+            } else if (record.type === "return") {
+                this.rval = this.arg = record.arg;
+                this.method = "return";
+                this.next = "end";
+            } else if (record.type === "normal" && afterLoc) {
+                this.next = afterLoc;
+            }
+            return ContinueSentinel;
+        },
+
+        finish: function (finallyLoc) {
+            for (var i = this.tryEntries.length - 1; i >= 0; --i) {
+                var entry = this.tryEntries[i];
+                if (entry.finallyLoc === finallyLoc) {
+                    this.complete(entry.completion, entry.afterLoc);
+                    resetTryEntry(entry);
+                    return ContinueSentinel;
+                }
+            }
+        },
+
+        "catch": function (tryLoc) {
+            for (var i = this.tryEntries.length - 1; i >= 0; --i) {
+                var entry = this.tryEntries[i];
+                if (entry.tryLoc === tryLoc) {
+                    var record = entry.completion;
+                    var thrown;
+                    if (record.type === "throw") {
+                        thrown = record.arg;
+                        resetTryEntry(entry);
+                    }
+                    return thrown;
+                }
+            }
+            throw new Error("illegal catch attempt");
+        },
+
+        delegateYield: function (iterable, resultName, nextLoc) {
+            this.delegate = {
+                iterator: values(iterable),
+                resultName: resultName,
+                nextLoc: nextLoc
+            };
+            if (this.method === "next") {
+                this.arg = undefined;
+            }
+            return ContinueSentinel;
+        }
+    };
+
+    // Iterator of `for (a in obj)`.
+    function keys(object) {
+        var keys = [];
+        for (var key in object) {
+            keys.push(key);
+        }
+        keys.reverse();
+
+        return function next() {
+            while (keys.length) {
+                var key = keys.pop();
+                // Deleted properties are skipped.
+                if (key in object) {
+                    next.value = key;
+                    next.done = false;
+                    return next;
+                }
+            }
+            next.done = true;
+            return next;
+        };
+    }
+
+    // Iterator of `for (a of iterable)` and `yield* iterable`.
+    function values(iterable) {
+        if (iterable != null) {
+            var iteratorMethod = typeof Symbol === "function" && iterable[Symbol.iterator];
+            if (iteratorMethod) {
+                return iteratorMethod.call(iterable);
+            }
+            if (typeof iterable.next === "function") {
+                return iterable;
+            }
+            if (!isNaN(iterable.length)) {
+                var i = -1;
+                return {
+                    next: function () {
+                        if (++i < iterable.length) {
+                            return { value: iterable[i], done: false };
+                        }
+                        return { value: undefined, done: true };
+                    }
+                };
+            }
+        }
+        throw new TypeError(typeof iterable + " is not iterable");
+    }
+
+    return { wrap: wrap, keys: keys, values: values };
+})();
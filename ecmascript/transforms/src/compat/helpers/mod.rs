@@ -1,4 +1,5 @@
 use ast::*;
+use crate::util::ExprFactory;
 use std::sync::{
     atomic::{AtomicBool, Ordering},
     Arc,
@@ -6,9 +7,12 @@ use std::sync::{
 use swc_common::{
     errors::{ColorConfig, Handler},
     sync::Lrc,
-    FileName, Fold, SourceMap,
+    FileName, Fold, SourceMap, DUMMY_SP,
 };
-use swc_ecma_parser::{Parser, Session, SourceFileInput};
+use swc_ecma_parser::{Parser, Session, SourceFileInput, Syntax};
+
+#[cfg(test)]
+mod tests;
 
 /// Tracks used helper methods. (e.g. __extends)
 #[derive(Debug, Default)]
@@ -30,72 +34,181 @@ pub struct Helpers {
     pub instance_of: AtomicBool,
     /// _typeof
     pub type_of: AtomicBool,
+    /// _interopRequireDefault
+    pub interop_require_default: AtomicBool,
+    /// _interopRequireWildcard
+    pub interop_require_wildcard: AtomicBool,
+    /// _exportStar
+    pub export_star: AtomicBool,
+    /// _objectWithoutProperties
+    pub object_without_properties: AtomicBool,
+    /// _taggedTemplateLiteral
+    pub tagged_template_literal: AtomicBool,
+    /// _asyncToGenerator
+    pub async_to_generator: AtomicBool,
+    /// _regeneratorRuntime
+    pub regenerator: AtomicBool,
+    /// _defineProperty
+    pub define_property: AtomicBool,
+    /// _classPrivateFieldGet
+    pub class_private_field_get: AtomicBool,
+    /// _classPrivateFieldSet
+    pub class_private_field_set: AtomicBool,
 }
 
+/// Module which exports all helpers, used if [InjectHelpers::external] is
+/// true.
+pub const HELPERS_MODULE: &str = "@swc/helpers";
+
+impl Helpers {
+    /// Names and code of used helpers, in order of injection.
+    fn used(&self) -> Vec<(&'static str, &'static str)> {
+        let mut buf = vec![];
+        macro_rules! add {
+            ($name:tt, $b:expr) => {
+                if $b.load(Ordering::SeqCst) {
+                    buf.push(($name, include_str!(concat!($name, ".js"))));
+                }
+            };
+        }
+
+        add!("_extends", self.extends);
+        add!("_toConsumableArray", self.to_consumable_array);
+        add!("_classCallCheck", self.class_call_check);
+        add!("_inherits", self.inherits);
+        add!("_possibleConstructorReturn", self.possible_constructor_return);
+        add!("_createClass", self.create_class);
+        add!("_get", self.get);
+        add!("_instanceof", self.instance_of);
+        add!("_typeof", self.type_of);
+        add!("_interopRequireDefault", self.interop_require_default);
+        add!("_interopRequireWildcard", self.interop_require_wildcard);
+        add!("_exportStar", self.export_star);
+        add!("_objectWithoutProperties", self.object_without_properties);
+        add!("_taggedTemplateLiteral", self.tagged_template_literal);
+        add!("_asyncToGenerator", self.async_to_generator);
+        add!("_regeneratorRuntime", self.regenerator);
+        add!("_defineProperty", self.define_property);
+        add!("_classPrivateFieldGet", self.class_private_field_get);
+        add!("_classPrivateFieldSet", self.class_private_field_set);
+
+        buf
+    }
+}
+
+/// Prepends helpers marked as used by other passes to the module.
+///
+/// Each helper is injected once, however many times it's used.
 pub struct InjectHelpers {
     pub cm: Lrc<SourceMap>,
     pub helpers: Arc<Helpers>,
+    /// Import helpers from [HELPERS_MODULE] instead of inlining them.
+    ///
+    /// They are imported with `import` if the module still has imports or
+    /// exports, and with `require` otherwise.
+    pub external: bool,
 }
 
 impl InjectHelpers {
-    fn mk_helpers(&self) -> Vec<Stmt> {
+    fn mk_helpers(&self, used: &[(&str, &'static str)]) -> Vec<Stmt> {
         let mut buf = vec![];
 
         let handler =
             Handler::with_tty_emitter(ColorConfig::Always, false, false, Some(self.cm.clone()));
 
         let session = Session {
-            cfg: Default::default(),
             logger: &slog::Logger::root(slog::Discard, o!()),
             handler: &handler,
         };
 
-        let mut add = |name: &str, flag: &AtomicBool, code: &'static str| {
-            if flag.load(Ordering::SeqCst) {
-                return;
-            }
+        for &(name, code) in used {
             let fm = self
                 .cm
-                .new_source_file(FileName::Custom(name.into()), code.into());
+                .new_source_file(FileName::Custom(format!("{}.js", name)), code.into());
 
-            let mut stmts = Parser::new(session, SourceFileInput::from(&*fm))
+            let mut stmts = Parser::new(session, Syntax::default(), SourceFileInput::from(&*fm))
                 .parse_script()
-                .unwrap();
+                .unwrap()
+                .body;
 
             buf.append(&mut stmts);
-        };
-        macro_rules! add {
-            ($name:tt,$b:expr) => {
-                add($name, &self.helpers.extends, include_str!($name));
-            };
         }
 
-        add!("_extends.js", &self.helpers.extends);
-        add!("_toConsumableArray.js", &self.helpers.to_consumable_array);
-        add!("_classCallCheck.js", &self.helpers.class_call_check);
-        add!("_inherits.js", &self.helpers.inherits);
-        add!(
-            "_possibleConstructorReturn.js",
-            &self.helpers.possible_constructor_return
-        );
-        add!("_createClass.js", &self.helpers.create_class);
-        add!("_get.js", &self.helpers.get);
-        add!("_instanceof.js", &self.helpers.instance_of);
-        add!("_typeof.js", &self.helpers.type_of);
-
         buf
     }
 }
 
 impl Fold<Module> for InjectHelpers {
     fn fold(&mut self, module: Module) -> Module {
-        let body = self
-            .mk_helpers()
-            .into_iter()
-            .map(ModuleItem::Stmt)
-            .chain(module.body)
-            .collect();
+        let used = self.helpers.used();
+        if used.is_empty() {
+            return module;
+        }
+
+        let is_es_module = module.body.iter().any(|item| match *item {
+            ModuleItem::ModuleDecl(..) => true,
+            _ => false,
+        });
+        let helpers = if !self.external {
+            self.mk_helpers(&used)
+                .into_iter()
+                .map(ModuleItem::Stmt)
+                .collect()
+        } else if is_es_module {
+            vec![import_helpers(&used)]
+        } else {
+            used.iter()
+                .map(|&(name, _)| ModuleItem::Stmt(require_helper(name)))
+                .collect()
+        };
+
+        let body = helpers.into_iter().chain(module.body).collect();
 
         Module { body, ..module }
     }
 }
+
+/// `import { _extends, _typeof } from "@swc/helpers";`
+fn import_helpers(used: &[(&str, &str)]) -> ModuleItem {
+    let specifiers = used
+        .iter()
+        .map(|&(name, _)| {
+            ImportSpecifier::Specific(ImportSpecific {
+                span: DUMMY_SP,
+                local: quote_ident!(name),
+                imported: None,
+            })
+        })
+        .collect();
+
+    ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+        span: DUMMY_SP,
+        specifiers,
+        src: quote_str!(HELPERS_MODULE),
+    }))
+}
+
+/// `var _extends = require("@swc/helpers")._extends;`
+fn require_helper(name: &str) -> Stmt {
+    let require = Expr::Call(CallExpr {
+        span: DUMMY_SP,
+        callee: quote_ident!("require").as_callee(),
+        args: vec![Lit::Str(quote_str!(HELPERS_MODULE)).as_arg()],
+    });
+
+    Stmt::Decl(Decl::Var(VarDecl {
+        span: DUMMY_SP,
+        kind: VarDeclKind::Var,
+        declare: false,
+        decls: vec![VarDeclarator {
+            span: DUMMY_SP,
+            name: Pat::Ident(quote_ident!(name)),
+            init: Some(box Expr::Member(MemberExpr {
+                span: DUMMY_SP,
+                obj: ExprOrSuper::Expr(box require),
+                prop: box Expr::Ident(quote_ident!(name)),
+                computed: false,
+            })),
+        }],
+    }))
+}
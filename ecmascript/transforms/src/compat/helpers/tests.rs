@@ -0,0 +1,50 @@
+use super::*;
+use swc_common::FilePathMapping;
+
+/// Injects helpers marked by `mark`.
+fn tr(external: bool, mark: fn(&Helpers)) -> InjectHelpers {
+    let helpers = Helpers::default();
+    mark(&helpers);
+
+    InjectHelpers {
+        cm: Lrc::new(SourceMap::new(FilePathMapping::empty())),
+        helpers: Arc::new(helpers),
+        external,
+    }
+}
+
+fn extends_and_class_call_check(helpers: &Helpers) {
+    helpers.extends.store(true, Ordering::SeqCst);
+    helpers.class_call_check.store(true, Ordering::SeqCst);
+}
+
+test!(tr(false, |_| {}), unused, "foo();", "foo();");
+
+test!(
+    tr(false, |helpers| helpers.class_call_check.store(true, Ordering::SeqCst)),
+    inline,
+    "foo();",
+    r#"function _classCallCheck(instance, Constructor) {
+    if (!(instance instanceof Constructor)) {
+        throw new TypeError("Cannot call a class as a function");
+    }
+}
+foo();"#
+);
+
+test!(
+    tr(true, extends_and_class_call_check),
+    external_require,
+    "foo();",
+    r#"var _extends = require("@swc/helpers")._extends;
+var _classCallCheck = require("@swc/helpers")._classCallCheck;
+foo();"#
+);
+
+test!(
+    tr(true, extends_and_class_call_check),
+    external_import,
+    "export default foo;",
+    r#"import { _extends, _classCallCheck } from "@swc/helpers";
+export default foo;"#
+);
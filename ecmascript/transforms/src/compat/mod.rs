@@ -1,8 +1,111 @@
 //! New-generation javascript to old-javascript compiler.
 
-pub use self::{es2015::es2015, es2016::es2016, es3::es3};
+pub use self::{
+    es2015::es2015, es2016::es2016, es2017::es2017, es2020::es2020, es2022::es2022, es3::es3,
+    preset_env::preset_env,
+};
 
 pub mod es2015;
 pub mod es2016;
+pub mod es2017;
+pub mod es2020;
+pub mod es2022;
 pub mod es3;
 pub mod helpers;
+pub mod preset_env;
+
+use ast::*;
+use crate::util::StmtLike;
+use std::mem;
+use swc_common::{Fold, Span, DUMMY_SP};
+
+/// Folds `stmts`, declaring temporary variables collected in `vars(folder)`
+/// right before the statement which uses them.
+fn fold_stmts<F, T>(
+    folder: &mut F,
+    vars: fn(&mut F) -> &mut Vec<VarDeclarator>,
+    stmts: Vec<T>,
+) -> Vec<T>
+where
+    F: Fold<T>,
+    T: StmtLike,
+{
+    let mut buf = Vec::with_capacity(stmts.len());
+
+    for stmt in stmts {
+        let old = mem::replace(vars(folder), vec![]);
+        let stmt = folder.fold(stmt);
+        let decls = mem::replace(vars(folder), old);
+
+        if !decls.is_empty() {
+            buf.push(T::from_stmt(Stmt::Decl(Decl::Var(VarDecl {
+                span: DUMMY_SP,
+                kind: VarDeclKind::Var,
+                declare: false,
+                decls,
+            }))));
+        }
+        buf.push(stmt);
+    }
+
+    buf
+}
+
+/// Creates the `idx`th temporary variable named `prefix` and adds it to
+/// `vars`.
+fn alias_ident(
+    vars: &mut Vec<VarDeclarator>,
+    span: Span,
+    prefix: &str,
+    idx: usize,
+) -> Ident {
+    let i = if idx == 0 {
+        quote_ident!(span, prefix)
+    } else {
+        quote_ident!(span, format!("{}{}", prefix, idx))
+    };
+    vars.push(VarDeclarator {
+        span,
+        name: Pat::Ident(i.clone()),
+        init: None,
+    });
+    i
+}
+
+/// `left = right`
+fn assign(span: Span, left: Ident, right: Box<Expr>) -> Expr {
+    Expr::Assign(AssignExpr {
+        span,
+        op: op!("="),
+        left: PatOrExpr::Pat(box Pat::Ident(left)),
+        right,
+    })
+}
+
+/// Returns true if `e` can be evaluated twice instead of being stored in a
+/// temporary variable.
+fn is_simple(e: &Expr) -> bool {
+    match *e {
+        Expr::Ident(..) | Expr::This(..) => true,
+        _ => false,
+    }
+}
+
+/// `+` of `+=`.
+fn to_bin_op(op: AssignOp) -> BinaryOp {
+    match op {
+        AssignOp::Assign => unreachable!(),
+        AssignOp::AddAssign => BinaryOp::Add,
+        AssignOp::SubAssign => BinaryOp::Sub,
+        AssignOp::MulAssign => BinaryOp::Mul,
+        AssignOp::DivAssign => BinaryOp::Div,
+        AssignOp::ModAssign => BinaryOp::Mod,
+        AssignOp::LShiftAssign => BinaryOp::LShift,
+        AssignOp::RShiftAssign => BinaryOp::RShift,
+        AssignOp::ZeroFillRShiftAssign => BinaryOp::ZeroFillRShift,
+        AssignOp::BitOrAssign => BinaryOp::BitOr,
+        AssignOp::BitXorAssign => BinaryOp::BitXor,
+        AssignOp::BitAndAssign => BinaryOp::BitAnd,
+        AssignOp::ExpAssign => BinaryOp::Exp,
+    }
+}
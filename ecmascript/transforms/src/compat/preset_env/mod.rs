@@ -0,0 +1,458 @@
+use super::{
+    es2015::{
+        Arrow, BlockScoping, Classes, Destructuring, InstanceOf, Regenerator, Shorthand, Spread,
+        StickyRegex, TemplateLiteral, TypeOfSymbol,
+    },
+    es2016::Exponentation,
+    es2017::AsyncToGenerator,
+    es2020::{NullishCoalescing, OptChaining},
+    es2022::class_properties,
+    es3::{MemberExprLit, PropertyLiteral},
+    helpers::Helpers,
+};
+use ast::*;
+use std::{str::FromStr, sync::Arc};
+use swc_common::{when, Fold};
+
+#[cfg(test)]
+mod tests;
+
+/// Applies compat passes which are required by `targets`.
+///
+/// Passes are applied in the same order as [es2022](super::es2022),
+/// [es2020](super::es2020), [es2017](super::es2017), [es2016](super::es2016),
+/// [es2015](super::es2015) and [es3](super::es3), skipping ones for features
+/// all targets support.
+///
+/// # Example
+///
+/// ```ignore
+/// let targets = "chrome 60, safari 11".parse()?;
+/// // Applies `OptChaining`, `NullishCoalescing` and `TemplateLiteral`.
+/// let pass = preset_env(&helpers, targets);
+/// ```
+pub fn preset_env(helpers: &Arc<Helpers>, targets: Targets) -> impl Fold<Module> {
+    let needs = |feature| targets.needs(feature);
+
+    // `Classes` lowers fields itself.
+    when(
+        needs(Feature::ClassProperties) && !needs(Feature::Classes),
+        class_properties(helpers),
+    )
+    .then(when(needs(Feature::OptionalChaining), OptChaining::default()))
+    .then(when(
+        needs(Feature::NullishCoalescing),
+        NullishCoalescing::default(),
+    ))
+    .then(when(
+        needs(Feature::AsyncToGenerator),
+        AsyncToGenerator {
+            helpers: helpers.clone(),
+        },
+    ))
+    .then(when(needs(Feature::Exponentiation), Exponentation))
+    .then(when(
+        needs(Feature::Classes),
+        Classes {
+            helpers: helpers.clone(),
+        },
+    ))
+    .then(when(needs(Feature::Arrow), Arrow))
+    .then(when(
+        needs(Feature::Destructuring),
+        Destructuring {
+            helpers: helpers.clone(),
+        },
+    ))
+    .then(when(needs(Feature::BlockScoping), BlockScoping::default()))
+    .then(when(
+        needs(Feature::Spread),
+        Spread {
+            helpers: helpers.clone(),
+        },
+    ))
+    .then(when(
+        needs(Feature::TemplateLiteral),
+        TemplateLiteral {
+            helpers: helpers.clone(),
+        },
+    ))
+    .then(when(needs(Feature::StickyRegex), StickyRegex))
+    .then(when(needs(Feature::Shorthand), Shorthand))
+    .then(when(
+        needs(Feature::InstanceOf),
+        InstanceOf {
+            helpers: helpers.clone(),
+        },
+    ))
+    .then(when(
+        needs(Feature::TypeOfSymbol),
+        TypeOfSymbol {
+            helpers: helpers.clone(),
+        },
+    ))
+    .then(when(
+        needs(Feature::Regenerator),
+        Regenerator {
+            helpers: helpers.clone(),
+        },
+    ))
+    .then(when(
+        needs(Feature::PropertyLiterals),
+        PropertyLiteral.then(MemberExprLit),
+    ))
+}
+
+/// Environments which the output should run in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Targets {
+    /// Environments which support all features of the version.
+    EsVersion(EsVersion),
+    /// Minimum versions of browsers and node, like `chrome 60`.
+    ///
+    /// An empty list is treated as environments which support every feature.
+    Browsers(Vec<(Browser, Version)>),
+}
+
+impl Targets {
+    /// Returns true if any of targets doesn't support `feature`.
+    fn needs(&self, feature: Feature) -> bool {
+        match *self {
+            Targets::EsVersion(version) => version < feature.es_version(),
+            Targets::Browsers(ref browsers) => browsers.iter().any(|&(browser, version)| {
+                match feature.support().iter().find(|s| s.0 == browser) {
+                    Some(&(_, major, minor)) => version < Version::new(major, minor, 0),
+                    // Not supported by any version.
+                    None => true,
+                }
+            }),
+        }
+    }
+}
+
+/// Parses an ecmascript version like `es2015` or comma separated browsers like
+/// `chrome 60, ie 11`.
+impl FromStr for Targets {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let version = match &*s.trim().to_ascii_lowercase() {
+            "es3" => Some(EsVersion::Es3),
+            "es5" => Some(EsVersion::Es5),
+            "es2015" | "es6" => Some(EsVersion::Es2015),
+            "es2016" => Some(EsVersion::Es2016),
+            "es2017" => Some(EsVersion::Es2017),
+            "es2018" => Some(EsVersion::Es2018),
+            "es2019" => Some(EsVersion::Es2019),
+            "es2020" => Some(EsVersion::Es2020),
+            "es2021" => Some(EsVersion::Es2021),
+            "es2022" => Some(EsVersion::Es2022),
+            _ => None,
+        };
+        if let Some(version) = version {
+            return Ok(Targets::EsVersion(version));
+        }
+
+        s.split(',')
+            .map(|query| {
+                let mut parts = query.split_whitespace();
+                match (parts.next(), parts.next(), parts.next()) {
+                    (Some(browser), Some(version), None) => {
+                        Ok((browser.parse()?, version.parse()?))
+                    }
+                    _ => Err(format!("invalid target: {}", query.trim())),
+                }
+            })
+            .collect::<Result<_, _>>()
+            .map(Targets::Browsers)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Browser {
+    Chrome,
+    Edge,
+    Firefox,
+    Ie,
+    Ios,
+    Node,
+    Opera,
+    Safari,
+    Samsung,
+}
+
+/// Parses a name used by browserslist, like `chrome` and `ios_saf`.
+impl FromStr for Browser {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match &*s.to_ascii_lowercase() {
+            "chrome" | "and_chr" => Browser::Chrome,
+            "edge" => Browser::Edge,
+            "firefox" | "ff" | "and_ff" => Browser::Firefox,
+            "ie" | "explorer" => Browser::Ie,
+            "ios" | "ios_saf" => Browser::Ios,
+            "node" => Browser::Node,
+            "opera" => Browser::Opera,
+            "safari" => Browser::Safari,
+            "samsung" => Browser::Samsung,
+            _ => return Err(format!("unknown browser: {}", s)),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Version {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl Version {
+    pub fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Version {
+            major,
+            minor,
+            patch,
+        }
+    }
+}
+
+/// Parses `60`, `13.1` or `10.13.0`.
+impl FromStr for Version {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split('.').map(|part| part.parse::<u32>());
+        let mut next = || match parts.next() {
+            Some(Ok(n)) => Ok(n),
+            None => Ok(0),
+            Some(Err(..)) => Err(format!("invalid version: {}", s)),
+        };
+        let version = Version::new(next()?, next()?, next()?);
+
+        match parts.next() {
+            Some(..) => Err(format!("invalid version: {}", s)),
+            None => Ok(version),
+        }
+    }
+}
+
+/// Features which are compiled by a compat pass.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Feature {
+    /// Fields, private methods and static blocks.
+    ClassProperties,
+    OptionalChaining,
+    NullishCoalescing,
+    AsyncToGenerator,
+    Exponentiation,
+    Classes,
+    Arrow,
+    Destructuring,
+    BlockScoping,
+    Spread,
+    TemplateLiteral,
+    StickyRegex,
+    Shorthand,
+    /// `Symbol.hasInstance`
+    InstanceOf,
+    TypeOfSymbol,
+    Regenerator,
+    /// Reserved words as property names.
+    PropertyLiterals,
+}
+
+impl Feature {
+    /// The version which introduced the feature.
+    fn es_version(self) -> EsVersion {
+        match self {
+            Feature::ClassProperties => EsVersion::Es2022,
+            Feature::OptionalChaining | Feature::NullishCoalescing => EsVersion::Es2020,
+            Feature::AsyncToGenerator => EsVersion::Es2017,
+            Feature::Exponentiation => EsVersion::Es2016,
+            Feature::PropertyLiterals => EsVersion::Es5,
+            _ => EsVersion::Es2015,
+        }
+    }
+
+    /// The first versions of browsers which support the feature, from the
+    /// compat data of babel.
+    fn support(self) -> &'static [(Browser, u32, u32)] {
+        use self::Browser::*;
+
+        match self {
+            Feature::ClassProperties => &[
+                (Chrome, 94, 0),
+                (Edge, 94, 0),
+                (Firefox, 93, 0),
+                (Safari, 16, 4),
+                (Node, 16, 11),
+                (Ios, 16, 4),
+                (Samsung, 17, 0),
+                (Opera, 80, 0),
+            ],
+            Feature::OptionalChaining => &[
+                (Chrome, 80, 0),
+                (Edge, 80, 0),
+                (Firefox, 74, 0),
+                (Safari, 13, 1),
+                (Node, 14, 0),
+                (Ios, 13, 4),
+                (Samsung, 13, 0),
+                (Opera, 67, 0),
+            ],
+            Feature::NullishCoalescing => &[
+                (Chrome, 80, 0),
+                (Edge, 80, 0),
+                (Firefox, 72, 0),
+                (Safari, 13, 1),
+                (Node, 14, 0),
+                (Ios, 13, 4),
+                (Samsung, 13, 0),
+                (Opera, 67, 0),
+            ],
+            Feature::AsyncToGenerator => &[
+                (Chrome, 55, 0),
+                (Edge, 15, 0),
+                (Firefox, 52, 0),
+                (Safari, 11, 0),
+                (Node, 7, 6),
+                (Ios, 11, 0),
+                (Samsung, 6, 0),
+                (Opera, 42, 0),
+            ],
+            Feature::Exponentiation => &[
+                (Chrome, 52, 0),
+                (Edge, 14, 0),
+                (Firefox, 52, 0),
+                (Safari, 10, 1),
+                (Node, 7, 0),
+                (Ios, 10, 3),
+                (Samsung, 6, 0),
+                (Opera, 39, 0),
+            ],
+            Feature::Classes => &[
+                (Chrome, 46, 0),
+                (Edge, 13, 0),
+                (Firefox, 45, 0),
+                (Safari, 10, 0),
+                (Node, 5, 0),
+                (Ios, 10, 0),
+                (Samsung, 5, 0),
+                (Opera, 33, 0),
+            ],
+            Feature::Arrow => &[
+                (Chrome, 47, 0),
+                (Edge, 13, 0),
+                (Firefox, 45, 0),
+                (Safari, 10, 0),
+                (Node, 6, 0),
+                (Ios, 10, 0),
+                (Samsung, 5, 0),
+                (Opera, 34, 0),
+            ],
+            Feature::Destructuring => &[
+                (Chrome, 51, 0),
+                (Edge, 15, 0),
+                (Firefox, 53, 0),
+                (Safari, 10, 0),
+                (Node, 6, 5),
+                (Ios, 10, 0),
+                (Samsung, 5, 0),
+                (Opera, 38, 0),
+            ],
+            Feature::BlockScoping => &[
+                (Chrome, 49, 0),
+                (Edge, 14, 0),
+                (Firefox, 51, 0),
+                (Safari, 11, 0),
+                (Node, 6, 0),
+                (Ios, 11, 0),
+                (Samsung, 5, 0),
+                (Opera, 36, 0),
+            ],
+            Feature::Spread => &[
+                (Chrome, 46, 0),
+                (Edge, 13, 0),
+                (Firefox, 36, 0),
+                (Safari, 10, 0),
+                (Node, 5, 0),
+                (Ios, 10, 0),
+                (Samsung, 5, 0),
+                (Opera, 33, 0),
+            ],
+            Feature::TemplateLiteral => &[
+                (Chrome, 41, 0),
+                (Edge, 13, 0),
+                (Firefox, 34, 0),
+                (Safari, 13, 0),
+                (Node, 4, 0),
+                (Ios, 13, 0),
+                (Samsung, 3, 4),
+                (Opera, 28, 0),
+            ],
+            Feature::StickyRegex => &[
+                (Chrome, 49, 0),
+                (Edge, 13, 0),
+                (Firefox, 3, 0),
+                (Safari, 10, 0),
+                (Node, 6, 0),
+                (Ios, 10, 0),
+                (Samsung, 5, 0),
+                (Opera, 36, 0),
+            ],
+            Feature::Shorthand => &[
+                (Chrome, 43, 0),
+                (Edge, 12, 0),
+                (Firefox, 33, 0),
+                (Safari, 9, 0),
+                (Node, 4, 0),
+                (Ios, 9, 0),
+                (Samsung, 4, 0),
+                (Opera, 30, 0),
+            ],
+            Feature::InstanceOf => &[
+                (Chrome, 51, 0),
+                (Edge, 15, 0),
+                (Firefox, 50, 0),
+                (Safari, 10, 0),
+                (Node, 6, 5),
+                (Ios, 10, 0),
+                (Samsung, 5, 0),
+                (Opera, 38, 0),
+            ],
+            Feature::TypeOfSymbol => &[
+                (Chrome, 38, 0),
+                (Edge, 12, 0),
+                (Firefox, 36, 0),
+                (Safari, 9, 0),
+                (Node, 0, 12),
+                (Ios, 9, 0),
+                (Samsung, 3, 0),
+                (Opera, 25, 0),
+            ],
+            Feature::Regenerator => &[
+                (Chrome, 50, 0),
+                (Edge, 13, 0),
+                (Firefox, 53, 0),
+                (Safari, 10, 0),
+                (Node, 6, 0),
+                (Ios, 10, 0),
+                (Samsung, 5, 0),
+                (Opera, 37, 0),
+            ],
+            Feature::PropertyLiterals => &[
+                (Chrome, 7, 0),
+                (Edge, 12, 0),
+                (Firefox, 2, 0),
+                (Safari, 5, 1),
+                (Node, 0, 10),
+                (Ie, 9, 0),
+                (Ios, 6, 0),
+                (Samsung, 1, 0),
+                (Opera, 12, 0),
+            ],
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use super::*;
+
+fn targets(s: &str) -> Targets {
+    s.parse().unwrap()
+}
+
+#[test]
+fn parse() {
+    assert_eq!(targets("es2016"), Targets::EsVersion(EsVersion::Es2016));
+    assert_eq!(
+        targets("chrome 60, ios_saf 10.3, node 10.13.0"),
+        Targets::Browsers(vec![
+            (Browser::Chrome, Version::new(60, 0, 0)),
+            (Browser::Ios, Version::new(10, 3, 0)),
+            (Browser::Node, Version::new(10, 13, 0)),
+        ])
+    );
+
+    assert!("chrome".parse::<Targets>().is_err());
+    assert!("netscape 4".parse::<Targets>().is_err());
+    assert!("chrome 1.2.3.4".parse::<Targets>().is_err());
+}
+
+#[test]
+fn needs() {
+    let es2016 = targets("es2016");
+    assert!(es2016.needs(Feature::AsyncToGenerator));
+    assert!(!es2016.needs(Feature::Exponentiation));
+    assert!(!es2016.needs(Feature::Arrow));
+
+    let browsers = targets("chrome 60, safari 11");
+    assert!(browsers.needs(Feature::OptionalChaining));
+    // Safari supports template literals since 13.
+    assert!(browsers.needs(Feature::TemplateLiteral));
+    assert!(!browsers.needs(Feature::AsyncToGenerator));
+    assert!(!browsers.needs(Feature::Arrow));
+
+    let ie = targets("ie 11");
+    assert!(ie.needs(Feature::Classes));
+    assert!(!ie.needs(Feature::PropertyLiterals));
+
+    assert!(targets("es2021").needs(Feature::ClassProperties));
+    assert!(targets("chrome 80").needs(Feature::ClassProperties));
+    assert!(!targets("chrome 94, node 16.11").needs(Feature::ClassProperties));
+}
+
+test!(
+    preset_env(&Default::default(), targets("es2015")),
+    es_version,
+    "a ** b; var f = () => ({ c });",
+    "Math.pow(a, b); var f = () => ({ c });"
+);
+
+test!(
+    preset_env(&Default::default(), targets("chrome 45")),
+    browsers,
+    "a ** b; var f = () => ({ c });",
+    "Math.pow(a, b); var f = function () { return { c }; };"
+);
+
+test!(
+    preset_env(&Default::default(), targets("chrome 80")),
+    class_properties,
+    "class Foo { a = 1; }",
+    r#"class Foo { constructor() { _defineProperty(this, "a", 1); } }"#
+);
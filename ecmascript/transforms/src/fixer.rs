@@ -30,25 +30,233 @@ impl Fold<Expr> for Fixer {
     }
 }
 
+/// Sequences don't need parens if they are not operands.
+impl Fold<Stmt> for Fixer {
+    fn fold(&mut self, stmt: Stmt) -> Stmt {
+        let stmt = stmt.fold_children(self);
+
+        match stmt {
+            // `(a, b);` -> `a, b;`
+            Stmt::Expr(ExprStmt { span, expr }) => Stmt::Expr(ExprStmt {
+                span,
+                expr: wrap_stmt_start(unwrap_seq(expr)),
+            }),
+            Stmt::Return(ReturnStmt { span, arg }) => Stmt::Return(ReturnStmt {
+                span,
+                arg: arg.map(unwrap_seq),
+            }),
+            Stmt::Throw(ThrowStmt { span, arg }) => Stmt::Throw(ThrowStmt {
+                span,
+                arg: unwrap_seq(arg),
+            }),
+            Stmt::If(IfStmt {
+                span,
+                test,
+                cons,
+                alt,
+            }) => Stmt::If(IfStmt {
+                span,
+                test: unwrap_seq(test),
+                // `if (a) { if (b) c() } else d()`
+                cons: if alt.is_some() && dangles(&cons) {
+                    box Stmt::Block(BlockStmt {
+                        span: cons.span(),
+                        stmts: vec![*cons],
+                    })
+                } else {
+                    cons
+                },
+                alt,
+            }),
+            Stmt::Switch(SwitchStmt {
+                span,
+                discriminant,
+                cases,
+            }) => Stmt::Switch(SwitchStmt {
+                span,
+                discriminant: unwrap_seq(discriminant),
+                cases,
+            }),
+            Stmt::For(ForStmt {
+                span,
+                init,
+                test,
+                update,
+                body,
+            }) => Stmt::For(ForStmt {
+                span,
+                // `for (a, b in c;;)` is a syntax error.
+                init,
+                test: test.map(unwrap_seq),
+                update: update.map(unwrap_seq),
+                body,
+            }),
+            _ => stmt,
+        }
+    }
+}
+
+/// Returns true if `else` after `stmt` would belong to an `if` in `stmt`.
+fn dangles(stmt: &Stmt) -> bool {
+    match *stmt {
+        Stmt::If(IfStmt { alt: None, .. }) => true,
+        Stmt::If(IfStmt {
+            alt: Some(ref body),
+            ..
+        })
+        | Stmt::While(WhileStmt { ref body, .. })
+        | Stmt::For(ForStmt { ref body, .. })
+        | Stmt::ForIn(ForInStmt { ref body, .. })
+        | Stmt::ForOf(ForOfStmt { ref body, .. })
+        | Stmt::Labeled(LabeledStmt { ref body, .. })
+        | Stmt::With(WithStmt { ref body, .. }) => dangles(body),
+        _ => false,
+    }
+}
+
+/// `({}).a` and `(function () {})()` would be parsed as statements without
+/// parens.
+fn wrap_stmt_start(expr: Box<Expr>) -> Box<Expr> {
+    match expr {
+        box Expr::Object(..) | box Expr::Fn(..) | box Expr::Class(..) => box expr.wrap_with_paren(),
+        box Expr::Call(CallExpr {
+            span,
+            callee: ExprOrSuper::Expr(callee),
+            args,
+        }) => box Expr::Call(CallExpr {
+            span,
+            callee: ExprOrSuper::Expr(wrap_stmt_start(callee)),
+            args,
+        }),
+        box Expr::Member(MemberExpr {
+            span,
+            obj: ExprOrSuper::Expr(obj),
+            prop,
+            computed,
+        }) => box Expr::Member(MemberExpr {
+            span,
+            obj: ExprOrSuper::Expr(wrap_stmt_start(obj)),
+            prop,
+            computed,
+        }),
+        box Expr::Bin(BinExpr {
+            span,
+            op,
+            left,
+            right,
+        }) => box Expr::Bin(BinExpr {
+            span,
+            op,
+            left: wrap_stmt_start(left),
+            right,
+        }),
+        box Expr::Cond(CondExpr {
+            span,
+            test,
+            cons,
+            alt,
+        }) => box Expr::Cond(CondExpr {
+            span,
+            test: wrap_stmt_start(test),
+            cons,
+            alt,
+        }),
+        box Expr::Update(UpdateExpr {
+            span,
+            op,
+            prefix: false,
+            arg,
+        }) => box Expr::Update(UpdateExpr {
+            span,
+            op,
+            prefix: false,
+            arg: wrap_stmt_start(arg),
+        }),
+        box Expr::Assign(AssignExpr {
+            span,
+            op,
+            left: PatOrExpr::Expr(left),
+            right,
+        }) => box Expr::Assign(AssignExpr {
+            span,
+            op,
+            left: PatOrExpr::Expr(wrap_stmt_start(left)),
+            right,
+        }),
+        box Expr::Seq(SeqExpr { span, mut exprs }) => {
+            let first = exprs.remove(0);
+            exprs.insert(0, wrap_stmt_start(first));
+            box Expr::Seq(SeqExpr { span, exprs })
+        }
+        _ => expr,
+    }
+}
+
+fn unwrap_seq(expr: Box<Expr>) -> Box<Expr> {
+    match expr {
+        box Expr::Paren(ParenExpr {
+            expr: box expr @ Expr::Seq(..),
+            ..
+        }) => box expr,
+        _ => expr,
+    }
+}
+
 impl Fold<BinExpr> for Fixer {
     fn fold(&mut self, expr: BinExpr) -> BinExpr {
-        let expr = expr.fold_children(self);
+        let mut expr = expr.fold_children(self);
 
-        match *expr.left {
+        let wrap_left = match *expr.left {
             // While simplifying, (1 + x) * Nan becomes `1 + x * Nan`.
             // But it should be `(1 + x) * Nan`
             Expr::Bin(BinExpr { op: op_of_lhs, .. }) => {
-                if op_of_lhs.precedence() < expr.op.precedence() {
-                    return BinExpr {
-                        left: box expr.left.wrap_with_paren(),
-                        ..expr
-                    };
-                } else {
-                    expr
-                }
+                op_of_lhs.precedence() < expr.op.precedence()
             }
-            Expr::Cond(cond_expr) => BinExpr {
-                left: box cond_expr.wrap_with_paren(),
+            Expr::Cond(..) | Expr::Assign(..) => true,
+            _ => false,
+        };
+        // `a - (b - c)`, `a && (b = c)`
+        let wrap_right = match *expr.right {
+            Expr::Bin(BinExpr { op: op_of_rhs, .. }) => {
+                op_of_rhs.precedence() <= expr.op.precedence()
+            }
+            Expr::Cond(..) | Expr::Assign(..) => true,
+            _ => false,
+        };
+
+        if wrap_left {
+            expr.left = box expr.left.wrap_with_paren();
+        }
+        if wrap_right {
+            expr.right = box expr.right.wrap_with_paren();
+        }
+        expr
+    }
+}
+
+impl Fold<UnaryExpr> for Fixer {
+    fn fold(&mut self, expr: UnaryExpr) -> UnaryExpr {
+        let expr = expr.fold_children(self);
+
+        match *expr.arg {
+            // `!(a && b)`
+            Expr::Bin(..) | Expr::Cond(..) | Expr::Assign(..) => UnaryExpr {
+                arg: box expr.arg.wrap_with_paren(),
+                ..expr
+            },
+            _ => expr,
+        }
+    }
+}
+
+impl Fold<CondExpr> for Fixer {
+    fn fold(&mut self, expr: CondExpr) -> CondExpr {
+        let expr = expr.fold_children(self);
+
+        match *expr.test {
+            // `(a = b) ? c : d`
+            Expr::Cond(..) | Expr::Assign(..) => CondExpr {
+                test: box expr.test.wrap_with_paren(),
                 ..expr
             },
             _ => expr,
@@ -0,0 +1,215 @@
+use ast::*;
+use crate::{
+    scope::{FoldScoped, ScopedFold, Scopes},
+    util::pat_ids,
+};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use swc_atoms::JsWord;
+use swc_common::{Fold, FoldWith, SyntaxContext};
+
+#[cfg(test)]
+mod tests;
+
+/// Renames bindings which would shadow another binding or a free variable
+/// once syntax contexts are dropped by the code generator.
+///
+/// Transforms can introduce variables like `_foo` with a fresh mark instead
+/// of looking for an unused name. They are renamed to `_foo1`, `_foo2` and so
+/// on only if they collide with other variables.
+///
+/// This should be applied after all other transforms, and expects bindings of
+/// the input to be marked by [resolver].
+///
+/// [resolver]: crate::resolver
+pub fn hygiene() -> impl Fold<Module> + 'static {
+    Hygiene
+}
+
+#[derive(Debug)]
+struct Hygiene;
+
+impl Fold<Module> for Hygiene {
+    fn fold(&mut self, module: Module) -> Module {
+        let mut collector = ScopedFold::new(Collector::default());
+        let module = module.fold_with(&mut collector);
+        let renames = collector.folder.renames();
+        if renames.is_empty() {
+            return module;
+        }
+
+        let originals = renames
+            .iter()
+            .map(|(&(ref sym, _), new)| (new.clone(), sym.clone()))
+            .collect();
+        module.fold_with(&mut ScopedFold::new(Renamer { renames, originals }))
+    }
+}
+
+#[derive(Debug, Default)]
+struct Collector {
+    /// Names of all identifiers in the module, which are not used for new
+    /// names.
+    used: HashSet<JsWord>,
+    /// Bindings which shadow a binding or a free variable referred in their
+    /// scope.
+    conflicts: BTreeSet<(JsWord, SyntaxContext)>,
+    /// Names of exported bindings can't be changed.
+    exported: HashSet<(JsWord, SyntaxContext)>,
+}
+
+impl Collector {
+    fn renames(self) -> HashMap<(JsWord, SyntaxContext), JsWord> {
+        let mut used = self.used;
+        let mut renames = HashMap::new();
+
+        for id in self.conflicts {
+            if self.exported.contains(&id) {
+                continue;
+            }
+
+            let new = (1..)
+                .map(|n| JsWord::from(format!("{}{}", id.0, n)))
+                .find(|sym| !used.contains(sym))
+                .expect("there are names which are not used");
+            used.insert(new.clone());
+            renames.insert(id, new);
+        }
+
+        renames
+    }
+}
+
+impl FoldScoped<Ident> for Collector {
+    fn fold_scoped(&mut self, scopes: &mut Scopes, i: Ident) -> Ident {
+        self.used.insert(i.sym.clone());
+
+        let ctxt = i.span.ctxt();
+        let declared_at = scopes.resolve(&i);
+        let start = declared_at.unwrap_or(0);
+        for (depth, scope) in scopes.scopes().iter().enumerate().skip(start) {
+            let conflicts = scope.bindings().filter(|&&(ref sym, c)| {
+                // If both are declared in a scope, the latter is renamed.
+                *sym == i.sym && c != ctxt && (Some(depth) != declared_at || c > ctxt)
+            });
+            self.conflicts.extend(conflicts.cloned());
+        }
+
+        i
+    }
+}
+
+impl FoldScoped<ExportDecl> for Collector {
+    fn fold_scoped(&mut self, _: &mut Scopes, e: ExportDecl) -> ExportDecl {
+        let mut ids = vec![];
+        match e.decl {
+            Decl::Class(ClassDecl { ref ident, .. }) | Decl::Fn(FnDecl { ref ident, .. }) => {
+                ids.push(ident.clone())
+            }
+            Decl::Var(ref v) => v.decls.iter().for_each(|d| pat_ids(&d.name, &mut ids)),
+            _ => {}
+        }
+        self.exported
+            .extend(ids.into_iter().map(|i| (i.sym, i.span.ctxt())));
+
+        e
+    }
+}
+
+#[derive(Debug)]
+struct Renamer {
+    renames: HashMap<(JsWord, SyntaxContext), JsWord>,
+    /// Original names of renamed bindings, keyed by new names.
+    originals: HashMap<JsWord, JsWord>,
+}
+
+impl Renamer {
+    /// Returns the original name of `i` if it's renamed.
+    fn original(&self, i: &Ident) -> Option<Ident> {
+        self.originals.get(&i.sym).map(|sym| Ident {
+            sym: sym.clone(),
+            ..i.clone()
+        })
+    }
+}
+
+impl FoldScoped<Ident> for Renamer {
+    fn fold_scoped(&mut self, _: &mut Scopes, i: Ident) -> Ident {
+        match self.renames.get(&(i.sym.clone(), i.span.ctxt())) {
+            Some(sym) => Ident {
+                sym: sym.clone(),
+                ..i
+            },
+            None => i,
+        }
+    }
+}
+
+/// `{ a }` -> `{ a: a1 }`
+impl FoldScoped<Prop> for Renamer {
+    fn fold_scoped(&mut self, _: &mut Scopes, p: Prop) -> Prop {
+        match p {
+            Prop::Shorthand(i) => match self.original(&i) {
+                Some(key) => Prop::KeyValue(KeyValueProp {
+                    key: PropName::Ident(key),
+                    value: box Expr::Ident(i),
+                }),
+                None => Prop::Shorthand(i),
+            },
+            _ => p,
+        }
+    }
+}
+
+/// `{ a = 1 } = b` -> `{ a: a1 = 1 } = b`
+impl FoldScoped<ObjectPatProp> for Renamer {
+    fn fold_scoped(&mut self, _: &mut Scopes, p: ObjectPatProp) -> ObjectPatProp {
+        match p {
+            ObjectPatProp::Assign(AssignPatProp { span, key, value }) => {
+                let orig = match self.original(&key) {
+                    Some(orig) => orig,
+                    None => return ObjectPatProp::Assign(AssignPatProp { span, key, value }),
+                };
+                let value = match value {
+                    Some(right) => Pat::Assign(AssignPat {
+                        span,
+                        left: box Pat::Ident(key),
+                        right,
+                    }),
+                    None => Pat::Ident(key),
+                };
+
+                ObjectPatProp::KeyValue(KeyValuePatProp {
+                    key: PropName::Ident(orig),
+                    value: box value,
+                })
+            }
+            _ => p,
+        }
+    }
+}
+
+/// `export { a }` -> `export { a1 as a }`
+impl FoldScoped<ExportSpecifier> for Renamer {
+    fn fold_scoped(&mut self, _: &mut Scopes, s: ExportSpecifier) -> ExportSpecifier {
+        match s.exported {
+            Some(..) => s,
+            None => ExportSpecifier {
+                exported: self.original(&s.orig),
+                ..s
+            },
+        }
+    }
+}
+
+/// `import { a } from 'foo'` -> `import { a as a1 } from 'foo'`
+impl FoldScoped<ImportSpecific> for Renamer {
+    fn fold_scoped(&mut self, _: &mut Scopes, s: ImportSpecific) -> ImportSpecific {
+        match s.imported {
+            Some(..) => s,
+            None => ImportSpecific {
+                imported: self.original(&s.local),
+                ..s
+            },
+        }
+    }
+}
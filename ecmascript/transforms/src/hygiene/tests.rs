@@ -0,0 +1,99 @@
+use super::*;
+use crate::{resolver, util::ExprFactory};
+use swc_common::{Mark, DUMMY_SP};
+
+/// Prepends `var a = 2; use({ a });` to functions, using a fresh mark for
+/// `a`.
+struct InjectVar;
+
+impl Fold<Function> for InjectVar {
+    fn fold(&mut self, f: Function) -> Function {
+        let a = quote_ident!(DUMMY_SP.apply_mark(Mark::fresh(Mark::root())), "a");
+        let var = Stmt::Decl(Decl::Var(VarDecl {
+            span: DUMMY_SP,
+            kind: VarDeclKind::Var,
+            declare: false,
+            decls: vec![VarDeclarator {
+                span: DUMMY_SP,
+                name: Pat::Ident(a.clone()),
+                init: Some(box Expr::Lit(Lit::Num(Number {
+                    span: DUMMY_SP,
+                    value: 2.0,
+                    raw: None,
+                }))),
+            }],
+        }));
+        let obj = Expr::Object(ObjectLit {
+            span: DUMMY_SP,
+            props: vec![PropOrSpread::Prop(box Prop::Shorthand(a))],
+        });
+        let use_a = Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            callee: quote_ident!("use").as_callee(),
+            args: vec![obj.as_arg()],
+        })
+        .into_stmt();
+
+        let mut body = f.body.unwrap();
+        body.stmts.splice(0..0, vec![var, use_a]);
+        Function {
+            body: Some(body),
+            ..f
+        }
+    }
+}
+
+fn tr() -> impl Fold<Module> {
+    resolver().then(InjectVar).then(hygiene())
+}
+
+#[test]
+fn captured() {
+    test_transform!(
+        tr(),
+        "var a = 1; function f() { use(a); }",
+        "var a = 1; function f() { var a1 = 2; use({ a: a1 }); use(a); }",
+        true
+    );
+}
+
+#[test]
+fn no_conflict() {
+    test_transform!(
+        tr(),
+        "var b = 1; function f() { use(b); }",
+        "var b = 1; function f() { var a = 2; use({ a }); use(b); }",
+        true
+    );
+}
+
+#[test]
+fn free_variable() {
+    test_transform!(
+        tr(),
+        "function f() { use(a); }",
+        "function f() { var a1 = 2; use({ a: a1 }); use(a); }",
+        true
+    );
+}
+
+#[test]
+fn used_names() {
+    test_transform!(
+        tr(),
+        "var a = 1, a1 = 3; function f() { use(a, a1); }",
+        "var a = 1, a1 = 3; function f() { var a2 = 2; use({ a: a2 }); use(a, a1); }",
+        true
+    );
+}
+
+/// Marks of the resolver don't make names collide.
+#[test]
+fn resolved() {
+    test_transform!(
+        resolver().then(hygiene()),
+        "var a = 1; a: for (;;) { let a = a.a; function f(a) { use({ a }); break a; } }",
+        "var a = 1; a: for (;;) { let a = a.a; function f(a) { use({ a }); break a; } }",
+        true
+    );
+}
@@ -1,66 +1,78 @@
 use ast::*;
+use crate::scope::{FoldScoped, ScopedFold, Scopes};
 use std::collections::HashMap;
 use swc_atoms::JsWord;
 use swc_common::{Fold, FoldWith};
 
+/// Replaces global variables and member expressions on them with constant
+/// expressions, like `__DEBUG__` with `false`.
+///
+/// This should be applied before the simplifier, so branches which are
+/// only for development are removed.
 pub struct InlineGlobals {
+    /// Values of `process.env.*`, keyed by names of environment variables.
     pub envs: HashMap<JsWord, Expr>,
+    /// Values keyed by global variables or member expressions on them, like
+    /// `__DEBUG__` and `process.env.NODE_ENV`.
     pub globals: HashMap<JsWord, Expr>,
 }
 
-impl Fold<Expr> for InlineGlobals {
-    fn fold(&mut self, expr: Expr) -> Expr {
-        let expr = match expr {
-            // Don't recurese into member expression.
-            Expr::Member(..) => expr,
-            _ => expr.fold_children(self),
+impl Fold<Module> for InlineGlobals {
+    fn fold(&mut self, module: Module) -> Module {
+        module.fold_with(&mut ScopedFold::new(Inliner {
+            envs: &self.envs,
+            globals: &self.globals,
+        }))
+    }
+}
+
+struct Inliner<'a> {
+    envs: &'a HashMap<JsWord, Expr>,
+    globals: &'a HashMap<JsWord, Expr>,
+}
+
+impl<'a> FoldScoped<Expr> for Inliner<'a> {
+    fn fold_scoped(&mut self, scopes: &mut Scopes, e: Expr) -> Expr {
+        let path = match path(&e) {
+            // Skip shadowed variables.
+            Some((root, _)) if scopes.resolve(root).is_some() => return e,
+            Some((_, path)) => path,
+            None => return e,
         };
 
-        match expr {
-            Expr::Ident(Ident { ref sym, span }) => {
-                // It's ok because we don't recurse into member expressions.
-                if let Some(value) = self.globals.get(sym) {
-                    return value.clone();
-                } else {
-                    expr
-                }
-            }
-            Expr::Member(MemberExpr {
-                span,
-                obj:
-                    ExprOrSuper::Expr(box Expr::Member(MemberExpr {
-                        obj:
-                            ExprOrSuper::Expr(box Expr::Ident(Ident {
-                                sym: js_word!("process"),
-                                ..
-                            })),
-                        prop:
-                            box Expr::Ident(Ident {
-                                sym: js_word!("env"),
-                                ..
-                            }),
-                        span: _,
-                        computed: _,
-                    })),
-                prop,
-                computed: _,
-            }) => {
-                let prop = match *prop {
-                    Expr::Ident(Ident { ref sym, .. }) => {
-                        if let Some(env) = self.envs.get(sym) {
-                            return env.clone();
-                        }
-                        return Expr::Lit(Lit::Str(Str {
-                            value: js_word!(""),
-                            span: mark!(span),
-                            has_escape: false,
-                        }));
-                    }
-                    _ => unimplemented!("node.env.NONE-IDENT"),
-                };
+        if let Some(value) = self.globals.get(&JsWord::from(&*path)) {
+            return value.clone();
+        }
+        if path.starts_with("process.env.") {
+            let name = JsWord::from(&path["process.env.".len()..]);
+            if let Some(value) = self.envs.get(&name) {
+                return value.clone();
             }
-            _ => expr,
         }
+
+        e
+    }
+}
+
+/// Returns the object and the dotted path of `a.b["c"]`.
+fn path(e: &Expr) -> Option<(&Ident, String)> {
+    match *e {
+        Expr::Ident(ref i) => Some((i, i.sym.to_string())),
+        Expr::Member(MemberExpr {
+            obj: ExprOrSuper::Expr(ref obj),
+            ref prop,
+            computed,
+            ..
+        }) => {
+            let prop = match **prop {
+                Expr::Ident(ref i) if !computed => &*i.sym,
+                Expr::Lit(Lit::Str(ref s)) if computed => &*s.value,
+                _ => return None,
+            };
+            let (root, path) = path(obj)?;
+            Some((root, format!("{}.{}", path, prop)))
+        }
+        _ => None,
     }
 }
 
@@ -71,7 +83,7 @@ mod tests {
     fn mk_map(values: &[(&str, &str)], is_env: bool) -> HashMap<JsWord, Expr> {
         let mut m = HashMap::new();
 
-        crate::tests::Tester::run(|tester| {
+        crate::tests::Tester::run(crate::tests::syntax(), |tester| {
             for (k, v) in values {
                 let v = if is_env {
                     format!("'{}'", v)
@@ -82,7 +94,7 @@ mod tests {
                 let mut v = tester.apply_transform(::testing::DropSpan, "global.js", &v)?;
                 assert_eq!(v.body.len(), 1);
                 let v = match v.body.pop().unwrap() {
-                    ModuleItem::Stmt(Stmt::Expr(box expr)) => expr,
+                    ModuleItem::Stmt(Stmt::Expr(ExprStmt { expr: box expr, .. })) => expr,
                     _ => unreachable!(),
                 };
 
@@ -132,4 +144,35 @@ mod tests {
         r#"if (foo.debug) {}"#,
         r#"if (foo.debug) {}"#
     );
+
+    test!(
+        InlineGlobals {
+            envs: envs(&[]),
+            globals: globals(&[("process.env.NODE_ENV", "'production'"), ("a.b", "x")]),
+        },
+        member_expr,
+        r#"use(process.env.NODE_ENV, process.env["NODE_ENV"], a.b.c, f(a).b, process.env.FOO);"#,
+        r#"use('production', 'production', x.c, f(a).b, process.env.FOO);"#
+    );
+
+    test!(
+        InlineGlobals {
+            envs: envs(&[("NODE_ENV", "development")]),
+            globals: globals(&[("__DEBUG__", "true")]),
+        },
+        shadowed,
+        r#"function f(__DEBUG__, process) { use(__DEBUG__, process.env.NODE_ENV); }"#,
+        r#"function f(__DEBUG__, process) { use(__DEBUG__, process.env.NODE_ENV); }"#
+    );
+
+    test!(
+        InlineGlobals {
+            envs: envs(&[]),
+            globals: globals(&[("__DEBUG__", "false")]),
+        }
+        .then(crate::simplifier()),
+        strip_branches,
+        r#"if (__DEBUG__) { log(); } else { run(); }"#,
+        r#"run();"#
+    );
 }
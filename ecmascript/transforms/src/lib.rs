@@ -3,6 +3,7 @@
 #![feature(try_trait)]
 #![feature(specialization)]
 #![feature(nll)]
+#![feature(proc_macro_hygiene)]
 #![feature(trace_macros)]
 
 #[macro_use]
@@ -11,28 +12,44 @@ extern crate slog;
 extern crate swc_atoms;
 extern crate swc_common;
 extern crate swc_ecma_ast as ast;
-#[cfg(test)]
-extern crate swc_ecma_codegen;
 extern crate swc_ecma_parser;
+#[macro_use]
+extern crate swc_ecma_transforms_macros;
 #[cfg(test)]
 #[macro_use]
 extern crate pretty_assertions;
 #[cfg(test)]
-extern crate sourcemap;
-#[cfg(test)]
 #[macro_use]
 extern crate testing;
 
-pub use self::{inline_globals::InlineGlobals, simplify::simplifier};
+pub use self::{
+    fixer::fixer,
+    hygiene::hygiene,
+    inline_globals::InlineGlobals,
+    pass::{chain, Chain, Pass},
+    pass_manager::{PassManager, PassTiming},
+    resolver::resolver,
+    simplify::simplifier,
+    stats::AstStats,
+};
 
 #[cfg(test)]
 #[macro_use]
 mod tests;
 #[macro_use]
 mod quote;
+pub mod analysis;
 pub mod compat;
 mod fixer;
+mod hygiene;
 mod inline_globals;
+pub mod modules;
+mod pass;
+mod pass_manager;
+pub mod react;
+mod resolver;
 pub mod scope;
-mod simplify;
+pub mod simplify;
+mod stats;
+pub mod typescript;
 pub mod util;
@@ -0,0 +1,186 @@
+use super::{
+    common_js,
+    util::{fold_module_items, Loader, ModuleBody, Scope},
+};
+use ast::*;
+use crate::{compat::helpers::Helpers, util::ExprFactory};
+use std::{iter, sync::Arc};
+use swc_common::{Fold, DUMMY_SP};
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Config {
+    /// The name of the module, which is passed to `define`.
+    pub module_id: Option<String>,
+    /// Don't define `exports.__esModule`.
+    pub strict: bool,
+    /// Don't wrap imports with `_interopRequireDefault` and
+    /// `_interopRequireWildcard`.
+    pub no_interop: bool,
+}
+
+/// Converts es modules to amd modules.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// import foo from 'foo';
+/// export default foo;
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// define(["exports", "foo"], function (exports, _foo) {
+///     "use strict";
+///     Object.defineProperty(exports, "__esModule", { value: true });
+///     var _foo1 = _interopRequireDefault(_foo);
+///     var foo = _foo1.default;
+///     exports.default = foo;
+/// });
+/// ```
+pub fn amd(helpers: Arc<Helpers>, config: Config) -> impl Fold<Module> {
+    Amd { helpers, config }
+}
+
+struct Amd {
+    helpers: Arc<Helpers>,
+    config: Config,
+}
+
+impl Fold<Module> for Amd {
+    fn fold(&mut self, module: Module) -> Module {
+        let mut deps = Deps::default();
+        let body = fold_module_items(
+            &self.helpers,
+            common_js::Config {
+                strict: self.config.strict,
+                no_interop: self.config.no_interop,
+                // Dependencies are loaded before the factory is called.
+                lazy: false,
+            },
+            &mut deps,
+            &mut Scope::default(),
+            module.body,
+        );
+
+        // define("id", ["exports", "foo"], function (exports, _foo) {})
+        let args = self
+            .config
+            .module_id
+            .iter()
+            .map(|id| Lit::Str(quote_str!(&**id)).as_arg())
+            .chain(iter::once(deps.array(body.uses_exports).as_arg()))
+            .chain(iter::once(deps.factory(body).as_arg()))
+            .collect();
+        let define = Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            callee: quote_ident!("define").as_callee(),
+            args,
+        });
+
+        Module {
+            span: module.span,
+            body: vec![ModuleItem::Stmt(define.into_stmt())],
+            shebang: module.shebang,
+        }
+    }
+}
+
+/// Loads modules from parameters of the factory function.
+///
+/// Modules are passed to the factory in order of [Deps::srcs].
+#[derive(Debug, Default)]
+pub(super) struct Deps {
+    /// Imported modules and the parameters they are passed to.
+    pub params: Vec<(Str, Ident)>,
+    /// Modules imported only for side effects, which don't have parameters.
+    pub side_effects: Vec<Str>,
+}
+
+impl Loader for Deps {
+    fn load(&mut self, scope: &mut Scope, src: Str) -> Box<Expr> {
+        if let Some(&(_, ref param)) = self.params.iter().find(|dep| dep.0.value == src.value) {
+            return box Expr::Ident(param.clone());
+        }
+
+        self.side_effects.retain(|s| s.value != src.value);
+        let param = scope.ident_for_src(&src.value);
+        self.params.push((src, param.clone()));
+        box Expr::Ident(param)
+    }
+
+    fn load_for_side_effects(&mut self, _: &mut Scope, src: Str) -> Option<Stmt> {
+        let is_loaded = self.srcs().any(|s| s.value == src.value);
+        if !is_loaded {
+            self.side_effects.push(src);
+        }
+        None
+    }
+}
+
+impl Deps {
+    /// Sources of all dependencies except `exports`.
+    pub fn srcs(&self) -> impl Iterator<Item = &Str> {
+        self.params
+            .iter()
+            .map(|dep| &dep.0)
+            .chain(self.side_effects.iter())
+    }
+
+    /// `["exports", "foo"]`
+    pub fn array(&self, uses_exports: bool) -> Expr {
+        let exports = if uses_exports {
+            Some(quote_str!("exports"))
+        } else {
+            None
+        };
+
+        Expr::Array(ArrayLit {
+            span: DUMMY_SP,
+            elems: exports
+                .into_iter()
+                .chain(self.srcs().cloned())
+                .map(|src| Some(Lit::Str(src).as_arg()))
+                .collect(),
+        })
+    }
+
+    /// `function (exports, _foo) {}`
+    pub fn factory(&self, body: ModuleBody) -> Expr {
+        let exports = if body.uses_exports {
+            Some(quote_ident!("exports"))
+        } else {
+            None
+        };
+        let params = exports
+            .into_iter()
+            .chain(self.params.iter().map(|dep| dep.1.clone()))
+            .map(|ident| Param {
+                span: DUMMY_SP,
+                decorators: vec![],
+                pat: Pat::Ident(ident),
+            })
+            .collect();
+
+        Expr::Fn(FnExpr {
+            ident: None,
+            function: Function {
+                params,
+                span: DUMMY_SP,
+                body: Some(BlockStmt {
+                    span: DUMMY_SP,
+                    stmts: body.stmts,
+                }),
+                generator_token: None,
+                async_token: None,
+                type_params: None,
+                return_type: None,
+            },
+        })
+    }
+}
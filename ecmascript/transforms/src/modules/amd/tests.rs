@@ -0,0 +1,51 @@
+use super::*;
+
+fn tr(config: Config) -> impl Fold<Module> {
+    amd(Default::default(), config)
+}
+
+test!(
+    tr(Default::default()),
+    import_default,
+    r#"import foo from 'foo';
+export default foo;"#,
+    r#"define(["exports", "foo"], function (exports, _foo) {
+    "use strict";
+    Object.defineProperty(exports, "__esModule", {
+        value: true
+    });
+    var _foo1 = _interopRequireDefault(_foo);
+    var foo = _foo1.default;
+    exports.default = foo;
+});"#
+);
+
+test!(
+    tr(Config {
+        module_id: Some("my-module".into()),
+        strict: true,
+        no_interop: true,
+    }),
+    module_id_and_deps,
+    r#"import 'side';
+import { a } from './lib/a.js';
+export { b } from './lib/a.js';
+export * from 'other';
+a();"#,
+    r#"define("my-module", ["exports", "./lib/a.js", "other", "side"], function (exports, _a, _other) {
+    "use strict";
+    var a = _a.a;
+    exports.b = _a.b;
+    _exportStar(_other, exports);
+    a();
+});"#
+);
+
+test!(
+    tr(Default::default()),
+    script,
+    r#"foo();"#,
+    r#"define([], function () {
+    foo();
+});"#
+);
@@ -0,0 +1,84 @@
+use super::util::{fold_module_items, require, Loader, Scope};
+use ast::*;
+use crate::{compat::helpers::Helpers, util::ExprFactory};
+use std::sync::Arc;
+use swc_common::Fold;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Config {
+    /// Don't define `exports.__esModule`.
+    pub strict: bool,
+    /// Don't wrap imports with `_interopRequireDefault` and
+    /// `_interopRequireWildcard`.
+    pub no_interop: bool,
+    /// Load modules other than local files (`./foo`) when bindings imported
+    /// from them are used first, instead of when the module is loaded.
+    ///
+    /// References are found by their syntax contexts, so `resolver` should
+    /// be applied beforehand if imported bindings are shadowed.
+    pub lazy: bool,
+}
+
+/// Converts es modules to commonjs modules.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// import foo, { bar } from 'foo';
+/// export default foo(bar);
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// "use strict";
+/// Object.defineProperty(exports, "__esModule", { value: true });
+/// var _foo = _interopRequireWildcard(require('foo'));
+/// var foo = _foo.default;
+/// var bar = _foo.bar;
+/// exports.default = foo(bar);
+/// ```
+pub fn common_js(helpers: Arc<Helpers>, config: Config) -> impl Fold<Module> {
+    CommonJs { helpers, config }
+}
+
+struct CommonJs {
+    helpers: Arc<Helpers>,
+    config: Config,
+}
+
+impl Fold<Module> for CommonJs {
+    fn fold(&mut self, module: Module) -> Module {
+        let body = fold_module_items(
+            &self.helpers,
+            self.config,
+            &mut Require,
+            &mut Scope::default(),
+            module.body,
+        );
+
+        Module {
+            span: module.span,
+            body: body.stmts.into_iter().map(ModuleItem::Stmt).collect(),
+            shebang: module.shebang,
+        }
+    }
+}
+
+/// Loads modules with `require`.
+struct Require;
+
+impl Loader for Require {
+    fn load(&mut self, _: &mut Scope, src: Str) -> Box<Expr> {
+        require(src)
+    }
+
+    fn load_for_side_effects(&mut self, _: &mut Scope, src: Str) -> Option<Stmt> {
+        Some(require(src).into_stmt())
+    }
+}
@@ -0,0 +1,125 @@
+use super::*;
+
+fn tr(config: Config) -> impl Fold<Module> {
+    common_js(Default::default(), config)
+}
+
+test!(
+    tr(Default::default()),
+    import_default_and_named,
+    r#"import foo, { bar, baz as qux } from 'foo';
+foo(bar, qux);"#,
+    r#""use strict";
+Object.defineProperty(exports, "__esModule", {
+  value: true
+});
+var _foo = _interopRequireWildcard(require('foo'));
+var foo = _foo.default;
+var bar = _foo.bar;
+var qux = _foo.baz;
+foo(bar, qux);"#
+);
+
+test!(
+    tr(Config {
+        strict: true,
+        no_interop: true,
+        ..Default::default()
+    }),
+    import_no_interop,
+    r#"import foo from 'foo';
+import * as ns from './lib/bar.js';
+import 'side-effect';"#,
+    r#""use strict";
+var _foo = require('foo');
+var foo = _foo.default;
+var ns = require('./lib/bar.js');
+require('side-effect');"#
+);
+
+test!(
+    tr(Config {
+        strict: true,
+        ..Default::default()
+    }),
+    export_decls,
+    r#"export var a = 1, { b } = c;
+export class Foo {}
+export function bar() {}"#,
+    r#""use strict";
+exports.bar = bar;
+var a = 1, { b } = c;
+exports.a = a;
+exports.b = b;
+class Foo {}
+exports.Foo = Foo;
+function bar() {}"#
+);
+
+test!(
+    tr(Config {
+        strict: true,
+        ..Default::default()
+    }),
+    export_default,
+    r#"export default function foo() {}"#,
+    r#""use strict";
+exports.default = foo;
+function foo() {}"#
+);
+
+test!(
+    tr(Config {
+        strict: true,
+        ..Default::default()
+    }),
+    export_named,
+    r#"export { a as b, c };
+export { d as e } from 'mod';
+export * from 'other';
+var a = 1, c = 2;"#,
+    r#""use strict";
+var _mod = require('mod');
+exports.e = _mod.d;
+_exportStar(require('other'), exports);
+var a = 1, c = 2;
+exports.b = a;
+exports.c = c;"#
+);
+
+test!(
+    ::resolver().then(tr(Config {
+        strict: true,
+        no_interop: true,
+        lazy: true,
+    })),
+    import_lazy,
+    r#"import foo, { bar } from 'foo';
+import * as local from './local';
+export { bar };
+function f(foo) {
+    return foo;
+}
+foo(bar, { bar }, bar.foo, local);"#,
+    r#""use strict";
+function _foo() {
+    var data = require('foo');
+    _foo = function () {
+        return data;
+    };
+    return data;
+}
+var local = require('./local');
+function f(foo) {
+    return foo;
+}
+(0, _foo().default)(_foo().bar, { bar: _foo().bar }, _foo().bar.foo, local);
+exports.bar = _foo().bar;"#
+);
+
+test!(
+    tr(Default::default()),
+    script_is_untouched,
+    r#"foo();"#,
+    r#"foo();"#
+);
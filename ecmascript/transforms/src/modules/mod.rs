@@ -0,0 +1,9 @@
+//! Module transforms.
+
+pub use self::{amd::amd, common_js::common_js, system_js::system_js, umd::umd};
+
+pub mod amd;
+pub mod common_js;
+pub mod system_js;
+pub mod umd;
+mod util;
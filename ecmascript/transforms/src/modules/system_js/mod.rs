@@ -0,0 +1,249 @@
+use super::{
+    amd::Deps,
+    common_js,
+    util::{fold_module_items, Scope},
+};
+use ast::*;
+use crate::{compat::helpers::Helpers, util::ExprFactory};
+use std::{iter, sync::Arc};
+use swc_common::{Fold, DUMMY_SP};
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Config {
+    /// The name of the module, which is passed to `System.register`.
+    pub module_id: Option<String>,
+}
+
+/// Converts es modules to systemjs modules.
+///
+/// Imported modules are assigned to variables by setters, and exports are
+/// passed to `_export` after the body is executed.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// import foo from 'foo';
+/// export default foo;
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// System.register(["foo"], function (_export, _context) {
+///     var _foo;
+///     return {
+///         setters: [function (m) {
+///             _foo = m;
+///         }],
+///         execute: function () {
+///             "use strict";
+///             var exports = {};
+///             var foo = _foo.default;
+///             exports.default = foo;
+///             _export(exports);
+///         }
+///     };
+/// });
+/// ```
+pub fn system_js(helpers: Arc<Helpers>, config: Config) -> impl Fold<Module> {
+    SystemJs { helpers, config }
+}
+
+struct SystemJs {
+    helpers: Arc<Helpers>,
+    config: Config,
+}
+
+impl Fold<Module> for SystemJs {
+    fn fold(&mut self, module: Module) -> Module {
+        let mut scope = Scope::default();
+        // Reserve names of the parameters.
+        let export = scope.ident_for_src(&"export".into());
+        let context = scope.ident_for_src(&"context".into());
+
+        let mut deps = Deps::default();
+        let mut body = fold_module_items(
+            &self.helpers,
+            common_js::Config {
+                // Modules are namespace objects.
+                strict: true,
+                no_interop: true,
+                lazy: false,
+            },
+            &mut deps,
+            &mut scope,
+            module.body,
+        );
+
+        if body.uses_exports {
+            let exports = quote_ident!("exports");
+            let pos = match body.stmts.first() {
+                Some(&Stmt::Expr(ExprStmt {
+                    expr: box Expr::Lit(Lit::Str(..)),
+                    ..
+                })) => 1,
+                _ => 0,
+            };
+            // var exports = {};
+            body.stmts.insert(
+                pos,
+                Stmt::Decl(Decl::Var(VarDecl {
+                    span: DUMMY_SP,
+                    kind: VarDeclKind::Var,
+                    declare: false,
+                    decls: vec![VarDeclarator {
+                        span: DUMMY_SP,
+                        name: Pat::Ident(exports.clone()),
+                        init: Some(box Expr::Object(ObjectLit {
+                            span: DUMMY_SP,
+                            props: vec![],
+                        })),
+                    }],
+                })),
+            );
+            // _export(exports);
+            body.stmts.push(
+                Expr::Call(CallExpr {
+                    span: DUMMY_SP,
+                    callee: export.clone().as_callee(),
+                    args: vec![exports.as_arg()],
+                })
+                .into_stmt(),
+            );
+        }
+
+        // var _foo;
+        let vars = if deps.params.is_empty() {
+            None
+        } else {
+            Some(Stmt::Decl(Decl::Var(VarDecl {
+                span: DUMMY_SP,
+                kind: VarDeclKind::Var,
+                declare: false,
+                decls: deps
+                    .params
+                    .iter()
+                    .map(|dep| VarDeclarator {
+                        span: DUMMY_SP,
+                        name: Pat::Ident(dep.1.clone()),
+                        init: None,
+                    })
+                    .collect(),
+            })))
+        };
+
+        // Setters are called in order of the dependencies.
+        let setters = deps
+            .params
+            .iter()
+            .map(|dep| {
+                let m = quote_ident!("m");
+                let assign = Expr::Assign(AssignExpr {
+                    span: DUMMY_SP,
+                    op: op!("="),
+                    left: PatOrExpr::Expr(box Expr::Ident(dep.1.clone())),
+                    right: box Expr::Ident(m.clone()),
+                });
+                function(vec![m], vec![assign.into_stmt()])
+            })
+            .chain(deps.side_effects.iter().map(|_| function(vec![], vec![])))
+            .map(|f| Some(f.as_arg()))
+            .collect();
+
+        // return { setters: [], execute: function () {} };
+        let ret = Stmt::Return(ReturnStmt {
+            span: DUMMY_SP,
+            arg: Some(box Expr::Object(ObjectLit {
+                span: DUMMY_SP,
+                props: vec![
+                    prop(
+                        "setters",
+                        Expr::Array(ArrayLit {
+                            span: DUMMY_SP,
+                            elems: setters,
+                        }),
+                    ),
+                    prop("execute", function(vec![], body.stmts)),
+                ],
+            })),
+        });
+
+        let srcs = Expr::Array(ArrayLit {
+            span: DUMMY_SP,
+            elems: deps
+                .srcs()
+                .cloned()
+                .map(|src| Some(Lit::Str(src).as_arg()))
+                .collect(),
+        });
+        let declare = function(
+            vec![export, context],
+            vars.into_iter().chain(iter::once(ret)).collect(),
+        );
+
+        // System.register("id", ["foo"], function (_export, _context) {})
+        let args = self
+            .config
+            .module_id
+            .iter()
+            .map(|id| Lit::Str(quote_str!(&**id)).as_arg())
+            .chain(iter::once(srcs.as_arg()))
+            .chain(iter::once(declare.as_arg()))
+            .collect();
+        let register = Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            callee: ExprOrSuper::Expr(box Expr::Member(MemberExpr {
+                span: DUMMY_SP,
+                obj: quote_ident!("System").as_callee(),
+                prop: box Expr::Ident(quote_ident!("register")),
+                computed: false,
+            })),
+            args,
+        });
+
+        Module {
+            span: module.span,
+            body: vec![ModuleItem::Stmt(register.into_stmt())],
+            shebang: module.shebang,
+        }
+    }
+}
+
+/// `function (params) { stmts }`
+fn function(params: Vec<Ident>, stmts: Vec<Stmt>) -> Expr {
+    Expr::Fn(FnExpr {
+        ident: None,
+        function: Function {
+            params: params
+                .into_iter()
+                .map(|ident| Param {
+                    span: DUMMY_SP,
+                    decorators: vec![],
+                    pat: Pat::Ident(ident),
+                })
+                .collect(),
+            span: DUMMY_SP,
+            body: Some(BlockStmt {
+                span: DUMMY_SP,
+                stmts,
+            }),
+            generator_token: None,
+            async_token: None,
+            type_params: None,
+            return_type: None,
+        },
+    })
+}
+
+/// `key: value`
+fn prop(key: &str, value: Expr) -> PropOrSpread {
+    PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+        key: PropName::Ident(quote_ident!(key)),
+        value: box value,
+    }))
+}
@@ -0,0 +1,50 @@
+use super::*;
+
+fn tr(config: Config) -> impl Fold<Module> {
+    system_js(Default::default(), config)
+}
+
+test!(
+    tr(Default::default()),
+    import_default,
+    r#"import foo from 'foo';
+export default foo;"#,
+    r#"System.register(["foo"], function (_export, _context) {
+    var _foo;
+    return {
+        setters: [function (m) {
+            _foo = m;
+        }],
+        execute: function () {
+            "use strict";
+            var exports = {};
+            var foo = _foo.default;
+            exports.default = foo;
+            _export(exports);
+        }
+    };
+});"#
+);
+
+test!(
+    tr(Config {
+        module_id: Some("my-module".into()),
+    }),
+    module_id_and_deps,
+    r#"import 'side';
+import { a } from './lib/a.js';
+a();"#,
+    r#"System.register("my-module", ["./lib/a.js", "side"], function (_export, _context) {
+    var _a;
+    return {
+        setters: [function (m) {
+            _a = m;
+        }, function () {}],
+        execute: function () {
+            "use strict";
+            var a = _a.a;
+            a();
+        }
+    };
+});"#
+);
@@ -0,0 +1,239 @@
+use super::{
+    amd::Deps,
+    common_js,
+    util::{fold_module_items, name_for_src, require, Scope},
+};
+use ast::*;
+use crate::{compat::helpers::Helpers, util::ExprFactory};
+use std::{collections::HashMap, iter, sync::Arc};
+use swc_common::{Fold, DUMMY_SP};
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Config {
+    /// The name of the module, which is passed to `define`.
+    pub module_id: Option<String>,
+    /// The global variable which the exports are assigned to when neither amd
+    /// nor commonjs is available, like `"MyLib"` or `"My.lib"`.
+    ///
+    /// The exports are not assigned if this is `None`.
+    pub global_name: Option<String>,
+    /// Global variables of imported modules, keyed by the source.
+    ///
+    /// Defaults to the camel cased name of the module, which is `barBaz` for
+    /// `'./foo/bar-baz.js'`.
+    pub globals: HashMap<String, String>,
+    /// Don't define `exports.__esModule`.
+    pub strict: bool,
+    /// Don't wrap imports with `_interopRequireDefault` and
+    /// `_interopRequireWildcard`.
+    pub no_interop: bool,
+}
+
+/// Converts es modules to umd modules, which work as amd modules, commonjs
+/// modules and scripts using global variables.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// import foo from 'foo';
+/// export default foo;
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// (function (global, factory) {
+///     if (typeof define === "function" && define.amd) {
+///         define(["exports", "foo"], factory);
+///     } else if (typeof exports !== "undefined") {
+///         factory(exports, require("foo"));
+///     } else {
+///         var mod = { exports: {} };
+///         factory(mod.exports, global.foo);
+///         global.MyLib = mod.exports;
+///     }
+/// })(this, function (exports, _foo) {
+///     // Same as amd
+/// });
+/// ```
+pub fn umd(helpers: Arc<Helpers>, config: Config) -> impl Fold<Module> {
+    Umd { helpers, config }
+}
+
+struct Umd {
+    helpers: Arc<Helpers>,
+    config: Config,
+}
+
+impl Fold<Module> for Umd {
+    fn fold(&mut self, module: Module) -> Module {
+        let mut deps = Deps::default();
+        let body = fold_module_items(
+            &self.helpers,
+            common_js::Config {
+                strict: self.config.strict,
+                no_interop: self.config.no_interop,
+                // Dependencies are loaded before the factory is called.
+                lazy: false,
+            },
+            &mut deps,
+            &mut Scope::default(),
+            module.body,
+        );
+        let uses_exports = body.uses_exports;
+
+        // define("id", ["exports", "foo"], factory);
+        let define = Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            callee: quote_ident!("define").as_callee(),
+            args: self
+                .config
+                .module_id
+                .iter()
+                .map(|id| Lit::Str(quote_str!(&**id)).as_arg())
+                .chain(iter::once(deps.array(uses_exports).as_arg()))
+                .chain(iter::once(quote_ident!("factory").as_arg()))
+                .collect(),
+        });
+
+        // factory(exports, require("foo"));
+        let exports = if uses_exports {
+            Some(quote_ident!("exports").as_arg())
+        } else {
+            None
+        };
+        let common_js = call_factory(
+            exports
+                .into_iter()
+                .chain(deps.srcs().map(|src| require(src.clone()).as_arg()))
+                .collect(),
+        );
+
+        // factory(mod.exports, global.foo);
+        let mod_exports = if uses_exports {
+            Some(quote_expr!(DUMMY_SP, "mod.exports").as_arg())
+        } else {
+            None
+        };
+        let globals = deps.params.iter().map(|dep| {
+            let name = match self.config.globals.get(&*dep.0.value) {
+                Some(name) => name.clone(),
+                None => name_for_src(&dep.0.value),
+            };
+            global(&name).as_arg()
+        });
+        let mut browser = vec![call_factory(mod_exports.into_iter().chain(globals).collect())];
+        if uses_exports {
+            browser.insert(0, quote_stmt!(DUMMY_SP, "var mod = { exports: {} };"));
+            if let Some(ref name) = self.config.global_name {
+                // global.MyLib = mod.exports;
+                browser.push(
+                    Expr::Assign(AssignExpr {
+                        span: DUMMY_SP,
+                        op: op!("="),
+                        left: PatOrExpr::Expr(global(name)),
+                        right: box quote_expr!(DUMMY_SP, "mod.exports"),
+                    })
+                    .into_stmt(),
+                );
+            }
+        }
+
+        let wrapper = Expr::Fn(FnExpr {
+            ident: None,
+            function: Function {
+                params: vec![param("global"), param("factory")],
+                span: DUMMY_SP,
+                body: Some(BlockStmt {
+                    span: DUMMY_SP,
+                    stmts: vec![Stmt::If(IfStmt {
+                        span: DUMMY_SP,
+                        test: box quote_expr!(
+                            DUMMY_SP,
+                            "typeof define === 'function' && define.amd"
+                        ),
+                        cons: box block(vec![define.into_stmt()]),
+                        alt: Some(box Stmt::If(IfStmt {
+                            span: DUMMY_SP,
+                            test: box quote_expr!(DUMMY_SP, "typeof exports !== 'undefined'"),
+                            cons: box block(vec![common_js]),
+                            alt: Some(box block(browser)),
+                        })),
+                    })],
+                }),
+                generator_token: None,
+                async_token: None,
+                type_params: None,
+                return_type: None,
+            },
+        });
+
+        // (function (global, factory) {})(this, function (exports, _foo) {});
+        let stmt = Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            callee: wrapper.wrap_with_paren().as_callee(),
+            args: vec![
+                Expr::This(ThisExpr { span: DUMMY_SP }).as_arg(),
+                deps.factory(body).as_arg(),
+            ],
+        })
+        .into_stmt();
+
+        Module {
+            span: module.span,
+            body: vec![ModuleItem::Stmt(stmt)],
+            shebang: module.shebang,
+        }
+    }
+}
+
+/// `factory(args);`
+fn call_factory(args: Vec<ExprOrSpread>) -> Stmt {
+    Expr::Call(CallExpr {
+        span: DUMMY_SP,
+        callee: quote_ident!("factory").as_callee(),
+        args,
+    })
+    .into_stmt()
+}
+
+/// `global.My.lib` for `"My.lib"`
+fn global(name: &str) -> Box<Expr> {
+    name.split('.').fold(box Expr::Ident(quote_ident!("global")), |obj, prop| {
+        let is_ident = !prop.starts_with(|c: char| c.is_ascii_digit())
+            && prop.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '$');
+        // global["jquery-ui"]
+        let (prop, computed) = if is_ident {
+            (box Expr::Ident(quote_ident!(prop)), false)
+        } else {
+            (box Expr::Lit(Lit::Str(quote_str!(prop))), true)
+        };
+
+        box Expr::Member(MemberExpr {
+            span: DUMMY_SP,
+            obj: ExprOrSuper::Expr(obj),
+            prop,
+            computed,
+        })
+    })
+}
+
+fn param(name: &str) -> Param {
+    Param {
+        span: DUMMY_SP,
+        decorators: vec![],
+        pat: Pat::Ident(quote_ident!(name)),
+    }
+}
+
+fn block(stmts: Vec<Stmt>) -> Stmt {
+    Stmt::Block(BlockStmt {
+        span: DUMMY_SP,
+        stmts,
+    })
+}
@@ -0,0 +1,62 @@
+use super::*;
+
+fn tr(config: Config) -> impl Fold<Module> {
+    umd(Default::default(), config)
+}
+
+test!(
+    tr(Config {
+        global_name: Some("My.lib".into()),
+        globals: vec![("jquery-ui".into(), "jQuery".into())]
+            .into_iter()
+            .collect(),
+        strict: true,
+        no_interop: true,
+        ..Default::default()
+    }),
+    globals,
+    r#"import $ from 'jquery-ui';
+import 'side';
+export default $;"#,
+    r#"(function (global, factory) {
+    if (typeof define === "function" && define.amd) {
+        define(["exports", "jquery-ui", "side"], factory);
+    } else if (typeof exports !== "undefined") {
+        factory(exports, require("jquery-ui"), require("side"));
+    } else {
+        var mod = {
+            exports: {}
+        };
+        factory(mod.exports, global.jQuery);
+        global.My.lib = mod.exports;
+    }
+})(this, function (exports, _jqueryUi) {
+    "use strict";
+    var $ = _jqueryUi.default;
+    exports.default = $;
+});"#
+);
+
+test!(
+    tr(Config {
+        strict: true,
+        no_interop: true,
+        ..Default::default()
+    }),
+    default_globals,
+    r#"import foo from './foo-bar.js';
+foo();"#,
+    r#"(function (global, factory) {
+    if (typeof define === "function" && define.amd) {
+        define(["./foo-bar.js"], factory);
+    } else if (typeof exports !== "undefined") {
+        factory(require("./foo-bar.js"));
+    } else {
+        factory(global.fooBar);
+    }
+})(this, function (_fooBar) {
+    "use strict";
+    var foo = _fooBar.default;
+    foo();
+});"#
+);
@@ -0,0 +1,638 @@
+use super::common_js::Config;
+use ast::*;
+use crate::{compat::helpers::Helpers, util::ExprFactory};
+use std::{
+    collections::HashSet,
+    sync::{atomic::Ordering, Arc},
+};
+use swc_atoms::JsWord;
+use swc_common::{Fold, FoldWith, DUMMY_SP};
+
+/// Loads modules imported by import and export declarations.
+pub(super) trait Loader {
+    /// Returns an expression which evaluates to the exports of `src`.
+    fn load(&mut self, scope: &mut Scope, src: Str) -> Box<Expr>;
+
+    /// Returns a statement which loads `src` only for its side effects.
+    fn load_for_side_effects(&mut self, scope: &mut Scope, src: Str) -> Option<Stmt>;
+}
+
+/// Statements of a module, converted by [fold_module_items].
+pub(super) struct ModuleBody {
+    /// Statements including directives.
+    pub stmts: Vec<Stmt>,
+    /// True if `stmts` refers to `exports`.
+    pub uses_exports: bool,
+}
+
+/// Converts import and export declarations to statements which use `exports`
+/// and modules returned by `loader`.
+///
+/// All module formats share this, and only differ in how modules are loaded
+/// and how the statements are wrapped.
+pub(super) fn fold_module_items<L: Loader>(
+    helpers: &Arc<Helpers>,
+    config: Config,
+    loader: &mut L,
+    scope: &mut Scope,
+    items: Vec<ModuleItem>,
+) -> ModuleBody {
+    let is_es_module = items.iter().any(|item| match *item {
+        ModuleItem::ModuleDecl(..) => true,
+        _ => false,
+    });
+    let has_exports = items.iter().any(|item| match *item {
+        ModuleItem::ModuleDecl(ModuleDecl::Import(..)) | ModuleItem::Stmt(..) => false,
+        ModuleItem::ModuleDecl(..) => true,
+    });
+
+    let mut folder = ItemFolder {
+        helpers,
+        config,
+        loader,
+        scope,
+        // Hoisted statements. (e.g. exported function declarations)
+        prepend: vec![],
+        stmts: vec![],
+        append: vec![],
+        lazy: vec![],
+    };
+    for item in items {
+        folder.fold_item(item);
+    }
+    let ItemFolder {
+        mut prepend,
+        mut stmts,
+        mut append,
+        lazy,
+        ..
+    } = folder;
+    if !lazy.is_empty() {
+        let mut refs = LazyRefs { refs: lazy };
+        prepend = prepend.fold_with(&mut refs);
+        stmts = stmts.fold_with(&mut refs);
+        append = append.fold_with(&mut refs);
+    }
+
+    let mut body = vec![];
+    if is_es_module {
+        let has_use_strict = match stmts.first() {
+            Some(&Stmt::Expr(ExprStmt {
+                expr: box Expr::Lit(Lit::Str(Str { ref value, .. })),
+                ..
+            })) => value == "use strict",
+            _ => false,
+        };
+        // Directives must come first.
+        if has_use_strict {
+            stmts.remove(0);
+        }
+        body.push(use_strict());
+        if !config.strict {
+            body.push(define_es_module());
+        }
+    }
+    body.extend(prepend);
+    body.extend(stmts);
+    body.extend(append);
+
+    ModuleBody {
+        stmts: body,
+        uses_exports: has_exports || (is_es_module && !config.strict),
+    }
+}
+
+struct ItemFolder<'a, L: Loader> {
+    helpers: &'a Arc<Helpers>,
+    config: Config,
+    loader: &'a mut L,
+    scope: &'a mut Scope,
+    prepend: Vec<Stmt>,
+    stmts: Vec<Stmt>,
+    append: Vec<Stmt>,
+    /// Lazily imported bindings and expressions replacing them.
+    lazy: Vec<(Ident, Box<Expr>)>,
+}
+
+impl<'a, L: Loader> ItemFolder<'a, L> {
+    fn fold_item(&mut self, item: ModuleItem) {
+        let decl = match item {
+            ModuleItem::Stmt(stmt) => {
+                self.stmts.push(stmt);
+                return;
+            }
+            ModuleItem::ModuleDecl(decl) => decl,
+        };
+
+        match decl {
+            ModuleDecl::Import(import) => self.fold_import(import),
+
+            ModuleDecl::ExportDecl(ExportDecl { decl, .. }) => match decl {
+                Decl::Fn(f) => {
+                    let ident = f.ident.clone();
+                    self.prepend.push(export(ident.sym.clone(), box Expr::Ident(ident)));
+                    self.stmts.push(Stmt::Decl(Decl::Fn(f)));
+                }
+                Decl::Class(c) => {
+                    let ident = c.ident.clone();
+                    self.stmts.push(Stmt::Decl(Decl::Class(c)));
+                    self.stmts.push(export(ident.sym.clone(), box Expr::Ident(ident)));
+                }
+                Decl::Var(v) => {
+                    let mut idents = vec![];
+                    for decl in &v.decls {
+                        pat_idents(&decl.name, &mut idents);
+                    }
+                    self.stmts.push(Stmt::Decl(Decl::Var(v)));
+                    for ident in idents {
+                        self.stmts.push(export(ident.sym.clone(), box Expr::Ident(ident)));
+                    }
+                }
+                // Typescript declarations are kept as is.
+                decl => self.stmts.push(Stmt::Decl(decl)),
+            },
+
+            ModuleDecl::ExportDefaultDecl(ExportDefaultDecl { decl, .. }) => match decl {
+                DefaultDecl::Fn(FnExpr {
+                    ident: Some(ident),
+                    function,
+                }) => {
+                    let local = box Expr::Ident(ident.clone());
+                    self.prepend.push(export(js_word!("default"), local));
+                    self.stmts.push(Stmt::Decl(Decl::Fn(FnDecl {
+                        ident,
+                        declare: false,
+                        function,
+                    })));
+                }
+                DefaultDecl::Fn(f) => {
+                    self.stmts.push(export(js_word!("default"), box Expr::Fn(f)));
+                }
+                DefaultDecl::Class(ClassExpr {
+                    ident: Some(ident),
+                    class,
+                }) => {
+                    self.stmts.push(Stmt::Decl(Decl::Class(ClassDecl {
+                        ident: ident.clone(),
+                        declare: false,
+                        class,
+                    })));
+                    self.stmts.push(export(js_word!("default"), box Expr::Ident(ident)));
+                }
+                DefaultDecl::Class(c) => {
+                    self.stmts.push(export(js_word!("default"), box Expr::Class(c)));
+                }
+                DefaultDecl::Var(v) => self.stmts.push(Stmt::Decl(Decl::Var(v))),
+            },
+
+            ModuleDecl::ExportDefaultExpr(ExportDefaultExpr { expr, .. }) => {
+                self.stmts.push(export(js_word!("default"), expr));
+            }
+
+            ModuleDecl::ExportNamed(NamedExport {
+                specifiers,
+                src: None,
+                ..
+            }) => {
+                // Exported bindings may be declared later.
+                for s in specifiers {
+                    let exported = s.exported.unwrap_or_else(|| s.orig.clone());
+                    self.append.push(export(exported.sym, box Expr::Ident(s.orig)));
+                }
+            }
+
+            ModuleDecl::ExportNamed(NamedExport {
+                specifiers,
+                src: Some(src),
+                ..
+            }) => {
+                let src_value = src.value.clone();
+                let obj = self.loader.load(self.scope, src);
+                let tmp = self.bind(&src_value, obj);
+                for s in specifiers {
+                    let exported = s.exported.unwrap_or_else(|| s.orig.clone());
+                    self.stmts.push(export(exported.sym, member(tmp.clone(), s.orig.sym)));
+                }
+            }
+
+            ModuleDecl::ExportAll(ExportAll { src, .. }) => {
+                self.helpers.export_star.store(true, Ordering::SeqCst);
+
+                let obj = self.loader.load(self.scope, src);
+                self.stmts.push(
+                    Expr::Call(CallExpr {
+                        span: DUMMY_SP,
+                        callee: quote_ident!("_exportStar").as_callee(),
+                        args: vec![obj.as_arg(), quote_ident!("exports").as_arg()],
+                    })
+                    .into_stmt(),
+                );
+            }
+        }
+    }
+
+    fn fold_import(&mut self, import: ImportDecl) {
+        if import.specifiers.is_empty() {
+            if let Some(stmt) = self.loader.load_for_side_effects(self.scope, import.src) {
+                self.stmts.push(stmt);
+            }
+            return;
+        }
+
+        let has_default = import.specifiers.iter().any(|s| match *s {
+            ImportSpecifier::Default(..) => true,
+            _ => false,
+        });
+        let has_named = import.specifiers.iter().any(|s| match *s {
+            ImportSpecifier::Specific(..) => true,
+            _ => false,
+        });
+        let namespace = import.specifiers.iter().find_map(|s| match *s {
+            ImportSpecifier::Namespace(ImportStarAs { ref local, .. }) => Some(local.clone()),
+            _ => None,
+        });
+
+        let src = import.src.value.clone();
+        let is_lazy = self.config.lazy && !src.starts_with('.');
+        let obj = self.loader.load(self.scope, import.src);
+        let obj = if self.config.no_interop {
+            obj
+        } else if namespace.is_some() || (has_default && has_named) {
+            self.helpers
+                .interop_require_wildcard
+                .store(true, Ordering::SeqCst);
+            box Expr::Call(CallExpr {
+                span: DUMMY_SP,
+                callee: quote_ident!("_interopRequireWildcard").as_callee(),
+                args: vec![obj.as_arg()],
+            })
+        } else if has_default {
+            self.helpers
+                .interop_require_default
+                .store(true, Ordering::SeqCst);
+            box Expr::Call(CallExpr {
+                span: DUMMY_SP,
+                callee: quote_ident!("_interopRequireDefault").as_callee(),
+                args: vec![obj.as_arg()],
+            })
+        } else {
+            obj
+        };
+
+        if is_lazy {
+            let getter = self.scope.ident_for_src(&src);
+            self.stmts.push(lazy_getter(getter.clone(), obj));
+            // _foo()
+            let module = box Expr::Call(CallExpr {
+                span: DUMMY_SP,
+                callee: getter.as_callee(),
+                args: vec![],
+            });
+
+            for s in import.specifiers {
+                let (local, value) = match s {
+                    ImportSpecifier::Namespace(ImportStarAs { local, .. }) => {
+                        (local, module.clone())
+                    }
+                    ImportSpecifier::Default(ImportDefault { local, .. }) => {
+                        (local, member_of(module.clone(), js_word!("default")))
+                    }
+                    ImportSpecifier::Specific(ImportSpecific {
+                        local, imported, ..
+                    }) => {
+                        let imported = imported.unwrap_or_else(|| local.clone());
+                        (local, member_of(module.clone(), imported.sym))
+                    }
+                };
+                self.lazy.push((local, value));
+            }
+            return;
+        }
+
+        let tmp = match namespace {
+            Some(ns) => {
+                self.stmts.push(var(ns.clone(), obj));
+                ns
+            }
+            None => self.bind(&src, obj),
+        };
+
+        for s in import.specifiers {
+            match s {
+                ImportSpecifier::Namespace(..) => {}
+                ImportSpecifier::Default(ImportDefault { local, .. }) => self
+                    .stmts
+                    .push(var(local, member(tmp.clone(), js_word!("default")))),
+                ImportSpecifier::Specific(ImportSpecific {
+                    local, imported, ..
+                }) => {
+                    let imported = imported.unwrap_or_else(|| local.clone());
+                    self.stmts.push(var(local, member(tmp.clone(), imported.sym)))
+                }
+            }
+        }
+    }
+
+    /// Declares `var _src = obj;` unless `obj` is already a variable, like a
+    /// parameter of the factory function of amd modules.
+    fn bind(&mut self, src: &JsWord, obj: Box<Expr>) -> Ident {
+        match obj {
+            box Expr::Ident(ident) => ident,
+            obj => {
+                let tmp = self.scope.ident_for_src(src);
+                self.stmts.push(var(tmp.clone(), obj));
+                tmp
+            }
+        }
+    }
+}
+
+/// Generates unique names for temporary variables holding imported modules.
+#[derive(Debug, Default)]
+pub(super) struct Scope {
+    used: HashSet<JsWord>,
+}
+
+impl Scope {
+    /// `'./foo/bar-baz.js'` -> `_barBaz`
+    pub fn ident_for_src(&mut self, src: &JsWord) -> Ident {
+        let base = format!("_{}", name_for_src(src));
+
+        let mut sym: JsWord = base.clone().into();
+        let mut cnt = 1;
+        while self.used.contains(&sym) {
+            sym = format!("{}{}", base, cnt).into();
+            cnt += 1;
+        }
+        self.used.insert(sym.clone());
+
+        quote_ident!(sym)
+    }
+}
+
+/// `'./foo/bar-baz.js'` -> `barBaz`
+pub(super) fn name_for_src(src: &str) -> String {
+    let stem = src
+        .rsplit('/')
+        .next()
+        .unwrap_or(src)
+        .split('.')
+        .next()
+        .unwrap_or("");
+
+    let mut name = String::new();
+    let mut upper = false;
+    for c in stem.chars() {
+        if c.is_alphanumeric() || c == '_' || c == '$' {
+            if upper {
+                name.extend(c.to_uppercase());
+            } else {
+                name.push(c);
+            }
+            upper = false;
+        } else {
+            upper = !name.is_empty();
+        }
+    }
+    name
+}
+
+/// Returns `require('src')`.
+pub(super) fn require(src: Str) -> Box<Expr> {
+    box Expr::Call(CallExpr {
+        span: DUMMY_SP,
+        callee: quote_ident!("require").as_callee(),
+        args: vec![Lit::Str(src).as_arg()],
+    })
+}
+
+/// Returns `var name = init;`
+pub(super) fn var(name: Ident, init: Box<Expr>) -> Stmt {
+    Stmt::Decl(Decl::Var(VarDecl {
+        span: DUMMY_SP,
+        kind: VarDeclKind::Var,
+        declare: false,
+        decls: vec![VarDeclarator {
+            span: DUMMY_SP,
+            name: Pat::Ident(name),
+            init: Some(init),
+        }],
+    }))
+}
+
+/// Returns `obj.prop`.
+pub(super) fn member(obj: Ident, prop: JsWord) -> Box<Expr> {
+    member_of(box Expr::Ident(obj), prop)
+}
+
+fn member_of(obj: Box<Expr>, prop: JsWord) -> Box<Expr> {
+    box Expr::Member(MemberExpr {
+        span: DUMMY_SP,
+        obj: ExprOrSuper::Expr(obj),
+        prop: box Expr::Ident(quote_ident!(prop)),
+        computed: false,
+    })
+}
+
+/// Returns a function which loads the module on the first call.
+///
+/// ```js
+/// function _foo() {
+///     var data = require('foo');
+///     _foo = function () {
+///         return data;
+///     };
+///     return data;
+/// }
+/// ```
+fn lazy_getter(name: Ident, obj: Box<Expr>) -> Stmt {
+    let data = quote_ident!("data");
+    let function = |stmts| Function {
+        params: vec![],
+        span: DUMMY_SP,
+        body: Some(BlockStmt {
+            span: DUMMY_SP,
+            stmts,
+        }),
+        generator_token: None,
+        async_token: None,
+        type_params: None,
+        return_type: None,
+    };
+    let ret = Stmt::Return(ReturnStmt {
+        span: DUMMY_SP,
+        arg: Some(box Expr::Ident(data.clone())),
+    });
+
+    let cache = Expr::Assign(AssignExpr {
+        span: DUMMY_SP,
+        op: op!("="),
+        left: PatOrExpr::Expr(box Expr::Ident(name.clone())),
+        right: box Expr::Fn(FnExpr {
+            ident: None,
+            function: function(vec![ret.clone()]),
+        }),
+    })
+    .into_stmt();
+
+    Stmt::Decl(Decl::Fn(FnDecl {
+        ident: name,
+        declare: false,
+        function: function(vec![var(data, obj), cache, ret]),
+    }))
+}
+
+/// Replaces references to lazily imported bindings.
+///
+/// Bindings are identified by their names and syntax contexts.
+struct LazyRefs {
+    refs: Vec<(Ident, Box<Expr>)>,
+}
+
+impl LazyRefs {
+    fn get(&self, i: &Ident) -> Option<Box<Expr>> {
+        self.refs
+            .iter()
+            .find(|r| r.0.sym == i.sym && r.0.span.ctxt() == i.span.ctxt())
+            .map(|r| r.1.clone())
+    }
+}
+
+impl Fold<Expr> for LazyRefs {
+    fn fold(&mut self, e: Expr) -> Expr {
+        match e {
+            Expr::Ident(i) => match self.get(&i) {
+                Some(value) => *value,
+                None => Expr::Ident(i),
+            },
+            // `.foo` in `a.foo` is not a reference.
+            Expr::Member(MemberExpr {
+                span,
+                obj,
+                prop,
+                computed: false,
+            }) => Expr::Member(MemberExpr {
+                span,
+                obj: obj.fold_with(self),
+                prop,
+                computed: false,
+            }),
+            _ => e.fold_children(self),
+        }
+    }
+}
+
+/// `foo()` is converted to `(0, _foo().default)()` so `this` is not changed.
+impl Fold<CallExpr> for LazyRefs {
+    fn fold(&mut self, call: CallExpr) -> CallExpr {
+        let value = match call.callee {
+            ExprOrSuper::Expr(box Expr::Ident(ref i)) => self.get(i),
+            _ => None,
+        };
+        match value {
+            Some(value) => CallExpr {
+                callee: Expr::Seq(SeqExpr {
+                    span: DUMMY_SP,
+                    exprs: vec![
+                        box Expr::Lit(Lit::Num(Number {
+                            span: DUMMY_SP,
+                            value: 0.0,
+                            raw: None,
+                        })),
+                        value,
+                    ],
+                })
+                .wrap_with_paren()
+                .as_callee(),
+                args: call.args.fold_with(self),
+                ..call
+            },
+            None => call.fold_children(self),
+        }
+    }
+}
+
+/// `{ foo }` -> `{ foo: _foo().foo }`
+impl Fold<Prop> for LazyRefs {
+    fn fold(&mut self, prop: Prop) -> Prop {
+        match prop {
+            Prop::Shorthand(i) => match self.get(&i) {
+                Some(value) => Prop::KeyValue(KeyValueProp {
+                    key: PropName::Ident(i),
+                    value,
+                }),
+                None => Prop::Shorthand(i),
+            },
+            _ => prop.fold_children(self),
+        }
+    }
+}
+
+/// Returns `exports.name = value;`
+pub(super) fn export(name: JsWord, value: Box<Expr>) -> Stmt {
+    Expr::Assign(AssignExpr {
+        span: DUMMY_SP,
+        op: op!("="),
+        left: PatOrExpr::Expr(member(quote_ident!("exports"), name)),
+        right: value,
+    })
+    .into_stmt()
+}
+
+/// Returns `Object.defineProperty(exports, "__esModule", { value: true });`
+pub(super) fn define_es_module() -> Stmt {
+    Expr::Call(CallExpr {
+        span: DUMMY_SP,
+        callee: member_expr!(DUMMY_SP, Object.defineProperty).as_callee(),
+        args: vec![
+            quote_ident!("exports").as_arg(),
+            Lit::Str(quote_str!("__esModule")).as_arg(),
+            ObjectLit {
+                span: DUMMY_SP,
+                props: vec![PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                    key: PropName::Ident(quote_ident!("value")),
+                    value: box Expr::Lit(Lit::Bool(Bool {
+                        span: DUMMY_SP,
+                        value: true,
+                    })),
+                }))],
+            }
+            .as_arg(),
+        ],
+    })
+    .into_stmt()
+}
+
+/// Returns `"use strict";`
+pub(super) fn use_strict() -> Stmt {
+    Lit::Str(quote_str!("use strict")).into_stmt()
+}
+
+/// Collects identifiers bound by `pat`.
+pub(super) fn pat_idents(pat: &Pat, idents: &mut Vec<Ident>) {
+    match *pat {
+        Pat::Ident(ref i) => idents.push(i.clone()),
+        Pat::Array(ArrayPat { ref elems, .. }) => {
+            for elem in elems.iter().filter_map(|e| e.as_ref()) {
+                pat_idents(elem, idents)
+            }
+        }
+        Pat::Rest(RestPat { ref arg, .. }) => pat_idents(arg, idents),
+        Pat::Object(ObjectPat { ref props, .. }) => {
+            for prop in props {
+                match *prop {
+                    ObjectPatProp::KeyValue(KeyValuePatProp { ref value, .. }) => {
+                        pat_idents(value, idents)
+                    }
+                    ObjectPatProp::Assign(AssignPatProp { ref key, .. }) => {
+                        idents.push(key.clone())
+                    }
+                    ObjectPatProp::Rest(RestPat { ref arg, .. }) => pat_idents(arg, idents),
+                }
+            }
+        }
+        Pat::Assign(AssignPat { ref left, .. }) => pat_idents(left, idents),
+        Pat::TsTyped(TsTypedPat { ref pat, .. }) => pat_idents(pat, idents),
+        Pat::Expr(..) => {}
+    }
+}
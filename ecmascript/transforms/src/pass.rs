@@ -0,0 +1,74 @@
+//! Passes which can be stored as trait objects.
+
+use ast::Module;
+use swc_common::Fold;
+
+/// Object safe version of `Fold<Module>`, used to create pipelines at runtime
+/// like from a config file.
+///
+/// This is implemented for all `Fold<Module>`s.
+pub trait Pass {
+    fn process(&mut self, module: Module) -> Module;
+}
+
+impl<F> Pass for F
+where
+    F: Fold<Module>,
+{
+    default fn process(&mut self, module: Module) -> Module {
+        self.fold(module)
+    }
+}
+
+impl Pass for Box<Pass> {
+    fn process(&mut self, module: Module) -> Module {
+        (**self).process(module)
+    }
+}
+
+/// Passes applied in order. Created by [chain].
+pub struct Chain {
+    passes: Vec<Box<Pass>>,
+}
+
+/// Creates a pass which applies `passes` in order.
+pub fn chain(passes: Vec<Box<Pass>>) -> Chain {
+    Chain { passes }
+}
+
+impl Chain {
+    /// Adds a pass which is applied after other passes.
+    pub fn push(&mut self, pass: Box<Pass>) {
+        self.passes.push(pass)
+    }
+}
+
+impl Fold<Module> for Chain {
+    fn fold(&mut self, module: Module) -> Module {
+        self.passes
+            .iter_mut()
+            .fold(module, |module, pass| pass.process(module))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fixer, simplifier};
+
+    #[test]
+    fn chained() {
+        crate::tests::Tester::run(crate::tests::syntax(), |tester| {
+            let module = tester.apply_transform(fixer(), "input.js", "use(1 + 2);")?;
+
+            let mut chained = chain(vec![box simplifier()]);
+            chained.push(box fixer());
+            let mut pass: Box<Pass> = box chained;
+            let module = pass.process(module);
+
+            assert_eq!(tester.print(&module).trim(), "use(3);");
+
+            Ok(())
+        });
+    }
+}
@@ -0,0 +1,170 @@
+//! Runs named passes in order, recording how long each pass takes.
+
+use ast::*;
+use std::{
+    fmt::Write,
+    time::{Duration, Instant},
+};
+use swc_common::{Fold, Visit, VisitWith};
+
+/// Passes with names, applied in the order of [PassManager::add].
+///
+/// # Example
+///
+/// ```ignore
+/// let mut passes = PassManager::default();
+/// passes.add("es2015", compat::es2015(&helpers)).add("simplifier", simplifier());
+///
+/// let module = passes.run(module);
+/// println!("{}", passes.report());
+/// ```
+#[derive(Default)]
+pub struct PassManager {
+    passes: Vec<(String, Box<Fold<Module>>)>,
+    timings: Vec<PassTiming>,
+}
+
+/// Recorded by the last [PassManager::run].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PassTiming {
+    pub name: String,
+    /// Wall time of the pass.
+    pub time: Duration,
+    /// Number of statements, expressions and patterns after the pass.
+    pub nodes: usize,
+}
+
+impl PassManager {
+    /// Adds a pass which is applied after passes already added.
+    pub fn add<P>(&mut self, name: &str, pass: P) -> &mut Self
+    where
+        P: Fold<Module> + 'static,
+    {
+        self.passes.push((name.into(), box pass));
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.passes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.passes.is_empty()
+    }
+
+    /// Applies passes to `module` in order.
+    pub fn run(&mut self, mut module: Module) -> Module {
+        self.timings.clear();
+
+        for &mut (ref name, ref mut pass) in &mut self.passes {
+            let start = Instant::now();
+            module = pass.fold(module);
+            let time = start.elapsed();
+
+            let mut counter = NodeCounter::default();
+            module.visit_with(&mut counter);
+
+            self.timings.push(PassTiming {
+                name: name.clone(),
+                time,
+                nodes: counter.count,
+            });
+        }
+
+        module
+    }
+
+    /// Timings of passes recorded by the last [PassManager::run].
+    pub fn timings(&self) -> &[PassTiming] {
+        &self.timings
+    }
+
+    /// Formats timings as a table.
+    pub fn report(&self) -> String {
+        let width = self
+            .timings
+            .iter()
+            .map(|t| t.name.len())
+            .chain(Some("pass".len()))
+            .max()
+            .unwrap();
+
+        let mut buf = String::new();
+        let _ = writeln!(buf, "{:w$}  {:>12}  {:>8}", "pass", "time", "nodes", w = width);
+        let mut total = Duration::default();
+        for t in &self.timings {
+            total += t.time;
+            let _ = writeln!(
+                buf,
+                "{:w$}  {:>12}  {:>8}",
+                t.name,
+                format_duration(t.time),
+                t.nodes,
+                w = width
+            );
+        }
+        let _ = writeln!(buf, "{:w$}  {:>12}", "total", format_duration(total), w = width);
+        buf
+    }
+}
+
+impl Fold<Module> for PassManager {
+    fn fold(&mut self, module: Module) -> Module {
+        self.run(module)
+    }
+}
+
+fn format_duration(d: Duration) -> String {
+    let ms = d.as_secs() as f64 * 1e3 + f64::from(d.subsec_nanos()) / 1e6;
+    format!("{:.3}ms", ms)
+}
+
+#[derive(Default)]
+struct NodeCounter {
+    count: usize,
+}
+
+macro_rules! count {
+    ($T:ty) => {
+        impl Visit<$T> for NodeCounter {
+            fn visit(&mut self, node: &$T) {
+                self.count += 1;
+                node.visit_children(self)
+            }
+        }
+    };
+}
+
+count!(Stmt);
+count!(Expr);
+count!(Pat);
+count!(ModuleDecl);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fixer, simplifier};
+
+    #[test]
+    fn timings() {
+        crate::tests::Tester::run(crate::tests::syntax(), |tester| {
+            let module = tester.apply_transform(fixer(), "input.js", "use(1 + 2); use(x);")?;
+
+            let mut passes = PassManager::default();
+            passes.add("simplifier", simplifier()).add("fixer", fixer());
+            let module = passes.run(module);
+
+            assert_eq!(tester.print(&module).trim(), "use(3);\nuse(x);");
+            let names: Vec<_> = passes.timings().iter().map(|t| &*t.name).collect();
+            assert_eq!(names, vec!["simplifier", "fixer"]);
+            // Two statements, two calls, two callees and two arguments.
+            assert_eq!(passes.timings()[0].nodes, 8);
+
+            let report = passes.report();
+            assert!(report.contains("simplifier"));
+            assert!(report.starts_with("pass"));
+
+            Ok(())
+        });
+    }
+}
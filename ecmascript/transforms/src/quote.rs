@@ -18,6 +18,7 @@ macro_rules! quote_str {
             span: $span,
             value: $s.into(),
             has_escape: false,
+            raw: None,
         }
     }};
 }
@@ -32,18 +33,6 @@ macro_rules! mark {
     }};
 }
 
-#[macro_export]
-macro_rules! quote_expr {
-    ($span:expr, null) => {{
-        use ast::*;
-        Expr::Lit(Lit::Null(Null { span: $span }))
-    }};
-
-    ($span:expr, undefined) => {{
-        box Expr::Ident(Ident::new(js_word!("undefined"), $span))
-    }};
-}
-
 /// Creates a member expression.
 ///
 /// # Usage
@@ -110,4 +99,60 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn quote_expr_with_vars() {
+        let obj = member_expr!(span, a.b);
+        let arg = box Expr::Ident(quote_ident!("c"));
+        assert_eq_ignore_span!(
+            quote_expr!(span, "$obj.call(this, $arg)", obj = obj.clone(), arg = arg.clone()),
+            Expr::Call(CallExpr {
+                span,
+                callee: ExprOrSuper::Expr(box Expr::Member(MemberExpr {
+                    span,
+                    obj: ExprOrSuper::Expr(obj),
+                    computed: false,
+                    prop: member_expr!(span, call),
+                })),
+                args: vec![
+                    ExprOrSpread {
+                        spread: None,
+                        expr: box Expr::This(ThisExpr { span }),
+                    },
+                    ExprOrSpread {
+                        spread: None,
+                        expr: arg,
+                    },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn quote_stmt_with_vars() {
+        let value = box quote_expr!(span, "null");
+        assert_eq_ignore_span!(
+            quote_stmt!(
+                span,
+                "var $name = $value || $value;",
+                name = quote_ident!("a"),
+                value = value.clone(),
+            ),
+            Stmt::Decl(Decl::Var(VarDecl {
+                span,
+                kind: VarDeclKind::Var,
+                declare: false,
+                decls: vec![VarDeclarator {
+                    span,
+                    name: Pat::Ident(quote_ident!("a")),
+                    init: Some(box Expr::Bin(BinExpr {
+                        span,
+                        op: BinaryOp::LogicalOr,
+                        left: value.clone(),
+                        right: value,
+                    })),
+                }],
+            }))
+        );
+    }
 }
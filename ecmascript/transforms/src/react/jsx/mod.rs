@@ -0,0 +1,546 @@
+use crate::{compat::helpers::Helpers, util::ExprFactory};
+use ast::*;
+use std::{
+    mem,
+    sync::{atomic::Ordering, Arc},
+};
+use swc_common::{Fold, FoldWith, Span, DUMMY_SP};
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Runtime {
+    /// `React.createElement("div", null)`
+    Classic,
+    /// `_jsx("div", {})`, where `_jsx` is imported from `react/jsx-runtime`.
+    Automatic,
+}
+
+impl Default for Runtime {
+    fn default() -> Self {
+        Runtime::Classic
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    pub runtime: Runtime,
+    /// The function which creates elements for the classic runtime.
+    pub pragma: String,
+    /// The component for fragments of the classic runtime.
+    pub pragma_frag: String,
+    /// The module which the automatic runtime imports `jsx-runtime` from.
+    pub import_source: String,
+    /// Merge spread attributes with `Object.assign` instead of `_extends`.
+    pub use_builtins: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            runtime: Default::default(),
+            pragma: "React.createElement".into(),
+            pragma_frag: "React.Fragment".into(),
+            import_source: "react".into(),
+            use_builtins: false,
+        }
+    }
+}
+
+/// `@babel/plugin-transform-react-jsx`
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// <div className="a" {...props}>
+///     Hello {name}
+/// </div>;
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// React.createElement("div", _extends({ className: "a" }, props), "Hello ", name);
+/// ```
+///
+/// With the automatic runtime, children are passed as the `children` prop
+/// and `key` is passed separately.
+///
+/// ```js
+/// import { jsxs as _jsxs } from "react/jsx-runtime";
+/// _jsxs("div", _extends({ className: "a" }, props, { children: ["Hello ", name] }));
+/// ```
+pub fn jsx(helpers: Arc<Helpers>, options: Options) -> impl Fold<Module> {
+    Jsx {
+        helpers,
+        pragma: member_chain(&options.pragma),
+        pragma_frag: member_chain(&options.pragma_frag),
+        options,
+        imports: Default::default(),
+    }
+}
+
+struct Jsx {
+    helpers: Arc<Helpers>,
+    options: Options,
+    pragma: Box<Expr>,
+    pragma_frag: Box<Expr>,
+    imports: Imports,
+}
+
+/// Functions used by the automatic runtime.
+#[derive(Debug, Default)]
+struct Imports {
+    jsx: bool,
+    jsxs: bool,
+    fragment: bool,
+    /// `createElement` of `react`, which is used if `key` comes after spread
+    /// attributes.
+    create_element: bool,
+}
+
+impl Fold<Module> for Jsx {
+    fn fold(&mut self, module: Module) -> Module {
+        let module = module.fold_children(self);
+
+        // import { jsx as _jsx } from "react/jsx-runtime";
+        let runtime = [
+            (self.imports.jsx, "jsx"),
+            (self.imports.jsxs, "jsxs"),
+            (self.imports.fragment, "Fragment"),
+        ]
+        .iter()
+        .filter(|&&(used, _)| used)
+        .map(|&(_, name)| name)
+        .collect::<Vec<_>>();
+        let mut imports = vec![];
+        if !runtime.is_empty() {
+            imports.push(import(
+                &format!("{}/jsx-runtime", self.options.import_source),
+                &runtime,
+            ));
+        }
+        if self.imports.create_element {
+            imports.push(import(&self.options.import_source, &["createElement"]));
+        }
+        if imports.is_empty() {
+            return module;
+        }
+
+        Module {
+            body: imports
+                .into_iter()
+                .map(|decl| ModuleItem::ModuleDecl(ModuleDecl::Import(decl)))
+                .chain(module.body)
+                .collect(),
+            ..module
+        }
+    }
+}
+
+impl Fold<Expr> for Jsx {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children(self);
+
+        match e {
+            Expr::JSXElement(el) => self.element(*el),
+            Expr::JSXFragment(frag) => self.fragment(frag),
+            _ => e,
+        }
+    }
+}
+
+impl Jsx {
+    fn element(&mut self, el: JSXElement) -> Expr {
+        let name = element_name(el.opening.name);
+        let children = self.children(el.children);
+
+        match self.options.runtime {
+            Runtime::Classic => {
+                let callee = self.pragma.clone();
+                self.create_element(el.span, callee, name, el.opening.attrs, children)
+            }
+            Runtime::Automatic => self.jsx_call(el.span, name, el.opening.attrs, children),
+        }
+    }
+
+    fn fragment(&mut self, frag: JSXFragment) -> Expr {
+        let children = self.children(frag.children);
+
+        match self.options.runtime {
+            Runtime::Classic => {
+                let callee = self.pragma.clone();
+                let name = self.pragma_frag.clone();
+                self.create_element(frag.span, callee, name, vec![], children)
+            }
+            Runtime::Automatic => {
+                self.imports.fragment = true;
+                let name = box Expr::Ident(quote_ident!("_Fragment"));
+                self.jsx_call(frag.span, name, vec![], children)
+            }
+        }
+    }
+
+    /// `React.createElement(name, props, ...children)`
+    fn create_element(
+        &mut self,
+        span: Span,
+        callee: Box<Expr>,
+        name: Box<Expr>,
+        attrs: Vec<JSXAttrOrSpread>,
+        children: Vec<ExprOrSpread>,
+    ) -> Expr {
+        let props = self
+            .props(attrs, vec![])
+            .unwrap_or_else(|| box Expr::Lit(Lit::Null(Null { span: DUMMY_SP })));
+
+        Expr::Call(CallExpr {
+            span,
+            callee: callee.as_callee(),
+            args: vec![name.as_arg(), props.as_arg()]
+                .into_iter()
+                .chain(children)
+                .collect(),
+        })
+    }
+
+    /// `_jsx(name, { ...props, children }, key)`
+    fn jsx_call(
+        &mut self,
+        span: Span,
+        name: Box<Expr>,
+        mut attrs: Vec<JSXAttrOrSpread>,
+        children: Vec<ExprOrSpread>,
+    ) -> Expr {
+        let key_pos = attrs.iter().position(|attr| match *attr {
+            JSXAttrOrSpread::JSXAttr(box JSXAttr {
+                name: JSXAttrName::Ident(ref i),
+                ..
+            }) => &*i.sym == "key",
+            _ => false,
+        });
+
+        // `key` may be overridden by spread attributes before it, so they can't
+        // be separated.
+        let key_after_spread = key_pos.map_or(false, |pos| {
+            attrs[..pos].iter().any(|attr| match *attr {
+                JSXAttrOrSpread::SpreadElement(..) => true,
+                _ => false,
+            })
+        });
+        if key_after_spread {
+            self.imports.create_element = true;
+            let callee = box Expr::Ident(quote_ident!("_createElement"));
+            return self.create_element(span, callee, name, attrs, children);
+        }
+
+        let key = key_pos.map(|pos| match attrs.remove(pos) {
+            JSXAttrOrSpread::JSXAttr(attr) => self.attr_value(attr.value),
+            _ => unreachable!(),
+        });
+
+        let is_static = children.len() > 1;
+        let children = match children.len() {
+            0 => None,
+            1 if children[0].spread.is_none() => Some(children.into_iter().next().unwrap().expr),
+            _ => Some(box Expr::Array(ArrayLit {
+                span: DUMMY_SP,
+                elems: children.into_iter().map(Some).collect(),
+            })),
+        };
+        let children = children.map(|value| {
+            PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                key: PropName::Ident(quote_ident!("children")),
+                value,
+            }))
+        });
+        let props = self
+            .props(attrs, children.into_iter().collect())
+            .unwrap_or_else(|| box object(vec![]));
+
+        let callee = if is_static {
+            self.imports.jsxs = true;
+            quote_ident!("_jsxs")
+        } else {
+            self.imports.jsx = true;
+            quote_ident!("_jsx")
+        };
+
+        Expr::Call(CallExpr {
+            span,
+            callee: callee.as_callee(),
+            args: vec![name.as_arg(), props.as_arg()]
+                .into_iter()
+                .chain(key.map(|key| key.as_arg()))
+                .collect(),
+        })
+    }
+
+    /// Creates the props object from attributes, followed by `extra`.
+    ///
+    /// Returns `None` if there's no prop.
+    fn props(
+        &mut self,
+        attrs: Vec<JSXAttrOrSpread>,
+        extra: Vec<PropOrSpread>,
+    ) -> Option<Box<Expr>> {
+        let first_is_spread = match attrs.first() {
+            Some(&JSXAttrOrSpread::SpreadElement(..)) => true,
+            _ => false,
+        };
+
+        // Object literals and spread attributes.
+        let mut objects = vec![];
+        let mut props = vec![];
+        for attr in attrs {
+            match attr {
+                JSXAttrOrSpread::JSXAttr(attr) => {
+                    let JSXAttr { name, value, .. } = *attr;
+                    props.push(PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                        key: attr_name(name),
+                        value: self.attr_value(value),
+                    })));
+                }
+                JSXAttrOrSpread::SpreadElement(SpreadElement { expr, .. }) => {
+                    if !props.is_empty() {
+                        objects.push(box object(mem::replace(&mut props, vec![])));
+                    }
+                    objects.push(expr);
+                }
+            }
+        }
+        props.extend(extra);
+        if !props.is_empty() {
+            objects.push(box object(props));
+        }
+
+        if objects.len() <= 1 {
+            return objects.pop();
+        }
+
+        // _extends({}, props, { a: 1 })
+        if first_is_spread {
+            objects.insert(0, box object(vec![]));
+        }
+        let callee = if self.options.use_builtins {
+            member_chain("Object.assign")
+        } else {
+            self.helpers.extends.store(true, Ordering::SeqCst);
+            box Expr::Ident(quote_ident!("_extends"))
+        };
+        Some(box Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            callee: callee.as_callee(),
+            args: objects.into_iter().map(|obj| obj.as_arg()).collect(),
+        }))
+    }
+
+    fn attr_value(&mut self, value: Option<JSXAttrValue>) -> Box<Expr> {
+        match value {
+            // <input disabled />
+            None => box Expr::Lit(Lit::Bool(Bool {
+                span: DUMMY_SP,
+                value: true,
+            })),
+            Some(JSXAttrValue::Lit(Lit::Str(s))) => box Expr::Lit(Lit::Str(Str {
+                value: join_attr_lines(&s.value).into(),
+                has_escape: false,
+                raw: None,
+                ..s
+            })),
+            Some(JSXAttrValue::Lit(lit)) => box Expr::Lit(lit),
+            Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+                expr: JSXExpr::Expr(expr),
+                ..
+            })) => expr,
+            Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+                expr: JSXExpr::JSXEmptyExpr(..),
+                ..
+            })) => unreachable!("the parser rejects empty expressions in attributes"),
+            Some(JSXAttrValue::JSXElement(el)) => box self.element(*el),
+            Some(JSXAttrValue::JSXFragment(frag)) => box self.fragment(frag),
+        }
+    }
+
+    fn children(&mut self, children: Vec<JSXElementChild>) -> Vec<ExprOrSpread> {
+        children
+            .into_iter()
+            .filter_map(|child| match child {
+                JSXElementChild::JSXText(text) => jsx_text(&text.value).map(|value| {
+                    Lit::Str(quote_str!(text.span, value)).as_arg()
+                }),
+                JSXElementChild::JSXExprContainer(JSXExprContainer {
+                    expr: JSXExpr::Expr(expr),
+                    ..
+                }) => Some(expr.as_arg()),
+                // {/* comment */}
+                JSXElementChild::JSXExprContainer(..) => None,
+                JSXElementChild::JSXSpreadChild(JSXSpreadChild { span, expr }) => {
+                    Some(ExprOrSpread {
+                        spread: Some(span),
+                        expr,
+                    })
+                }
+                JSXElementChild::JSXElement(el) => Some(self.element(*el).as_arg()),
+                JSXElementChild::JSXFragment(frag) => Some(self.fragment(frag).as_arg()),
+            })
+            .collect()
+    }
+}
+
+/// `"div"` for `<div>`, and `Foo.Bar` for `<Foo.Bar>`
+fn element_name(name: JSXElementName) -> Box<Expr> {
+    match name {
+        JSXElementName::Ident(i) => {
+            // Lowercase names are intrinsic elements like `div`.
+            let is_tag =
+                i.sym.starts_with(|c: char| c.is_ascii_lowercase()) || i.sym.contains('-');
+            if &*i.sym == "this" {
+                box Expr::This(ThisExpr { span: i.span })
+            } else if is_tag {
+                box Expr::Lit(Lit::Str(quote_str!(i.span, i.sym)))
+            } else {
+                box Expr::Ident(i)
+            }
+        }
+        JSXElementName::JSXMemberExpr(e) => jsx_member(e),
+        JSXElementName::JSXNamespacedName(JSXNamespacedName { ns, name }) => box Expr::Lit(
+            Lit::Str(quote_str!(format!("{}:{}", ns.sym, name.sym))),
+        ),
+    }
+}
+
+fn jsx_member(e: JSXMemberExpr) -> Box<Expr> {
+    let obj = match e.obj {
+        JSXObject::Ident(i) => {
+            if &*i.sym == "this" {
+                box Expr::This(ThisExpr { span: i.span })
+            } else {
+                box Expr::Ident(i)
+            }
+        }
+        JSXObject::JSXMemberExpr(box e) => jsx_member(e),
+    };
+
+    box Expr::Member(MemberExpr {
+        span: DUMMY_SP,
+        obj: ExprOrSuper::Expr(obj),
+        prop: box Expr::Ident(e.prop),
+        computed: false,
+    })
+}
+
+/// `aria-label` and `xlink:href` are not valid identifiers.
+fn attr_name(name: JSXAttrName) -> PropName {
+    match name {
+        JSXAttrName::Ident(i) => {
+            if i.sym.contains('-') {
+                PropName::Str(quote_str!(i.span, i.sym))
+            } else {
+                PropName::Ident(i)
+            }
+        }
+        JSXAttrName::JSXNamespacedName(JSXNamespacedName { ns, name }) => {
+            PropName::Str(quote_str!(format!("{}:{}", ns.sym, name.sym)))
+        }
+    }
+}
+
+/// Removes whitespaces around line breaks in text, and joins the lines with a
+/// space.
+///
+/// Returns `None` if the text is empty after trimming.
+fn jsx_text(text: &str) -> Option<String> {
+    let text = text.replace("\r\n", "\n").replace('\r', "\n");
+    let lines: Vec<_> = text.split('\n').collect();
+    let last_non_empty = lines
+        .iter()
+        .rposition(|line| line.chars().any(|c| c != ' ' && c != '\t'))
+        .unwrap_or(0);
+
+    let mut buf = String::new();
+    for (i, line) in lines.iter().enumerate() {
+        let line = line.replace('\t', " ");
+        let mut line = &*line;
+        if i != 0 {
+            line = line.trim_start_matches(' ');
+        }
+        if i != lines.len() - 1 {
+            line = line.trim_end_matches(' ');
+        }
+
+        if !line.is_empty() {
+            buf.push_str(line);
+            if i != last_non_empty {
+                buf.push(' ');
+            }
+        }
+    }
+
+    if buf.is_empty() {
+        None
+    } else {
+        Some(buf)
+    }
+}
+
+/// Replaces a line break and following whitespaces in a string attribute with
+/// a space.
+fn join_attr_lines(s: &str) -> String {
+    let mut buf = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\n' || chars.peek().map_or(true, |c| !c.is_whitespace()) {
+            buf.push(c);
+            continue;
+        }
+        while chars.peek().map_or(false, |c| c.is_whitespace()) {
+            chars.next();
+        }
+        buf.push(' ');
+    }
+    buf
+}
+
+/// `React.createElement` for `"React.createElement"`
+fn member_chain(s: &str) -> Box<Expr> {
+    let mut parts = s.split('.');
+    let first = box Expr::Ident(quote_ident!(parts.next().unwrap()));
+
+    parts.fold(first, |obj, prop| {
+        box Expr::Member(MemberExpr {
+            span: DUMMY_SP,
+            obj: ExprOrSuper::Expr(obj),
+            prop: box Expr::Ident(quote_ident!(prop)),
+            computed: false,
+        })
+    })
+}
+
+fn object(props: Vec<PropOrSpread>) -> Expr {
+    Expr::Object(ObjectLit {
+        span: DUMMY_SP,
+        props,
+    })
+}
+
+/// `import { jsx as _jsx } from "src";`
+fn import(src: &str, names: &[&str]) -> ImportDecl {
+    ImportDecl {
+        span: DUMMY_SP,
+        specifiers: names
+            .iter()
+            .map(|name| {
+                ImportSpecifier::Specific(ImportSpecific {
+                    span: DUMMY_SP,
+                    local: quote_ident!(format!("_{}", name)),
+                    imported: Some(quote_ident!(*name)),
+                })
+            })
+            .collect(),
+        src: quote_str!(src),
+    }
+}
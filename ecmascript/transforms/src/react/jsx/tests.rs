@@ -0,0 +1,78 @@
+use super::*;
+
+fn tr(options: Options) -> impl Fold<Module> {
+    jsx(Default::default(), options)
+}
+
+fn automatic() -> Options {
+    Options {
+        runtime: Runtime::Automatic,
+        ..Default::default()
+    }
+}
+
+test!(
+    tr(Default::default()),
+    classic,
+    r#"<div className="a" {...props} key={id} disabled>{name}<br /></div>;"#,
+    r#"React.createElement("div", _extends({ className: "a" }, props, { key: id, disabled: true }),
+    name, React.createElement("br", null));"#
+);
+
+test!(
+    tr(Default::default()),
+    classic_components,
+    "<Foo.Bar x={<Baz />} aria-label='b' {...props} />; <this.Comp />;",
+    r#"React.createElement(Foo.Bar, _extends({ x: React.createElement(Baz, null),
+    "aria-label": "b" }, props));
+React.createElement(this.Comp, null);"#
+);
+
+test!(
+    tr(Default::default()),
+    classic_spread_first,
+    "<div {...a} b />; <div {...a} />;",
+    r#"React.createElement("div", _extends({}, a, { b: true }));
+React.createElement("div", a);"#
+);
+
+test!(
+    tr(Options {
+        pragma: "h".into(),
+        pragma_frag: "Fragment".into(),
+        use_builtins: true,
+        ..Default::default()
+    }),
+    custom_pragma,
+    "<><a {...b} c /></>;",
+    r#"h(Fragment, null, h("a", Object.assign({}, b, { c: true })));"#
+);
+
+test!(
+    tr(Default::default()),
+    children_whitespace,
+    "<div>
+    hello
+       world  {/* comment */}
+    <b> a </b>
+</div>;",
+    r#"React.createElement("div", null, "hello world  ", React.createElement("b", null, " a "));"#
+);
+
+test!(
+    tr(automatic()),
+    automatic_runtime,
+    "<div key='a' id='b'>text</div>; <ul>{a}{b}</ul>; <><br /></>;",
+    r#"import { jsx as _jsx, jsxs as _jsxs, Fragment as _Fragment } from "react/jsx-runtime";
+_jsx("div", { id: "b", children: "text" }, "a");
+_jsxs("ul", { children: [a, b] });
+_jsx(_Fragment, { children: _jsx("br", {}) });"#
+);
+
+test!(
+    tr(automatic()),
+    automatic_key_after_spread,
+    "<div {...props} key='a' />;",
+    r#"import { createElement as _createElement } from "react";
+_createElement("div", _extends({}, props, { key: "a" }));"#
+);
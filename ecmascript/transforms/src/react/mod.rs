@@ -0,0 +1,3 @@
+pub use self::jsx::jsx;
+
+pub mod jsx;
@@ -0,0 +1,39 @@
+use ast::*;
+use crate::scope::{FoldScoped, ScopedFold, Scopes};
+use swc_common::Fold;
+
+#[cfg(test)]
+mod tests;
+
+/// Marks bindings and references to them with a mark of the scope which
+/// declares them.
+///
+/// Identifiers which refer to the same binding have the same syntax context
+/// after this pass, so transforms can distinguish variables from others with
+/// the same name. Free variables like `window` are not marked.
+///
+/// This should be applied before other transforms, and [hygiene] should be
+/// applied after them.
+///
+/// [hygiene]: crate::hygiene
+pub fn resolver() -> impl Fold<Module> + 'static {
+    ScopedFold::new(Resolver)
+}
+
+#[derive(Debug)]
+struct Resolver;
+
+impl FoldScoped<Ident> for Resolver {
+    fn fold_scoped(&mut self, scopes: &mut Scopes, i: Ident) -> Ident {
+        match scopes.resolve(&i) {
+            Some(depth) => {
+                let mark = scopes.scopes()[depth].mark;
+                Ident {
+                    span: i.span.apply_mark(mark),
+                    ..i
+                }
+            }
+            None => i,
+        }
+    }
+}
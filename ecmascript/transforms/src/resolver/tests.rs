@@ -0,0 +1,44 @@
+use super::*;
+use swc_atoms::JsWord;
+use swc_common::{FoldWith, SyntaxContext, Visit, VisitWith};
+
+#[derive(Default)]
+struct Contexts {
+    idents: Vec<(JsWord, SyntaxContext)>,
+}
+
+impl Visit<Ident> for Contexts {
+    fn visit(&mut self, i: &Ident) {
+        self.idents.push((i.sym.clone(), i.span.ctxt()));
+    }
+}
+
+#[test]
+fn marks() {
+    crate::tests::Tester::run(crate::tests::syntax(), |tester| {
+        let module = tester.apply_transform(
+            crate::fixer(),
+            "input.js",
+            "var a; function f(a) { return a; } use(a.a);",
+        )?;
+        // Spans are dropped by the tester.
+        let module = module.fold_with(&mut resolver());
+
+        let mut contexts = Contexts::default();
+        module.visit_with(&mut contexts);
+        // a, f, a, a, use, a, a
+        let ctxts: Vec<_> = contexts.idents.into_iter().map(|(_, ctxt)| ctxt).collect();
+        assert_eq!(ctxts.len(), 7);
+
+        assert_ne!(ctxts[0], SyntaxContext::empty());
+        assert_eq!(ctxts[0], ctxts[1]);
+        assert_eq!(ctxts[0], ctxts[5]);
+        assert_ne!(ctxts[0], ctxts[2]);
+        assert_eq!(ctxts[2], ctxts[3]);
+        // Free variables and property names are not marked.
+        assert_eq!(ctxts[4], SyntaxContext::empty());
+        assert_eq!(ctxts[6], SyntaxContext::empty());
+
+        Ok(())
+    });
+}
@@ -1,5 +1,8 @@
 use ast::*;
-use swc_common::{Fold, FoldWith};
+use crate::util::{pat_ids, var_ids, VarCollector};
+use std::collections::HashMap;
+use swc_atoms::JsWord;
+use swc_common::{Fold, FoldWith, Mark, Span, SyntaxContext, VisitWith};
 
 pub trait FoldScope<T> {
     /// `scope`: Scope which contains `node`.
@@ -51,7 +54,10 @@ where
     fn fold(&mut self, n: Function) -> Function {
         let mut new_scope = Scope::new(Some(self.cur_scope), ScopeKind::Fn);
         // TODO: Add
-        let body = FoldScope::<BlockStmt>::fold_scope(&mut self.folder, &mut new_scope, n.body);
+        let folder = &mut self.folder;
+        let body = n
+            .body
+            .map(|body| FoldScope::<BlockStmt>::fold_scope(folder, &mut new_scope, body));
         Function { body, ..n }
     }
 }
@@ -88,8 +94,529 @@ impl<'a> Scope<'a> {
 pub enum ScopeKind {
     Fn,
     Block,
+    /// The top-level scope of a module, which works like a function scope.
+    Module,
 }
 
+/// Folder which maintains scopes for `folder`, calling
+/// `FoldScoped::fold_scoped` of the folder for each node.
+///
+/// Identifiers which are neither bindings nor references, like property names
+/// and labels, are not folded.
+///
+/// # Example
+///
+/// ```ignore
+/// impl FoldScoped<Expr> for InlineConsts {
+///     fn fold_scoped(&mut self, scopes: &mut Scopes, e: Expr) -> Expr {
+///         match e {
+///             // Skip shadowed variables.
+///             Expr::Ident(ref i) if scopes.resolve(i).is_some() => e,
+///             ...
+///         }
+///     }
+/// }
+///
+/// let module = module.fold_with(&mut ScopedFold::new(InlineConsts));
+/// ```
+#[derive(Debug)]
+pub struct ScopedFold<F> {
+    pub folder: F,
+    scopes: Scopes,
+}
+
+impl<F> ScopedFold<F> {
+    pub fn new(folder: F) -> Self {
+        ScopedFold {
+            folder,
+            scopes: Default::default(),
+        }
+    }
+}
+
+/// Called by [ScopedFold] with scopes containing `node`, after children of
+/// `node` are folded.
+pub trait FoldScoped<T> {
+    fn fold_scoped(&mut self, scopes: &mut Scopes, node: T) -> T;
+}
+
+impl<T, F> FoldScoped<T> for F {
+    default fn fold_scoped(&mut self, _: &mut Scopes, node: T) -> T {
+        node
+    }
+}
+
+/// Stack of scopes, from the global scope to the current scope.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scopes {
+    stack: Vec<ScopeData>,
+}
+
+/// A scope in [Scopes].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScopeData {
+    pub kind: ScopeKind,
+    /// A mark unique to this scope, or the root mark for the global scope.
+    pub mark: Mark,
+    /// Spans of declarations, keyed by bindings.
+    bindings: HashMap<(JsWord, SyntaxContext), Vec<Span>>,
+}
+
+impl ScopeData {
+    fn new(kind: ScopeKind, mark: Mark) -> Self {
+        ScopeData {
+            kind,
+            mark,
+            bindings: Default::default(),
+        }
+    }
+
+    /// Returns true if `ident` is declared in this scope.
+    pub fn has(&self, ident: &Ident) -> bool {
+        self.bindings
+            .contains_key(&(ident.sym.clone(), ident.span.ctxt()))
+    }
+
+    /// Names and syntax contexts of bindings declared in this scope.
+    pub fn bindings(&self) -> impl Iterator<Item = &(JsWord, SyntaxContext)> {
+        self.bindings.keys()
+    }
+
+    /// Spans of identifiers declaring `ident` in this scope, which is empty if
+    /// it's not declared.
+    pub fn decls(&self, ident: &Ident) -> &[Span] {
+        self.bindings
+            .get(&(ident.sym.clone(), ident.span.ctxt()))
+            .map(|spans| &**spans)
+            .unwrap_or(&[])
+    }
+}
+
+impl Default for Scopes {
+    /// Creates scopes with only the global scope.
+    fn default() -> Self {
+        Scopes {
+            stack: vec![ScopeData::new(ScopeKind::Fn, Mark::root())],
+        }
+    }
+}
+
+impl Scopes {
+    pub fn current_scope(&self) -> &ScopeData {
+        self.stack.last().expect("the global scope should not be popped")
+    }
+
+    /// Scopes from the global scope to the current scope, which are indexed
+    /// like [Scopes::resolve].
+    pub fn scopes(&self) -> &[ScopeData] {
+        &self.stack
+    }
+
+    /// Number of scopes, including the global scope.
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Declares `ident` in the current scope.
+    pub fn declare(&mut self, ident: &Ident) {
+        self.stack
+            .last_mut()
+            .expect("the global scope should not be popped")
+            .bindings
+            .entry((ident.sym.clone(), ident.span.ctxt()))
+            .or_insert_with(Vec::new)
+            .push(ident.span);
+    }
+
+    /// Declares `ident` in the nearest function or module scope, like `var`.
+    pub fn declare_var(&mut self, ident: &Ident) {
+        self.stack
+            .iter_mut()
+            .rev()
+            .find(|scope| scope.kind != ScopeKind::Block)
+            .expect("the global scope should be a function scope")
+            .bindings
+            .entry((ident.sym.clone(), ident.span.ctxt()))
+            .or_insert_with(Vec::new)
+            .push(ident.span);
+    }
+
+    /// Returns the index of the nearest scope declaring `ident`, where `0` is
+    /// the global scope, or `None` if it's not declared.
+    pub fn resolve(&self, ident: &Ident) -> Option<usize> {
+        self.stack.iter().rposition(|scope| scope.has(ident))
+    }
+
+    fn push(&mut self, kind: ScopeKind) {
+        self.stack.push(ScopeData::new(kind, Mark::fresh(Mark::root())))
+    }
+
+    fn pop(&mut self) {
+        debug_assert!(self.stack.len() > 1, "the global scope should not be popped");
+        self.stack.pop();
+    }
+
+    fn declare_pat(&mut self, pat: &Pat) {
+        let mut ids = vec![];
+        pat_ids(pat, &mut ids);
+        ids.iter().for_each(|id| self.declare(id));
+    }
+
+    fn declare_vars<T>(&mut self, node: &T)
+    where
+        T: VisitWith<VarCollector>,
+    {
+        var_ids(node).iter().for_each(|id| self.declare_var(id));
+    }
+
+    /// Declares bindings of `let`, `const`, classes and functions.
+    fn declare_block_scoped(&mut self, decl: &Decl) {
+        match *decl {
+            Decl::Class(ClassDecl { ref ident, .. }) | Decl::Fn(FnDecl { ref ident, .. }) => {
+                self.declare(ident)
+            }
+            Decl::Var(ref v) => self.declare_lexical(v),
+            Decl::TsEnum(TsEnumDecl { ref id, .. }) => self.declare(id),
+            Decl::TsInterface(..) | Decl::TsTypeAlias(..) | Decl::TsModule(..) => {}
+        }
+    }
+
+    fn declare_lexical(&mut self, v: &VarDecl) {
+        if v.kind != VarDeclKind::Var {
+            v.decls.iter().for_each(|d| self.declare_pat(&d.name));
+        }
+    }
+}
+
+impl<T, F> Fold<T> for ScopedFold<F>
+where
+    T: FoldWith<Self>,
+{
+    default fn fold(&mut self, node: T) -> T {
+        let node = node.fold_children(self);
+        self.folder.fold_scoped(&mut self.scopes, node)
+    }
+}
+
+impl<F> ScopedFold<F> {
+    /// Folds children of `node` in a new scope, which is initialized by
+    /// `declare`.
+    fn fold_in_scope<T, D>(&mut self, kind: ScopeKind, node: T, declare: D) -> T
+    where
+        T: FoldWith<Self>,
+        D: FnOnce(&mut Scopes, &T),
+    {
+        self.scopes.push(kind);
+        declare(&mut self.scopes, &node);
+        let node = node.fold_children(self);
+        self.scopes.pop();
+
+        self.folder.fold_scoped(&mut self.scopes, node)
+    }
+}
+
+impl<F> Fold<Module> for ScopedFold<F> {
+    fn fold(&mut self, m: Module) -> Module {
+        self.fold_in_scope(ScopeKind::Module, m, |scopes, m| {
+            scopes.declare_vars(&m.body);
+            for item in &m.body {
+                match *item {
+                    ModuleItem::ModuleDecl(ModuleDecl::Import(ref import)) => {
+                        for s in &import.specifiers {
+                            match *s {
+                                ImportSpecifier::Specific(ImportSpecific { ref local, .. })
+                                | ImportSpecifier::Default(ImportDefault { ref local, .. })
+                                | ImportSpecifier::Namespace(ImportStarAs { ref local, .. }) => {
+                                    scopes.declare(local)
+                                }
+                            }
+                        }
+                    }
+                    ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { ref decl, .. }))
+                    | ModuleItem::Stmt(Stmt::Decl(ref decl)) => scopes.declare_block_scoped(decl),
+                    ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                        decl: DefaultDecl::Fn(FnExpr { ident: Some(ref ident), .. }),
+                        ..
+                    }))
+                    | ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                        decl: DefaultDecl::Class(ClassExpr { ident: Some(ref ident), .. }),
+                        ..
+                    })) => scopes.declare(ident),
+                    _ => {}
+                }
+            }
+        })
+    }
+}
+
+impl<F> Fold<Function> for ScopedFold<F> {
+    fn fold(&mut self, f: Function) -> Function {
+        self.fold_in_scope(ScopeKind::Fn, f, |scopes, f| {
+            f.params.iter().for_each(|p| scopes.declare_pat(&p.pat));
+            scopes.declare_vars(&f.body);
+        })
+    }
+}
+
+impl<F> Fold<ArrowExpr> for ScopedFold<F> {
+    fn fold(&mut self, f: ArrowExpr) -> ArrowExpr {
+        self.fold_in_scope(ScopeKind::Fn, f, |scopes, f| {
+            f.params.iter().for_each(|p| scopes.declare_pat(p));
+            scopes.declare_vars(&f.body);
+        })
+    }
+}
+
+/// The name of a function expression is visible only in the function.
+impl<F> Fold<FnExpr> for ScopedFold<F> {
+    fn fold(&mut self, f: FnExpr) -> FnExpr {
+        self.fold_in_scope(ScopeKind::Block, f, |scopes, f| {
+            if let Some(ref ident) = f.ident {
+                scopes.declare(ident)
+            }
+        })
+    }
+}
+
+/// The name of a class expression is visible only in the class.
+impl<F> Fold<ClassExpr> for ScopedFold<F> {
+    fn fold(&mut self, c: ClassExpr) -> ClassExpr {
+        self.fold_in_scope(ScopeKind::Block, c, |scopes, c| {
+            if let Some(ref ident) = c.ident {
+                scopes.declare(ident)
+            }
+        })
+    }
+}
+
+impl<F> Fold<BlockStmt> for ScopedFold<F> {
+    fn fold(&mut self, b: BlockStmt) -> BlockStmt {
+        self.fold_in_scope(ScopeKind::Block, b, |scopes, b| {
+            for stmt in &b.stmts {
+                if let Stmt::Decl(ref decl) = *stmt {
+                    scopes.declare_block_scoped(decl)
+                }
+            }
+        })
+    }
+}
+
+impl<F> Fold<CatchClause> for ScopedFold<F> {
+    fn fold(&mut self, c: CatchClause) -> CatchClause {
+        self.fold_in_scope(ScopeKind::Block, c, |scopes, c| {
+            if let Some(ref param) = c.param {
+                scopes.declare_pat(param)
+            }
+        })
+    }
+}
+
+impl<F> Fold<ForStmt> for ScopedFold<F> {
+    fn fold(&mut self, s: ForStmt) -> ForStmt {
+        self.fold_in_scope(ScopeKind::Block, s, |scopes, s| {
+            if let Some(VarDeclOrExpr::VarDecl(ref v)) = s.init {
+                scopes.declare_lexical(v)
+            }
+        })
+    }
+}
+
+impl<F> Fold<ForInStmt> for ScopedFold<F> {
+    fn fold(&mut self, s: ForInStmt) -> ForInStmt {
+        self.fold_in_scope(ScopeKind::Block, s, |scopes, s| {
+            if let VarDeclOrPat::VarDecl(ref v) = s.left {
+                scopes.declare_lexical(v)
+            }
+        })
+    }
+}
+
+impl<F> Fold<ForOfStmt> for ScopedFold<F> {
+    fn fold(&mut self, s: ForOfStmt) -> ForOfStmt {
+        self.fold_in_scope(ScopeKind::Block, s, |scopes, s| {
+            if let VarDeclOrPat::VarDecl(ref v) = s.left {
+                scopes.declare_lexical(v)
+            }
+        })
+    }
+}
+
+/// Cases of a `switch` share a block scope.
+impl<F> Fold<SwitchStmt> for ScopedFold<F> {
+    fn fold(&mut self, s: SwitchStmt) -> SwitchStmt {
+        self.fold_in_scope(ScopeKind::Block, s, |scopes, s| {
+            for stmt in s.cases.iter().flat_map(|case| &case.cons) {
+                if let Stmt::Decl(ref decl) = *stmt {
+                    scopes.declare_block_scoped(decl)
+                }
+            }
+        })
+    }
+}
+
+impl<F> Fold<GetterProp> for ScopedFold<F> {
+    fn fold(&mut self, p: GetterProp) -> GetterProp {
+        self.fold_in_scope(ScopeKind::Fn, p, |scopes, p| scopes.declare_vars(&p.body))
+    }
+}
+
+impl<F> Fold<SetterProp> for ScopedFold<F> {
+    fn fold(&mut self, p: SetterProp) -> SetterProp {
+        self.fold_in_scope(ScopeKind::Fn, p, |scopes, p| {
+            scopes.declare_pat(&p.param);
+            scopes.declare_vars(&p.body);
+        })
+    }
+}
+
+impl<F> Fold<StaticBlock> for ScopedFold<F> {
+    fn fold(&mut self, b: StaticBlock) -> StaticBlock {
+        self.fold_in_scope(ScopeKind::Fn, b, |scopes, b| scopes.declare_vars(&b.body))
+    }
+}
+
+/// Names of functions and classes exported as default are declared in the
+/// module.
+impl<F> Fold<ExportDefaultDecl> for ScopedFold<F> {
+    fn fold(&mut self, e: ExportDefaultDecl) -> ExportDefaultDecl {
+        let decl = match e.decl {
+            DefaultDecl::Fn(FnExpr { ident, function }) => DefaultDecl::Fn(FnExpr {
+                ident: ident.fold_with(self),
+                function: function.fold_with(self),
+            }),
+            DefaultDecl::Class(ClassExpr { ident, class }) => DefaultDecl::Class(ClassExpr {
+                ident: ident.fold_with(self),
+                class: class.fold_with(self),
+            }),
+            decl => decl.fold_with(self),
+        };
+
+        self.folder
+            .fold_scoped(&mut self.scopes, ExportDefaultDecl { decl, ..e })
+    }
+}
+
+impl<F> Fold<MemberExpr> for ScopedFold<F> {
+    fn fold(&mut self, e: MemberExpr) -> MemberExpr {
+        let e = MemberExpr {
+            obj: e.obj.fold_with(self),
+            prop: if e.computed {
+                e.prop.fold_with(self)
+            } else {
+                e.prop
+            },
+            ..e
+        };
+
+        self.folder.fold_scoped(&mut self.scopes, e)
+    }
+}
+
+impl<F> Fold<PropName> for ScopedFold<F> {
+    fn fold(&mut self, n: PropName) -> PropName {
+        let n = match n {
+            PropName::Computed(c) => PropName::Computed(c.fold_with(self)),
+            _ => n,
+        };
+
+        self.folder.fold_scoped(&mut self.scopes, n)
+    }
+}
+
+impl<F> Fold<LabeledStmt> for ScopedFold<F> {
+    fn fold(&mut self, s: LabeledStmt) -> LabeledStmt {
+        let s = LabeledStmt {
+            body: s.body.fold_with(self),
+            ..s
+        };
+
+        self.folder.fold_scoped(&mut self.scopes, s)
+    }
+}
+
+impl<F> Fold<ImportSpecific> for ScopedFold<F> {
+    fn fold(&mut self, s: ImportSpecific) -> ImportSpecific {
+        let s = ImportSpecific {
+            local: s.local.fold_with(self),
+            ..s
+        };
+
+        self.folder.fold_scoped(&mut self.scopes, s)
+    }
+}
+
+/// `export { a } from 'foo'` doesn't refer to bindings of the module.
+impl<F> Fold<NamedExport> for ScopedFold<F> {
+    fn fold(&mut self, e: NamedExport) -> NamedExport {
+        let e = if e.src.is_some() {
+            e
+        } else {
+            e.fold_children(self)
+        };
+
+        self.folder.fold_scoped(&mut self.scopes, e)
+    }
+}
+
+impl<F> Fold<ExportSpecifier> for ScopedFold<F> {
+    fn fold(&mut self, s: ExportSpecifier) -> ExportSpecifier {
+        let s = ExportSpecifier {
+            orig: s.orig.fold_with(self),
+            ..s
+        };
+
+        self.folder.fold_scoped(&mut self.scopes, s)
+    }
+}
+
+/// `<div>` is an intrinsic element, not a reference.
+impl<F> Fold<JSXElementName> for ScopedFold<F> {
+    fn fold(&mut self, n: JSXElementName) -> JSXElementName {
+        let is_tag = match n {
+            JSXElementName::Ident(ref i) => {
+                i.sym.starts_with(|c: char| c.is_ascii_lowercase()) || i.sym.contains('-')
+            }
+            _ => false,
+        };
+        let n = if is_tag { n } else { n.fold_children(self) };
+
+        self.folder.fold_scoped(&mut self.scopes, n)
+    }
+}
+
+impl<F> Fold<JSXMemberExpr> for ScopedFold<F> {
+    fn fold(&mut self, e: JSXMemberExpr) -> JSXMemberExpr {
+        let e = JSXMemberExpr {
+            obj: e.obj.fold_with(self),
+            ..e
+        };
+
+        self.folder.fold_scoped(&mut self.scopes, e)
+    }
+}
+
+/// Nodes which contain identifiers, but no binding or reference.
+macro_rules! skip_idents {
+    ($($T:ty),*) => {
+        $(
+            impl<F> Fold<$T> for ScopedFold<F> {
+                fn fold(&mut self, node: $T) -> $T {
+                    self.folder.fold_scoped(&mut self.scopes, node)
+                }
+            }
+        )*
+    };
+}
+
+skip_idents!(
+    BreakStmt,
+    ContinueStmt,
+    MetaPropExpr,
+    PrivateName,
+    JSXAttrName,
+    JSXNamespacedName
+);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -120,13 +647,17 @@ mod tests {
                     Stmt::Decl(Decl::Var(VarDecl {
                         span,
                         kind: VarDeclKind::Let,
+                        declare: false,
                         decls: vec![VarDeclarator {
                             span,
                             name: Pat::Ident(Ident::new("abc".into(), span)),
                             init: None,
                         }],
                     })),
-                    Stmt::Expr(member_expr!(span, abc)),
+                    Stmt::Expr(ExprStmt {
+                        span,
+                        expr: member_expr!(span, abc),
+                    }),
                 ],
             };
 
@@ -134,4 +665,46 @@ mod tests {
         }
     }
 
+    /// Records where identifiers in expressions are declared.
+    #[derive(Default)]
+    struct Resolver {
+        resolved: Vec<(JsWord, Option<usize>)>,
+    }
+
+    impl FoldScoped<Expr> for Resolver {
+        fn fold_scoped(&mut self, scopes: &mut Scopes, e: Expr) -> Expr {
+            if let Expr::Ident(ref i) = e {
+                self.resolved.push((i.sym.clone(), scopes.resolve(i)));
+            }
+            e
+        }
+    }
+
+    #[test]
+    fn scoped_fold() {
+        crate::tests::Tester::run(crate::tests::syntax(), |tester| {
+            let module = tester.apply_transform(
+                crate::fixer(),
+                "input.js",
+                "var a; function f(b) { let c; use(a, b, c, d); }",
+            )?;
+
+            let mut folder = ScopedFold::new(Resolver::default());
+            module.fold_with(&mut folder);
+
+            assert_eq!(
+                folder.folder.resolved,
+                vec![
+                    ("use".into(), None),
+                    ("a".into(), Some(1)),
+                    ("b".into(), Some(2)),
+                    ("c".into(), Some(3)),
+                    ("d".into(), None),
+                ]
+            );
+
+            Ok(())
+        });
+    }
+
 }
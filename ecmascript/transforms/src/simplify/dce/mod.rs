@@ -0,0 +1,267 @@
+use super::Simplifier;
+use ast::*;
+use crate::{
+    analysis::{analyze, Analysis},
+    util::{extract_side_effects_to, ExprExt, StmtLike},
+};
+use std::mem;
+use swc_common::{CommentKind, Comments, Fold, FoldWith, Repeat, Repeated, Span};
+
+#[cfg(test)]
+mod tests;
+
+/// Removes unreachable code and unused declarations.
+///
+/// Statements after `return`, `throw`, `break` and `continue` are removed
+/// along with branches of `if` and cases of `switch` which can't be taken, as
+/// [simplifier](crate::simplifier) does.
+///
+/// Functions and variables are removed if they are never referenced, unless
+/// an initializer has side effects like `var a = f();`. Top-level
+/// declarations are treated as module scoped, unless [Config::top_level] is
+/// disabled.
+pub fn dce() -> impl Fold<Module> + 'static {
+    dce_with_config(Default::default(), None)
+}
+
+/// Same as [dce], but calls and `new` expressions annotated with
+/// `/*#__PURE__*/` in `comments` are also removed if their results are
+/// unused. Arguments with side effects are kept.
+///
+/// ```js
+/// var a = /*#__PURE__*/ f();
+/// /*#__PURE__*/ g(h());
+/// ```
+///
+/// becomes `h();`.
+pub fn dce_with_comments<'a>(comments: &'a Comments) -> impl Fold<Module> + 'a {
+    dce_with_config(Default::default(), Some(comments))
+}
+
+/// Same as [dce_with_comments], but configurable.
+pub fn dce_with_config<'a>(
+    config: Config,
+    comments: Option<&'a Comments>,
+) -> impl Fold<Module> + 'a {
+    Simplifier.then(Repeat::new(UnusedDecls {
+        config,
+        analysis: Default::default(),
+        comments,
+        in_module_body: false,
+        changed: false,
+    }))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Config {
+    /// Remove unused declarations of the module.
+    ///
+    /// This should be disabled for modules which may be loaded as scripts,
+    /// as their bindings are globals. Pure calls whose results are unused are
+    /// removed regardless of this.
+    pub top_level: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config { top_level: true }
+    }
+}
+
+struct UnusedDecls<'a> {
+    config: Config,
+    /// Shadowed bindings are merged into one, so they are never removed.
+    analysis: Analysis,
+    /// Used to find pure annotations.
+    comments: Option<&'a Comments>,
+    /// True while folding statements of the module itself.
+    in_module_body: bool,
+    changed: bool,
+}
+
+impl<'a> Repeated for UnusedDecls<'a> {
+    fn changed(&self) -> bool {
+        self.changed
+    }
+
+    fn reset(&mut self) {
+        self.changed = false;
+    }
+}
+
+impl<'a> Fold<Module> for UnusedDecls<'a> {
+    fn fold(&mut self, module: Module) -> Module {
+        let analysis = analyze(&module);
+        // Direct `eval` can access any binding.
+        if analysis.free_refs(&js_word!("eval")) != 0 {
+            return module;
+        }
+
+        self.analysis = analysis;
+        self.in_module_body = true;
+        module.fold_children(self)
+    }
+}
+
+impl<'a, T: StmtLike> Fold<Vec<T>> for UnusedDecls<'a>
+where
+    Self: Fold<T>,
+{
+    fn fold(&mut self, stmts: Vec<T>) -> Vec<T> {
+        let mut buf = Vec::with_capacity(stmts.len());
+        // Nested statements are not in the module body.
+        let keep_decls = mem::replace(&mut self.in_module_body, false) && !self.config.top_level;
+
+        for stmt_like in stmts {
+            let stmt_like = self.fold(stmt_like);
+            match stmt_like.try_into_stmt() {
+                Ok(stmt) => buf.extend(self.remove_unused(stmt, keep_decls).map(T::from_stmt)),
+                Err(stmt_like) => buf.push(stmt_like),
+            }
+        }
+
+        buf
+    }
+}
+
+impl<'a> UnusedDecls<'a> {
+    /// Returns `None` if `stmt` only declares unused bindings or calls a pure
+    /// function.
+    ///
+    /// Declarations are kept if `keep_decls` is true.
+    fn remove_unused(&mut self, stmt: Stmt, keep_decls: bool) -> Option<Stmt> {
+        match stmt {
+            Stmt::Decl(..) if keep_decls => Some(stmt),
+
+            Stmt::Decl(Decl::Fn(FnDecl { ref ident, .. })) if self.is_unused(ident) => {
+                self.changed = true;
+                None
+            }
+
+            Stmt::Decl(Decl::Var(var)) => {
+                let len = var.decls.len();
+                let decls: Vec<_> = var
+                    .decls
+                    .into_iter()
+                    .filter(|decl| !self.is_removable(decl))
+                    .collect();
+                if decls.len() != len {
+                    self.changed = true;
+                }
+                if decls.is_empty() {
+                    return None;
+                }
+
+                Some(Stmt::Decl(Decl::Var(VarDecl { decls, ..var })))
+            }
+
+            Stmt::Expr(ExprStmt { span, expr }) => {
+                if !self.is_pure_call(&expr) {
+                    return Some(Stmt::Expr(ExprStmt { span, expr }));
+                }
+                self.changed = true;
+
+                let mut exprs = vec![];
+                self.extract_impure_to(&mut exprs, expr);
+                let expr = match exprs.len() {
+                    0 => return None,
+                    1 => exprs.pop().unwrap(),
+                    _ => box Expr::Seq(SeqExpr { span, exprs }),
+                };
+
+                Some(Stmt::Expr(ExprStmt { span, expr }))
+            }
+
+            _ => Some(stmt),
+        }
+    }
+
+    fn is_removable(&self, decl: &VarDeclarator) -> bool {
+        match decl.name {
+            Pat::Ident(ref i) => {
+                self.is_unused(i)
+                    && decl
+                        .init
+                        .as_ref()
+                        .map_or(true, |init| self.is_pure(init))
+            }
+            // TODO: Remove unused bindings in patterns.
+            _ => false,
+        }
+    }
+
+    /// Returns true if `ident` is never referenced.
+    fn is_unused(&self, ident: &Ident) -> bool {
+        self.analysis
+            .get(ident)
+            .map_or(true, |binding| binding.refs == 0)
+    }
+
+    /// Returns true if `e` can be removed when its value is unused.
+    fn is_pure(&self, e: &Expr) -> bool {
+        if self.is_pure_call(e) {
+            return self.args(e).iter().all(|arg| self.is_pure(&arg.expr));
+        }
+
+        !e.may_have_side_effects()
+    }
+
+    /// Returns true if `e` is a call or a `new` expression annotated with
+    /// `/*#__PURE__*/` or `/*@__PURE__*/`.
+    ///
+    /// Spread arguments may have side effects while iterating, so such calls
+    /// are not pure.
+    fn is_pure_call(&self, e: &Expr) -> bool {
+        let comments = match self.comments {
+            Some(comments) => comments,
+            None => return false,
+        };
+        let span = match *e {
+            Expr::Paren(ParenExpr { ref expr, .. }) => return self.is_pure_call(expr),
+            Expr::Call(CallExpr { span, .. }) | Expr::New(NewExpr { span, .. }) => span,
+            _ => return false,
+        };
+
+        self.args(e).iter().all(|arg| arg.spread.is_none()) && has_pure_annotation(comments, span)
+    }
+
+    /// Adds side effects of `e` to `to`, dropping pure calls but keeping their
+    /// arguments.
+    fn extract_impure_to(&self, to: &mut Vec<Box<Expr>>, e: Box<Expr>) {
+        if !self.is_pure_call(&e) {
+            return extract_side_effects_to(to, e);
+        }
+
+        match *e {
+            Expr::Paren(ParenExpr { expr, .. }) => self.extract_impure_to(to, expr),
+            Expr::Call(CallExpr { args, .. })
+            | Expr::New(NewExpr {
+                args: Some(args), ..
+            }) => args
+                .into_iter()
+                .for_each(|arg| self.extract_impure_to(to, arg.expr)),
+            _ => {}
+        }
+    }
+
+    fn args<'e>(&self, e: &'e Expr) -> &'e [ExprOrSpread] {
+        match *e {
+            Expr::Paren(ParenExpr { ref expr, .. }) => self.args(expr),
+            Expr::Call(CallExpr { ref args, .. })
+            | Expr::New(NewExpr {
+                args: Some(ref args),
+                ..
+            }) => args,
+            _ => &[],
+        }
+    }
+}
+
+/// The annotation is a leading comment of the call, which is stored at the
+/// start of the callee.
+fn has_pure_annotation(comments: &Comments, span: Span) -> bool {
+    comments.leading_comments(span.lo()).iter().any(|c| {
+        let text = c.text.trim();
+        c.kind == CommentKind::Block && (text == "#__PURE__" || text == "@__PURE__")
+    })
+}
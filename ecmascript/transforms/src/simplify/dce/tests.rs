@@ -0,0 +1,113 @@
+use super::*;
+use crate::tests::{syntax, Tester};
+use swc_common::noop;
+
+/// Applies [dce_with_comments] to `src` and compares the output with
+/// `expected`.
+fn test_pure(src: &str, expected: &str) {
+    test_pure_with(Default::default(), src, expected)
+}
+
+fn test_pure_with(config: Config, src: &str, expected: &str) {
+    Tester::run(syntax(), |tester| {
+        let comments = Comments::default();
+        let module = tester
+            .parse_with_comments("input.js", src, &comments)?
+            .fold_with(&mut dce_with_config(config, Some(&comments)))
+            .fold_with(&mut ::testing::DropSpan);
+        let expected = tester.apply_transform(noop(), "expected.js", expected)?;
+
+        assert_eq!(tester.print(&expected), tester.print(&module));
+        Ok(())
+    });
+}
+
+test!(
+    dce(),
+    unused_fn,
+    "function a() { b(); } function b() {} function c() {} export default c;",
+    "function c() {} export default c;"
+);
+
+test!(
+    dce(),
+    unused_var,
+    "var a = 1, b = f(), c = 2; let d = function () {}; const e = 3; export { c };",
+    "var b = f(), c = 2; export { c };"
+);
+
+test!(
+    dce(),
+    unreachable,
+    "export function f() { if (false) { g(); } return h(); var a = 1; function h() {} }",
+    "export function f() { return h(); function h() {} }"
+);
+
+test!(
+    dce(),
+    shadowed,
+    "var a = 1; export function f(a) { return a; }",
+    "var a = 1; export function f(a) { return a; }"
+);
+
+test!(
+    dce(),
+    property_names,
+    "var a = 1; function b() {} use({ a: 1, b() {} }.a);",
+    "use({ a: 1, b() {} }.a);"
+);
+
+test!(
+    dce(),
+    eval,
+    "var a = 1; function f() {} eval('a');",
+    "var a = 1; function f() {} eval('a');"
+);
+
+test!(
+    dce_with_config(Config { top_level: false }, None),
+    keep_top_level,
+    "var a = 1; function b() {} export function f() { var c = 1; function d() {} }",
+    "var a = 1; function b() {} export function f() {}"
+);
+
+#[test]
+fn pure_calls() {
+    test_pure(
+        "var a = /*#__PURE__*/ f(); /*#__PURE__*/ g(); /* @__PURE__ */ new C(); export { b };",
+        "export { b };",
+    );
+}
+
+#[test]
+fn pure_calls_keep_side_effects() {
+    test_pure(
+        "/*#__PURE__*/ f(/*#__PURE__*/ g(), h(), 1); /*#__PURE__*/ f(a(), b());",
+        "h(); a(), b();",
+    );
+}
+
+#[test]
+fn pure_calls_used() {
+    test_pure(
+        "var a = /*#__PURE__*/ f(), b = /*#__PURE__*/ g(h()); export default a;",
+        "var a = /*#__PURE__*/ f(), b = /*#__PURE__*/ g(h()); export default a;",
+    );
+}
+
+#[test]
+fn not_annotated() {
+    test_pure(
+        "var a = f(); /* pure */ g(); // #__PURE__\nh(); /*#__PURE__*/ i(...j);",
+        "var a = f(); /* pure */ g(); // #__PURE__\nh(); /*#__PURE__*/ i(...j);",
+    );
+}
+
+#[test]
+fn pure_calls_keep_top_level() {
+    test_pure_with(
+        Config { top_level: false },
+        "var a = /*#__PURE__*/ f(); /*#__PURE__*/ g(h()); export { b };",
+        "var a = /*#__PURE__*/ f(); h(); export { b };",
+    );
+}
@@ -1,31 +1,76 @@
 use crate::util::*;
 use std::iter;
 use swc_atoms::JsWord;
-use swc_common::{Fold, FoldWith, Span, Spanned};
+use swc_common::{Fold, FoldWith, Repeated, Span, Spanned};
 use ast::{Ident, Lit, *};
 
 #[cfg(test)]
 mod tests;
 
-pub(super) struct SimplifyExpr;
+/// Evaluates constant expressions like `1 + 2`, `typeof 'a'`, `!true` and
+/// `true ? a : b`.
+///
+/// Folding a node may leave something foldable behind, like `1` in
+/// `[1, a()], b` -> `1, a(), b`, so this should be applied with
+/// [Repeat](swc_common::Repeat).
+pub fn expr_simplifier() -> impl Fold<Module> + Repeated + 'static {
+    SimplifyExpr::default()
+}
+
+#[derive(Debug, Default)]
+pub(super) struct SimplifyExpr {
+    changed: bool,
+}
+
+impl Repeated for SimplifyExpr {
+    fn changed(&self) -> bool {
+        self.changed
+    }
+
+    fn reset(&mut self) {
+        self.changed = false;
+    }
+}
 
 impl Fold<Expr> for SimplifyExpr {
     /// Ported from [optimizeSubtree](https://github.com/google/closure-compiler/blob/9203e01b/src/com/google/javascript/jscomp/PeepholeFoldConstants.java#L74-L98)
     fn fold(&mut self, expr: Expr) -> Expr {
         // fold children before doing something more.
-        let expr = expr.fold_children(self);
+        let expr = match expr {
+            // Optional chains should stay members or calls, so `"a"?.length` is
+            // not folded.
+            Expr::OptChain(OptChainExpr {
+                span,
+                expr: box expr,
+            }) => {
+                return Expr::OptChain(OptChainExpr {
+                    span,
+                    expr: box expr.fold_children(self),
+                });
+            }
+            _ => expr.fold_children(self),
+        };
 
         match expr {
             // Do nothing.
             Expr::Lit(_) | Expr::This(..) => expr,
 
             // Remove parenthesis. This may break ast, but it will be fixed up later.
-            Expr::Paren(ParenExpr { expr, .. }) => *expr,
+            //
+            // `(a?.b).c` is not `a?.b.c`, though.
+            Expr::Paren(ParenExpr { span, expr }) => {
+                if is_opt_chain(&expr) {
+                    Expr::Paren(ParenExpr { span, expr })
+                } else {
+                    self.changed = true;
+                    *expr
+                }
+            }
 
-            Expr::Unary(expr) => fold_unary(expr),
-            Expr::Bin(expr) => fold_bin(expr),
+            Expr::Unary(expr) => self.fold_unary(expr),
+            Expr::Bin(expr) => self.fold_bin(expr),
 
-            Expr::Member(e) => fold_member_expr(e),
+            Expr::Member(e) => self.fold_member_expr(e),
 
             Expr::Cond(CondExpr {
                 span,
@@ -34,6 +79,7 @@ impl Fold<Expr> for SimplifyExpr {
                 alt,
             }) => match test.as_bool() {
                 (p, Known(val)) => {
+                    self.changed = true;
                     let expr_value = if val { cons } else { alt };
                     if p.is_pure() {
                         *expr_value
@@ -55,6 +101,7 @@ impl Fold<Expr> for SimplifyExpr {
             // Simplify sequence expression.
             Expr::Seq(SeqExpr { span, exprs }) => {
                 if exprs.len() == 1 {
+                    self.changed = true;
                     //TODO: Respan
                     *exprs.into_iter().next().unwrap()
                 } else {
@@ -70,427 +117,586 @@ impl Fold<Expr> for SimplifyExpr {
     }
 }
 
-fn fold_member_expr(e: MemberExpr) -> Expr {
-    #[derive(Clone, PartialEq, Eq)]
-    enum KnownOp {
-        /// [a, b].length
-        Len,
+impl SimplifyExpr {
+    fn fold_member_expr(&mut self, e: MemberExpr) -> Expr {
+        #[derive(Clone, PartialEq, Eq)]
+        enum KnownOp {
+            /// [a, b].length
+            Len,
 
-        Index(u32),
+            Index(u32),
 
-        /// ({}).foo
-        IndexStr(JsWord),
-    }
-    let op = match *e.prop {
-        Expr::Ident(Ident {
-            sym: js_word!("length"),
-            ..
-        }) => KnownOp::Len,
-        Expr::Ident(Ident { ref sym, .. }) => KnownOp::IndexStr(sym.clone()),
-        // Lit(Lit::Num(Number(f)))=>{
-        //     if f==0{
+            /// ({}).foo
+            IndexStr(JsWord),
+        }
+        let op = match *e.prop {
+            Expr::Ident(Ident {
+                sym: js_word!("length"),
+                ..
+            }) => KnownOp::Len,
+            Expr::Ident(Ident { ref sym, .. }) => KnownOp::IndexStr(sym.clone()),
+            // Lit(Lit::Num(Number(f)))=>{
+            //     if f==0{
 
-        //     }else{
+            //     }else{
 
-        //     }
-        //     // TODO: Report error
-        //     KnownOp::Index(f)},
-        _ => return Expr::Member(e),
-    };
+            //     }
+            //     // TODO: Report error
+            //     KnownOp::Index(f)},
+            _ => return Expr::Member(e),
+        };
 
-    let obj = match e.obj {
-        ExprOrSuper::Super(_) => return Expr::Member(e),
-        ExprOrSuper::Expr(box o) => o,
-    };
+        let obj = match e.obj {
+            ExprOrSuper::Super(_) | ExprOrSuper::Import(_) => return Expr::Member(e),
+            ExprOrSuper::Expr(box o) => o,
+        };
 
-    match obj {
-        Expr::Lit(Lit::Str(Str {
-            ref value, span, ..
-        })) => match op {
-            // 'foo'.length
-            KnownOp::Len => Expr::Lit(Lit::Num(Number {
-                value: value.chars().count() as f64,
-                span: mark!(span),
-            })),
-
-            // 'foo'[1]
-            KnownOp::Index(idx) if (idx as usize) < value.len() => Expr::Lit(Lit::Str(Str {
-                value: value
-                    .chars()
-                    .nth(idx as _)
-                    .unwrap_or_else(|| panic!("failed to index char?"))
-                    .to_string()
-                    .into(),
-                span: mark!(span),
-                has_escape: false,
-            })),
+        match obj {
+            Expr::Lit(Lit::Str(Str {
+                ref value, span, ..
+            })) => match op {
+                // 'foo'.length
+                KnownOp::Len => {
+                    self.changed = true;
+                    Expr::Lit(Lit::Num(Number {
+                        value: value.chars().count() as f64,
+                        span: mark!(span),
+                        raw: None,
+                    }))
+                }
 
-            _ => Expr::Member(MemberExpr {
-                obj: ExprOrSuper::Expr(box obj),
-                ..e
-            }),
-        },
+                // 'foo'[1]
+                KnownOp::Index(idx) if (idx as usize) < value.len() => {
+                    self.changed = true;
+                    Expr::Lit(Lit::Str(Str {
+                        value: value
+                            .chars()
+                            .nth(idx as _)
+                            .unwrap_or_else(|| panic!("failed to index char?"))
+                            .to_string()
+                            .into(),
+                        span: mark!(span),
+                        has_escape: false,
+                        raw: None,
+                    }))
+                }
 
-        // [1, 2, 3].length
-        Expr::Array(ArrayLit { ref elems, span })
-            if op == KnownOp::Len && !obj.may_have_side_effects() =>
-        {
-            // do nothing if spread exists
-            let has_spread = elems.iter().any(|elem| {
-                elem.as_ref()
-                    .map(|elem| elem.spread.is_some())
-                    .unwrap_or(false)
-            });
-
-            if has_spread {
-                return Expr::Member(MemberExpr {
+                _ => Expr::Member(MemberExpr {
                     obj: ExprOrSuper::Expr(box obj),
                     ..e
+                }),
+            },
+
+            // [1, 2, 3].length
+            Expr::Array(ArrayLit { ref elems, span })
+                if op == KnownOp::Len && !obj.may_have_side_effects() =>
+            {
+                // do nothing if spread exists
+                let has_spread = elems.iter().any(|elem| {
+                    elem.as_ref()
+                        .map(|elem| elem.spread.is_some())
+                        .unwrap_or(false)
                 });
+
+                if has_spread {
+                    return Expr::Member(MemberExpr {
+                        obj: ExprOrSuper::Expr(box obj),
+                        ..e
+                    });
+                }
+
+                self.changed = true;
+                return Expr::Lit(Lit::Num(Number {
+                    value: elems.len() as _,
+                    span: mark!(span),
+                    raw: None,
+                }));
             }
 
-            return Expr::Lit(Lit::Num(Number {
-                value: elems.len() as _,
-                span: mark!(span),
-            }));
-        }
+            // { foo: true }['foo']
+            Expr::Object(ObjectLit { props, span }) => match op {
+                // TODO
+                // KnownOp::IndexStr(key) => {
+                // }
+                _ => Expr::Member(MemberExpr {
+                    obj: ExprOrSuper::Expr(box Expr::Object(ObjectLit { props, span })),
+                    ..e
+                }),
+            },
 
-        // { foo: true }['foo']
-        Expr::Object(ObjectLit { props, span }) => match op {
-            // TODO
-            // KnownOp::IndexStr(key) => {
-            // }
             _ => Expr::Member(MemberExpr {
-                obj: ExprOrSuper::Expr(box Expr::Object(ObjectLit { props, span })),
+                obj: ExprOrSuper::Expr(box obj),
                 ..e
             }),
-        },
-
-        _ => Expr::Member(MemberExpr {
-            obj: ExprOrSuper::Expr(box obj),
-            ..e
-        }),
+        }
     }
-}
 
-fn fold_bin(
-    BinExpr {
-        left,
-        op,
-        right,
-        span,
-    }: BinExpr,
-) -> Expr {
-    macro_rules! try_replace {
-        ($v:expr) => {{
-            match $v {
-                Known(v) => {
-                    // TODO: Optimize
-
-                    return make_bool_expr(span, v, { iter::once(left).chain(iter::once(right)) });
+    fn fold_bin(
+        &mut self,
+        BinExpr {
+            left,
+            op,
+            right,
+            span,
+        }: BinExpr,
+    ) -> Expr {
+        macro_rules! try_replace {
+            ($v:expr) => {{
+                match $v {
+                    Known(v) => {
+                        // TODO: Optimize
+
+                        self.changed = true;
+                        return make_bool_expr(span, v, {
+                            iter::once(left).chain(iter::once(right))
+                        });
+                    }
+                    _ => (left, right),
                 }
-                _ => (left, right),
-            }
-        }};
-        (number, $v:expr) => {{
-            match $v {
-                Known(v) => {
-                    return preserve_effects(
-                        span,
-                        Expr::Lit(Lit::Num(Number {
-                            value: v,
-                            span: mark!(span),
-                        })),
-                        { iter::once(left).chain(iter::once(right)) },
-                    );
+            }};
+            (number, $v:expr) => {{
+                match $v {
+                    Known(v) => {
+                        self.changed = true;
+                        return preserve_effects(
+                            span,
+                            Expr::Lit(Lit::Num(Number {
+                                value: v,
+                                span: mark!(span),
+                                raw: None,
+                            })),
+                            { iter::once(left).chain(iter::once(right)) },
+                        );
+                    }
+                    _ => (left, right),
                 }
-                _ => (left, right),
-            }
-        }};
-    }
-
-    let (left, right) = match op {
-        op!(bin, "+") => {
-            // It's string concatenation if either left or right is string.
+            }};
+        }
 
-            let mut bin = Expr::Bin(BinExpr {
-                span,
-                left,
-                op: op!(bin, "+"),
-                right,
-            });
+        let (left, right) = match op {
+            op!(bin, "+") => {
+                // It's string concatenation if either left or right is string.
 
-            match bin.get_type() {
-                // String concatenation
-                Known(StringType) => match bin {
-                    Expr::Bin(BinExpr {
-                        left,
-                        op,
-                        right,
-                        span,
-                    }) => {
-                        if !left.may_have_side_effects() && !right.may_have_side_effects() {
-                            match (left.as_string(), right.as_string()) {
-                                (Known(l), Known(r)) => {
-                                    return Expr::Lit(Lit::Str(Str {
-                                        value: format!("{}{}", l, r).into(),
-                                        span: mark!(span),
-                                        // TODO
-                                        has_escape: false,
-                                    }));
-                                }
-                                _ => {}
-                            }
-                        }
+                let mut bin = Expr::Bin(BinExpr {
+                    span,
+                    left,
+                    op: op!(bin, "+"),
+                    right,
+                });
 
-                        return Expr::Bin(BinExpr {
-                            left,
-                            op,
-                            right,
-                            span,
-                        });
-                    }
-                    _ => unreachable!(),
-                },
-                // Numerical calculation
-                Known(BoolType) | Known(NullType) | Known(NumberType) | Known(UndefinedType) => {
-                    bin = match bin {
+                match bin.get_type() {
+                    // String concatenation
+                    Known(StringType) => match bin {
                         Expr::Bin(BinExpr {
                             left,
-                            op: _,
+                            op,
                             right,
                             span,
-                        }) => match perform_arithmetic_op(op!(bin, "+"), &left, &right) {
-                            Known(v) => {
-                                return preserve_effects(
-                                    span,
-                                    Expr::Lit(Lit::Num(Number { value: v, span })),
-                                    { iter::once(left).chain(iter::once(right)) },
-                                );
+                        }) => {
+                            if !left.may_have_side_effects() && !right.may_have_side_effects() {
+                                match (left.as_string(), right.as_string()) {
+                                    (Known(l), Known(r)) => {
+                                        self.changed = true;
+                                        return Expr::Lit(Lit::Str(Str {
+                                            value: format!("{}{}", l, r).into(),
+                                            span: mark!(span),
+                                            // TODO
+                                            has_escape: false,
+                                            raw: None,
+                                        }));
+                                    }
+                                    _ => {}
+                                }
                             }
-                            _ => Expr::Bin(BinExpr {
-                                span,
+
+                            return Expr::Bin(BinExpr {
                                 left,
-                                op: op!(bin, "+"),
+                                op,
                                 right,
-                            }),
-                        },
+                                span,
+                            });
+                        }
                         _ => unreachable!(),
-                    };
+                    },
+                    // Numerical calculation
+                    Known(BoolType)
+                    | Known(NullType)
+                    | Known(NumberType)
+                    | Known(UndefinedType) => {
+                        bin = match bin {
+                            Expr::Bin(BinExpr {
+                                left,
+                                op: _,
+                                right,
+                                span,
+                            }) => match perform_arithmetic_op(op!(bin, "+"), &left, &right) {
+                                Known(v) => {
+                                    self.changed = true;
+                                    return preserve_effects(
+                                        span,
+                                        Expr::Lit(Lit::Num(Number {
+                                            value: v,
+                                            span,
+                                            raw: None,
+                                        })),
+                                        { iter::once(left).chain(iter::once(right)) },
+                                    );
+                                }
+                                _ => Expr::Bin(BinExpr {
+                                    span,
+                                    left,
+                                    op: op!(bin, "+"),
+                                    right,
+                                }),
+                            },
+                            _ => unreachable!(),
+                        };
+                    }
+                    _ => {}
                 }
-                _ => {}
-            }
 
-            //TODO: try string concat
-            return bin;
-        }
+                //TODO: try string concat
+                return bin;
+            }
 
-        op!("&&") | op!("||") => match left.as_bool() {
-            (_, Known(val)) => {
-                let node = if op == op!("&&") {
-                    if val {
-                        // 1 && $right
-                        right
+            op!("&&") | op!("||") => match left.as_bool() {
+                (_, Known(val)) => {
+                    self.changed = true;
+                    let node = if op == op!("&&") {
+                        if val {
+                            // 1 && $right
+                            right
+                        } else {
+                            // 0 && $right
+                            return *left;
+                        }
                     } else {
-                        // 0 && $right
-                        return *left;
-                    }
-                } else {
-                    if val {
-                        // 1 || $right
-                        return *left;
+                        if val {
+                            // 1 || $right
+                            return *left;
+                        } else {
+                            // 0 || $right
+                            right
+                        }
+                    };
+
+                    return if !left.may_have_side_effects() {
+                        *node
                     } else {
-                        // 0 || $right
-                        right
+                        let seq = self.fold(SeqExpr {
+                            span,
+                            exprs: vec![left, node],
+                        });
+
+                        Expr::Seq(seq)
+                    };
+                }
+                _ => (left, right),
+            },
+            op!("instanceof") => {
+                fn is_non_obj(e: &Expr) -> bool {
+                    match *e {
+                        // Non-object types are never instances.
+                        Expr::Lit(Lit::Str { .. })
+                        | Expr::Lit(Lit::Num(..))
+                        | Expr::Lit(Lit::Null(..))
+                        | Expr::Lit(Lit::Bool(..))
+                        | Expr::Ident(Ident {
+                            sym: js_word!("undefined"),
+                            ..
+                        })
+                        | Expr::Ident(Ident {
+                            sym: js_word!("Infinity"),
+                            ..
+                        })
+                        | Expr::Ident(Ident {
+                            sym: js_word!("NaN"),
+                            ..
+                        }) => true,
+
+                        Expr::Unary(UnaryExpr {
+                            op: op!("!"),
+                            ref arg,
+                            ..
+                        })
+                        | Expr::Unary(UnaryExpr {
+                            op: op!(unary, "-"),
+                            ref arg,
+                            ..
+                        })
+                        | Expr::Unary(UnaryExpr {
+                            op: op!("void"),
+                            ref arg,
+                            ..
+                        }) => is_non_obj(&arg),
+                        _ => false,
                     }
-                };
+                }
 
-                return if !left.may_have_side_effects() {
-                    *node
-                } else {
-                    let seq = SimplifyExpr.fold(SeqExpr {
-                        span,
-                        exprs: vec![left, node],
-                    });
+                // Non-object types are never instances.
+                if is_non_obj(&left) {
+                    self.changed = true;
+                    return make_bool_expr(span, false, iter::once(right));
+                }
 
-                    Expr::Seq(seq)
-                };
-            }
-            _ => (left, right),
-        },
-        op!("instanceof") => {
-            fn is_non_obj(e: &Expr) -> bool {
-                match *e {
-                    // Non-object types are never instances.
-                    Expr::Lit(Lit::Str { .. })
-                    | Expr::Lit(Lit::Num(..))
-                    | Expr::Lit(Lit::Null(..))
-                    | Expr::Lit(Lit::Bool(..))
-                    | Expr::Ident(Ident {
-                        sym: js_word!("undefined"),
-                        ..
-                    })
-                    | Expr::Ident(Ident {
-                        sym: js_word!("Infinity"),
-                        ..
-                    })
-                    | Expr::Ident(Ident {
-                        sym: js_word!("NaN"),
-                        ..
-                    }) => true,
-
-                    Expr::Unary(UnaryExpr {
-                        op: op!("!"),
-                        ref arg,
-                        ..
-                    })
-                    | Expr::Unary(UnaryExpr {
-                        op: op!(unary, "-"),
-                        ref arg,
-                        ..
-                    })
-                    | Expr::Unary(UnaryExpr {
-                        op: op!("void"),
-                        ref arg,
-                        ..
-                    }) => is_non_obj(&arg),
-                    _ => false,
+                if right.is_ident_ref_to(js_word!("Object")) {
+                    self.changed = true;
+                    return make_bool_expr(span, true, iter::once(left));
                 }
-            }
 
-            // Non-object types are never instances.
-            if is_non_obj(&left) {
-                return make_bool_expr(span, false, iter::once(right));
+                (left, right)
             }
 
-            if right.is_ident_ref_to(js_word!("Object")) {
-                return make_bool_expr(span, true, iter::once(left));
+            // Arithmetic operations
+            op!(bin, "-") | op!("/") | op!("%") => {
+                try_replace!(number, perform_arithmetic_op(op, &left, &right))
             }
 
-            (left, right)
-        }
+            // Bit shift operations
+            op!("<<") | op!(">>") | op!(">>>") => {
+                /// Uses a method for treating a double as 32bits that is equivalent
+                /// to how JavaScript would convert a number before applying a bit
+                /// operation.
+                fn js_convert_double_to_bits(d: f64) -> i32 {
+                    return ((d.floor() as i64) & 0xffffffff) as i32;
+                }
 
-        // Arithmetic operations
-        op!(bin, "-") | op!("/") | op!("%") => {
-            try_replace!(number, perform_arithmetic_op(op, &left, &right))
-        }
+                fn try_fold_shift(op: BinaryOp, left: &Expr, right: &Expr) -> Value<f64> {
+                    if !left.is_number() || !right.is_number() {
+                        return Unknown;
+                    }
 
-        // Bit shift operations
-        op!("<<") | op!(">>") | op!(">>>") => {
-            /// Uses a method for treating a double as 32bits that is equivalent
-            /// to how JavaScript would convert a number before applying a bit
-            /// operation.
-            fn js_convert_double_to_bits(d: f64) -> i32 {
-                return ((d.floor() as i64) & 0xffffffff) as i32;
-            }
+                    let (lv, rv) = match (left.as_number(), right.as_number()) {
+                        (Known(lv), Known(rv)) => (lv, rv),
+                        _ => unreachable!(),
+                    };
 
-            fn try_fold_shift(op: BinaryOp, left: &Expr, right: &Expr) -> Value<f64> {
-                if !left.is_number() || !right.is_number() {
-                    return Unknown;
-                }
+                    // only the lower 5 bits are used when shifting, so don't do anything
+                    // if the shift amount is outside [0,32)
+                    if !(rv >= 0.0 && rv < 32.0) {
+                        return Unknown;
+                    }
 
-                let (lv, rv) = match (left.as_number(), right.as_number()) {
-                    (Known(lv), Known(rv)) => (lv, rv),
-                    _ => unreachable!(),
-                };
+                    let rv_int = rv as i32;
+                    if rv_int as f64 != rv {
+                        unimplemented!("error reporting: FRACTIONAL_BITWISE_OPERAND")
+                        // report(FRACTIONAL_BITWISE_OPERAND, right.span());
+                        // return n;
+                    }
 
-                // only the lower 5 bits are used when shifting, so don't do anything
-                // if the shift amount is outside [0,32)
-                if !(rv >= 0.0 && rv < 32.0) {
-                    return Unknown;
-                }
+                    if lv.floor() != lv {
+                        unimplemented!("error reporting: FRACTIONAL_BITWISE_OPERAND")
+                        // report(FRACTIONAL_BITWISE_OPERAND, left.span());
+                        // return n;
+                    }
 
-                let rv_int = rv as i32;
-                if rv_int as f64 != rv {
-                    unimplemented!("error reporting: FRACTIONAL_BITWISE_OPERAND")
-                    // report(FRACTIONAL_BITWISE_OPERAND, right.span());
-                    // return n;
-                }
+                    let bits = js_convert_double_to_bits(lv);
+
+                    Known(match op {
+                        op!("<<") => (bits << rv_int) as f64,
+                        op!(">>") => (bits >> rv_int) as f64,
+                        op!(">>>") => {
+                            let res = bits as u32 >> rv_int as u32;
+                            // JavaScript always treats the result of >>> as unsigned.
+                            // We must force Java to do the same here.
+                            // unimplemented!(">>> (Zerofill rshift)")
+                            (0xffffffffu32 & res) as f64
+                        }
 
-                if lv.floor() != lv {
-                    unimplemented!("error reporting: FRACTIONAL_BITWISE_OPERAND")
-                    // report(FRACTIONAL_BITWISE_OPERAND, left.span());
-                    // return n;
+                        _ => unreachable!("Unknown bit operator {:?}", op),
+                    })
                 }
+                try_replace!(number, try_fold_shift(op, &left, &right))
+            }
+
+            // These needs one more check.
+            //
+            // (a * 1) * 2 --> a * (1 * 2) --> a * 2
+            op!("*") | op!("&") | op!("|") | op!("^") => {
+                let (mut left, right) =
+                    try_replace!(number, perform_arithmetic_op(op, &left, &right));
 
-                let bits = js_convert_double_to_bits(lv);
-
-                Known(match op {
-                    op!("<<") => (bits << rv_int) as f64,
-                    op!(">>") => (bits >> rv_int) as f64,
-                    op!(">>>") => {
-                        let res = bits as u32 >> rv_int as u32;
-                        // JavaScript always treats the result of >>> as unsigned.
-                        // We must force Java to do the same here.
-                        // unimplemented!(">>> (Zerofill rshift)")
-                        (0xffffffffu32 & res) as f64
+                // Try left.rhs * right
+                match *left {
+                    Expr::Bin(BinExpr {
+                        span: left_span,
+                        left: left_lhs,
+                        op: left_op,
+                        right: left_rhs,
+                    }) => {
+                        let v = perform_arithmetic_op(op, &left_rhs, &right);
+                        match v {
+                            Known(value) => {
+                                self.changed = true;
+                                return Expr::Bin(BinExpr {
+                                    span,
+                                    left: left_lhs,
+                                    op: left_op,
+                                    right: box Expr::Lit(Lit::Num(Number {
+                                        value,
+                                        span,
+                                        raw: None,
+                                    })),
+                                })
+                            }
+                            _ => {
+                                left = box Expr::Bin(BinExpr {
+                                    left: left_lhs,
+                                    op: left_op,
+                                    span: left_span,
+                                    right: left_rhs,
+                                })
+                            }
+                        }
                     }
+                    _ => {}
+                }
+
+                (left, right)
+            }
+
+            // Comparisons
+            op!("<") => try_replace!(perform_abstract_rel_cmp(span, &left, &right, false)),
+            op!(">") => try_replace!(perform_abstract_rel_cmp(span, &right, &left, false)),
+            op!("<=") => try_replace!(!perform_abstract_rel_cmp(span, &right, &left, true)),
+            op!(">=") => try_replace!(!perform_abstract_rel_cmp(span, &left, &right, true)),
+
+            op!("==") => try_replace!(perform_abstract_eq_cmp(span, &left, &right)),
+            op!("!=") => try_replace!(!perform_abstract_eq_cmp(span, &left, &right)),
+            op!("===") => try_replace!(perform_strict_eq_cmp(span, &left, &right)),
+            op!("!==") => try_replace!(!perform_strict_eq_cmp(span, &left, &right)),
+            _ => (left, right),
+        };
 
-                    _ => unreachable!("Unknown bit operator {:?}", op),
+        Expr::Bin(BinExpr {
+            left,
+            op,
+            right,
+            span,
+        })
+    }
+
+    /// Folds 'typeof(foo)' if foo is a literal, e.g.
+    ///
+    /// typeof("bar") --> "string"
+    ///
+    /// typeof(6) --> "number"
+    fn try_fold_typeof(&mut self, UnaryExpr { span, op, arg }: UnaryExpr) -> Expr {
+        assert_eq!(op, op!("typeof"));
+
+        let val = match *arg {
+            Expr::Fn(..) => "function",
+            Expr::Lit(Lit::Str { .. }) => "string",
+            Expr::Lit(Lit::Num(..)) => "number",
+            Expr::Lit(Lit::BigInt(..)) => "bigint",
+            Expr::Lit(Lit::Bool(..)) => "boolean",
+            Expr::Lit(Lit::Null(..)) | Expr::Object { .. } | Expr::Array { .. } => "object",
+            Expr::Unary(UnaryExpr {
+                op: op!("void"), ..
+            })
+            | Expr::Ident(Ident {
+                sym: js_word!("undefined"),
+                ..
+            }) => {
+                // We can assume `undefined` is `undefined`,
+                // because overriding `undefined` is always hard error in swc.
+                "undefined"
+            }
+
+            _ => {
+                return Expr::Unary(UnaryExpr {
+                    op: op!("typeof"),
+                    arg,
+                    span,
                 })
             }
-            try_replace!(number, try_fold_shift(op, &left, &right))
-        }
+        };
+
+        self.changed = true;
+        Expr::Lit(Lit::Str(Str {
+            span: mark!(span),
+            value: val.into(),
+            has_escape: false,
+            raw: None,
+        }))
+    }
 
-        // These needs one more check.
-        //
-        // (a * 1) * 2 --> a * (1 * 2) --> a * 2
-        op!("*") | op!("&") | op!("|") | op!("^") => {
-            let (mut left, right) = try_replace!(number, perform_arithmetic_op(op, &left, &right));
-
-            // Try left.rhs * right
-            match *left {
-                Expr::Bin(BinExpr {
-                    span: left_span,
-                    left: left_lhs,
-                    op: left_op,
-                    right: left_rhs,
+    fn fold_unary(&mut self, UnaryExpr { span, op, arg }: UnaryExpr) -> Expr {
+        let may_have_side_effects = arg.may_have_side_effects();
+
+        match op {
+            op!("typeof") if !may_have_side_effects => {
+                return self.try_fold_typeof(UnaryExpr { span, op, arg })
+            }
+            op!("!") => match arg.as_bool() {
+                (_, Known(val)) => {
+                    self.changed = true;
+                    return make_bool_expr(span, !val, iter::once(arg));
+                }
+                _ => return Expr::Unary(UnaryExpr { op, arg, span }),
+            },
+            op!(unary, "+") => match arg.as_number() {
+                Known(v) => {
+                    self.changed = true;
+                    return preserve_effects(
+                        span,
+                        Expr::Lit(Lit::Num(Number {
+                            value: v,
+                            span: mark!(span),
+                            raw: None,
+                        })),
+                        iter::once(arg),
+                    )
+                }
+                _ => return Expr::Unary(UnaryExpr { op, arg, span }),
+            },
+            op!(unary, "-") => match *arg {
+                Expr::Ident(Ident {
+                    sym: js_word!("Infinity"),
+                    span,
+                }) => return Expr::Unary(UnaryExpr { op, arg, span }),
+                // "-NaN" is "NaN"
+                Expr::Ident(Ident {
+                    sym: js_word!("NaN"),
+                    ..
                 }) => {
-                    let v = perform_arithmetic_op(op, &left_rhs, &right);
-                    match v {
-                        Known(value) => {
-                            return Expr::Bin(BinExpr {
-                                span,
-                                left: left_lhs,
-                                op: left_op,
-                                right: box Expr::Lit(Lit::Num(Number { value, span })),
-                            })
-                        }
-                        _ => {
-                            left = box Expr::Bin(BinExpr {
-                                left: left_lhs,
-                                op: left_op,
-                                span: left_span,
-                                right: left_rhs,
-                            })
-                        }
-                    }
+                    self.changed = true;
+                    return *arg;
                 }
-                _ => {}
-            }
+                Expr::Lit(Lit::Num(Number { value: f, .. })) => {
+                    self.changed = true;
+                    return Expr::Lit(Lit::Num(Number {
+                        value: -f,
+                        span: mark!(span),
+                        raw: None,
+                    }))
+                }
+                _ => {
 
-            (left, right)
+                    // TODO: Report that user is something bad (negating non-number value)
+                }
+            },
+            op!("void") if !may_have_side_effects => {
+                // `void 0` is already the shortest form.
+                match *arg {
+                    Expr::Lit(Lit::Num(Number { value, .. })) if value == 0.0 => {}
+                    _ => self.changed = true,
+                }
+                return Expr::Unary(UnaryExpr {
+                    op: op!("void"),
+                    arg: box Expr::Lit(Lit::Num(Number {
+                        value: 0.0,
+                        span: mark!(arg.span()),
+                        raw: None,
+                    })),
+                    span,
+                })
+            }
+            _ => {}
         }
 
-        // Comparisons
-        op!("<") => try_replace!(perform_abstract_rel_cmp(span, &left, &right, false)),
-        op!(">") => try_replace!(perform_abstract_rel_cmp(span, &right, &left, false)),
-        op!("<=") => try_replace!(!perform_abstract_rel_cmp(span, &right, &left, true)),
-        op!(">=") => try_replace!(!perform_abstract_rel_cmp(span, &left, &right, true)),
-
-        op!("==") => try_replace!(perform_abstract_eq_cmp(span, &left, &right)),
-        op!("!=") => try_replace!(!perform_abstract_eq_cmp(span, &left, &right)),
-        op!("===") => try_replace!(perform_strict_eq_cmp(span, &left, &right)),
-        op!("!==") => try_replace!(!perform_strict_eq_cmp(span, &left, &right)),
-        _ => (left, right),
-    };
-
-    Expr::Bin(BinExpr {
-        left,
-        op,
-        right,
-        span,
-    })
+        Expr::Unary(UnaryExpr { op, arg, span })
+    }
 }
 
 /// Drops unused values
@@ -506,7 +712,7 @@ impl Fold<SeqExpr> for SimplifyExpr {
         for expr in e.exprs {
             match *expr {
                 // Drop side-effect free nodes.
-                Expr::Lit(_) => {}
+                Expr::Lit(_) => self.changed = true,
 
                 // Flatten array
                 Expr::Array(ArrayLit { span, elems }) => {
@@ -516,6 +722,7 @@ impl Fold<SeqExpr> for SimplifyExpr {
                     });
 
                     if is_simple {
+                        self.changed = true;
                         exprs.extend(elems.into_iter().filter_map(|e| e).map(|e| e.expr));
                     } else {
                         exprs.push(box ArrayLit { span, elems }.into());
@@ -537,109 +744,51 @@ impl Fold<SeqExpr> for SimplifyExpr {
     }
 }
 
-/// Folds 'typeof(foo)' if foo is a literal, e.g.
-///
-/// typeof("bar") --> "string"
-///
-/// typeof(6) --> "number"
-fn try_fold_typeof(UnaryExpr { span, op, arg }: UnaryExpr) -> Expr {
-    assert_eq!(op, op!("typeof"));
-
-    let val = match *arg {
-        Expr::Fn(..) => "function",
-        Expr::Lit(Lit::Str { .. }) => "string",
-        Expr::Lit(Lit::Num(..)) => "number",
-        Expr::Lit(Lit::Bool(..)) => "boolean",
-        Expr::Lit(Lit::Null(..)) | Expr::Object { .. } | Expr::Array { .. } => "object",
-        Expr::Unary(UnaryExpr {
-            op: op!("void"), ..
+/// Returns true if `e` is `a?.b` or a subscript of it, like `a?.b.c`.
+fn is_opt_chain(e: &Expr) -> bool {
+    match *e {
+        Expr::OptChain(..) => true,
+        Expr::Member(MemberExpr {
+            obj: ExprOrSuper::Expr(ref obj),
+            ..
         })
-        | Expr::Ident(Ident {
-            sym: js_word!("undefined"),
+        | Expr::Call(CallExpr {
+            callee: ExprOrSuper::Expr(ref obj),
             ..
-        }) => {
-            // We can assume `undefined` is `undefined`,
-            // because overriding `undefined` is always hard error in swc.
-            "undefined"
-        }
-
-        _ => {
-            return Expr::Unary(UnaryExpr {
-                op: op!("typeof"),
-                arg,
-                span,
-            })
-        }
-    };
-
-    Expr::Lit(Lit::Str(Str {
-        span: mark!(span),
-        value: val.into(),
-        has_escape: false,
-    }))
+        }) => is_opt_chain(obj),
+        _ => false,
+    }
 }
 
-fn fold_unary(UnaryExpr { span, op, arg }: UnaryExpr) -> Expr {
-    let may_have_side_effects = arg.may_have_side_effects();
+/// Keeps sequences in callees, as `(0, a.b)()` calls `a.b` without `this`.
+impl Fold<CallExpr> for SimplifyExpr {
+    fn fold(&mut self, e: CallExpr) -> CallExpr {
+        let CallExpr { span, callee, args } = e;
 
-    match op {
-        op!("typeof") if !may_have_side_effects => {
-            return try_fold_typeof(UnaryExpr { span, op, arg })
-        }
-        op!("!") => match arg.as_bool() {
-            (_, Known(val)) => return make_bool_expr(span, !val, iter::once(arg)),
-            _ => return Expr::Unary(UnaryExpr { op, arg, span }),
-        },
-        op!(unary, "+") => match arg.as_number() {
-            Known(v) => {
-                return preserve_effects(
-                    span,
-                    Expr::Lit(Lit::Num(Number {
-                        value: v,
-                        span: mark!(span),
-                    })),
-                    iter::once(arg),
-                )
-            }
-            _ => return Expr::Unary(UnaryExpr { op, arg, span }),
-        },
-        op!(unary, "-") => match *arg {
-            Expr::Ident(Ident {
-                sym: js_word!("Infinity"),
-                span,
-            }) => return Expr::Unary(UnaryExpr { op, arg, span }),
-            // "-NaN" is "NaN"
-            Expr::Ident(Ident {
-                sym: js_word!("NaN"),
+        let callee = match callee {
+            ExprOrSuper::Expr(box Expr::Paren(ParenExpr {
+                expr: box Expr::Seq(seq),
                 ..
-            }) => return *arg,
-            Expr::Lit(Lit::Num(Number { value: f, .. })) => {
-                return Expr::Lit(Lit::Num(Number {
-                    value: -f,
-                    span: mark!(span),
+            }))
+            | ExprOrSuper::Expr(box Expr::Seq(seq)) => {
+                let exprs = seq.exprs.into_iter().map(|e| e.fold_with(self)).collect();
+                ExprOrSuper::Expr(box Expr::Seq(SeqExpr {
+                    span: seq.span,
+                    exprs,
                 }))
             }
-            _ => {
+            _ => callee.fold_with(self),
+        };
 
-                // TODO: Report that user is something bad (negating non-number value)
-            }
-        },
-        op!("void") if !may_have_side_effects => {
-            return Expr::Unary(UnaryExpr {
-                op: op!("void"),
-                arg: box Expr::Lit(Lit::Num(Number {
-                    value: 0.0,
-                    span: mark!(arg.span()),
-                })),
-                span,
-            })
+        CallExpr {
+            span,
+            callee,
+            args: args.fold_with(self),
         }
-        _ => {}
     }
-
-    Expr::Unary(UnaryExpr { op, arg, span })
 }
 
+
 /// Try to fold arithmetic binary operators
 fn perform_arithmetic_op(op: BinaryOp, left: &Expr, right: &Expr) -> Value<f64> {
     /// Replace only if it becomes shorter
@@ -846,7 +995,11 @@ fn perform_abstract_eq_cmp(span: Span, left: &Expr, right: &Expr) -> Value<bool>
             return perform_abstract_eq_cmp(
                 span,
                 left,
-                &Expr::Lit(Lit::Num(Number { value: rv, span })),
+                &Expr::Lit(Lit::Num(Number {
+                    value: rv,
+                    span,
+                    raw: None,
+                })),
             );
         }
 
@@ -854,7 +1007,11 @@ fn perform_abstract_eq_cmp(span: Span, left: &Expr, right: &Expr) -> Value<bool>
             let lv = left.as_number()?;
             return perform_abstract_eq_cmp(
                 span,
-                &Expr::Lit(Lit::Num(Number { value: lv, span })),
+                &Expr::Lit(Lit::Num(Number {
+                    value: lv,
+                    span,
+                    raw: None,
+                })),
                 right,
             );
         }
@@ -935,84 +1092,8 @@ fn preserve_effects<I>(span: Span, val: Expr, exprs: I) -> Expr
 where
     I: IntoIterator<Item = Box<Expr>>,
 {
-    /// Add side effects of `expr` to `v`
-    /// preserving order and conditions. (think a() ? yield b() : c())
-    fn add_effects(v: &mut Vec<Box<Expr>>, box expr: Box<Expr>) {
-        match expr {
-            Expr::Lit(..) | Expr::This(..) | Expr::Fn(..) | Expr::Arrow(..) | Expr::Ident(..) => {
-                return
-            }
-
-            // In most case, we can do nothing for this.
-            Expr::Update(_) | Expr::Assign(_) | Expr::Yield(_) | Expr::Await(_) => v.push(box expr),
-
-            // TODO
-            Expr::MetaProp(_) => v.push(box expr),
-
-            Expr::Call(_) => v.push(box expr),
-            Expr::New(NewExpr {
-                callee: box Expr::Ident(Ident { ref sym, .. }),
-                ref args,
-                ..
-            }) if &*sym == "Date" && args.is_empty() => {}
-            Expr::New(_) => v.push(box expr),
-            Expr::Member(_) => v.push(box expr),
-
-            // We are at here because we could not determine value of test.
-            //TODO: Drop values if it does not have side effects.
-            Expr::Cond(_) => v.push(box expr),
-
-            Expr::Unary(UnaryExpr { arg, .. }) => add_effects(v, arg),
-            Expr::Bin(BinExpr { left, right, .. }) => {
-                add_effects(v, left);
-                add_effects(v, right);
-            }
-            Expr::Seq(SeqExpr { exprs, .. }) => exprs.into_iter().for_each(|e| add_effects(v, e)),
-
-            Expr::Paren(e) => add_effects(v, e.expr),
-
-            Expr::Object(ObjectLit { props, .. }) => {
-                props.into_iter().for_each(|node| match node {
-                    PropOrSpread::Prop(box node) => match node {
-                        Prop::Shorthand(..) => return,
-                        Prop::KeyValue(KeyValueProp { key, value }) => {
-                            match key {
-                                PropName::Computed(e) => add_effects(v, e),
-                                _ => {}
-                            }
-
-                            add_effects(v, value)
-                        }
-                        Prop::Getter(GetterProp { key, .. })
-                        | Prop::Setter(SetterProp { key, .. })
-                        | Prop::Method(MethodProp { key, .. }) => match key {
-                            PropName::Computed(e) => add_effects(v, e),
-                            _ => {}
-                        },
-                        Prop::Assign(..) => {
-                            unreachable!("assign property in object literal is not a valid syntax")
-                        }
-                    },
-                    PropOrSpread::Spread(SpreadElement { expr, .. }) => add_effects(v, expr),
-                })
-            }
-
-            Expr::Array(ArrayLit { elems, .. }) => {
-                elems.into_iter().filter_map(|e| e).fold(v, |v, e| {
-                    add_effects(v, e.expr);
-
-                    v
-                });
-                return;
-            }
-
-            Expr::Tpl { .. } => unimplemented!("add_effects for template literal"),
-            Expr::Class(ClassExpr { .. }) => unimplemented!("add_effects for class expression"),
-        }
-    }
-
     let mut exprs = exprs.into_iter().fold(vec![], |mut v, e| {
-        add_effects(&mut v, e);
+        extract_side_effects_to(&mut v, e);
         v
     });
 
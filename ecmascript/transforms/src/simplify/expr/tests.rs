@@ -1,7 +1,8 @@
 use super::SimplifyExpr;
+use swc_common::{Repeat, Repeated};
 
 fn test_expr(src: &str, expected: &str) {
-    test_transform!(SimplifyExpr, src, expected, true)
+    test_transform!(SimplifyExpr::default(), src, expected, true)
 }
 
 /// Should not modify expression.
@@ -35,6 +36,19 @@ fn cond_simple() {
     test_expr("false ? 3 : 6", "6");
 }
 
+#[test]
+fn opt_chain() {
+    same_expr("'a'?.length");
+    same_expr("(a?.b).c");
+    test_expr("(a.b).c", "a.b.c");
+}
+
+#[test]
+fn seq_callee() {
+    same_expr("(0, a.b)()");
+    test_expr("(0, a.b)(8 + 8)", "(0, a.b)(16)");
+}
+
 #[test]
 fn cond_side_effect() {
     test_expr("new UnknownClass() ? 3 : 6", "new UnknownClass(), 3");
@@ -538,6 +552,34 @@ fn seq_expr_array() {
     test_expr("([foo()], x)", "(foo(), x)");
 }
 
+#[test]
+fn repeat() {
+    test_transform!(
+        Repeat::new(SimplifyExpr::default()),
+        "use(([1, foo()], x))",
+        "use((foo(), x))",
+        true
+    );
+}
+
+#[test]
+fn changed() {
+    crate::tests::Tester::run(crate::tests::syntax(), |tester| {
+        let mut pass = SimplifyExpr::default();
+        tester.apply_transform(&mut pass, "input.js", "use(a + b, typeof c);")?;
+        assert!(!pass.changed());
+
+        tester.apply_transform(&mut pass, "input.js", "use(!true);")?;
+        assert!(pass.changed());
+
+        pass.reset();
+        tester.apply_transform(&mut pass, "input.js", "use(void 0);")?;
+        assert!(!pass.changed());
+
+        Ok(())
+    });
+}
+
 #[test]
 #[ignore]
 fn logical_ops() {
@@ -2,9 +2,10 @@
 use self::expr::SimplifyExpr;
 use ast::*;
 use crate::util::*;
-use swc_common::{Fold, FoldWith, DUMMY_SP};
+use swc_common::{Fold, FoldWith, Repeat, DUMMY_SP};
 
-mod expr;
+pub mod dce;
+pub mod expr;
 #[cfg(test)]
 mod tests;
 
@@ -22,7 +23,8 @@ where
     fn fold(&mut self, stmts: Vec<T>) -> Vec<T> {
         let mut buf = Vec::with_capacity(stmts.len());
 
-        for stmt_like in stmts {
+        let mut stmts = stmts.into_iter();
+        while let Some(stmt_like) = stmts.next() {
             let stmt_like = self.fold(stmt_like);
             let stmt_like = match stmt_like.try_into_stmt() {
                 Ok(stmt) => {
@@ -37,6 +39,16 @@ where
                         | Stmt::Break { .. } => {
                             let stmt_like = T::from_stmt(stmt);
                             buf.push(stmt_like);
+
+                            // Following statements are unreachable, but declarations in them are
+                            // hoisted.
+                            for stmt_like in stmts {
+                                match stmt_like.try_into_stmt() {
+                                    Ok(stmt) => buf
+                                        .extend(hoisted_decls(stmt).into_iter().map(T::from_stmt)),
+                                    Err(stmt_like) => buf.push(stmt_like),
+                                }
+                            }
                             return buf;
                         }
                         // Optimize if statement.
@@ -49,10 +61,25 @@ where
                             // check if
                             let node = match test.as_bool() {
                                 (Pure, Known(val)) => {
-                                    if val {
-                                        *cons
+                                    let (node, dead) = if val {
+                                        (*cons, alt.map(|e| *e))
                                     } else {
-                                        alt.map(|e| *e).unwrap_or(Stmt::Empty(EmptyStmt { span }))
+                                        (
+                                            alt.map(|e| *e)
+                                                .unwrap_or(Stmt::Empty(EmptyStmt { span })),
+                                            Some(*cons),
+                                        )
+                                    };
+
+                                    let mut stmts = dead.map(hoisted_decls).unwrap_or_default();
+                                    match node {
+                                        Stmt::Empty(..) => {}
+                                        _ => stmts.push(node),
+                                    }
+                                    match stmts.len() {
+                                        0 => Stmt::Empty(EmptyStmt { span }),
+                                        1 => stmts.pop().unwrap(),
+                                        _ => Stmt::Block(BlockStmt { span, stmts }),
                                     }
                                 }
                                 // TODO: Impure
@@ -83,14 +110,17 @@ where
 impl Fold<Stmt> for Simplifier {
     fn fold(&mut self, stmt: Stmt) -> Stmt {
         // Simplfy expressions.
-        let stmt = stmt.fold_children(&mut SimplifyExpr);
+        let stmt = stmt.fold_children(&mut Repeat::new(SimplifyExpr::default()));
         let stmt = stmt.fold_children(self);
 
         match stmt {
             // `1;` -> `;`
-            Stmt::Expr(box node) => match node {
+            Stmt::Expr(ExprStmt {
+                span,
+                expr: box node,
+            }) => match node {
                 Expr::Lit(Lit::Num(..)) | Expr::Lit(Lit::Bool(..)) | Expr::Lit(Lit::Regex(..)) => {
-                    Stmt::Empty(EmptyStmt { span: DUMMY_SP })
+                    Stmt::Empty(EmptyStmt { span })
                 }
 
                 //
@@ -103,14 +133,16 @@ impl Fold<Stmt> for Simplifier {
                     function: Function { span, .. },
                     ..
                 }) => Stmt::Empty(EmptyStmt { span }),
-                _ => Stmt::Expr(box node),
+                _ => Stmt::Expr(ExprStmt {
+                    span,
+                    expr: box node,
+                }),
             },
 
             Stmt::Block(BlockStmt { span, stmts }) => {
                 if stmts.len() == 0 {
                     return Stmt::Empty(EmptyStmt { span });
-                } else if stmts.len() == 1 {
-                    // TODO: Check if lexical variable exists.
+                } else if stmts.len() == 1 && !is_block_scoped(&stmts[0]) {
                     return stmts.into_iter().next().unwrap();
                 } else {
                     Stmt::Block(BlockStmt { span, stmts })
@@ -169,34 +201,92 @@ impl Fold<Stmt> for Simplifier {
                 })
             }
 
+            Stmt::Switch(s) => prune_cases(s),
+
             _ => stmt,
         }
     }
 }
 
-trait StmtLike: Sized {
-    fn try_into_stmt(self) -> Result<Stmt, Self>;
-    fn from_stmt(stmt: Stmt) -> Self;
-}
+/// Removes cases of `switch` which can't be reached, if the discriminant and
+/// tests are literals.
+///
+/// `switch (1) { case 0: a(); case 1: b(); default: c(); }` becomes
+/// `switch (1) { case 1: b(); default: c(); }`.
+fn prune_cases(s: SwitchStmt) -> Stmt {
+    let start = match first_reachable_case(&s) {
+        Some(start) => start,
+        None => return Stmt::Switch(s),
+    };
+    if start == 0 {
+        return Stmt::Switch(s);
+    }
 
-impl StmtLike for Stmt {
-    fn try_into_stmt(self) -> Result<Stmt, Self> {
-        Ok(self)
+    let SwitchStmt {
+        span,
+        discriminant,
+        mut cases,
+    } = s;
+    let reachable = cases.split_off(start);
+
+    let mut stmts: Vec<_> = cases
+        .into_iter()
+        .flat_map(|case| case.cons)
+        .flat_map(hoisted_decls)
+        .collect();
+    if !reachable.is_empty() {
+        stmts.push(Stmt::Switch(SwitchStmt {
+            span,
+            discriminant,
+            cases: reachable,
+        }));
     }
-    fn from_stmt(stmt: Stmt) -> Self {
-        stmt
+
+    match stmts.len() {
+        0 => Stmt::Empty(EmptyStmt { span }),
+        1 => stmts.pop().unwrap(),
+        _ => Stmt::Block(BlockStmt { span, stmts }),
     }
 }
 
-impl StmtLike for ModuleItem {
-    fn try_into_stmt(self) -> Result<Stmt, Self> {
-        match self {
-            ModuleItem::Stmt(stmt) => Ok(stmt),
-            _ => Err(self),
+/// Returns the index of the case which is executed first, which is the length
+/// of `cases` if none is executed.
+///
+/// Returns `None` if it's unknown.
+fn first_reachable_case(s: &SwitchStmt) -> Option<usize> {
+    let discriminant = match *s.discriminant {
+        Expr::Lit(ref lit) => lit,
+        _ => return None,
+    };
+
+    let mut default = None;
+    for (i, case) in s.cases.iter().enumerate() {
+        match case.test {
+            // Tests after the matching one are not evaluated.
+            Some(box Expr::Lit(ref lit)) => {
+                if lit_strict_eq(discriminant, lit)? {
+                    return Some(i);
+                }
+            }
+            Some(..) => return None,
+            None => default = Some(i),
         }
     }
-    fn from_stmt(stmt: Stmt) -> Self {
-        ModuleItem::Stmt(stmt)
+
+    Some(default.unwrap_or(s.cases.len()))
+}
+
+/// Returns `None` if the result of `===` is unknown.
+fn lit_strict_eq(l: &Lit, r: &Lit) -> Option<bool> {
+    match (l, r) {
+        (&Lit::Str(ref l), &Lit::Str(ref r)) => Some(l.value == r.value),
+        (&Lit::Bool(ref l), &Lit::Bool(ref r)) => Some(l.value == r.value),
+        (&Lit::Null(..), &Lit::Null(..)) => Some(true),
+        (&Lit::Num(ref l), &Lit::Num(ref r)) => Some(l.value == r.value),
+        (&Lit::BigInt(..), _) | (_, &Lit::BigInt(..)) => None,
+        // Different types, or regular expressions which create a new object
+        // each time.
+        _ => Some(false),
     }
 }
 
@@ -35,3 +35,52 @@ fn compiled_out_simple() {
     compiled_out!("8;");
     compiled_out!("8+8;");
 }
+
+#[test]
+fn unreachable_decls_are_hoisted() {
+    test_stmt!(
+        "function f() { return g(); var a = 1; function g() {} h(); }",
+        "function f() { return g(); var a; function g() {} }"
+    );
+    test_stmt!("if (false) { var a = 1; b(); }", "var a;");
+}
+
+#[test]
+fn impure_test() {
+    same_stmt!("if (a(), true) b();");
+    same_stmt!("if (a = true) b();");
+}
+
+#[test]
+fn block_scoped() {
+    same_stmt!("{ let a = 1; }");
+    test_stmt!("{ var a = 1; }", "var a = 1;");
+}
+
+#[test]
+fn switch_cases() {
+    test_stmt!(
+        "switch (2) { case 1: a(); case 2: b(); break; default: c(); }",
+        "switch (2) { case 2: b(); break; default: c(); }"
+    );
+    test_stmt!(
+        "switch (1) { default: a(); case 1: b(); }",
+        "switch (1) { case 1: b(); }"
+    );
+    test_stmt!(
+        "switch ('a') { case 'b': var x = 1; default: c(); }",
+        "{ var x; switch ('a') { default: c(); } }"
+    );
+    compiled_out!("switch (3) { case 1: a(); case '3': b(); }");
+    same_stmt!("switch (1) { case a: b(); case 1: c(); }");
+}
+
+#[test]
+fn fixture() {
+    ::testing::test_fixture(
+        Default::default(),
+        || Simplifier,
+        "tests/fixture/simplify/fold/input.js",
+        "tests/fixture/simplify/fold/output.js",
+    );
+}
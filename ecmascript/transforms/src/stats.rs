@@ -0,0 +1,239 @@
+//! Statistics of a module, for heuristics and for tracking of parser output.
+
+use ast::*;
+use std::collections::BTreeMap;
+use swc_common::{Spanned, Visit, VisitWith};
+
+/// Counts of nodes in a module.
+///
+/// # Example
+///
+/// ```ignore
+/// let stats = AstStats::of(&module);
+/// if stats.total() < 100 {
+///     // Skip minification of tiny modules.
+/// }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct AstStats {
+    /// Type names of nodes to the number of them.
+    pub nodes: BTreeMap<&'static str, usize>,
+    /// Maximum number of nodes from the module to a leaf, including both.
+    pub max_depth: usize,
+    /// Number of bytes covered by items of the module.
+    ///
+    /// Items with a dummy span are ignored.
+    pub span_coverage: usize,
+    depth: usize,
+}
+
+impl AstStats {
+    pub fn of(module: &Module) -> Self {
+        let mut stats = AstStats::default();
+        module.visit_with(&mut stats);
+
+        stats.span_coverage = module
+            .body
+            .iter()
+            .map(|item| item.span())
+            .filter(|span| !span.is_dummy())
+            .map(|span| (span.hi().0 - span.lo().0) as usize)
+            .sum();
+
+        stats
+    }
+
+    /// Number of nodes of type `name`.
+    pub fn count(&self, name: &str) -> usize {
+        self.nodes.get(name).cloned().unwrap_or(0)
+    }
+
+    /// Number of all nodes.
+    pub fn total(&self) -> usize {
+        self.nodes.values().sum()
+    }
+}
+
+macro_rules! count {
+    ($($T:ident),*) => {
+        $(
+            impl Visit<$T> for AstStats {
+                fn visit(&mut self, node: &$T) {
+                    *self.nodes.entry(stringify!($T)).or_insert(0) += 1;
+
+                    self.depth += 1;
+                    if self.depth > self.max_depth {
+                        self.max_depth = self.depth;
+                    }
+                    node.visit_children(self);
+                    self.depth -= 1;
+                }
+            }
+        )*
+    };
+}
+
+count!(
+    ArrayLit,
+    ArrayPat,
+    ArrowExpr,
+    AssignExpr,
+    AssignPat,
+    AssignPatProp,
+    AssignProp,
+    AwaitExpr,
+    BigInt,
+    BinExpr,
+    BlockStmt,
+    Bool,
+    BreakStmt,
+    CallExpr,
+    CatchClause,
+    Class,
+    ClassDecl,
+    ClassExpr,
+    ClassMethod,
+    ClassProp,
+    CondExpr,
+    ContinueStmt,
+    DebuggerStmt,
+    Decorator,
+    DoWhileStmt,
+    EmptyStmt,
+    ExportAll,
+    ExportSpecifier,
+    ExprOrSpread,
+    FnDecl,
+    FnExpr,
+    ForInStmt,
+    ForOfStmt,
+    ForStmt,
+    Function,
+    GetterProp,
+    Ident,
+    IfStmt,
+    ImportDecl,
+    ImportDefault,
+    ImportSpecific,
+    ImportStarAs,
+    JSXAttr,
+    JSXClosingElement,
+    JSXClosingFragment,
+    JSXElement,
+    JSXEmptyExpr,
+    JSXExprContainer,
+    JSXFragment,
+    JSXMemberExpr,
+    JSXNamespacedName,
+    JSXOpeningElement,
+    JSXOpeningFragment,
+    JSXSpreadChild,
+    JSXText,
+    KeyValuePatProp,
+    KeyValueProp,
+    LabeledStmt,
+    MemberExpr,
+    MetaPropExpr,
+    MethodProp,
+    Module,
+    NamedExport,
+    NewExpr,
+    Null,
+    Number,
+    ObjectLit,
+    ObjectPat,
+    OptChainExpr,
+    Param,
+    ParenExpr,
+    PrivateMethod,
+    PrivateName,
+    PrivateProp,
+    Program,
+    Regex,
+    RestPat,
+    ReturnStmt,
+    Script,
+    SeqExpr,
+    SetterProp,
+    SpreadElement,
+    StaticBlock,
+    Str,
+    SwitchCase,
+    SwitchStmt,
+    ThisExpr,
+    ThrowStmt,
+    TplElement,
+    TplLit,
+    TryStmt,
+    TsArrayType,
+    TsAsExpr,
+    TsCallSignatureDecl,
+    TsConditionalType,
+    TsConstructSignatureDecl,
+    TsConstructorType,
+    TsEnumDecl,
+    TsEnumMember,
+    TsExprWithTypeArgs,
+    TsFnType,
+    TsIndexSignature,
+    TsIndexedAccessType,
+    TsInterfaceBody,
+    TsInterfaceDecl,
+    TsIntersectionType,
+    TsKeywordType,
+    TsLitType,
+    TsMappedType,
+    TsMethodSignature,
+    TsModuleBlock,
+    TsModuleDecl,
+    TsParenthesizedType,
+    TsPropertySignature,
+    TsQualifiedName,
+    TsThisType,
+    TsTupleType,
+    TsTypeAliasDecl,
+    TsTypeAnn,
+    TsTypeLit,
+    TsTypeOperator,
+    TsTypeParam,
+    TsTypeParamDecl,
+    TsTypeParamInstantiation,
+    TsTypeQuery,
+    TsTypeRef,
+    TsTypedPat,
+    TsUnionType,
+    UnaryExpr,
+    UpdateExpr,
+    VarDecl,
+    VarDeclarator,
+    WhileStmt,
+    WithStmt,
+    YieldExpr
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixer;
+
+    #[test]
+    fn stats() {
+        crate::tests::Tester::run(crate::tests::syntax(), |tester| {
+            let module = tester.apply_transform(fixer(), "input.js", "use(1);\nfoo;")?;
+            let stats = AstStats::of(&module);
+
+            assert_eq!(stats.count("Module"), 1);
+            assert_eq!(stats.count("CallExpr"), 1);
+            assert_eq!(stats.count("Ident"), 2);
+            assert_eq!(stats.count("Number"), 1);
+            assert_eq!(stats.count("ForStmt"), 0);
+            assert_eq!(stats.total(), 6);
+            // Module > CallExpr > ExprOrSpread > Number
+            assert_eq!(stats.max_depth, 4);
+            // `use(1)` and `foo`
+            assert_eq!(stats.span_coverage, 9);
+
+            Ok(())
+        });
+    }
+}
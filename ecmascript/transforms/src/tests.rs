@@ -1,104 +1,16 @@
-use ast::*;
-use slog::Logger;
-use sourcemap::SourceMapBuilder;
-use std::{
-    io::{self, Write},
-    sync::{Arc, RwLock},
-};
-use swc_common::{errors::Handler, sync::Lrc, FileName, Fold, FoldWith, SourceMap};
-use swc_ecma_codegen::Emitter;
-use swc_ecma_parser::{Parser, Session, SourceFileInput};
-
-struct MyHandlers;
-
-impl swc_ecma_codegen::Handlers for MyHandlers {}
-
-pub(crate) struct Tester<'a> {
-    cm: Lrc<SourceMap>,
-    logger: Logger,
-    handler: &'a Handler,
-}
-
-impl<'a> Tester<'a> {
-    pub fn run<F>(op: F)
-    where
-        F: FnOnce(&mut Tester) -> Result<(), ()>,
-    {
-        let out = ::testing::run_test(|logger, cm, handler| {
-            op(&mut Tester {
-                cm,
-                logger,
-                handler,
-            })
-        });
-
-        match out {
-            Ok(()) => {}
-            Err(stderr) => panic!("Stderr:\n{}", stderr),
-        }
-    }
-
-    pub fn apply_transform<T: Fold<Module>>(
-        &mut self,
-        mut tr: T,
-        name: &str,
-        src: &str,
-    ) -> Result<Module, ()> {
-        let fm = self
-            .cm
-            .new_source_file(FileName::Real(name.into()), src.into());
-
-        let module = {
-            let sess = Session {
-                handler: &self.handler,
-                logger: &self.logger,
-                cfg: Default::default(),
-            };
-
-            let module = {
-                let mut p = Parser::new(sess, SourceFileInput::from(&*fm));
-                p.parse_module()?
-            };
-            // println!("parsed {} as a module\n{:?}", src, module);
-
-            module
-        };
-
-        let module = module
-            .fold_with(&mut tr)
-            .fold_with(&mut ::testing::DropSpan)
-            .fold_with(&mut Normalizer);
-
-        Ok(module)
-    }
-
-    pub fn print(&mut self, module: &Module) -> String {
-        let handlers = box MyHandlers;
-
-        let mut wr = Buf(Arc::new(RwLock::new(vec![])));
-        {
-            let mut src_map_builder = SourceMapBuilder::new(None);
-            let mut emitter = Emitter {
-                cfg: Default::default(),
-                cm: self.cm.clone(),
-                wr: box swc_ecma_codegen::text_writer::JsWriter::new(
-                    self.cm.clone(),
-                    "\n",
-                    &mut wr,
-                    &mut src_map_builder,
-                ),
-                handlers,
-                pos_of_leading_comments: Default::default(),
-            };
-
-            // println!("Emitting: {:?}", module);
-            emitter.emit_module(&module).unwrap();
-        }
-
-        let r = wr.0.read().unwrap();
-        let s = String::from_utf8_lossy(&*r);
-        s.to_string()
-    }
+use swc_ecma_parser::{EsConfig, Syntax};
+pub(crate) use testing::Tester;
+
+/// Syntax of inputs and expected outputs, with proposals lowered or handled by
+/// passes enabled.
+pub(crate) fn syntax() -> Syntax {
+    Syntax::Es(EsConfig {
+        class_props: true,
+        optional_chaining: true,
+        nullish_coalescing: true,
+        jsx: true,
+        ..Default::default()
+    })
 }
 
 #[cfg(test)]
@@ -108,35 +20,8 @@ macro_rules! test_transform {
     };
 
     ($tr:expr, $input:expr, $expected:expr, $ok_if_src_eq:expr) => {{
-        use swc_common::FoldWith;
-
-        crate::tests::Tester::run(|tester: &mut crate::tests::Tester| {
-            let expected =
-                tester.apply_transform(crate::fixer::fixer(), "expected.js", $expected)?;
-
-            let actual = tester.apply_transform($tr, "actual.js", $input)?;
-            let actual = actual.fold_with(&mut crate::fixer::fixer());
-
-            if actual == expected {
-                return Ok(());
-            }
-
-            let (actual_src, expected_src) = (tester.print(&actual), tester.print(&expected));
-
-            if actual_src == expected_src {
-                if $ok_if_src_eq {
-                    return Ok(());
-                }
-                // Diff it
-                println!(">>>>> Code <<<<<\n{}", actual_src);
-                assert_eq!(actual, expected, "different ast was detected");
-                unreachable!()
-            }
-
-            panic!(
-                "\n>>>>> Actual <<<<<\n{}\n>>>>> Expected <<<<<\n{}",
-                actual_src, expected_src
-            );
+        crate::tests::Tester::run(crate::tests::syntax(), |tester| {
+            tester.test_transform(crate::fixer::fixer, $tr, $input, $expected, $ok_if_src_eq)
         });
     }};
 }
@@ -159,40 +44,20 @@ macro_rules! test {
     };
 }
 
-/// Test transformation.
+/// Test transformation by running the transformed code with node.
+///
+/// `assert` is the `assert` module of node.js.
 #[cfg(test)]
 macro_rules! test_exec {
     ($tr:expr, $test_name:ident, $input:expr) => {
         #[test]
-        #[ignore]
         fn $test_name() {
-            crate::tests::Tester::run(|tester| {
-                let _transformed = tester.apply_transform($tr, stringify!($test_name), $input);
-
-                Ok(())
-            });
+            ::testing::test_exec(
+                &::testing::Node,
+                crate::tests::syntax(),
+                || ::swc_common::Fold::<::ast::Module>::then($tr, crate::fixer::fixer()),
+                $input,
+            )
         }
     };
 }
-
-#[derive(Debug, Clone)]
-struct Buf(Arc<RwLock<Vec<u8>>>);
-impl Write for Buf {
-    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
-        self.0.write().unwrap().write(data)
-    }
-
-    fn flush(&mut self) -> io::Result<()> {
-        self.0.write().unwrap().flush()
-    }
-}
-
-struct Normalizer;
-impl Fold<PatOrExpr> for Normalizer {
-    fn fold(&mut self, n: PatOrExpr) -> PatOrExpr {
-        match n {
-            PatOrExpr::Pat(box Pat::Expr(e)) => PatOrExpr::Expr(e),
-            _ => n,
-        }
-    }
-}
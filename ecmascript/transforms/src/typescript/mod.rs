@@ -0,0 +1,555 @@
+use crate::util::{pat_ids, ExprFactory};
+use ast::*;
+use std::collections::HashSet;
+use swc_atoms::JsWord;
+use swc_common::{Fold, FoldWith, Span, Visit, VisitWith, DUMMY_SP};
+
+#[cfg(test)]
+mod tests;
+
+/// Removes typescript syntax, so that other passes see ecmascript.
+///
+/// Interfaces, type aliases, `declare`d declarations, overloads, type
+/// annotations and `as` are removed. Enums and namespaces are lowered like
+/// tsc does. Imports which are only used as types are removed, while
+/// `React` is kept for jsx.
+///
+/// This doesn't look at other files, so
+///
+///  - members of enums should refer to other members as `E.A`, as members
+///    are not inlined.
+///  - exported bindings of namespaces are copied to the namespace at its
+///    end, so later assignments to them are not seen outside of it.
+pub fn strip() -> impl Fold<Module> {
+    Strip
+}
+
+#[derive(Debug)]
+struct Strip;
+
+/// What a declaration is replaced with.
+enum Lowered {
+    /// The declaration only declares types.
+    Removed,
+    Decl(Decl),
+    /// `var E;` and the statement initializing `E`.
+    Var(Ident, Stmt),
+}
+
+impl Fold<Module> for Strip {
+    fn fold(&mut self, module: Module) -> Module {
+        // Names which can't be exported after types are removed.
+        let mut types = HashSet::new();
+        for item in &module.body {
+            match *item {
+                ModuleItem::Stmt(Stmt::Decl(ref decl))
+                | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { ref decl, .. })) => {
+                    match *decl {
+                        Decl::TsInterface(TsInterfaceDecl { ref id, .. })
+                        | Decl::TsTypeAlias(TsTypeAliasDecl { ref id, .. }) => {
+                            types.insert(id.sym.clone());
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let mut module = module.fold_children(self);
+
+        // Interfaces may be merged with classes.
+        let mut values = vec![];
+        for item in &module.body {
+            match *item {
+                ModuleItem::Stmt(Stmt::Decl(ref decl))
+                | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { ref decl, .. })) => {
+                    match *decl {
+                        Decl::Fn(FnDecl { ref ident, .. })
+                        | Decl::Class(ClassDecl { ref ident, .. }) => values.push(ident.clone()),
+                        Decl::Var(VarDecl { ref decls, .. }) => {
+                            for d in decls {
+                                pat_ids(&d.name, &mut values);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+        types.retain(|sym| values.iter().all(|v| v.sym != *sym));
+
+        let mut used = UsedFinder {
+            used: Default::default(),
+        };
+        for item in &module.body {
+            match *item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(..)) => {}
+                _ => item.visit_with(&mut used),
+            }
+        }
+        let used = used.used;
+
+        module.body = module
+            .body
+            .into_iter()
+            .filter_map(|item| match item {
+                // `import 'foo'` is kept.
+                ModuleItem::ModuleDecl(ModuleDecl::Import(mut import))
+                    if !import.specifiers.is_empty() =>
+                {
+                    import.specifiers.retain(|s| {
+                        let local = match *s {
+                            ImportSpecifier::Specific(ImportSpecific { ref local, .. })
+                            | ImportSpecifier::Default(ImportDefault { ref local, .. })
+                            | ImportSpecifier::Namespace(ImportStarAs { ref local, .. }) => local,
+                        };
+                        used.contains(&local.sym)
+                    });
+                    if import.specifiers.is_empty() {
+                        None
+                    } else {
+                        Some(ModuleItem::ModuleDecl(ModuleDecl::Import(import)))
+                    }
+                }
+                // `export {}` is kept.
+                ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(mut export))
+                    if export.src.is_none() && !export.specifiers.is_empty() =>
+                {
+                    export.specifiers.retain(|s| !types.contains(&s.orig.sym));
+                    if export.specifiers.is_empty() {
+                        None
+                    } else {
+                        Some(ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)))
+                    }
+                }
+                item => Some(item),
+            })
+            .collect();
+
+        module
+    }
+}
+
+impl Fold<Vec<ModuleItem>> for Strip {
+    fn fold(&mut self, items: Vec<ModuleItem>) -> Vec<ModuleItem> {
+        let mut buf = Vec::with_capacity(items.len());
+
+        for item in items {
+            match item.fold_with(self) {
+                ModuleItem::Stmt(Stmt::Decl(decl)) => match self.lower_decl(decl) {
+                    Lowered::Removed => {}
+                    Lowered::Decl(decl) => buf.push(ModuleItem::Stmt(Stmt::Decl(decl))),
+                    Lowered::Var(id, stmt) => {
+                        buf.push(ModuleItem::Stmt(var(id)));
+                        buf.push(ModuleItem::Stmt(stmt));
+                    }
+                },
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { span, decl })) => {
+                    match self.lower_decl(decl) {
+                        Lowered::Removed => {}
+                        Lowered::Decl(decl) => buf.push(ModuleItem::ModuleDecl(
+                            ModuleDecl::ExportDecl(ExportDecl { span, decl }),
+                        )),
+                        // export var E;
+                        Lowered::Var(id, stmt) => {
+                            let decl = match var(id) {
+                                Stmt::Decl(decl) => decl,
+                                _ => unreachable!(),
+                            };
+                            buf.push(ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(
+                                ExportDecl { span, decl },
+                            )));
+                            buf.push(ModuleItem::Stmt(stmt));
+                        }
+                    }
+                }
+                item => buf.push(item),
+            }
+        }
+
+        buf
+    }
+}
+
+impl Fold<Vec<Stmt>> for Strip {
+    fn fold(&mut self, stmts: Vec<Stmt>) -> Vec<Stmt> {
+        let mut buf = Vec::with_capacity(stmts.len());
+
+        for stmt in stmts {
+            match stmt.fold_with(self) {
+                Stmt::Decl(decl) => match self.lower_decl(decl) {
+                    Lowered::Removed => {}
+                    Lowered::Decl(decl) => buf.push(Stmt::Decl(decl)),
+                    Lowered::Var(id, stmt) => {
+                        buf.push(var(id));
+                        buf.push(stmt);
+                    }
+                },
+                stmt => buf.push(stmt),
+            }
+        }
+
+        buf
+    }
+}
+
+impl Strip {
+    /// `decl` should be already folded.
+    fn lower_decl(&mut self, decl: Decl) -> Lowered {
+        match decl {
+            Decl::TsInterface(..)
+            | Decl::TsTypeAlias(..)
+            | Decl::Fn(FnDecl { declare: true, .. })
+            | Decl::Class(ClassDecl { declare: true, .. })
+            | Decl::Var(VarDecl { declare: true, .. })
+            | Decl::TsEnum(TsEnumDecl { declare: true, .. })
+            | Decl::TsModule(TsModuleDecl { declare: true, .. }) => Lowered::Removed,
+
+            // An overload.
+            Decl::Fn(FnDecl {
+                function: Function { body: None, .. },
+                ..
+            }) => Lowered::Removed,
+
+            Decl::TsEnum(decl) => {
+                let id = decl.id.clone();
+                Lowered::Var(id, lower_enum(decl))
+            }
+
+            Decl::TsModule(TsModuleDecl {
+                span,
+                id: TsModuleName::Ident(id),
+                body: Some(TsModuleBlock { body, .. }),
+                ..
+            }) => match lower_namespace(span, id.clone(), body) {
+                Some(stmt) => Lowered::Var(id, stmt),
+                None => Lowered::Removed,
+            },
+            // `module 'foo' {}` is only valid with `declare`.
+            Decl::TsModule(..) => Lowered::Removed,
+
+            decl => Lowered::Decl(decl),
+        }
+    }
+}
+
+/// ```js
+/// (function (E) {
+///     E[E["A"] = 0] = "A";
+///     E["B"] = "b";
+/// })(E || (E = {}));
+/// ```
+fn lower_enum(decl: TsEnumDecl) -> Stmt {
+    let TsEnumDecl {
+        span, id, members, ..
+    } = decl;
+
+    let mut stmts = vec![];
+    // Value of the next member without an initializer, if it's a constant.
+    let mut next = Some(0.0);
+    let mut prev: Option<JsWord> = None;
+    for member in members {
+        let name = match member.id {
+            TsEnumMemberId::Ident(i) => i.sym,
+            TsEnumMemberId::Str(s) => s.value,
+        };
+        // E["A"]
+        let prop = |name: &JsWord| {
+            box Expr::Member(MemberExpr {
+                span: DUMMY_SP,
+                obj: ExprOrSuper::Expr(box Expr::Ident(id.clone())),
+                prop: box Expr::Lit(Lit::Str(quote_str!(name.clone()))),
+                computed: true,
+            })
+        };
+
+        let value = match (member.init, next, prev.take()) {
+            // String members are not mapped back to their names.
+            (Some(init @ box Expr::Lit(Lit::Str(..))), ..)
+            | (Some(init @ box Expr::Tpl(..)), ..) => {
+                stmts.push(assign(prop(&name), init).into_stmt());
+                next = None;
+                prev = Some(name);
+                continue;
+            }
+            (Some(box Expr::Lit(Lit::Num(Number { value, .. }))), ..) | (None, Some(value), _) => {
+                next = Some(value + 1.0);
+                num(value)
+            }
+            (Some(init), ..) => {
+                next = None;
+                init
+            }
+            // E["A"] + 1
+            (None, None, Some(prev)) => box Expr::Bin(BinExpr {
+                span: DUMMY_SP,
+                op: op!(bin, "+"),
+                left: prop(&prev),
+                right: num(1.0),
+            }),
+            (None, None, None) => unreachable!("the first member is a constant"),
+        };
+        prev = Some(name.clone());
+
+        // E[E["A"] = 0] = "A";
+        let target = box Expr::Member(MemberExpr {
+            span: DUMMY_SP,
+            obj: ExprOrSuper::Expr(box Expr::Ident(id.clone())),
+            prop: box assign(prop(&name), value),
+            computed: true,
+        });
+        stmts.push(assign(target, box Expr::Lit(Lit::Str(quote_str!(name)))).into_stmt());
+    }
+
+    iife(span, id, stmts)
+}
+
+/// Returns `None` if the namespace only contains types.
+///
+/// Exported bindings are assigned to the namespace at the end, as nested
+/// enums and namespaces are initialized after their declarations.
+fn lower_namespace(span: Span, id: Ident, items: Vec<ModuleItem>) -> Option<Stmt> {
+    let mut stmts = vec![];
+    let mut ids = vec![];
+    for item in items {
+        let decl = match item {
+            ModuleItem::Stmt(stmt) => {
+                stmts.push(stmt);
+                continue;
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { decl, .. })) => decl,
+            // Imports and exports of modules are invalid in namespaces.
+            ModuleItem::ModuleDecl(..) => continue,
+        };
+
+        match decl {
+            Decl::Fn(FnDecl { ref ident, .. }) | Decl::Class(ClassDecl { ref ident, .. }) => {
+                ids.push(ident.clone())
+            }
+            Decl::Var(VarDecl { ref decls, .. }) => {
+                for d in decls {
+                    pat_ids(&d.name, &mut ids);
+                }
+            }
+            _ => {}
+        }
+        stmts.push(Stmt::Decl(decl));
+    }
+
+    if stmts.is_empty() {
+        return None;
+    }
+
+    // N.a = a;
+    for i in ids {
+        let prop = box Expr::Member(MemberExpr {
+            span: DUMMY_SP,
+            obj: ExprOrSuper::Expr(box Expr::Ident(id.clone())),
+            prop: box Expr::Ident(quote_ident!(i.sym.clone())),
+            computed: false,
+        });
+        stmts.push(assign(prop, box Expr::Ident(i)).into_stmt());
+    }
+
+    Some(iife(span, id, stmts))
+}
+
+/// `(function (N) { ... })(N || (N = {}));`
+fn iife(span: Span, id: Ident, stmts: Vec<Stmt>) -> Stmt {
+    let function = Function {
+        params: vec![Param {
+            span: DUMMY_SP,
+            decorators: vec![],
+            pat: Pat::Ident(id.clone()),
+        }],
+        span: DUMMY_SP,
+        body: Some(BlockStmt {
+            span: DUMMY_SP,
+            stmts,
+        }),
+        generator_token: None,
+        async_token: None,
+        type_params: None,
+        return_type: None,
+    };
+
+    let init = Expr::Bin(BinExpr {
+        span: DUMMY_SP,
+        op: op!("||"),
+        left: box Expr::Ident(id.clone()),
+        right: box assign(
+            box Expr::Ident(id),
+            box Expr::Object(ObjectLit {
+                span: DUMMY_SP,
+                props: vec![],
+            }),
+        )
+        .wrap_with_paren(),
+    });
+
+    Expr::Call(CallExpr {
+        span,
+        callee: FnExpr {
+            ident: None,
+            function,
+        }
+        .wrap_with_paren()
+        .as_callee(),
+        args: vec![init.as_arg()],
+    })
+    .into_stmt()
+}
+
+/// `var E;`
+fn var(id: Ident) -> Stmt {
+    Stmt::Decl(Decl::Var(VarDecl {
+        span: DUMMY_SP,
+        kind: VarDeclKind::Var,
+        declare: false,
+        decls: vec![VarDeclarator {
+            span: DUMMY_SP,
+            name: Pat::Ident(id),
+            init: None,
+        }],
+    }))
+}
+
+fn assign(left: Box<Expr>, right: Box<Expr>) -> Expr {
+    Expr::Assign(AssignExpr {
+        span: DUMMY_SP,
+        op: op!("="),
+        left: PatOrExpr::Expr(left),
+        right,
+    })
+}
+
+fn num(value: f64) -> Box<Expr> {
+    box Expr::Lit(Lit::Num(Number {
+        span: DUMMY_SP,
+        value,
+        raw: None,
+    }))
+}
+
+impl Fold<Expr> for Strip {
+    fn fold(&mut self, e: Expr) -> Expr {
+        match e {
+            Expr::TsAs(TsAsExpr { expr, .. }) => expr.fold_with(self),
+            _ => e.fold_children(self),
+        }
+    }
+}
+
+impl Fold<Pat> for Strip {
+    fn fold(&mut self, pat: Pat) -> Pat {
+        match pat {
+            Pat::TsTyped(TsTypedPat { pat, .. }) => pat.fold_with(self),
+            _ => pat.fold_children(self),
+        }
+    }
+}
+
+impl Fold<Function> for Strip {
+    fn fold(&mut self, f: Function) -> Function {
+        let mut f = f.fold_children(self);
+
+        // `function foo(this: Foo) {}`
+        let has_this = match f.params.first() {
+            Some(Param {
+                pat: Pat::Ident(ref i),
+                ..
+            }) => i.sym == js_word!("this"),
+            _ => false,
+        };
+        if has_this {
+            f.params.remove(0);
+        }
+
+        Function {
+            type_params: None,
+            return_type: None,
+            ..f
+        }
+    }
+}
+
+impl Fold<ArrowExpr> for Strip {
+    fn fold(&mut self, f: ArrowExpr) -> ArrowExpr {
+        ArrowExpr {
+            type_params: None,
+            return_type: None,
+            ..f.fold_children(self)
+        }
+    }
+}
+
+impl Fold<Class> for Strip {
+    fn fold(&mut self, class: Class) -> Class {
+        let class = class.fold_children(self);
+
+        Class {
+            // Overloads and abstract methods.
+            body: class
+                .body
+                .into_iter()
+                .filter(|member| match *member {
+                    ClassMember::Method(ClassMethod {
+                        function: Function { body: None, .. },
+                        ..
+                    }) => false,
+                    _ => true,
+                })
+                .collect(),
+            type_params: None,
+            super_type_params: None,
+            implements: vec![],
+            ..class
+        }
+    }
+}
+
+impl Fold<ClassProp> for Strip {
+    fn fold(&mut self, prop: ClassProp) -> ClassProp {
+        ClassProp {
+            type_ann: None,
+            ..prop.fold_children(self)
+        }
+    }
+}
+
+impl Fold<PrivateProp> for Strip {
+    fn fold(&mut self, prop: PrivateProp) -> PrivateProp {
+        PrivateProp {
+            type_ann: None,
+            ..prop.fold_children(self)
+        }
+    }
+}
+
+/// Collects identifiers, which is used to find imports used as values.
+struct UsedFinder {
+    used: HashSet<JsWord>,
+}
+
+impl Visit<Ident> for UsedFinder {
+    fn visit(&mut self, i: &Ident) {
+        self.used.insert(i.sym.clone());
+    }
+}
+
+/// The jsx transform creates references to `React`.
+impl Visit<JSXElement> for UsedFinder {
+    fn visit(&mut self, e: &JSXElement) {
+        self.used.insert("React".into());
+        e.visit_children(self)
+    }
+}
+
+impl Visit<JSXFragment> for UsedFinder {
+    fn visit(&mut self, e: &JSXFragment) {
+        self.used.insert("React".into());
+        e.visit_children(self)
+    }
+}
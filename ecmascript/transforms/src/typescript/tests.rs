@@ -0,0 +1,107 @@
+use super::strip;
+use crate::tests::Tester;
+use swc_ecma_parser::Syntax;
+
+fn test(input: &str, expected: &str) {
+    Tester::run(Syntax::Typescript(Default::default()), |tester| {
+        tester.test_transform(crate::fixer::fixer, strip(), input, expected, false)
+    });
+}
+
+#[test]
+fn types() {
+    test(
+        "interface Foo { a: string }
+type Bar = Foo | number;
+declare const c: number;
+declare function d(): void;
+function foo<T>(a: T, b?: string): Bar {
+    return a as any as Bar;
+}
+function bar(a: string): void;
+function bar(a) {}
+class Baz<T> extends Qux<T> implements Foo {
+    a: string = '';
+    constructor(a: string);
+    constructor(a) {
+        super();
+    }
+}
+var e = (a: number): number => a;",
+        "function foo(a, b) {
+    return a;
+}
+function bar(a) {}
+class Baz extends Qux {
+    a = '';
+    constructor(a) {
+        super();
+    }
+}
+var e = (a) => a;",
+    );
+}
+
+#[test]
+fn enums() {
+    test(
+        "enum E { A, B = 5, C, D = 'd', F = a.length, G }",
+        r#"var E;
+(function (E) {
+    E[E["A"] = 0] = "A";
+    E[E["B"] = 5] = "B";
+    E[E["C"] = 6] = "C";
+    E["D"] = 'd';
+    E[E["F"] = a.length] = "F";
+    E[E["G"] = E["F"] + 1] = "G";
+})(E || (E = {}));"#,
+    );
+}
+
+#[test]
+fn namespaces() {
+    test(
+        "export namespace N {
+    export interface I {}
+    export const a = 1;
+    export function f() {}
+    export namespace M {
+        export const b = 2;
+    }
+    const c = 3;
+}
+namespace Types {
+    export type T = string;
+}",
+        "export var N;
+(function (N) {
+    const a = 1;
+    function f() {}
+    var M;
+    (function (M) {
+        const b = 2;
+        M.b = b;
+    })(M || (M = {}));
+    const c = 3;
+    N.a = a;
+    N.f = f;
+    N.M = M;
+})(N || (N = {}));",
+    );
+}
+
+#[test]
+fn imports() {
+    test(
+        "import Foo, { Bar, baz } from './foo';
+import * as types from './types';
+import './side-effect';
+interface I {}
+export { I, baz };
+let a: Bar = baz as types.T;",
+        "import { baz } from './foo';
+import './side-effect';
+export { baz };
+let a = baz;",
+    );
+}
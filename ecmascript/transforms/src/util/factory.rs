@@ -37,6 +37,12 @@ pub trait ExprFactory: Into<Expr> {
         let span = expr.span();
         Expr::Paren(ParenExpr { expr, span })
     }
+
+    fn into_stmt(self) -> Stmt {
+        let expr = box self.into();
+        let span = expr.span();
+        Stmt::Expr(ExprStmt { span, expr })
+    }
 }
 
 impl<T: Into<Expr>> ExprFactory for T {}
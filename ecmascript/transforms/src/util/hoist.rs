@@ -0,0 +1,118 @@
+use ast::*;
+use swc_common::{Spanned, Visit, VisitWith};
+
+/// Returns declarations hoisted out of `stmt`, which should be kept even if
+/// `stmt` is unreachable.
+///
+/// Function declarations are kept as is, and `var` declarations lose their
+/// initializers. Functions declared in blocks are not hoisted in strict mode.
+pub fn hoisted_decls(stmt: Stmt) -> Vec<Stmt> {
+    if let Stmt::Decl(Decl::Fn(..)) = stmt {
+        return vec![stmt];
+    }
+
+    let ids = var_ids(&stmt);
+    if ids.is_empty() {
+        return vec![];
+    }
+
+    vec![Stmt::Decl(Decl::Var(VarDecl {
+        span: stmt.span(),
+        kind: VarDeclKind::Var,
+        declare: false,
+        decls: ids
+            .into_iter()
+            .map(|id| VarDeclarator {
+                span: id.span,
+                name: Pat::Ident(id),
+                init: None,
+            })
+            .collect(),
+    }))]
+}
+
+/// Returns true if `stmt` declares a binding scoped to the enclosing block.
+///
+/// Function declarations are block scoped in strict mode.
+pub fn is_block_scoped(stmt: &Stmt) -> bool {
+    match *stmt {
+        Stmt::Decl(Decl::Var(VarDecl {
+            kind: VarDeclKind::Var,
+            ..
+        })) => false,
+        Stmt::Decl(..) => true,
+        _ => false,
+    }
+}
+
+/// Adds identifiers bound by `pat` to `ids`.
+pub fn pat_ids(pat: &Pat, ids: &mut Vec<Ident>) {
+    match *pat {
+        Pat::Ident(ref i) => ids.push(i.clone()),
+        Pat::Array(ArrayPat { ref elems, .. }) => elems
+            .iter()
+            .filter_map(|e| e.as_ref())
+            .for_each(|e| pat_ids(e, ids)),
+        Pat::Rest(RestPat { ref arg, .. }) => pat_ids(arg, ids),
+        Pat::Object(ObjectPat { ref props, .. }) => {
+            for prop in props {
+                match *prop {
+                    ObjectPatProp::KeyValue(KeyValuePatProp { ref value, .. }) => {
+                        pat_ids(value, ids)
+                    }
+                    ObjectPatProp::Assign(AssignPatProp { ref key, .. }) => ids.push(key.clone()),
+                    ObjectPatProp::Rest(RestPat { ref arg, .. }) => pat_ids(arg, ids),
+                }
+            }
+        }
+        Pat::Assign(AssignPat { ref left, .. }) => pat_ids(left, ids),
+        Pat::TsTyped(TsTypedPat { ref pat, .. }) => pat_ids(pat, ids),
+        Pat::Expr(..) => {}
+    }
+}
+
+/// Returns identifiers declared by `var`s in `node`, excluding nested
+/// functions.
+pub(crate) fn var_ids<T>(node: &T) -> Vec<Ident>
+where
+    T: VisitWith<VarCollector>,
+{
+    let mut v = VarCollector { ids: vec![] };
+    node.visit_with(&mut v);
+    v.ids
+}
+
+pub(crate) struct VarCollector {
+    ids: Vec<Ident>,
+}
+
+impl Visit<VarDecl> for VarCollector {
+    fn visit(&mut self, v: &VarDecl) {
+        if v.kind == VarDeclKind::Var {
+            for d in &v.decls {
+                pat_ids(&d.name, &mut self.ids);
+            }
+        }
+    }
+}
+
+/// `var`s in functions are not hoisted to the outer scope.
+impl Visit<Function> for VarCollector {
+    fn visit(&mut self, _: &Function) {}
+}
+
+impl Visit<ArrowExpr> for VarCollector {
+    fn visit(&mut self, _: &ArrowExpr) {}
+}
+
+impl Visit<GetterProp> for VarCollector {
+    fn visit(&mut self, _: &GetterProp) {}
+}
+
+impl Visit<SetterProp> for VarCollector {
+    fn visit(&mut self, _: &SetterProp) {}
+}
+
+impl Visit<StaticBlock> for VarCollector {
+    fn visit(&mut self, _: &StaticBlock) {}
+}
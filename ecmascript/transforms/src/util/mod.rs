@@ -1,5 +1,6 @@
 pub use self::{
     factory::ExprFactory,
+    hoist::{hoisted_decls, is_block_scoped, pat_ids},
     value::{
         Type::{
             self, Bool as BoolType, Null as NullType, Num as NumberType, Obj as ObjectType,
@@ -9,6 +10,7 @@ pub use self::{
     },
     Purity::{MayBeImpure, Pure},
 };
+pub(crate) use self::hoist::{var_ids, VarCollector};
 use ast::*;
 use std::{
     borrow::Cow,
@@ -19,6 +21,7 @@ use std::{
 use swc_atoms::JsWord;
 
 mod factory;
+mod hoist;
 mod value;
 
 pub type BoolValue = Value<bool>;
@@ -68,6 +71,33 @@ impl<T> IsEmpty for Vec<T> {
     }
 }
 
+/// A node which can contain a statement, like [Stmt] and [ModuleItem].
+pub trait StmtLike: Sized {
+    fn try_into_stmt(self) -> Result<Stmt, Self>;
+    fn from_stmt(stmt: Stmt) -> Self;
+}
+
+impl StmtLike for Stmt {
+    fn try_into_stmt(self) -> Result<Stmt, Self> {
+        Ok(self)
+    }
+    fn from_stmt(stmt: Stmt) -> Self {
+        stmt
+    }
+}
+
+impl StmtLike for ModuleItem {
+    fn try_into_stmt(self) -> Result<Stmt, Self> {
+        match self {
+            ModuleItem::Stmt(stmt) => Ok(stmt),
+            _ => Err(self),
+        }
+    }
+    fn from_stmt(stmt: Stmt) -> Self {
+        ModuleItem::Stmt(stmt)
+    }
+}
+
 /// Extension methods for [Expr].
 pub trait ExprExt {
     fn as_expr_kind(&self) -> &Expr;
@@ -107,8 +137,17 @@ pub trait ExprExt {
         let expr = self.as_expr_kind();
         let val = match *expr {
             Expr::Paren(ref e) => return e.expr.as_bool(),
-            Expr::Seq(SeqExpr { ref exprs, .. }) => return exprs.last().unwrap().as_bool(),
-            Expr::Assign(AssignExpr { ref right, .. }) => return right.as_bool(),
+            Expr::Seq(SeqExpr { ref exprs, .. }) => {
+                let (p, v) = exprs.last().unwrap().as_bool();
+                if exprs[..exprs.len() - 1]
+                    .iter()
+                    .any(|e| e.may_have_side_effects())
+                {
+                    return (MayBeImpure, v);
+                }
+                return (p, v);
+            }
+            Expr::Assign(AssignExpr { ref right, .. }) => return (MayBeImpure, right.as_bool().1),
 
             Expr::Unary(UnaryExpr {
                 op: op!("!"),
@@ -164,6 +203,7 @@ pub trait ExprExt {
                             FpCategory::Nan | FpCategory::Zero => false,
                             _ => true,
                         },
+                        Lit::BigInt(BigInt { ref value, .. }) => !big_int_is_zero(value),
                         Lit::Bool(b) => b.value,
                         Lit::Str(Str { ref value, .. }) => !value.is_empty(),
                         Lit::Null(..) => false,
@@ -334,6 +374,12 @@ pub trait ExprExt {
                 ref right,
                 ..
             })
+            | Expr::Bin(BinExpr {
+                ref left,
+                op: op!("??"),
+                ref right,
+                ..
+            })
             | Expr::Cond(CondExpr {
                 cons: ref left,
                 alt: ref right,
@@ -501,6 +547,7 @@ pub trait ExprExt {
             Expr::Await(_)
             | Expr::Yield(_)
             | Expr::Member(_)
+            | Expr::OptChain(_)
             | Expr::Update(_)
             | Expr::Assign(_) => true,
 
@@ -527,7 +574,7 @@ pub trait ExprExt {
                     Prop::Shorthand(..) => false,
                     Prop::KeyValue(KeyValueProp { ref key, ref value }) => {
                         let k = match *key {
-                            PropName::Computed(ref e) => e.may_have_side_effects(),
+                            PropName::Computed(ref e) => e.expr.may_have_side_effects(),
                             _ => false,
                         };
 
@@ -537,6 +584,12 @@ pub trait ExprExt {
                 },
                 PropOrSpread::Spread(SpreadElement { expr, .. }) => expr.may_have_side_effects(),
             }),
+
+            Expr::JSXElement(..) | Expr::JSXFragment(..) => true,
+
+            Expr::TsAs(TsAsExpr { ref expr, .. }) => expr.may_have_side_effects(),
+
+            Expr::PrivateName(..) => false,
         }
     }
 }
@@ -557,6 +610,15 @@ fn may_be_str(ty: Value<Type>) -> bool {
     }
 }
 
+/// `digits` is the value of a `BigInt`, which may start with a radix prefix.
+fn big_int_is_zero(digits: &str) -> bool {
+    let digits = match digits.get(..2) {
+        Some("0x") | Some("0X") | Some("0o") | Some("0O") | Some("0b") | Some("0B") => &digits[2..],
+        _ => digits,
+    };
+    digits.chars().all(|c| c == '0')
+}
+
 fn num_from_str(s: &str) -> Value<f64> {
     if s.contains('\u{000b}') {
         return Unknown;
@@ -605,6 +667,111 @@ impl ExprExt for Expr {
     }
 }
 
+/// Adds side effects of `expr` to `to`, preserving order and conditions.
+/// (think `a() ? yield b() : c()`)
+pub fn extract_side_effects_to(to: &mut Vec<Box<Expr>>, box expr: Box<Expr>) {
+    match expr {
+        Expr::Lit(..) | Expr::This(..) | Expr::Fn(..) | Expr::Arrow(..) | Expr::Ident(..) => {
+            return
+        }
+
+        // In most case, we can do nothing for this.
+        Expr::Update(_) | Expr::Assign(_) | Expr::Yield(_) | Expr::Await(_) => to.push(box expr),
+
+        // TODO
+        Expr::MetaProp(_) => to.push(box expr),
+
+        Expr::Call(_) => to.push(box expr),
+        Expr::New(NewExpr {
+            callee: box Expr::Ident(Ident { ref sym, .. }),
+            ref args,
+            ..
+        }) if &*sym == "Date" && args.is_empty() => {}
+        Expr::New(_) => to.push(box expr),
+        Expr::Member(_) | Expr::OptChain(_) => to.push(box expr),
+
+        // We are at here because we could not determine value of test.
+        //TODO: Drop values if it does not have side effects.
+        Expr::Cond(_) => to.push(box expr),
+
+        Expr::Unary(UnaryExpr {
+            op: op!("delete"), ..
+        }) => to.push(box expr),
+        Expr::Unary(UnaryExpr { arg, .. }) => extract_side_effects_to(to, arg),
+
+        // The right operand is evaluated conditionally.
+        Expr::Bin(BinExpr {
+            op: op!("&&"),
+            ref right,
+            ..
+        })
+        | Expr::Bin(BinExpr {
+            op: op!("||"),
+            ref right,
+            ..
+        })
+        | Expr::Bin(BinExpr {
+            op: op!("??"),
+            ref right,
+            ..
+        }) if right.may_have_side_effects() => to.push(box expr),
+
+        Expr::Bin(BinExpr { left, right, .. }) => {
+            extract_side_effects_to(to, left);
+            extract_side_effects_to(to, right);
+        }
+        Expr::Seq(SeqExpr { exprs, .. }) => exprs
+            .into_iter()
+            .for_each(|e| extract_side_effects_to(to, e)),
+
+        Expr::Paren(e) => extract_side_effects_to(to, e.expr),
+
+        Expr::Object(ObjectLit { props, .. }) => props.into_iter().for_each(|node| match node {
+            PropOrSpread::Prop(box node) => match node {
+                Prop::Shorthand(..) => return,
+                Prop::KeyValue(KeyValueProp { key, value }) => {
+                    match key {
+                        PropName::Computed(e) => extract_side_effects_to(to, e.expr),
+                        _ => {}
+                    }
+
+                    extract_side_effects_to(to, value)
+                }
+                Prop::Getter(GetterProp { key, .. })
+                | Prop::Setter(SetterProp { key, .. })
+                | Prop::Method(MethodProp { key, .. }) => match key {
+                    PropName::Computed(e) => extract_side_effects_to(to, e.expr),
+                    _ => {}
+                },
+                Prop::Assign(..) => {
+                    unreachable!("assign property in object literal is not a valid syntax")
+                }
+            },
+            PropOrSpread::Spread(SpreadElement { expr, .. }) => extract_side_effects_to(to, expr),
+        }),
+
+        Expr::Array(ArrayLit { elems, .. }) => elems
+            .into_iter()
+            .filter_map(|e| e)
+            .for_each(|e| extract_side_effects_to(to, e.expr)),
+
+        Expr::Tpl(TplLit { tag: Some(..), .. }) => to.push(box expr),
+        Expr::Tpl(TplLit { exprs, .. }) => exprs
+            .into_iter()
+            .for_each(|e| extract_side_effects_to(to, e)),
+
+        // TODO
+        Expr::Class(..) => to.push(box expr),
+
+        // Elements are created by calling a component.
+        Expr::JSXElement(..) | Expr::JSXFragment(..) => to.push(box expr),
+
+        Expr::TsAs(TsAsExpr { expr, .. }) => extract_side_effects_to(to, expr),
+
+        Expr::PrivateName(..) => {}
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Purity {
     /// May have some side effects.
@@ -671,3 +838,19 @@ pub(crate) fn to_u32(_d: f64) -> u32 {
     // return (int) l;
     unimplemented!()
 }
+
+/// Inserts `stmt` at the start of a function body, after directives like
+/// `"use strict"`.
+pub(crate) fn prepend(stmts: &mut Vec<Stmt>, stmt: Stmt) {
+    let pos = stmts
+        .iter()
+        .position(|stmt| match *stmt {
+            Stmt::Expr(ExprStmt {
+                expr: box Expr::Lit(Lit::Str(..)),
+                ..
+            }) => false,
+            _ => true,
+        })
+        .unwrap_or(stmts.len());
+    stmts.insert(pos, stmt);
+}
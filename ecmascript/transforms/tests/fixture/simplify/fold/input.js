@@ -0,0 +1,2 @@
+use(8 + 8);
+use(x);
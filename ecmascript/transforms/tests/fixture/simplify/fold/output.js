@@ -0,0 +1,2 @@
+use(16);
+use(x);
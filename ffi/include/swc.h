@@ -0,0 +1,41 @@
+/*
+ * C binding for swc.
+ *
+ * All strings are nul-terminated and utf-8 encoded. Link against
+ * `libswc_ffi`.
+ */
+
+#ifndef SWC_H
+#define SWC_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct swc_output {
+    /* Generated code, or NULL on failure. */
+    char *code;
+    /* Source map, if requested by options. */
+    char *map;
+    /* Error message, or NULL on success. Panics are reported here. */
+    char *error;
+} swc_output;
+
+/*
+ * Compiles `src`.
+ *
+ * `filename` is used for diagnostics and source maps, and `options` is the
+ * json representation of `.swcrc`. Both may be NULL.
+ *
+ * The returned value must be released with `swc_output_free`.
+ */
+swc_output *swc_transform(const char *src, const char *filename,
+                          const char *options);
+
+void swc_output_free(swc_output *output);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* SWC_H */
@@ -0,0 +1,124 @@
+//! C binding for swc.
+//!
+//! See `include/swc.h` for the interface.
+
+extern crate libswc as swc;
+extern crate serde_json;
+#[macro_use]
+extern crate slog;
+
+use std::{
+    any::Any,
+    ffi::{CStr, CString},
+    os::raw::c_char,
+    panic, ptr,
+};
+use swc::{
+    common::{
+        errors::{ColorConfig, Handler},
+        sync::Lrc,
+        FileName, FilePathMapping, Globals, SourceMap, GLOBALS,
+    },
+    config::Config,
+    Compiler, TransformOutput,
+};
+
+#[allow(non_camel_case_types)]
+#[repr(C)]
+pub struct swc_output {
+    pub code: *mut c_char,
+    pub map: *mut c_char,
+    pub error: *mut c_char,
+}
+
+/// Compiles `src` with options serialized as json.
+fn transform(src: &str, filename: &str, options: &str) -> Result<TransformOutput, String> {
+    let config: Config = serde_json::from_str(options)
+        .map_err(|err| format!("failed to deserialize options: {}", err))?;
+
+    GLOBALS.set(&Globals::new(), || {
+        let cm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        let handler = Handler::with_tty_emitter(ColorConfig::Auto, true, false, Some(cm.clone()));
+        let logger = slog::Logger::root(slog::Discard, o!());
+        let compiler = Compiler::new(logger, cm.clone(), handler);
+
+        let fm = cm.new_source_file(FileName::Real(filename.into()), src.into());
+        compiler
+            .process_js_file(&fm, &config)
+            .map_err(|err| err.to_string())
+    })
+}
+
+/// Returns `default` for null pointers.
+unsafe fn to_str<'a>(s: *const c_char, default: &'a str) -> Result<&'a str, String> {
+    if s.is_null() {
+        return Ok(default);
+    }
+
+    CStr::from_ptr(s)
+        .to_str()
+        .map_err(|err| format!("invalid utf-8 in arguments: {}", err))
+}
+
+/// Panics must not unwind into C, so they are returned as errors.
+fn panic_message(payload: Box<Any + Send>) -> String {
+    let msg = match payload.downcast::<String>() {
+        Ok(msg) => *msg,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(msg) => msg.to_string(),
+            Err(_) => "unknown error".into(),
+        },
+    };
+    format!("swc panicked: {}", msg)
+}
+
+fn into_raw(s: String) -> *mut c_char {
+    // Interior nul bytes are replaced as C strings cannot contain them.
+    CString::new(s.replace('\0', "\u{FFFD}"))
+        .expect("nul bytes are removed")
+        .into_raw()
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn swc_transform(
+    src: *const c_char,
+    filename: *const c_char,
+    options: *const c_char,
+) -> *mut swc_output {
+    let result = panic::catch_unwind(|| {
+        let src = to_str(src, "")?;
+        let filename = to_str(filename, "input.js")?;
+        let options = to_str(options, "{}")?;
+        transform(src, filename, options)
+    })
+    .unwrap_or_else(|payload| Err(panic_message(payload)));
+
+    let output = match result {
+        Ok(output) => swc_output {
+            code: into_raw(output.code),
+            map: output.map.map(into_raw).unwrap_or_else(ptr::null_mut),
+            error: ptr::null_mut(),
+        },
+        Err(err) => swc_output {
+            code: ptr::null_mut(),
+            map: ptr::null_mut(),
+            error: into_raw(err),
+        },
+    };
+
+    Box::into_raw(Box::new(output))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn swc_output_free(output: *mut swc_output) {
+    if output.is_null() {
+        return;
+    }
+
+    let output = Box::from_raw(output);
+    for s in &[output.code, output.map, output.error] {
+        if !s.is_null() {
+            drop(CString::from_raw(*s));
+        }
+    }
+}
@@ -0,0 +1,48 @@
+"use strict";
+
+const crypto = require("crypto");
+const swc = require("node-swc");
+const { version } = require("node-swc/package.json");
+
+/**
+ * Creates a jest transformer.
+ *
+ * `options` mirrors `.swcrc`. Jest requires commonjs, so `module` defaults to
+ * it, and source maps are always inlined so that stack traces point to the
+ * original source.
+ */
+function createTransformer(options) {
+  const swcOptions = Object.assign({ module: { type: "commonjs" } }, options, {
+    sourceMaps: "inline"
+  });
+  const serialized = JSON.stringify(swcOptions);
+
+  return {
+    canInstrument: false,
+
+    process(src, filename) {
+      return swc.transformSync(
+        src,
+        Object.assign({}, swcOptions, { filename })
+      ).code;
+    },
+
+    getCacheKey(src, filename, configString) {
+      return crypto
+        .createHash("md5")
+        .update(version)
+        .update("\0")
+        .update(serialized)
+        .update("\0")
+        .update(src)
+        .update("\0")
+        .update(filename)
+        .update("\0")
+        .update(configString)
+        .digest("hex");
+    }
+  };
+}
+
+module.exports = createTransformer();
+module.exports.createTransformer = createTransformer;
@@ -0,0 +1,1270 @@
+//! Conversion between modules and the ast of babel, to run plugins of babel
+//! on modules parsed by swc.
+//!
+//! Modules are converted from and to their json representation, so
+//! [to_babel] returns a babel `File` which can be passed to babel as is, and
+//! [from_babel] accepts a `File` returned by babel.
+//!
+//! Positions of babel are offsets from the start of the source file and
+//! lines with columns, computed using the [SourceMap]. Nodes created by babel
+//! plugins don't have positions, and they get dummy spans. Comments and
+//! typescript-specific nodes are not converted, except for their positions.
+
+use crate::{
+    common::{BytePos, SourceFile, SourceMap},
+    ecmascript::ast::Module,
+};
+use serde_json::{json, Value};
+
+type Map = serde_json::Map<String, Value>;
+
+/// Converts `module`, which is parsed from `fm`, to a babel `File`.
+///
+/// Spans which are not in `fm` are treated as dummy spans.
+pub fn to_babel(cm: &SourceMap, fm: &SourceFile, module: &Module) -> Value {
+    let module = serde_json::to_value(module).expect("failed to serialize module");
+    ToBabel { cm, fm }.file(module)
+}
+
+/// Converts a babel `File` of `fm` to a module.
+pub fn from_babel(fm: &SourceFile, file: Value) -> Result<Module, serde_json::Error> {
+    let module = FromBabel { fm }.file(file)?;
+    serde_json::from_value(module)
+}
+
+struct ToBabel<'a> {
+    cm: &'a SourceMap,
+    fm: &'a SourceFile,
+}
+
+impl<'a> ToBabel<'a> {
+    fn file(&self, module: Value) -> Value {
+        let mut module = match self.value(module) {
+            Value::Object(module) => module,
+            _ => unreachable!("module should be serialized as an object"),
+        };
+
+        let mut program = node("Program");
+        copy_start(&mut program, &module);
+        copy_end(&mut program, &module);
+        program.insert("sourceType".into(), "module".into());
+        let mut body = take_vec(&mut module, "body");
+        program.insert("directives".into(), take_directives(&mut body).into());
+        program.insert("body".into(), body.into());
+        program.insert(
+            "interpreter".into(),
+            match take(&mut module, "shebang") {
+                Value::Null => Value::Null,
+                value => json!({ "type": "InterpreterDirective", "value": value }),
+            },
+        );
+
+        let mut file = node("File");
+        copy_start(&mut file, &module);
+        copy_end(&mut file, &module);
+        file.insert("program".into(), program.into());
+        file.insert("comments".into(), json!([]));
+        file.into()
+    }
+
+    fn value(&self, v: Value) -> Value {
+        match v {
+            Value::Array(v) => v.into_iter().map(|v| self.value(v)).collect(),
+            Value::Object(obj) => self.object(obj),
+            v => v,
+        }
+    }
+
+    fn object(&self, obj: Map) -> Value {
+        let mut obj: Map = obj
+            .into_iter()
+            .map(|(key, v)| {
+                let v = if key == "span" { v } else { self.value(v) };
+                (key, v)
+            })
+            .collect();
+        let ty = match obj.get("type").and_then(Value::as_str) {
+            Some(ty) => ty.to_string(),
+            None => return obj.into(),
+        };
+
+        if let Some(span) = obj.remove("span") {
+            self.set_span(&mut obj, &span);
+        }
+        self.fix(&ty, obj)
+    }
+
+    /// Converts a node of type `ty`, whose children are already converted.
+    fn fix(&self, ty: &str, mut obj: Map) -> Value {
+        match ty {
+            "BlockStatement" => {
+                obj.insert("directives".into(), json!([]));
+            }
+
+            "StringLiteral" | "NumericLiteral" => {
+                obj.remove("hasEscape");
+                let raw = take(&mut obj, "raw");
+                if !raw.is_null() {
+                    let value = get(&obj, "value").clone();
+                    obj.insert("extra".into(), json!({ "rawValue": value, "raw": raw }));
+                }
+            }
+            "RegExpLiteral" => {
+                let pattern = take(&mut obj, "pattern")["value"].take();
+                obj.insert("pattern".into(), pattern);
+                let flags = match take(&mut obj, "flags") {
+                    Value::Null => "".into(),
+                    mut flags => flags["value"].take(),
+                };
+                obj.insert("flags".into(), flags);
+            }
+            "TemplateLiteral" => {
+                let tag = take(&mut obj, "tag");
+                if !tag.is_null() {
+                    let mut tagged = node("TaggedTemplateExpression");
+                    copy_start(&mut tagged, &obj);
+                    copy_end(&mut tagged, &obj);
+                    tagged.insert("tag".into(), tag);
+                    tagged.insert("quasi".into(), obj.into());
+                    return tagged.into();
+                }
+            }
+            "TemplateElement" => {
+                let raw = take(&mut obj, "raw");
+                let cooked = take(&mut obj, "cooked");
+                obj.insert("value".into(), json!({ "raw": raw, "cooked": cooked }));
+            }
+
+            "ArrayExpression" => map_vec(&mut obj, "elements", |v| self.spread(v)),
+            "CallExpression" => {
+                map_vec(&mut obj, "arguments", |v| self.spread(v));
+                if is_optional(get(&obj, "callee")) {
+                    obj.insert("type".into(), "OptionalCallExpression".into());
+                    obj.insert("optional".into(), false.into());
+                }
+            }
+            "NewExpression" => {
+                if get(&obj, "arguments").is_null() {
+                    obj.insert("arguments".into(), json!([]));
+                }
+                map_vec(&mut obj, "arguments", |v| self.spread(v));
+            }
+            "MemberExpression" => {
+                if is_optional(get(&obj, "object")) {
+                    obj.insert("type".into(), "OptionalMemberExpression".into());
+                    obj.insert("optional".into(), false.into());
+                }
+            }
+            "OptionalChainingExpression" => {
+                let mut expr = match take(&mut obj, "expression") {
+                    Value::Object(expr) => expr,
+                    expr => return expr,
+                };
+                let ty = match expr.get("type").and_then(Value::as_str) {
+                    Some("MemberExpression") | Some("OptionalMemberExpression") => {
+                        "OptionalMemberExpression"
+                    }
+                    _ => "OptionalCallExpression",
+                };
+                expr.insert("type".into(), ty.into());
+                expr.insert("optional".into(), true.into());
+                return expr.into();
+            }
+
+            "UnaryExpression" => {
+                obj.insert("prefix".into(), true.into());
+            }
+            "BinaryExpression" => {
+                let is_logical = match get(&obj, "operator").as_str() {
+                    Some("||") | Some("&&") | Some("??") => true,
+                    _ => false,
+                };
+                if is_logical {
+                    obj.insert("type".into(), "LogicalExpression".into());
+                }
+            }
+            "MetaProperty" => {
+                let (meta, prop) = (get(&obj, "meta").clone(), get(&obj, "property").clone());
+                copy_start(&mut obj, &meta);
+                copy_end(&mut obj, &prop);
+            }
+
+            "ObjectExpression" => map_vec(&mut obj, "properties", |prop| {
+                if is_type(&prop, "Identifier") {
+                    let mut shorthand = node("ObjectProperty");
+                    copy_start(&mut shorthand, &prop);
+                    copy_end(&mut shorthand, &prop);
+                    shorthand.insert("key".into(), prop.clone());
+                    shorthand.insert("value".into(), prop);
+                    shorthand.insert("computed".into(), false.into());
+                    shorthand.insert("shorthand".into(), true.into());
+                    shorthand.into()
+                } else {
+                    prop
+                }
+            }),
+            "SpreadElement" | "RestElement" => {
+                let dot3 = take(&mut obj, "dot3Token");
+                if let Some(lo) = dot3["start"].as_u64() {
+                    self.set_pos(&mut obj, "start", lo as u32);
+                }
+                let arg = get(&obj, "argument").clone();
+                copy_end(&mut obj, &arg);
+            }
+            "KeyValueProperty" | "KeyValuePatternProperty" => {
+                let (key, value) = (take(&mut obj, "key"), get(&obj, "value").clone());
+                copy_start(&mut obj, &key);
+                copy_end(&mut obj, &value);
+                obj.insert("type".into(), "ObjectProperty".into());
+                set_key(&mut obj, key);
+                obj.insert("shorthand".into(), false.into());
+            }
+            "AssignmentProperty" | "AssignmentPatternProperty" => {
+                let (key, value) = (get(&obj, "key").clone(), take(&mut obj, "value"));
+                if !obj.contains_key("start") {
+                    copy_start(&mut obj, &key);
+                    copy_end(&mut obj, &value);
+                }
+                obj.insert("type".into(), "ObjectProperty".into());
+                let value = if value.is_null() {
+                    key
+                } else {
+                    let mut pat = node("AssignmentPattern");
+                    copy_start(&mut pat, &obj);
+                    copy_end(&mut pat, &obj);
+                    pat.insert("left".into(), key);
+                    pat.insert("right".into(), value);
+                    pat.into()
+                };
+                obj.insert("value".into(), value);
+                obj.insert("computed".into(), false.into());
+                obj.insert("shorthand".into(), true.into());
+            }
+            "GetterProperty" | "SetterProperty" => {
+                let (kind, params) = if ty == "GetterProperty" {
+                    ("get", json!([]))
+                } else {
+                    ("set", json!([take(&mut obj, "param")]))
+                };
+                obj.insert("type".into(), "ObjectMethod".into());
+                obj.insert("kind".into(), kind.into());
+                let key = take(&mut obj, "key");
+                set_key(&mut obj, key);
+                obj.insert("params".into(), params);
+                obj.insert("generator".into(), false.into());
+                obj.insert("async".into(), false.into());
+            }
+            "MethodProperty" => {
+                let key = take(&mut obj, "key");
+                copy_start(&mut obj, &key);
+                obj.insert("type".into(), "ObjectMethod".into());
+                obj.insert("kind".into(), "method".into());
+                set_key(&mut obj, key);
+                let function = take(&mut obj, "function");
+                function_to_babel(&mut obj, function);
+            }
+
+            "FunctionExpression" | "FunctionDeclaration" => {
+                rename(&mut obj, "identifier", "id");
+                remove_false(&mut obj, "declare");
+                let function = take(&mut obj, "function");
+                copy_start(&mut obj, &function);
+                function_to_babel(&mut obj, function);
+            }
+            "ArrowFunctionExpression" => {
+                let is_async = !take(&mut obj, "asyncToken").is_null();
+                obj.remove("generatorToken");
+                obj.insert("async".into(), is_async.into());
+                obj.insert("generator".into(), false.into());
+                let is_block = is_type(get(&obj, "body"), "BlockStatement");
+                if let Some(body) = obj.get_mut("body") {
+                    move_directives(body);
+                }
+                obj.insert("expression".into(), (!is_block).into());
+                remove_null(&mut obj, "typeParameters");
+                remove_null(&mut obj, "returnType");
+            }
+
+            "ClassExpression" | "ClassDeclaration" => {
+                rename(&mut obj, "identifier", "id");
+                remove_false(&mut obj, "declare");
+                let mut class = match take(&mut obj, "class") {
+                    Value::Object(class) => class,
+                    _ => return obj.into(),
+                };
+                copy_start(&mut obj, &class);
+                copy_end(&mut obj, &class);
+
+                let mut body = node("ClassBody");
+                copy_start(&mut body, &class);
+                copy_end(&mut body, &class);
+                body.insert("body".into(), take(&mut class, "body"));
+                obj.insert("body".into(), body.into());
+                obj.insert("superClass".into(), take(&mut class, "superClass"));
+                for key in &["decorators", "implements"] {
+                    match take(&mut class, key) {
+                        Value::Array(ref v) if v.is_empty() => {}
+                        v => {
+                            obj.insert(key.to_string(), v);
+                        }
+                    }
+                }
+                for key in &["typeParameters", "superTypeParameters"] {
+                    match take(&mut class, key) {
+                        Value::Null => {}
+                        v => {
+                            obj.insert(key.to_string(), v);
+                        }
+                    }
+                }
+            }
+            "ClassMethod" | "ClassPrivateMethod" => {
+                let key = take(&mut obj, "key");
+                set_key(&mut obj, key);
+                let is_static = !take(&mut obj, "staticToken").is_null();
+                obj.insert("static".into(), is_static.into());
+                remove_empty(&mut obj, "decorators");
+                let function = take(&mut obj, "function");
+                function_to_babel(&mut obj, function);
+            }
+            "ClassProperty" | "ClassPrivateProperty" => {
+                let key = take(&mut obj, "key");
+                set_key(&mut obj, key);
+                let is_static = !take(&mut obj, "staticToken").is_null();
+                obj.insert("static".into(), is_static.into());
+                remove_empty(&mut obj, "decorators");
+                remove_null(&mut obj, "typeAnnotation");
+            }
+            "StaticBlock" => {
+                let stmts = take(&mut obj, "body")["body"].take();
+                obj.insert("body".into(), stmts);
+            }
+
+            "VariableDeclaration" => remove_false(&mut obj, "declare"),
+            "ForOfStatement" => {
+                let is_await = !take(&mut obj, "awaitToken").is_null();
+                obj.insert("await".into(), is_await.into());
+            }
+
+            "ImportSpecifier" | "ExportSpecifier" => {
+                let key = if ty == "ImportSpecifier" {
+                    "imported"
+                } else {
+                    "exported"
+                };
+                if get(&obj, key).is_null() {
+                    let local = get(&obj, "local").clone();
+                    obj.insert(key.into(), local);
+                }
+            }
+            "ExportDeclaration" => {
+                let decl = take(&mut obj, "declaration");
+                obj.insert("type".into(), "ExportNamedDeclaration".into());
+                obj.insert("declaration".into(), decl);
+                obj.insert("specifiers".into(), json!([]));
+                obj.insert("source".into(), Value::Null);
+            }
+            "ExportNamedDeclaration" => {
+                obj.insert("declaration".into(), Value::Null);
+            }
+            "ExportDefaultDeclaration" | "ExportDefaultExpression" => {
+                let mut decl = if ty == "ExportDefaultDeclaration" {
+                    take(&mut obj, "declaration")
+                } else {
+                    take(&mut obj, "expression")
+                };
+                if ty == "ExportDefaultDeclaration" {
+                    let decl_ty = match decl["type"].as_str() {
+                        Some("FunctionExpression") => Some("FunctionDeclaration"),
+                        Some("ClassExpression") => Some("ClassDeclaration"),
+                        _ => None,
+                    };
+                    if let Some(decl_ty) = decl_ty {
+                        decl["type"] = decl_ty.into();
+                    }
+                }
+                obj.insert("type".into(), "ExportDefaultDeclaration".into());
+                obj.insert("declaration".into(), decl);
+            }
+
+            "JSXOpeningElement" => {
+                jsx_ident(&mut obj, "name");
+                map_vec(&mut obj, "attributes", |mut attr| {
+                    if is_type(&attr, "SpreadElement") {
+                        attr["type"] = "JSXSpreadAttribute".into();
+                    }
+                    attr
+                });
+            }
+            "JSXClosingElement" | "JSXAttribute" => jsx_ident(&mut obj, "name"),
+            "JSXMemberExpression" | "JSXNamespacedName" => {
+                let (first, last) = if ty == "JSXMemberExpression" {
+                    ("object", "property")
+                } else {
+                    ("namespace", "name")
+                };
+                jsx_ident(&mut obj, first);
+                jsx_ident(&mut obj, last);
+                let (first, last) = (get(&obj, first).clone(), get(&obj, last).clone());
+                copy_start(&mut obj, &first);
+                copy_end(&mut obj, &last);
+            }
+            "JSXText" => {
+                let raw = take(&mut obj, "raw");
+                let value = get(&obj, "value").clone();
+                obj.insert("extra".into(), json!({ "rawValue": value, "raw": raw }));
+            }
+
+            _ => {}
+        }
+
+        obj.into()
+    }
+
+    /// Converts `ExprOrSpread`.
+    fn spread(&self, v: Value) -> Value {
+        let mut v = match v {
+            Value::Object(v) => v,
+            v => return v,
+        };
+        if v.contains_key("type") {
+            return v.into();
+        }
+
+        let spread = take(&mut v, "spread");
+        let expr = take(&mut v, "expression");
+        let lo = match spread["start"].as_u64() {
+            Some(lo) => lo as u32,
+            None => return expr,
+        };
+        let mut obj = node("SpreadElement");
+        self.set_pos(&mut obj, "start", lo);
+        copy_end(&mut obj, &expr);
+        obj.insert("argument".into(), expr);
+        obj.into()
+    }
+
+    /// Sets `start` and `end` from a serialized span.
+    fn set_span(&self, obj: &mut Map, span: &Value) {
+        let (lo, hi) = match (span["start"].as_u64(), span["end"].as_u64()) {
+            (Some(lo), Some(hi)) => (lo as u32, hi as u32),
+            _ => return,
+        };
+        if lo == 0 && hi == 0 {
+            return;
+        }
+        self.set_pos(obj, "start", lo);
+        self.set_pos(obj, "end", hi);
+    }
+
+    /// Sets `start` or `end` and the corresponding field of `loc`.
+    fn set_pos(&self, obj: &mut Map, key: &str, pos: u32) {
+        if pos < self.fm.start_pos.0 || self.fm.end_pos.0 < pos {
+            return;
+        }
+        let loc = self.cm.lookup_char_pos(BytePos(pos));
+        let loc = json!({ "line": loc.line, "column": loc.col.0 });
+
+        obj.insert(key.into(), (pos - self.fm.start_pos.0).into());
+        set_loc(obj, key, loc);
+    }
+}
+
+/// Moves fields of `function` to `obj`, which is a function node of babel.
+fn function_to_babel(obj: &mut Map, function: Value) {
+    copy_end(obj, &function);
+    let mut function = match function {
+        Value::Object(function) => function,
+        _ => return,
+    };
+
+    let params = take_vec(&mut function, "params")
+        .into_iter()
+        .map(|mut param| {
+            let mut pat = param["pat"].take();
+            match param["decorators"] {
+                Value::Array(ref v) if v.is_empty() => {}
+                ref decorators => pat["decorators"] = decorators.clone(),
+            }
+            pat
+        })
+        .collect::<Vec<_>>();
+    obj.insert("params".into(), params.into());
+
+    let mut body = take(&mut function, "body");
+    move_directives(&mut body);
+    obj.insert("body".into(), body);
+    let is_generator = !take(&mut function, "generatorToken").is_null();
+    obj.insert("generator".into(), is_generator.into());
+    let is_async = !take(&mut function, "asyncToken").is_null();
+    obj.insert("async".into(), is_async.into());
+    for key in &["typeParameters", "returnType"] {
+        match take(&mut function, key) {
+            Value::Null => {}
+            v => {
+                obj.insert(key.to_string(), v);
+            }
+        }
+    }
+}
+
+/// Moves leading string literals of a block to its `directives`.
+fn move_directives(block: &mut Value) {
+    if let Value::Object(ref mut block) = *block {
+        let mut body = take_vec(block, "body");
+        block.insert("directives".into(), take_directives(&mut body).into());
+        block.insert("body".into(), body.into());
+    }
+}
+
+/// Removes leading string literals from `stmts` and returns them as
+/// directives.
+fn take_directives(stmts: &mut Vec<Value>) -> Vec<Value> {
+    let len = stmts
+        .iter()
+        .take_while(|stmt| {
+            is_type(stmt, "ExpressionStatement") && is_type(&stmt["expression"], "StringLiteral")
+        })
+        .count();
+
+    stmts
+        .drain(..len)
+        .map(|mut stmt| {
+            let mut lit = stmt["expression"].take();
+            let raw = lit["extra"]["raw"].take();
+            let value = match raw.as_str() {
+                Some(raw) if raw.len() >= 2 => raw[1..raw.len() - 1].into(),
+                _ => lit["value"].take(),
+            };
+
+            let mut dl = node("DirectiveLiteral");
+            copy_start(&mut dl, &lit);
+            copy_end(&mut dl, &lit);
+            if !raw.is_null() {
+                dl.insert("extra".into(), json!({ "rawValue": value, "raw": raw }));
+            }
+            dl.insert("value".into(), value);
+
+            let mut directive = node("Directive");
+            copy_start(&mut directive, &stmt);
+            copy_end(&mut directive, &stmt);
+            directive.insert("value".into(), dl.into());
+            directive.into()
+        })
+        .collect()
+}
+
+/// Sets `key` and `computed` from a serialized `PropName`.
+fn set_key(obj: &mut Map, mut key: Value) {
+    let computed = is_type(&key, "Computed");
+    if computed {
+        key = key["expression"].take();
+    }
+    obj.insert("key".into(), key);
+    obj.insert("computed".into(), computed.into());
+}
+
+fn jsx_ident(obj: &mut Map, key: &str) {
+    if let Some(v) = obj.get_mut(key) {
+        if is_type(v, "Identifier") {
+            v["type"] = "JSXIdentifier".into();
+        }
+    }
+}
+
+fn is_optional(v: &Value) -> bool {
+    is_type(v, "OptionalMemberExpression") || is_type(v, "OptionalCallExpression")
+}
+
+struct FromBabel<'a> {
+    fm: &'a SourceFile,
+}
+
+impl<'a> FromBabel<'a> {
+    fn file(&self, file: Value) -> Result<Value, serde_json::Error> {
+        let mut file = match file {
+            Value::Object(file) => file,
+            _ => return Err(invalid("expected a babel `File`")),
+        };
+        let mut program = match take(&mut file, "program") {
+            Value::Object(program) => program,
+            _ => return Err(invalid("`program` of `File` should be an object")),
+        };
+
+        let mut module = node("Module");
+        module.insert("span".into(), self.span(&program));
+        let mut body = self.value(take(&mut program, "body"));
+        let directives = self.value(take(&mut program, "directives"));
+        merge_directives(&mut body, directives);
+        module.insert("body".into(), body);
+        module.insert(
+            "shebang".into(),
+            take(&mut program, "interpreter")["value"].take(),
+        );
+        Ok(module.into())
+    }
+
+    fn value(&self, v: Value) -> Value {
+        match v {
+            Value::Array(v) => v.into_iter().map(|v| self.value(v)).collect(),
+            Value::Object(obj) => self.object(obj),
+            v => v,
+        }
+    }
+
+    fn object(&self, obj: Map) -> Value {
+        let mut obj: Map = obj
+            .into_iter()
+            .filter(|&(ref key, _)| match &**key {
+                "loc" | "range" | "leadingComments" | "innerComments" | "trailingComments" => {
+                    false
+                }
+                _ => true,
+            })
+            .map(|(key, v)| (key, self.value(v)))
+            .collect();
+        let ty = match obj.get("type").and_then(Value::as_str) {
+            Some(ty) => ty.to_string(),
+            None => return obj.into(),
+        };
+
+        let span = self.span(&obj);
+        obj.insert("span".into(), span.clone());
+        let extra = take(&mut obj, "extra");
+        let node = self.fix(&ty, obj, &extra);
+
+        // Babel records parentheses instead of creating nodes by default.
+        if extra["parenthesized"] == true {
+            return json!({
+                "type": "ParenthesizedExpression",
+                "span": span,
+                "expression": node,
+            });
+        }
+        node
+    }
+
+    /// Converts a node of type `ty`, whose children are already converted.
+    fn fix(&self, ty: &str, mut obj: Map, extra: &Value) -> Value {
+        match ty {
+            "JSXIdentifier" => {
+                obj.insert("type".into(), "Identifier".into());
+            }
+            "BlockStatement" => {
+                let directives = take(&mut obj, "directives");
+                if let Some(body) = obj.get_mut("body") {
+                    merge_directives(body, directives);
+                }
+            }
+            "DirectiveLiteral" => {
+                obj.insert("raw".into(), extra["raw"].clone());
+            }
+
+            "StringLiteral" | "NumericLiteral" => {
+                let raw = extra["raw"].clone();
+                if ty == "StringLiteral" {
+                    obj.insert("hasEscape".into(), has_escape(&raw).into());
+                }
+                obj.insert("raw".into(), raw);
+            }
+            "RegExpLiteral" => {
+                let span = get(&obj, "span").clone();
+                let str_lit = |value: Value| {
+                    json!({
+                        "type": "StringLiteral",
+                        "span": span,
+                        "value": value,
+                        "hasEscape": false,
+                        "raw": null,
+                    })
+                };
+                let pattern = str_lit(take(&mut obj, "pattern"));
+                obj.insert("pattern".into(), pattern);
+                let flags = match take(&mut obj, "flags") {
+                    Value::Null => Value::Null,
+                    Value::String(ref flags) if flags.is_empty() => Value::Null,
+                    flags => str_lit(flags),
+                };
+                obj.insert("flags".into(), flags);
+            }
+            "TemplateLiteral" => {
+                obj.insert("tag".into(), Value::Null);
+            }
+            "TaggedTemplateExpression" => {
+                let mut quasi = take(&mut obj, "quasi");
+                quasi["span"] = get(&obj, "span").clone();
+                quasi["tag"] = take(&mut obj, "tag");
+                return quasi;
+            }
+            "TemplateElement" => {
+                let mut value = take(&mut obj, "value");
+                obj.insert("raw".into(), value["raw"].take());
+                obj.insert("cooked".into(), value["cooked"].take());
+            }
+
+            "ArrayExpression" => map_vec(&mut obj, "elements", spread_from_babel),
+            "CallExpression" | "NewExpression" => {
+                map_vec(&mut obj, "arguments", spread_from_babel);
+            }
+            "SpreadElement" | "RestElement" | "JSXSpreadAttribute" => {
+                if ty == "JSXSpreadAttribute" {
+                    obj.insert("type".into(), "SpreadElement".into());
+                }
+                let span = get(&obj, "span").clone();
+                let dot3 = match (span["start"].as_u64(), span["end"].as_u64()) {
+                    (Some(lo), Some(hi)) if hi != 0 => json!({ "start": lo, "end": lo + 3 }),
+                    _ => dummy_span(),
+                };
+                obj.insert("dot3Token".into(), dot3);
+            }
+            "LogicalExpression" => {
+                obj.insert("type".into(), "BinaryExpression".into());
+            }
+
+            "ObjectProperty" => {
+                let computed = take(&mut obj, "computed") == true;
+                let shorthand = take(&mut obj, "shorthand") == true;
+                let key = take(&mut obj, "key");
+                let mut value = take(&mut obj, "value");
+                if shorthand && is_type(&value, "Identifier") {
+                    return value;
+                }
+                if shorthand && is_type(&value, "AssignmentPattern") {
+                    obj.insert("type".into(), "AssignmentProperty".into());
+                    obj.insert("key".into(), value["left"].take());
+                    obj.insert("value".into(), value["right"].take());
+                } else {
+                    obj.insert("type".into(), "KeyValueProperty".into());
+                    obj.insert("key".into(), prop_name(key, computed));
+                    obj.insert("value".into(), value);
+                }
+            }
+            "ObjectPattern" => map_vec(&mut obj, "properties", |mut prop| {
+                if is_type(&prop, "Identifier") {
+                    return json!({
+                        "type": "AssignmentPatternProperty",
+                        "span": prop["span"],
+                        "key": prop,
+                        "value": null,
+                    });
+                }
+                let ty = if is_type(&prop, "AssignmentProperty") {
+                    "AssignmentPatternProperty"
+                } else if is_type(&prop, "KeyValueProperty") {
+                    "KeyValuePatternProperty"
+                } else {
+                    return prop;
+                };
+                prop["type"] = ty.into();
+                prop
+            }),
+            "ObjectMethod" => {
+                let computed = get(&obj, "computed") == true;
+                let key = prop_name(take(&mut obj, "key"), computed);
+                let kind = get(&obj, "kind").clone();
+                return match kind.as_str() {
+                    Some("get") => json!({
+                        "type": "GetterProperty",
+                        "span": obj.get("span"),
+                        "key": key,
+                        "body": obj.get("body"),
+                    }),
+                    Some("set") => json!({
+                        "type": "SetterProperty",
+                        "span": obj.get("span"),
+                        "key": key,
+                        "param": get(&obj, "params")[0],
+                        "body": obj.get("body"),
+                    }),
+                    _ => json!({
+                        "type": "MethodProperty",
+                        "key": key,
+                        "function": function_from_babel(&mut obj),
+                    }),
+                };
+            }
+
+            "OptionalMemberExpression" | "OptionalCallExpression" => {
+                map_vec(&mut obj, "arguments", spread_from_babel);
+                let optional = take(&mut obj, "optional") == true;
+                obj.insert("type".into(), ty.replace("Optional", "").into());
+                if optional {
+                    return json!({
+                        "type": "OptionalChainingExpression",
+                        "span": obj.get("span"),
+                        "expression": obj,
+                    });
+                }
+            }
+
+            "FunctionExpression" | "FunctionDeclaration" => {
+                let function = function_from_babel(&mut obj);
+                return json!({
+                    "type": ty,
+                    "identifier": obj.get("id"),
+                    "declare": get(&obj, "declare") == true,
+                    "function": function,
+                });
+            }
+            "ArrowFunctionExpression" => {
+                let async_token = token(take(&mut obj, "async"));
+                obj.insert("asyncToken".into(), async_token);
+                obj.insert("generatorToken".into(), Value::Null);
+                default_null(&mut obj, "typeParameters");
+                default_null(&mut obj, "returnType");
+            }
+
+            "ClassExpression" | "ClassDeclaration" => {
+                let class = json!({
+                    "type": "Class",
+                    "span": obj.get("span"),
+                    "body": get(&obj, "body")["body"],
+                    "superClass": obj.get("superClass"),
+                    "decorators": or_empty(get(&obj, "decorators")),
+                    "typeParameters": obj.get("typeParameters"),
+                    "superTypeParameters": obj.get("superTypeParameters"),
+                    "implements": or_empty(get(&obj, "implements")),
+                });
+                return json!({
+                    "type": ty,
+                    "identifier": obj.get("id"),
+                    "declare": get(&obj, "declare") == true,
+                    "class": class,
+                });
+            }
+            "ClassMethod" | "ClassPrivateMethod" => {
+                let function = function_from_babel(&mut obj);
+                let mut key = take(&mut obj, "key");
+                if ty == "ClassMethod" {
+                    key = prop_name(key, get(&obj, "computed") == true);
+                }
+                return json!({
+                    "type": ty,
+                    "span": obj.get("span"),
+                    "key": key,
+                    "function": function,
+                    "kind": obj.get("kind"),
+                    "staticToken": token(take(&mut obj, "static")),
+                    "decorators": or_empty(get(&obj, "decorators")),
+                });
+            }
+            "ClassProperty" | "ClassPrivateProperty" => {
+                let mut key = take(&mut obj, "key");
+                if ty == "ClassProperty" {
+                    key = prop_name(key, get(&obj, "computed") == true);
+                }
+                obj.insert("key".into(), key);
+                let static_token = token(take(&mut obj, "static"));
+                obj.insert("staticToken".into(), static_token);
+                let decorators = or_empty(get(&obj, "decorators"));
+                obj.insert("decorators".into(), decorators);
+                default_null(&mut obj, "value");
+                default_null(&mut obj, "typeAnnotation");
+            }
+            "StaticBlock" => {
+                let block = json!({
+                    "type": "BlockStatement",
+                    "span": obj.get("span"),
+                    "body": take(&mut obj, "body"),
+                });
+                obj.insert("body".into(), block);
+            }
+
+            "VariableDeclaration" => {
+                let declare = get(&obj, "declare") == true;
+                obj.insert("declare".into(), declare.into());
+            }
+            "ForOfStatement" => {
+                let await_token = token(take(&mut obj, "await"));
+                obj.insert("awaitToken".into(), await_token);
+            }
+
+            "ImportSpecifier" | "ExportSpecifier" => {
+                let key = if ty == "ImportSpecifier" {
+                    "imported"
+                } else {
+                    "exported"
+                };
+                if get(&obj, key)["name"] == get(&obj, "local")["name"] {
+                    obj.insert(key.into(), Value::Null);
+                }
+            }
+            "ExportNamedDeclaration" => match take(&mut obj, "declaration") {
+                Value::Null => default_null(&mut obj, "source"),
+                decl => {
+                    return json!({
+                        "type": "ExportDeclaration",
+                        "span": obj.get("span"),
+                        "declaration": decl,
+                    });
+                }
+            },
+            "ExportDefaultDeclaration" => {
+                let mut decl = take(&mut obj, "declaration");
+                let decl_ty = match decl["type"].as_str() {
+                    Some("FunctionDeclaration") => Some("FunctionExpression"),
+                    Some("ClassDeclaration") => Some("ClassExpression"),
+                    _ => None,
+                };
+                match decl_ty {
+                    Some(decl_ty) => {
+                        decl["type"] = decl_ty.into();
+                        obj.insert("declaration".into(), decl);
+                    }
+                    None => {
+                        obj.insert("type".into(), "ExportDefaultExpression".into());
+                        obj.insert("expression".into(), decl);
+                    }
+                }
+            }
+
+            "JSXText" => {
+                let raw = match extra["raw"] {
+                    Value::Null => get(&obj, "value").clone(),
+                    ref raw => raw.clone(),
+                };
+                obj.insert("raw".into(), raw);
+            }
+
+            _ => {}
+        }
+
+        obj.into()
+    }
+
+    /// Returns the span of a babel node.
+    fn span(&self, obj: &Map) -> Value {
+        let base = u64::from(self.fm.start_pos.0);
+        match (get(obj, "start").as_u64(), get(obj, "end").as_u64()) {
+            (Some(lo), Some(hi)) => json!({ "start": base + lo, "end": base + hi }),
+            _ => dummy_span(),
+        }
+    }
+}
+
+/// Converts an element of arguments or an array to `ExprOrSpread`.
+fn spread_from_babel(mut v: Value) -> Value {
+    if v.is_null() {
+        return v;
+    }
+    if is_type(&v, "SpreadElement") {
+        return json!({
+            "spread": v["dot3Token"].take(),
+            "expression": v["argument"].take(),
+        });
+    }
+    json!({ "spread": null, "expression": v })
+}
+
+/// Returns a serialized `Function` using fields of a babel function node.
+fn function_from_babel(obj: &mut Map) -> Value {
+    let params = take_vec(obj, "params")
+        .into_iter()
+        .map(|pat| {
+            json!({
+                "type": "Parameter",
+                "span": pat["span"],
+                "decorators": or_empty(&pat["decorators"]),
+                "pat": pat,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "type": "Function",
+        "span": obj.get("span"),
+        "params": params,
+        "body": obj.get("body"),
+        "generatorToken": token(get(obj, "generator").clone()),
+        "asyncToken": token(get(obj, "async").clone()),
+        "typeParameters": obj.get("typeParameters"),
+        "returnType": obj.get("returnType"),
+    })
+}
+
+/// Prepends `directives` to `body` as expression statements.
+fn merge_directives(body: &mut Value, directives: Value) {
+    let directives = match directives {
+        Value::Array(directives) => directives,
+        _ => return,
+    };
+    let stmts = directives.into_iter().map(|mut directive| {
+        let dl = directive["value"].take();
+        json!({
+            "type": "ExpressionStatement",
+            "span": directive["span"],
+            "expression": {
+                "type": "StringLiteral",
+                "span": dl["span"],
+                "value": dl["value"],
+                "hasEscape": has_escape(&dl["raw"]),
+                "raw": dl["raw"],
+            },
+        })
+    });
+
+    if let Value::Array(ref mut body) = *body {
+        let rest = std::mem::replace(body, stmts.collect());
+        body.extend(rest);
+    }
+}
+
+fn has_escape(raw: &Value) -> bool {
+    raw.as_str().map(|raw| raw.contains('\\')).unwrap_or(false)
+}
+
+/// Returns a serialized `PropName`.
+fn prop_name(key: Value, computed: bool) -> Value {
+    if computed {
+        // Babel doesn't have positions of the brackets.
+        json!({ "type": "Computed", "span": dummy_span(), "expression": key })
+    } else {
+        key
+    }
+}
+
+/// Returns a dummy span if `flag` is `true`, for tokens like `static`.
+fn token(flag: Value) -> Value {
+    if flag == true {
+        dummy_span()
+    } else {
+        Value::Null
+    }
+}
+
+fn dummy_span() -> Value {
+    json!({ "start": 0, "end": 0 })
+}
+
+fn invalid(msg: &str) -> serde_json::Error {
+    serde::de::Error::custom(msg)
+}
+
+fn node(ty: &str) -> Map {
+    let mut obj = Map::new();
+    obj.insert("type".into(), ty.into());
+    obj
+}
+
+fn is_type(v: &Value, ty: &str) -> bool {
+    v.get("type").and_then(Value::as_str) == Some(ty)
+}
+
+/// Returns `obj[key]`, or `null` if it's not present.
+fn get<'a>(obj: &'a Map, key: &str) -> &'a Value {
+    static NULL: Value = Value::Null;
+    obj.get(key).unwrap_or(&NULL)
+}
+
+fn take(obj: &mut Map, key: &str) -> Value {
+    obj.remove(key).unwrap_or(Value::Null)
+}
+
+fn take_vec(obj: &mut Map, key: &str) -> Vec<Value> {
+    match take(obj, key) {
+        Value::Array(v) => v,
+        _ => vec![],
+    }
+}
+
+fn map_vec<F>(obj: &mut Map, key: &str, op: F)
+where
+    F: FnMut(Value) -> Value,
+{
+    if let Some(&mut Value::Array(ref mut v)) = obj.get_mut(key) {
+        let items = std::mem::replace(v, vec![]);
+        *v = items.into_iter().map(op).collect();
+    }
+}
+
+fn rename(obj: &mut Map, from: &str, to: &str) {
+    let v = take(obj, from);
+    obj.insert(to.into(), v);
+}
+
+fn remove_null(obj: &mut Map, key: &str) {
+    if obj.get(key) == Some(&Value::Null) {
+        obj.remove(key);
+    }
+}
+
+fn remove_false(obj: &mut Map, key: &str) {
+    if obj.get(key) == Some(&Value::Bool(false)) {
+        obj.remove(key);
+    }
+}
+
+fn remove_empty(obj: &mut Map, key: &str) {
+    match obj.get(key) {
+        Some(&Value::Array(ref v)) if v.is_empty() => {}
+        _ => return,
+    }
+    obj.remove(key);
+}
+
+fn default_null(obj: &mut Map, key: &str) {
+    if !obj.contains_key(key) {
+        obj.insert(key.into(), Value::Null);
+    }
+}
+
+fn or_empty(v: &Value) -> Value {
+    match *v {
+        Value::Array(ref v) => v.clone().into(),
+        _ => json!([]),
+    }
+}
+
+/// Copies `start` with `loc.start` of `from`.
+fn copy_start(obj: &mut Map, from: &Value) {
+    copy_pos(obj, from, "start");
+}
+
+/// Copies `end` with `loc.end` of `from`.
+fn copy_end(obj: &mut Map, from: &Value) {
+    copy_pos(obj, from, "end");
+}
+
+fn copy_pos(obj: &mut Map, from: &Value, key: &str) {
+    let pos = match from.get(key) {
+        Some(pos) if !pos.is_null() => pos.clone(),
+        _ => return,
+    };
+    obj.insert(key.into(), pos);
+
+    set_loc(obj, key, from["loc"][key].clone());
+}
+
+fn set_loc(obj: &mut Map, key: &str, loc: Value) {
+    if !obj.contains_key("loc") {
+        obj.insert("loc".into(), json!({}));
+    }
+    obj["loc"][key] = loc;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        common::{span_remapper, FileName, FoldWith, DUMMY_SP},
+        ecmascript::parser::{EsConfig, Syntax},
+        tests::with_compiler,
+    };
+
+    fn with_module<F>(src: &str, op: F)
+    where
+        F: FnOnce(&SourceMap, &SourceFile, Module),
+    {
+        with_compiler(|compiler, cm| {
+            let fm = cm.new_source_file(FileName::Custom("test.js".into()), src.into());
+            let syntax = Syntax::Es(EsConfig {
+                jsx: true,
+                class_props: true,
+                import_meta: true,
+                optional_chaining: true,
+                nullish_coalescing: true,
+                ..Default::default()
+            });
+            let module = compiler.parse_js_file(&fm, syntax).expect("failed to parse");
+            op(cm, &fm, module)
+        })
+    }
+
+    fn drop_span(module: Module) -> Module {
+        module.fold_with(&mut span_remapper(|_| DUMMY_SP))
+    }
+
+    fn round_trip(src: &str) {
+        with_module(src, |cm, fm, module| {
+            let file = to_babel(cm, fm, &module);
+            let converted = from_babel(fm, file.clone())
+                .unwrap_or_else(|err| panic!("failed to convert {}: {}", file, err));
+
+            assert_eq!(drop_span(module), drop_span(converted), "{}", src);
+        })
+    }
+
+    #[test]
+    fn round_trip_stmts() {
+        round_trip("'use strict'; a || b; x = y ? /re/g : null; f(...args, c);");
+        round_trip("label: for (const [a, ...b] of c) { if (a) break label; else continue; }");
+        round_trip("try { throw e; } catch (e) {} finally {} for (var k in o); while (0);");
+        round_trip("a?.b.c; a?.(b); (a?.b).c; new Foo(); import.meta;");
+    }
+
+    #[test]
+    fn round_trip_functions() {
+        round_trip("async function* f(a = 1, { b, c: d, e = 2, ...f }, [g]) { 'use asm'; }");
+        round_trip("var f = async (a) => a, g = () => { return this; };");
+        round_trip("var o = { a, 'b': 1, [c]: 2, get d() {}, set d(v) {}, e() {}, ...f };");
+        round_trip("class A extends B { constructor() { super(); } static get c() {} d = 1; }");
+        round_trip("tag`a${b}c`; `d`;");
+    }
+
+    #[test]
+    fn round_trip_module_decls() {
+        round_trip("import foo, { bar as baz, qux } from 'mod'; import * as ns from 'ns';");
+        round_trip("export default function () {} export const x = 1; export * from 'y';");
+        round_trip("export default a + b; export { x as y, z }; export class C {}");
+    }
+
+    #[test]
+    fn round_trip_jsx() {
+        round_trip("<A.B c='d' e={f} {...g}>{h} text<i:j /><></></A.B>;");
+    }
+
+    #[test]
+    fn babel_shape() {
+        with_module("a ?? b;\nvar o = { c };", |cm, fm, module| {
+            let file = to_babel(cm, fm, &module);
+            assert_eq!(file["type"], "File");
+
+            let program = &file["program"];
+            assert_eq!(program["type"], "Program");
+            let expr = &program["body"][0]["expression"];
+            assert_eq!(expr["type"], "LogicalExpression");
+            assert_eq!(expr["start"], 0);
+            assert_eq!(expr["end"], 6);
+
+            let prop = &program["body"][1]["declarations"][0]["init"]["properties"][0];
+            assert_eq!(prop["type"], "ObjectProperty");
+            assert_eq!(prop["shorthand"], true);
+            assert_eq!(prop["loc"]["start"], json!({ "line": 2, "column": 10 }));
+        })
+    }
+
+    #[test]
+    fn node_without_position() {
+        with_module("f(a);", |_, fm, module| {
+            let file = json!({
+                "type": "File",
+                "program": {
+                    "type": "Program",
+                    "sourceType": "module",
+                    "directives": [],
+                    "body": [{
+                        "type": "ExpressionStatement",
+                        "expression": {
+                            "type": "CallExpression",
+                            "callee": { "type": "Identifier", "name": "f" },
+                            "arguments": [{ "type": "Identifier", "name": "a" }],
+                        },
+                    }],
+                },
+            });
+            let converted = from_babel(fm, file).expect("failed to convert");
+
+            assert_eq!(drop_span(module), converted);
+        })
+    }
+
+    #[test]
+    fn invalid_node() {
+        with_module("a;", |cm, fm, module| {
+            let mut file = to_babel(cm, fm, &module);
+            file["program"]["body"][0]["expression"]["type"] = "Unknown".into();
+
+            assert!(from_babel(fm, file).is_err());
+        })
+    }
+}
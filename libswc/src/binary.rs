@@ -0,0 +1,105 @@
+//! Versioned binary encoding of modules, which wasm plugins exchange instead
+//! of javascript source.
+//!
+//! An encoded module is [MAGIC], [VERSION] as a little endian `u32` and the
+//! module as cbor. As nodes have the same shape as the json of
+//! [Compiler::parse_js_json](crate::Compiler::parse_js_json), decoding is not
+//! zero-copy, but strings don't need escaping and numbers are not printed.
+//!
+//! Modules are not cached on disk in this encoding. Spans and comments of a
+//! parsed module refer to positions in the source map of the compiler which
+//! parsed it, so [crate::cache] stores outputs instead.
+
+use crate::ecmascript::ast::Module;
+use std::{error::Error, fmt};
+
+/// Prefix of encoded modules.
+pub const MAGIC: &[u8; 4] = b"swcA";
+
+/// Bumped whenever the ast changes incompatibly.
+pub const VERSION: u32 = 1;
+
+pub fn encode(module: &Module) -> Vec<u8> {
+    let mut buf = MAGIC.to_vec();
+    buf.extend((0..4).map(|i| (VERSION >> (8 * i)) as u8));
+    buf.extend(serde_cbor::to_vec(module).expect("failed to serialize a module"));
+    buf
+}
+
+pub fn decode(buf: &[u8]) -> Result<Module, DecodeError> {
+    if buf.len() < 8 || &buf[..4] != MAGIC {
+        return Err(DecodeError::Magic);
+    }
+    let version = buf[4..8]
+        .iter()
+        .rev()
+        .fold(0, |v, &b| v << 8 | u32::from(b));
+    if version != VERSION {
+        return Err(DecodeError::Version(version));
+    }
+
+    serde_cbor::from_slice(&buf[8..]).map_err(DecodeError::Cbor)
+}
+
+#[derive(Debug)]
+pub enum DecodeError {
+    /// The buffer doesn't start with [MAGIC].
+    Magic,
+    /// Encoded with another [VERSION].
+    Version(u32),
+    Cbor(serde_cbor::error::Error),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DecodeError::Magic => write!(f, "not an encoded module"),
+            DecodeError::Version(v) => write!(
+                f,
+                "module is encoded with ast v{} but swc requires v{}",
+                v, VERSION
+            ),
+            DecodeError::Cbor(ref err) => write!(f, "invalid module: {}", err),
+        }
+    }
+}
+
+impl Error for DecodeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{common::FileName, tests::with_compiler};
+
+    fn parse(src: &str) -> Module {
+        with_compiler(|compiler, cm| {
+            let fm = cm.new_source_file(FileName::Custom("test.js".into()), src.into());
+            compiler
+                .parse_js_file(&fm, Default::default())
+                .expect("failed to parse")
+        })
+    }
+
+    #[test]
+    fn round_trip() {
+        let module = parse("import a from 'a'; export default class extends a { b() { `${1}` } }");
+        let buf = encode(&module);
+        assert!(buf.starts_with(MAGIC));
+        assert_eq!(decode(&buf).unwrap(), module);
+    }
+
+    #[test]
+    fn version() {
+        let mut buf = encode(&parse("a;"));
+        buf[4] += 1;
+        match decode(&buf) {
+            Err(DecodeError::Version(v)) => assert_eq!(v, VERSION + 1),
+            res => panic!("unexpected result: {:?}", res),
+        }
+
+        match decode(b"{}") {
+            Err(DecodeError::Magic) => {}
+            res => panic!("unexpected result: {:?}", res),
+        }
+    }
+}
@@ -0,0 +1,184 @@
+//! Persistent cache of outputs, like `cacheDirectory` of babel-loader.
+//!
+//! Unlike [crate::incremental], outputs are kept across runs of the process.
+
+use crate::{common::SourceFile, config::Config, Compiler, TransformOutput};
+use std::{
+    collections::hash_map::DefaultHasher,
+    error::Error,
+    fmt, fs,
+    hash::{Hash, Hasher},
+    io,
+    path::PathBuf,
+    process,
+};
+
+/// Storage of outputs, keyed by [CacheKey].
+pub trait CacheStore: Send + Sync {
+    /// Returns `None` if `key` is not stored or the entry is unreadable.
+    fn get(&self, key: &CacheKey) -> Option<TransformOutput>;
+
+    fn put(&self, key: &CacheKey, output: &TransformOutput) -> io::Result<()>;
+}
+
+/// Hash of the name and content of a file, the config it's compiled with and
+/// the version of swc.
+///
+/// Plugins are identified by their paths and modification times, so entries
+/// compiled with a plugin are invalidated when the plugin is rebuilt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CacheKey(u64);
+
+impl CacheKey {
+    pub fn new(fm: &SourceFile, config: &Config) -> Self {
+        let mut hasher = DefaultHasher::new();
+        env!("CARGO_PKG_VERSION").hash(&mut hasher);
+        fm.name.to_string().hash(&mut hasher);
+        fm.src.as_ref().map(|src| &**src).hash(&mut hasher);
+        config.hash_with_plugins().hash(&mut hasher);
+        CacheKey(hasher.finish())
+    }
+}
+
+impl fmt::Display for CacheKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+/// Stores each output as a json file in a directory.
+#[derive(Debug, Clone)]
+pub struct DirCache {
+    dir: PathBuf,
+}
+
+impl DirCache {
+    /// The directory is created on the first write.
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        DirCache { dir: dir.into() }
+    }
+
+    fn path(&self, key: &CacheKey) -> PathBuf {
+        self.dir.join(format!("{}.json", key))
+    }
+}
+
+impl CacheStore for DirCache {
+    fn get(&self, key: &CacheKey) -> Option<TransformOutput> {
+        let json = fs::read_to_string(self.path(key)).ok()?;
+        serde_json::from_str(&json).ok()
+    }
+
+    fn put(&self, key: &CacheKey, output: &TransformOutput) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+
+        // Renaming makes the write atomic for processes sharing the
+        // directory.
+        let tmp = self.dir.join(format!("{}.{}.tmp", key, process::id()));
+        fs::write(&tmp, serde_json::to_string(output)?)?;
+        fs::rename(&tmp, self.path(key))
+    }
+}
+
+impl Compiler {
+    /// Same as [Compiler::process_js_file], but reuses an output stored in
+    /// `cache`.
+    ///
+    /// Failures to write to `cache` are logged instead of being returned.
+    pub fn process_js_file_cached(
+        &self,
+        fm: &SourceFile,
+        config: &Config,
+        cache: &CacheStore,
+    ) -> Result<TransformOutput, Box<Error>> {
+        let key = CacheKey::new(fm, config);
+        if let Some(output) = cache.get(&key) {
+            return Ok(output);
+        }
+
+        let output = self.process_js_file(fm, config)?;
+        if let Err(err) = cache.put(&key, &output) {
+            warn!(self.logger, "failed to write cache {}: {}", key, err);
+        }
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{FileName, FilePathMapping, SourceMap};
+
+    fn key(name: &str, src: &str, config: &Config) -> CacheKey {
+        let cm = SourceMap::new(FilePathMapping::empty());
+        let fm = cm.new_source_file(FileName::Real(name.into()), src.into());
+        CacheKey::new(&fm, config)
+    }
+
+    #[test]
+    fn keys() {
+        let config = Config::default();
+        let minify = Config {
+            minify: true,
+            ..Default::default()
+        };
+
+        assert_eq!(key("a.js", "a;", &config), key("a.js", "a;", &config));
+        assert_ne!(key("a.js", "a;", &config), key("b.js", "a;", &config));
+        assert_ne!(key("a.js", "a;", &config), key("a.js", "b;", &config));
+        assert_ne!(key("a.js", "a;", &config), key("a.js", "a;", &minify));
+    }
+
+    #[test]
+    fn keys_of_maps() {
+        let globals: serde_json::Map<_, _> = (0..16)
+            .map(|i| (format!("G{}", i), serde_json::Value::from(i)))
+            .collect();
+        let config = || -> Config {
+            serde_json::from_value(serde_json::json!({
+                "jsc": { "transform": { "globals": globals } },
+            }))
+            .unwrap()
+        };
+
+        // Each map has its own random order.
+        assert_eq!(key("a.js", "a;", &config()), key("a.js", "a;", &config()));
+    }
+
+    #[test]
+    fn keys_of_plugins() {
+        let path = std::env::temp_dir().join(format!("swc-cache-plugin-{}", process::id()));
+        let config: Config = serde_json::from_value(serde_json::json!({
+            "jsc": { "plugins": [{ "path": path.to_string_lossy() }] },
+        }))
+        .unwrap();
+
+        let missing = key("a.js", "a;", &config);
+        fs::write(&path, "").unwrap();
+        let built = key("a.js", "a;", &config);
+        fs::remove_file(&path).unwrap();
+
+        assert_ne!(missing, built);
+    }
+
+    #[test]
+    fn dir_cache() {
+        let dir = std::env::temp_dir().join(format!("swc-cache-test-{}", process::id()));
+        let cache = DirCache::new(dir.clone());
+        let k = key("a.js", "a;", &Config::default());
+        let output = TransformOutput {
+            code: "a;".into(),
+            map: Some("{}".into()),
+            dependencies: vec![".swcrc".into()],
+        };
+
+        assert!(cache.get(&k).is_none());
+        cache.put(&k, &output).unwrap();
+        let cached = cache.get(&k).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(cached.code, output.code);
+        assert_eq!(cached.map, output.map);
+        assert_eq!(cached.dependencies, output.dependencies);
+    }
+}
@@ -0,0 +1,1041 @@
+//! Options loaded from `.swcrc`.
+//!
+//! `.swcrc` contains either a single config object or an array of them. When
+//! an array is used, the first entry whose `test` / `include` / `exclude`
+//! patterns accept the input file is used.
+
+use crate::{
+    common::{noop, sync::Lrc, BytePos, Fold, SourceFile, Span, DUMMY_SP},
+    ecmascript::{
+        ast::{
+            ArrayLit, Bool, EsVersion, Expr, ExprOrSpread, KeyValueProp, Lit, Module, Null,
+            Number, ObjectLit, Prop, PropName, PropOrSpread, Str,
+        },
+        lint::LintConfig,
+        minifier::MinifyOptions,
+        parser,
+        transforms::{
+            compat::{helpers::Helpers, preset_env::Targets},
+            modules, react, InlineGlobals,
+        },
+    },
+};
+use globset::Glob;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    fmt, fs,
+    hash::{Hash, Hasher},
+    path::Path,
+    sync::Arc,
+};
+
+/// Content of `.swcrc`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Rc {
+    Single(Config),
+    Multi(Vec<Config>),
+}
+
+impl Default for Rc {
+    fn default() -> Self {
+        Rc::Single(Default::default())
+    }
+}
+
+impl Rc {
+    pub fn parse(s: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(s)
+    }
+
+    /// Records `fm`, which `self` is parsed from, in each entry.
+    pub fn with_source(self, fm: Lrc<SourceFile>) -> Self {
+        match self {
+            Rc::Single(c) => Rc::Single(Config {
+                source: Some(RcSource { fm, entry: None }),
+                ..c
+            }),
+            Rc::Multi(configs) => Rc::Multi(
+                configs
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, c)| Config {
+                        source: Some(RcSource {
+                            fm: fm.clone(),
+                            entry: Some(i),
+                        }),
+                        ..c
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Returns the first config applicable to `path`.
+    pub fn config_for_file(&self, path: &Path) -> Result<Option<Config>, MatcherError> {
+        let configs: &[Config] = match *self {
+            Rc::Single(ref c) => std::slice::from_ref(c),
+            Rc::Multi(ref configs) => configs,
+        };
+
+        for c in configs {
+            if c.matches(path)? {
+                return Ok(Some(c.clone()));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// Where a config is loaded from, used to point errors at `.swcrc`.
+#[derive(Debug, Clone)]
+pub struct RcSource {
+    pub fm: Lrc<SourceFile>,
+    /// Index of the config if `.swcrc` is an array.
+    pub entry: Option<usize>,
+}
+
+impl RcSource {
+    /// Returns the span of `jsc.plugins[plugin].config` followed by `path`,
+    /// which is formatted like `.foo[0]`.
+    pub fn plugin_config_span(&self, plugin: usize, path: &str) -> Option<Span> {
+        let src = self.fm.src.as_ref()?;
+        let mut keys: Vec<_> = self.entry.into_iter().map(JsonKey::Index).collect();
+        keys.extend(vec![
+            JsonKey::Field("jsc"),
+            JsonKey::Field("plugins"),
+            JsonKey::Index(plugin),
+            JsonKey::Field("config"),
+        ]);
+        keys.extend(json_path(path)?);
+
+        let mut lo = 0;
+        for key in &keys {
+            lo = find_json(src, lo, key)?;
+        }
+        let hi = skip_value(src.as_bytes(), lo)?;
+
+        let start = self.fm.start_pos;
+        Some(Span::new(
+            start + BytePos(lo as u32),
+            start + BytePos(hi as u32),
+            Default::default(),
+        ))
+    }
+}
+
+/// A single entry of `.swcrc`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct Config {
+    /// The config applies only to files matching this.
+    #[serde(default)]
+    pub test: Option<FileMatcher>,
+
+    /// Alias of `test`, for babel users.
+    #[serde(default)]
+    pub include: Option<FileMatcher>,
+
+    /// The config does not apply to files matching this.
+    #[serde(default)]
+    pub exclude: Option<FileMatcher>,
+
+    #[serde(default)]
+    pub jsc: JscConfig,
+
+    #[serde(default)]
+    pub minify: bool,
+
+    /// Environments which the output should run in.
+    #[serde(default)]
+    pub env: Option<EnvConfig>,
+
+    #[serde(default)]
+    pub module: Option<ModuleConfig>,
+
+    #[serde(default)]
+    pub source_maps: Option<SourceMapsConfig>,
+
+    /// Value of `file` in the generated source map.
+    #[serde(default)]
+    pub source_map_target: Option<String>,
+
+    /// Value of `sourceRoot` in the generated source map.
+    #[serde(default)]
+    pub source_root: Option<String>,
+
+    /// Embed original sources into the source map. Defaults to true.
+    #[serde(default)]
+    pub sources_content: Option<bool>,
+
+    /// Severities of lint rules, used by [Compiler::lint].
+    ///
+    /// [Compiler::lint]: crate::Compiler::lint
+    #[serde(default)]
+    pub lint: Option<LintConfig>,
+
+    /// Set by [Compiler::read_rc].
+    ///
+    /// [Compiler::read_rc]: crate::Compiler::read_rc
+    #[serde(skip)]
+    pub source: Option<RcSource>,
+}
+
+impl Config {
+    /// Returns true if this config should be used for `path`.
+    pub fn matches(&self, path: &Path) -> Result<bool, MatcherError> {
+        for m in self.test.iter().chain(self.include.iter()) {
+            if !m.matches(path)? {
+                return Ok(false);
+            }
+        }
+
+        if let Some(ref exclude) = self.exclude {
+            if exclude.matches(path)? {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Environments which compat passes are selected for.
+    ///
+    /// Defaults to es3, which applies all compat passes.
+    pub fn targets(&self) -> Result<Targets, String> {
+        match self.env {
+            Some(ref env) => env.targets.parse(),
+            None => Ok(Targets::EsVersion(EsVersion::Es3)),
+        }
+    }
+
+    /// Hash of the config and modification times of plugins it loads, used
+    /// to find outputs which are compiled with another config.
+    pub(crate) fn hash_with_plugins(&self) -> u64 {
+        let json = serde_json::to_value(self).expect("failed to serialize config");
+
+        let mut hasher = DefaultHasher::new();
+        hash_json(&json, &mut hasher);
+        for p in &self.jsc.plugins {
+            // A missing plugin fails compilation anyway.
+            fs::metadata(&p.path)
+                .and_then(|m| m.modified())
+                .ok()
+                .hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    /// Creates the module transform configured by `module`.
+    pub fn module_pass(&self, helpers: &Arc<Helpers>) -> Result<Box<Fold<Module>>, Unsupported> {
+        match self.module {
+            Some(ref module) => module.build(helpers),
+            None => Ok(box noop()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct EnvConfig {
+    /// An ecmascript version like `"es2015"`, or browsers like
+    /// `"chrome 60, ie 11"`.
+    pub targets: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct ModuleConfig {
+    #[serde(rename = "type")]
+    pub kind: ModuleKind,
+
+    /// Don't define `exports.__esModule`.
+    #[serde(default)]
+    pub strict: bool,
+
+    #[serde(default)]
+    pub no_interop: bool,
+
+    /// Require imported modules when they are first used.
+    #[serde(default)]
+    pub lazy: bool,
+
+    /// Name of amd, umd and systemjs modules.
+    #[serde(default)]
+    pub module_id: Option<String>,
+
+    /// Global variable which exports of umd modules are assigned to.
+    #[serde(default)]
+    pub global_name: Option<String>,
+
+    /// Global variables of modules imported by umd modules.
+    #[serde(default)]
+    pub globals: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModuleKind {
+    CommonJs,
+    Amd,
+    Umd,
+    SystemJs,
+    /// Keep es modules as-is.
+    Es6,
+}
+
+impl ModuleConfig {
+    /// Creates the module transform selected by this config.
+    pub fn build(&self, helpers: &Arc<Helpers>) -> Result<Box<Fold<Module>>, Unsupported> {
+        if self.lazy && self.kind != ModuleKind::CommonJs {
+            return Err(Unsupported("module.lazy for modules other than commonjs"));
+        }
+
+        Ok(match self.kind {
+            // Bindings are marked by `resolver` beforehand, so lazy imports
+            // are not confused with shadowing bindings.
+            ModuleKind::CommonJs => box modules::common_js(
+                helpers.clone(),
+                modules::common_js::Config {
+                    strict: self.strict,
+                    no_interop: self.no_interop,
+                    lazy: self.lazy,
+                },
+            ),
+            ModuleKind::Amd => box modules::amd(
+                helpers.clone(),
+                modules::amd::Config {
+                    module_id: self.module_id.clone(),
+                    strict: self.strict,
+                    no_interop: self.no_interop,
+                },
+            ),
+            ModuleKind::Umd => box modules::umd(
+                helpers.clone(),
+                modules::umd::Config {
+                    module_id: self.module_id.clone(),
+                    global_name: self.global_name.clone(),
+                    globals: self.globals.clone(),
+                    strict: self.strict,
+                    no_interop: self.no_interop,
+                },
+            ),
+            ModuleKind::Es6 => box noop(),
+            ModuleKind::SystemJs => box modules::system_js(
+                helpers.clone(),
+                modules::system_js::Config {
+                    module_id: self.module_id.clone(),
+                },
+            ),
+        })
+    }
+}
+
+/// `true`, `false`, `"inline"` or `"both"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SourceMapsConfig {
+    Bool(bool),
+    Kind(SourceMapKind),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceMapKind {
+    /// Append source map to the output as a data url.
+    Inline,
+    /// Both of inline and a separate file.
+    Both,
+}
+
+impl SourceMapsConfig {
+    /// Should a separate `.map` file be generated?
+    pub fn file(self) -> bool {
+        match self {
+            SourceMapsConfig::Bool(b) => b,
+            SourceMapsConfig::Kind(SourceMapKind::Inline) => false,
+            SourceMapsConfig::Kind(SourceMapKind::Both) => true,
+        }
+    }
+
+    /// Should source map be appended to the output?
+    pub fn inline(self) -> bool {
+        match self {
+            SourceMapsConfig::Bool(..) => false,
+            SourceMapsConfig::Kind(..) => true,
+        }
+    }
+}
+
+impl std::str::FromStr for SourceMapsConfig {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "true" => Ok(SourceMapsConfig::Bool(true)),
+            "false" => Ok(SourceMapsConfig::Bool(false)),
+            "inline" => Ok(SourceMapsConfig::Kind(SourceMapKind::Inline)),
+            "both" => Ok(SourceMapsConfig::Kind(SourceMapKind::Both)),
+            _ => Err(format!("invalid value for source maps: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct JscConfig {
+    #[serde(default)]
+    pub parser: ParserConfig,
+
+    #[serde(default)]
+    pub transform: TransformConfig,
+
+    /// Native plugins, which are applied in order.
+    #[serde(default)]
+    pub plugins: Vec<PluginConfig>,
+
+    /// Options of the minifier in the shape of terser, used if `minify` is
+    /// true.
+    #[serde(default)]
+    pub minify: Option<MinifyOptions>,
+
+    /// Import helpers from `@swc/helpers` instead of inlining them.
+    #[serde(default)]
+    pub external_helpers: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct PluginConfig {
+    /// Path to the dynamic library.
+    pub path: String,
+
+    /// Passed to the plugin as-is.
+    #[serde(default)]
+    pub config: serde_json::Value,
+
+    /// Overrides the phase declared by the plugin.
+    #[serde(default)]
+    pub phase: Option<PluginPhase>,
+
+    /// Instructions a wasm plugin may execute for a file. Defaults to
+    /// [DEFAULT_FUEL](crate::plugin::wasm::DEFAULT_FUEL).
+    #[serde(default)]
+    pub fuel: Option<u64>,
+}
+
+/// When a plugin runs, relative to the built-in passes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PluginPhase {
+    /// Sees the original syntax.
+    BeforeCompat,
+    /// Sees es3 code with es modules.
+    AfterCompat,
+    /// Sees the module transformed, before helpers are injected.
+    AfterModule,
+    /// Sees the final output, right before it's emitted.
+    BeforeEmit,
+}
+
+impl Default for PluginPhase {
+    fn default() -> Self {
+        PluginPhase::AfterCompat
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct ParserConfig {
+    /// Typescript is stripped before any other pass. Options of proposals
+    /// other than `dynamicImport` are ignored for typescript, and so is
+    /// `jsx` as tsx can't be parsed yet.
+    #[serde(default)]
+    pub syntax: ParserSyntax,
+
+    #[serde(default)]
+    pub num_sep: bool,
+
+    #[serde(default)]
+    pub fn_bind: bool,
+
+    #[serde(default)]
+    pub jsx: bool,
+
+    #[serde(default)]
+    pub class_props: bool,
+
+    #[serde(default)]
+    pub dynamic_import: bool,
+
+    #[serde(default)]
+    pub import_meta: bool,
+
+    #[serde(default)]
+    pub optional_chaining: bool,
+
+    #[serde(default)]
+    pub nullish_coalescing: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ParserSyntax {
+    Ecmascript,
+    Typescript,
+}
+
+impl Default for ParserSyntax {
+    fn default() -> Self {
+        ParserSyntax::Ecmascript
+    }
+}
+
+impl From<ParserConfig> for parser::Syntax {
+    fn from(c: ParserConfig) -> Self {
+        if c.syntax == ParserSyntax::Typescript {
+            return parser::Syntax::Typescript(parser::TsConfig {
+                dynamic_import: c.dynamic_import,
+                ..Default::default()
+            });
+        }
+
+        parser::Syntax::Es(parser::EsConfig {
+            num_sep: c.num_sep,
+            fn_bind: c.fn_bind,
+            jsx: c.jsx,
+            class_props: c.class_props,
+            dynamic_import: c.dynamic_import,
+            import_meta: c.import_meta,
+            optional_chaining: c.optional_chaining,
+            nullish_coalescing: c.nullish_coalescing,
+            // No pass lowers decorators yet.
+            ..Default::default()
+        })
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct TransformConfig {
+    /// Run the simplifier after lowering. Unused bindings of functions and
+    /// unused calls annotated with `/*#__PURE__*/` are also removed.
+    #[serde(default)]
+    pub optimize: bool,
+
+    /// Values of global variables and member expressions on them, like
+    /// `{ "process.env.NODE_ENV": "production", "__DEBUG__": false }`.
+    #[serde(default)]
+    pub globals: HashMap<String, serde_json::Value>,
+
+    /// Options of the jsx transform, which runs if `jsc.parser.jsx` is set.
+    #[serde(default)]
+    pub react: ReactConfig,
+}
+
+impl TransformConfig {
+    /// Creates the pass which replaces `globals`.
+    pub fn inline_globals(&self) -> InlineGlobals {
+        InlineGlobals {
+            envs: Default::default(),
+            globals: self
+                .globals
+                .iter()
+                .map(|(k, v)| (k.as_str().into(), json_to_expr(v)))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct ReactConfig {
+    #[serde(default)]
+    pub runtime: ReactRuntime,
+
+    /// Defaults to `React.createElement`.
+    #[serde(default)]
+    pub pragma: Option<String>,
+
+    /// Defaults to `React.Fragment`.
+    #[serde(default)]
+    pub pragma_frag: Option<String>,
+
+    /// Defaults to `react`.
+    #[serde(default)]
+    pub import_source: Option<String>,
+
+    /// Use `Object.assign` instead of the `_extends` helper.
+    #[serde(default)]
+    pub use_builtins: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReactRuntime {
+    Classic,
+    Automatic,
+}
+
+impl Default for ReactRuntime {
+    fn default() -> Self {
+        ReactRuntime::Classic
+    }
+}
+
+impl ReactConfig {
+    /// Creates options of the jsx transform.
+    pub fn options(&self) -> react::jsx::Options {
+        let default = react::jsx::Options::default();
+
+        react::jsx::Options {
+            runtime: match self.runtime {
+                ReactRuntime::Classic => react::jsx::Runtime::Classic,
+                ReactRuntime::Automatic => react::jsx::Runtime::Automatic,
+            },
+            pragma: self.pragma.clone().unwrap_or(default.pragma),
+            pragma_frag: self.pragma_frag.clone().unwrap_or(default.pragma_frag),
+            import_source: self.import_source.clone().unwrap_or(default.import_source),
+            use_builtins: self.use_builtins,
+        }
+    }
+}
+
+fn json_to_expr(v: &serde_json::Value) -> Expr {
+    use serde_json::Value;
+
+    let str_lit = |s: &str| Str {
+        span: DUMMY_SP,
+        value: s.into(),
+        has_escape: false,
+        raw: None,
+    };
+
+    match *v {
+        Value::Null => Expr::Lit(Lit::Null(Null { span: DUMMY_SP })),
+        Value::Bool(value) => Expr::Lit(Lit::Bool(Bool {
+            span: DUMMY_SP,
+            value,
+        })),
+        Value::Number(ref n) => Expr::Lit(Lit::Num(Number {
+            span: DUMMY_SP,
+            value: n.as_f64().unwrap_or(0.0),
+            raw: None,
+        })),
+        Value::String(ref s) => Expr::Lit(Lit::Str(str_lit(s))),
+        Value::Array(ref elems) => Expr::Array(ArrayLit {
+            span: DUMMY_SP,
+            elems: elems
+                .iter()
+                .map(|v| {
+                    Some(ExprOrSpread {
+                        spread: None,
+                        expr: box json_to_expr(v),
+                    })
+                })
+                .collect(),
+        }),
+        Value::Object(ref props) => Expr::Object(ObjectLit {
+            span: DUMMY_SP,
+            props: props
+                .iter()
+                .map(|(k, v)| {
+                    PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                        key: PropName::Str(str_lit(k)),
+                        value: box json_to_expr(v),
+                    }))
+                })
+                .collect(),
+        }),
+    }
+}
+
+/// Pattern used by `test`, `include` and `exclude`.
+///
+/// A plain string is a regex matched against the path. `{ "glob": "..." }`
+/// is a glob pattern, and an array matches if any of its elements matches.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum FileMatcher {
+    Regex(String),
+    Glob { glob: String },
+    Multi(Vec<FileMatcher>),
+}
+
+impl FileMatcher {
+    pub fn matches(&self, path: &Path) -> Result<bool, MatcherError> {
+        match *self {
+            FileMatcher::Regex(ref s) => {
+                let re = Regex::new(s).map_err(MatcherError::Regex)?;
+                Ok(re.is_match(&path.to_string_lossy()))
+            }
+            FileMatcher::Glob { ref glob } => {
+                let glob = Glob::new(glob).map_err(MatcherError::Glob)?;
+                Ok(glob.compile_matcher().is_match(path))
+            }
+            FileMatcher::Multi(ref matchers) => {
+                for m in matchers {
+                    if m.matches(path)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum MatcherError {
+    Regex(regex::Error),
+    Glob(globset::Error),
+}
+
+impl fmt::Display for MatcherError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            MatcherError::Regex(ref err) => write!(f, "invalid regex in .swcrc: {}", err),
+            MatcherError::Glob(ref err) => write!(f, "invalid glob in .swcrc: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for MatcherError {}
+
+/// Hashes objects in the order of keys, as maps like `globals` are
+/// serialized in arbitrary order.
+fn hash_json<H: Hasher>(json: &serde_json::Value, hasher: &mut H) {
+    use serde_json::Value;
+
+    match *json {
+        Value::Array(ref elems) => {
+            elems.len().hash(hasher);
+            for e in elems {
+                hash_json(e, hasher);
+            }
+        }
+        Value::Object(ref obj) => {
+            let mut entries: Vec<_> = obj.iter().collect();
+            entries.sort_by_key(|e| e.0);
+            entries.len().hash(hasher);
+            for (key, value) in entries {
+                key.hash(hasher);
+                hash_json(value, hasher);
+            }
+        }
+        ref json => json.to_string().hash(hasher),
+    }
+}
+
+enum JsonKey<'a> {
+    Field(&'a str),
+    Index(usize),
+}
+
+/// Splits a path like `.foo[0]` into keys.
+fn json_path(mut path: &str) -> Option<Vec<JsonKey>> {
+    let mut keys = vec![];
+    while !path.is_empty() {
+        if path.starts_with('.') {
+            let end = path[1..]
+                .find(|c: char| c == '.' || c == '[')
+                .map_or(path.len(), |i| i + 1);
+            keys.push(JsonKey::Field(&path[1..end]));
+            path = &path[end..];
+        } else if path.starts_with('[') {
+            let end = path.find(']')?;
+            keys.push(JsonKey::Index(path[1..end].parse().ok()?));
+            path = &path[end + 1..];
+        } else {
+            return None;
+        }
+    }
+    Some(keys)
+}
+
+/// Returns the position of the value at `key` in the json value at `pos`.
+fn find_json(src: &str, pos: usize, key: &JsonKey) -> Option<usize> {
+    let s = src.as_bytes();
+    let mut i = skip_ws(s, pos);
+    match *key {
+        JsonKey::Field(name) => {
+            if s.get(i) != Some(&b'{') {
+                return None;
+            }
+            i += 1;
+            loop {
+                i = skip_ws(s, i);
+                if s.get(i) != Some(&b'"') {
+                    return None;
+                }
+                let end = skip_string(s, i)?;
+                let found = serde_json::from_str::<String>(&src[i..end]).ok()? == name;
+                i = skip_ws(s, end);
+                if s.get(i) != Some(&b':') {
+                    return None;
+                }
+                i = skip_ws(s, i + 1);
+                if found {
+                    return Some(i);
+                }
+                i = skip_ws(s, skip_value(s, i)?);
+                if s.get(i) != Some(&b',') {
+                    return None;
+                }
+                i += 1;
+            }
+        }
+        JsonKey::Index(idx) => {
+            if s.get(i) != Some(&b'[') {
+                return None;
+            }
+            i += 1;
+            for _ in 0..idx {
+                i = skip_ws(s, skip_value(s, skip_ws(s, i))?);
+                if s.get(i) != Some(&b',') {
+                    return None;
+                }
+                i += 1;
+            }
+            Some(skip_ws(s, i))
+        }
+    }
+}
+
+fn skip_ws(s: &[u8], mut i: usize) -> usize {
+    while s.get(i).map_or(false, |c| c.is_ascii_whitespace()) {
+        i += 1;
+    }
+    i
+}
+
+/// Returns the end of the string starting at `i`.
+fn skip_string(s: &[u8], mut i: usize) -> Option<usize> {
+    i += 1;
+    loop {
+        match *s.get(i)? {
+            b'\\' => i += 2,
+            b'"' => return Some(i + 1),
+            _ => i += 1,
+        }
+    }
+}
+
+/// Returns the end of the json value starting at `i`.
+fn skip_value(s: &[u8], mut i: usize) -> Option<usize> {
+    match *s.get(i)? {
+        b'"' => skip_string(s, i),
+        b'{' | b'[' => {
+            let mut depth = 0;
+            loop {
+                match *s.get(i)? {
+                    b'"' => {
+                        i = skip_string(s, i)?;
+                        continue;
+                    }
+                    b'{' | b'[' => depth += 1,
+                    b'}' | b']' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return Some(i + 1);
+                        }
+                    }
+                    _ => {}
+                }
+                i += 1;
+            }
+        }
+        _ => {
+            while s
+                .get(i)
+                .map_or(false, |&c| !c.is_ascii_whitespace() && !b",}]".contains(&c))
+            {
+                i += 1;
+            }
+            Some(i)
+        }
+    }
+}
+
+/// An option which is recognized but not implemented yet.
+#[derive(Debug, Clone, Copy)]
+pub struct Unsupported(pub &'static str);
+
+impl fmt::Display for Unsupported {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} is not supported yet", self.0)
+    }
+}
+
+impl std::error::Error for Unsupported {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{FileName, FilePathMapping, SourceMap};
+
+    fn select(rc: &str, path: &str) -> Option<Config> {
+        Rc::parse(rc)
+            .expect("failed to parse .swcrc")
+            .config_for_file(Path::new(path))
+            .expect("invalid matcher")
+    }
+
+    #[test]
+    fn single_without_test() {
+        assert!(select(r#"{ "minify": true }"#, "a.js").unwrap().minify);
+    }
+
+    #[test]
+    fn first_matching_entry() {
+        let rc = r#"[
+            { "test": ".*\\.js$", "jsc": { "parser": { "numSep": true } } },
+            { "test": ".*\\.mjs$", "minify": true }
+        ]"#;
+
+        assert!(select(rc, "a.js").unwrap().jsc.parser.num_sep);
+        assert!(select(rc, "a.mjs").unwrap().minify);
+        assert!(select(rc, "a.ts").is_none());
+    }
+
+    #[test]
+    fn plugin_config_span() {
+        let rc = r#"[
+            { "test": ".*\\.mjs$" },
+            { "jsc": { "plugins": [
+                { "path": "a" },
+                { "path": "b", "config": { "x": [1, "y"] } }
+            ] } }
+        ]"#;
+        let cm = SourceMap::new(FilePathMapping::empty());
+        let fm = cm.new_source_file(FileName::Real(".swcrc".into()), rc.into());
+        let c = Rc::parse(rc)
+            .unwrap()
+            .with_source(fm)
+            .config_for_file(Path::new("a.js"))
+            .unwrap()
+            .unwrap();
+        let source = c.source.unwrap();
+        let snippet = |path: &str| cm.span_to_snippet(source.plugin_config_span(1, path).unwrap());
+
+        assert_eq!(snippet("").unwrap(), r#"{ "x": [1, "y"] }"#);
+        assert_eq!(snippet(".x[1]").unwrap(), r#""y""#);
+        assert!(source.plugin_config_span(0, "").is_none());
+    }
+
+    #[test]
+    fn typescript() {
+        let c = select(r#"{ "jsc": { "parser": { "syntax": "typescript" } } }"#, "a.ts").unwrap();
+        match c.jsc.parser.into() {
+            parser::Syntax::Typescript(..) => {}
+            syntax => panic!("expected typescript, got {:?}", syntax),
+        }
+    }
+
+    #[test]
+    fn source_maps() {
+        let c = select(r#"{ "sourceMaps": "both" }"#, "a.js").unwrap();
+        let sm = c.source_maps.unwrap();
+        assert!(sm.file() && sm.inline());
+
+        let c = select(r#"{ "sourceMaps": true }"#, "a.js").unwrap();
+        let sm = c.source_maps.unwrap();
+        assert!(sm.file() && !sm.inline());
+    }
+
+    #[test]
+    fn module() {
+        let c = select(
+            r#"{ "module": { "type": "commonjs", "noInterop": true } }"#,
+            "a.js",
+        )
+        .unwrap();
+        assert_eq!(
+            c.module,
+            Some(ModuleConfig {
+                kind: ModuleKind::CommonJs,
+                strict: false,
+                no_interop: true,
+                lazy: false,
+                module_id: None,
+                global_name: None,
+                globals: Default::default(),
+            })
+        );
+
+        let c = select(
+            r#"{ "module": { "type": "umd", "moduleId": "foo", "globals": { "jquery": "$" } } }"#,
+            "a.js",
+        )
+        .unwrap();
+        let module = c.module.unwrap();
+        assert_eq!(module.kind, ModuleKind::Umd);
+        assert_eq!(module.module_id, Some("foo".into()));
+        assert_eq!(module.globals["jquery"], "$");
+    }
+
+    #[test]
+    fn exclude() {
+        let rc = r#"[
+            { "exclude": { "glob": "**/vendor/**" }, "minify": true },
+            {}
+        ]"#;
+
+        assert!(select(rc, "src/a.js").unwrap().minify);
+        assert!(!select(rc, "src/vendor/a.js").unwrap().minify);
+    }
+
+    #[test]
+    fn plugins() {
+        let c = select(
+            r#"{ "jsc": { "plugins": [
+                { "path": "./a.so" },
+                { "path": "./b.so", "phase": "beforeCompat", "config": { "x": 1 } }
+            ] } }"#,
+            "a.js",
+        )
+        .unwrap();
+        let plugins = &c.jsc.plugins;
+        assert_eq!(plugins[0].phase, None);
+        assert_eq!(plugins[1].phase, Some(PluginPhase::BeforeCompat));
+        assert_eq!(plugins[1].config["x"], 1);
+    }
+
+    #[test]
+    fn minify() {
+        let c = select(
+            r#"{ "minify": true, "jsc": { "minify": {
+                "compress": { "defaults": false, "dead_code": true },
+                "mangle": false
+            } } }"#,
+            "a.js",
+        )
+        .unwrap();
+        let minify = c.jsc.minify.unwrap();
+        assert!(minify.compress.unwrap().dead_code);
+        assert!(minify.mangle.is_none());
+    }
+
+    #[test]
+    fn react() {
+        let c = select(r#"{}"#, "a.jsx").unwrap();
+        assert_eq!(c.jsc.transform.react.options(), Default::default());
+
+        let c = select(
+            r#"{ "jsc": { "transform": { "react": {
+                "pragma": "h", "pragmaFrag": "Fragment", "useBuiltins": true
+            } } } }"#,
+            "a.jsx",
+        )
+        .unwrap();
+        let options = c.jsc.transform.react.options();
+        assert_eq!(options.runtime, react::jsx::Runtime::Classic);
+        assert_eq!(options.pragma, "h");
+        assert_eq!(options.pragma_frag, "Fragment");
+        assert_eq!(options.import_source, "react");
+        assert!(options.use_builtins);
+    }
+}
@@ -0,0 +1,185 @@
+//! Comment directives like `// swc-disable-next-line`.
+//!
+//! The parser drops comments, so directives are read from the source text
+//! between statements. Only comments directly preceding a statement of a
+//! module or a block are recognized.
+
+use crate::{
+    common::{sync::Lrc, BytePos, Fold, FoldWith, SourceMap, Span, Spanned},
+    ecmascript::ast::{BlockStmt, Module, ModuleItem, Stmt},
+};
+
+/// A comment starting with the name of a [DirectiveHandler].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Directive {
+    /// Span of the comment.
+    pub span: Span,
+    pub name: String,
+    /// Rest of the comment, trimmed.
+    pub args: String,
+}
+
+/// Handler for directives, which are applied before the other passes.
+pub trait DirectiveHandler {
+    /// `// <name> args` and `/* <name> args */` are passed to this handler.
+    fn name(&self) -> &str;
+
+    /// Called with the statement following the directive.
+    fn handle(&mut self, directive: &Directive, stmt: Stmt) -> Stmt;
+}
+
+pub fn directives(
+    cm: Lrc<SourceMap>,
+    handlers: Vec<Box<DirectiveHandler>>,
+) -> impl Fold<Module> {
+    Directives { cm, handlers }
+}
+
+struct Directives {
+    cm: Lrc<SourceMap>,
+    handlers: Vec<Box<DirectiveHandler>>,
+}
+
+impl Fold<Module> for Directives {
+    fn fold(&mut self, module: Module) -> Module {
+        if self.handlers.is_empty() {
+            return module;
+        }
+
+        let module = module.fold_children(self);
+
+        let mut pos = module.span.lo();
+        let body = module
+            .body
+            .into_iter()
+            .map(|item| match item {
+                ModuleItem::Stmt(stmt) => {
+                    let span = stmt.span();
+                    let stmt = self.apply(pos, stmt);
+                    pos = span.hi();
+                    ModuleItem::Stmt(stmt)
+                }
+                ModuleItem::ModuleDecl(decl) => {
+                    pos = decl.span().hi();
+                    ModuleItem::ModuleDecl(decl)
+                }
+            })
+            .collect();
+
+        Module { body, ..module }
+    }
+}
+
+impl Fold<BlockStmt> for Directives {
+    fn fold(&mut self, block: BlockStmt) -> BlockStmt {
+        let block = block.fold_children(self);
+
+        let mut pos = block.span.lo();
+        let stmts = block
+            .stmts
+            .into_iter()
+            .map(|stmt| {
+                let span = stmt.span();
+                let stmt = self.apply(pos, stmt);
+                pos = span.hi();
+                stmt
+            })
+            .collect();
+
+        BlockStmt { stmts, ..block }
+    }
+}
+
+impl Directives {
+    /// Applies directives found between `pos` and `stmt`.
+    fn apply(&mut self, pos: BytePos, mut stmt: Stmt) -> Stmt {
+        let span = stmt.span();
+        if span.lo() <= pos {
+            return stmt;
+        }
+        let gap = match self.cm.span_to_snippet(span.with_lo(pos).with_hi(span.lo())) {
+            Ok(gap) => gap,
+            Err(..) => return stmt,
+        };
+
+        for (offset, len, text) in comments(&gap) {
+            let text = text.trim();
+            for h in &mut self.handlers {
+                let args = match strip_name(text, h.name()) {
+                    Some(args) => args,
+                    None => continue,
+                };
+
+                let lo = BytePos(pos.0 + offset as u32);
+                let directive = Directive {
+                    span: span.with_lo(lo).with_hi(lo + BytePos(len as u32)),
+                    name: h.name().into(),
+                    args: args.into(),
+                };
+                stmt = h.handle(&directive, stmt);
+            }
+        }
+
+        stmt
+    }
+}
+
+/// Returns `Some(args)` if `text` is `name` followed by whitespace.
+fn strip_name<'a>(text: &'a str, name: &str) -> Option<&'a str> {
+    if !text.starts_with(name) {
+        return None;
+    }
+
+    let rest = &text[name.len()..];
+    match rest.chars().next() {
+        None => Some(""),
+        Some(c) if c.is_whitespace() => Some(rest.trim()),
+        Some(..) => None,
+    }
+}
+
+/// Returns offsets, lengths and contents of comments in `s`, which contains
+/// only whitespaces, punctuators and comments.
+fn comments(s: &str) -> Vec<(usize, usize, &str)> {
+    let mut comments = vec![];
+    let mut i = 0;
+    while let Some(start) = s[i..].find('/').map(|idx| idx + i) {
+        let rest = &s[start..];
+        let (len, end) = if rest.starts_with("//") {
+            let end = rest.find(|c| c == '\n' || c == '\r').unwrap_or(rest.len());
+            (end, end)
+        } else if rest.starts_with("/*") {
+            match rest[2..].find("*/").map(|idx| idx + 2) {
+                Some(end) => (end + 2, end),
+                None => (rest.len(), rest.len()),
+            }
+        } else {
+            i = start + 1;
+            continue;
+        };
+
+        comments.push((start, len, &rest[2..end]));
+        i = start + len;
+    }
+    comments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_comments() {
+        assert_eq!(
+            comments("; // a\n/* b */ {"),
+            vec![(2, 4, " a"), (7, 7, " b ")]
+        );
+    }
+
+    #[test]
+    fn name() {
+        assert_eq!(strip_name("swc-ignore foo ", "swc-ignore"), Some("foo"));
+        assert_eq!(strip_name("swc-ignore", "swc-ignore"), Some(""));
+        assert_eq!(strip_name("swc-ignored", "swc-ignore"), None);
+    }
+}
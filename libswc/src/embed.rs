@@ -0,0 +1,120 @@
+//! Compilation without global state, for module loaders of javascript
+//! runtimes.
+//!
+//! Unlike [Compiler], [transpile] does not touch the filesystem, stderr or
+//! state shared between calls.
+
+use crate::{
+    common::{
+        errors::{EmitterWriter, Handler, HandlerFlags},
+        sync::Lrc,
+        FileName, FilePathMapping, Globals, SourceMap, GLOBALS,
+    },
+    config::{Config, ParserSyntax},
+    Compiler, TransformOutput,
+};
+use slog::{Discard, Logger};
+use std::{
+    fmt,
+    io::{self, Write},
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+/// Kind of a source file.
+///
+/// Tsx is not listed as the parser can't parse it yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MediaType {
+    JavaScript,
+    Jsx,
+    TypeScript,
+}
+
+impl MediaType {
+    /// Guesses media type from the extension of `path`.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "js" | "mjs" | "cjs" => Some(MediaType::JavaScript),
+            "jsx" => Some(MediaType::Jsx),
+            "ts" | "mts" | "cts" => Some(MediaType::TypeScript),
+            _ => None,
+        }
+    }
+}
+
+/// Compiles `src` of `media_type`.
+///
+/// `specifier` is used as the file name in diagnostics and source maps. Jsx
+/// is enabled for [MediaType::Jsx] and types are stripped for
+/// [MediaType::TypeScript], regardless of `config`.
+pub fn transpile(
+    specifier: &str,
+    media_type: MediaType,
+    src: String,
+    config: &Config,
+) -> Result<TransformOutput, TranspileError> {
+    let mut config = config.clone();
+    match media_type {
+        MediaType::JavaScript => {}
+        MediaType::Jsx => config.jsc.parser.jsx = true,
+        MediaType::TypeScript => config.jsc.parser.syntax = ParserSyntax::Typescript,
+    }
+
+    GLOBALS.set(&Globals::new(), || {
+        let cm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        let buf = Buffer::default();
+        let handler = Handler::with_emitter(
+            box EmitterWriter::new(box buf.clone(), Some(cm.clone()), false, false),
+            HandlerFlags {
+                can_emit_warnings: true,
+                ..Default::default()
+            },
+        );
+        let compiler = Compiler::new(Logger::root(Discard, o!()), cm.clone(), handler);
+
+        let fm = cm.new_source_file(FileName::Custom(specifier.into()), src);
+        compiler
+            .process_js_file(&fm, &config)
+            .map_err(|err| TranspileError {
+                message: err.to_string(),
+                diagnostics: buf.take(),
+            })
+    })
+}
+
+#[derive(Debug)]
+pub struct TranspileError {
+    pub message: String,
+    /// Rendered diagnostics.
+    pub diagnostics: String,
+}
+
+impl fmt::Display for TranspileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.diagnostics, self.message)
+    }
+}
+
+impl std::error::Error for TranspileError {}
+
+/// Collects output of the emitter.
+#[derive(Clone, Default)]
+struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+impl Buffer {
+    fn take(&self) -> String {
+        let buf = std::mem::replace(&mut *self.0.lock().unwrap(), vec![]);
+        String::from_utf8_lossy(&buf).into_owned()
+    }
+}
+
+impl Write for Buffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
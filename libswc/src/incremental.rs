@@ -0,0 +1,130 @@
+//! Recompiles only files affected by changes, for dev servers and watchers.
+
+use crate::{config::Config, Compiler, TransformOutput};
+use std::{
+    collections::HashMap,
+    error::Error,
+    path::{Path, PathBuf},
+};
+
+/// Outputs of previous compilations, updated by [Compiler::recompile].
+#[derive(Debug, Default, Clone)]
+pub struct Outputs {
+    files: HashMap<PathBuf, Cached>,
+}
+
+#[derive(Debug, Clone)]
+struct Cached {
+    output: TransformOutput,
+    /// Hash of the config the file is compiled with.
+    config_hash: u64,
+}
+
+impl Outputs {
+    pub fn get(&self, path: &Path) -> Option<&TransformOutput> {
+        self.files.get(path).map(|cached| &cached.output)
+    }
+
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Returns true if `path` should be compiled again.
+    fn is_affected(&self, path: &Path, config_hash: u64, changed: &[PathBuf]) -> bool {
+        let cached = match self.files.get(path) {
+            Some(cached) => cached,
+            None => return true,
+        };
+
+        cached.config_hash != config_hash
+            || changed
+                .iter()
+                .any(|c| c == path || cached.output.dependencies.contains(c))
+    }
+}
+
+impl Compiler {
+    /// Compiles `files` with configs returned by `config_for`, reusing
+    /// `outputs` of files not affected by `changed` files.
+    ///
+    /// A file is compiled again if it's not compiled yet, if its config is
+    /// changed, or if it or one of its [TransformOutput::dependencies] is in
+    /// `changed`. Outputs of files not in `files` are removed.
+    ///
+    /// Returns files whose code or source map is changed, in the order of
+    /// `files`.
+    pub fn recompile<F>(
+        &self,
+        files: &[PathBuf],
+        changed: &[PathBuf],
+        outputs: &mut Outputs,
+        config_for: F,
+    ) -> Result<Vec<PathBuf>, Box<Error>>
+    where
+        F: Fn(&Path) -> Result<Config, Box<Error>>,
+    {
+        outputs.files.retain(|path, _| files.contains(path));
+
+        let mut updated = vec![];
+        for path in files {
+            let config = config_for(path)?;
+            let config_hash = config.hash_with_plugins();
+            if !outputs.is_affected(path, config_hash, changed) {
+                continue;
+            }
+
+            let fm = self.load_file(path)?;
+            let output = self.process_js_file(&fm, &config)?;
+            let is_updated = match outputs.get(path) {
+                Some(prev) => prev.code != output.code || prev.map != output.map,
+                None => true,
+            };
+
+            outputs.files.insert(
+                path.clone(),
+                Cached {
+                    output,
+                    config_hash,
+                },
+            );
+            if is_updated {
+                updated.push(path.clone());
+            }
+        }
+
+        Ok(updated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn affected() {
+        let mut outputs = Outputs::default();
+        outputs.files.insert(
+            "a.js".into(),
+            Cached {
+                output: TransformOutput {
+                    code: String::new(),
+                    map: None,
+                    dependencies: vec![".swcrc".into()],
+                },
+                config_hash: 1,
+            },
+        );
+        let a = Path::new("a.js");
+
+        assert!(!outputs.is_affected(a, 1, &[]));
+        assert!(!outputs.is_affected(a, 1, &["b.js".into()]));
+        assert!(outputs.is_affected(a, 1, &["a.js".into()]));
+        assert!(outputs.is_affected(a, 1, &[".swcrc".into()]));
+        assert!(outputs.is_affected(a, 2, &[]));
+        assert!(outputs.is_affected(Path::new("b.js"), 1, &[]));
+    }
+}
@@ -1,6 +1,6 @@
 #![feature(box_syntax)]
+#![feature(specialization)]
 
-extern crate rayon;
 #[macro_use]
 pub extern crate slog;
 pub extern crate sourcemap;
@@ -9,24 +9,68 @@ pub extern crate swc_common as common;
 pub extern crate swc_ecmascript as ecmascript;
 
 use self::{
-    common::{errors::Handler, sync::Lrc, SourceMap},
+    common::{
+        errors::Handler, noop, span_remapper, sync::Lrc, when, Comments, FileName, Fold,
+        FoldWith, SourceFile, SourceMap, DUMMY_SP,
+    },
+    config::{Config, ParserSyntax, PluginPhase, Rc},
+    directive::DirectiveHandler,
     ecmascript::{
-        ast::Module,
+        ast::{Module, Script},
         codegen::{self, Emitter},
-        parser::{Parser, Session as ParseSess, SourceFileInput},
+        lint::{Linter, Registry, Summary},
+        minifier::{minifier, Stats},
+        parser::{Parser, Session as ParseSess, SourceFileInput, Syntax},
+        transforms::{
+            compat::{
+                self,
+                helpers::{Helpers, InjectHelpers},
+            },
+            fixer, hygiene, react, resolver,
+            simplify::dce::{self, dce_with_config},
+            typescript,
+        },
     },
 };
+use serde::{Deserialize, Serialize};
 use slog::Logger;
 use sourcemap::SourceMapBuilder;
 use std::{
     io::{self, Write},
-    path::Path,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
 };
 
+pub mod babel;
+pub mod binary;
+pub mod cache;
+pub mod config;
+pub mod directive;
+pub mod embed;
+pub mod incremental;
+#[cfg(feature = "plugin")]
+pub mod plugin;
+
+/// Code and source map generated by [Compiler::print].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransformOutput {
+    pub code: String,
+    /// Content of the `.map` file, if requested.
+    pub map: Option<String>,
+    /// Files other than the input read while compiling, like `.swcrc` and
+    /// plugins. Bundlers should recompile the input when one of them changes.
+    pub dependencies: Vec<PathBuf>,
+}
+
 pub struct Compiler {
     cm: Lrc<SourceMap>,
     logger: Logger,
     handler: Handler,
+    /// Comments of parsed files, used to find `/*#__PURE__*/` annotations.
+    comments: Comments,
+    /// Shared by compilers created by [Compiler::with_handler].
+    #[cfg(feature = "plugin")]
+    plugins: Arc<plugin::Loader>,
 }
 
 impl Compiler {
@@ -35,23 +79,354 @@ impl Compiler {
             cm,
             logger,
             handler,
+            comments: Default::default(),
+            #[cfg(feature = "plugin")]
+            plugins: Default::default(),
+        }
+    }
+
+    /// Creates a compiler which reports diagnostics to `handler`, sharing the
+    /// source map and loaded plugins with `self`.
+    ///
+    /// This allows collecting diagnostics of each file while compiling files
+    /// in parallel. Comments are not shared, so a file should be parsed and
+    /// transformed by the same compiler.
+    pub fn with_handler(&self, handler: Handler) -> Compiler {
+        Compiler {
+            cm: self.cm.clone(),
+            logger: self.logger.clone(),
+            handler,
+            comments: Default::default(),
+            #[cfg(feature = "plugin")]
+            plugins: self.plugins.clone(),
         }
     }
 
-    /// TODO
-    pub fn parse_js(&self, path: &Path) -> Result<Module, ()> {
-        let fm = self.cm.load_file(path).expect("failed to load file");
+    /// Creates the transform pipeline configured by `config`.
+    ///
+    /// The pipeline borrows comments of files parsed by `self`.
+    pub fn pass(&self, config: &Config) -> Result<Box<Fold<Module> + '_>, Box<std::error::Error>> {
+        self.pass_with_stats(config, None)
+    }
+
+    /// Same as [Compiler::pass], but the minifier records what it does into
+    /// `stats`.
+    pub fn pass_with_stats(
+        &self,
+        config: &Config,
+        stats: Option<Arc<Mutex<Stats>>>,
+    ) -> Result<Box<Fold<Module> + '_>, Box<std::error::Error>> {
+        let helpers = Arc::new(Helpers::default());
+
+        let targets = config.targets()?;
+        let module_pass = config.module_pass(&helpers)?;
+        let mut plugins = self.plugins(config, &helpers)?;
+
+        let pass: Box<Fold<Module> + '_> = box when(
+            config.jsc.parser.syntax == ParserSyntax::Typescript,
+            typescript::strip(),
+        )
+        // Marks bindings, so helper variables introduced by later passes are
+        // renamed by `hygiene` only if they collide.
+        .then(resolver())
+        .then(directive::directives(
+            self.cm.clone(),
+            std::mem::replace(&mut plugins.directive_handlers, vec![]),
+        ))
+        .then(when(
+            !config.jsc.transform.globals.is_empty(),
+            config.jsc.transform.inline_globals(),
+        ))
+        .then(plugins.take(PluginPhase::BeforeCompat))
+        .then(when(
+            config.jsc.parser.jsx,
+            react::jsx(helpers.clone(), config.jsc.transform.react.options()),
+        ))
+        .then(compat::preset_env(&helpers, targets))
+        .then(plugins.take(PluginPhase::AfterCompat))
+        .then(module_pass)
+        .then(plugins.take(PluginPhase::AfterModule))
+        .then(InjectHelpers {
+            cm: self.cm.clone(),
+            helpers: helpers.clone(),
+            external: config.jsc.external_helpers,
+        })
+        .then(when(
+            config.jsc.transform.optimize,
+            dce_with_config(dce::Config { top_level: false }, Some(&self.comments)),
+        ))
+        .then(hygiene())
+        .then(fixer());
+
+        let pass: Box<Fold<Module> + '_> = if config.minify {
+            let options = config.jsc.minify.clone().unwrap_or_default();
+            // The minifier doesn't know comments, so pure calls are dropped
+            // beforehand.
+            let (unused, top_level) = options
+                .compress
+                .as_ref()
+                .map_or((false, false), |c| (c.unused, c.top_level));
+            let pure_calls = dce_with_config(dce::Config { top_level }, Some(&self.comments));
+
+            let mut minifier = minifier(options);
+            if let Some(stats) = stats {
+                minifier = minifier.with_stats(stats);
+            }
+            box pass.then(when(unused, pure_calls)).then(minifier)
+        } else {
+            pass
+        };
+
+        Ok(box pass.then(plugins.take(PluginPhase::BeforeEmit)))
+    }
+
+    /// Creates passes of plugins.
+    #[cfg(feature = "plugin")]
+    fn plugins(
+        &self,
+        config: &Config,
+        helpers: &Arc<Helpers>,
+    ) -> Result<PluginPasses<'_>, Box<std::error::Error>> {
+        let mut passes = PluginPasses::default();
+        for (i, p) in config.jsc.plugins.iter().enumerate() {
+            let plugin = self
+                .plugins
+                .create(&self.cm, &self.handler, p, helpers)
+                .map_err(|err| {
+                    if let Some(err) = err.downcast_ref::<plugin::schema::InvalidConfig>() {
+                        for e in &err.errors {
+                            let msg = format!(
+                                "invalid config for plugin {}: `jsc.plugins[{}].config{}`: {}",
+                                err.plugin, i, e.path, e.message
+                            );
+                            let span = config
+                                .source
+                                .as_ref()
+                                .and_then(|s| s.plugin_config_span(i, &e.path));
+                            match span {
+                                Some(span) => self.handler.error(&msg).span(span).emit(),
+                                None => self.handler.error(&msg).emit(),
+                            }
+                        }
+                    }
+                    err
+                })?;
+            passes.passes.push((plugin.phase, plugin.pass));
+            passes.directive_handlers.extend(plugin.directive_handlers);
+        }
+        Ok(passes)
+    }
+
+    #[cfg(not(feature = "plugin"))]
+    fn plugins(
+        &self,
+        config: &Config,
+        _: &Arc<Helpers>,
+    ) -> Result<PluginPasses<'_>, Box<std::error::Error>> {
+        if config.jsc.plugins.is_empty() {
+            Ok(PluginPasses::default())
+        } else {
+            Err(config::Unsupported("plugins").into())
+        }
+    }
+
+    /// Parses, transforms and prints `fm`.
+    pub fn process_js_file(
+        &self,
+        fm: &SourceFile,
+        config: &Config,
+    ) -> Result<TransformOutput, Box<std::error::Error>> {
+        let module = self
+            .parse_js_file(fm, config.jsc.parser.into())
+            .map_err(|()| format!("failed to parse {}", fm.name))?;
+        self.process_js_module(module, config)
+    }
+
+    /// Transforms and prints an already parsed module.
+    pub fn process_js_module(
+        &self,
+        module: Module,
+        config: &Config,
+    ) -> Result<TransformOutput, Box<std::error::Error>> {
+        let module = self.pass(config)?.fold(module);
+
+        let cfg = codegen::Config {
+            minify: config.minify,
+            ..Default::default()
+        };
+        let mut output = self.print(&module, cfg, config)?;
+        output
+            .dependencies
+            .extend(config.jsc.plugins.iter().map(|p| PathBuf::from(&p.path)));
+        Ok(output)
+    }
+
+    /// Parses `fm` and serializes the module as json, in the shape of ESTree.
+    pub fn parse_js_json(
+        &self,
+        fm: &SourceFile,
+        config: &Config,
+    ) -> Result<String, Box<std::error::Error>> {
+        let module = self
+            .parse_js_file(fm, config.jsc.parser.into())
+            .map_err(|()| format!("failed to parse {}", fm.name))?;
+        Ok(serde_json::to_string(&module)?)
+    }
+
+    /// Compiles `json`, a module of `fm` serialized by
+    /// [Compiler::parse_js_json] and possibly modified by a javascript
+    /// plugin.
+    ///
+    /// Spans which are not in `fm` are replaced with dummy spans, because
+    /// source maps can't be generated for them.
+    pub fn process_js_json(
+        &self,
+        fm: &SourceFile,
+        json: &str,
+        config: &Config,
+    ) -> Result<TransformOutput, Box<std::error::Error>> {
+        let module: Module = serde_json::from_str(json)
+            .map_err(|err| format!("invalid ast for {}: {}", fm.name, err))?;
+
+        let (start, end) = (fm.start_pos, fm.end_pos);
+        let module = module.fold_with(&mut span_remapper(|span| {
+            if start <= span.lo() && span.hi() <= end {
+                span
+            } else {
+                DUMMY_SP
+            }
+        }));
+        self.process_js_module(module, config)
+    }
+
+    /// Prints `json`, a module serialized by [Compiler::parse_js_json], without
+    /// transforming it. Only output options like `minify` are used.
+    ///
+    /// Spans are replaced with dummy spans, as there's no source file which
+    /// source maps could point to.
+    pub fn print_js_json(
+        &self,
+        json: &str,
+        config: &Config,
+    ) -> Result<TransformOutput, Box<std::error::Error>> {
+        let module: Module =
+            serde_json::from_str(json).map_err(|err| format!("invalid ast: {}", err))?;
+        let module = module.fold_with(&mut span_remapper(|_| DUMMY_SP));
+
+        let cfg = codegen::Config {
+            minify: config.minify,
+            ..Default::default()
+        };
+        Ok(self.print(&module, cfg, config)?)
+    }
+
+    /// Compiles `fm` with the entry of `.swcrc` at `rc` applicable to it.
+    pub fn process_js_file_with_rc(
+        &self,
+        fm: &SourceFile,
+        rc: &Path,
+    ) -> Result<TransformOutput, Box<std::error::Error>> {
+        let config = self.read_config(fm, rc)?;
+
+        let mut output = self.process_js_file(fm, &config)?;
+        output.dependencies.push(rc.to_path_buf());
+        Ok(output)
+    }
+
+    /// Returns the entry of `.swcrc` at `rc` applicable to `fm`.
+    pub fn read_config(
+        &self,
+        fm: &SourceFile,
+        rc: &Path,
+    ) -> Result<Config, Box<std::error::Error>> {
+        let path = match fm.name {
+            FileName::Real(ref path) => path.clone(),
+            ref name => PathBuf::from(name.to_string()),
+        };
+        Ok(self
+            .read_rc(rc)?
+            .config_for_file(&path)?
+            .unwrap_or_default())
+    }
+
+    /// Loads `.swcrc` from `path`.
+    ///
+    /// The file is added to the source map, so errors caused by its content
+    /// can point at it.
+    pub fn read_rc(&self, path: &Path) -> Result<Rc, Box<std::error::Error>> {
+        let fm = self.cm.load_file(path)?;
+        let rc = Rc::parse(fm.src.as_ref().map_or("", |s| &**s))?;
+        Ok(rc.with_source(fm))
+    }
+
+    pub fn load_file(&self, path: &Path) -> io::Result<Lrc<SourceFile>> {
+        self.cm.load_file(path)
+    }
+
+    /// Loads and parses `path` as an es module.
+    pub fn parse_js(&self, path: &Path, syntax: Syntax) -> Result<Module, Box<std::error::Error>> {
+        let fm = self.load_file(path)?;
+        Ok(self
+            .parse_js_file(&fm, syntax)
+            .map_err(|()| format!("failed to parse {}", fm.name))?)
+    }
+
+    /// Reports problems of `module` found by builtin lint rules, with
+    /// severities configured by `config.lint`.
+    ///
+    /// Nothing is reported if `config.lint` is `None`.
+    pub fn lint(
+        &self,
+        module: &Module,
+        config: &Config,
+    ) -> Result<Summary, Box<std::error::Error>> {
+        match config.lint {
+            Some(ref lint) => {
+                let linter = Linter::new(Registry::builtin(), lint)?;
+                Ok(linter.lint(module, &self.handler))
+            }
+            None => Ok(Summary::default()),
+        }
+    }
 
-        let logger = self
-            .logger
-            .new(o!("input" => format!("{}", path.display())));
+    /// Loads and parses `path` as a script.
+    pub fn parse_js_script(
+        &self,
+        path: &Path,
+        syntax: Syntax,
+    ) -> Result<Script, Box<std::error::Error>> {
+        let fm = self.load_file(path)?;
+        Ok(self
+            .parse_js_script_file(&fm, syntax)
+            .map_err(|()| format!("failed to parse {}", fm.name))?)
+    }
+
+    /// Parses an already loaded file as an es module.
+    pub fn parse_js_file(&self, fm: &SourceFile, syntax: Syntax) -> Result<Module, ()> {
+        self.parse_with(fm, syntax, |p| p.parse_module())
+    }
+
+    /// Parses an already loaded file as a script.
+    pub fn parse_js_script_file(&self, fm: &SourceFile, syntax: Syntax) -> Result<Script, ()> {
+        self.parse_with(fm, syntax, |p| p.parse_script())
+    }
+
+    fn parse_with<F, Ret>(&self, fm: &SourceFile, syntax: Syntax, op: F) -> Result<Ret, ()>
+    where
+        F: for<'a> FnOnce(&mut Parser<'a, SourceFileInput<'a>>) -> Result<Ret, ()>,
+    {
+        let logger = self.logger.new(o!("input" => format!("{}", fm.name)));
         {
             let session = ParseSess {
                 handler: &self.handler,
                 logger: &logger,
-                cfg: Default::default(),
             };
-            Parser::new(session, SourceFileInput::from(&*fm)).parse_module()
+            op(&mut Parser::with_comments(
+                session,
+                syntax,
+                SourceFileInput::from(fm),
+                &self.comments,
+            ))
         }
     }
 
@@ -62,26 +437,228 @@ impl Compiler {
         wr: &mut Write,
     ) -> io::Result<()> {
         let mut src_map_builder = SourceMapBuilder::new(None);
-        {
-            let handlers = box MyHandlers;
-            let mut emitter = Emitter {
-                cfg,
-                cm: self.cm.clone(),
-                wr: box swc_ecmascript::codegen::text_writer::JsWriter::new(
-                    self.cm.clone(),
-                    "\n",
-                    wr,
-                    &mut src_map_builder,
-                ),
-                handlers,
-                pos_of_leading_comments: Default::default(),
-            };
+        self.emit_module_with(module, cfg, wr, &mut src_map_builder)
+    }
+
+    /// Emits `module` and generates source map according to `config`.
+    pub fn print(
+        &self,
+        module: &Module,
+        cfg: codegen::Config,
+        config: &Config,
+    ) -> io::Result<TransformOutput> {
+        let mut src_map_builder =
+            SourceMapBuilder::new(config.source_map_target.as_ref().map(|s| &**s));
+        src_map_builder.set_source_root(config.source_root.as_ref().map(|s| &**s));
+
+        let mut buf = vec![];
+        self.emit_module_with(module, cfg, &mut buf, &mut src_map_builder)?;
+        let mut code = String::from_utf8(buf).expect("emitter generated invalid utf8");
+
+        let source_maps = match config.source_maps {
+            Some(sm) if sm.file() || sm.inline() => sm,
+            _ => {
+                return Ok(TransformOutput {
+                    code,
+                    map: None,
+                    dependencies: vec![],
+                });
+            }
+        };
 
-            emitter.emit_module(&module)
+        let mut map = src_map_builder.into_sourcemap();
+        if config.sources_content.unwrap_or(true) {
+            for idx in 0..map.get_source_count() {
+                let name = map.get_source(idx).map(String::from);
+                let content = name.and_then(|name| {
+                    self.cm
+                        .files()
+                        .iter()
+                        .find(|fm| fm.name.to_string() == name)
+                        .and_then(|fm| fm.src.clone())
+                });
+                if let Some(content) = content {
+                    map.set_source_contents(idx, Some(&**content));
+                }
+            }
         }
+
+        let mut json = vec![];
+        map.to_writer(&mut json)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+        let json = String::from_utf8(json).expect("source map is not utf8");
+
+        if source_maps.inline() {
+            code.push_str("\n//# sourceMappingURL=data:application/json;base64,");
+            code.push_str(&base64::encode(json.as_bytes()));
+        }
+
+        Ok(TransformOutput {
+            code,
+            map: if source_maps.file() { Some(json) } else { None },
+            dependencies: vec![],
+        })
+    }
+
+    fn emit_module_with(
+        &self,
+        module: &Module,
+        cfg: codegen::Config,
+        wr: &mut Write,
+        src_map_builder: &mut SourceMapBuilder,
+    ) -> io::Result<()> {
+        use swc_ecmascript::codegen::text_writer::{
+            omit_trailing_newline, omit_trailing_semi, JsWriter, WriteJs,
+        };
+
+        let handlers = box MyHandlers;
+        let mut wr: Box<WriteJs + '_> =
+            box JsWriter::new(self.cm.clone(), "\n", wr, src_map_builder).with_indent(cfg.indent);
+        if cfg.omit_trailing_newline {
+            wr = box omit_trailing_newline(wr);
+        }
+        if cfg.omit_trailing_semi || cfg.minify {
+            wr = box omit_trailing_semi(wr);
+        }
+
+        let mut emitter = Emitter {
+            cfg,
+            cm: self.cm.clone(),
+            wr,
+            handlers,
+            comments: Some(&self.comments),
+        };
+
+        emitter.emit_module(&module)
+    }
+}
+
+#[derive(Default)]
+struct PluginPasses<'a> {
+    /// Passes with the phase they run at.
+    passes: Vec<(PluginPhase, Box<Fold<Module> + 'a>)>,
+    directive_handlers: Vec<Box<DirectiveHandler>>,
+}
+
+impl<'a> PluginPasses<'a> {
+    /// Chains passes which run at `phase`, keeping the order of `.swcrc`.
+    fn take(&mut self, phase: PluginPhase) -> Box<Fold<Module> + 'a> {
+        let mut pass: Box<Fold<Module> + 'a> = box noop();
+        let mut rest = vec![];
+        for (p, plugin_pass) in self.passes.drain(..) {
+            if p == phase {
+                pass = box pass.then(plugin_pass);
+            } else {
+                rest.push((p, plugin_pass));
+            }
+        }
+        self.passes = rest;
+
+        pass
     }
 }
 
 struct MyHandlers;
 
 impl swc_ecmascript::codegen::Handlers for MyHandlers {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{errors::ColorConfig, FilePathMapping, Globals, GLOBALS};
+
+    /// Runs `op` with a compiler which prints errors to stderr.
+    pub(crate) fn with_compiler<F, T>(op: F) -> T
+    where
+        F: FnOnce(&Compiler, &Lrc<SourceMap>) -> T,
+    {
+        GLOBALS.set(&Globals::new(), || {
+            let cm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let handler =
+                Handler::with_tty_emitter(ColorConfig::Never, true, false, Some(cm.clone()));
+            let compiler = Compiler::new(Logger::root(slog::Discard, o!()), cm.clone(), handler);
+
+            op(&compiler, &cm)
+        })
+    }
+
+    fn compile(src: &str, config: &Config) -> String {
+        with_compiler(|compiler, cm| {
+            let fm = cm.new_source_file(FileName::Custom("test.js".into()), src.into());
+            let output = compiler
+                .process_js_file(&fm, config)
+                .expect("failed to compile");
+            output.code.trim().to_string()
+        })
+    }
+
+    #[test]
+    fn pure_annotations() {
+        let src = "var a = /*#__PURE__*/ f(); /*#__PURE__*/ g(h()); i();";
+
+        let mut config = Config::default();
+        config.jsc.transform.optimize = true;
+        let code = compile(src, &config);
+        // Top-level bindings are kept, as the file may be a script.
+        assert!(code.contains("f()"), "{}", code);
+        assert!(!code.contains("g("), "{}", code);
+        assert!(code.contains("h()"), "{}", code);
+
+        let config = Config {
+            minify: true,
+            ..Default::default()
+        };
+        let code = compile(src, &config);
+        assert!(!code.contains("g("), "{}", code);
+        assert!(code.contains("h()"), "{}", code);
+    }
+
+    #[test]
+    fn lazy_imports_are_resolved() {
+        let config: Config =
+            serde_json::from_str(r#"{ "module": { "type": "commonjs", "lazy": true } }"#).unwrap();
+        let code = compile(
+            "import foo from 'foo'; function f(foo) { return foo; } foo();",
+            &config,
+        );
+        assert!(code.contains("return foo;"), "{}", code);
+        assert!(code.contains("(0, _foo().default)()"), "{}", code);
+    }
+
+    #[test]
+    fn lint() {
+        with_compiler(|compiler, cm| {
+            let fm = cm.new_source_file(FileName::Custom("test.js".into()), "debugger;".into());
+            let module = compiler.parse_js_file(&fm, Default::default()).unwrap();
+
+            let summary = compiler.lint(&module, &Config::default()).unwrap();
+            assert_eq!(summary, Summary::default());
+
+            let config: Config =
+                serde_json::from_str(r#"{ "lint": { "rules": { "no-debugger": "warn" } } }"#)
+                    .unwrap();
+            let summary = compiler.lint(&module, &config).unwrap();
+            assert_eq!(summary.warnings, 1);
+
+            let config: Config =
+                serde_json::from_str(r#"{ "lint": { "rules": { "no-such-rule": "warn" } } }"#)
+                    .unwrap();
+            assert!(compiler.lint(&module, &config).is_err());
+        })
+    }
+
+    #[test]
+    fn print_json() {
+        with_compiler(|compiler, cm| {
+            let config = Config::default();
+            let fm = cm.new_source_file(FileName::Custom("test.js".into()), "a(b);".into());
+            let json = compiler.parse_js_json(&fm, &config).unwrap();
+
+            let output = compiler.print_js_json(&json, &config).unwrap();
+            assert_eq!(output.code.trim(), "a(b);");
+            assert!(output.map.is_none());
+
+            assert!(compiler.print_js_json("{}", &config).is_err());
+        })
+    }
+}
@@ -0,0 +1,300 @@
+//! Native plugins loaded from dynamic libraries.
+//!
+//! A plugin is a `cdylib` crate depending on `libswc`, which implements
+//! [Plugin] and registers it using [declare_plugin!].
+//!
+//! ```ignore
+//! use libswc::{declare_plugin, plugin::Plugin};
+//!
+//! #[derive(Default)]
+//! struct MyPlugin;
+//!
+//! impl Plugin for MyPlugin {
+//!     fn name(&self) -> &str {
+//!         "my-plugin"
+//!     }
+//!
+//!     fn create(&self, config: &Value, helpers: &Arc<Helpers>) -> Result<Box<Fold<Module>>, Box<Error>> {
+//!         Ok(box my_pass(config))
+//!     }
+//! }
+//!
+//! declare_plugin!(MyPlugin::default);
+//! ```
+//!
+//! Rust does not have a stable abi, so a plugin must be compiled with the
+//! same compiler and the same version of swc as the host.
+//!
+//! Plugins with the `.wasm` extension are sandboxed instead. See [wasm].
+
+use crate::{
+    common::{errors::Handler, sync::Lrc, Fold, SourceMap},
+    config::{PluginConfig, PluginPhase},
+    directive::{Directive, DirectiveHandler},
+    ecmascript::{
+        ast::{Module, Stmt},
+        transforms::compat::helpers::Helpers,
+    },
+};
+use libloading::Library;
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+pub use self::wasm::WasmPlugin;
+use self::schema::InvalidConfig;
+
+pub mod schema;
+pub mod wasm;
+
+/// Bumped whenever [Plugin] or the ast changes incompatibly.
+pub const PLUGIN_API_VERSION: u32 = 1;
+
+pub trait Plugin: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Json schema of the config, which is validated before calling
+    /// [Plugin::create]. See [schema] for supported keywords.
+    fn config_schema(&self) -> Option<Value> {
+        None
+    }
+
+    /// When the plugin runs. `phase` of `.swcrc` takes precedence.
+    fn phase(&self) -> PluginPhase {
+        PluginPhase::AfterCompat
+    }
+
+    /// Handlers of comment directives. See [crate::directive].
+    fn directive_handlers(&self, _config: &Value) -> Vec<Box<DirectiveHandler>> {
+        vec![]
+    }
+
+    /// Creates a pass from `config` of `.swcrc`.
+    fn create(
+        &self,
+        config: &Value,
+        helpers: &Arc<Helpers>,
+    ) -> Result<Box<Fold<Module>>, Box<Error>>;
+}
+
+/// Exports symbols required by the plugin loader.
+///
+/// `$ctor` is a function returning the plugin.
+#[macro_export]
+macro_rules! declare_plugin {
+    ($ctor:path) => {
+        #[no_mangle]
+        pub static swc_plugin_api_version: u32 = $crate::plugin::PLUGIN_API_VERSION;
+
+        #[no_mangle]
+        pub extern "C" fn swc_plugin_create() -> *mut Box<$crate::plugin::Plugin> {
+            let plugin: Box<$crate::plugin::Plugin> = Box::new($ctor());
+            Box::into_raw(Box::new(plugin))
+        }
+    };
+}
+
+/// A plugin with the library it's loaded from.
+pub struct LoadedPlugin {
+    // Declared before `_lib` as it must be dropped before unloading.
+    plugin: Box<Plugin>,
+    _lib: Library,
+}
+
+impl LoadedPlugin {
+    pub fn load(path: &Path) -> Result<Self, PluginError> {
+        let err = |kind| PluginError {
+            path: path.to_path_buf(),
+            kind,
+        };
+
+        let lib = Library::new(path).map_err(|e| err(ErrorKind::Load(e)))?;
+
+        let plugin = unsafe {
+            let version = lib
+                .get::<*const u32>(b"swc_plugin_api_version\0")
+                .map_err(|e| err(ErrorKind::Load(e)))?;
+            if **version != PLUGIN_API_VERSION {
+                return Err(err(ErrorKind::Version(**version)));
+            }
+
+            let create = lib
+                .get::<unsafe extern "C" fn() -> *mut Box<Plugin>>(b"swc_plugin_create\0")
+                .map_err(|e| err(ErrorKind::Load(e)))?;
+            *Box::from_raw(create())
+        };
+
+        Ok(LoadedPlugin { plugin, _lib: lib })
+    }
+
+    pub fn plugin(&self) -> &Plugin {
+        &*self.plugin
+    }
+}
+
+/// Loads each plugin only once.
+#[derive(Default)]
+pub struct Loader {
+    cache: Mutex<HashMap<PathBuf, Arc<LoadedPlugin>>>,
+    wasm: Mutex<HashMap<PathBuf, Arc<WasmPlugin>>>,
+}
+
+impl Loader {
+    pub fn load(&self, path: &Path) -> Result<Arc<LoadedPlugin>, PluginError> {
+        let mut cache = self.cache.lock().unwrap();
+        if let Some(plugin) = cache.get(path) {
+            return Ok(plugin.clone());
+        }
+
+        let plugin = Arc::new(LoadedPlugin::load(path)?);
+        cache.insert(path.to_path_buf(), plugin.clone());
+        Ok(plugin)
+    }
+
+    pub fn load_wasm(&self, path: &Path) -> Result<Arc<WasmPlugin>, PluginError> {
+        let mut cache = self.wasm.lock().unwrap();
+        if let Some(plugin) = cache.get(path) {
+            return Ok(plugin.clone());
+        }
+
+        let plugin = Arc::new(WasmPlugin::load(path)?);
+        cache.insert(path.to_path_buf(), plugin.clone());
+        Ok(plugin)
+    }
+
+    /// Creates the pass configured by `config`.
+    ///
+    /// Wasm plugins report their failures to `handler`.
+    pub fn create<'a>(
+        &self,
+        cm: &Lrc<SourceMap>,
+        handler: &'a Handler,
+        config: &PluginConfig,
+        helpers: &Arc<Helpers>,
+    ) -> Result<PluginInstance<'a>, Box<Error>> {
+        let path = Path::new(&config.path);
+        if path.extension().map_or(false, |ext| ext == "wasm") {
+            let plugin = self.load_wasm(path)?;
+            return Ok(PluginInstance {
+                phase: config.phase.unwrap_or(plugin.phase()),
+                pass: wasm::pass(plugin, cm, handler, config),
+                directive_handlers: vec![],
+            });
+        }
+
+        let plugin = self.load(path)?;
+        if let Some(schema) = plugin.plugin().config_schema() {
+            let errors = schema::validate(&schema, &config.config);
+            if !errors.is_empty() {
+                return Err(box InvalidConfig {
+                    plugin: plugin.plugin().name().into(),
+                    errors,
+                });
+            }
+        }
+        let pass = plugin.plugin().create(&config.config, helpers)?;
+        let directive_handlers = plugin
+            .plugin()
+            .directive_handlers(&config.config)
+            .into_iter()
+            .map(|handler| -> Box<DirectiveHandler> {
+                box PluginDirectiveHandler {
+                    handler,
+                    _plugin: plugin.clone(),
+                }
+            })
+            .collect();
+
+        Ok(PluginInstance {
+            phase: config.phase.unwrap_or(plugin.plugin().phase()),
+            pass: box PluginPass {
+                pass,
+                _plugin: plugin,
+            },
+            directive_handlers,
+        })
+    }
+}
+
+/// What a plugin adds to the pipeline.
+pub struct PluginInstance<'a> {
+    pub phase: PluginPhase,
+    pub pass: Box<Fold<Module> + 'a>,
+    /// Applied before any pass.
+    pub directive_handlers: Vec<Box<DirectiveHandler>>,
+}
+
+/// Keeps the library loaded while the pass is alive.
+struct PluginPass {
+    pass: Box<Fold<Module>>,
+    _plugin: Arc<LoadedPlugin>,
+}
+
+impl Fold<Module> for PluginPass {
+    fn fold(&mut self, module: Module) -> Module {
+        self.pass.fold(module)
+    }
+}
+
+struct PluginDirectiveHandler {
+    handler: Box<DirectiveHandler>,
+    _plugin: Arc<LoadedPlugin>,
+}
+
+impl DirectiveHandler for PluginDirectiveHandler {
+    fn name(&self) -> &str {
+        self.handler.name()
+    }
+
+    fn handle(&mut self, directive: &Directive, stmt: Stmt) -> Stmt {
+        self.handler.handle(directive, stmt)
+    }
+}
+
+#[derive(Debug)]
+pub struct PluginError {
+    pub path: PathBuf,
+    pub kind: ErrorKind,
+}
+
+#[derive(Debug)]
+pub enum ErrorKind {
+    Load(std::io::Error),
+    /// Invalid wasm module.
+    Wasm(wasmi::Error),
+    /// Plugin is built for another version of the plugin api.
+    Version(u32),
+}
+
+impl fmt::Display for PluginError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            ErrorKind::Load(ref err) => write!(
+                f,
+                "failed to load plugin {}: {}",
+                self.path.display(),
+                err
+            ),
+            ErrorKind::Wasm(ref err) => write!(
+                f,
+                "failed to load wasm plugin {}: {}",
+                self.path.display(),
+                err
+            ),
+            ErrorKind::Version(v) => write!(
+                f,
+                "plugin {} is built for plugin api v{} but swc requires v{}",
+                self.path.display(),
+                v,
+                PLUGIN_API_VERSION
+            ),
+        }
+    }
+}
+
+impl Error for PluginError {}
@@ -0,0 +1,181 @@
+//! Validation of plugin configs.
+//!
+//! Only a subset of json schema is supported: `type`, `enum`, `properties`,
+//! `required`, `additionalProperties` and `items`. Other keywords are
+//! ignored.
+
+use serde_json::Value;
+use std::{error::Error, fmt};
+
+/// A config which does not match the schema of a plugin.
+#[derive(Debug)]
+pub struct InvalidConfig {
+    pub plugin: String,
+    pub errors: Vec<SchemaError>,
+}
+
+impl fmt::Display for InvalidConfig {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid config for plugin {}", self.plugin)?;
+        for err in &self.errors {
+            write!(f, "\n  {}", err)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for InvalidConfig {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaError {
+    /// Path to the invalid value, like `.foo[0]`. Empty for the root.
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.path, self.message)
+        }
+    }
+}
+
+pub fn validate(schema: &Value, value: &Value) -> Vec<SchemaError> {
+    let mut errors = vec![];
+    validate_at(schema, value, &mut String::new(), &mut errors);
+    errors
+}
+
+fn validate_at(schema: &Value, value: &Value, path: &mut String, errors: &mut Vec<SchemaError>) {
+    let mut error = |path: &str, message: String| {
+        errors.push(SchemaError {
+            path: path.into(),
+            message,
+        })
+    };
+
+    if let Some(ty) = schema.get("type") {
+        let matches = match *ty {
+            Value::String(ref ty) => type_matches(ty, value),
+            Value::Array(ref types) => types
+                .iter()
+                .filter_map(Value::as_str)
+                .any(|ty| type_matches(ty, value)),
+            _ => true,
+        };
+        if !matches {
+            error(path, format!("expected {}, got {}", ty, type_of(value)));
+            return;
+        }
+    }
+
+    if let Some(variants) = schema.get("enum").and_then(Value::as_array) {
+        if !variants.contains(value) {
+            error(path, format!("expected one of {}", Value::Array(variants.clone())));
+        }
+    }
+
+    if let Value::Object(ref obj) = *value {
+        let props = schema.get("properties").and_then(Value::as_object);
+
+        if let Some(required) = schema.get("required").and_then(Value::as_array) {
+            for key in required.iter().filter_map(Value::as_str) {
+                if !obj.contains_key(key) {
+                    error(path, format!("missing field `{}`", key));
+                }
+            }
+        }
+
+        for (key, v) in obj {
+            let len = path.len();
+            path.push('.');
+            path.push_str(key);
+
+            match props.and_then(|props| props.get(key)) {
+                Some(schema) => validate_at(schema, v, path, errors),
+                None => match schema.get("additionalProperties") {
+                    Some(&Value::Bool(false)) => errors.push(SchemaError {
+                        path: path.clone(),
+                        message: "unknown field".into(),
+                    }),
+                    Some(schema @ &Value::Object(..)) => validate_at(schema, v, path, errors),
+                    _ => {}
+                },
+            }
+
+            path.truncate(len);
+        }
+    }
+
+    if let (&Value::Array(ref values), Some(items)) = (value, schema.get("items")) {
+        for (i, v) in values.iter().enumerate() {
+            let len = path.len();
+            path.push_str(&format!("[{}]", i));
+            validate_at(items, v, path, errors);
+            path.truncate(len);
+        }
+    }
+}
+
+fn type_matches(ty: &str, value: &Value) -> bool {
+    match ty {
+        "integer" => value.is_i64() || value.is_u64(),
+        _ => type_of(value) == ty,
+    }
+}
+
+fn type_of(value: &Value) -> &'static str {
+    match *value {
+        Value::Null => "null",
+        Value::Bool(..) => "boolean",
+        Value::Number(..) => "number",
+        Value::String(..) => "string",
+        Value::Array(..) => "array",
+        Value::Object(..) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "mode": { "enum": ["loose", "strict"] },
+                "names": { "type": "array", "items": { "type": "string" } },
+            },
+            "required": ["mode"],
+            "additionalProperties": false,
+        })
+    }
+
+    #[test]
+    fn valid() {
+        let config = json!({ "mode": "loose", "names": ["a", "b"] });
+        assert_eq!(validate(&schema(), &config), vec![]);
+    }
+
+    #[test]
+    fn invalid() {
+        let config = json!({ "names": ["a", 1], "foo": true });
+        let errors: Vec<_> = validate(&schema(), &config)
+            .into_iter()
+            .map(|e| e.to_string())
+            .collect();
+
+        assert_eq!(
+            errors,
+            vec![
+                "missing field `mode`",
+                ".foo: unknown field",
+                ".names[1]: expected \"string\", got number",
+            ]
+        );
+    }
+}
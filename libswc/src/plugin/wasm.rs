@@ -0,0 +1,414 @@
+//! Plugins compiled to WebAssembly.
+//!
+//! Wasm plugins run in an interpreter without any import, so they cannot
+//! access anything other than their own memory. Each call may execute at most
+//! `fuel` instructions of `jsc.plugins[i]` ([DEFAULT_FUEL] by default), which
+//! is counted by an `env.gas` import injected on load. A plugin module exports
+//!
+//!  - `memory`
+//!  - `swc_plugin_api_version() -> i32`
+//!  - `swc_alloc(len: i32) -> i32`, which allocates `len` bytes
+//!  - `swc_transform(code_ptr: i32, code_len: i32, config_ptr: i32,
+//!    config_len: i32) -> i64`
+//!
+//! and optionally `swc_plugin_phase() -> i32`, which returns the index of the
+//! [PluginPhase] the plugin runs at.
+//!
+//! `swc_transform` receives the printed module and the config as json, and
+//! returns `(ptr << 32) | len` of a buffer whose first byte is `0` for
+//! success or `1` for failure, followed by the transformed code or the error
+//! message. As the module is reparsed, spans of the resulting module point to
+//! the output of the plugin.
+//!
+//! Plugins which also export `swc_transform_ast(ast_ptr: i32, ast_len: i32,
+//! config_ptr: i32, config_len: i32) -> i64` receive and return the module
+//! encoded by [binary](crate::binary) instead, so nothing is printed or
+//! reparsed and spans are kept. Spans which don't point into a loaded file
+//! are replaced with dummy spans.
+//!
+//! Failures of a plugin, including traps, exhausted fuel and invalid outputs,
+//! are reported to the handler and the module is left unchanged.
+
+use super::{ErrorKind, PluginError, PLUGIN_API_VERSION};
+use crate::{
+    binary::{self, DecodeError},
+    common::{
+        errors::Handler,
+        span_remapper,
+        sync::Lrc,
+        FileName, Fold, FoldWith, SourceMap, DUMMY_SP,
+    },
+    config::{PluginConfig, PluginPhase},
+    ecmascript::{
+        ast::Module,
+        codegen::{text_writer::JsWriter, Emitter},
+        parser::{Parser, Session, SourceFileInput, Syntax},
+    },
+    MyHandlers,
+};
+use slog::{Discard, Logger};
+use sourcemap::SourceMapBuilder;
+use std::{error::Error, fmt, path::Path, sync::Arc};
+use wasmi::{
+    Externals, FuncInstance, FuncRef, HostError, ImportsBuilder, MemoryRef, ModuleImportResolver,
+    ModuleInstance, ModuleRef, RuntimeArgs, RuntimeValue, Signature, Trap, TrapKind, ValueType,
+};
+
+/// Instructions a plugin may execute for a file, unless configured otherwise.
+pub const DEFAULT_FUEL: u64 = 1_000_000_000;
+
+/// Instructions used by the exports called on load.
+const LOAD_FUEL: u64 = 1_000_000;
+
+/// A compiled wasm plugin.
+pub struct WasmPlugin {
+    name: String,
+    module: wasmi::Module,
+    phase: PluginPhase,
+    /// True if the plugin exports `swc_transform_ast`.
+    ast: bool,
+}
+
+impl WasmPlugin {
+    pub fn load(path: &Path) -> Result<Self, PluginError> {
+        let err = |kind| PluginError {
+            path: path.to_path_buf(),
+            kind,
+        };
+
+        let buf = std::fs::read(path).map_err(|e| err(ErrorKind::Load(e)))?;
+        let module = parity_wasm::deserialize_buffer(&buf)
+            .map_err(|e| err(ErrorKind::Wasm(wasmi::Error::Validation(e.to_string()))))?;
+        let module = pwasm_utils::inject_gas_counter(module, &Default::default()).map_err(|_| {
+            err(ErrorKind::Wasm(wasmi::Error::Validation(
+                "failed to inject the gas counter".into(),
+            )))
+        })?;
+        let module =
+            wasmi::Module::from_parity_wasm_module(module).map_err(|e| err(ErrorKind::Wasm(e)))?;
+
+        let mut plugin = WasmPlugin {
+            name: path.display().to_string(),
+            module,
+            phase: PluginPhase::AfterCompat,
+            ast: false,
+        };
+
+        let instance = plugin
+            .instantiate()
+            .map_err(|e| err(ErrorKind::Wasm(e)))?;
+        let mut fuel = Fuel(LOAD_FUEL);
+        let version = call_i32(&instance, &mut fuel, "swc_plugin_api_version", &[])
+            .map_err(|e| err(ErrorKind::Wasm(e)))?;
+        if version as u32 != PLUGIN_API_VERSION {
+            return Err(err(ErrorKind::Version(version as _)));
+        }
+
+        if instance.export_by_name("swc_plugin_phase").is_some() {
+            let phase = call_i32(&instance, &mut fuel, "swc_plugin_phase", &[])
+                .map_err(|e| err(ErrorKind::Wasm(e)))?;
+            plugin.phase = match phase {
+                0 => PluginPhase::BeforeCompat,
+                1 => PluginPhase::AfterCompat,
+                2 => PluginPhase::AfterModule,
+                3 => PluginPhase::BeforeEmit,
+                _ => {
+                    return Err(err(ErrorKind::Wasm(wasmi::Error::Function(format!(
+                        "invalid phase: {}",
+                        phase
+                    )))));
+                }
+            };
+        }
+        plugin.ast = instance.export_by_name("swc_transform_ast").is_some();
+
+        Ok(plugin)
+    }
+
+    pub fn phase(&self) -> PluginPhase {
+        self.phase
+    }
+
+    /// Every file gets a fresh instance, so plugins cannot leak state between
+    /// files.
+    fn instantiate(&self) -> Result<ModuleRef, wasmi::Error> {
+        let imports = ImportsBuilder::new().with_resolver("env", &EnvResolver);
+        Ok(ModuleInstance::new(&self.module, &imports)?.assert_no_start())
+    }
+
+    /// Calls `export` with `input` and the config, and returns the output of
+    /// the plugin.
+    ///
+    /// Allocations and the transform share `fuel` instructions.
+    fn run(
+        &self,
+        export: &str,
+        input: &[u8],
+        config: &str,
+        fuel: u64,
+    ) -> Result<Vec<u8>, WasmPluginError> {
+        let mut fuel = Fuel(fuel);
+        let res = self.run_with(&mut fuel, export, input, config);
+        match res {
+            Err(WasmPluginError::Wasm(..)) if fuel.0 == 0 => Err(WasmPluginError::OutOfFuel),
+            res => res,
+        }
+    }
+
+    fn run_with(
+        &self,
+        fuel: &mut Fuel,
+        export: &str,
+        input: &[u8],
+        config: &str,
+    ) -> Result<Vec<u8>, WasmPluginError> {
+        let instance = self.instantiate()?;
+        let memory = instance
+            .export_by_name("memory")
+            .and_then(|e| e.as_memory().cloned())
+            .ok_or_else(|| WasmPluginError::Abi("memory is not exported"))?;
+
+        let input = write(&instance, fuel, &memory, input)?;
+        let config = write(&instance, fuel, &memory, config.as_bytes())?;
+
+        let ret = match instance.invoke_export(
+            export,
+            &[input.0, input.1, config.0, config.1],
+            fuel,
+        )? {
+            Some(RuntimeValue::I64(v)) => v as u64,
+            _ => return Err(WasmPluginError::Abi("transform functions should return i64")),
+        };
+
+        let buf = memory.get((ret >> 32) as u32, (ret & 0xffff_ffff) as usize)?;
+        match buf.split_first() {
+            Some((&0, output)) => Ok(output.to_vec()),
+            Some((_, msg)) => Err(WasmPluginError::Failed(
+                String::from_utf8_lossy(msg).into_owned(),
+            )),
+            None => Err(WasmPluginError::Abi("empty result")),
+        }
+    }
+}
+
+/// Index of `env.gas`, which is the only host function.
+const GAS_INDEX: usize = 0;
+
+/// Provides `env.gas`, which is called by the injected gas counter.
+struct EnvResolver;
+
+impl ModuleImportResolver for EnvResolver {
+    fn resolve_func(&self, name: &str, _: &Signature) -> Result<FuncRef, wasmi::Error> {
+        match name {
+            "gas" => Ok(FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32][..], None),
+                GAS_INDEX,
+            )),
+            _ => Err(wasmi::Error::Instantiation(format!(
+                "unknown import: env.{}",
+                name
+            ))),
+        }
+    }
+}
+
+/// Instructions left for a plugin.
+struct Fuel(u64);
+
+impl Externals for Fuel {
+    fn invoke_index(
+        &mut self,
+        index: usize,
+        args: RuntimeArgs,
+    ) -> Result<Option<RuntimeValue>, Trap> {
+        debug_assert_eq!(index, GAS_INDEX);
+
+        let used = u64::from(args.nth_checked::<u32>(0)?);
+        if used > self.0 {
+            self.0 = 0;
+            return Err(Trap::new(TrapKind::Host(box OutOfFuel)));
+        }
+        self.0 -= used;
+        Ok(None)
+    }
+}
+
+#[derive(Debug)]
+struct OutOfFuel;
+
+impl fmt::Display for OutOfFuel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "out of fuel")
+    }
+}
+
+impl HostError for OutOfFuel {}
+
+fn call_i32(
+    instance: &ModuleRef,
+    fuel: &mut Fuel,
+    name: &str,
+    args: &[RuntimeValue],
+) -> Result<i32, wasmi::Error> {
+    match instance.invoke_export(name, args, fuel)? {
+        Some(RuntimeValue::I32(v)) => Ok(v),
+        _ => Err(wasmi::Error::Function(format!("{} should return i32", name))),
+    }
+}
+
+/// Copies `data` into the memory of the plugin and returns `(ptr, len)`.
+fn write(
+    instance: &ModuleRef,
+    fuel: &mut Fuel,
+    memory: &MemoryRef,
+    data: &[u8],
+) -> Result<(RuntimeValue, RuntimeValue), wasmi::Error> {
+    let len = RuntimeValue::I32(data.len() as i32);
+    let ptr = call_i32(instance, fuel, "swc_alloc", &[len])?;
+    memory.set(ptr as u32, data)?;
+
+    Ok((RuntimeValue::I32(ptr), len))
+}
+
+/// Creates a pass which runs a wasm plugin, reporting failures to `handler`.
+pub(super) fn pass<'a>(
+    plugin: Arc<WasmPlugin>,
+    cm: &Lrc<SourceMap>,
+    handler: &'a Handler,
+    config: &PluginConfig,
+) -> Box<Fold<Module> + 'a> {
+    box WasmPass {
+        plugin,
+        cm: cm.clone(),
+        handler,
+        config: config.config.to_string(),
+        fuel: config.fuel.unwrap_or(DEFAULT_FUEL),
+    }
+}
+
+struct WasmPass<'a> {
+    plugin: Arc<WasmPlugin>,
+    cm: Lrc<SourceMap>,
+    handler: &'a Handler,
+    config: String,
+    fuel: u64,
+}
+
+impl Fold<Module> for WasmPass<'_> {
+    fn fold(&mut self, module: Module) -> Module {
+        let res = if self.plugin.ast {
+            self.plugin
+                .run(
+                    "swc_transform_ast",
+                    &binary::encode(&module),
+                    &self.config,
+                    self.fuel,
+                )
+                .and_then(|buf| binary::decode(&buf).map_err(WasmPluginError::Decode))
+                .map(|module| self.remap(module))
+        } else {
+            let code = self.print(&module);
+            self.plugin
+                .run("swc_transform", code.as_bytes(), &self.config, self.fuel)
+                .and_then(|code| self.parse(String::from_utf8_lossy(&code).into_owned()))
+        };
+
+        match res {
+            Ok(module) => module,
+            Err(err) => {
+                self.handler
+                    .error(&format!("wasm plugin {} failed: {}", self.plugin.name, err))
+                    .emit();
+                module
+            }
+        }
+    }
+}
+
+impl WasmPass<'_> {
+    /// Plugins may return arbitrary spans, which would break source maps and
+    /// diagnostics.
+    fn remap(&self, module: Module) -> Module {
+        let files = self.cm.files();
+        module.fold_with(&mut span_remapper(|span| {
+            if files
+                .iter()
+                .any(|fm| fm.start_pos <= span.lo() && span.hi() <= fm.end_pos)
+            {
+                span
+            } else {
+                DUMMY_SP
+            }
+        }))
+    }
+
+    fn print(&self, module: &Module) -> String {
+        let mut buf = vec![];
+        {
+            let mut src_map_builder = SourceMapBuilder::new(None);
+            let mut emitter = Emitter {
+                cfg: Default::default(),
+                cm: self.cm.clone(),
+                wr: box JsWriter::new(self.cm.clone(), "\n", &mut buf, &mut src_map_builder),
+                handlers: box MyHandlers,
+                comments: None,
+            };
+            emitter
+                .emit_module(module)
+                .expect("failed to write to a vector");
+        }
+
+        String::from_utf8(buf).expect("emitter generated invalid utf8")
+    }
+
+    /// Syntax errors are reported to the handler.
+    fn parse(&self, code: String) -> Result<Module, WasmPluginError> {
+        let fm = self
+            .cm
+            .new_source_file(FileName::Custom(self.plugin.name.clone()), code);
+        let logger = Logger::root(Discard, o!());
+
+        let session = Session {
+            handler: self.handler,
+            logger: &logger,
+        };
+        Parser::new(session, Syntax::default(), SourceFileInput::from(&*fm))
+            .parse_module()
+            .map_err(|()| WasmPluginError::InvalidCode)
+    }
+}
+
+#[derive(Debug)]
+enum WasmPluginError {
+    Wasm(wasmi::Error),
+    Abi(&'static str),
+    /// The plugin returned an invalid module.
+    Decode(DecodeError),
+    /// The plugin returned code which can't be parsed.
+    InvalidCode,
+    /// The plugin executed more instructions than allowed.
+    OutOfFuel,
+    /// Error reported by the plugin.
+    Failed(String),
+}
+
+impl From<wasmi::Error> for WasmPluginError {
+    fn from(err: wasmi::Error) -> Self {
+        WasmPluginError::Wasm(err)
+    }
+}
+
+impl fmt::Display for WasmPluginError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            WasmPluginError::Wasm(ref err) => write!(f, "{}", err),
+            WasmPluginError::Abi(msg) => write!(f, "invalid abi: {}", msg),
+            WasmPluginError::Decode(ref err) => write!(f, "{}", err),
+            WasmPluginError::InvalidCode => write!(f, "generated invalid code"),
+            WasmPluginError::OutOfFuel => write!(f, "executed too many instructions"),
+            WasmPluginError::Failed(ref msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl Error for WasmPluginError {}
@@ -0,0 +1,58 @@
+use swc_macros_common::prelude::*;
+
+pub fn derive(input: DeriveInput) -> ItemImpl {
+    let variants = Binder::new_from(&input).variants();
+    let has_many_variants = variants.len() > 1;
+
+    let mut arms = TokenStream::new();
+    for v in variants {
+        let (l_pat, l_bindings) = v.bind("l_", Some(def_site()), None);
+        let (r_pat, r_bindings) = v.bind("r_", Some(def_site()), None);
+
+        let mut body = Quote::new(def_site::<Span>());
+        if l_bindings.is_empty() {
+            body = body.quote_with(smart_quote!(Vars {}, { true }));
+        }
+        for (i, (l, r)) in l_bindings.iter().zip(r_bindings.iter()).enumerate() {
+            if i != 0 {
+                body = body.quote_with(smart_quote!(Vars {}, { && }));
+            }
+            body = body.quote_with(smart_quote!(Vars { l, r }, {
+                swc_common::EqIgnoreSpan::eq_ignore_span(l, r)
+            }));
+        }
+        let body = body.parse::<Expr>();
+
+        let arm: TokenStream = Quote::new(def_site::<Span>())
+            .quote_with(smart_quote!(Vars { l_pat, r_pat, body }, {
+                (&l_pat, &r_pat) => body,
+            }))
+            .into();
+        arm.to_tokens(&mut arms);
+    }
+    if has_many_variants {
+        let arm: TokenStream = Quote::new(def_site::<Span>())
+            .quote_with(smart_quote!(Vars {}, { _ => false, }))
+            .into();
+        arm.to_tokens(&mut arms);
+    }
+
+    Quote::new(def_site::<Span>())
+        .quote_with(smart_quote!(
+            Vars {
+                Type: &input.ident,
+                arms,
+            },
+            {
+                impl swc_common::EqIgnoreSpan for Type {
+                    fn eq_ignore_span(&self, other: &Self) -> bool {
+                        match (self, other) {
+                            arms
+                        }
+                    }
+                }
+            }
+        ))
+        .parse::<ItemImpl>()
+        .with_generics(input.generics)
+}
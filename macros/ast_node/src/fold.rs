@@ -1,17 +1,5 @@
-use darling::FromField;
 use swc_macros_common::prelude::*;
-
-#[derive(Debug, FromField)]
-#[darling(attributes(fold))]
-struct FieldAttrs {
-    ///
-    #[darling(default)]
-    pub ignore: bool,
-
-    /// Should we add bound for the field's type?
-    #[darling(default)]
-    pub bound: bool,
-}
+use util::{normalize_type_for_bound, should_skip_field};
 
 pub fn derive(input: DeriveInput) -> ItemImpl {
     let mut derive_generics = Derive::new(&input);
@@ -107,7 +95,7 @@ pub fn derive(input: DeriveInput) -> ItemImpl {
                 Fields::Unit => box Quote::new(def_site::<Span>())
                     .quote_with(smart_quote!(Vars { Name: qual_name }, {
                         {
-                            return Name;
+                            Name
                         }
                     }))
                     .parse(),
@@ -119,7 +107,7 @@ pub fn derive(input: DeriveInput) -> ItemImpl {
                         },
                         {
                             {
-                                return Name { fields };
+                                Name { fields }
                             }
                         }
                     ))
@@ -154,6 +142,26 @@ pub fn derive(input: DeriveInput) -> ItemImpl {
         arms,
     });
 
+    // `#[fold(hooks)]` calls `FoldHooks` of the folder before and after
+    // folding fields.
+    let has_hooks = input
+        .attrs
+        .iter()
+        .any(|attr| is_attr_name(attr, "fold") && attr.tts.to_string() == "( hooks )");
+    let body: Expr = if has_hooks {
+        Quote::new(def_site::<Span>())
+            .quote_with(smart_quote!(Vars { body }, {
+                {
+                    swc_common::FoldHooks::<Self>::enter(_f, &self);
+                    let node = body;
+                    swc_common::FoldHooks::<Self>::exit(_f, node)
+                }
+            }))
+            .parse()
+    } else {
+        body
+    };
+
     let item = Quote::new(def_site::<Span>())
         .quote_with(smart_quote!(
             Vars {
@@ -175,55 +183,3 @@ pub fn derive(input: DeriveInput) -> ItemImpl {
 
     item
 }
-
-fn should_skip_field(field: &Field) -> bool {
-    let attrs = FieldAttrs::from_field(field).expect("#[derive(Fold)]: failed to parse attribute");
-    if attrs.ignore {
-        return true;
-    }
-
-    let ty_str = field.ty.dump().to_string();
-    match &*ty_str {
-        "bool" | "usize" | "u128" | "u64" | "u32" | "u16" | "u8" | "isize" | "i128" | "i64"
-        | "i32" | "i16" | "i8" | "f64" | "f32" | "String" => return true,
-        _ => {}
-    }
-
-    false
-}
-
-fn normalize_type_for_bound(ty: Type) -> Type {
-    use syn::fold::{self, Fold};
-
-    struct Norm;
-    impl Fold for Norm {
-        fn fold_path(&mut self, path: Path) -> Path {
-            if path.segments.len() == 1 {
-                let seg = &path.segments[0];
-                if seg.ident != "Box" && seg.ident != "Option" && seg.ident != "Vec" {
-                    return path.clone();
-                }
-
-                if let PathArguments::AngleBracketed(ref args) = seg.arguments {
-                    if args.args.len() == 1 {
-                        if let GenericArgument::Type(ref ty) =
-                            *args.args.last().unwrap().into_value()
-                        {
-                            match *ty {
-                                Type::Path(TypePath { ref path, .. }) => {
-                                    return self.fold_path(path.clone())
-                                }
-                                _ => {}
-                            }
-                        }
-                    }
-                }
-            }
-
-            fold::fold_path(self, path)
-        }
-    }
-
-    let out = Norm.fold_type(ty);
-    out
-}
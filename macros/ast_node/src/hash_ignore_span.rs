@@ -0,0 +1,60 @@
+use swc_macros_common::prelude::*;
+
+pub fn derive(input: DeriveInput) -> ItemImpl {
+    let is_enum = match input.data {
+        Data::Enum(..) => true,
+        _ => false,
+    };
+
+    let mut arms = TokenStream::new();
+    for v in Binder::new_from(&input).variants() {
+        let (pat, bindings) = v.bind("_", Some(def_site()), None);
+
+        let mut body = Quote::new(def_site::<Span>());
+        for binding in bindings {
+            body = body.quote_with(smart_quote!(Vars { binding }, {
+                swc_common::HashIgnoreSpan::hash_ignore_span(binding, state);
+            }));
+        }
+
+        let body: TokenStream = body.into();
+        let arm: TokenStream = Quote::new(def_site::<Span>())
+            .quote_with(smart_quote!(Vars { pat, body }, {
+                pat => { body }
+            }))
+            .into();
+        arm.to_tokens(&mut arms);
+    }
+
+    // Variants without fields are distinguished by this.
+    let discriminant: TokenStream = if is_enum {
+        Quote::new(def_site::<Span>())
+            .quote_with(smart_quote!(Vars {}, {
+                ::std::hash::Hash::hash(&::std::mem::discriminant(self), state);
+            }))
+            .into()
+    } else {
+        TokenStream::new()
+    };
+
+    Quote::new(def_site::<Span>())
+        .quote_with(smart_quote!(
+            Vars {
+                Type: &input.ident,
+                discriminant,
+                arms,
+            },
+            {
+                impl swc_common::HashIgnoreSpan for Type {
+                    fn hash_ignore_span<__H: ::std::hash::Hasher>(&self, state: &mut __H) {
+                        discriminant
+                        match *self {
+                            arms
+                        }
+                    }
+                }
+            }
+        ))
+        .parse::<ItemImpl>()
+        .with_generics(input.generics)
+}
@@ -12,32 +12,78 @@ extern crate syn;
 
 use swc_macros_common::prelude::*;
 
+mod eq_ignore_span;
 mod fold;
+mod fold_path;
 mod from_variant;
+mod hash_ignore_span;
+mod map_fold;
+mod serde;
 mod spanned;
+mod stable_fold;
+mod try_fold;
+mod util;
 mod visit;
+mod visit_ctl;
+mod visit_mut;
+mod visit_path;
 
 #[proc_macro_derive(Fold, attributes(fold))]
 pub fn derive_fold(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse::<DeriveInput>(input).expect("failed to parse input as DeriveInput");
 
     let fold_item = self::fold::derive(input.clone());
-    let visit_item = self::visit::derive(input);
+    let try_fold_item = self::try_fold::derive(input.clone());
+    let visit_item = self::visit::derive(input.clone());
+    let visit_mut_item = self::visit_mut::derive(input.clone());
+    let fold_path_item = self::fold_path::derive(input.clone());
+    let visit_path_item = self::visit_path::derive(input.clone());
+    let visit_ctl_item = self::visit_ctl::derive(input.clone());
+    let stable_fold_item = self::stable_fold::derive(input);
     let item = Quote::new(def_site::<Span>()).quote_with(smart_quote!(
         Vars {
             fold_item: fold_item,
+            try_fold_item: try_fold_item,
             visit_item: visit_item,
+            visit_mut_item: visit_mut_item,
+            fold_path_item: fold_path_item,
+            visit_path_item: visit_path_item,
+            visit_ctl_item: visit_ctl_item,
+            stable_fold_item: stable_fold_item,
         },
         {
             extern crate swc_common;
             fold_item
+            try_fold_item
             visit_item
+            visit_mut_item
+            fold_path_item
+            visit_path_item
+            visit_ctl_item
+            stable_fold_item
         }
     ));
 
     print("derive(Fold)", item.dump())
 }
 
+/// Derives `MapFoldWith<Self, F>` for source types in
+/// `#[map_fold(from = "..")]` or `#[map_fold(bridge = "..")]`.
+#[proc_macro_derive(MapFold, attributes(map_fold))]
+pub fn derive_map_fold(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse::<DeriveInput>(input).expect("failed to parse input as DeriveInput");
+
+    let items =
+        self::map_fold::derive(input)
+            .into_iter()
+            .fold(TokenStream::new(), |mut t, item| {
+                item.to_tokens(&mut t);
+                t
+            });
+
+    print_item("derive(MapFold)", items.dump())
+}
+
 #[proc_macro_derive(Spanned, attributes(span))]
 pub fn derive_spanned(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse::<DeriveInput>(input).expect("failed to parse input as DeriveInput");
@@ -47,6 +93,26 @@ pub fn derive_spanned(input: proc_macro::TokenStream) -> proc_macro::TokenStream
     print_item("derive(Spanned)", item.dump())
 }
 
+/// Derives `EqIgnoreSpan`, which compares all fields.
+#[proc_macro_derive(EqIgnoreSpan)]
+pub fn derive_eq_ignore_span(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse::<DeriveInput>(input).expect("failed to parse input as DeriveInput");
+
+    let item = self::eq_ignore_span::derive(input);
+
+    print_item("derive(EqIgnoreSpan)", item.dump())
+}
+
+/// Derives `HashIgnoreSpan`, which hashes all fields.
+#[proc_macro_derive(HashIgnoreSpan)]
+pub fn derive_hash_ignore_span(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse::<DeriveInput>(input).expect("failed to parse input as DeriveInput");
+
+    let item = self::hash_ignore_span::derive(input);
+
+    print_item("derive(HashIgnoreSpan)", item.dump())
+}
+
 #[proc_macro_derive(FromVariant)]
 pub fn derive_from_variant(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse::<DeriveInput>(input).expect("failed to parse input as DeriveInput");
@@ -63,33 +129,46 @@ pub fn derive_from_variant(input: proc_macro::TokenStream) -> proc_macro::TokenS
 }
 
 /// Alias for
-/// `#[derive(Spanned, Fold, Clone, Debug, PartialEq)]` for a struct and
-/// `#[derive(Spanned, Fold, Clone, Debug, PartialEq, FromVariant)]` for an
-/// enum.
+/// `#[derive(Spanned, Fold, EqIgnoreSpan, HashIgnoreSpan, Clone, Debug,
+/// PartialEq)]` for a struct and the same with `FromVariant` for an enum,
+/// with `#[fold(hooks)]`.
+///
+/// With the `serde` feature of the crate, this also derives `Serialize` and
+/// `Deserialize`. Structs with named fields have a `type` field, which is the
+/// argument like `#[ast_node("Identifier")]` or the name of the struct, and
+/// enums are untagged. See `serde.rs` for details.
 #[proc_macro_attribute]
 pub fn ast_node(
     args: proc_macro::TokenStream,
     input: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
-    if !args.is_empty() {
-        panic!("#[ast_node] takes no arguments");
-    }
+    let type_name = if args.is_empty() {
+        None
+    } else {
+        Some(parse::<LitStr>(args).expect("#[ast_node] takes a type name like \"Identifier\""))
+    };
 
-    let input: DeriveInput = parse(input).expect("failed to parse input as a DeriveInput");
+    let mut input: DeriveInput = parse(input).expect("failed to parse input as a DeriveInput");
+    let de_impl = self::serde::expand(&mut input, type_name);
 
     // we should use call_site
     let mut item = Quote::new(Span::call_site());
     item = match input.data {
         Data::Enum(..) => item.quote_with(smart_quote!(Vars { input }, {
             #[derive(::swc_common::FromVariant, ::swc_common::Spanned,
-            ::swc_common::Fold, Clone, Debug, PartialEq)]
+            ::swc_common::Fold, ::swc_common::EqIgnoreSpan, ::swc_common::HashIgnoreSpan,
+            Clone, Debug, PartialEq)]
+            #[fold(hooks)]
             input
         })),
         _ => item.quote_with(smart_quote!(Vars { input }, {
-            #[derive(::swc_common::Spanned, ::swc_common::Fold, Clone, Debug, PartialEq)]
+            #[derive(::swc_common::Spanned, ::swc_common::Fold, ::swc_common::EqIgnoreSpan,
+            ::swc_common::HashIgnoreSpan, Clone, Debug, PartialEq)]
+            #[fold(hooks)]
             input
         })),
     };
+    item = item.quote_with(smart_quote!(Vars { de_impl }, { de_impl }));
 
     print("ast_node", item)
 }
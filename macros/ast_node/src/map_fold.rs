@@ -0,0 +1,251 @@
+use swc_macros_common::prelude::*;
+use util::is_primitive;
+
+/// Type a node is converted from.
+enum Source {
+    /// `#[map_fold(from = "path::Node")]`
+    Fields(Path),
+    /// `#[map_fold(bridge = "path::Node")]`
+    Bridge(Path),
+}
+
+pub fn derive(input: DeriveInput) -> Vec<ItemImpl> {
+    if !input.generics.params.is_empty() {
+        panic!("#[derive(MapFold)] does not support generic types");
+    }
+
+    parse_sources(&input)
+        .into_iter()
+        .map(|source| match source {
+            Source::Fields(path) => derive_fields(&input, &path),
+            Source::Bridge(path) => Quote::new(def_site::<Span>())
+                .quote_with(smart_quote!(
+                    Vars {
+                        Type: &input.ident,
+                        Source: &path,
+                    },
+                    {
+                        impl<__Fold> swc_common::MapFoldWith<Type, __Fold> for Source {
+                            fn map_fold_children(self, _f: &mut __Fold) -> Type {
+                                ::std::convert::From::from(self)
+                            }
+                        }
+                    }
+                ))
+                .parse(),
+        })
+        .collect()
+}
+
+fn parse_sources(input: &DeriveInput) -> Vec<Source> {
+    let sources: Vec<_> = input
+        .attrs
+        .iter()
+        .filter(|attr| is_attr_name(attr, "map_fold"))
+        .flat_map(|attr| match attr.interpret_meta() {
+            Some(Meta::List(MetaList { nested, .. })) => nested.into_iter().collect::<Vec<_>>(),
+            _ => panic!("#[derive(MapFold)]: failed to parse {}", attr.tts),
+        })
+        .map(|meta| match meta {
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                ident,
+                lit: Lit::Str(s),
+                ..
+            })) => {
+                let path = parse_str::<Path>(&s.value()).unwrap_or_else(|err| {
+                    panic!("failed to parse {} as a path: {}", s.value(), err)
+                });
+                if ident == "from" {
+                    Source::Fields(path)
+                } else if ident == "bridge" {
+                    Source::Bridge(path)
+                } else {
+                    panic!("#[derive(MapFold)]: unknown attribute `{}`", ident)
+                }
+            }
+            _ => panic!(
+                "#[derive(MapFold)]: expected #[map_fold(from = \"..\")] or \
+                 #[map_fold(bridge = \"..\")]"
+            ),
+        })
+        .collect();
+
+    if sources.is_empty() {
+        panic!("#[derive(MapFold)] requires #[map_fold(from = \"..\")]");
+    }
+
+    sources
+}
+
+/// Converts fields of `source` with the same names as fields of `input`.
+fn derive_fields(input: &DeriveInput, source: &Path) -> ItemImpl {
+    let is_struct = match input.data {
+        Data::Struct(..) => true,
+        _ => false,
+    };
+
+    let arms = Binder::new_from(input)
+        .variants()
+        .into_iter()
+        .map(|v| {
+            // Qualified path of variant.
+            let qual_name = v.qual_path();
+
+            let (mut pat, bindings) = v.bind("_", None, None);
+
+            // Match on the source type instead.
+            let source_path = if is_struct {
+                source.clone()
+            } else {
+                let mut path = source.clone();
+                path.segments.push(v.variant_name().clone().into());
+                path
+            };
+            match pat {
+                Pat::Struct(ref mut pat) => {
+                    pat.path = source_path;
+                    // Fields which the target does not have are dropped.
+                    if is_struct {
+                        if !pat.fields.empty_or_trailing() {
+                            pat.fields.push_punct(def_site());
+                        }
+                        pat.dot2_token = Some(def_site());
+                    }
+                }
+                Pat::TupleStruct(ref mut pat) => pat.path = source_path,
+                Pat::Path(ref mut pat) => pat.path = source_path,
+                _ => unreachable!("Binder::bind() returned {}", pat.dump()),
+            }
+
+            let fields: Punctuated<FieldValue, token::Comma> = bindings
+                .into_iter()
+                .map(|binding| {
+                    // This closure will not be called for unit-like struct.
+
+                    let field_name: TokenStream = binding
+                        .field()
+                        .ident
+                        .as_ref()
+                        .map(|s| s.dump())
+                        .unwrap_or_else(|| {
+                            // Use index
+
+                            // call_site is important for unexported tuple fields.
+                            Index {
+                                index: binding.idx() as _,
+                                span: call_site(),
+                            }
+                            .dump()
+                        });
+
+                    // Fields of primitive types are moved as is.
+                    let value = match is_primitive(&binding.field().ty) {
+                        true => Quote::new(def_site::<Span>()).quote_with(smart_quote!(
+                            Vars {
+                                binded_field: binding.name(),
+                            },
+                            { binded_field }
+                        )),
+                        false => Quote::new(def_site::<Span>()).quote_with(smart_quote!(
+                            Vars {
+                                FieldType: &binding.field().ty,
+                                binded_field: binding.name(),
+                            },
+                            {
+                                swc_common::MapFoldWith::<FieldType, __Fold>::map_fold_with(
+                                    binded_field,
+                                    _f,
+                                )
+                            }
+                        )),
+                    };
+
+                    let v = Quote::new(def_site::<Span>())
+                        .quote_with(smart_quote!(
+                            Vars { field_name, value },
+                            (field_name: value)
+                        ))
+                        .parse::<FieldValue>();
+                    FieldValue {
+                        attrs: binding
+                            .field()
+                            .attrs
+                            .iter()
+                            .filter(|attr| is_attr_name(attr, "cfg"))
+                            .cloned()
+                            .collect(),
+                        ..v
+                    }
+                })
+                .map(|t| Element::Punctuated(t, def_site()))
+                .collect();
+
+            let body = match *v.data() {
+                // Handle unit-like structs separately
+                Fields::Unit => box Quote::new(def_site::<Span>())
+                    .quote_with(smart_quote!(Vars { Name: qual_name }, {
+                        {
+                            return Name;
+                        }
+                    }))
+                    .parse(),
+                _ => box Quote::new(def_site::<Span>())
+                    .quote_with(smart_quote!(
+                        Vars {
+                            Name: qual_name,
+                            fields,
+                        },
+                        {
+                            {
+                                return Name { fields };
+                            }
+                        }
+                    ))
+                    .parse(),
+            };
+
+            Arm {
+                body,
+
+                attrs: v
+                    .attrs()
+                    .iter()
+                    .filter(|attr| is_attr_name(attr, "cfg"))
+                    .cloned()
+                    .collect(),
+                pats: vec![Element::End(pat)].into_iter().collect(),
+                guard: None,
+                fat_arrow_token: def_site(),
+                comma: Some(def_site()),
+                leading_vert: None,
+            }
+        })
+        .collect();
+
+    let body = Expr::Match(ExprMatch {
+        attrs: Default::default(),
+        match_token: def_site(),
+        brace_token: def_site(),
+        expr: box Quote::new(def_site::<Span>())
+            .quote_with(smart_quote!(Vars {}, { self }))
+            .parse(),
+        arms,
+    });
+
+    Quote::new(def_site::<Span>())
+        .quote_with(smart_quote!(
+            Vars {
+                Type: &input.ident,
+                Source: source,
+                body,
+            },
+            {
+                impl<__Fold> swc_common::MapFoldWith<Type, __Fold> for Source {
+                    fn map_fold_children(self, _f: &mut __Fold) -> Type {
+                        body
+                    }
+                }
+            }
+        ))
+        .parse()
+}
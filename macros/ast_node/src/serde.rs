@@ -0,0 +1,250 @@
+//! Serde support of `#[ast_node]`.
+//!
+//! Generated items are behind `cfg(feature = "serde")` of the crate using
+//! `#[ast_node]`, and so are `#[serde(..)]` attributes of the input.
+//!
+//! Structs with named fields are serialized with a `type` field, which is
+//! checked on deserialization so that enums can be untagged. Fields of type
+//! `Span` and `Option<Span>` use `::serde_impls::span` and
+//! `::serde_impls::opt_span` of the crate unless they have a `#[serde]`
+//! attribute, because `Span` does not implement serde traits.
+use swc_macros_common::prelude::*;
+
+/// `smart_quote!` which returns a `TokenStream`.
+macro_rules! q {
+    ($($tt:tt)*) => {{
+        let tokens: TokenStream = Quote::new(call_site::<Span>())
+            .quote_with(smart_quote!($($tt)*))
+            .into();
+        tokens
+    }};
+}
+
+/// Adds derives to `input` and moves its `#[serde]` attributes behind the
+/// feature.
+///
+/// Returns `impl Deserialize` for structs with named fields, which is empty
+/// for others.
+pub fn expand(input: &mut DeriveInput, type_name: Option<LitStr>) -> TokenStream {
+    let is_named_struct = match input.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(..),
+            ..
+        }) => true,
+        _ => false,
+    };
+    let type_name = match type_name {
+        Some(name) => {
+            if !is_named_struct {
+                panic!("#[ast_node(\"..\")] can only be used for structs with named fields")
+            }
+            name
+        }
+        None => LitStr::new(&input.ident.to_string(), call_site()),
+    };
+
+    let container_attrs = take_serde_attrs(&mut input.attrs);
+    input
+        .attrs
+        .extend(container_attrs.iter().cloned().map(behind_feature));
+
+    let mut fields = vec![];
+    match input.data {
+        Data::Struct(ref mut data) => {
+            for field in fields_mut(&mut data.fields) {
+                let attrs = prepare_field(field);
+                fields.push((field.ident.clone(), field.ty.clone(), attrs));
+            }
+        }
+        Data::Enum(ref mut data) => {
+            for v in data.variants.iter_mut() {
+                let attrs = take_serde_attrs(&mut v.attrs);
+                v.attrs.extend(attrs.into_iter().map(behind_feature));
+                for field in fields_mut(&mut v.fields) {
+                    prepare_field(field);
+                }
+            }
+        }
+        Data::Union(..) => unimplemented!("#[ast_node] for union"),
+    }
+
+    let (derive, repr) = match input.data {
+        Data::Enum(..) => (
+            q!(Vars {}, { (::serde::Serialize, ::serde::Deserialize) }),
+            Some(q!(Vars {}, { (untagged) })),
+        ),
+        _ if is_named_struct => (
+            q!(Vars {}, { (::serde::Serialize) }),
+            Some(q!(Vars { type_name: &type_name }, {
+                (tag = "type", rename = type_name, rename_all = "camelCase")
+            })),
+        ),
+        _ => (
+            q!(Vars {}, { (::serde::Serialize, ::serde::Deserialize) }),
+            None,
+        ),
+    };
+    input.attrs.push(behind_feature(attr("derive", derive)));
+    if let Some(repr) = repr {
+        input.attrs.push(behind_feature(attr("serde", repr)));
+    }
+
+    if !is_named_struct {
+        return TokenStream::new();
+    }
+    make_deserialize(&input.ident, &type_name, &container_attrs, fields)
+}
+
+/// Implements `Deserialize` using a struct with same fields and `type`,
+/// because `#[serde(tag)]` of structs is ignored on deserialization.
+fn make_deserialize(
+    ident: &Ident,
+    type_name: &LitStr,
+    container_attrs: &[Attribute],
+    fields: Vec<(Option<Ident>, Type, Vec<Attribute>)>,
+) -> TokenStream {
+    let mut container = TokenStream::new();
+    for attr in container_attrs {
+        attr.to_tokens(&mut container);
+    }
+
+    let mut shadow_fields = TokenStream::new();
+    let mut inits = TokenStream::new();
+    for (field, ty, attrs) in fields {
+        for attr in attrs {
+            attr.to_tokens(&mut shadow_fields);
+        }
+        q!(Vars { field: &field, ty }, { field: ty, }).to_tokens(&mut shadow_fields);
+        q!(Vars { field: &field }, { field: node.field, }).to_tokens(&mut inits);
+    }
+
+    q!(
+        Vars {
+            Type: ident,
+            type_name,
+            container,
+            shadow_fields,
+            inits,
+        },
+        {
+            #[cfg(feature = "serde")]
+            impl<'de> ::serde::Deserialize<'de> for Type {
+                fn deserialize<D>(deserializer: D) -> ::std::result::Result<Self, D::Error>
+                where
+                    D: ::serde::Deserializer<'de>,
+                {
+                    #[derive(::serde::Deserialize)]
+                    #[serde(rename_all = "camelCase")]
+                    container
+                    struct Shadow {
+                        #[serde(rename = "type")]
+                        __type: ::std::string::String,
+                        shadow_fields
+                    }
+
+                    let node = <Shadow as ::serde::Deserialize>::deserialize(deserializer)?;
+                    if node.__type != type_name {
+                        return Err(::serde::de::Error::invalid_value(
+                            ::serde::de::Unexpected::Str(&node.__type),
+                            &type_name,
+                        ));
+                    }
+                    Ok(Type { inits })
+                }
+            }
+        }
+    )
+}
+
+fn fields_mut(fields: &mut Fields) -> Vec<&mut Field> {
+    match *fields {
+        Fields::Named(ref mut fields) => fields.named.iter_mut().collect(),
+        Fields::Unnamed(ref mut fields) => fields.unnamed.iter_mut().collect(),
+        Fields::Unit => vec![],
+    }
+}
+
+/// Moves `#[serde]` attributes of `field` behind the feature, and returns
+/// them.
+fn prepare_field(field: &mut Field) -> Vec<Attribute> {
+    let mut attrs = take_serde_attrs(&mut field.attrs);
+    if attrs.is_empty() {
+        if let Some(with) = span_with(&field.ty) {
+            let with = LitStr::new(with, call_site());
+            attrs.push(attr("serde", q!(Vars { with }, { (with = with) })));
+        }
+    }
+    field
+        .attrs
+        .extend(attrs.iter().cloned().map(behind_feature));
+    attrs
+}
+
+fn take_serde_attrs(attrs: &mut Vec<Attribute>) -> Vec<Attribute> {
+    let (serde, others): (Vec<_>, Vec<_>) = attrs
+        .drain(..)
+        .partition(|attr| is_attr_name(attr, "serde"));
+    *attrs = others;
+    serde
+}
+
+/// `with` for fields of type `Span` or `Option<Span>`.
+fn span_with(ty: &Type) -> Option<&'static str> {
+    fn last_segment(ty: &Type) -> Option<&PathSegment> {
+        match *ty {
+            Type::Path(TypePath {
+                qself: None,
+                ref path,
+            }) => path.segments.last().map(|s| s.into_value()),
+            _ => None,
+        }
+    }
+
+    fn is_span(ty: &Type) -> bool {
+        match last_segment(ty) {
+            Some(&PathSegment {
+                ref ident,
+                arguments: PathArguments::None,
+            }) => *ident == "Span",
+            _ => false,
+        }
+    }
+
+    if is_span(ty) {
+        return Some("::serde_impls::span");
+    }
+    let s = last_segment(ty)?;
+    if s.ident != "Option" {
+        return None;
+    }
+    match s.arguments {
+        PathArguments::AngleBracketed(ref args) if args.args.len() == 1 => {
+            match *args.args.first().unwrap().into_value() {
+                GenericArgument::Type(ref ty) if is_span(ty) => Some("::serde_impls::opt_span"),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// `#[cfg_attr(feature = "serde", attr)]`
+fn behind_feature(attr: Attribute) -> Attribute {
+    let Attribute { path, tts, .. } = attr;
+    self::attr(
+        "cfg_attr",
+        q!(Vars { path, tts }, { (feature = "serde", path tts) }),
+    )
+}
+
+/// `#[name tts]`
+fn attr(name: &str, tts: TokenStream) -> Attribute {
+    Attribute {
+        pound_token: def_site(),
+        style: AttrStyle::Outer,
+        bracket_token: def_site(),
+        path: Ident::new(name, call_site()).into(),
+        tts,
+        is_sugared_doc: false,
+    }
+}
@@ -0,0 +1,74 @@
+//! Helpers shared by derives of visitors.
+
+use darling::FromField;
+use swc_macros_common::prelude::*;
+
+#[derive(Debug, FromField)]
+#[darling(attributes(fold))]
+struct FieldAttrs {
+    ///
+    #[darling(default)]
+    pub ignore: bool,
+
+    /// Should we add bound for the field's type?
+    #[darling(default)]
+    pub bound: bool,
+}
+
+/// Returns true for `bool` and numeric types.
+pub fn is_primitive(ty: &Type) -> bool {
+    match &*ty.dump().to_string() {
+        "bool" | "usize" | "u128" | "u64" | "u32" | "u16" | "u8" | "isize" | "i128" | "i64"
+        | "i32" | "i16" | "i8" | "f64" | "f32" => true,
+        _ => false,
+    }
+}
+
+/// Returns true for fields ignored with `#[fold(ignore)]` and fields of
+/// primitive types or `String`, which are not visited.
+pub fn should_skip_field(field: &Field) -> bool {
+    let attrs = FieldAttrs::from_field(field).expect("#[derive(Fold)]: failed to parse attribute");
+    if attrs.ignore {
+        return true;
+    }
+
+    is_primitive(&field.ty) || field.ty.dump().to_string() == "String"
+}
+
+/// Unwraps `Box`, `Option` and `Vec` of the type of a field with
+/// `#[fold(bound)]`, so the node type is used in where clauses.
+pub fn normalize_type_for_bound(ty: Type) -> Type {
+    use syn::fold::{self, Fold};
+
+    struct Norm;
+    impl Fold for Norm {
+        fn fold_path(&mut self, path: Path) -> Path {
+            if path.segments.len() == 1 {
+                let seg = &path.segments[0];
+                if seg.ident != "Box" && seg.ident != "Option" && seg.ident != "Vec" {
+                    return path.clone();
+                }
+
+                if let PathArguments::AngleBracketed(ref args) = seg.arguments {
+                    if args.args.len() == 1 {
+                        if let GenericArgument::Type(ref ty) =
+                            *args.args.last().unwrap().into_value()
+                        {
+                            match *ty {
+                                Type::Path(TypePath { ref path, .. }) => {
+                                    return self.fold_path(path.clone())
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+            }
+
+            fold::fold_path(self, path)
+        }
+    }
+
+    let out = Norm.fold_type(ty);
+    out
+}
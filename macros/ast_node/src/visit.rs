@@ -1,17 +1,5 @@
-use darling::FromField;
 use swc_macros_common::prelude::*;
-
-#[derive(Debug, FromField)]
-#[darling(attributes(fold))]
-struct FieldAttrs {
-    ///
-    #[darling(default)]
-    pub ignore: bool,
-
-    /// Should we add bound for the field's type?
-    #[darling(default)]
-    pub bound: bool,
-}
+use util::{normalize_type_for_bound, should_skip_field};
 
 pub fn derive(input: DeriveInput) -> ItemImpl {
     let mut derive_generics = Derive::new(&input);
@@ -144,55 +132,3 @@ pub fn derive(input: DeriveInput) -> ItemImpl {
 
     item
 }
-
-fn should_skip_field(field: &Field) -> bool {
-    let attrs = FieldAttrs::from_field(field).expect("#[derive(Fold)]: failed to parse attribute");
-    if attrs.ignore {
-        return true;
-    }
-
-    let ty_str = field.ty.dump().to_string();
-    match &*ty_str {
-        "bool" | "usize" | "u128" | "u64" | "u32" | "u16" | "u8" | "isize" | "i128" | "i64"
-        | "i32" | "i16" | "i8" | "f64" | "f32" | "String" => return true,
-        _ => {}
-    }
-
-    false
-}
-
-fn normalize_type_for_bound(ty: Type) -> Type {
-    use syn::fold::{self, Fold};
-
-    struct Norm;
-    impl Fold for Norm {
-        fn fold_path(&mut self, path: Path) -> Path {
-            if path.segments.len() == 1 {
-                let seg = &path.segments[0];
-                if seg.ident != "Box" && seg.ident != "Option" && seg.ident != "Vec" {
-                    return path.clone();
-                }
-
-                if let PathArguments::AngleBracketed(ref args) = seg.arguments {
-                    if args.args.len() == 1 {
-                        if let GenericArgument::Type(ref ty) =
-                            *args.args.last().unwrap().into_value()
-                        {
-                            match *ty {
-                                Type::Path(TypePath { ref path, .. }) => {
-                                    return self.fold_path(path.clone())
-                                }
-                                _ => {}
-                            }
-                        }
-                    }
-                }
-            }
-
-            fold::fold_path(self, path)
-        }
-    }
-
-    let out = Norm.fold_type(ty);
-    out
-}
@@ -0,0 +1,146 @@
+use fold_path::parent_kind;
+use swc_macros_common::prelude::*;
+use util::{normalize_type_for_bound, should_skip_field};
+
+pub fn derive(input: DeriveInput) -> ItemImpl {
+    let mut derive_generics = Derive::new(&input);
+
+    let preds = derive_generics
+        .all_generic_fields()
+        .into_iter()
+        .filter(|f| {
+            f.attrs
+                .iter()
+                .any(|attr| is_attr_name(attr, "fold") && attr.tts.to_string() == "( bound )")
+        })
+        .map(|f| f.ty.clone())
+        .map(normalize_type_for_bound)
+        .map(|ty| {
+            Quote::new(def_site::<Span>())
+                .quote_with(smart_quote!(
+                    Vars { Type: &ty },
+                    (Type: swc_common::VisitWithPath<__V>)
+                ))
+                .parse()
+        });
+    derive_generics.add_where_predicates(preds);
+
+    let arms = Binder::new_from(&input)
+        .variants()
+        .into_iter()
+        .map(|v| {
+            let kind = parent_kind(&input, &v);
+            let (pat, bindings) = v.bind("_", Some(def_site()), None);
+
+            let fields: Punctuated<Stmt, token::Semi> = bindings
+                .into_iter()
+                .filter_map(|binding| {
+                    // This closure will not be called for unit-like struct.
+
+                    let value = match should_skip_field(binding.field()) {
+                        true => None,
+                        false => Some(
+                            Quote::new(def_site::<Span>())
+                                .quote_with(smart_quote!(
+                                    Vars {
+                                        FieldType: &binding.field().ty,
+                                        binded_field: binding.name(),
+                                    },
+                                    {
+                                        swc_common::VisitPath::<FieldType>::visit_path(
+                                            _v,
+                                            binded_field,
+                                            _path,
+                                        );
+                                    }
+                                ))
+                                .parse::<Stmt>(),
+                        ),
+                    };
+
+                    let _attrs = binding
+                        .field()
+                        .attrs
+                        .iter()
+                        .filter(|attr| is_attr_name(attr, "cfg"))
+                        .cloned()
+                        .collect::<Vec<_>>();
+
+                    value
+                })
+                .map(|t| Element::Punctuated(t, def_site()))
+                .collect();
+
+            let body = match *v.data() {
+                // Handle unit-like structs separately
+                Fields::Unit => box Quote::new(def_site::<Span>())
+                    .quote_with(smart_quote!(Vars {}, {
+                        {
+                            // no-op
+                        }
+                    }))
+                    .parse(),
+                _ => box Quote::new(def_site::<Span>())
+                    .quote_with(smart_quote!(Vars { kind, fields }, {
+                        {
+                            _path.with(kind, |_path| {
+                                fields
+                            })
+                        }
+                    }))
+                    .parse(),
+            };
+
+            Arm {
+                body,
+
+                attrs: v
+                    .attrs()
+                    .iter()
+                    .filter(|attr| is_attr_name(attr, "cfg"))
+                    .cloned()
+                    .collect(),
+                pats: vec![Element::End(pat)].into_iter().collect(),
+                guard: None,
+                fat_arrow_token: def_site(),
+                comma: Some(def_site()),
+                leading_vert: None,
+            }
+        })
+        .collect();
+
+    let body = Expr::Match(ExprMatch {
+        attrs: Default::default(),
+        match_token: def_site(),
+        brace_token: def_site(),
+        expr: box Quote::new(def_site::<Span>())
+            .quote_with(smart_quote!(Vars {}, { *self }))
+            .parse(),
+        arms,
+    });
+
+    let item = Quote::new(def_site::<Span>())
+        .quote_with(smart_quote!(
+            Vars {
+                Type: &input.ident,
+                body,
+            },
+            {
+                impl<__V> swc_common::VisitWithPath<__V> for Type {
+                    fn visit_children_with_path(
+                        &self,
+                        _v: &mut __V,
+                        _path: &mut swc_common::AstPath,
+                    ) {
+                        body
+                    }
+                }
+            }
+        ))
+        .parse();
+    let item = derive_generics.append_to(item);
+
+    // println!("Expaned:\n {}\n\n", item.dump());
+
+    item
+}
@@ -1,6 +1,6 @@
 //! Test that `#[span]` and `#[fold]` can be used at same time.
 extern crate swc_common;
-use swc_common::{ast_node, Fold, Span, Spanned};
+use swc_common::{ast_node, EqIgnoreSpan, Fold, HashIgnoreSpan, Span, Spanned};
 
 #[ast_node]
 // See https://github.com/rust-lang/rust/issues/44925
@@ -14,7 +14,7 @@ pub struct Class {
 #[ast_node]
 pub struct Tuple(#[span] HasSpan, #[fold(ignore)] usize, usize);
 
-#[derive(Debug, Clone, PartialEq, Fold, Spanned)]
+#[derive(Debug, Clone, PartialEq, Fold, Spanned, EqIgnoreSpan, HashIgnoreSpan)]
 pub struct HasSpan {
     pub span: Span,
 }
@@ -0,0 +1,78 @@
+extern crate swc_common;
+use std::{collections::hash_map::DefaultHasher, hash::Hasher};
+use swc_common::{BytePos, EqIgnoreSpan, HashIgnoreSpan, Span, SyntaxContext, DUMMY_SP};
+
+#[derive(Debug, EqIgnoreSpan, HashIgnoreSpan)]
+pub struct Expr {
+    pub span: Span,
+    pub kind: ExprKind,
+}
+
+#[derive(Debug, EqIgnoreSpan, HashIgnoreSpan)]
+pub enum ExprKind {
+    Seq(Vec<Option<Box<Expr>>>),
+    Ident { span: Span, sym: String },
+    Null,
+    Undefined,
+}
+
+fn span(lo: u32, hi: u32) -> Span {
+    Span::new(BytePos(lo), BytePos(hi), SyntaxContext::empty())
+}
+
+fn ident(span: Span, sym: &str) -> Expr {
+    Expr {
+        span,
+        kind: ExprKind::Ident {
+            span,
+            sym: sym.into(),
+        },
+    }
+}
+
+fn hash(e: &Expr) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    e.hash_ignore_span(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn ignores_span() {
+    let l = Expr {
+        span: span(0, 6),
+        kind: ExprKind::Seq(vec![Some(Box::new(ident(span(0, 1), "a"))), None]),
+    };
+    let r = Expr {
+        span: DUMMY_SP,
+        kind: ExprKind::Seq(vec![Some(Box::new(ident(DUMMY_SP, "a"))), None]),
+    };
+
+    assert!(l.eq_ignore_span(&r));
+    assert_eq!(hash(&l), hash(&r));
+}
+
+#[test]
+fn compares_fields() {
+    assert!(!ident(DUMMY_SP, "a").eq_ignore_span(&ident(DUMMY_SP, "b")));
+
+    let seq = |elems| Expr {
+        span: DUMMY_SP,
+        kind: ExprKind::Seq(elems),
+    };
+    assert!(!seq(vec![None]).eq_ignore_span(&seq(vec![None, None])));
+}
+
+#[test]
+fn compares_variants() {
+    let null = Expr {
+        span: DUMMY_SP,
+        kind: ExprKind::Null,
+    };
+    let undefined = Expr {
+        span: DUMMY_SP,
+        kind: ExprKind::Undefined,
+    };
+
+    assert!(!null.eq_ignore_span(&undefined));
+    assert_ne!(hash(&null), hash(&undefined));
+}
@@ -0,0 +1,52 @@
+#![feature(specialization)]
+
+extern crate swc_common;
+use swc_common::{Fold, FoldHooks, FoldWith};
+
+#[derive(Fold, Debug, PartialEq)]
+#[fold(hooks)]
+pub enum Expr {
+    Paren(Box<Expr>),
+    Lit(Lit),
+}
+
+#[derive(Fold, Debug, PartialEq)]
+#[fold(hooks)]
+pub enum Lit {
+    A,
+    B,
+}
+
+/// Records the order of hooks, and replaces `A` with `B` on exit.
+#[derive(Default)]
+struct Hooks {
+    log: Vec<&'static str>,
+}
+impl FoldHooks<Expr> for Hooks {
+    fn enter(&mut self, _: &Expr) {
+        self.log.push("enter expr");
+    }
+
+    fn exit(&mut self, node: Expr) -> Expr {
+        self.log.push("exit expr");
+        node
+    }
+}
+impl FoldHooks<Lit> for Hooks {
+    fn exit(&mut self, _: Lit) -> Lit {
+        self.log.push("exit lit");
+        Lit::B
+    }
+}
+
+#[test]
+fn hooks() {
+    let mut hooks = Hooks::default();
+    let e = Expr::Paren(Box::new(Expr::Lit(Lit::A))).fold_with(&mut hooks);
+
+    assert_eq!(e, Expr::Paren(Box::new(Expr::Lit(Lit::B))));
+    assert_eq!(
+        hooks.log,
+        vec!["enter expr", "enter expr", "exit lit", "exit expr", "exit expr"]
+    );
+}
@@ -1,7 +1,7 @@
 #![feature(specialization)]
 
 extern crate swc_common;
-use swc_common::{Fold, FoldWith, Visit, VisitWith};
+use swc_common::{Fold, FoldWith, Visit, VisitMut, VisitMutWith, VisitWith};
 struct MyFold;
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
@@ -16,6 +16,11 @@ impl<F> VisitWith<F> for PanicOnFold {
         unreachable!("this should not be called")
     }
 }
+impl<F> VisitMutWith<F> for PanicOnFold {
+    fn visit_mut_children(&mut self, _: &mut F) {
+        unreachable!("this should not be called")
+    }
+}
 
 #[test]
 fn ignore_struct_named_field() {
@@ -26,6 +31,7 @@ fn ignore_struct_named_field() {
     }
     Foo::default().fold_with(&mut MyFold);
     Foo::default().visit_with(&mut MyFold);
+    Foo::default().visit_mut_with(&mut MyFold);
 }
 
 #[test]
@@ -34,6 +40,7 @@ fn ignore_struct_unnamed_field() {
     struct Bar(#[fold(ignore)] PanicOnFold);
     Bar::default().fold_with(&mut MyFold);
     Bar::default().visit_with(&mut MyFold);
+    Bar::default().visit_mut_with(&mut MyFold);
 }
 
 #[test]
@@ -45,6 +52,7 @@ fn ignore_enum_unnamed_field() {
 
     A::Field(Default::default()).fold_with(&mut MyFold);
     A::Field(Default::default()).visit_with(&mut MyFold);
+    A::Field(Default::default()).visit_mut_with(&mut MyFold);
 }
 
 #[test]
@@ -65,4 +73,8 @@ fn ignore_enum_named_field() {
         named: Default::default(),
     }
     .visit_with(&mut MyFold);
+    A::Field {
+        named: Default::default(),
+    }
+    .visit_mut_with(&mut MyFold);
 }
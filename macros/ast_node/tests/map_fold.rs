@@ -0,0 +1,119 @@
+#![feature(specialization)]
+
+extern crate swc_common;
+use swc_common::{MapFold, MapFoldWith, DUMMY_SP};
+
+mod ast {
+    use swc_common::Span;
+
+    pub struct Expr {
+        pub span: Span,
+        pub node: ExprKind,
+    }
+
+    pub enum ExprKind {
+        Seq(Vec<Option<Box<Expr>>>),
+        Ident(String),
+        Lit(Lit),
+    }
+
+    pub enum Lit {
+        Num(f64),
+        Null,
+    }
+}
+
+mod ir {
+    use swc_common::MapFold;
+
+    /// Lowered without span.
+    #[derive(MapFold, Debug, PartialEq)]
+    #[map_fold(from = "::ast::Expr")]
+    pub struct Expr {
+        pub node: ExprKind,
+    }
+
+    #[derive(MapFold, Debug, PartialEq)]
+    #[map_fold(from = "::ast::ExprKind")]
+    pub enum ExprKind {
+        Seq(Vec<Option<Box<Expr>>>),
+        Ident(String),
+        Lit(Lit),
+    }
+
+    #[derive(MapFold, Debug, PartialEq)]
+    #[map_fold(bridge = "::ast::Lit")]
+    pub struct Lit(pub Option<f64>);
+
+    impl From<::ast::Lit> for Lit {
+        fn from(lit: ::ast::Lit) -> Self {
+            match lit {
+                ::ast::Lit::Num(v) => Lit(Some(v)),
+                ::ast::Lit::Null => Lit(None),
+            }
+        }
+    }
+}
+
+fn ast_expr(node: ast::ExprKind) -> Option<Box<ast::Expr>> {
+    Some(Box::new(ast::Expr {
+        span: DUMMY_SP,
+        node,
+    }))
+}
+
+fn ir_expr(node: ir::ExprKind) -> Option<Box<ir::Expr>> {
+    Some(Box::new(ir::Expr { node }))
+}
+
+fn input() -> ast::Expr {
+    ast::Expr {
+        span: DUMMY_SP,
+        node: ast::ExprKind::Seq(vec![
+            ast_expr(ast::ExprKind::Ident("a".into())),
+            None,
+            ast_expr(ast::ExprKind::Lit(ast::Lit::Num(1.0))),
+            ast_expr(ast::ExprKind::Lit(ast::Lit::Null)),
+        ]),
+    }
+}
+
+struct Lowering;
+
+#[test]
+fn lower() {
+    let expr: ir::Expr = input().map_fold_with(&mut Lowering);
+
+    assert_eq!(
+        expr,
+        ir::Expr {
+            node: ir::ExprKind::Seq(vec![
+                ir_expr(ir::ExprKind::Ident("a".into())),
+                None,
+                ir_expr(ir::ExprKind::Lit(ir::Lit(Some(1.0)))),
+                ir_expr(ir::ExprKind::Lit(ir::Lit(None))),
+            ]),
+        }
+    );
+}
+
+/// Prefixes identifiers while lowering.
+struct Prefixer;
+impl MapFold<String, String> for Prefixer {
+    fn map_fold(&mut self, s: String) -> String {
+        format!("_{}", s)
+    }
+}
+
+#[test]
+fn custom() {
+    let expr: ir::Expr = input().map_fold_with(&mut Prefixer);
+
+    match expr.node {
+        ir::ExprKind::Seq(ref exprs) => assert_eq!(
+            exprs[0].as_ref().unwrap().node,
+            ir::ExprKind::Ident("_a".into())
+        ),
+        _ => unreachable!(),
+    }
+}
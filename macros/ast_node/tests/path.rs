@@ -0,0 +1,55 @@
+#![feature(specialization)]
+
+extern crate swc_common;
+use swc_common::{AstParentKind, AstPath, Fold, VisitPath, VisitWithPath};
+
+#[derive(Fold)]
+pub struct Expr {
+    pub node: ExprKind,
+}
+
+#[derive(Fold)]
+pub enum ExprKind {
+    Paren(Box<Expr>),
+    Seq(Vec<Expr>),
+    Lit(Lit),
+}
+
+#[derive(Fold, Debug)]
+pub enum Lit {
+    A,
+}
+
+#[derive(Default)]
+struct Paths(Vec<Vec<AstParentKind>>);
+impl VisitPath<Lit> for Paths {
+    fn visit_path(&mut self, _: &Lit, path: &mut AstPath) {
+        self.0.push(path.kinds().to_vec());
+    }
+}
+
+#[test]
+fn ancestors() {
+    let e = Expr {
+        node: ExprKind::Seq(vec![Expr {
+            node: ExprKind::Paren(Box::new(Expr {
+                node: ExprKind::Lit(Lit::A),
+            })),
+        }]),
+    };
+    let mut paths = Paths::default();
+    e.visit_with_path(&mut paths, &mut Default::default());
+
+    use AstParentKind::*;
+    assert_eq!(
+        paths.0,
+        vec![vec![
+            Struct("Expr"),
+            Variant("ExprKind", "Seq"),
+            Struct("Expr"),
+            Variant("ExprKind", "Paren"),
+            Struct("Expr"),
+            Variant("ExprKind", "Lit"),
+        ]]
+    );
+}
@@ -0,0 +1,65 @@
+#![feature(specialization)]
+
+extern crate swc_common;
+use swc_common::{Fold, TryFold, TryFoldWith};
+
+#[derive(Fold, Debug, PartialEq)]
+pub struct Expr {
+    pub node: ExprKind,
+}
+
+#[derive(Fold, Debug, PartialEq)]
+pub enum ExprKind {
+    Seq(Vec<Option<Box<Expr>>>),
+    Lit(Lit),
+}
+
+#[derive(Fold, Debug, PartialEq)]
+pub enum Lit {
+    A,
+    B,
+}
+
+#[derive(Debug, PartialEq)]
+struct FoundB;
+
+/// Replaces `A` with `B`, and fails on `B`.
+struct AToB;
+impl TryFold<Lit, FoundB> for AToB {
+    fn try_fold(&mut self, lit: Lit) -> Result<Lit, FoundB> {
+        match lit {
+            Lit::A => Ok(Lit::B),
+            Lit::B => Err(FoundB),
+        }
+    }
+}
+
+fn seq(lits: Vec<Lit>) -> Expr {
+    Expr {
+        node: ExprKind::Seq(
+            lits.into_iter()
+                .map(|lit| {
+                    Some(Box::new(Expr {
+                        node: ExprKind::Lit(lit),
+                    }))
+                })
+                .collect(),
+        ),
+    }
+}
+
+#[test]
+fn ok() {
+    assert_eq!(
+        seq(vec![Lit::A, Lit::A]).try_fold_with(&mut AToB),
+        Ok(seq(vec![Lit::B, Lit::B]))
+    );
+}
+
+#[test]
+fn err() {
+    assert_eq!(
+        seq(vec![Lit::A, Lit::B]).try_fold_with(&mut AToB),
+        Err(FoundB)
+    );
+}
@@ -0,0 +1,74 @@
+#![feature(specialization)]
+
+extern crate swc_common;
+use swc_common::{Fold, VisitControl, VisitCtl, VisitCtlWith};
+
+#[derive(Fold)]
+pub struct Expr {
+    pub node: ExprKind,
+}
+
+#[derive(Fold)]
+pub enum ExprKind {
+    Fn(Box<Expr>),
+    Seq(Vec<Expr>),
+    Lit(Lit),
+}
+
+#[derive(Fold, Debug, PartialEq)]
+pub enum Lit {
+    A,
+    B,
+}
+
+/// Finds `B`, skipping functions.
+#[derive(Default)]
+struct FindB {
+    visited: Vec<&'static str>,
+}
+impl VisitCtl<ExprKind> for FindB {
+    fn visit_ctl(&mut self, node: &ExprKind) -> VisitControl {
+        match *node {
+            ExprKind::Fn(..) => VisitControl::SkipChildren,
+            _ => VisitControl::Continue,
+        }
+    }
+}
+impl VisitCtl<Lit> for FindB {
+    fn visit_ctl(&mut self, lit: &Lit) -> VisitControl {
+        match *lit {
+            Lit::A => {
+                self.visited.push("A");
+                VisitControl::Continue
+            }
+            Lit::B => {
+                self.visited.push("B");
+                VisitControl::Stop
+            }
+        }
+    }
+}
+
+fn lit(lit: Lit) -> Expr {
+    Expr {
+        node: ExprKind::Lit(lit),
+    }
+}
+
+#[test]
+fn control() {
+    let e = Expr {
+        node: ExprKind::Seq(vec![
+            lit(Lit::A),
+            Expr {
+                node: ExprKind::Fn(Box::new(lit(Lit::B))),
+            },
+            lit(Lit::B),
+            lit(Lit::A),
+        ]),
+    };
+
+    let mut v = FindB::default();
+    assert_eq!(e.visit_ctl_with(&mut v), VisitControl::Stop);
+    assert_eq!(v.visited, vec!["A", "B"]);
+}
@@ -0,0 +1,63 @@
+#![feature(box_syntax)]
+#![feature(specialization)]
+
+extern crate swc_common;
+use swc_common::{Fold, VisitMut, VisitMutWith};
+
+#[derive(Fold, Debug, PartialEq)]
+pub struct Expr {
+    pub node: ExprKind,
+}
+
+#[derive(Fold, Debug, PartialEq)]
+pub enum ExprKind {
+    Paren(Box<Expr>),
+    Seq(Vec<Option<Expr>>),
+    Lit(Lit),
+}
+
+#[derive(Fold, Debug, PartialEq)]
+pub enum Lit {
+    A,
+    B,
+}
+
+struct AToB;
+impl VisitMut<Lit> for AToB {
+    fn visit_mut(&mut self, lit: &mut Lit) {
+        *lit = Lit::B;
+    }
+}
+
+fn lit(lit: Lit) -> Expr {
+    Expr {
+        node: ExprKind::Lit(lit),
+    }
+}
+
+#[test]
+fn mutate_in_place() {
+    let mut e = Expr {
+        node: ExprKind::Seq(vec![
+            Some(lit(Lit::A)),
+            None,
+            Some(Expr {
+                node: ExprKind::Paren(box lit(Lit::A)),
+            }),
+        ]),
+    };
+    e.visit_mut_with(&mut AToB);
+
+    assert_eq!(
+        e,
+        Expr {
+            node: ExprKind::Seq(vec![
+                Some(lit(Lit::B)),
+                None,
+                Some(Expr {
+                    node: ExprKind::Paren(box lit(Lit::B)),
+                }),
+            ]),
+        }
+    );
+}
@@ -34,6 +34,7 @@ use swc_macros_common::prelude::*;
 ///  - `pub fn as_str(&self) -> &'static str`
 ///  - `impl Debug`
 ///  - `impl Display`
+///  - `impl FromStr`, if all variants are unit variants
 ///
 ///# Example
 ///
@@ -78,6 +79,9 @@ pub fn derive_string_enum(input: proc_macro::TokenStream) -> proc_macro::TokenSt
 
     derive_fmt(&input, quote_spanned!(def_site() => std::fmt::Debug)).to_tokens(&mut tts);
     derive_fmt(&input, quote_spanned!(def_site() => std::fmt::Display)).to_tokens(&mut tts);
+    if let Some(item) = make_from_str(&input) {
+        item.to_tokens(&mut tts);
+    }
 
     // println!("Expanded:{}", tokens);
 
@@ -106,23 +110,78 @@ fn derive_fmt(i: &DeriveInput, trait_path: TokenStream) -> ItemImpl {
         .into()
 }
 
-fn make_as_str(i: &DeriveInput) -> ItemImpl {
-    fn get_str_value(attrs: &[Attribute]) -> String {
-        // TODO: Accept multiline string
-        let docs: Vec<_> = attrs.iter().map(doc_str).filter_map(|o| o).collect();
-        for raw_line in docs {
-            let line = raw_line.trim();
-            if line.starts_with("`") && line.ends_with("`") {
-                let mut s: String = line.split_at(1).1.into();
-                let new_len = s.len() - 1;
-                s.truncate(new_len);
-                return s;
-            }
+fn get_str_value(attrs: &[Attribute]) -> String {
+    // TODO: Accept multiline string
+    let docs: Vec<_> = attrs.iter().map(doc_str).filter_map(|o| o).collect();
+    for raw_line in docs {
+        let line = raw_line.trim();
+        if line.starts_with("`") && line.ends_with("`") {
+            let mut s: String = line.split_at(1).1.into();
+            let new_len = s.len() - 1;
+            s.truncate(new_len);
+            return s;
         }
+    }
 
-        panic!("Cannot determine string value of this variant")
+    panic!("Cannot determine string value of this variant")
+}
+
+/// Returns `None` if a variant has fields, because it can't be created from
+/// a string.
+fn make_from_str(i: &DeriveInput) -> Option<ItemImpl> {
+    let variants = Binder::new_from(&i).variants();
+    let has_fields = variants.iter().any(|v| match *v.data() {
+        Fields::Unit => false,
+        _ => true,
+    });
+    if has_fields {
+        return None;
     }
 
+    let arms = variants
+        .iter()
+        .map(|v| {
+            Quote::new(def_site::<Span>()).quote_with(smart_quote!(
+                Vars {
+                    qual_name: v.qual_path(),
+                    str_value: get_str_value(&v.attrs()),
+                },
+                { str_value => Ok(qual_name), }
+            ))
+        })
+        .fold(TokenStream::new(), |mut t, arm| {
+            let arm: TokenStream = arm.into();
+            arm.to_tokens(&mut t);
+            t
+        });
+
+    Some(
+        Quote::new(def_site::<Span>())
+            .quote_with(smart_quote!(
+                Vars {
+                    Type: &i.ident,
+                    arms,
+                },
+                {
+                    impl std::str::FromStr for Type {
+                        type Err = ();
+
+                        fn from_str(s: &str) -> Result<Self, ()> {
+                            match s {
+                                arms
+                                _ => Err(()),
+                            }
+                        }
+                    }
+                }
+            ))
+            .parse::<ItemImpl>()
+            .with_generics(i.generics.clone())
+            .into(),
+    )
+}
+
+fn make_as_str(i: &DeriveInput) -> ItemImpl {
     let arms = Binder::new_from(&i)
         .variants()
         .into_iter()
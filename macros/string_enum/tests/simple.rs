@@ -28,3 +28,18 @@ fn test_fmt() {
     assert_eq!(Tokens::A.to_string(), "a");
     assert_eq!(format!("{}", Tokens::A), "a");
 }
+
+#[derive(StringEnum, PartialEq)]
+pub enum Units {
+    /// `a`
+    A,
+    /// `b-c`
+    BC,
+}
+
+#[test]
+fn from_str() {
+    assert_eq!("a".parse(), Ok(Units::A));
+    assert_eq!("b-c".parse(), Ok(Units::BC));
+    assert_eq!("d".parse::<Units>(), Err(()));
+}
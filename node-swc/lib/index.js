@@ -0,0 +1,155 @@
+"use strict";
+
+const native = require("../native");
+
+/**
+ * Splits `options` into the file name, the path to `.swcrc`, the options
+ * passed to swc, the plugin and the cache directory.
+ */
+function normalize(options) {
+  const opts = Object.assign({}, options);
+  const filename = opts.filename || "unknown";
+  const configFile = opts.configFile || "";
+  const plugin = opts.plugin;
+  const cacheDir = opts.cacheDir || "";
+  delete opts.filename;
+  delete opts.configFile;
+  delete opts.plugin;
+  delete opts.cacheDir;
+  return [filename, configFile, JSON.stringify(opts), plugin, cacheDir];
+}
+
+/**
+ * Runs `plugin` with the ast of `src`, and returns the ast it returns as
+ * json.
+ */
+function runPlugin(plugin, src, filename, configFile, opts) {
+  const ast = JSON.parse(native.parseSync(src, filename, configFile, opts));
+  const result = plugin(ast);
+  if (!result || result.type !== "Module") {
+    throw new Error("plugin should return a `Module`");
+  }
+  return JSON.stringify(result);
+}
+
+/**
+ * Compiles `src`.
+ *
+ * `options` mirrors `.swcrc`, with an extra `filename` used for source maps
+ * and diagnostics. If `configFile` is given, options are loaded from it
+ * instead.
+ *
+ * `plugin` is a function which receives the ast of `src` in the shape of
+ * ESTree, and returns the ast to compile. It runs before other transforms,
+ * and is slower than native plugins as the ast is passed as json.
+ *
+ * If `cacheDir` is given, outputs are stored in the directory, and reused
+ * while the source, the options and the version of swc are unchanged. Files
+ * compiled with `plugin` are not cached.
+ *
+ * `dependencies` lists files read while compiling, which loaders of bundlers
+ * should watch.
+ *
+ * @returns {Promise<{ code: string, map?: string, dependencies: string[] }>}
+ */
+function transform(src, options) {
+  const [filename, configFile, opts, plugin, cacheDir] = normalize(options);
+  return new Promise((resolve, reject) => {
+    const callback = (err, output) => {
+      if (err) {
+        reject(err);
+      } else {
+        resolve(output);
+      }
+    };
+
+    if (plugin) {
+      const ast = runPlugin(plugin, src, filename, configFile, opts);
+      native.transformAst(src, ast, filename, configFile, opts, callback);
+    } else {
+      native.transform(src, filename, configFile, opts, cacheDir, callback);
+    }
+  });
+}
+
+/**
+ * Synchronous version of `transform`.
+ *
+ * @returns {{ code: string, map?: string, dependencies: string[] }}
+ */
+function transformSync(src, options) {
+  const [filename, configFile, opts, plugin, cacheDir] = normalize(options);
+  if (plugin) {
+    const ast = runPlugin(plugin, src, filename, configFile, opts);
+    return native.transformAstSync(src, ast, filename, configFile, opts);
+  }
+  return native.transformSync(src, filename, configFile, opts, cacheDir);
+}
+
+/**
+ * Parses `src` with the parser options of `options`, like `jsc.parser`.
+ *
+ * @returns {Promise<object>} The module in the shape of ESTree.
+ */
+function parse(src, options) {
+  const [filename, configFile, opts] = normalize(options);
+  return new Promise((resolve, reject) => {
+    native.parse(src, filename, configFile, opts, (err, json) => {
+      if (err) {
+        reject(err);
+      } else {
+        resolve(JSON.parse(json));
+      }
+    });
+  });
+}
+
+/**
+ * Synchronous version of `parse`.
+ *
+ * @returns {object}
+ */
+function parseSync(src, options) {
+  const [filename, configFile, opts] = normalize(options);
+  return JSON.parse(native.parseSync(src, filename, configFile, opts));
+}
+
+/**
+ * Prints `ast`, a module in the shape returned by `parse`, without
+ * transforming it. Output options of `options` like `minify` are used.
+ *
+ * @returns {Promise<{ code: string, map?: string, dependencies: string[] }>}
+ */
+function print(ast, options) {
+  const [filename, configFile, opts] = normalize(options);
+  return new Promise((resolve, reject) => {
+    const callback = (err, output) => {
+      if (err) {
+        reject(err);
+      } else {
+        resolve(output);
+      }
+    };
+
+    native.print(JSON.stringify(ast), filename, configFile, opts, callback);
+  });
+}
+
+/**
+ * Synchronous version of `print`.
+ *
+ * @returns {{ code: string, map?: string, dependencies: string[] }}
+ */
+function printSync(ast, options) {
+  const [filename, configFile, opts] = normalize(options);
+  return native.printSync(JSON.stringify(ast), filename, configFile, opts);
+}
+
+module.exports = {
+  transform,
+  transformSync,
+  parse,
+  parseSync,
+  print,
+  printSync
+};
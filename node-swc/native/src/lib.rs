@@ -0,0 +1,384 @@
+//! Node.js binding for swc.
+
+#[macro_use]
+extern crate neon;
+extern crate libswc as swc;
+extern crate serde_json;
+#[macro_use]
+extern crate slog;
+
+use neon::prelude::*;
+use std::{error::Error, path::Path};
+use swc::{
+    common::{
+        errors::{Handler, HandlerFlags},
+        sync::Lrc,
+        FileName, FilePathMapping, Globals, SourceFile, SourceMap, GLOBALS,
+    },
+    cache::DirCache,
+    config::Config,
+    Compiler, TransformOutput,
+};
+
+/// Runs `op` for `src` with options serialized as json, or with `.swcrc` at
+/// `config_file` if it's not empty.
+///
+/// Errors contain diagnostics emitted while running `op`.
+fn with_compiler<F, Ret>(
+    src: String,
+    filename: String,
+    config_file: &str,
+    options: &str,
+    op: F,
+) -> Result<Ret, String>
+where
+    F: FnOnce(&Compiler, &SourceFile, &Config) -> Result<Ret, Box<Error>>,
+{
+    let config: Config = serde_json::from_str(options)
+        .map_err(|err| format!("failed to deserialize options: {}", err))?;
+
+    GLOBALS.set(&Globals::new(), || {
+        let cm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        let (handler, diagnostics) = Handler::with_buffer(HandlerFlags {
+            can_emit_warnings: true,
+            ..Default::default()
+        });
+        let logger = slog::Logger::root(slog::Discard, o!());
+        let compiler = Compiler::new(logger, cm.clone(), handler);
+
+        let fm = cm.new_source_file(FileName::Real(filename.into()), src);
+        let config = if config_file.is_empty() {
+            config
+        } else {
+            compiler
+                .read_config(&fm, Path::new(config_file))
+                .map_err(|err| err.to_string())?
+        };
+        op(&compiler, &fm, &config)
+            .map_err(|err| format!("{}{}", diagnostics.render(cm.clone()), err))
+    })
+}
+
+/// Compiles `src`, or `ast` if a javascript plugin returned it.
+///
+/// Outputs are cached in `cache_dir` if it's not empty.
+fn transform(
+    src: String,
+    ast: Option<&str>,
+    filename: String,
+    config_file: &str,
+    options: &str,
+    cache_dir: &str,
+) -> Result<TransformOutput, String> {
+    with_compiler(src, filename, config_file, options, |compiler, fm, config| {
+        let mut output = match ast {
+            Some(ast) => compiler.process_js_json(fm, ast, config)?,
+            None if cache_dir.is_empty() => compiler.process_js_file(fm, config)?,
+            None => compiler.process_js_file_cached(fm, config, &DirCache::new(cache_dir))?,
+        };
+        if !config_file.is_empty() {
+            output.dependencies.push(config_file.into());
+        }
+        Ok(output)
+    })
+}
+
+fn to_js<'a, C: Context<'a>>(cx: &mut C, output: TransformOutput) -> JsResult<'a, JsObject> {
+    let obj = cx.empty_object();
+
+    let code = cx.string(&output.code);
+    obj.set(cx, "code", code)?;
+    if let Some(ref map) = output.map {
+        let map = cx.string(map);
+        obj.set(cx, "map", map)?;
+    }
+
+    let deps = JsArray::new(cx, output.dependencies.len() as u32);
+    for (i, path) in output.dependencies.iter().enumerate() {
+        let path = cx.string(path.to_string_lossy());
+        deps.set(cx, i as u32, path)?;
+    }
+    obj.set(cx, "dependencies", deps)?;
+
+    Ok(obj)
+}
+
+struct TransformTask {
+    src: String,
+    ast: Option<String>,
+    filename: String,
+    config_file: String,
+    options: String,
+    cache_dir: String,
+}
+
+impl Task for TransformTask {
+    type Output = TransformOutput;
+    type Error = String;
+    type JsEvent = JsObject;
+
+    fn perform(&self) -> Result<Self::Output, Self::Error> {
+        transform(
+            self.src.clone(),
+            self.ast.as_ref().map(|s| &**s),
+            self.filename.clone(),
+            &self.config_file,
+            &self.options,
+            &self.cache_dir,
+        )
+    }
+
+    fn complete(
+        self,
+        mut cx: TaskContext,
+        result: Result<Self::Output, Self::Error>,
+    ) -> JsResult<Self::JsEvent> {
+        match result {
+            Ok(output) => to_js(&mut cx, output),
+            Err(err) => cx.throw_error(err),
+        }
+    }
+}
+
+/// `transform(src, filename, configFile, options, cacheDir, callback)`
+fn transform_async(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let src = cx.argument::<JsString>(0)?.value();
+    let filename = cx.argument::<JsString>(1)?.value();
+    let config_file = cx.argument::<JsString>(2)?.value();
+    let options = cx.argument::<JsString>(3)?.value();
+    let cache_dir = cx.argument::<JsString>(4)?.value();
+    let callback = cx.argument::<JsFunction>(5)?;
+
+    TransformTask {
+        src,
+        ast: None,
+        filename,
+        config_file,
+        options,
+        cache_dir,
+    }
+    .schedule(callback);
+
+    Ok(cx.undefined())
+}
+
+/// `transformSync(src, filename, configFile, options, cacheDir)`
+fn transform_sync(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let src = cx.argument::<JsString>(0)?.value();
+    let filename = cx.argument::<JsString>(1)?.value();
+    let config_file = cx.argument::<JsString>(2)?.value();
+    let options = cx.argument::<JsString>(3)?.value();
+    let cache_dir = cx.argument::<JsString>(4)?.value();
+
+    match transform(src, None, filename, &config_file, &options, &cache_dir) {
+        Ok(output) => to_js(&mut cx, output),
+        Err(err) => cx.throw_error(err),
+    }
+}
+
+/// `transformAst(src, ast, filename, configFile, options, callback)`, where
+/// `ast` is json returned by a plugin.
+fn transform_ast_async(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let src = cx.argument::<JsString>(0)?.value();
+    let ast = cx.argument::<JsString>(1)?.value();
+    let filename = cx.argument::<JsString>(2)?.value();
+    let config_file = cx.argument::<JsString>(3)?.value();
+    let options = cx.argument::<JsString>(4)?.value();
+    let callback = cx.argument::<JsFunction>(5)?;
+
+    TransformTask {
+        src,
+        ast: Some(ast),
+        filename,
+        config_file,
+        options,
+        cache_dir: String::new(),
+    }
+    .schedule(callback);
+
+    Ok(cx.undefined())
+}
+
+/// `transformAstSync(src, ast, filename, configFile, options)`
+fn transform_ast_sync(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let src = cx.argument::<JsString>(0)?.value();
+    let ast = cx.argument::<JsString>(1)?.value();
+    let filename = cx.argument::<JsString>(2)?.value();
+    let config_file = cx.argument::<JsString>(3)?.value();
+    let options = cx.argument::<JsString>(4)?.value();
+
+    match transform(src, Some(&ast), filename, &config_file, &options, "") {
+        Ok(output) => to_js(&mut cx, output),
+        Err(err) => cx.throw_error(err),
+    }
+}
+
+/// Parses `src` and returns the module as json.
+fn parse(
+    src: String,
+    filename: String,
+    config_file: &str,
+    options: &str,
+) -> Result<String, String> {
+    with_compiler(src, filename, config_file, options, |compiler, fm, config| {
+        compiler.parse_js_json(fm, config)
+    })
+}
+
+struct ParseTask {
+    src: String,
+    filename: String,
+    config_file: String,
+    options: String,
+}
+
+impl Task for ParseTask {
+    type Output = String;
+    type Error = String;
+    type JsEvent = JsString;
+
+    fn perform(&self) -> Result<Self::Output, Self::Error> {
+        parse(
+            self.src.clone(),
+            self.filename.clone(),
+            &self.config_file,
+            &self.options,
+        )
+    }
+
+    fn complete(
+        self,
+        mut cx: TaskContext,
+        result: Result<Self::Output, Self::Error>,
+    ) -> JsResult<Self::JsEvent> {
+        match result {
+            Ok(json) => Ok(cx.string(json)),
+            Err(err) => cx.throw_error(err),
+        }
+    }
+}
+
+/// `parse(src, filename, configFile, options, callback)`, which passes the
+/// module as json to `callback`.
+fn parse_async(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let src = cx.argument::<JsString>(0)?.value();
+    let filename = cx.argument::<JsString>(1)?.value();
+    let config_file = cx.argument::<JsString>(2)?.value();
+    let options = cx.argument::<JsString>(3)?.value();
+    let callback = cx.argument::<JsFunction>(4)?;
+
+    ParseTask {
+        src,
+        filename,
+        config_file,
+        options,
+    }
+    .schedule(callback);
+
+    Ok(cx.undefined())
+}
+
+/// `parseSync(src, filename, configFile, options)`, which returns the module
+/// as json.
+fn parse_sync(mut cx: FunctionContext) -> JsResult<JsString> {
+    let src = cx.argument::<JsString>(0)?.value();
+    let filename = cx.argument::<JsString>(1)?.value();
+    let config_file = cx.argument::<JsString>(2)?.value();
+    let options = cx.argument::<JsString>(3)?.value();
+
+    match parse(src, filename, &config_file, &options) {
+        Ok(json) => Ok(cx.string(json)),
+        Err(err) => cx.throw_error(err),
+    }
+}
+
+/// Prints `ast`, a module serialized as json, with output options like
+/// `minify`.
+fn print(
+    ast: &str,
+    filename: String,
+    config_file: &str,
+    options: &str,
+) -> Result<TransformOutput, String> {
+    with_compiler(String::new(), filename, config_file, options, |compiler, _, config| {
+        compiler.print_js_json(ast, config)
+    })
+}
+
+struct PrintTask {
+    ast: String,
+    filename: String,
+    config_file: String,
+    options: String,
+}
+
+impl Task for PrintTask {
+    type Output = TransformOutput;
+    type Error = String;
+    type JsEvent = JsObject;
+
+    fn perform(&self) -> Result<Self::Output, Self::Error> {
+        print(
+            &self.ast,
+            self.filename.clone(),
+            &self.config_file,
+            &self.options,
+        )
+    }
+
+    fn complete(
+        self,
+        mut cx: TaskContext,
+        result: Result<Self::Output, Self::Error>,
+    ) -> JsResult<Self::JsEvent> {
+        match result {
+            Ok(output) => to_js(&mut cx, output),
+            Err(err) => cx.throw_error(err),
+        }
+    }
+}
+
+/// `print(ast, filename, configFile, options, callback)`, where `ast` is a
+/// module as json.
+fn print_async(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let ast = cx.argument::<JsString>(0)?.value();
+    let filename = cx.argument::<JsString>(1)?.value();
+    let config_file = cx.argument::<JsString>(2)?.value();
+    let options = cx.argument::<JsString>(3)?.value();
+    let callback = cx.argument::<JsFunction>(4)?;
+
+    PrintTask {
+        ast,
+        filename,
+        config_file,
+        options,
+    }
+    .schedule(callback);
+
+    Ok(cx.undefined())
+}
+
+/// `printSync(ast, filename, configFile, options)`
+fn print_sync(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let ast = cx.argument::<JsString>(0)?.value();
+    let filename = cx.argument::<JsString>(1)?.value();
+    let config_file = cx.argument::<JsString>(2)?.value();
+    let options = cx.argument::<JsString>(3)?.value();
+
+    match print(&ast, filename, &config_file, &options) {
+        Ok(output) => to_js(&mut cx, output),
+        Err(err) => cx.throw_error(err),
+    }
+}
+
+register_module!(mut cx, {
+    cx.export_function("transform", transform_async)?;
+    cx.export_function("transformSync", transform_sync)?;
+    cx.export_function("transformAst", transform_ast_async)?;
+    cx.export_function("transformAstSync", transform_ast_sync)?;
+    cx.export_function("parse", parse_async)?;
+    cx.export_function("parseSync", parse_sync)?;
+    cx.export_function("print", print_async)?;
+    cx.export_function("printSync", print_sync)?;
+    Ok(())
+});
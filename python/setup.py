@@ -0,0 +1,13 @@
+from setuptools import setup
+from setuptools_rust import Binding, RustExtension
+
+setup(
+    name="swc",
+    version="0.1.0",
+    description="Python binding for swc, the speedy web compiler",
+    license="Apache-2.0 OR MIT",
+    packages=["swc"],
+    rust_extensions=[RustExtension("swc._swc", "Cargo.toml", binding=Binding.PyO3)],
+    setup_requires=["setuptools-rust"],
+    zip_safe=False,
+)
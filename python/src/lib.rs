@@ -0,0 +1,90 @@
+//! Python binding for swc.
+//!
+//! See `swc/__init__.py` for the public interface.
+
+#![feature(specialization)]
+
+extern crate libswc as swc;
+#[macro_use]
+extern crate pyo3;
+extern crate serde_json;
+#[macro_use]
+extern crate slog;
+
+use pyo3::{exceptions::ValueError, prelude::*, types::PyDict};
+use std::error::Error;
+use swc::{
+    common::{
+        errors::{Handler, HandlerFlags},
+        sync::Lrc,
+        FileName, FilePathMapping, Globals, SourceFile, SourceMap, GLOBALS,
+    },
+    config::Config,
+    Compiler,
+};
+
+/// Runs `op` without holding the gil.
+///
+/// `options` is the json representation of `.swcrc`. Errors contain
+/// diagnostics emitted while running `op`.
+fn with_compiler<F, Ret>(
+    py: Python,
+    code: String,
+    filename: String,
+    options: &str,
+    op: F,
+) -> PyResult<Ret>
+where
+    F: Send + FnOnce(&Compiler, &SourceFile, &Config) -> Result<Ret, Box<Error>>,
+    Ret: Send,
+{
+    let config: Config = serde_json::from_str(options)
+        .map_err(|err| ValueError::py_err(format!("failed to deserialize options: {}", err)))?;
+
+    let result = py.allow_threads(|| {
+        GLOBALS.set(&Globals::new(), || {
+            let cm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let (handler, diagnostics) = Handler::with_buffer(HandlerFlags {
+                can_emit_warnings: true,
+                ..Default::default()
+            });
+            let logger = slog::Logger::root(slog::Discard, o!());
+            let compiler = Compiler::new(logger, cm.clone(), handler);
+
+            let fm = cm.new_source_file(FileName::Real(filename.into()), code);
+            op(&compiler, &fm, &config)
+                .map_err(|err| format!("{}{}", diagnostics.render(cm.clone()), err))
+        })
+    });
+    result.map_err(ValueError::py_err)
+}
+
+/// `transform(code, filename, options)`
+#[pyfunction]
+fn transform(py: Python, code: String, filename: String, options: String) -> PyResult<PyObject> {
+    let output = with_compiler(py, code, filename, &options, |compiler, fm, config| {
+        compiler.process_js_file(fm, config)
+    })?;
+
+    let dict = PyDict::new(py);
+    dict.set_item("code", output.code)?;
+    if let Some(map) = output.map {
+        dict.set_item("map", map)?;
+    }
+    Ok(dict.into())
+}
+
+/// `parse(code, filename, options)`, which returns the module as json.
+#[pyfunction]
+fn parse(py: Python, code: String, filename: String, options: String) -> PyResult<String> {
+    with_compiler(py, code, filename, &options, |compiler, fm, config| {
+        compiler.parse_js_json(fm, config)
+    })
+}
+
+#[pymodinit]
+fn _swc(_py: Python, m: &PyModule) -> PyResult<()> {
+    m.add_function(wrap_function!(transform))?;
+    m.add_function(wrap_function!(parse))?;
+    Ok(())
+}
@@ -0,0 +1,33 @@
+"""Python binding for swc."""
+
+import json
+
+from . import _swc
+
+
+def transform(code, filename="input.js", **options):
+    """Compiles `code`.
+
+    Keyword arguments mirror `.swcrc`, e.g. ``transform(code, jsc={...})``.
+    Returns a dict with `code` and, if source maps are requested, `map`.
+    Raises `ValueError` on failure.
+    """
+    return _swc.transform(code, filename, json.dumps(options))
+
+
+def parse(code, filename="input.js", **options):
+    """Parses `code` as a module.
+
+    Keyword arguments mirror `.swcrc`, and only `jsc.parser` is used.
+    Returns the module as a dict in the shape of ESTree.
+    Raises `ValueError` on failure.
+    """
+    return json.loads(_swc.parse(code, filename, json.dumps(options)))
+
+
+def minify(code, filename="input.js", **options):
+    """Same as `transform`, but with `minify` and the optimizer enabled."""
+    options["minify"] = True
+    jsc = options.setdefault("jsc", {})
+    jsc.setdefault("transform", {})["optimize"] = True
+    return transform(code, filename, **options)
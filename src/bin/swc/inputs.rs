@@ -0,0 +1,119 @@
+//! Expands files, directories and glob patterns passed to `swc jsc`.
+
+use globset::GlobBuilder;
+use std::{
+    error::Error,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Extensions of files compiled when a directory is given.
+///
+/// `.ts` files are always parsed as typescript. Tsx can't be parsed yet.
+const EXTENSIONS: &[&str] = &["js", "jsx", "mjs", "es6", "ts"];
+
+/// A file to compile.
+#[derive(Debug)]
+pub struct Input {
+    pub path: PathBuf,
+    /// Path of the output relative to `--out-dir`, before the extension is
+    /// replaced.
+    ///
+    /// This is the file name for files, and the path relative to the
+    /// directory or the literal prefix of the pattern otherwise.
+    pub rel: PathBuf,
+}
+
+/// Returns files to compile in the order of `args`.
+///
+/// Files in a directory are filtered by [EXTENSIONS], while patterns like
+/// `src/**/*.ts` select files regardless of extensions.
+pub fn expand(args: &[&str]) -> Result<Vec<Input>, Box<Error>> {
+    let mut inputs = vec![];
+
+    for &arg in args {
+        let path = Path::new(arg);
+        if path.is_file() {
+            inputs.push(Input {
+                path: path.to_path_buf(),
+                rel: PathBuf::from(path.file_name().ok_or("invalid file name")?),
+            });
+        } else if path.is_dir() {
+            let mut files = vec![];
+            walk(path, &mut files)?;
+            files.retain(|f| {
+                f.extension()
+                    .and_then(|ext| ext.to_str())
+                    .map_or(false, |ext| EXTENSIONS.contains(&ext))
+            });
+            push_relative(&mut inputs, path, files);
+        } else if is_pattern(arg) {
+            let matcher = GlobBuilder::new(arg)
+                .literal_separator(true)
+                .build()?
+                .compile_matcher();
+            let base = literal_prefix(path);
+
+            let mut files = vec![];
+            walk(&base, &mut files)?;
+            files.retain(|f| matcher.is_match(f));
+            if files.is_empty() {
+                return Err(format!("no files matched {}", arg).into());
+            }
+            push_relative(&mut inputs, &base, files);
+        } else {
+            return Err(format!("{} does not exist", arg).into());
+        }
+    }
+
+    Ok(inputs)
+}
+
+fn push_relative(inputs: &mut Vec<Input>, base: &Path, files: Vec<PathBuf>) {
+    inputs.extend(files.into_iter().map(|path| Input {
+        rel: path.strip_prefix(base).unwrap_or(&path).to_path_buf(),
+        path,
+    }))
+}
+
+/// Adds files in `dir` to `files` recursively, sorted by paths.
+///
+/// An empty path is the current directory, and paths of its files don't
+/// start with `./` so that they can be matched against patterns.
+fn walk(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    let entries = if dir.as_os_str().is_empty() {
+        fs::read_dir(".")?
+    } else {
+        fs::read_dir(dir)?
+    };
+    let mut paths = entries
+        .map(|entry| entry.map(|entry| dir.join(entry.file_name())))
+        .collect::<io::Result<Vec<_>>>()?;
+    paths.sort();
+
+    for path in paths {
+        if path.is_dir() {
+            walk(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+fn is_pattern(s: &str) -> bool {
+    s.contains(|c| match c {
+        '*' | '?' | '[' | '{' => true,
+        _ => false,
+    })
+}
+
+/// Returns leading components of `pattern` which don't contain
+/// metacharacters, like `src` of `src/**/*.js`.
+fn literal_prefix(pattern: &Path) -> PathBuf {
+    pattern
+        .components()
+        .take_while(|c| !is_pattern(&c.as_os_str().to_string_lossy()))
+        .collect()
+}
@@ -0,0 +1,278 @@
+//! `swc lsp`: A language server which publishes syntax errors and problems
+//! found by lint rules configured in `.swcrc` of open documents.
+//!
+//! Only full document synchronization is supported, so each change reparses
+//! the whole document.
+
+use super::{load_config, logger};
+use clap::ArgMatches;
+use serde_json::Value;
+use std::{
+    error::Error,
+    io::{self, BufRead, Read, Write},
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+use swc::{
+    common::{
+        errors::{Handler, HandlerFlags, JsonEmitter},
+        sync::Lrc,
+        FileName, FilePathMapping, SourceMap,
+    },
+    Compiler,
+};
+
+/// Returns `Ok(true)` if the client requested shutdown before exiting.
+pub fn run(matches: &ArgMatches) -> Result<bool, Box<Error>> {
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+    let stdout = io::stdout();
+    let mut output = stdout.lock();
+
+    let mut shutdown = false;
+    while let Some(msg) = read_message(&mut input)? {
+        let id = msg.get("id").cloned();
+        let params = &msg["params"];
+
+        match msg["method"].as_str().unwrap_or("") {
+            "initialize" => respond(
+                &mut output,
+                id,
+                json!({
+                    "capabilities": {
+                        // Full
+                        "textDocumentSync": 1,
+                    },
+                }),
+            )?,
+            "shutdown" => {
+                shutdown = true;
+                respond(&mut output, id, Value::Null)?
+            }
+            "exit" => return Ok(shutdown),
+
+            "textDocument/didOpen" => {
+                let doc = &params["textDocument"];
+                let text = doc["text"].as_str().unwrap_or("");
+                check(&mut output, matches, &doc["uri"], text)?
+            }
+            "textDocument/didChange" => {
+                let changes = params["contentChanges"].as_array();
+                if let Some(change) = changes.and_then(|changes| changes.last()) {
+                    let text = change["text"].as_str().unwrap_or("");
+                    check(&mut output, matches, &params["textDocument"]["uri"], text)?
+                }
+            }
+            "textDocument/didClose" => {
+                publish(&mut output, &params["textDocument"]["uri"], vec![])?
+            }
+
+            method => {
+                // Notifications can be ignored, but requests need a response.
+                if let Some(id) = id {
+                    send(
+                        &mut output,
+                        &json!({
+                            "jsonrpc": "2.0",
+                            "id": id,
+                            "error": {
+                                "code": -32601,
+                                "message": format!("unknown method: {}", method),
+                            },
+                        }),
+                    )?;
+                }
+            }
+        }
+    }
+
+    Ok(shutdown)
+}
+
+/// Parses and lints `text`, and publishes diagnostics.
+fn check(w: &mut Write, matches: &ArgMatches, uri: &Value, text: &str) -> io::Result<()> {
+    let diagnostics = match diagnostics(matches, uri.as_str().unwrap_or(""), text) {
+        Ok(diagnostics) => diagnostics,
+        Err(err) => {
+            eprintln!("failed to check {}: {}", uri, err);
+            return Ok(());
+        }
+    };
+
+    publish(w, uri, diagnostics)
+}
+
+fn diagnostics(matches: &ArgMatches, uri: &str, text: &str) -> Result<Vec<Value>, Box<Error>> {
+    let path = uri_to_path(uri)?;
+
+    let cm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+    let buf = Buffer::default();
+    let handler = Handler::with_emitter(
+        box JsonEmitter::new(box buf.clone(), None, cm.clone(), false),
+        HandlerFlags {
+            can_emit_warnings: true,
+            ..Default::default()
+        },
+    );
+    let comp = Compiler::new(logger(), cm.clone(), handler);
+
+    let config = load_config(&comp, matches, &path)?;
+    let fm = cm.new_source_file(FileName::Real(path), text.into());
+    // Errors are reported via handler.
+    if let Ok(module) = comp.parse_js_file(&fm, config.jsc.parser.into()) {
+        comp.lint(&module, &config)?;
+    }
+
+    let buf = buf.0.lock().unwrap();
+    let mut diagnostics = vec![];
+    // The json emitter writes a diagnostic per line.
+    for line in buf.split(|&b| b == b'\n').filter(|line| !line.is_empty()) {
+        diagnostics.push(to_lsp(&serde_json::from_slice(line)?, text));
+    }
+    Ok(diagnostics)
+}
+
+/// `file:///a%20b.js` -> `/a b.js`
+fn uri_to_path(uri: &str) -> Result<PathBuf, Box<Error>> {
+    let path = uri.trim_start_matches("file://").as_bytes();
+
+    let mut decoded = Vec::with_capacity(path.len());
+    let mut i = 0;
+    while i < path.len() {
+        if path[i] == b'%' {
+            let hex = path
+                .get(i + 1..i + 3)
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+            if let Some(b) = hex {
+                decoded.push(b);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(path[i]);
+        i += 1;
+    }
+
+    Ok(PathBuf::from(String::from_utf8(decoded)?))
+}
+
+/// Converts a json diagnostic of rustc to a diagnostic of lsp.
+///
+/// Columns of rustc count chars, while lsp counts utf-16 code units, so
+/// positions are computed from byte offsets in `text`.
+fn to_lsp(d: &Value, text: &str) -> Value {
+    let span = d["spans"]
+        .as_array()
+        .and_then(|spans| {
+            spans
+                .iter()
+                .find(|s| s["is_primary"].as_bool() == Some(true))
+                .or_else(|| spans.first())
+        })
+        .cloned()
+        .unwrap_or(Value::Null);
+    let pos = |key: &str| position(text, span[key].as_u64().unwrap_or(0) as usize);
+
+    json!({
+        "range": {
+            "start": pos("byte_start"),
+            "end": pos("byte_end"),
+        },
+        "severity": match d["level"].as_str() {
+            Some("warning") => 2,
+            Some("note") => 3,
+            Some("help") => 4,
+            _ => 1,
+        },
+        "source": "swc",
+        // Names of lint rules.
+        "code": d["code"]["code"],
+        "message": d["message"],
+    })
+}
+
+/// Zero-based line and utf-16 column of byte offset `pos` in `text`.
+fn position(text: &str, pos: usize) -> Value {
+    let mut pos = pos.min(text.len());
+    while !text.is_char_boundary(pos) {
+        pos -= 1;
+    }
+
+    let before = &text[..pos];
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    json!({
+        "line": before.matches('\n').count(),
+        "character": before[line_start..].encode_utf16().count(),
+    })
+}
+
+fn publish(w: &mut Write, uri: &Value, diagnostics: Vec<Value>) -> io::Result<()> {
+    send(
+        w,
+        &json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/publishDiagnostics",
+            "params": {
+                "uri": uri,
+                "diagnostics": diagnostics,
+            },
+        }),
+    )
+}
+
+fn respond(w: &mut Write, id: Option<Value>, result: Value) -> io::Result<()> {
+    send(
+        w,
+        &json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "result": result,
+        }),
+    )
+}
+
+fn send(w: &mut Write, msg: &Value) -> io::Result<()> {
+    let body = msg.to_string();
+    write!(w, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    w.flush()
+}
+
+/// Returns `Ok(None)` on eof.
+fn read_message(r: &mut BufRead) -> Result<Option<Value>, Box<Error>> {
+    let mut len = None;
+    loop {
+        let mut line = String::new();
+        if r.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+
+        const CONTENT_LENGTH: &str = "Content-Length:";
+        if line.starts_with(CONTENT_LENGTH) {
+            len = Some(line[CONTENT_LENGTH.len()..].trim().parse::<usize>()?);
+        }
+    }
+
+    let len = len.ok_or("missing Content-Length header")?;
+    let mut buf = vec![0; len];
+    r.read_exact(&mut buf)?;
+    Ok(Some(serde_json::from_slice(&buf)?))
+}
+
+/// Collects output of the emitter.
+#[derive(Clone, Default)]
+struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Buffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
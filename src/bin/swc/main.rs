@@ -0,0 +1,745 @@
+#![feature(box_syntax)]
+
+#[macro_use]
+extern crate clap;
+extern crate globset;
+extern crate rayon;
+extern crate serde;
+#[macro_use]
+extern crate serde_json;
+#[macro_use]
+extern crate slog;
+pub extern crate libswc as swc;
+extern crate slog_envlogger;
+extern crate slog_term;
+use clap::{AppSettings, Arg, ArgMatches, SubCommand};
+use inputs::Input;
+use rayon::prelude::*;
+use serde::Serialize;
+use slog::{Drain, Logger};
+use std::{
+    error::Error,
+    fmt::Debug,
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+use swc::{
+    common::{
+        errors::{ColorConfig, EmitterWriter, Handler, HandlerFlags, JsonEmitter},
+        sync::Lrc,
+        FilePathMapping, Fold, Globals, SourceMap, GLOBALS,
+    },
+    cache::{CacheKey, CacheStore, DirCache},
+    config::{Config, JscConfig, ParserConfig, ParserSyntax},
+    ecmascript::{codegen, minifier::Stats, parser::{EsConfig, Syntax}},
+    Compiler,
+};
+
+mod inputs;
+mod lsp;
+mod watch;
+
+fn main() {
+    let globals = Globals::new();
+    let res = GLOBALS.set(&globals, || run(&globals));
+    match res {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(err) => {
+            eprintln!("error: {}", err);
+            process::exit(1)
+        }
+    }
+}
+
+/// Returns `Ok(false)` if any input failed to compile.
+///
+/// `globals` are shared by worker threads.
+fn run(globals: &Globals) -> Result<bool, Box<Error>> {
+    let matches = app_from_crate!()
+        .global_settings(&[AppSettings::StrictUtf8, AppSettings::GlobalVersion])
+        .settings(&[AppSettings::SubcommandRequiredElseHelp])
+        .arg(
+            Arg::with_name("worker")
+                .short("w")
+                .long("worker")
+                .help("Number of threads to use for cpu-intensive tasks")
+                .takes_value(true)
+                .value_name("N"),
+        )
+        .arg(
+            Arg::with_name("error-format")
+                .long("error-format")
+                .help("How errors are rendered")
+                .takes_value(true)
+                .possible_values(&["pretty", "json", "short"])
+                .default_value("pretty"),
+        )
+        .subcommand(
+            SubCommand::with_name("jsc")
+                .arg(
+                    Arg::with_name("passes")
+                        .short("p")
+                        .long("passes")
+                        .takes_value(true)
+                        .multiple(true),
+                )
+                .arg(
+                    Arg::with_name("config-file")
+                        .long("config-file")
+                        .help("Path to .swcrc (defaults to .swcrc in the current directory)")
+                        .takes_value(true)
+                        .value_name("FILE"),
+                )
+                .arg(
+                    Arg::with_name("out-file")
+                        .short("o")
+                        .long("out-file")
+                        .help("Write output to FILE instead of stdout")
+                        .takes_value(true)
+                        .value_name("FILE"),
+                )
+                .arg(
+                    Arg::with_name("source-maps")
+                        .short("s")
+                        .long("source-maps")
+                        .takes_value(true)
+                        .possible_values(&["true", "false", "inline", "both"]),
+                )
+                .arg(
+                    Arg::with_name("source-map-target")
+                        .long("source-map-target")
+                        .help("Value of `file` in the source map")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("source-root")
+                        .long("source-root")
+                        .help("Value of `sourceRoot` in the source map")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("no-sources-content")
+                        .long("no-sources-content")
+                        .help("Do not embed original sources into the source map"),
+                )
+                .arg(
+                    Arg::with_name("out-dir")
+                        .short("d")
+                        .long("out-dir")
+                        .help("Write output files into DIR")
+                        .takes_value(true)
+                        .value_name("DIR"),
+                )
+                .arg(
+                    Arg::with_name("fail-fast")
+                        .long("fail-fast")
+                        .help("Stop at the first file which fails to compile"),
+                )
+                .arg(
+                    Arg::with_name("cache-dir")
+                        .long("cache-dir")
+                        .help("Reuse outputs stored in DIR by previous runs")
+                        .takes_value(true)
+                        .value_name("DIR"),
+                )
+                .arg(
+                    Arg::with_name("watch")
+                        .long("watch")
+                        .help("Compile inputs again when they change"),
+                )
+                .arg(Arg::with_name("optimize").long("optimize"))
+                .arg(Arg::with_name("minify").short("m").long("minify"))
+                .arg(
+                    Arg::with_name("stats")
+                        .long("stats")
+                        .help("Print sizes and what each rule of the minifier removed to stderr"),
+                )
+                .arg(
+                    Arg::with_name("input file")
+                        .help("Files, directories or glob patterns like 'src/**/*.ts' to compile")
+                        .required(true)
+                        .takes_value(true)
+                        .multiple(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("bench")
+                .about("Measures speed of each stage of the compilation")
+                .arg(
+                    Arg::with_name("iterations")
+                        .short("n")
+                        .long("iterations")
+                        .help("Number of iterations")
+                        .takes_value(true)
+                        .value_name("N")
+                        .default_value("100"),
+                )
+                .arg(
+                    Arg::with_name("config-file")
+                        .long("config-file")
+                        .help("Path to .swcrc (defaults to .swcrc in the current directory)")
+                        .takes_value(true)
+                        .value_name("FILE"),
+                )
+                .arg(Arg::with_name("optimize").long("optimize"))
+                .arg(
+                    Arg::with_name("input file")
+                        .required(true)
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("parse")
+                .about("Parses a file and prints the ast")
+                .arg(
+                    Arg::with_name("format")
+                        .short("f")
+                        .long("format")
+                        .help("Output format of the ast. `json` is in the shape of ESTree")
+                        .takes_value(true)
+                        .possible_values(&["json", "debug", "pretty"])
+                        .default_value("json"),
+                )
+                .arg(
+                    Arg::with_name("script")
+                        .long("script")
+                        .help("Parse input as a script instead of a module"),
+                )
+                .arg(
+                    Arg::with_name("num-sep")
+                        .long("num-sep")
+                        .help("Enable numeric separator"),
+                )
+                .arg(
+                    Arg::with_name("fn-bind")
+                        .long("fn-bind")
+                        .help("Enable function bind expression"),
+                )
+                .arg(Arg::with_name("jsx").long("jsx").help("Enable jsx"))
+                .arg(
+                    Arg::with_name("class-props")
+                        .long("class-props")
+                        .help("Enable class fields and private names"),
+                )
+                .arg(
+                    Arg::with_name("typescript")
+                        .long("typescript")
+                        .conflicts_with_all(&["num-sep", "fn-bind", "jsx", "class-props"])
+                        .help("Parse typescript"),
+                )
+                .arg(
+                    Arg::with_name("input file")
+                        .required(true)
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("lsp")
+                .about("Runs a language server reporting syntax errors over stdio")
+                .arg(
+                    Arg::with_name("config-file")
+                        .long("config-file")
+                        .help("Path to .swcrc (defaults to .swcrc in the current directory)")
+                        .takes_value(true)
+                        .value_name("FILE"),
+                ),
+        )
+        .get_matches();
+
+    rayon::ThreadPoolBuilder::new()
+        .thread_name(|i| format!("swc-worker-{}", i))
+        .num_threads(
+            matches
+                .value_of("worker")
+                .map(|v| v.parse().expect("expected number for --worker"))
+                .unwrap_or(0),
+        )
+        .build_global()
+        .expect("failed to configure rayon::ThreadPool");
+
+    if let Some(ref matches) = matches.subcommand_matches("lsp") {
+        return lsp::run(matches);
+    }
+
+    let cm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+
+    let error_format = matches.value_of("error-format").unwrap();
+    let handler = handler(cm.clone(), error_format);
+
+    let comp = Compiler::new(logger(), cm.clone(), handler);
+
+    if let Some(ref matches) = matches.subcommand_matches("jsc") {
+        let args: Vec<_> = matches.values_of("input file").unwrap().collect();
+        let inputs = inputs::expand(&args)?;
+        if inputs.len() > 1 && matches.is_present("out-file") {
+            return Err("--out-file cannot be used with multiple input files".into());
+        }
+        let watching = matches.is_present("watch");
+        if watching && !matches.is_present("out-file") && !matches.is_present("out-dir") {
+            return Err("--watch requires --out-file or --out-dir".into());
+        }
+
+        let fail_fast = matches.is_present("fail-fast");
+        let stop = AtomicBool::new(false);
+        let start = Instant::now();
+
+        // Files are compiled in parallel, and printed in the order of inputs.
+        //
+        // Diagnostics of each file are buffered and printed along with its
+        // output, so that they are not interleaved.
+        let buffered = inputs.len() > 1;
+        let results: Vec<_> = inputs
+            .par_iter()
+            .map(|input| {
+                if stop.load(Ordering::SeqCst) {
+                    return None;
+                }
+                let buf = Buffer::default();
+                let file_comp;
+                let comp = if buffered {
+                    file_comp = comp.with_handler(buffered_handler(
+                        cm.clone(),
+                        error_format,
+                        buf.clone(),
+                    ));
+                    &file_comp
+                } else {
+                    &comp
+                };
+
+                let res = GLOBALS
+                    .set(globals, || compile_file(comp, matches, input))
+                    .map_err(|err| err.to_string());
+                if res.is_err() && fail_fast {
+                    stop.store(true, Ordering::SeqCst);
+                }
+                Some((buf.take(), res))
+            })
+            .collect();
+
+        let (mut succeeded, mut failed) = (0, 0);
+        let stdout = io::stdout();
+        let stderr = io::stderr();
+        for (input, res) in inputs.iter().zip(results) {
+            let (diagnostics, res) = match res {
+                Some(res) => res,
+                // Skipped by --fail-fast
+                None => continue,
+            };
+            stderr.lock().write_all(&diagnostics)?;
+
+            match res {
+                Ok(compiled) => {
+                    if let Some(stats) = compiled.stats {
+                        eprint!("{}", stats);
+                    }
+                    if let Some(code) = compiled.stdout {
+                        stdout.lock().write_all(code.as_bytes())?;
+                    }
+                    succeeded += 1;
+                }
+                Err(err) => {
+                    eprintln!("failed to compile {}: {}", input.path.display(), err);
+                    failed += 1;
+                }
+            }
+        }
+
+        if succeeded + failed > 1 || failed != 0 {
+            let time = start.elapsed();
+            eprintln!(
+                "{} succeeded, {} failed ({}.{:03}s)",
+                succeeded,
+                failed,
+                time.as_secs(),
+                time.subsec_millis()
+            );
+        }
+
+        if watching {
+            return watch::run(&comp, matches, &args);
+        }
+        return Ok(failed == 0);
+    }
+
+    if let Some(ref matches) = matches.subcommand_matches("bench") {
+        let input = Path::new(matches.value_of("input file").unwrap());
+        let iterations: u32 = matches
+            .value_of("iterations")
+            .unwrap()
+            .parse()
+            .map_err(|_| "expected number for --iterations")?;
+        let config = load_config(&comp, matches, input)?;
+
+        bench(&comp, &config, input, iterations)?;
+    }
+
+    if let Some(ref matches) = matches.subcommand_matches("parse") {
+        let input = Path::new(matches.value_of("input file").unwrap());
+        let format = matches.value_of("format").unwrap();
+        let is_ts = matches.is_present("typescript");
+        let is_script = matches.is_present("script");
+
+        let stdout = std::io::stdout();
+        let mut output = stdout.lock();
+
+        // Same as the ast passed to javascript plugins.
+        if format == "json" && !is_ts && !is_script {
+            let config = Config {
+                jsc: JscConfig {
+                    parser: ParserConfig {
+                        num_sep: matches.is_present("num-sep"),
+                        fn_bind: matches.is_present("fn-bind"),
+                        jsx: matches.is_present("jsx"),
+                        class_props: matches.is_present("class-props"),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            let fm = comp.load_file(input)?;
+            writeln!(output, "{}", comp.parse_js_json(&fm, &config)?)?;
+            return Ok(true);
+        }
+
+        let syntax = if is_ts {
+            Syntax::Typescript(Default::default())
+        } else {
+            Syntax::Es(EsConfig {
+                num_sep: matches.is_present("num-sep"),
+                fn_bind: matches.is_present("fn-bind"),
+                jsx: matches.is_present("jsx"),
+                class_props: matches.is_present("class-props"),
+                ..Default::default()
+            })
+        };
+
+        let fm = comp.load_file(input)?;
+        let res = if is_script {
+            comp.parse_js_script_file(&fm, syntax)
+                .map(|script| print_ast(&mut output, &script, format))
+        } else {
+            comp.parse_js_file(&fm, syntax)
+                .map(|module| print_ast(&mut output, &module, format))
+        };
+        match res {
+            Ok(res) => res?,
+            // Syntax errors are already reported.
+            Err(()) => return Ok(false),
+        }
+    }
+
+    Ok(true)
+}
+
+/// Output of [compile_file], which is printed after all files are compiled.
+struct Compiled {
+    /// Code to print if neither `--out-file` nor `--out-dir` is given.
+    stdout: Option<String>,
+    /// Statistics requested by `--stats`.
+    stats: Option<String>,
+}
+
+/// Compiles a file passed to `swc jsc`. Called from worker threads.
+fn compile_file(
+    comp: &Compiler,
+    matches: &ArgMatches,
+    input: &Input,
+) -> Result<Compiled, Box<Error>> {
+    let Input { ref path, ref rel } = *input;
+    let input = &**path;
+
+    let mut config = load_config(&comp, matches, input)?;
+    if let Some(sm) = matches.value_of("source-maps") {
+        config.source_maps = Some(sm.parse()?);
+    }
+    if let Some(target) = matches.value_of("source-map-target") {
+        config.source_map_target = Some(target.into());
+    }
+    if let Some(root) = matches.value_of("source-root") {
+        config.source_root = Some(root.into());
+    }
+    if matches.is_present("no-sources-content") {
+        config.sources_content = Some(false);
+    }
+    if matches.is_present("minify") {
+        config.minify = true;
+    }
+
+    let fm = comp.load_file(input)?;
+    let stats = if matches.is_present("stats") {
+        Some(Arc::new(Mutex::new(Stats::default())))
+    } else {
+        None
+    };
+    // Statistics are collected while compiling.
+    let cache = match matches.value_of("cache-dir") {
+        Some(dir) if stats.is_none() => Some((DirCache::new(dir), CacheKey::new(&fm, &config))),
+        _ => None,
+    };
+
+    let out_file = match (matches.value_of("out-file"), matches.value_of("out-dir")) {
+        (Some(out_file), _) => Some(PathBuf::from(out_file)),
+        // `src/a.ts` in `swc jsc src --out-dir lib` is written to `lib/a.js`.
+        (None, Some(out_dir)) => {
+            let out_file = Path::new(out_dir).join(rel).with_extension("js");
+            fs::create_dir_all(out_file.parent().unwrap())?;
+            Some(out_file)
+        }
+        (None, None) => None,
+    };
+    if out_file.is_none() && config.source_maps.map(|sm| sm.file()).unwrap_or(false) {
+        return Err("source map file requires --out-file or --out-dir".into());
+    }
+
+    let cached = cache.as_ref().and_then(|&(ref cache, ref key)| cache.get(key));
+    let mut output = match cached {
+        Some(output) => output,
+        None => {
+            let module = comp
+                .parse_js_file(&fm, config.jsc.parser.into())
+                .map_err(|()| "failed to parse module")?;
+            let module = comp.pass_with_stats(&config, stats.clone())?.fold(module);
+            let output = comp.print(
+                &module,
+                codegen::Config {
+                    minify: config.minify,
+                    ..Default::default()
+                },
+                &config,
+            )?;
+
+            if let Some((ref cache, ref key)) = cache {
+                if let Err(err) = cache.put(key, &output) {
+                    eprintln!("failed to write cache of {}: {}", input.display(), err);
+                }
+            }
+            output
+        }
+    };
+
+    let stats = stats.map(|stats| {
+        let bytes = fm.src.as_ref().map(|s| s.len()).unwrap_or(0);
+        format_stats(input, bytes, output.code.len(), &stats.lock().unwrap())
+    });
+
+    match out_file {
+        Some(out_file) => {
+            if let Some(ref map) = output.map {
+                let mut map_file = out_file.as_os_str().to_owned();
+                map_file.push(".map");
+                let map_file = PathBuf::from(map_file);
+                fs::write(&map_file, map)?;
+
+                // Inline source map already has the comment.
+                if !config.source_maps.map(|sm| sm.inline()).unwrap_or(false) {
+                    output.code.push_str(&format!(
+                        "\n//# sourceMappingURL={}",
+                        map_file.file_name().unwrap().to_string_lossy()
+                    ));
+                }
+            }
+            fs::write(&out_file, &output.code)?;
+            Ok(Compiled {
+                stdout: None,
+                stats,
+            })
+        }
+        None => Ok(Compiled {
+            stdout: Some(output.code),
+            stats,
+        }),
+    }
+}
+
+/// Loads the config for `input` from `--config-file` or `./.swcrc`.
+fn load_config(
+    comp: &Compiler,
+    matches: &ArgMatches,
+    input: &Path,
+) -> Result<Config, Box<Error>> {
+    let rc = match matches.value_of("config-file") {
+        Some(path) => comp.read_rc(Path::new(path))?,
+        None if Path::new(".swcrc").is_file() => comp.read_rc(Path::new(".swcrc"))?,
+        None => Default::default(),
+    };
+
+    let mut config = rc.config_for_file(input)?.unwrap_or_default();
+    if input.extension().map_or(false, |ext| ext == "ts") {
+        config.jsc.parser.syntax = ParserSyntax::Typescript;
+    }
+    if matches.is_present("optimize") {
+        config.jsc.transform.optimize = true;
+    }
+
+    Ok(config)
+}
+
+fn bench(
+    comp: &Compiler,
+    config: &Config,
+    input: &Path,
+    iterations: u32,
+) -> Result<(), Box<Error>> {
+    let fm = comp.load_file(input)?;
+    let bytes = fm.src.as_ref().map(|s| s.len()).unwrap_or(0);
+
+    let mut parse = Duration::new(0, 0);
+    let mut transform = Duration::new(0, 0);
+    let mut emit = Duration::new(0, 0);
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let module = comp
+            .parse_js_file(&fm, config.jsc.parser.into())
+            .map_err(|()| format!("failed to parse {}", input.display()))?;
+        parse += start.elapsed();
+
+        let start = Instant::now();
+        let module = comp.pass(config)?.fold(module);
+        transform += start.elapsed();
+
+        let start = Instant::now();
+        comp.print(&module, Default::default(), config)?;
+        emit += start.elapsed();
+    }
+
+    println!("{} ({} bytes), {} iterations", input.display(), bytes, iterations);
+    println!(
+        "{:<10} {:>12} {:>12} {:>12}",
+        "stage", "total", "per iter", "throughput"
+    );
+    for &(name, time) in &[
+        ("parse", parse),
+        ("transform", transform),
+        ("emit", emit),
+        ("total", parse + transform + emit),
+    ] {
+        let secs = time.as_secs() as f64 + f64::from(time.subsec_nanos()) * 1e-9;
+        println!(
+            "{:<10} {:>10.3}ms {:>10.3}ms {:>8.2}MB/s",
+            name,
+            secs * 1e3,
+            secs * 1e3 / f64::from(iterations),
+            (bytes as f64 * f64::from(iterations)) / secs / 1e6
+        );
+    }
+
+    Ok(())
+}
+
+/// Formats statistics of `input` collected by `--stats`.
+fn format_stats(input: &Path, bytes_before: usize, bytes_after: usize, stats: &Stats) -> String {
+    let mut s = format!(
+        "{}: {} -> {} bytes ({:.1}%), {} -> {} nodes, {} passes\n",
+        input.display(),
+        bytes_before,
+        bytes_after,
+        bytes_after as f64 * 100.0 / bytes_before.max(1) as f64,
+        stats.nodes_before,
+        stats.nodes_after,
+        stats.passes
+    );
+    if stats.rules.is_empty() {
+        return s;
+    }
+
+    s.push_str(&format!("{:<14} {:>8} {:>14}\n", "rule", "changes", "nodes removed"));
+    for r in &stats.rules {
+        s.push_str(&format!("{:<14} {:>8} {:>14}\n", r.rule, r.changes, r.nodes_removed));
+    }
+    s
+}
+
+fn print_ast<T: Debug + Serialize>(w: &mut Write, node: &T, format: &str) -> io::Result<()> {
+    match format {
+        "json" => {
+            serde_json::to_writer(&mut *w, node)?;
+            writeln!(w)
+        }
+        "pretty" => writeln!(w, "{:#?}", node),
+        _ => writeln!(w, "{:?}", node),
+    }
+}
+
+fn handler(cm: Lrc<SourceMap>, error_format: &str) -> Handler {
+    let flags = HandlerFlags {
+        can_emit_warnings: true,
+        ..Default::default()
+    };
+
+    match error_format {
+        "json" => Handler::with_emitter(box JsonEmitter::stderr(None, cm, false), flags),
+        "short" => Handler::with_emitter(
+            box EmitterWriter::stderr(ColorConfig::Auto, Some(cm), true, false),
+            flags,
+        ),
+        _ => Handler::with_tty_emitter_and_flags(ColorConfig::Always, Some(cm), flags),
+    }
+}
+
+/// Same as [handler], but diagnostics are written to `buf` without colors.
+fn buffered_handler(cm: Lrc<SourceMap>, error_format: &str, buf: Buffer) -> Handler {
+    let flags = HandlerFlags {
+        can_emit_warnings: true,
+        ..Default::default()
+    };
+
+    match error_format {
+        "json" => Handler::with_json_emitter(cm, box buf, flags),
+        _ => Handler::with_emitter(
+            box EmitterWriter::new(box buf, Some(cm), error_format == "short", false),
+            flags,
+        ),
+    }
+}
+
+/// Diagnostics of a file, collected by [buffered_handler].
+#[derive(Clone, Default)]
+struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+impl Buffer {
+    fn take(&self) -> Vec<u8> {
+        std::mem::replace(&mut *self.0.lock().unwrap(), vec![])
+    }
+}
+
+impl Write for Buffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn logger() -> Logger {
+    fn no_timestamp(_: &mut Write) -> io::Result<()> {
+        Ok(())
+    }
+    fn root() -> Logger {
+        let dec = slog_term::TermDecorator::new()
+            .force_color()
+            .stderr()
+            .build();
+        let drain = slog_term::FullFormat::new(dec)
+            .use_custom_timestamp(no_timestamp)
+            .build();
+        let drain = slog_envlogger::new(drain);
+        let drain = std::sync::Mutex::new(drain).fuse();
+        let logger = Logger::root(drain, o!());
+
+        logger
+    }
+
+    root()
+}
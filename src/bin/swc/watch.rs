@@ -0,0 +1,115 @@
+//! `swc jsc --watch`: Compiles inputs again when they change.
+//!
+//! Inputs are polled instead of subscribing to events of the OS. A file is
+//! compiled again only if its content hash is changed, so touching a file or
+//! saving it without changes doesn't trigger a compilation.
+//!
+//! Changed files are recompiled as a whole, from parsing to printing, and
+//! parsed modules are not kept between compilations. As each input is
+//! compiled on its own, the output of a file doesn't depend on other inputs
+//! and no dependents are compiled again. With `--cache-dir`, outputs are
+//! still reused if a file is changed back to contents compiled before.
+
+use super::{compile_file, inputs};
+use clap::ArgMatches;
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    error::Error,
+    fs,
+    hash::{Hash, Hasher},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+use swc::Compiler;
+
+/// Last seen state of a file.
+struct State {
+    modified: Option<SystemTime>,
+    /// `None` if the file doesn't exist.
+    hash: Option<u64>,
+}
+
+/// Watches inputs compiled by `swc jsc`. This never returns unless inputs
+/// can't be expanded at first.
+///
+/// Changes of the config file compile all inputs again.
+pub fn run(comp: &Compiler, matches: &ArgMatches, args: &[&str]) -> Result<bool, Box<Error>> {
+    let config_file = Path::new(matches.value_of("config-file").unwrap_or(".swcrc"));
+
+    let mut states = HashMap::new();
+    update(&mut states, config_file);
+    for input in inputs::expand(args)? {
+        update(&mut states, &input.path);
+    }
+    eprintln!("watching for changes");
+
+    let stdout = io::stdout();
+    let mut last_err = None;
+    loop {
+        thread::sleep(Duration::from_millis(200));
+
+        let config_changed = update(&mut states, config_file);
+        let inputs = match inputs::expand(args) {
+            Ok(inputs) => inputs,
+            // Inputs may be removed temporarily.
+            Err(err) => {
+                let err = err.to_string();
+                if last_err.as_ref() != Some(&err) {
+                    eprintln!("{}", err);
+                    last_err = Some(err);
+                }
+                continue;
+            }
+        };
+        last_err = None;
+
+        for input in inputs {
+            if !update(&mut states, &input.path) && !config_changed {
+                continue;
+            }
+
+            let start = Instant::now();
+            match compile_file(comp, matches, &input) {
+                Ok(compiled) => {
+                    if let Some(stats) = compiled.stats {
+                        eprint!("{}", stats);
+                    }
+                    if let Some(code) = compiled.stdout {
+                        stdout.lock().write_all(code.as_bytes())?;
+                    }
+
+                    let time = start.elapsed();
+                    eprintln!(
+                        "compiled {} ({}.{:03}s)",
+                        input.path.display(),
+                        time.as_secs(),
+                        time.subsec_millis()
+                    );
+                }
+                Err(err) => eprintln!("failed to compile {}: {}", input.path.display(), err),
+            }
+        }
+    }
+}
+
+/// Returns true if the content of `path` is changed since the last call.
+///
+/// The file is read only if its modification time is changed.
+fn update(states: &mut HashMap<PathBuf, State>, path: &Path) -> bool {
+    let modified = fs::metadata(path).and_then(|m| m.modified()).ok();
+    if let Some(state) = states.get(path) {
+        if state.modified == modified {
+            return false;
+        }
+    }
+
+    let hash = fs::read(path).ok().map(|bytes| {
+        let mut hasher = DefaultHasher::new();
+        bytes.hash(&mut hasher);
+        hasher.finish()
+    });
+    let prev = states.insert(path.to_path_buf(), State { modified, hash });
+    prev.map_or(true, |prev| prev.hash != hash)
+}
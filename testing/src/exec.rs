@@ -0,0 +1,40 @@
+use std::{
+    io::{self, Write},
+    process::{Command, Stdio},
+};
+
+/// Runs javascript code, for tests checking what transformed code does.
+pub trait Executor {
+    /// Runs `code` as a script, returning its stdout if it completes and an
+    /// error message if it throws.
+    ///
+    /// The outer error means the engine is not available.
+    fn execute(&self, code: &str) -> io::Result<Result<String, String>>;
+}
+
+/// Runs code with `node`. `assert` is the `assert` module of node.js.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Node;
+
+impl Executor for Node {
+    fn execute(&self, code: &str) -> io::Result<Result<String, String>> {
+        let mut child = Command::new("node")
+            .arg("-")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        {
+            let stdin = child.stdin.as_mut().expect("stdin should be piped");
+            stdin.write_all(b"var assert = require('assert');\n")?;
+            stdin.write_all(code.as_bytes())?;
+        }
+
+        let output = child.wait_with_output()?;
+        if output.status.success() {
+            Ok(Ok(String::from_utf8_lossy(&output.stdout).into_owned()))
+        } else {
+            Ok(Err(String::from_utf8_lossy(&output.stderr).into_owned()))
+        }
+    }
+}
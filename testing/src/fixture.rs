@@ -0,0 +1,170 @@
+use exec::Executor;
+use paths;
+use slog::Logger;
+use sourcemap::SourceMapBuilder;
+use std::{cmp, env, fs};
+use swc_common::{errors::Handler, sync::Lrc, FileName, Fold, SourceFile, SourceMap};
+use swc_ecma_ast::Module;
+use swc_ecma_codegen::{text_writer::JsWriter, Emitter, Handlers};
+use swc_ecma_parser::{Parser, Session, SourceFileInput, Syntax};
+
+/// Tests a transform using files.
+///
+/// Parses `input` with `syntax`, applies the pass created by `tr` and
+/// compares the printed code with the content of `output`. Paths are
+/// relative to the directory of the crate under test.
+///
+/// If `UPDATE` is set, `output` is overwritten with the actual code instead.
+///
+/// # Panics
+///
+/// Panics with a diff if the code differs from `output`, or if `input` has
+/// syntax errors.
+pub fn test_fixture<F, P>(syntax: Syntax, tr: F, input: &str, output: &str)
+where
+    F: FnOnce() -> P,
+    P: Fold<Module>,
+{
+    let (input, output) = (
+        paths::manifest_dir().join(input),
+        paths::manifest_dir().join(output),
+    );
+
+    let actual = ::run_test(|logger, cm, handler| {
+        let fm = cm
+            .load_file(&input)
+            .unwrap_or_else(|err| panic!("failed to load {}: {}", input.display(), err));
+        apply(&cm, handler, &logger, &fm, syntax, tr)
+    })
+    .unwrap_or_else(|stderr| panic!("failed to parse {}:\n{}", input.display(), stderr));
+
+    if env::var("UPDATE").is_ok() {
+        ::write_to_file(&output, &actual);
+        return;
+    }
+
+    let expected = fs::read_to_string(&output).unwrap_or_else(|err| {
+        panic!(
+            "failed to read {}: {}\nRun with UPDATE=1 to create it.",
+            output.display(),
+            err
+        )
+    });
+    if actual.trim_right() == expected.trim_right() {
+        return;
+    }
+
+    panic!(
+        "output of {} differs from {}\n{}",
+        input.display(),
+        output.display(),
+        diff(expected.trim_right(), actual.trim_right())
+    );
+}
+
+/// Applies the pass created by `tr` to `src`, and runs the code with
+/// `executor`.
+///
+/// # Panics
+///
+/// Panics with the code if it throws, or if `src` has syntax errors. The test
+/// is skipped if `executor` is not available.
+pub fn test_exec<E, F, P>(executor: &E, syntax: Syntax, tr: F, src: &str)
+where
+    E: Executor,
+    F: FnOnce() -> P,
+    P: Fold<Module>,
+{
+    let code = ::run_test(|logger, cm, handler| {
+        let fm = cm.new_source_file(FileName::Real("input.js".into()), src.into());
+        apply(&cm, handler, &logger, &fm, syntax, tr)
+    })
+    .unwrap_or_else(|stderr| panic!("failed to parse:\n{}", stderr));
+
+    match executor.execute(&code) {
+        Ok(Ok(_)) => {}
+        Ok(Err(err)) => panic!("failed to execute:\n{}\n>>>>> Code <<<<<\n{}", err, code),
+        Err(err) => eprintln!("skipping as the executor is not available: {}", err),
+    }
+}
+
+/// Parses `fm` as a module, applies the pass created by `tr`, and prints it.
+fn apply<F, P>(
+    cm: &Lrc<SourceMap>,
+    handler: &Handler,
+    logger: &Logger,
+    fm: &SourceFile,
+    syntax: Syntax,
+    tr: F,
+) -> Result<String, ()>
+where
+    F: FnOnce() -> P,
+    P: Fold<Module>,
+{
+    let session = Session { handler, logger };
+    let module = Parser::new(session, syntax, SourceFileInput::from(fm)).parse_module()?;
+    let module = tr().fold(module);
+
+    Ok(print(cm, &module))
+}
+
+pub(crate) fn print(cm: &Lrc<SourceMap>, module: &Module) -> String {
+    let mut buf = vec![];
+    {
+        let mut src_map_builder = SourceMapBuilder::new(None);
+        let mut emitter = Emitter {
+            cfg: Default::default(),
+            cm: cm.clone(),
+            wr: box JsWriter::new(cm.clone(), "\n", &mut buf, &mut src_map_builder),
+            handlers: box NoopHandlers,
+            comments: None,
+        };
+
+        emitter
+            .emit_module(module)
+            .expect("failed to write to a buffer");
+    }
+
+    String::from_utf8(buf).expect("codegen should emit utf8")
+}
+
+struct NoopHandlers;
+
+impl Handlers for NoopHandlers {}
+
+/// Diffs lines of `expected` and `actual`. Removed lines are red and prefixed
+/// with `-`, and added lines are green and prefixed with `+`.
+pub fn diff(expected: &str, actual: &str) -> String {
+    let l: Vec<_> = expected.lines().collect();
+    let r: Vec<_> = actual.lines().collect();
+
+    // `lcs[i][j]` is the length of the longest common subsequence of `l[i..]`
+    // and `r[j..]`.
+    let mut lcs = vec![vec![0; r.len() + 1]; l.len() + 1];
+    for i in (0..l.len()).rev() {
+        for j in (0..r.len()).rev() {
+            lcs[i][j] = if l[i] == r[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                cmp::max(lcs[i + 1][j], lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut buf = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < l.len() || j < r.len() {
+        if i < l.len() && j < r.len() && l[i] == r[j] {
+            buf.push_str(&format!("  {}\n", l[i]));
+            i += 1;
+            j += 1;
+        } else if j < r.len() && (i == l.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            buf.push_str(&format!("\x1b[32m+ {}\x1b[0m\n", r[j]));
+            j += 1;
+        } else {
+            buf.push_str(&format!("\x1b[31m- {}\x1b[0m\n", l[i]));
+            i += 1;
+        }
+    }
+    buf
+}
@@ -1,3 +1,4 @@
+#![feature(box_patterns)]
 #![feature(box_syntax)]
 #![feature(specialization)]
 #![feature(test)]
@@ -11,10 +12,19 @@ extern crate slog;
 extern crate relative_path;
 extern crate slog_envlogger;
 extern crate slog_term;
+extern crate sourcemap;
 extern crate swc_common;
+extern crate swc_ecma_ast;
+extern crate swc_ecma_codegen;
+extern crate swc_ecma_parser;
 extern crate test;
 
-pub use self::output::{NormalizedOutput, StdErr, StdOut, TestOutput};
+pub use self::{
+    exec::{Executor, Node},
+    fixture::{diff, test_exec, test_fixture},
+    output::{NormalizedOutput, StdErr, StdOut, TestOutput},
+    tester::Tester,
+};
 use regex::Regex;
 use slog::{Drain, Logger};
 use std::{
@@ -29,8 +39,11 @@ use swc_common::{errors::Handler, sync::Lrc, FilePathMapping, Fold, FoldWith, So
 #[macro_use]
 mod macros;
 mod errors;
+mod exec;
+mod fixture;
 mod output;
 mod paths;
+mod tester;
 
 pub fn run_test<F, Ret>(op: F) -> Result<Ret, StdErr>
 where
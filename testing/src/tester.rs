@@ -0,0 +1,166 @@
+use fixture::print;
+use print_left_right;
+use slog::Logger;
+use swc_common::{errors::Handler, sync::Lrc, Comments, FileName, Fold, FoldWith, SourceMap};
+use swc_ecma_ast::*;
+use swc_ecma_parser::{Parser, Session, SourceFileInput, Syntax};
+use DropSpan;
+
+/// Parses, transforms and prints modules in unit tests of passes.
+pub struct Tester<'a> {
+    cm: Lrc<SourceMap>,
+    logger: Logger,
+    handler: &'a Handler,
+    /// Syntax of inputs and expected outputs.
+    syntax: Syntax,
+}
+
+impl<'a> Tester<'a> {
+    /// Runs `op` with a tester which parses code with `syntax`.
+    ///
+    /// # Panics
+    ///
+    /// Panics with reported errors if `op` fails.
+    pub fn run<F>(syntax: Syntax, op: F)
+    where
+        F: FnOnce(&mut Tester) -> Result<(), ()>,
+    {
+        let out = ::run_test(|logger, cm, handler| {
+            op(&mut Tester {
+                cm,
+                logger,
+                handler,
+                syntax,
+            })
+        });
+
+        match out {
+            Ok(()) => {}
+            Err(stderr) => panic!("Stderr:\n{}", stderr),
+        }
+    }
+
+    /// Parses `src` as a module and applies `tr`.
+    ///
+    /// Spans and source text of literals are removed from the result, as
+    /// generated nodes don't have them.
+    pub fn apply_transform<T: Fold<Module>>(
+        &mut self,
+        mut tr: T,
+        name: &str,
+        src: &str,
+    ) -> Result<Module, ()> {
+        let fm = self
+            .cm
+            .new_source_file(FileName::Real(name.into()), src.into());
+
+        let module = {
+            let sess = Session {
+                handler: &self.handler,
+                logger: &self.logger,
+            };
+            let mut p = Parser::new(sess, self.syntax, SourceFileInput::from(&*fm));
+            p.parse_module()?
+        };
+
+        let module = module
+            .fold_with(&mut tr)
+            .fold_with(&mut DropSpan)
+            .fold_with(&mut Normalizer);
+
+        Ok(module)
+    }
+
+    /// Parses `src` as a module, recording comments to `comments`.
+    ///
+    /// Spans are kept so that transforms can look up comments.
+    pub fn parse_with_comments(
+        &mut self,
+        name: &str,
+        src: &str,
+        comments: &Comments,
+    ) -> Result<Module, ()> {
+        let fm = self
+            .cm
+            .new_source_file(FileName::Real(name.into()), src.into());
+
+        let sess = Session {
+            handler: &self.handler,
+            logger: &self.logger,
+        };
+        let mut p = Parser::with_comments(sess, self.syntax, SourceFileInput::from(&*fm), comments);
+        p.parse_module()
+    }
+
+    pub fn print(&mut self, module: &Module) -> String {
+        print(&self.cm, module)
+    }
+
+    /// Applies `tr` to `input` and compares the result with `expected`.
+    ///
+    /// The pass created by `fixer` is applied to both modules. If
+    /// `ok_if_src_eq` is true, modules which differ but are printed the same
+    /// are accepted.
+    ///
+    /// # Panics
+    ///
+    /// Panics with both codes if they differ.
+    pub fn test_transform<F, P, T>(
+        &mut self,
+        fixer: F,
+        tr: T,
+        input: &str,
+        expected: &str,
+        ok_if_src_eq: bool,
+    ) -> Result<(), ()>
+    where
+        F: Fn() -> P,
+        P: Fold<Module>,
+        T: Fold<Module>,
+    {
+        let expected = self.apply_transform(fixer(), "expected.js", expected)?;
+        let actual = fixer().fold(self.apply_transform(tr, "actual.js", input)?);
+
+        if actual == expected {
+            return Ok(());
+        }
+
+        let (actual_src, expected_src) = (self.print(&actual), self.print(&expected));
+        if actual_src == expected_src {
+            if ok_if_src_eq {
+                return Ok(());
+            }
+            panic!(
+                "different ast was detected\n>>>>> Code <<<<<\n{}\n{}",
+                actual_src,
+                print_left_right(&actual, &expected)
+            );
+        }
+
+        panic!(
+            "\n>>>>> Actual <<<<<\n{}\n>>>>> Expected <<<<<\n{}",
+            actual_src, expected_src
+        );
+    }
+}
+
+/// Removes differences which are not visible in code.
+struct Normalizer;
+impl Fold<PatOrExpr> for Normalizer {
+    fn fold(&mut self, n: PatOrExpr) -> PatOrExpr {
+        match n {
+            PatOrExpr::Pat(box Pat::Expr(e)) => PatOrExpr::Expr(e),
+            _ => n,
+        }
+    }
+}
+impl Fold<Str> for Normalizer {
+    fn fold(&mut self, s: Str) -> Str {
+        Str { raw: None, ..s }
+    }
+}
+impl Fold<Number> for Normalizer {
+    fn fold(&mut self, n: Number) -> Number {
+        Number { raw: None, ..n }
+    }
+}
@@ -0,0 +1,142 @@
+//! WebAssembly binding for swc.
+//!
+//! There's no filesystem nor thread in `wasm32-unknown-unknown`, so sources
+//! and options are passed from javascript and diagnostics are buffered
+//! instead of being printed to stderr. Native plugins and the bundler, which
+//! spawns threads, are not built.
+
+#![feature(box_syntax)]
+
+extern crate libswc as swc;
+extern crate serde_json;
+#[macro_use]
+extern crate slog;
+extern crate wasm_bindgen;
+
+use std::{
+    error::Error,
+    io::{self, Write},
+    sync::{Arc, Mutex},
+};
+use swc::{
+    common::{
+        errors::{EmitterWriter, Handler, HandlerFlags},
+        sync::Lrc,
+        FileName, FilePathMapping, Globals, SourceMap, GLOBALS,
+    },
+    config::Config,
+    Compiler,
+};
+use wasm_bindgen::prelude::*;
+
+/// Result of `transformSync` and `printSync`.
+#[wasm_bindgen]
+pub struct Output {
+    code: String,
+    map: Option<String>,
+}
+
+#[wasm_bindgen]
+impl Output {
+    #[wasm_bindgen(getter)]
+    pub fn code(&self) -> String {
+        self.code.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn map(&self) -> Option<String> {
+        self.map.clone()
+    }
+}
+
+/// `transformSync(src, filename, options)`
+///
+/// `options` is the json representation of `.swcrc`. Throws an error
+/// containing rendered diagnostics on failure.
+#[wasm_bindgen(js_name = transformSync)]
+pub fn transform_sync(src: String, filename: String, options: &str) -> Result<Output, JsValue> {
+    with_compiler(options, |compiler, cm, config| {
+        let fm = cm.new_source_file(FileName::Real(filename.into()), src);
+        compiler.process_js_file(&fm, config)
+    })
+    .map(|output| Output {
+        code: output.code,
+        map: output.map,
+    })
+}
+
+/// `parseSync(src, filename, options)`
+///
+/// Returns the module as json, in the shape of ESTree. Only `jsc.parser` of
+/// `options` is used.
+#[wasm_bindgen(js_name = parseSync)]
+pub fn parse_sync(src: String, filename: String, options: &str) -> Result<String, JsValue> {
+    with_compiler(options, |compiler, cm, config| {
+        let fm = cm.new_source_file(FileName::Real(filename.into()), src);
+        compiler.parse_js_json(&fm, config)
+    })
+}
+
+/// `printSync(ast, options)`
+///
+/// Prints `ast`, a module returned by `parseSync`, without transforming it.
+/// Output options of `options` like `minify` are used.
+#[wasm_bindgen(js_name = printSync)]
+pub fn print_sync(ast: &str, options: &str) -> Result<Output, JsValue> {
+    with_compiler(options, |compiler, _, config| {
+        compiler.print_js_json(ast, config)
+    })
+    .map(|output| Output {
+        code: output.code,
+        map: output.map,
+    })
+}
+
+/// Runs `op` with options deserialized from `options`.
+///
+/// Errors contain diagnostics emitted while running `op`.
+fn with_compiler<F, Ret>(options: &str, op: F) -> Result<Ret, JsValue>
+where
+    F: FnOnce(&Compiler, &Lrc<SourceMap>, &Config) -> Result<Ret, Box<Error>>,
+{
+    let config: Config = serde_json::from_str(options)
+        .map_err(|err| format!("failed to deserialize options: {}", err))?;
+
+    GLOBALS.set(&Globals::new(), || {
+        let cm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        let buf = BufferedWriter::default();
+        let handler = Handler::with_emitter(
+            box EmitterWriter::new(box buf.clone(), Some(cm.clone()), false, false),
+            HandlerFlags {
+                can_emit_warnings: true,
+                ..Default::default()
+            },
+        );
+        let logger = slog::Logger::root(slog::Discard, o!());
+        let compiler = Compiler::new(logger, cm.clone(), handler);
+
+        op(&compiler, &cm, &config)
+            .map_err(|err| JsValue::from_str(&format!("{}\n{}", buf.take(), err)))
+    })
+}
+
+/// Collects output of the emitter.
+#[derive(Clone, Default)]
+struct BufferedWriter(Arc<Mutex<Vec<u8>>>);
+
+impl BufferedWriter {
+    fn take(&self) -> String {
+        let buf = std::mem::replace(&mut *self.0.lock().unwrap(), vec![]);
+        String::from_utf8_lossy(&buf).into_owned()
+    }
+}
+
+impl Write for BufferedWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}